@@ -0,0 +1,30 @@
+import { invoke } from '@tauri-apps/api/core'
+import type { ExportOptions, ExportResult } from '@/types/export'
+import type {
+  AuditLogEntry,
+  AuditLogVerification,
+  SearchAuditLogRequest
+} from '@/types/audit-log'
+
+export const auditLogApi = {
+  /**
+   * 監査ログを検索（新しい順）
+   */
+  async search(request: SearchAuditLogRequest = {}): Promise<AuditLogEntry[]> {
+    return invoke<AuditLogEntry[]>('search_audit_log', { request })
+  },
+
+  /**
+   * 検索条件に一致する監査ログをCSV/JSONで書き出す
+   */
+  async export(request: SearchAuditLogRequest, options: ExportOptions): Promise<ExportResult> {
+    return invoke<ExportResult>('export_audit_log', { request, options })
+  },
+
+  /**
+   * ハッシュチェーンを検証（改ざんの検出）
+   */
+  async verify(): Promise<AuditLogVerification> {
+    return invoke<AuditLogVerification>('verify_audit_log')
+  }
+}
@@ -0,0 +1,25 @@
+import { invoke } from '@tauri-apps/api/core'
+import type { PoolSettings, PoolStats } from '@/types/connection-pool'
+
+export const connectionPoolApi = {
+  /**
+   * 接続プールごとの接続状況を取得
+   */
+  async getPoolStats(): Promise<PoolStats[]> {
+    return invoke<PoolStats[]>('get_pool_stats')
+  },
+
+  /**
+   * 接続プールの設定を取得
+   */
+  async getPoolSettings(): Promise<PoolSettings> {
+    return invoke<PoolSettings>('get_pool_settings')
+  },
+
+  /**
+   * 接続プールの設定を更新（最大接続数は以降に作成されるプールから適用）
+   */
+  async updatePoolSettings(settings: PoolSettings): Promise<void> {
+    return invoke('update_pool_settings', { settings })
+  },
+}
@@ -5,7 +5,12 @@ import type {
   UpdateSqlQueryModel,
   UpdateSqlResult,
 } from '@/types/mutation-query'
-import type { MutationExecuteRequest, MutationResult } from '@/types/mutation-result'
+import type {
+  MutationExecuteRequest,
+  MutationPreview,
+  MutationPreviewRequest,
+  MutationResult,
+} from '@/types/mutation-result'
 
 export const mutationApi = {
   /**
@@ -43,4 +48,11 @@ export const mutationApi = {
   async executeMutation(request: MutationExecuteRequest): Promise<MutationResult> {
     return invoke('execute_mutation', { request })
   },
+
+  /**
+   * UPDATE/DELETEの対象行数とサンプルを取得（実行はしない）
+   */
+  async previewMutation(request: MutationPreviewRequest): Promise<MutationPreview> {
+    return invoke('preview_mutation', { request })
+  },
 }
@@ -1,7 +1,16 @@
 import { invoke } from '@tauri-apps/api/core';
 import type { QueryModel } from '@/types/query-model';
-import type { QueryExecuteRequest, QueryExecuteResponse } from '@/types/query-result';
+import type {
+  BoundSql,
+  QueryCursorOpenRequest,
+  QueryExecuteRequest,
+  QueryExecuteResponse,
+  QueryResultPage,
+  ScriptExecuteRequest,
+  ScriptExecuteResponse,
+} from '@/types/query-result';
 import type { QueryAnalysisResult } from '@/types/query-analysis';
+import type { ExplainRequest, QueryPlan } from '@/types/query-plan';
 
 export const queryApi = {
   /**
@@ -18,6 +27,13 @@ export const queryApi = {
     return invoke('generate_sql_formatted', { query, pretty, smartQuote });
   },
 
+  /**
+   * プレースホルダー付きSQLとバインドパラメータを生成
+   */
+  async generateSqlWithParams(query: QueryModel): Promise<BoundSql> {
+    return invoke<BoundSql>('generate_sql_with_params', { query });
+  },
+
   /**
    * クエリを実行
    */
@@ -25,6 +41,20 @@ export const queryApi = {
     return await invoke<QueryExecuteResponse>('execute_query', { request })
   },
 
+  /**
+   * 複数文のスクリプトを実行し、文ごとの結果を取得
+   */
+  async executeScript(request: ScriptExecuteRequest): Promise<ScriptExecuteResponse> {
+    return await invoke<ScriptExecuteResponse>('execute_script', { request })
+  },
+
+  /**
+   * 実行計画を取得
+   */
+  async explainQuery(request: ExplainRequest): Promise<QueryPlan> {
+    return await invoke<QueryPlan>('explain_query', { request })
+  },
+
   /**
    * クエリをキャンセル
    */
@@ -32,6 +62,27 @@ export const queryApi = {
     return await invoke<boolean>('cancel_query', { queryId })
   },
 
+  /**
+   * カーソルを開いて最初のページを取得
+   */
+  async openQueryCursor(request: QueryCursorOpenRequest): Promise<QueryResultPage> {
+    return await invoke<QueryResultPage>('open_query_cursor', { request })
+  },
+
+  /**
+   * カーソルから次のページを取得
+   */
+  async fetchQueryCursor(cursorId: string, pageSize?: number, timeoutSeconds?: number): Promise<QueryResultPage> {
+    return await invoke<QueryResultPage>('fetch_query_cursor', { cursorId, pageSize, timeoutSeconds })
+  },
+
+  /**
+   * カーソルを閉じる
+   */
+  async closeQueryCursor(cursorId: string): Promise<boolean> {
+    return await invoke<boolean>('close_query_cursor', { cursorId })
+  },
+
   /**
    * クエリを解析して危険度を判定
    */
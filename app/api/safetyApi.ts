@@ -1,5 +1,9 @@
 import { invoke } from '@tauri-apps/api/core'
-import type { SafetySettings, EnvironmentSafetyConfig } from '@/types/safety-settings'
+import type {
+  SafetySettings,
+  EnvironmentSafetyConfig,
+  ConfirmationToken,
+} from '@/types/safety-settings'
 import type { Environment } from '@/types'
 
 export const safetyApi = {
@@ -11,7 +15,7 @@ export const safetyApi = {
   },
 
   /**
-   * 環境別の安全設定を更新
+   * 環境別の安全設定を更新（ネイティブの確認ダイアログで承認された場合のみ）
    */
   async updateEnvironmentSafety(
     environment: Environment,
@@ -21,9 +25,18 @@ export const safetyApi = {
   },
 
   /**
-   * 安全設定をデフォルトにリセット
+   * 安全設定をデフォルトにリセット（ネイティブの確認ダイアログで承認された場合のみ）
    */
   async resetSettings(): Promise<SafetySettings> {
     return await invoke('reset_safety_settings')
   },
+
+  /**
+   * ネイティブの確認ダイアログで承認されたSQLの確認トークンを取得（1回だけ使える）
+   *
+   * 承認されなかった場合はエラーになる
+   */
+  async issueConfirmationToken(connectionId: string, sql: string): Promise<ConfirmationToken> {
+    return await invoke('issue_confirmation_token', { connectionId, sql })
+  },
 }
@@ -0,0 +1,32 @@
+import { invoke } from '@tauri-apps/api/core'
+import type { TransactionInfo } from '@/types/transaction'
+
+export const transactionApi = {
+  /**
+   * トランザクションを開始（呼び出し元ウィンドウに接続を確保）
+   */
+  async beginTransaction(connectionId: string, timeoutSeconds?: number): Promise<TransactionInfo> {
+    return invoke<TransactionInfo>('begin_transaction', { connectionId, timeoutSeconds })
+  },
+
+  /**
+   * トランザクションをコミット
+   */
+  async commitTransaction(connectionId: string): Promise<void> {
+    return invoke('commit_transaction', { connectionId })
+  },
+
+  /**
+   * トランザクションをロールバック
+   */
+  async rollbackTransaction(connectionId: string): Promise<void> {
+    return invoke('rollback_transaction', { connectionId })
+  },
+
+  /**
+   * トランザクションの状態を取得
+   */
+  async getTransactionStatus(connectionId: string): Promise<TransactionInfo | null> {
+    return invoke<TransactionInfo | null>('get_transaction_status', { connectionId })
+  },
+}
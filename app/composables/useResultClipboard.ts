@@ -1,4 +1,5 @@
 import type { QueryExecuteResult, QueryResultColumn, QueryValue } from '@/types/query-result'
+import { formatTypedQueryValue, isTypedQueryValue } from '@/utils/query-value'
 
 export type CopyFormat = 'csv' | 'tsv' | 'markdown'
 
@@ -16,6 +17,7 @@ export function formatValueForClipboard(value: QueryValue, column: QueryResultCo
   if (typeof value === 'boolean') return value ? 'true' : 'false'
   if (typeof value === 'number') return value.toString()
   if (Array.isArray(value)) return `[${value.length} bytes]`
+  if (isTypedQueryValue(value)) return formatTypedQueryValue(value)
   if (column.dataType.includes('JSON')) {
     try {
       return JSON.stringify(JSON.parse(value as string))
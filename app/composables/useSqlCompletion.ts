@@ -126,7 +126,9 @@ export function useSqlCompletion() {
     const rawDatabaseType = currentConnection?.type || null
     const databaseType = rawDatabaseType === 'postgresql' || rawDatabaseType === 'mysql' || rawDatabaseType === 'sqlite'
       ? rawDatabaseType
-      : null
+      : rawDatabaseType === 'mariadb' ? 'mysql'
+        // DuckDBはPostgreSQLに近い構文のため同じ補完を使う
+        : rawDatabaseType === 'duckdb' ? 'postgresql' : null
     const selectedDatabase = sqlEditorStore.selectedDatabase
 
     // エイリアスを抽出
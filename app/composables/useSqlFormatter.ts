@@ -12,18 +12,27 @@ export const useSqlFormatter = () => {
   const formatInsertSql = (sql: string): string => {
     if (!sql || !sql.trim()) return sql
 
+    // SQL ServerのUPSERT（MERGE）はVALUESが副問い合わせの中にあるため整形しない
+    if (/^\s*MERGE\s/i.test(sql)) return sql
+
     try {
       let formatted = sql.trim()
 
       // INSERT INTO の後で改行
       formatted = formatted.replace(/INSERT\s+INTO/gi, 'INSERT INTO')
 
+      // INSERT ... SELECT: カラム一覧の後ろのSELECTの前で改行
+      formatted = formatted.replace(/\)\s+SELECT\s+/i, ')\nSELECT ')
+
       // VALUES の前で改行、インデント追加
       formatted = formatted.replace(/\s+VALUES\s*/gi, '\nVALUES\n  ')
 
       // 複数行VALUES: ), ( を ), \n  ( に変換
       formatted = formatted.replace(/\)\s*,\s*\(/g, '),\n  (')
 
+      // UPSERT の重複時の動作の前で改行
+      formatted = formatted.replace(/\s+(ON\s+CONFLICT|ON\s+DUPLICATE\s+KEY)\s+/gi, '\n$1 ')
+
       // 末尾のセミコロン前で改行（存在する場合）
       formatted = formatted.replace(/\s*;\s*$/, ';\n')
 
@@ -48,6 +57,9 @@ export const useSqlFormatter = () => {
       // UPDATE の後で改行
       formatted = formatted.replace(/UPDATE\s+/gi, 'UPDATE ')
 
+      // MySQLの結合（UPDATE t INNER JOIN ... SET）の前で改行
+      formatted = formatted.replace(/\s+INNER\s+JOIN\s+/gi, '\nINNER JOIN ')
+
       // SET の前で改行
       formatted = formatted.replace(/\s+SET\s+/gi, '\nSET\n  ')
 
@@ -78,6 +90,9 @@ export const useSqlFormatter = () => {
       // DELETE FROM の後で改行
       formatted = formatted.replace(/DELETE\s+FROM/gi, 'DELETE FROM')
 
+      // 結合するテーブル（USING / INNER JOIN）の前で改行
+      formatted = formatted.replace(/\s+(USING|INNER\s+JOIN)\s+/gi, '\n$1 ')
+
       // WHERE の前で改行
       formatted = formatted.replace(/\s+WHERE\s+/gi, '\nWHERE\n  ')
 
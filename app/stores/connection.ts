@@ -42,9 +42,23 @@ function toRustConnection(connection: Connection | Omit<Connection, 'id' | 'crea
     password: baseConnection.password || '',
     savePassword: Boolean(baseConnection.password),
     type: baseConnection.type,
-    ssl: false,
-    sshTunnel: false,
+    ssl: Boolean(baseConnection.ssl?.enabled),
+    sslMode: baseConnection.ssl?.mode ?? null,
+    sslCaCertPath: baseConnection.ssl?.caCertPath || null,
+    sslClientCertPath: baseConnection.ssl?.clientCertPath || null,
+    sslClientKeyPath: baseConnection.ssl?.clientKeyPath || null,
+    sshTunnel: Boolean(baseConnection.ssh?.enabled),
+    sshHost: baseConnection.ssh?.host || null,
+    sshPort: baseConnection.ssh?.port ?? null,
+    sshUsername: baseConnection.ssh?.username || null,
+    sshAuthMethod: baseConnection.ssh?.authMethod ?? null,
+    sshPassword: baseConnection.ssh?.password || null,
+    sshPrivateKeyPath: baseConnection.ssh?.privateKeyPath || null,
+    sshPassphrase: baseConnection.ssh?.passphrase || null,
     timeout: 30,
+    options: baseConnection.options ?? null,
+    onConnectSql: baseConnection.onConnectSql || null,
+    readOnly: Boolean(baseConnection.readOnly),
     createdAt: baseConnection.createdAt,
     updatedAt: baseConnection.updatedAt
   }
@@ -52,7 +66,24 @@ function toRustConnection(connection: Connection | Omit<Connection, 'id' | 'crea
 
 // RustのFrontendConnection型からフロントエンド型に変換
 function fromRustConnection(rustConnection: any): Connection {
-  const { themeColor, themeBackgroundColor, ...rest } = rustConnection
+  const {
+    themeColor,
+    themeBackgroundColor,
+    ssl,
+    sslMode,
+    sslCaCertPath,
+    sslClientCertPath,
+    sslClientKeyPath,
+    sshTunnel,
+    sshHost,
+    sshPort,
+    sshUsername,
+    sshAuthMethod,
+    sshPassword,
+    sshPrivateKeyPath,
+    sshPassphrase,
+    ...rest
+  } = rustConnection
 
   // customColorの再構築
   // themeColorとthemeBackgroundColorが両方nullでない場合のみカスタムカラーとして扱う
@@ -66,7 +97,28 @@ function fromRustConnection(rustConnection: any): Connection {
 
   return {
     ...rest,
-    customColor
+    customColor,
+    ssl: ssl
+      ? {
+          enabled: true,
+          mode: sslMode ?? 'verify-full',
+          caCertPath: sslCaCertPath,
+          clientCertPath: sslClientCertPath,
+          clientKeyPath: sslClientKeyPath
+        }
+      : undefined,
+    ssh: sshTunnel
+      ? {
+          enabled: true,
+          host: sshHost ?? '',
+          port: sshPort ?? 22,
+          username: sshUsername ?? '',
+          authMethod: sshAuthMethod ?? 'password',
+          password: sshPassword,
+          privateKeyPath: sshPrivateKeyPath,
+          passphrase: sshPassphrase
+        }
+      : undefined
   }
 }
 
@@ -270,6 +322,14 @@ export const useConnectionStore = defineStore('connection', {
       }
     },
 
+    /**
+     * 接続テストで拒否されたSSHホスト鍵を、ネイティブダイアログで確認して信頼する
+     * @returns 信頼して登録した場合は true
+     */
+    async trustSshHostKey(host: string, port: number): Promise<boolean> {
+      return await invokeTauri<boolean>('trust_ssh_host_key', { host, port })
+    },
+
     setActiveConnection(connection: Connection | null) {
       this.activeConnection = connection
     }
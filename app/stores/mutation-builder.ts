@@ -4,16 +4,26 @@ import type {
   MutationQueryModel,
   DeleteSqlQueryModel,
   InsertQueryModel,
+  OnConflict,
   UpdateQueryModel,
   UpdateSetValue,
   UpdateSqlQueryModel,
 } from '@/types/mutation-query'
 import type { WhereCondition, ConditionGroup } from '@/types/query'
-import type { WhereClause, WhereConditionItem, WhereValue, WhereOperator } from '@/types/query-model'
+import type {
+  JoinClause,
+  QueryModel,
+  WhereClause,
+  WhereConditionItem,
+  WhereValue,
+  WhereOperator,
+} from '@/types/query-model'
 import type { QueryExecuteError } from '@/types/query-result'
 import type { QueryAnalysisResult } from '@/types/query-analysis'
+import type { MutationPreview, MutationPreviewTarget, MutationUndo } from '@/types/mutation-result'
 import { mutationApi } from '@/api/mutation'
 import { queryApi } from '@/api/query'
+import { safetyApi } from '@/api/safetyApi'
 import { useConnectionStore } from '@/stores/connection'
 import { useWindowStore } from '@/stores/window'
 import { useSqlFormatter } from '@/composables/useSqlFormatter'
@@ -169,6 +179,18 @@ interface MutationBuilderState {
   /** クエリ解析結果 */
   analysisResult: QueryAnalysisResult | null
 
+  /** UPDATE/DELETEの対象行プレビュー */
+  preview: MutationPreview | null
+
+  /** プレビュー取得中フラグ */
+  isLoadingPreview: boolean
+
+  /** UPDATE/DELETEの実行前に対象行を取得し、元に戻すSQLを履歴に保存する */
+  captureUndo: boolean
+
+  /** 直前の実行で生成された元に戻すSQL */
+  lastUndo: MutationUndo | null
+
   /** 実行中フラグ */
   isExecuting: boolean
 
@@ -194,6 +216,10 @@ export const useMutationBuilderStore = defineStore('mutation-builder', {
     sqlGenerationError: null,
     smartQuote: true,
     analysisResult: null,
+    preview: null,
+    isLoadingPreview: false,
+    captureUndo: true,
+    lastUndo: null,
     isExecuting: false,
     queryError: null,
     queryInfo: {
@@ -301,6 +327,37 @@ export const useMutationBuilderStore = defineStore('mutation-builder', {
       this.generateInsertSql()
     },
 
+    /**
+     * INSERTの重複時の動作（UPSERT）を設定
+     */
+    setOnConflict(onConflict: OnConflict | null): void {
+      if (!this.queryModel || this.queryModel.type !== 'INSERT') return
+
+      this.queryModel.onConflict = onConflict
+      this.generateInsertSql()
+    },
+
+    /**
+     * INSERT ... SELECT の取得元クエリを設定（null で VALUES に戻す）
+     */
+    setInsertSource(source: QueryModel | null): void {
+      if (!this.queryModel || this.queryModel.type !== 'INSERT') return
+
+      this.queryModel.source = source
+      this.generateInsertSql()
+    },
+
+    /**
+     * UPDATE/DELETEで対象行を絞り込むために結合するテーブルを設定
+     */
+    setJoins(joins: JoinClause[]): void {
+      if (!this.queryModel) return
+      if (this.queryModel.type !== 'UPDATE' && this.queryModel.type !== 'DELETE') return
+
+      this.queryModel.joins = joins
+      this.generateMutationSql()
+    },
+
     /**
      * UPDATEクエリモデルを取得または初期化
      */
@@ -435,7 +492,10 @@ export const useMutationBuilderStore = defineStore('mutation-builder', {
         return
       }
 
-      if (!this.queryModel.columns.length || !this.queryModel.values.length) {
+      if (
+        !this.queryModel.columns.length ||
+        (!this.queryModel.values.length && !this.queryModel.source)
+      ) {
         this.generatedSql = ''
         this.analysisResult = null
         return
@@ -455,7 +515,9 @@ export const useMutationBuilderStore = defineStore('mutation-builder', {
       this.sqlGenerationError = null
 
       try {
-        const rawSql = await mutationApi.generateInsertSql(this.queryModel, connectionId, this.smartQuote)
+        // 取得元クエリを指定した場合、入力フォームの値は送らない
+        const request = this.queryModel.source ? { ...this.queryModel, values: [] } : this.queryModel
+        const rawSql = await mutationApi.generateInsertSql(request, connectionId, this.smartQuote)
 
         // SQLをフォーマット
         const { formatMutationSql } = useSqlFormatter()
@@ -517,6 +579,7 @@ export const useMutationBuilderStore = defineStore('mutation-builder', {
           table: this.queryModel.table,
           setClause: this.queryModel.setClause,
           whereClause,
+          joins: this.queryModel.joins ?? [],
         }
 
         const result = await mutationApi.generateUpdateSql(request, connectionId, this.smartQuote)
@@ -579,6 +642,7 @@ export const useMutationBuilderStore = defineStore('mutation-builder', {
           type: 'DELETE',
           table: this.queryModel.table,
           whereClause,
+          joins: this.queryModel.joins ?? [],
         }
 
         const rawSql = await mutationApi.generateDeleteSql(request, connectionId, this.smartQuote)
@@ -621,15 +685,72 @@ export const useMutationBuilderStore = defineStore('mutation-builder', {
       }
     },
 
+    /**
+     * UPDATE/DELETEの対象行数とサンプルを取得（確認ダイアログ用）
+     *
+     * 取得できなかった場合は `preview` を null のままにする
+     */
+    async loadPreview(): Promise<void> {
+      this.preview = null
+
+      let target: MutationPreviewTarget
+      if (this.queryModel?.type === 'UPDATE') {
+        target = {
+          kind: 'update',
+          query: {
+            type: 'UPDATE',
+            table: this.queryModel.table,
+            setClause: this.queryModel.setClause,
+            whereClause: buildWhereClause(this.queryModel.whereConditions),
+            joins: this.queryModel.joins ?? [],
+          },
+        }
+      } else if (this.queryModel?.type === 'DELETE') {
+        target = {
+          kind: 'delete',
+          query: {
+            type: 'DELETE',
+            table: this.queryModel.table,
+            whereClause: buildWhereClause(this.queryModel.whereConditions),
+            joins: this.queryModel.joins ?? [],
+          },
+        }
+      } else {
+        return
+      }
+
+      const connectionStore = useConnectionStore()
+      const windowStore = useWindowStore()
+      const connectionId = connectionStore.activeConnection?.id || windowStore.currentConnectionId
+      if (!connectionId) return
+
+      this.isLoadingPreview = true
+      try {
+        this.preview = await mutationApi.previewMutation({
+          connectionId,
+          target,
+          smartQuote: this.smartQuote,
+          timeoutSeconds: 30,
+        })
+      } catch (error) {
+        console.warn('Failed to preview mutation:', error)
+      } finally {
+        this.isLoadingPreview = false
+      }
+    },
+
     /**
      * クエリを実行
+     *
+     * 確認ダイアログで承認済みの場合は `confirmed` を指定し、バックエンドの確認トークンを付けて実行する
      */
-    async executeMutation(): Promise<void> {
+    async executeMutation(confirmed = false): Promise<void> {
       if (!this.canExecuteQuery) return
       if (!this.generatedSql) return
 
       this.isExecuting = true
       this.queryError = null
+      this.lastUndo = null
 
       try {
         const connectionStore = useConnectionStore()
@@ -640,11 +761,18 @@ export const useMutationBuilderStore = defineStore('mutation-builder', {
           throw new Error('接続が選択されていません')
         }
 
+        const confirmation = confirmed
+          ? await safetyApi.issueConfirmationToken(connectionId, this.generatedSql)
+          : null
+
         const result = await mutationApi.executeMutation({
           connectionId,
           sql: this.generatedSql,
           timeoutSeconds: 30,
+          confirmationToken: confirmation?.token,
+          captureUndo: this.captureUndo && this.mutationType !== 'INSERT',
         })
+        this.lastUndo = result.undo ?? null
 
         this.queryInfo = {
           affectedRows: result.affectedRows,
@@ -661,6 +789,7 @@ export const useMutationBuilderStore = defineStore('mutation-builder', {
           success: true,
           resultCount: result.affectedRows,
           executionTimeMs: result.executionTimeMs,
+          undoSql: result.undo?.status === 'available' ? result.undo.sql : undefined,
         })
       } catch (error) {
         if (typeof error === 'string') {
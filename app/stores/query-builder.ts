@@ -14,6 +14,7 @@ import type {
 import type { JoinClause, JoinCondition as JoinConditionModel } from '@/types/query-model'
 import type { QueryAnalysisResult } from '@/types/query-analysis'
 import { queryApi } from '@/api/query'
+import { safetyApi } from '@/api/safetyApi'
 import { joinSuggestionsApi } from '@/api/join-suggestions'
 import { convertToQueryModel } from '@/utils/query-converter'
 import { generatePreviewSql } from '@/utils/expression-preview'
@@ -931,8 +932,10 @@ export const useQueryBuilderStore = defineStore('query-builder', {
 
     /**
      * クエリを実行
+     *
+     * 確認ダイアログで承認済みの場合は `confirmed` を指定し、バックエンドの確認トークンを付けて実行する
      */
-    async executeQuery() {
+    async executeQuery(confirmed = false) {
       if (!this.canExecuteQuery) return
       if (!this.generatedSql) return
 
@@ -950,10 +953,20 @@ export const useQueryBuilderStore = defineStore('query-builder', {
           throw new Error('接続が選択されていません')
         }
 
+        // 実行中にキャンセルできるよう、クエリIDは事前に発行する
+        const queryId = crypto.randomUUID()
+        this.executingQueryId = queryId
+
+        const confirmation = confirmed
+          ? await safetyApi.issueConfirmationToken(connectionId, this.generatedSql)
+          : null
+
         const response = await queryApi.executeQuery({
           connectionId,
           sql: this.generatedSql,
           timeoutSeconds: 30,
+          queryId,
+          confirmationToken: confirmation?.token,
         })
 
         this.executingQueryId = response.queryId
@@ -1,5 +1,8 @@
 import { defineStore } from 'pinia'
 import { queryHistoryApi } from '@/api/query-history'
+import { queryApi } from '@/api/query'
+import { safetyApi } from '@/api/safetyApi'
+import { transactionApi } from '@/api/transaction'
 import { ref, computed } from 'vue'
 import type {
   QueryHistory,
@@ -103,6 +106,84 @@ export const useQueryHistoryStore = defineStore('query-history', () => {
     }
   }
 
+  /**
+   * 履歴に保存された元に戻すSQLを実行
+   *
+   * 実行するSQLをネイティブの確認ダイアログで提示し、承認された場合のみ確認トークンを付けて実行する。
+   * 途中の文で失敗して一部だけ戻ることがないよう、1つのトランザクションで実行し、失敗したらロールバックする。
+   * 実行結果は履歴に追加し、成功した場合は実行した文の数を返す
+   */
+  async function revertHistory(id: string): Promise<number | null> {
+    error.value = null
+    try {
+      const history = await queryHistoryApi.loadHistory(id)
+      if (!history.undoSql) {
+        throw new Error('No undo SQL for this history')
+      }
+
+      const confirmation = await safetyApi.issueConfirmationToken(
+        history.connectionId,
+        history.undoSql
+      )
+
+      // トランザクションが開いている間、このウィンドウのスクリプトはその接続上で実行される
+      await transactionApi.beginTransaction(history.connectionId)
+      let response
+      try {
+        response = await queryApi.executeScript({
+          connectionId: history.connectionId,
+          sql: history.undoSql,
+          errorMode: 'stop_on_error',
+          confirmationToken: confirmation.token,
+        })
+      } catch (err) {
+        await transactionApi.rollbackTransaction(history.connectionId).catch(() => {})
+        throw err
+      }
+
+      let affectedRows = 0
+      let errorMessage: string | undefined
+      for (const result of response.results) {
+        if (result.outcome.type === 'affected') {
+          affectedRows += result.outcome.affectedRows
+        } else if (result.outcome.type === 'error') {
+          errorMessage = result.outcome.error.message
+        }
+      }
+
+      if (errorMessage === undefined) {
+        try {
+          await transactionApi.commitTransaction(history.connectionId)
+        } catch (err) {
+          errorMessage = err instanceof Error ? err.message : String(err)
+          affectedRows = 0
+        }
+      } else {
+        await transactionApi.rollbackTransaction(history.connectionId).catch(() => {})
+        affectedRows = 0
+      }
+
+      await queryHistoryApi.addHistory({
+        connectionId: history.connectionId,
+        query: history.query,
+        sql: history.undoSql,
+        success: errorMessage === undefined,
+        resultCount: affectedRows,
+        executionTimeMs: response.executionTimeMs,
+        errorMessage,
+      })
+      await fetchHistories()
+
+      if (errorMessage !== undefined) {
+        throw new Error(errorMessage)
+      }
+      return response.results.length
+    } catch (err) {
+      error.value = err instanceof Error ? err.message : 'Unknown error'
+      return null
+    }
+  }
+
   /**
    * クエリビルダーに読み込み
    */
@@ -193,6 +274,7 @@ export const useQueryHistoryStore = defineStore('query-history', () => {
     loadHistory,
     deleteHistory,
     loadToBuilder,
+    revertHistory,
     setSearchKeyword,
     setSelectedConnectionId,
     setSuccessOnly,
@@ -2,6 +2,8 @@ import { defineStore } from 'pinia'
 import { format as formatSql } from 'sql-formatter'
 import { useConnectionStore } from '~/stores/connection'
 import { queryApi } from '~/api/query'
+import { safetyApi } from '~/api/safetyApi'
+import { mutationApi } from '~/api/mutation'
 import { sqlEditorApi } from '~/api/sql-editor'
 import type {
   AddSqlEditorHistoryRequest,
@@ -10,6 +12,7 @@ import type {
   SavedQueryMetadata,
   SqlEditorState,
   SqlEditorHistoryEntry,
+  SqlEditorPendingConfirmation,
   SqlEditorTab,
   TreeNode,
 } from '~/types/sql-editor'
@@ -85,6 +88,7 @@ export const useSqlEditorStore = defineStore('sql-editor', {
       isLeftPanelVisible: true,
       isSavedPanelOpen: true,
       isHistoryPanelOpen: true,
+      pendingConfirmation: null,
     }
   },
 
@@ -259,7 +263,7 @@ export const useSqlEditorStore = defineStore('sql-editor', {
       // データベースタイプに応じてselectedDatabaseを初期化
       const connection = this.currentConnection
       if (connection) {
-        if (connection.type === 'mysql') {
+        if (connection.type === 'mysql' || connection.type === 'mariadb') {
           // MySQLの場合、接続のデフォルトデータベースを設定
           this.selectedDatabase = connection.database || null
         }
@@ -318,7 +322,7 @@ export const useSqlEditorStore = defineStore('sql-editor', {
         const connection = this.currentConnection
         if (connection) {
           // データベースタイプに応じて初期値を設定
-          if (connection.type === 'mysql') {
+          if (connection.type === 'mysql' || connection.type === 'mariadb') {
             // MySQLの場合、接続のデフォルトデータベースを設定
             this.selectedDatabase = connection.database || null
           }
@@ -992,6 +996,7 @@ export const useSqlEditorStore = defineStore('sql-editor', {
 
       switch (connection.type) {
         case 'mysql':
+        case 'mariadb':
           // MySQLではUSE文が使えないため、nullを返す
           // デフォルトデータベースがそのまま使われる
           return null
@@ -1001,7 +1006,12 @@ export const useSqlEditorStore = defineStore('sql-editor', {
           return `SET search_path TO "${database.replace(/"/g, '""')}";`
 
         case 'sqlite':
-          // SQLiteはデータベース切り替えの概念がない
+        case 'duckdb':
+          // SQLite・DuckDBはデータベース切り替えの概念がない
+          return null
+
+        case 'sqlserver':
+          // SQL Serverは既定のスキーマをセッション中に切り替えられない
           return null
 
         default:
@@ -1011,8 +1021,15 @@ export const useSqlEditorStore = defineStore('sql-editor', {
 
     /**
      * SQL文字列を実行
+     *
+     * 安全設定で確認が必要と判定された場合は `pendingConfirmation` に積み、
+     * 確認後に `confirmed` を指定して再実行する
      */
-    async executeSqlText(sqlToExecute: string, emptyMessage = '実行するSQLが空です') {
+    async executeSqlText(
+      sqlToExecute: string,
+      emptyMessage = '実行するSQLが空です',
+      confirmed = false
+    ) {
       if (this.isExecuting) return
       if (!this.connectionId) {
         const connectionError = createClientError('接続が選択されていません')
@@ -1052,17 +1069,25 @@ export const useSqlEditorStore = defineStore('sql-editor', {
 
       const executionId = ++latestExecutionId
       const targetTabId = this.activeTabId
+      // 実行中にキャンセルできるよう、クエリIDは事前に発行する
+      const queryId = crypto.randomUUID()
       this.isExecuting = true
-      this.executingQueryId = null
+      this.executingQueryId = queryId
       this.executingTabId = targetTabId
       this.updateTabExecutionState(targetTabId, null, null)
       const startTime = Date.now()
 
       try {
+        const confirmation = confirmed
+          ? await safetyApi.issueConfirmationToken(this.connectionId, finalSql)
+          : null
+
         const response = await queryApi.executeQuery({
           connectionId: this.connectionId,
           sql: finalSql,
           timeoutSeconds: 30,
+          queryId,
+          confirmationToken: confirmation?.token,
         })
 
         if (executionId !== latestExecutionId || !this.isExecuting) {
@@ -1084,6 +1109,17 @@ export const useSqlEditorStore = defineStore('sql-editor', {
         }
         const normalizedError = normalizeQueryError(error)
         this.updateTabExecutionState(targetTabId, null, normalizedError)
+        if (normalizedError.code === 'confirmation_required' && !confirmed) {
+          // 確認ダイアログを出すため、バックエンドと同じ解析結果を取得する
+          const dialect = this.currentConnection?.type ?? 'postgresql'
+          const analysis = await queryApi.analyzeQuery(finalSql, dialect).catch(() => null)
+          if (analysis) {
+            const pending = { sql: trimmedSql, analysis }
+            this.pendingConfirmation = pending
+            void this.loadPendingPreview(pending)
+          }
+          return
+        }
         void this.addHistory({
           connectionId: this.connectionId,
           sql: trimmedSql,
@@ -1100,6 +1136,43 @@ export const useSqlEditorStore = defineStore('sql-editor', {
       }
     },
 
+    /**
+     * 確認待ちの単一のUPDATE/DELETEについて対象行数を取得する（取得できなければ表示しない）
+     */
+    async loadPendingPreview(pending: SqlEditorPendingConfirmation) {
+      const { analysis } = pending
+      if (!this.connectionId) return
+      if (analysis.statements.length > 1) return
+      if (analysis.queryType !== 'update' && analysis.queryType !== 'delete') return
+
+      const preview = await mutationApi
+        .previewMutation({
+          connectionId: this.connectionId,
+          target: { kind: 'sql', sql: pending.sql },
+        })
+        .catch(() => null)
+      if (preview && this.pendingConfirmation === pending) {
+        this.pendingConfirmation = { ...pending, preview }
+      }
+    },
+
+    /**
+     * 確認待ちのクエリを確認済みとして実行
+     */
+    async confirmPendingExecution() {
+      const pending = this.pendingConfirmation
+      if (!pending) return
+      this.pendingConfirmation = null
+      await this.executeSqlText(pending.sql, undefined, true)
+    },
+
+    /**
+     * 確認待ちのクエリを破棄
+     */
+    cancelPendingExecution() {
+      this.pendingConfirmation = null
+    },
+
     /**
      * クエリ実行（Phase 3で実装）
      */
@@ -51,6 +51,18 @@ describe('formatValueForClipboard', () => {
   it('不正なJSONはそのまま返す', () => {
     expect(formatValueForClipboard('not json', col('x', 'JSONB'))).toBe('not json')
   })
+
+  it('型付きの値を文字列にする', () => {
+    expect(formatValueForClipboard({ type: 'decimal', value: '12.50' }, col('x', 'NUMERIC'))).toBe('12.50')
+    expect(formatValueForClipboard({ type: 'timestamp', value: '2024-03-15T09:30:00' }, col('x'))).toBe('2024-03-15 09:30:00')
+    expect(formatValueForClipboard({ type: 'json', value: { a: [1, 2] } }, col('x', 'JSONB'))).toBe('{"a":[1,2]}')
+    expect(
+      formatValueForClipboard({ type: 'array', value: [1, null, { type: 'date', value: '2024-03-15' }] }, col('x', 'DATE[]'))
+    ).toBe('[1,null,"2024-03-15"]')
+    expect(
+      formatValueForClipboard({ type: 'interval', value: { months: 14, days: 3, microseconds: 3_723_000_000 } }, col('x'))
+    ).toBe('1 years 2 months 3 days 01:02:03')
+  })
 })
 
 describe('formatAsCsv', () => {
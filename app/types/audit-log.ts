@@ -0,0 +1,60 @@
+import type { RiskLevel } from '@/types/query-analysis'
+
+/**
+ * 監査ログ上の環境種別（Rust側の EnvironmentType のシリアライズ名）
+ */
+export type AuditEnvironment = 'development' | 'testing' | 'staging' | 'production'
+
+export type AuditOperation = 'query' | 'script' | 'mutation' | 'cursor' | 'explain' | 'preview'
+
+export type AuditOutcome = 'success' | 'error' | 'rejected'
+
+/**
+ * 監査ログのエントリ
+ */
+export interface AuditLogEntry {
+  /** 通し番号（1始まり） */
+  sequence: number
+  /** 実行日時（RFC3339、UTC） */
+  timestamp: string
+  connectionId: string
+  connectionName: string
+  environment: AuditEnvironment
+  operation: AuditOperation
+  sql: string
+  riskLevel: RiskLevel
+  affectedRows: number | null
+  outcome: AuditOutcome
+  errorMessage: string | null
+  osUser: string
+  /** 直前のエントリのハッシュ */
+  prevHash: string
+  /** このエントリのハッシュ（HMAC-SHA256） */
+  hash: string
+}
+
+/**
+ * 監査ログ検索リクエスト
+ */
+export interface SearchAuditLogRequest {
+  /** 開始日（YYYY-MM-DD、UTC） */
+  from?: string
+  /** 終了日（YYYY-MM-DD、UTC） */
+  to?: string
+  connectionId?: string
+  environment?: AuditEnvironment
+  outcome?: AuditOutcome
+  keyword?: string
+  limit?: number
+}
+
+/**
+ * ハッシュチェーンの検証結果
+ */
+export interface AuditLogVerification {
+  valid: boolean
+  checkedEntries: number
+  invalidFile: string | null
+  invalidLine: number | null
+  message: string | null
+}
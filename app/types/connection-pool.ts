@@ -0,0 +1,31 @@
+/**
+ * 接続プールの設定
+ */
+export interface PoolSettings {
+  /** 1つのプールで保持する最大接続数 */
+  maxConnections: number
+  /** アイドル状態がこの秒数を超えた接続・プールを閉じる */
+  idleTimeoutSeconds: number
+  /** ヘルスチェックの間隔（秒） */
+  healthCheckIntervalSeconds: number
+}
+
+/**
+ * 接続プールの状態
+ */
+export interface PoolStats {
+  /** 接続ID */
+  connectionId: string
+  /** 開いている接続数 */
+  open: number
+  /** アイドル状態の接続数 */
+  idle: number
+  /** 使用中の接続数 */
+  inUse: number
+  /** 最大接続数 */
+  maxConnections: number
+  /** プールの作成日時（RFC3339） */
+  createdAt: string
+  /** 最終使用日時（RFC3339） */
+  lastUsedAt: string
+}
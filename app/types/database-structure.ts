@@ -7,7 +7,7 @@ export interface DatabaseStructure {
   /** データベース名 */
   databaseName: string;
   /** データベース種別 */
-  databaseType: 'postgresql' | 'mysql' | 'sqlite';
+  databaseType: 'postgresql' | 'mysql' | 'mariadb' | 'sqlite' | 'duckdb' | 'sqlserver';
   /** スキーマ一覧 */
   schemas: Schema[];
   /** 取得日時 */
@@ -23,7 +23,7 @@ export interface DatabaseStructureSummary {
   /** データベース名 */
   databaseName: string;
   /** データベース種別 */
-  databaseType: 'postgresql' | 'mysql' | 'sqlite';
+  databaseType: 'postgresql' | 'mysql' | 'mariadb' | 'sqlite' | 'duckdb' | 'sqlserver';
   /** スキーマ一覧 */
   schemas: SchemaSummary[];
   /** 取得日時 */
@@ -70,6 +70,28 @@ export interface Schema {
   tables: Table[];
   /** ビュー一覧 */
   views: View[];
+  /** シーケンス一覧（MariaDBのみ） */
+  sequences?: Sequence[];
+}
+
+/**
+ * シーケンス情報
+ */
+export interface Sequence {
+  /** シーケンス名 */
+  name: string;
+  /** スキーマ名 */
+  schema: string;
+  /** 開始値 */
+  startValue: number | null;
+  /** 増分 */
+  increment: number | null;
+  /** 最小値 */
+  minValue: number | null;
+  /** 最大値 */
+  maxValue: number | null;
+  /** 最大値に達したら最小値に戻るか */
+  cycle: boolean;
 }
 
 /**
@@ -94,6 +116,8 @@ export interface Table {
   foreignKeys: ForeignKey[];
   /** 参照されている外部キー */
   referencedBy: ForeignKeyReference[];
+  /** システムバージョニングされたテーブルか（MariaDB） */
+  isSystemVersioned?: boolean;
 }
 
 /**
@@ -134,6 +158,8 @@ export interface Column {
   isUnique: boolean;
   /** 自動インクリメントか */
   isAutoIncrement: boolean;
+  /** 生成列（計算列）か */
+  isGenerated: boolean;
   /** カラム位置(順序) */
   ordinalPosition: number;
   /** コメント/説明 */
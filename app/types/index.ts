@@ -6,7 +6,7 @@ export type Environment = 'development' | 'test' | 'staging' | 'production'
 /**
  * データベースタイプ
  */
-export type DatabaseType = 'mysql' | 'postgresql' | 'sqlite' | 'sqlserver' | 'oracle'
+export type DatabaseType = 'mysql' | 'mariadb' | 'postgresql' | 'sqlite' | 'duckdb' | 'sqlserver' | 'oracle'
 
 /**
  * セキュリティプロバイダー
@@ -31,6 +31,47 @@ export type Language = 'ja' | 'en'
 /**
  * 接続情報インターフェース
  */
+/**
+ * SSL/TLSの検証方法
+ */
+export type SslMode = 'require' | 'verify-ca' | 'verify-full'
+
+/**
+ * SSL/TLS設定
+ */
+export interface SslSettings {
+  enabled: boolean
+  mode: SslMode
+  /** CA証明書パス */
+  caCertPath?: string
+  /** クライアント証明書パス */
+  clientCertPath?: string
+  /** クライアント秘密鍵パス */
+  clientKeyPath?: string
+}
+
+/**
+ * SSH認証方式
+ */
+export type SshAuthMethod = 'password' | 'privateKey'
+
+/**
+ * SSHトンネル設定
+ */
+export interface SshTunnelSettings {
+  enabled: boolean
+  host: string
+  port: number
+  username: string
+  authMethod: SshAuthMethod
+  /** パスワード認証のパスワード */
+  password?: string
+  /** 秘密鍵パス */
+  privateKeyPath?: string
+  /** 秘密鍵のパスフレーズ */
+  passphrase?: string
+}
+
 export interface Connection {
   id: string
   name: string
@@ -45,6 +86,16 @@ export interface Connection {
     primary: string
     background: string
   }
+  /** SSL/TLS設定 */
+  ssl?: SslSettings
+  /** SSHトンネル設定 */
+  ssh?: SshTunnelSettings
+  /** 接続オプション（接続文字列パラメータ・セッション設定） */
+  options?: Record<string, string>
+  /** 接続ごとに実行する初期化SQL */
+  onConnectSql?: string
+  /** 読み取り専用接続（セッションを読み取り専用で開き、データ変更を拒否する） */
+  readOnly?: boolean
   createdAt: string
   updatedAt: string
 }
@@ -1,5 +1,5 @@
 import type { WhereCondition, ConditionGroup } from '@/types/query'
-import type { WhereClause } from '@/types/query-model'
+import type { JoinClause, QueryModel, WhereClause } from '@/types/query-model'
 
 /**
  * データ変更クエリの種別
@@ -18,6 +18,24 @@ export interface InsertQueryModel {
   columns: string[]
   /** 挿入する値の配列（複数行対応） */
   values: Array<Record<string, any>>
+  /** 主キー・一意インデックスと重複した場合の動作（UPSERT） */
+  onConflict?: OnConflict | null
+  /** 挿入する行を取得するSELECT（INSERT ... SELECT）。指定した場合 values は送らない */
+  source?: QueryModel | null
+}
+
+/**
+ * INSERTが重複した場合の動作
+ */
+export type OnConflictAction =
+  | { type: 'doNothing' }
+  | { type: 'doUpdate'; columns: string[] }
+
+export interface OnConflict {
+  /** 重複を判定するカラム（主キーまたは一意インデックスのカラム） */
+  target: string[]
+  /** 重複時の動作 */
+  action: OnConflictAction
 }
 
 /**
@@ -28,6 +46,8 @@ export interface UpdateSetValue {
   value: any
   /** NULL指定 */
   isNull: boolean
+  /** 値の代わりに参照するカラム（結合したテーブルのカラムなど） */
+  column?: { tableAlias: string; columnName: string } | null
 }
 
 export interface UpdateQueryModel {
@@ -39,6 +59,8 @@ export interface UpdateQueryModel {
   setClause: Record<string, UpdateSetValue>
   /** WHERE条件 */
   whereConditions: Array<WhereCondition | ConditionGroup>
+  /** 対象行を絞り込むために結合するテーブル（INNER JOINのみ） */
+  joins?: JoinClause[]
 }
 
 /**
@@ -53,6 +75,8 @@ export interface UpdateSqlQueryModel {
   setClause: Record<string, UpdateSetValue>
   /** WHERE句 */
   whereClause: WhereClause | null
+  /** 対象行を絞り込むために結合するテーブル（INNER JOINのみ） */
+  joins?: JoinClause[]
 }
 
 /**
@@ -75,6 +99,8 @@ export interface DeleteSqlQueryModel {
   table: string
   /** WHERE句 */
   whereClause: WhereClause | null
+  /** 対象行を絞り込むために結合するテーブル（INNER JOINのみ） */
+  joins?: JoinClause[]
 }
 
 /**
@@ -87,6 +113,8 @@ export interface DeleteQueryModel {
   table: string
   /** WHERE条件 */
   whereConditions: Array<WhereCondition | ConditionGroup>
+  /** 対象行を絞り込むために結合するテーブル（INNER JOINのみ） */
+  joins?: JoinClause[]
 }
 
 /**
@@ -1,3 +1,6 @@
+import type { DeleteSqlQueryModel, UpdateSqlQueryModel } from '@/types/mutation-query'
+import type { QueryExecuteResult } from '@/types/query-result'
+
 /**
  * データ変更クエリ実行結果
  */
@@ -6,8 +9,17 @@ export interface MutationResult {
   affectedRows: number
   /** 実行時間（ミリ秒） */
   executionTimeMs: number
+  /** 元に戻すSQL（`captureUndo` を指定した場合のみ） */
+  undo?: MutationUndo
 }
 
+/**
+ * 実行したUPDATE/DELETEを元に戻すSQLの生成結果
+ */
+export type MutationUndo =
+  | { status: 'available'; sql: string; rowCount: number }
+  | { status: 'unavailable'; reason: string }
+
 /**
  * データ変更クエリ実行リクエスト
  */
@@ -18,4 +30,48 @@ export interface MutationExecuteRequest {
   sql: string
   /** タイムアウト（秒） */
   timeoutSeconds?: number
+  /** クエリID（実行中にキャンセルする場合に指定） */
+  queryId?: string
+  /** 確認トークン（安全設定で確認が必要な文を実行する場合に指定） */
+  confirmationToken?: string
+  /** 実行前に対象行を取得し、元に戻すSQLを生成する（UPDATE/DELETEのみ） */
+  captureUndo?: boolean
+}
+
+/**
+ * UPDATE/DELETEの対象行プレビュー対象
+ */
+export type MutationPreviewTarget =
+  | { kind: 'update'; query: UpdateSqlQueryModel }
+  | { kind: 'delete'; query: DeleteSqlQueryModel }
+  | { kind: 'sql'; sql: string }
+
+/**
+ * UPDATE/DELETEの対象行プレビューリクエスト
+ */
+export interface MutationPreviewRequest {
+  /** 接続ID */
+  connectionId: string
+  /** プレビュー対象 */
+  target: MutationPreviewTarget
+  /** スマートクォーティング（モデル指定時のみ使用） */
+  smartQuote?: boolean
+  /** サンプル行数（省略時は10行） */
+  sampleLimit?: number
+  /** タイムアウト（秒） */
+  timeoutSeconds?: number
+}
+
+/**
+ * UPDATE/DELETEの対象行プレビュー
+ */
+export interface MutationPreview {
+  /** 対象行数（実行前の見積もり） */
+  affectedRows: number
+  /** 対象行のサンプル */
+  sample: QueryExecuteResult
+  /** 件数取得に使ったSQL */
+  countSql: string
+  /** サンプル取得に使ったSQL */
+  sampleSql: string
 }
@@ -9,7 +9,8 @@ export type QueryType =
   | 'create'
   | 'unknown'
 
-export type RiskLevel = 'safe' | 'warning' | 'danger'
+/** unparseable: 構文を解析できなかった（最も高い危険度として扱う） */
+export type RiskLevel = 'safe' | 'warning' | 'danger' | 'unparseable'
 
 export interface RiskFactor {
   code: string
@@ -22,4 +23,16 @@ export interface QueryAnalysisResult {
   riskFactors: RiskFactor[]
   affectedTables: string[]
   hasWhereClause: boolean
+  /** 文ごとの解析結果（全体の値は最も危険度の高い文のもの） */
+  statements: StatementAnalysis[]
+}
+
+/**
+ * 文ごとの解析結果
+ */
+export interface StatementAnalysis extends Omit<QueryAnalysisResult, 'statements'> {
+  /** 文の番号（0始まり） */
+  index: number
+  /** 元のSQL中での文の範囲（バイト位置） */
+  range: { start: number; end: number }
 }
@@ -16,6 +16,8 @@ export interface QueryHistory {
   resultCount?: number
   executionTimeMs?: number
   errorMessage?: string
+  /** 実行したUPDATE/DELETEを元に戻すSQL */
+  undoSql?: string
 }
 
 /**
@@ -29,6 +31,7 @@ export interface QueryHistoryMetadata {
   success: boolean
   resultCount?: number
   executionTimeMs?: number
+  undoSql?: string
 }
 
 /**
@@ -42,6 +45,7 @@ export interface AddHistoryRequest {
   resultCount?: number
   executionTimeMs?: number
   errorMessage?: string
+  undoSql?: string
 }
 
 /**
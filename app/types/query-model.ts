@@ -164,6 +164,8 @@ export interface WhereCondition {
   column: {
     tableAlias: string
     columnName: string
+    /** カラムの型（バインドするパラメータの型変換に使う） */
+    dataType?: string
   }
   /** 演算子 */
   operator: WhereOperator
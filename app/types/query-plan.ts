@@ -0,0 +1,71 @@
+/**
+ * 実行計画リクエスト
+ */
+export interface ExplainRequest {
+  /** 接続ID */
+  connectionId: string
+  /** 対象のSQL（単一文） */
+  sql: string
+  /** 実際に実行して計測する（EXPLAIN ANALYZE） */
+  analyze?: boolean
+  /** タイムアウト（秒） */
+  timeoutSeconds?: number
+  /** 確認トークン（analyze で確認が必要な文を実行する場合） */
+  confirmationToken?: string
+}
+
+/**
+ * 実行計画ノードの種類
+ */
+export type PlanNodeKind =
+  | 'seq_scan'
+  | 'index_scan'
+  | 'nested_loop'
+  | 'hash_join'
+  | 'merge_join'
+  | 'sort'
+  | 'aggregate'
+  | 'other'
+
+/**
+ * 実行計画ノード
+ */
+export interface QueryPlanNode {
+  /** 演算名（DBの表記のまま） */
+  operation: string
+  kind: PlanNodeKind
+  relation: string | null
+  schema: string | null
+  indexName: string | null
+  estimatedRows: number | null
+  estimatedCost: number | null
+  /** 実際の行数（ANALYZE時、1ループあたり） */
+  actualRows: number | null
+  /** 実際の所要時間（ANALYZE時、ミリ秒） */
+  actualTimeMs: number | null
+  actualLoops: number | null
+  detail: string | null
+  children: QueryPlanNode[]
+}
+
+/**
+ * 実行計画の注意点
+ */
+export interface PlanWarning {
+  code: 'seq_scan_large_table' | 'nested_loop_high_rows' | string
+  message: string
+  relation: string | null
+}
+
+/**
+ * 実行計画
+ */
+export interface QueryPlan {
+  root: QueryPlanNode
+  /** 実際に実行して計測したか */
+  analyzed: boolean
+  warnings: PlanWarning[]
+  /** DBが返した実行計画（JSON・テキスト） */
+  raw: string
+  executionTimeMs: number
+}
@@ -35,9 +35,59 @@ export interface QueryResultRow {
 }
 
 /**
- * 値の型
+ * 期間（月・日・マイクロ秒）
  */
-export type QueryValue = null | boolean | number | string | number[]
+export interface QueryInterval {
+  months: number
+  days: number
+  microseconds: number
+}
+
+/**
+ * 型付きの値（日付・数値・JSONなど）
+ */
+export type TypedQueryValue =
+  | { type: 'decimal'; value: string }
+  | { type: 'date'; value: string }
+  | { type: 'time'; value: string }
+  | { type: 'timestamp'; value: string }
+  | { type: 'timestamp_tz'; value: string }
+  | { type: 'json'; value: unknown }
+  | { type: 'array'; value: QueryValue[] }
+  | { type: 'uuid'; value: string }
+  | { type: 'interval'; value: QueryInterval }
+
+/**
+ * 値の型（number[] はバイナリデータ）
+ */
+export type QueryValue = null | boolean | number | string | number[] | TypedQueryValue
+
+/**
+ * 型付きのバインドパラメータ（DB側でネイティブ型としてバインドされる）
+ */
+export type TypedQueryParam =
+  | { type: 'decimal'; value: string }
+  | { type: 'date'; value: string }
+  | { type: 'time'; value: string }
+  | { type: 'timestamp'; value: string }
+  | { type: 'timestamp_tz'; value: string }
+  | { type: 'json'; value: unknown }
+  | { type: 'uuid'; value: string }
+
+/**
+ * バインドパラメータの値
+ */
+export type QueryParam = null | boolean | number | string | TypedQueryParam
+
+/**
+ * プレースホルダー付きSQLとバインドパラメータ
+ */
+export interface BoundSql {
+  /** プレースホルダー付きSQL */
+  sql: string
+  /** バインドパラメータ（プレースホルダー順） */
+  params: QueryParam[]
+}
 
 /**
  * クエリ実行リクエスト
@@ -47,8 +97,14 @@ export interface QueryExecuteRequest {
   connectionId: string
   /** 実行するSQL */
   sql: string
+  /** バインドパラメータ（プレースホルダー順） */
+  params?: QueryParam[]
   /** タイムアウト（秒） */
   timeoutSeconds?: number
+  /** クエリID（実行中にキャンセルする場合に指定） */
+  queryId?: string
+  /** 確認トークン（安全設定で確認が必要な文を実行する場合に指定） */
+  confirmationToken?: string
 }
 
 /**
@@ -61,6 +117,109 @@ export interface QueryExecuteResponse {
   result: QueryExecuteResult
 }
 
+/**
+ * スクリプト実行時のエラー処理
+ */
+export type ScriptErrorMode = 'stop_on_error' | 'continue_on_error'
+
+/**
+ * スクリプト実行リクエスト
+ */
+export interface ScriptExecuteRequest {
+  /** 接続ID */
+  connectionId: string
+  /** 実行するSQL（複数文） */
+  sql: string
+  /** エラー時の動作（既定: stop_on_error） */
+  errorMode?: ScriptErrorMode
+  /** 1文あたりのタイムアウト（秒） */
+  timeoutSeconds?: number
+  /** クエリID（実行中にキャンセルする場合に指定） */
+  queryId?: string
+  /** 確認トークン（安全設定で確認が必要な文を実行する場合に指定） */
+  confirmationToken?: string
+}
+
+/**
+ * SQL中の文の範囲（バイト位置）
+ */
+export interface StatementRange {
+  start: number
+  end: number
+}
+
+/**
+ * 文の実行結果の種類
+ */
+export type StatementOutcome =
+  | { type: 'result_set'; result: QueryExecuteResult }
+  | { type: 'affected'; affectedRows: number }
+  | { type: 'error'; error: QueryExecuteError }
+
+/**
+ * 文ごとの実行結果
+ */
+export interface StatementResult {
+  /** スクリプト内での文の番号（0始まり） */
+  index: number
+  /** 元のSQL中での文の範囲 */
+  range: StatementRange
+  /** 実行結果 */
+  outcome: StatementOutcome
+  /** 実行時間（ミリ秒） */
+  executionTimeMs: number
+}
+
+/**
+ * スクリプト実行レスポンス
+ */
+export interface ScriptExecuteResponse {
+  /** クエリID（キャンセル用） */
+  queryId: string
+  /** 文ごとの結果（中断した場合は実行した文まで） */
+  results: StatementResult[]
+  /** 文の総数 */
+  statementCount: number
+  /** 全体の実行時間（ミリ秒） */
+  executionTimeMs: number
+}
+
+/**
+ * カーソルオープンリクエスト（ストリーミング取得用）
+ */
+export interface QueryCursorOpenRequest {
+  /** 接続ID */
+  connectionId: string
+  /** 実行するSQL */
+  sql: string
+  /** 1ページあたりの取得行数 */
+  pageSize?: number
+  /** タイムアウト（秒） */
+  timeoutSeconds?: number
+  /** 確認トークン（安全設定で確認が必要な文を実行する場合に指定） */
+  confirmationToken?: string
+}
+
+/**
+ * カーソルから取得した1ページ分の結果
+ */
+export interface QueryResultPage {
+  /** カーソルID */
+  cursorId: string
+  /** カラム情報 */
+  columns: QueryResultColumn[]
+  /** このページの行データ */
+  rows: QueryResultRow[]
+  /** このページの行数 */
+  rowCount: number
+  /** これまでに取得した累計行数 */
+  totalFetched: number
+  /** 続きの行があるか */
+  hasMore: boolean
+  /** このページの取得時間（ミリ秒） */
+  executionTimeMs: number
+}
+
 /**
  * クエリエラー
  */
@@ -87,6 +246,7 @@ export type QueryErrorCode =
   | 'syntax_error'
   // 権限関連
   | 'permission_denied'
+  | 'confirmation_required'
   // オブジェクト関連
   | 'table_not_found'
   | 'column_not_found'
@@ -101,6 +261,8 @@ export type QueryErrorCode =
   | 'data_truncation'
   | 'division_by_zero'
   | 'invalid_data_type'
+  // トランザクション関連
+  | 'transaction_error'
   // その他
   | 'unknown'
 
@@ -23,6 +23,9 @@ export interface EnvironmentSafetyConfig {
 
   /** TRUNCATEクエリを禁止するか */
   disableTruncate: boolean
+
+  /** 監査ログに記録できない場合は実行を拒否するか */
+  requireAuditLog: boolean
 }
 
 /**
@@ -33,6 +36,16 @@ export interface SafetySettings {
   environments: Record<Environment, EnvironmentSafetyConfig>
 }
 
+/**
+ * 危険なクエリの実行を許可する確認トークン
+ */
+export interface ConfirmationToken {
+  /** 実行リクエストの confirmationToken に渡す値 */
+  token: string
+  /** 有効期限（Unix秒） */
+  expiresAt: number
+}
+
 /**
  * デフォルト設定
  */
@@ -45,6 +58,7 @@ export const DEFAULT_SAFETY_SETTINGS: SafetySettings = {
       countdownSeconds: 0,
       disableDrop: false,
       disableTruncate: false,
+      requireAuditLog: false,
     },
     test: {
       confirmationEnabled: true,
@@ -52,6 +66,7 @@ export const DEFAULT_SAFETY_SETTINGS: SafetySettings = {
       countdownSeconds: 0,
       disableDrop: false,
       disableTruncate: false,
+      requireAuditLog: false,
     },
     staging: {
       confirmationEnabled: true,
@@ -59,6 +74,7 @@ export const DEFAULT_SAFETY_SETTINGS: SafetySettings = {
       countdownSeconds: 3,
       disableDrop: false,
       disableTruncate: false,
+      requireAuditLog: false,
     },
     production: {
       confirmationEnabled: true,
@@ -66,6 +82,7 @@ export const DEFAULT_SAFETY_SETTINGS: SafetySettings = {
       countdownSeconds: 5,
       disableDrop: true,
       disableTruncate: true,
+      requireAuditLog: true,
     },
   },
 }
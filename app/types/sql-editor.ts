@@ -1,5 +1,7 @@
 import type * as monaco from 'monaco-editor'
 import type { QueryExecuteError, QueryExecuteResult } from './query-result'
+import type { QueryAnalysisResult } from './query-analysis'
+import type { MutationPreview } from './mutation-result'
 
 /**
  * SQLエディタの状態
@@ -69,6 +71,20 @@ export interface SqlEditorState {
   isSavedPanelOpen: boolean
   /** 履歴パネルの開閉状態 */
   isHistoryPanelOpen: boolean
+  /** 安全設定により確認待ちのクエリ */
+  pendingConfirmation: SqlEditorPendingConfirmation | null
+}
+
+/**
+ * 実行前に確認が必要なクエリ
+ */
+export interface SqlEditorPendingConfirmation {
+  /** 実行するSQL（コンテキストSQLを付ける前） */
+  sql: string
+  /** 確認ダイアログに表示する解析結果 */
+  analysis: QueryAnalysisResult
+  /** 単一のUPDATE/DELETEの対象行プレビュー（取得できた場合のみ） */
+  preview?: MutationPreview | null
 }
 
 /**
@@ -0,0 +1,15 @@
+/**
+ * 開いているトランザクションの情報
+ */
+export interface TransactionInfo {
+  /** トランザクションID */
+  transactionId: string
+  /** 接続ID */
+  connectionId: string
+  /** トランザクションを開始したウィンドウのラベル */
+  windowLabel: string
+  /** 開始日時（RFC3339） */
+  startedAt: string
+  /** アイドルタイムアウト（秒） */
+  timeoutSeconds: number
+}
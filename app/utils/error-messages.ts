@@ -48,6 +48,10 @@ const ERROR_MESSAGES: Record<QueryErrorCode, ErrorMessage> = {
     title: '権限エラー',
     description: 'この操作を実行する権限がありません。',
   },
+  confirmation_required: {
+    title: '確認が必要です',
+    description: '現在の環境の安全設定により、このクエリは確認してから実行する必要があります。',
+  },
 
   // オブジェクト関連
   table_not_found: {
@@ -99,6 +103,12 @@ const ERROR_MESSAGES: Record<QueryErrorCode, ErrorMessage> = {
     description: '値のデータ型が不正です。',
   },
 
+  // トランザクション関連
+  transaction_error: {
+    title: 'トランザクションエラー',
+    description: 'トランザクションの操作に失敗しました。',
+  },
+
   // その他
   unknown: {
     title: '予期しないエラー',
@@ -151,6 +161,7 @@ const ERROR_ICONS: Record<QueryErrorCode, string> = {
   query_cancelled: 'i-heroicons-stop',
   syntax_error: 'i-heroicons-code-bracket-square',
   permission_denied: 'i-heroicons-lock-closed',
+  confirmation_required: 'i-heroicons-shield-exclamation',
   table_not_found: 'i-heroicons-table-cells',
   column_not_found: 'i-heroicons-view-columns',
   schema_not_found: 'i-heroicons-folder',
@@ -162,6 +173,7 @@ const ERROR_ICONS: Record<QueryErrorCode, string> = {
   data_truncation: 'i-heroicons-scissors',
   division_by_zero: 'i-heroicons-divide',
   invalid_data_type: 'i-heroicons-variable',
+  transaction_error: 'i-heroicons-arrow-uturn-left',
   unknown: 'i-heroicons-exclamation-triangle',
 }
 
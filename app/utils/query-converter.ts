@@ -134,7 +134,8 @@ export function convertToQueryModel(
       },
     },
     joins: state.joins || [],
-    whereClause: convertWhereConditions(state.whereConditions, 'AND') || undefined,
+    whereClause:
+      convertWhereConditions(state.whereConditions, 'AND', state.selectedTables) || undefined,
     groupBy:
       state.groupByColumns.length > 0
         ? { columns: convertGroupByColumns(state.groupByColumns) }
@@ -158,7 +159,8 @@ export function convertToQueryModel(
  */
 function convertWhereConditions(
   conditions: Array<WhereCondition | ConditionGroup>,
-  logic: 'AND' | 'OR'
+  logic: 'AND' | 'OR',
+  tables: SelectedTable[]
 ): WhereClause | null {
   // 有効な条件のみフィルタ
   const validConditions = conditions.filter((c) => {
@@ -174,7 +176,7 @@ function convertWhereConditions(
 
   return {
     logic,
-    conditions: validConditions.map(convertWhereConditionItem),
+    conditions: validConditions.map((item) => convertWhereConditionItem(item, tables)),
   }
 }
 
@@ -182,14 +184,15 @@ function convertWhereConditions(
  * WHERE条件アイテムを変換
  */
 function convertWhereConditionItem(
-  item: WhereCondition | ConditionGroup
+  item: WhereCondition | ConditionGroup,
+  tables: SelectedTable[]
 ): WhereConditionItem {
   if (item.type === 'group') {
     return {
       type: 'group',
       id: item.id,
       logic: item.logic,
-      conditions: item.conditions.map(convertWhereConditionItem),
+      conditions: item.conditions.map((child) => convertWhereConditionItem(child, tables)),
     }
   }
 
@@ -200,7 +203,13 @@ function convertWhereConditionItem(
   return {
     type: 'condition',
     id: item.id,
-    column: item.column!,
+    column: {
+      ...item.column!,
+      // バインド時に値を列の型で比較できるよう、テーブル情報から型を付ける
+      dataType: tables
+        .find((table) => table.alias === item.column!.tableAlias)
+        ?.columns.find((column) => column.name === item.column!.columnName)?.dataType,
+    },
     operator: item.operator,
     value,
   }
@@ -0,0 +1,60 @@
+import type { QueryInterval, QueryValue, TypedQueryValue } from '@/types/query-result'
+
+export function isTypedQueryValue(value: QueryValue): value is TypedQueryValue {
+  return typeof value === 'object' && value !== null && !Array.isArray(value) && 'type' in value
+}
+
+/**
+ * 期間を `1 years 2 months 3 days 04:05:06` 形式にする（バックエンドの表示と同じ）
+ */
+export function formatInterval(interval: QueryInterval): string {
+  const parts: string[] = []
+  const years = Math.trunc(interval.months / 12)
+  const months = interval.months % 12
+  if (years !== 0) parts.push(`${years} years`)
+  if (months !== 0) parts.push(`${months} months`)
+  if (interval.days !== 0) parts.push(`${interval.days} days`)
+  if (interval.microseconds !== 0) {
+    const sign = interval.microseconds < 0 ? '-' : ''
+    const total = Math.abs(interval.microseconds)
+    const totalSecs = Math.floor(total / 1_000_000)
+    const micros = total % 1_000_000
+    const pad = (n: number) => String(n).padStart(2, '0')
+    const time = `${sign}${pad(Math.floor(totalSecs / 3600))}:${pad(Math.floor((totalSecs % 3600) / 60))}:${pad(totalSecs % 60)}`
+    parts.push(micros !== 0 ? `${time}.${String(micros).padStart(6, '0')}` : time)
+  }
+  return parts.length > 0 ? parts.join(' ') : '00:00:00'
+}
+
+function toPlainJson(value: QueryValue): unknown {
+  if (!isTypedQueryValue(value)) return value
+  switch (value.type) {
+    case 'json':
+      return value.value
+    case 'array':
+      return value.value.map(toPlainJson)
+    case 'interval':
+      return formatInterval(value.value)
+    default:
+      return value.value
+  }
+}
+
+/**
+ * 型付きの値を表示用の文字列にする
+ *
+ * @param indent JSON・配列の整形に使うインデント（省略時は1行）
+ */
+export function formatTypedQueryValue(value: TypedQueryValue, indent?: number): string {
+  switch (value.type) {
+    case 'json':
+    case 'array':
+      return JSON.stringify(toPlainJson(value), null, indent)
+    case 'timestamp':
+      return value.value.replace('T', ' ')
+    case 'interval':
+      return formatInterval(value.value)
+    default:
+      return value.value
+  }
+}
@@ -0,0 +1,38 @@
+-- 各種データ型の動作確認用テーブル（型変換表の確認用）
+-- DuckDB固有の型（符号なし整数・HUGEINT・入れ子型）を含める
+
+CREATE TABLE type_test (
+    id          INTEGER PRIMARY KEY,
+    label       VARCHAR,
+    bool_val    BOOLEAN,
+    ubig_val    UBIGINT,
+    huge_val    HUGEINT,
+    dec_val     DECIMAL(10, 2),
+    dbl_val     DOUBLE,
+    uuid_val    UUID,
+    blob_val    BLOB,
+    date_val    DATE,
+    time_val    TIME,
+    ts_val      TIMESTAMP,
+    tstz_val    TIMESTAMPTZ,
+    iv_val      INTERVAL,
+    list_val    INTEGER[],
+    struct_val  STRUCT(a INTEGER, b VARCHAR),
+    map_val     MAP(VARCHAR, INTEGER)
+);
+
+INSERT INTO type_test VALUES
+    -- 通常値
+    (1, '通常値', true, 42, 42, 1234.50, 1.5,
+     '550e8400-e29b-41d4-a716-446655440000', '\x00\xFF'::BLOB,
+     '2026-03-15', '10:00:00', '2026-03-15 10:00:00', '2026-03-15 10:00:00+09',
+     INTERVAL '1 month 2 days 3 seconds',
+     [1, 2, 3], {'a': 1, 'b': 'x'}, MAP {'k': 1}),
+    -- i64の範囲外の値
+    (2, '範囲外', false, 18446744073709551615, 170141183460469231731687303715884105727, -0.01, -0.0,
+     NULL, ''::BLOB,
+     '1970-01-01', '23:59:59.999999', '1900-01-01 00:00:00', '1970-01-01 00:00:00+00',
+     INTERVAL '-1 day',
+     [], {'a': NULL, 'b': NULL}, MAP {}),
+    -- NULL 値確認
+    (3, 'NULL', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
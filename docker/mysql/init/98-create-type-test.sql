@@ -86,3 +86,17 @@ INSERT INTO type_test (
         NULL, NULL,
         NULL, NULL
     );
+
+-- 空間型（WKTで表示されることの確認）
+CREATE TABLE IF NOT EXISTS spatial_test (
+    id          INT PRIMARY KEY AUTO_INCREMENT,
+    geom_val    GEOMETRY
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT='空間型の動作確認';
+
+INSERT INTO spatial_test (geom_val) VALUES
+    (ST_GeomFromText('POINT(1 2)')),
+    (ST_GeomFromText('LINESTRING(0 0,1 1,2 0)')),
+    (ST_GeomFromText('POLYGON((0 0,4 0,4 4,0 4,0 0),(1 1,2 1,2 2,1 1))')),
+    (ST_GeomFromText('MULTIPOINT((1 1),(2.5 3))')),
+    (ST_GeomFromText('GEOMETRYCOLLECTION(POINT(1 1),LINESTRING(0 0,1 1))')),
+    (NULL);
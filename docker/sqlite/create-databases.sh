@@ -61,6 +61,12 @@ EOF
 
 echo "✓ benchmark-small.db created (10 tables)"
 
+# 型変換確認用DB（アプリのテストでも同じSQLを使用）
+echo "Creating type-test.db..."
+rm -f type-test.db
+sqlite3 type-test.db < type-test.sql
+echo "✓ type-test.db created"
+
 # 中規模・大規模DBは手動生成（時間がかかるため）
 echo ""
 echo "Note: For medium (100 tables) and large (500 tables) databases,"
@@ -0,0 +1,26 @@
+-- 各種データ型の動作確認用テーブル（型変換表の確認用）
+-- SQLiteは宣言型と異なる格納クラスの値も保存できるため、宣言型どおりの値と合わない値の両方を入れる
+
+CREATE TABLE type_test (
+    id          INTEGER PRIMARY KEY,
+    label       TEXT,
+    bool_val    BOOLEAN,
+    int_val     INTEGER,
+    real_val    REAL,
+    text_val    TEXT,
+    blob_val    BLOB,
+    date_val    DATE,
+    time_val    TIME,
+    dt_val      DATETIME,
+    num_val     NUMERIC
+);
+
+INSERT INTO type_test VALUES
+    -- 宣言型どおりの値
+    (1, '通常値', 1, 9223372036854775807, 1.5, 'text', X'00FF', '2026-03-15', '10:00:00', '2026-03-15 10:00:00', 1234.5),
+    -- 宣言型と合わない格納クラス
+    (2, '格納クラス不一致', 'yes', 1.25, 'not real', 42, 'blob as text', 20260315, 'noon', 1773568800, 'abc'),
+    -- 宣言型の範囲外の値
+    (3, '範囲外', 2, -9223372036854775808, -0.0, '', X'', 'not a date', '25:61:00', '2026-13-45', NULL),
+    -- NULL 値確認
+    (4, 'NULL', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
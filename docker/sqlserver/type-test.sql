@@ -0,0 +1,56 @@
+-- 各種データ型の動作確認用テーブル（型変換表の確認用）
+-- SQL Serverはinitディレクトリの仕組みがないため、sqlserver-init サービスから sqlcmd で実行する
+
+IF DB_ID('benchmark_small') IS NULL
+    CREATE DATABASE benchmark_small;
+GO
+
+USE benchmark_small;
+GO
+
+IF OBJECT_ID('dbo.type_test', 'U') IS NOT NULL
+    DROP TABLE dbo.type_test;
+GO
+
+CREATE TABLE dbo.type_test (
+    id          INT IDENTITY(1, 1) PRIMARY KEY,
+    label       NVARCHAR(50) NOT NULL DEFAULT N'未設定',
+    bit_val     BIT,
+    tiny_val    TINYINT,
+    big_val     BIGINT,
+    dec_val     DECIMAL(10, 4),
+    money_val   MONEY,
+    float_val   FLOAT,
+    guid_val    UNIQUEIDENTIFIER,
+    bin_val     VARBINARY(16),
+    date_val    DATE,
+    time_val    TIME(3),
+    dt2_val     DATETIME2(3),
+    dto_val     DATETIMEOFFSET(0),
+    xml_val     XML
+);
+GO
+
+EXEC sp_addextendedproperty
+    @name = N'MS_Description', @value = N'型変換の確認用',
+    @level0type = N'SCHEMA', @level0name = N'dbo',
+    @level1type = N'TABLE', @level1name = N'type_test';
+GO
+
+INSERT INTO dbo.type_test
+    (label, bit_val, tiny_val, big_val, dec_val, money_val, float_val, guid_val, bin_val,
+     date_val, time_val, dt2_val, dto_val, xml_val)
+VALUES
+    -- 通常値
+    (N'通常値', 1, 255, 9223372036854775807, 1234.5678, 12.34, 1.5,
+     '550E8400-E29B-41D4-A716-446655440000', 0x00FF,
+     '2026-03-15', '10:00:00.123', '2026-03-15 10:00:00.123', '2026-03-15 10:00:00 +09:00',
+     N'<a>1</a>'),
+    -- 負の値・境界値
+    (N'負の値', 0, 0, -1, -0.0001, -12.5, -0.0,
+     NULL, 0x,
+     '0001-01-01', '23:59:59.999', '9999-12-31 23:59:59.999', '1970-01-01 00:00:00 +00:00',
+     NULL),
+    -- NULL 値確認
+    (N'NULL', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+GO
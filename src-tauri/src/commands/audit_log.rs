@@ -0,0 +1,31 @@
+use crate::models::audit_log::{AuditLogEntry, AuditLogVerification, SearchAuditLogRequest};
+use crate::models::export::{ExportOptions, ExportResult};
+use crate::services::audit_log::AuditLogService;
+use tauri::State;
+
+/// 監査ログを検索する（新しい順）
+#[tauri::command]
+pub async fn search_audit_log(
+    request: SearchAuditLogRequest,
+    audit_log: State<'_, AuditLogService>,
+) -> Result<Vec<AuditLogEntry>, String> {
+    audit_log.search(&request)
+}
+
+/// 検索条件に一致する監査ログをCSV/JSONで書き出す
+#[tauri::command]
+pub async fn export_audit_log(
+    request: SearchAuditLogRequest,
+    options: ExportOptions,
+    audit_log: State<'_, AuditLogService>,
+) -> Result<ExportResult, String> {
+    audit_log.export(&request, &options)
+}
+
+/// 監査ログのハッシュチェーンを検証する
+#[tauri::command]
+pub async fn verify_audit_log(
+    audit_log: State<'_, AuditLogService>,
+) -> Result<AuditLogVerification, String> {
+    audit_log.verify()
+}
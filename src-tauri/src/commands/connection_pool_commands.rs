@@ -0,0 +1,34 @@
+use crate::models::connection_pool::{PoolSettings, PoolStats};
+use crate::services::query_executor::ConnectionPoolManager;
+use tauri::{command, State};
+
+/// 接続プールごとの接続状況を取得
+#[command]
+pub async fn get_pool_stats(
+    pool_manager: State<'_, ConnectionPoolManager>,
+) -> Result<Vec<PoolStats>, String> {
+    Ok(pool_manager.stats().await)
+}
+
+/// 接続プールの設定を取得
+#[command]
+pub async fn get_pool_settings(
+    pool_manager: State<'_, ConnectionPoolManager>,
+) -> Result<PoolSettings, String> {
+    Ok(pool_manager.settings())
+}
+
+/// 接続プールの設定を更新
+///
+/// 最大接続数は以降に作成されるプールから適用される。
+#[command]
+pub async fn update_pool_settings(
+    settings: PoolSettings,
+    pool_manager: State<'_, ConnectionPoolManager>,
+) -> Result<(), String> {
+    if settings.max_connections == 0 {
+        return Err("max_connections must be at least 1".to_string());
+    }
+    pool_manager.update_settings(settings);
+    Ok(())
+}
@@ -71,7 +71,10 @@ pub async fn get_database_structure_summary(
         let database_type = match connection.database_type {
             crate::connection::DatabaseType::PostgreSQL => "postgresql",
             crate::connection::DatabaseType::MySQL => "mysql",
+            crate::connection::DatabaseType::MariaDB => "mariadb",
             crate::connection::DatabaseType::SQLite => "sqlite",
+            crate::connection::DatabaseType::DuckDB => "duckdb",
+            crate::connection::DatabaseType::SqlServer => "sqlserver",
         }
         .to_string();
 
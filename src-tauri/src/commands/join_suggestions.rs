@@ -44,11 +44,12 @@ pub async fn get_join_suggestions(
         Some(s) => s,
         None => match connection.database_type {
             DatabaseType::PostgreSQL => "public".to_string(),
-            DatabaseType::MySQL => match &connection.connection {
+            DatabaseType::MySQL | DatabaseType::MariaDB => match &connection.connection {
                 ConnectionConfig::Network(cfg) => cfg.database.clone(),
                 _ => return Err("Schema is required for MySQL connection".to_string()),
             },
-            DatabaseType::SQLite => "main".to_string(),
+            DatabaseType::SQLite | DatabaseType::DuckDB => "main".to_string(),
+            DatabaseType::SqlServer => "dbo".to_string(),
         },
     };
 
@@ -1,3 +1,5 @@
+pub mod audit_log;
+pub mod connection_pool_commands;
 pub mod database_structure;
 pub mod export_commands;
 pub mod join_suggestions;
@@ -10,4 +12,5 @@ pub mod safety;
 pub mod security;
 pub mod settings;
 pub mod sql_editor;
+pub mod transaction_commands;
 pub mod window;
@@ -1,37 +1,119 @@
-use crate::connection::{ConnectionConfig, ConnectionService, DatabaseType};
-use crate::models::mutation_result::{MutationExecuteRequest, MutationResult};
+use crate::connection::{ConnectionConfig, ConnectionInfo, ConnectionService, DatabaseType};
+use crate::models::audit_log::AuditOperation;
+use crate::models::database_structure::Table;
+use crate::models::mutation_result::{
+    MutationExecuteRequest, MutationPreview, MutationPreviewRequest, MutationPreviewTarget,
+    MutationResult, MutationUndo,
+};
+use crate::models::query_result::{QueryError, QueryErrorCode, QueryResult, QueryValue};
 use crate::query::mutation::{
-    generate_delete_sql as build_delete_sql, generate_insert_sql as build_insert_sql,
-    generate_update_sql as build_update_sql, DeleteQueryModel, InsertQueryModel, UpdateQueryModel,
-    UpdateSqlResult,
+    generate_delete_sql as build_delete_sql, generate_insert_sql_for_server as build_insert_sql,
+    generate_preview_sql as build_preview_sql,
+    generate_preview_sql_for_statement as build_preview_sql_for_statement,
+    generate_update_sql as build_update_sql, validate_conflict_target,
+    validate_duplicate_key_target, DeleteQueryModel, InsertQueryModel, OnConflictAction,
+    UpdateQueryModel, UpdateSqlResult,
+};
+use crate::query::undo::{generate_undo_sql, plan_undo, UndoTarget};
+use crate::services::audit_log::{command_error_message, AuditLogService};
+use crate::services::database_inspector::{DatabaseInspectorFactory, TableMetadataCache};
+use crate::services::query_analyzer::QueryAnalyzer;
+use crate::services::query_executor::{
+    ConnectionPoolManager, QueryCancellationManager, QueryExecutor, CURRENT_QUERY_ID,
 };
-use crate::services::query_executor::ConnectionPoolManager;
-use crate::sql_generator::dialects::{MysqlDialect, PostgresDialect, SqliteDialect};
+use crate::services::safety_guard::{dialect_name, safety_error, SafetyGuard};
+use crate::services::transaction_manager::{timeout_error, TransactionManager};
+use crate::sql_generator::dialects::dialect_for;
 use crate::sql_generator::Dialect;
+use std::sync::Arc;
 use std::time::Duration;
-use tauri::{command, State};
+use tauri::{command, State, WebviewWindow};
+
+/// プレビューで取得するサンプル行数の既定値
+const DEFAULT_PREVIEW_SAMPLE_LIMIT: u32 = 10;
+
+/// 元に戻すSQLのために取得する変更前の行数の上限
+const UNDO_SNAPSHOT_ROW_LIMIT: u64 = 1000;
 
 /// INSERT SQLを生成
+///
+/// `query.on_conflict` を指定した場合は、重複判定のカラムがテーブルの主キーまたは一意インデックスと一致するか検証する。
+/// 接続先に問い合わせるのはこの場合だけで、それ以外は認証情報を読み込まない
 #[command]
 pub async fn generate_insert_sql(
     query: InsertQueryModel,
     connection_id: String,
     smart_quote: bool,
     connection_service: State<'_, ConnectionService>,
+    pool_manager: State<'_, ConnectionPoolManager>,
+    table_cache: State<'_, TableMetadataCache>,
 ) -> Result<String, String> {
     let connection = connection_service
-        .get_by_id(&connection_id, false)
+        .get_by_id(&connection_id, query.on_conflict.is_some())
         .await
         .map_err(|e| e.to_string())?
         .ok_or_else(|| format!("Connection not found: {}", connection_id))?;
 
-    let dialect: Box<dyn Dialect> = match connection.database_type {
-        DatabaseType::PostgreSQL => Box::new(PostgresDialect),
-        DatabaseType::MySQL => Box::new(MysqlDialect),
-        DatabaseType::SQLite => Box::new(SqliteDialect),
+    let dialect = dialect_for(&connection.database_type);
+
+    let Some(on_conflict) = &query.on_conflict else {
+        return build_insert_sql(&query, dialect.as_ref(), smart_quote, None);
+    };
+
+    let (schema, table) = match query.table.split_once('.') {
+        Some((schema, table)) => (Some(schema), table),
+        None => (None, query.table.as_str()),
     };
+    let table = inspect_table(&connection, &table_cache, schema, table).await?;
 
-    build_insert_sql(&query, dialect.as_ref(), smart_quote)
+    let unique_keys = table
+        .primary_key
+        .iter()
+        .map(|primary_key| primary_key.columns.clone())
+        .chain(
+            table
+                .indexes
+                .iter()
+                .filter(|index| index.is_unique)
+                .map(|index| index.columns.clone()),
+        )
+        .collect::<Vec<_>>();
+    match connection.database_type {
+        DatabaseType::MySQL | DatabaseType::MariaDB => {
+            validate_duplicate_key_target(&on_conflict.target, &unique_keys)?
+        }
+        _ => validate_conflict_target(&on_conflict.target, &unique_keys)?,
+    }
+
+    // MySQLはバージョンによって挿入する値の参照方法が異なる
+    let server_version = match (&connection.database_type, &on_conflict.action) {
+        (DatabaseType::MySQL, OnConflictAction::DoUpdate { .. }) if query.source.is_none() => {
+            let password = match &connection.connection {
+                ConnectionConfig::Network(config) => config.encrypted_password.clone(),
+                _ => None,
+            };
+            let executor = pool_manager
+                .get_or_create(&connection_id, &connection, password.as_deref())
+                .await
+                .map_err(|e| serde_json::to_string(&e).unwrap_or(e.message))?;
+            let result = executor
+                .execute("SELECT VERSION()")
+                .await
+                .map_err(|e| serde_json::to_string(&e).unwrap_or(e.message))?;
+            match result.rows.first().and_then(|row| row.values.first()) {
+                Some(QueryValue::String(version)) => Some(version.clone()),
+                _ => None,
+            }
+        }
+        _ => None,
+    };
+
+    build_insert_sql(
+        &query,
+        dialect.as_ref(),
+        smart_quote,
+        server_version.as_deref(),
+    )
 }
 
 /// UPDATE SQLを生成
@@ -48,11 +130,7 @@ pub async fn generate_update_sql(
         .map_err(|e| e.to_string())?
         .ok_or_else(|| format!("Connection not found: {}", connection_id))?;
 
-    let dialect: Box<dyn Dialect> = match connection.database_type {
-        DatabaseType::PostgreSQL => Box::new(PostgresDialect),
-        DatabaseType::MySQL => Box::new(MysqlDialect),
-        DatabaseType::SQLite => Box::new(SqliteDialect),
-    };
+    let dialect = dialect_for(&connection.database_type);
 
     build_update_sql(&query, dialect.as_ref(), smart_quote)
 }
@@ -71,42 +149,498 @@ pub async fn generate_delete_sql(
         .map_err(|e| e.to_string())?
         .ok_or_else(|| format!("Connection not found: {}", connection_id))?;
 
-    let dialect: Box<dyn Dialect> = match connection.database_type {
-        DatabaseType::PostgreSQL => Box::new(PostgresDialect),
-        DatabaseType::MySQL => Box::new(MysqlDialect),
-        DatabaseType::SQLite => Box::new(SqliteDialect),
-    };
+    let dialect = dialect_for(&connection.database_type);
 
     build_delete_sql(&query, dialect.as_ref(), smart_quote)
 }
 
 /// INSERT/UPDATE/DELETEを実行
+///
+/// `request.query_id` を指定すると、実行中に `cancel_query` でキャンセルできる。
+/// `request.capture_undo` を指定すると、実行前に対象行を取得して元に戻すSQLを結果に含める
 #[command]
+#[allow(clippy::too_many_arguments)] // Tauriのコマンドは State を個別の引数で受け取る
 pub async fn execute_mutation(
     request: MutationExecuteRequest,
+    window: WebviewWindow,
     connection_service: State<'_, ConnectionService>,
     pool_manager: State<'_, ConnectionPoolManager>,
+    cancellation_manager: State<'_, QueryCancellationManager>,
+    transaction_manager: State<'_, TransactionManager>,
+    safety_guard: State<'_, SafetyGuard>,
+    audit_log: State<'_, AuditLogService>,
+    table_cache: State<'_, TableMetadataCache>,
 ) -> Result<MutationResult, String> {
+    let timeout = Duration::from_secs(request.timeout_seconds.unwrap_or(30) as u64);
+
+    // このウィンドウでトランザクションが開いていれば、その接続上で実行する
+    let in_transaction = transaction_manager
+        .is_active_in_window(&request.connection_id, window.label())
+        .await
+        .map_err(|e| serde_json::to_string(&e).unwrap_or(e.message))?;
+
     let connection = connection_service
         .get_by_id(&request.connection_id, true)
         .await
         .map_err(|e| e.to_string())?
         .ok_or_else(|| format!("Connection not found: {}", request.connection_id))?;
 
+    // トランザクション内でも環境の安全設定を適用する（拒否した実行も監査ログに残す）
+    let analysis = safety_guard
+        .check_connection(
+            &connection,
+            &request.sql,
+            request.confirmation_token.as_deref(),
+        )
+        .inspect_err(|e| {
+            audit_log.record_rejection(&connection, AuditOperation::Mutation, &request.sql, e)
+        })?;
+
+    // 監査ログが必須の環境では、記録できなければ実行しない
+    audit_log.ensure_recordable(&connection)?;
+
+    let executor = if in_transaction {
+        None
+    } else {
+        let password = match &connection.connection {
+            ConnectionConfig::Network(config) => config.encrypted_password.clone(),
+            _ => None,
+        };
+
+        let executor = pool_manager
+            .get_or_create(&request.connection_id, &connection, password.as_deref())
+            .await
+            .map_err(|e| {
+                audit_log.record_execution(
+                    &connection,
+                    AuditOperation::Mutation,
+                    &request.sql,
+                    analysis.risk_level.clone(),
+                    Err(&e.message),
+                );
+                e.message
+            })?;
+        Some(executor)
+    };
+
+    // 変更前の行の取得に失敗しても実行は続け、元に戻せない理由を結果に含める
+    let undo_plan = if request.capture_undo {
+        let dialect = dialect_for(&connection.database_type);
+        let plan = prepare_undo(&connection, &table_cache, dialect.as_ref(), &request.sql).await;
+        Some((dialect, plan))
+    } else {
+        None
+    };
+    let snapshot_sql = match &undo_plan {
+        Some((_, Ok(plan))) => Some(plan.target.snapshot_sql.as_str()),
+        _ => None,
+    };
+
+    let (query_id, cancel_token) = cancellation_manager
+        .create_token_for(request.query_id.clone(), &request.connection_id)
+        .await;
+
+    let execution = async {
+        match (&executor, snapshot_sql) {
+            (Some(executor), Some(snapshot_sql)) => {
+                execute_with_snapshot(executor.as_ref(), snapshot_sql, &request.sql, timeout).await
+            }
+            (Some(executor), None) => (
+                executor
+                    .execute_mutation_with_timeout(&request.sql, timeout)
+                    .await,
+                None,
+            ),
+            // 開いているトランザクション内で取得するため、行のロックは実行後も保持される
+            (None, snapshot_sql) => {
+                let snapshot = match snapshot_sql {
+                    Some(snapshot_sql) => Some(
+                        transaction_manager
+                            .execute_with_params(&request.connection_id, snapshot_sql, &[], timeout)
+                            .await
+                            .map_err(|e| e.message),
+                    ),
+                    None => None,
+                };
+                let result = transaction_manager
+                    .execute_mutation(&request.connection_id, &request.sql, timeout)
+                    .await;
+                (result, snapshot)
+            }
+        }
+    };
+    // 実行中の接続をクエリIDに紐づけ、サーバー側でキャンセルできるようにする
+    let execution = CURRENT_QUERY_ID.scope(query_id.clone(), execution);
+
+    let (result, snapshot) = tokio::select! {
+        result = execution => result,
+        _ = cancel_token.cancelled() => {
+            let error = QueryError {
+                code: QueryErrorCode::QueryCancelled,
+                message: "Query was cancelled".to_string(),
+                details: None,
+                native_code: None,
+            };
+            (Err(error), None)
+        }
+    };
+
+    cancellation_manager.remove(&query_id).await;
+
+    // 監査ログへの書き込みに失敗しても、実行済みの結果はそのまま返す
+    let audit_result = match &result {
+        Ok(mutation_result) => Ok(Some(mutation_result.affected_rows)),
+        Err(e) => Err(e.message.as_str()),
+    };
+    audit_log.record_execution(
+        &connection,
+        AuditOperation::Mutation,
+        &request.sql,
+        analysis.risk_level,
+        audit_result,
+    );
+
+    let mut result = result.map_err(|e| serde_json::to_string(&e).unwrap_or(e.message))?;
+    result.undo = undo_plan.map(|(dialect, plan)| build_undo(plan, snapshot, dialect.as_ref()));
+
+    Ok(result)
+}
+
+/// UPDATE/DELETEの対象行数とサンプルを取得（実行はしない）
+///
+/// 同じWHEREで `SELECT COUNT(*)` とサンプル取得のSELECTを実行する。
+/// 組み立てたSELECTも環境の安全設定で検査し、読み取り専用でなければ実行しない。
+/// このウィンドウでトランザクションが開いていれば、その接続上で実行する
+#[command]
+#[allow(clippy::too_many_arguments)]
+pub async fn preview_mutation(
+    request: MutationPreviewRequest,
+    window: WebviewWindow,
+    connection_service: State<'_, ConnectionService>,
+    pool_manager: State<'_, ConnectionPoolManager>,
+    transaction_manager: State<'_, TransactionManager>,
+    safety_guard: State<'_, SafetyGuard>,
+    audit_log: State<'_, AuditLogService>,
+) -> Result<MutationPreview, String> {
+    let timeout = Duration::from_secs(request.timeout_seconds.unwrap_or(30) as u64);
+    let sample_limit = request.sample_limit.unwrap_or(DEFAULT_PREVIEW_SAMPLE_LIMIT) as u64;
+
+    let in_transaction = transaction_manager
+        .is_active_in_window(&request.connection_id, window.label())
+        .await
+        .map_err(|e| serde_json::to_string(&e).unwrap_or(e.message))?;
+
+    let connection = connection_service
+        .get_by_id(&request.connection_id, true)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Connection not found: {}", request.connection_id))?;
+
+    let dialect = dialect_for(&connection.database_type);
+
+    let preview_sql = match &request.target {
+        MutationPreviewTarget::Update { query } => build_preview_sql(
+            &query.table,
+            query.where_clause.as_ref(),
+            &query.joins,
+            dialect.as_ref(),
+            request.smart_quote,
+            sample_limit,
+        )?,
+        MutationPreviewTarget::Delete { query } => build_preview_sql(
+            &query.table,
+            query.where_clause.as_ref(),
+            &query.joins,
+            dialect.as_ref(),
+            request.smart_quote,
+            sample_limit,
+        )?,
+        MutationPreviewTarget::Sql { sql } => {
+            build_preview_sql_for_statement(sql, dialect.as_ref(), sample_limit)?
+        }
+    };
+
+    // 実行する2つのSELECTを1件として検査・記録する
+    // （元の文のWHEREに関数呼び出しなどが含まれていても、書き込みになる文は実行しない）
+    let audit_sql = format!("{};\n{}", preview_sql.count_sql, preview_sql.sample_sql);
+    let analysis = safety_guard
+        .check_connection(&connection, &audit_sql, None)
+        .and_then(|analysis| {
+            if QueryAnalyzer::is_read_only(&audit_sql, dialect_name(&connection.database_type)) {
+                Ok(analysis)
+            } else {
+                Err(safety_error(
+                    QueryErrorCode::PermissionDenied,
+                    "The preview query is not read-only".to_string(),
+                ))
+            }
+        })
+        .inspect_err(|e| {
+            audit_log.record_rejection(&connection, AuditOperation::Preview, &audit_sql, e)
+        })?;
+
+    // 監査ログが必須の環境では、記録できなければ実行しない
+    audit_log.ensure_recordable(&connection)?;
+
+    let preview = async {
+        let executor = if in_transaction {
+            None
+        } else {
+            let password = match &connection.connection {
+                ConnectionConfig::Network(config) => config.encrypted_password.clone(),
+                _ => None,
+            };
+
+            let executor = pool_manager
+                .get_or_create(&request.connection_id, &connection, password.as_deref())
+                .await
+                .map_err(|e| e.message)?;
+            Some(executor)
+        };
+
+        let count_result = execute_select(
+            executor.as_ref(),
+            &transaction_manager,
+            &request.connection_id,
+            &preview_sql.count_sql,
+            timeout,
+        )
+        .await?;
+        let sample = execute_select(
+            executor.as_ref(),
+            &transaction_manager,
+            &request.connection_id,
+            &preview_sql.sample_sql,
+            timeout,
+        )
+        .await?;
+        Ok::<_, String>((count_value(&count_result)?, sample))
+    }
+    .await;
+
+    let audit_error = preview.as_ref().err().map(|e| command_error_message(e));
+    audit_log.record_execution(
+        &connection,
+        AuditOperation::Preview,
+        &audit_sql,
+        analysis.risk_level,
+        match &preview {
+            Ok((affected_rows, _)) => Ok(Some(*affected_rows)),
+            Err(_) => Err(audit_error.as_deref().unwrap_or_default()),
+        },
+    );
+
+    let (affected_rows, sample) = preview?;
+    Ok(MutationPreview {
+        affected_rows,
+        sample,
+        count_sql: preview_sql.count_sql,
+        sample_sql: preview_sql.sample_sql,
+    })
+}
+
+/// SELECTを実行（executorがなければトランザクション上で実行）
+async fn execute_select(
+    executor: Option<&Arc<dyn QueryExecutor>>,
+    transaction_manager: &TransactionManager,
+    connection_id: &str,
+    sql: &str,
+    timeout: Duration,
+) -> Result<QueryResult, String> {
+    let result = match executor {
+        Some(executor) => executor.execute_with_timeout(sql, timeout).await,
+        None => {
+            transaction_manager
+                .execute_with_params(connection_id, sql, &[], timeout)
+                .await
+        }
+    };
+    result.map_err(|e| serde_json::to_string(&e).unwrap_or(e.message))
+}
+
+/// `SELECT COUNT(*)` の結果から件数を取り出す（ドライバによって整数・数値文字列になる）
+fn count_value(result: &QueryResult) -> Result<u64, String> {
+    let value = result
+        .rows
+        .first()
+        .and_then(|row| row.values.first())
+        .ok_or("COUNT(*) returned no rows")?;
+
+    match value {
+        QueryValue::Int(count) => u64::try_from(*count).map_err(|e| e.to_string()),
+        QueryValue::Float(count) => Ok(*count as u64),
+        QueryValue::Decimal(count) | QueryValue::String(count) => {
+            count.parse::<u64>().map_err(|e| e.to_string())
+        }
+        other => Err(format!("Unexpected COUNT(*) value: {:?}", other)),
+    }
+}
+
+/// 元に戻すSQLの生成に使う対象の文とテーブルの情報（実行前に用意する）
+struct UndoPlan {
+    target: UndoTarget,
+    table: Table,
+}
+
+/// 変更前の行を取得するSELECTを組み立て、主キー・カラムの情報を取得
+async fn prepare_undo(
+    connection: &ConnectionInfo,
+    table_cache: &TableMetadataCache,
+    dialect: &dyn Dialect,
+    sql: &str,
+) -> Result<UndoPlan, String> {
+    let target = plan_undo(sql, dialect, UNDO_SNAPSHOT_ROW_LIMIT)?;
+    let table = inspect_table(
+        connection,
+        table_cache,
+        target.schema(),
+        target.table_name(),
+    )
+    .await?;
+    Ok(UndoPlan { target, table })
+}
+
+/// 変更前の行を取得してから、同じトランザクションでデータ変更を実行
+///
+/// 取得した行はコミットまでロックされ、他の接続から変更されない。
+/// 変更前の行を取得できなかった場合は、トランザクションを使わずに実行する
+async fn execute_with_snapshot(
+    executor: &dyn QueryExecutor,
+    snapshot_sql: &str,
+    sql: &str,
+    timeout: Duration,
+) -> (
+    Result<MutationResult, QueryError>,
+    Option<Result<QueryResult, String>>,
+) {
+    let snapshot = async {
+        let mut session = executor.begin_transaction().await?;
+        match tokio::time::timeout(timeout, session.execute_with_params(snapshot_sql, &[])).await {
+            Ok(Ok(rows)) => Ok((session, rows)),
+            Ok(Err(e)) => {
+                let _ = session.rollback().await;
+                Err(e)
+            }
+            Err(_) => {
+                let _ = session.rollback().await;
+                Err(timeout_error(timeout))
+            }
+        }
+    };
+    let (mut session, rows) = match snapshot.await {
+        Ok(snapshot) => snapshot,
+        Err(e) => {
+            let result = executor.execute_mutation_with_timeout(sql, timeout).await;
+            return (result, Some(Err(e.message)));
+        }
+    };
+
+    let result = match tokio::time::timeout(timeout, session.execute_mutation(sql)).await {
+        Ok(Ok(result)) => session.commit().await.map(|()| result),
+        Ok(Err(e)) => {
+            let _ = session.rollback().await;
+            Err(e)
+        }
+        Err(_) => {
+            let _ = session.rollback().await;
+            Err(timeout_error(timeout))
+        }
+    };
+    (result, Some(Ok(rows)))
+}
+
+/// テーブルの情報（主キー・インデックスを含む）を取得
+///
+/// キャッシュになければインスペクターから取得する。キャッシュにテーブルがなければ
+/// 作成されたばかりの可能性があるため、取得し直す
+async fn inspect_table(
+    connection: &ConnectionInfo,
+    table_cache: &TableMetadataCache,
+    schema: Option<&str>,
+    table_name: &str,
+) -> Result<Table, String> {
+    // スキーマ未指定の場合は接続先の既定スキーマとみなす
+    let schema = match schema {
+        Some(schema) => schema.to_string(),
+        None => match (&connection.database_type, &connection.connection) {
+            (DatabaseType::PostgreSQL, _) => "public".to_string(),
+            (DatabaseType::MySQL | DatabaseType::MariaDB, ConnectionConfig::Network(config)) => {
+                config.database.clone()
+            }
+            (DatabaseType::SqlServer, _) => "dbo".to_string(),
+            _ => "main".to_string(),
+        },
+    };
+
+    if let Some(table) = table_cache
+        .get(&connection.id, &schema)
+        .and_then(|tables| find_table(&tables, table_name))
+    {
+        return Ok(table);
+    }
+
     let password = match &connection.connection {
         ConnectionConfig::Network(config) => config.encrypted_password.clone(),
         _ => None,
     };
+    let inspector = DatabaseInspectorFactory::create(connection, password.as_deref()).await?;
+    let tables = table_cache.insert(
+        &connection.id,
+        &schema,
+        inspector.get_tables(&schema).await?,
+    );
+    find_table(&tables, table_name)
+        .ok_or_else(|| format!("Table not found: {}.{}", schema, table_name))
+}
 
-    let executor = pool_manager
-        .get_or_create(&request.connection_id, &connection, password.as_deref())
-        .await
-        .map_err(|e| e.message)?;
+/// 名前が一致するテーブル（完全一致がなければ大文字小文字を区別せずに探す）
+fn find_table(tables: &[Table], table_name: &str) -> Option<Table> {
+    tables
+        .iter()
+        .find(|table| table.name == table_name)
+        .or_else(|| {
+            tables
+                .iter()
+                .find(|table| table.name.eq_ignore_ascii_case(table_name))
+        })
+        .cloned()
+}
 
-    let timeout = Duration::from_secs(request.timeout_seconds.unwrap_or(30) as u64);
+/// 変更前の行から元に戻すSQLを生成した結果
+fn build_undo(
+    plan: Result<UndoPlan, String>,
+    snapshot: Option<Result<QueryResult, String>>,
+    dialect: &dyn Dialect,
+) -> MutationUndo {
+    let generated = plan.and_then(|plan| {
+        let rows = snapshot.unwrap_or_else(|| Err("Rows were not captured".to_string()))?;
+        if rows.rows.len() as u64 > UNDO_SNAPSHOT_ROW_LIMIT {
+            return Err(format!(
+                "More than {} rows are affected",
+                UNDO_SNAPSHOT_ROW_LIMIT
+            ));
+        }
+        let primary_key = plan
+            .table
+            .primary_key
+            .map(|primary_key| primary_key.columns)
+            .unwrap_or_default();
+
+        generate_undo_sql(
+            &plan.target,
+            &rows,
+            &primary_key,
+            &plan.table.columns,
+            dialect,
+        )
+        .map(|sql| sql.map(|sql| (sql, rows.rows.len())))
+    });
 
-    match executor.execute_mutation_with_timeout(&request.sql, timeout).await {
-        Ok(result) => Ok(result),
-        Err(e) => Err(serde_json::to_string(&e).unwrap_or(e.message)),
+    match generated {
+        Ok(Some((sql, row_count))) => MutationUndo::Available { sql, row_count },
+        Ok(None) => MutationUndo::Unavailable {
+            reason: "No rows matched".to_string(),
+        },
+        Err(reason) => MutationUndo::Unavailable { reason },
     }
 }
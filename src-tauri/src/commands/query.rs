@@ -1,13 +1,25 @@
-use crate::connection::{ConnectionConfig, ConnectionService, DatabaseType};
+use crate::connection::{ConnectionConfig, ConnectionService};
+use crate::models::audit_log::AuditOperation;
 use crate::models::query::QueryModel;
+use crate::models::query_plan::{ExplainRequest, QueryPlan};
 use crate::models::query_result::{
-    QueryError, QueryErrorCode, QueryExecuteRequest, QueryExecuteResponse,
+    QueryCursorOpenRequest, QueryError, QueryErrorCode, QueryExecuteRequest, QueryExecuteResponse,
+    QueryResultPage, ScriptExecuteRequest, ScriptExecuteResponse, StatementOutcome,
 };
-use crate::services::query_executor::{ConnectionPoolManager, QueryCancellationManager};
-use crate::sql_generator::dialects::{MysqlDialect, PostgresDialect, SqliteDialect};
-use crate::sql_generator::{Dialect, SqlBuilder};
-use std::time::Duration;
-use tauri::{command, State};
+use crate::services::audit_log::AuditLogService;
+use crate::services::database_inspector::{DatabaseInspectorFactory, TableMetadataCache};
+use crate::services::query_executor::{
+    ConnectionPoolManager, QueryCancellationManager, QueryCursorManager, CURRENT_QUERY_ID,
+    DEFAULT_CURSOR_PAGE_SIZE,
+};
+use crate::services::query_plan::{QueryPlanAnalyzer, TableRowCountCache};
+use crate::services::safety_guard::{dialect_name, SafetyGuard};
+use crate::services::transaction_manager::TransactionManager;
+use crate::sql::statement_splitter::StatementSplitter;
+use crate::sql_generator::dialects::dialect_for;
+use crate::sql_generator::{BoundSql, SqlBuilder};
+use std::time::{Duration, Instant};
+use tauri::{command, State, WebviewWindow};
 
 /// SQLを生成
 #[command]
@@ -22,11 +34,7 @@ pub async fn generate_sql(
         .map_err(|e| e.to_string())?
         .ok_or_else(|| format!("Connection not found: {}", query.connection_id))?;
 
-    let dialect: Box<dyn Dialect> = match connection.database_type {
-        DatabaseType::PostgreSQL => Box::new(PostgresDialect),
-        DatabaseType::MySQL => Box::new(MysqlDialect),
-        DatabaseType::SQLite => Box::new(SqliteDialect),
-    };
+    let dialect = dialect_for(&connection.database_type);
 
     let builder = SqlBuilder::new(dialect.as_ref());
     builder.build(&query)
@@ -46,11 +54,7 @@ pub async fn generate_sql_formatted(
         .map_err(|e| e.to_string())?
         .ok_or_else(|| format!("Connection not found: {}", query.connection_id))?;
 
-    let dialect: Box<dyn Dialect> = match connection.database_type {
-        DatabaseType::PostgreSQL => Box::new(PostgresDialect),
-        DatabaseType::MySQL => Box::new(MysqlDialect),
-        DatabaseType::SQLite => Box::new(SqliteDialect),
-    };
+    let dialect = dialect_for(&connection.database_type);
 
     let mut builder = SqlBuilder::new(dialect.as_ref());
     if !pretty {
@@ -61,14 +65,44 @@ pub async fn generate_sql_formatted(
     builder.build(&query)
 }
 
+/// プレースホルダー付きSQLとバインドパラメータを生成
+#[command]
+pub async fn generate_sql_with_params(
+    query: QueryModel,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<BoundSql, String> {
+    let connection = connection_service
+        .get_by_id(&query.connection_id, false)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Connection not found: {}", query.connection_id))?;
+
+    let dialect = dialect_for(&connection.database_type);
+
+    let builder = SqlBuilder::new(dialect.as_ref());
+    builder.build_with_params(&query)
+}
+
 /// クエリを実行
 #[command]
+#[allow(clippy::too_many_arguments)] // Tauriのコマンドは State を個別の引数で受け取る
 pub async fn execute_query(
     request: QueryExecuteRequest,
+    window: WebviewWindow,
     connection_service: State<'_, ConnectionService>,
     pool_manager: State<'_, ConnectionPoolManager>,
     cancellation_manager: State<'_, QueryCancellationManager>,
+    transaction_manager: State<'_, TransactionManager>,
+    safety_guard: State<'_, SafetyGuard>,
+    audit_log: State<'_, AuditLogService>,
+    table_cache: State<'_, TableMetadataCache>,
 ) -> Result<QueryExecuteResponse, String> {
+    // このウィンドウでトランザクションが開いていれば、その接続上で実行する
+    let in_transaction = transaction_manager
+        .is_active_in_window(&request.connection_id, window.label())
+        .await
+        .map_err(|e| serde_json::to_string(&e).unwrap_or(e.message))?;
+
     // 接続情報を取得
     let connection = connection_service
         .get_by_id(&request.connection_id, true)
@@ -76,6 +110,20 @@ pub async fn execute_query(
         .map_err(|e| e.to_string())?
         .ok_or_else(|| format!("Connection not found: {}", request.connection_id))?;
 
+    // 環境の安全設定で禁止・要確認の文をチェック（拒否した実行も監査ログに残す）
+    let analysis = safety_guard
+        .check_connection(
+            &connection,
+            &request.sql,
+            request.confirmation_token.as_deref(),
+        )
+        .inspect_err(|e| {
+            audit_log.record_rejection(&connection, AuditOperation::Query, &request.sql, e)
+        })?;
+
+    // 監査ログが必須の環境では、記録できなければ実行しない
+    audit_log.ensure_recordable(&connection)?;
+
     // パスワードを取得
     let password = match &connection.connection {
         ConnectionConfig::Network(config) => config.encrypted_password.clone(),
@@ -86,17 +134,51 @@ pub async fn execute_query(
     let executor = pool_manager
         .get_or_create(&request.connection_id, &connection, password.as_deref())
         .await
-        .map_err(|e| e.message)?;
+        .map_err(|e| {
+            audit_log.record_execution(
+                &connection,
+                AuditOperation::Query,
+                &request.sql,
+                analysis.risk_level.clone(),
+                Err(&e.message),
+            );
+            e.message
+        })?;
 
     // キャンセルトークンを作成
-    let (query_id, cancel_token) = cancellation_manager.create_token().await;
+    let (query_id, cancel_token) = cancellation_manager
+        .create_token_for(request.query_id.clone(), &request.connection_id)
+        .await;
 
     // タイムアウト設定
     let timeout = Duration::from_secs(request.timeout_seconds.unwrap_or(30) as u64);
 
     // クエリ実行
+    // パラメータがある場合はバインドして実行
+    let execution = async {
+        if in_transaction {
+            transaction_manager
+                .execute_with_params(
+                    &request.connection_id,
+                    &request.sql,
+                    &request.params,
+                    timeout,
+                )
+                .await
+        } else if request.params.is_empty() {
+            executor.execute_with_timeout(&request.sql, timeout).await
+        } else {
+            executor
+                .execute_with_params_and_timeout(&request.sql, &request.params, timeout)
+                .await
+        }
+    };
+
+    // 実行中の接続をクエリIDに紐づけ、サーバー側でキャンセルできるようにする
+    let execution = CURRENT_QUERY_ID.scope(query_id.clone(), execution);
+
     let result = tokio::select! {
-        result = executor.execute_with_timeout(&request.sql, timeout) => result,
+        result = execution => result,
         _ = cancel_token.cancelled() => {
             Err(QueryError {
                 code: QueryErrorCode::QueryCancelled,
@@ -110,6 +192,24 @@ pub async fn execute_query(
     // トークンをクリーンアップ
     cancellation_manager.remove(&query_id).await;
 
+    // 構造を変える文を実行した場合は、元に戻すSQLなどに使うテーブル情報を取得し直す
+    if analysis.may_change_schema() {
+        table_cache.invalidate(&request.connection_id);
+    }
+
+    // 監査ログへの書き込みに失敗しても、実行済みの結果はそのまま返す
+    let audit_result = match &result {
+        Ok(query_result) => Ok(Some(query_result.row_count as u64)),
+        Err(e) => Err(e.message.as_str()),
+    };
+    audit_log.record_execution(
+        &connection,
+        AuditOperation::Query,
+        &request.sql,
+        analysis.risk_level,
+        audit_result,
+    );
+
     match result {
         Ok(query_result) => Ok(QueryExecuteResponse {
             query_id,
@@ -119,11 +219,408 @@ pub async fn execute_query(
     }
 }
 
+/// 複数文のスクリプトを実行し、文ごとの結果を返す
+#[command]
+#[allow(clippy::too_many_arguments)] // Tauriのコマンドは State を個別の引数で受け取る
+pub async fn execute_script(
+    request: ScriptExecuteRequest,
+    window: WebviewWindow,
+    connection_service: State<'_, ConnectionService>,
+    pool_manager: State<'_, ConnectionPoolManager>,
+    cancellation_manager: State<'_, QueryCancellationManager>,
+    transaction_manager: State<'_, TransactionManager>,
+    safety_guard: State<'_, SafetyGuard>,
+    audit_log: State<'_, AuditLogService>,
+    table_cache: State<'_, TableMetadataCache>,
+) -> Result<ScriptExecuteResponse, String> {
+    let start = Instant::now();
+
+    // このウィンドウでトランザクションが開いていれば、その接続上で実行する
+    let in_transaction = transaction_manager
+        .is_active_in_window(&request.connection_id, window.label())
+        .await
+        .map_err(|e| serde_json::to_string(&e).unwrap_or(e.message))?;
+
+    let connection = connection_service
+        .get_by_id(&request.connection_id, true)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Connection not found: {}", request.connection_id))?;
+
+    let analysis = safety_guard
+        .check_connection(
+            &connection,
+            &request.sql,
+            request.confirmation_token.as_deref(),
+        )
+        .inspect_err(|e| {
+            audit_log.record_rejection(&connection, AuditOperation::Script, &request.sql, e)
+        })?;
+
+    // 監査ログが必須の環境では、記録できなければ実行しない
+    audit_log.ensure_recordable(&connection)?;
+
+    let password = match &connection.connection {
+        ConnectionConfig::Network(config) => config.encrypted_password.clone(),
+        _ => None,
+    };
+
+    let executor = pool_manager
+        .get_or_create(&request.connection_id, &connection, password.as_deref())
+        .await
+        .map_err(|e| {
+            audit_log.record_execution(
+                &connection,
+                AuditOperation::Script,
+                &request.sql,
+                analysis.risk_level.clone(),
+                Err(&e.message),
+            );
+            e.message
+        })?;
+
+    let (query_id, cancel_token) = cancellation_manager
+        .create_token_for(request.query_id.clone(), &request.connection_id)
+        .await;
+
+    // タイムアウトは1文ごとに適用する
+    let timeout = Duration::from_secs(request.timeout_seconds.unwrap_or(30) as u64);
+    let statement_count = StatementSplitter::new(dialect_name(&connection.database_type))
+        .split(&request.sql)
+        .len();
+
+    let execution = async {
+        if in_transaction {
+            transaction_manager
+                .execute_script(
+                    &request.connection_id,
+                    &request.sql,
+                    request.error_mode,
+                    timeout,
+                )
+                .await
+        } else {
+            executor
+                .execute_script(&request.sql, request.error_mode, timeout)
+                .await
+        }
+    };
+
+    // 実行中の接続をクエリIDに紐づけ、サーバー側でキャンセルできるようにする
+    let execution = CURRENT_QUERY_ID.scope(query_id.clone(), execution);
+
+    let result = tokio::select! {
+        result = execution => result,
+        _ = cancel_token.cancelled() => {
+            Err(QueryError {
+                code: QueryErrorCode::QueryCancelled,
+                message: "Query was cancelled".to_string(),
+                details: None,
+                native_code: None,
+            })
+        }
+    };
+
+    cancellation_manager.remove(&query_id).await;
+
+    // 構造を変える文を実行した場合は、元に戻すSQLなどに使うテーブル情報を取得し直す
+    if analysis.may_change_schema() {
+        table_cache.invalidate(&request.connection_id);
+    }
+
+    // スクリプトは1件として記録する（影響行数は文ごとの合計、エラーは最初のもの）
+    let audit_result = match &result {
+        Ok(results) => {
+            let first_error = results
+                .iter()
+                .find_map(|statement| match &statement.outcome {
+                    StatementOutcome::Error { error } => Some(error.message.as_str()),
+                    _ => None,
+                });
+            match first_error {
+                Some(message) => Err(message),
+                None => Ok(Some(
+                    results
+                        .iter()
+                        .map(|statement| match &statement.outcome {
+                            StatementOutcome::ResultSet { result } => result.row_count as u64,
+                            StatementOutcome::Affected { affected_rows } => *affected_rows,
+                            _ => 0,
+                        })
+                        .sum(),
+                )),
+            }
+        }
+        Err(e) => Err(e.message.as_str()),
+    };
+    audit_log.record_execution(
+        &connection,
+        AuditOperation::Script,
+        &request.sql,
+        analysis.risk_level,
+        audit_result,
+    );
+
+    match result {
+        Ok(results) => Ok(ScriptExecuteResponse {
+            query_id,
+            results,
+            statement_count,
+            execution_time_ms: start.elapsed().as_millis() as u64,
+        }),
+        Err(e) => Err(serde_json::to_string(&e).unwrap_or(e.message)),
+    }
+}
+
+/// 実行計画を取得
+///
+/// 大きなテーブルの判定にはインスペクターのテーブル推定行数を使う（取得できなければ計画上の推定行数）
+#[command]
+pub async fn explain_query(
+    request: ExplainRequest,
+    connection_service: State<'_, ConnectionService>,
+    pool_manager: State<'_, ConnectionPoolManager>,
+    row_count_cache: State<'_, TableRowCountCache>,
+    safety_guard: State<'_, SafetyGuard>,
+    audit_log: State<'_, AuditLogService>,
+) -> Result<QueryPlan, String> {
+    let connection = connection_service
+        .get_by_id(&request.connection_id, true)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Connection not found: {}", request.connection_id))?;
+
+    // ANALYZE は文を実際に実行するため、通常の実行と同じ安全チェックを行い監査ログに残す
+    let analysis = if request.analyze {
+        let analysis = safety_guard
+            .check_connection(
+                &connection,
+                &request.sql,
+                request.confirmation_token.as_deref(),
+            )
+            .inspect_err(|e| {
+                audit_log.record_rejection(&connection, AuditOperation::Explain, &request.sql, e)
+            })?;
+
+        // 監査ログが必須の環境では、記録できなければ実行しない
+        audit_log.ensure_recordable(&connection)?;
+        Some(analysis)
+    } else {
+        None
+    };
+    let record_analyze = |result: Result<Option<u64>, &str>| {
+        if let Some(analysis) = &analysis {
+            audit_log.record_execution(
+                &connection,
+                AuditOperation::Explain,
+                &request.sql,
+                analysis.risk_level.clone(),
+                result,
+            );
+        }
+    };
+
+    let password = match &connection.connection {
+        ConnectionConfig::Network(config) => config.encrypted_password.clone(),
+        _ => None,
+    };
+
+    let executor = pool_manager
+        .get_or_create(&request.connection_id, &connection, password.as_deref())
+        .await
+        .map_err(|e| {
+            record_analyze(Err(&e.message));
+            e.message
+        })?;
+
+    let timeout = Duration::from_secs(request.timeout_seconds.unwrap_or(30) as u64);
+
+    let explain = async {
+        let table_rows = match row_count_cache.get(&request.connection_id) {
+            Some(table_rows) => table_rows,
+            None => {
+                let inspector =
+                    DatabaseInspectorFactory::create(&connection, password.as_deref()).await;
+                let summaries = match inspector {
+                    Ok(inspector) => inspector.get_table_summaries().await.unwrap_or_default(),
+                    Err(_) => vec![],
+                };
+                row_count_cache.insert(
+                    &request.connection_id,
+                    QueryPlanAnalyzer::table_row_counts(&summaries),
+                )
+            }
+        };
+
+        QueryPlanAnalyzer::explain(
+            executor.as_ref(),
+            dialect_name(&connection.database_type),
+            &request.sql,
+            request.analyze,
+            &table_rows,
+        )
+        .await
+    };
+
+    let result = match tokio::time::timeout(timeout, explain).await {
+        Ok(result) => result,
+        Err(_) => Err(QueryError {
+            code: QueryErrorCode::QueryTimeout,
+            message: format!("Query timed out after {:?}", timeout),
+            details: None,
+            native_code: None,
+        }),
+    };
+    record_analyze(
+        result
+            .as_ref()
+            .map(|_| None)
+            .map_err(|e| e.message.as_str()),
+    );
+
+    result.map_err(|e| serde_json::to_string(&e).unwrap_or(e.message))
+}
+
 /// クエリをキャンセル
+///
+/// サーバー側で実行中の文を停止してから、結果の待機を打ち切る
 #[command]
 pub async fn cancel_query(
     query_id: String,
+    pool_manager: State<'_, ConnectionPoolManager>,
     cancellation_manager: State<'_, QueryCancellationManager>,
 ) -> Result<bool, String> {
-    Ok(cancellation_manager.cancel(&query_id).await)
+    let mut server_result = Ok(false);
+    if let Some(connection_id) = cancellation_manager.connection_of(&query_id).await {
+        if let Some(executor) = pool_manager.get(&connection_id).await {
+            server_result = executor.cancel(&query_id).await;
+        }
+    }
+
+    let cancelled = cancellation_manager.cancel(&query_id).await;
+
+    server_result
+        .map(|server_cancelled| cancelled || server_cancelled)
+        .map_err(|e| serde_json::to_string(&e).unwrap_or(e.message))
+}
+
+/// カーソルを開いて最初のページを取得
+///
+/// カーソルはプールの接続で開くため、このウィンドウでトランザクションが開いている間は開けない
+#[command]
+#[allow(clippy::too_many_arguments)] // Tauriのコマンドは State を個別の引数で受け取る
+pub async fn open_query_cursor(
+    request: QueryCursorOpenRequest,
+    window: WebviewWindow,
+    connection_service: State<'_, ConnectionService>,
+    pool_manager: State<'_, ConnectionPoolManager>,
+    cursor_manager: State<'_, QueryCursorManager>,
+    transaction_manager: State<'_, TransactionManager>,
+    safety_guard: State<'_, SafetyGuard>,
+    audit_log: State<'_, AuditLogService>,
+) -> Result<QueryResultPage, String> {
+    transaction_manager
+        .ensure_inactive_in_window(&request.connection_id, window.label())
+        .await
+        .map_err(|e| serde_json::to_string(&e).unwrap_or(e.message))?;
+
+    let connection = connection_service
+        .get_by_id(&request.connection_id, true)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Connection not found: {}", request.connection_id))?;
+
+    let analysis = safety_guard
+        .check_connection(
+            &connection,
+            &request.sql,
+            request.confirmation_token.as_deref(),
+        )
+        .inspect_err(|e| {
+            audit_log.record_rejection(&connection, AuditOperation::Cursor, &request.sql, e)
+        })?;
+
+    // 監査ログが必須の環境では、記録できなければ実行しない
+    audit_log.ensure_recordable(&connection)?;
+
+    let password = match &connection.connection {
+        ConnectionConfig::Network(config) => config.encrypted_password.clone(),
+        _ => None,
+    };
+
+    let page_size = request.page_size.unwrap_or(DEFAULT_CURSOR_PAGE_SIZE);
+    let timeout = Duration::from_secs(request.timeout_seconds.unwrap_or(30) as u64);
+
+    let opened = async {
+        let executor = pool_manager
+            .get_or_create(&request.connection_id, &connection, password.as_deref())
+            .await?;
+        let cursor = executor.open_cursor(&request.sql, page_size).await?;
+        cursor_manager
+            .register(&request.connection_id, cursor)
+            .await
+    }
+    .await;
+
+    // 取得する行数は開いた時点では分からないため、件数なしで記録する
+    audit_log.record_execution(
+        &connection,
+        AuditOperation::Cursor,
+        &request.sql,
+        analysis.risk_level,
+        opened
+            .as_ref()
+            .map(|_| None)
+            .map_err(|e| e.message.as_str()),
+    );
+    let cursor_id = opened.map_err(|e| serde_json::to_string(&e).unwrap_or(e.message))?;
+
+    fetch_page_with_timeout(&cursor_manager, &cursor_id, page_size, timeout).await
+}
+
+/// カーソルから次のページを取得
+#[command]
+pub async fn fetch_query_cursor(
+    cursor_id: String,
+    page_size: Option<usize>,
+    timeout_seconds: Option<u32>,
+    cursor_manager: State<'_, QueryCursorManager>,
+) -> Result<QueryResultPage, String> {
+    let page_size = page_size.unwrap_or(DEFAULT_CURSOR_PAGE_SIZE);
+    let timeout = Duration::from_secs(timeout_seconds.unwrap_or(30) as u64);
+
+    fetch_page_with_timeout(&cursor_manager, &cursor_id, page_size, timeout).await
+}
+
+/// カーソルを閉じる
+#[command]
+pub async fn close_query_cursor(
+    cursor_id: String,
+    cursor_manager: State<'_, QueryCursorManager>,
+) -> Result<bool, String> {
+    Ok(cursor_manager.close(&cursor_id).await)
+}
+
+/// タイムアウト付きでページを取得（タイムアウト時はカーソルを閉じる）
+async fn fetch_page_with_timeout(
+    cursor_manager: &QueryCursorManager,
+    cursor_id: &str,
+    page_size: usize,
+    timeout: Duration,
+) -> Result<QueryResultPage, String> {
+    let result =
+        match tokio::time::timeout(timeout, cursor_manager.fetch(cursor_id, page_size)).await {
+            Ok(result) => result,
+            Err(_) => {
+                cursor_manager.close(cursor_id).await;
+                Err(QueryError {
+                    code: QueryErrorCode::QueryTimeout,
+                    message: format!("Query timed out after {:?}", timeout),
+                    details: None,
+                    native_code: None,
+                })
+            }
+        };
+
+    result.map_err(|e| serde_json::to_string(&e).unwrap_or(e.message))
 }
@@ -1,5 +1,40 @@
-use crate::models::safety_settings::{EnvironmentSafetyConfig, SafetySettings};
+use crate::connection::ConnectionService;
+use crate::models::query_result::QueryErrorCode;
+use crate::models::safety_settings::{ConfirmationToken, EnvironmentSafetyConfig, SafetySettings};
 use crate::services::safety_config::SafetyConfigStorage;
+use crate::services::safety_guard::{safety_error, SafetyGuard};
+use tauri::{AppHandle, State};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+
+/// 確認ダイアログに表示するSQLの最大文字数
+const CONFIRMATION_SQL_PREVIEW_CHARS: usize = 1000;
+
+/// ネイティブの確認ダイアログを表示し、承認されたかを返す
+///
+/// WebView側のダイアログはスクリプトから迂回できるため、安全に関わる操作はこちらで確認する
+async fn confirm_natively(app: &AppHandle, title: &str, message: String, ok_label: &str) -> bool {
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    app.dialog()
+        .message(message)
+        .title(title)
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            ok_label.to_string(),
+            "キャンセル".to_string(),
+        ))
+        .show(move |confirmed| {
+            let _ = sender.send(confirmed);
+        });
+    receiver.await.unwrap_or(false)
+}
+
+/// 安全設定の変更が承認されなかったときのエラー
+fn settings_change_cancelled() -> String {
+    safety_error(
+        QueryErrorCode::PermissionDenied,
+        "The safety settings change was cancelled".to_string(),
+    )
+}
 
 #[tauri::command]
 pub async fn get_safety_settings() -> Result<SafetySettings, String> {
@@ -11,7 +46,19 @@ pub async fn get_safety_settings() -> Result<SafetySettings, String> {
 pub async fn update_environment_safety(
     environment: String,
     config: EnvironmentSafetyConfig,
+    app: AppHandle,
 ) -> Result<SafetySettings, String> {
+    let confirmed = confirm_natively(
+        &app,
+        "安全設定の変更",
+        format!("環境「{}」の安全設定を変更しますか？", environment),
+        "変更する",
+    )
+    .await;
+    if !confirmed {
+        return Err(settings_change_cancelled());
+    }
+
     let storage = SafetyConfigStorage::new();
     let mut settings = storage.load().map_err(|e| e.to_string())?;
 
@@ -22,7 +69,62 @@ pub async fn update_environment_safety(
 }
 
 #[tauri::command]
-pub async fn reset_safety_settings() -> Result<SafetySettings, String> {
+pub async fn reset_safety_settings(app: AppHandle) -> Result<SafetySettings, String> {
+    let confirmed = confirm_natively(
+        &app,
+        "安全設定のリセット",
+        "すべての環境の安全設定をデフォルトに戻しますか？".to_string(),
+        "リセットする",
+    )
+    .await;
+    if !confirmed {
+        return Err(settings_change_cancelled());
+    }
+
     let storage = SafetyConfigStorage::new();
     storage.reset().map_err(|e| e.to_string())
 }
+
+/// ネイティブの確認ダイアログで承認されたSQLに対して確認トークンを発行する
+///
+/// トークンは接続IDとSQLに紐づき、実行系コマンドの `confirmation_token` に渡すと確認済みとして扱われる。
+/// トークンは1回だけ使える
+#[tauri::command]
+pub async fn issue_confirmation_token(
+    connection_id: String,
+    sql: String,
+    app: AppHandle,
+    connection_service: State<'_, ConnectionService>,
+    safety_guard: State<'_, SafetyGuard>,
+) -> Result<ConfirmationToken, String> {
+    let connection = connection_service
+        .get_by_id(&connection_id, false)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Connection not found: {}", connection_id))?;
+    // 設定で禁止されている文はダイアログを出さずに拒否する
+    safety_guard.ensure_allowed(&connection, &sql)?;
+
+    let mut preview: String = sql.chars().take(CONFIRMATION_SQL_PREVIEW_CHARS).collect();
+    if preview.len() < sql.len() {
+        preview.push_str("\n…");
+    }
+    let confirmed = confirm_natively(
+        &app,
+        "実行の確認",
+        format!(
+            "接続「{}」で次のSQLを実行しますか？\n\n{}",
+            connection.name, preview
+        ),
+        "実行する",
+    )
+    .await;
+    if !confirmed {
+        return Err(safety_error(
+            QueryErrorCode::QueryCancelled,
+            "Execution was cancelled in the confirmation dialog".to_string(),
+        ));
+    }
+
+    safety_guard.issue_token(&connection, &sql)
+}
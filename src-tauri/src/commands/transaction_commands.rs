@@ -0,0 +1,83 @@
+use crate::connection::{ConnectionConfig, ConnectionService};
+use crate::models::transaction::TransactionInfo;
+use crate::services::query_executor::ConnectionPoolManager;
+use crate::services::transaction_manager::{TransactionManager, DEFAULT_TRANSACTION_TIMEOUT_SECS};
+use std::time::Duration;
+use tauri::{command, State, WebviewWindow};
+
+/// トランザクションを開始
+///
+/// 呼び出し元ウィンドウに専用の接続を確保する。`timeout_seconds` の間操作がなければ自動的にロールバックされる。
+#[command]
+pub async fn begin_transaction(
+    connection_id: String,
+    timeout_seconds: Option<u32>,
+    window: WebviewWindow,
+    connection_service: State<'_, ConnectionService>,
+    pool_manager: State<'_, ConnectionPoolManager>,
+    transaction_manager: State<'_, TransactionManager>,
+) -> Result<TransactionInfo, String> {
+    let connection = connection_service
+        .get_by_id(&connection_id, true)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Connection not found: {}", connection_id))?;
+
+    let password = match &connection.connection {
+        ConnectionConfig::Network(config) => config.encrypted_password.clone(),
+        _ => None,
+    };
+
+    let executor = pool_manager
+        .get_or_create(&connection_id, &connection, password.as_deref())
+        .await
+        .map_err(|e| e.message)?;
+
+    let idle_timeout =
+        Duration::from_secs(timeout_seconds.unwrap_or(DEFAULT_TRANSACTION_TIMEOUT_SECS) as u64);
+
+    transaction_manager
+        .begin(
+            &connection_id,
+            window.label(),
+            executor.as_ref(),
+            idle_timeout,
+        )
+        .await
+        .map_err(|e| serde_json::to_string(&e).unwrap_or(e.message))
+}
+
+/// トランザクションをコミット（開始したウィンドウからのみ）
+#[command]
+pub async fn commit_transaction(
+    connection_id: String,
+    window: WebviewWindow,
+    transaction_manager: State<'_, TransactionManager>,
+) -> Result<(), String> {
+    transaction_manager
+        .commit(&connection_id, window.label())
+        .await
+        .map_err(|e| serde_json::to_string(&e).unwrap_or(e.message))
+}
+
+/// トランザクションをロールバック（開始したウィンドウからのみ）
+#[command]
+pub async fn rollback_transaction(
+    connection_id: String,
+    window: WebviewWindow,
+    transaction_manager: State<'_, TransactionManager>,
+) -> Result<(), String> {
+    transaction_manager
+        .rollback(&connection_id, window.label())
+        .await
+        .map_err(|e| serde_json::to_string(&e).unwrap_or(e.message))
+}
+
+/// トランザクションの状態を取得
+#[command]
+pub async fn get_transaction_status(
+    connection_id: String,
+    transaction_manager: State<'_, TransactionManager>,
+) -> Result<Option<TransactionInfo>, String> {
+    Ok(transaction_manager.status(&connection_id))
+}
@@ -36,6 +36,7 @@ impl ConnectionInfoBuilder {
             username: username.into(),
             encrypted_password: None,
             ssl_config: None,
+            ssh_tunnel: None,
             options: None,
         }));
         self
@@ -2,7 +2,13 @@ use crate::connection::{
     service::ConnectionService, ConnectionConfig, ConnectionInfo, ConnectionTestService,
     FrontendConnection,
 };
-use tauri::State;
+use crate::services::database_inspector::TableMetadataCache;
+use crate::services::query_executor::{ConnectionPoolManager, QueryCursorManager};
+use crate::services::query_plan::TableRowCountCache;
+use tauri::{AppHandle, State};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+
+use super::ssh_tunnel;
 
 /// すべての接続情報を取得
 #[tauri::command]
@@ -68,10 +74,16 @@ pub async fn create_connection(
 }
 
 /// 接続情報を更新
+///
+/// 読み取り専用などの設定を反映するため、既存のプールとカーソルは閉じて次回の実行時に作り直す
 #[tauri::command]
 pub async fn update_connection(
     connection: FrontendConnection,
     service: State<'_, ConnectionService>,
+    pool_manager: State<'_, ConnectionPoolManager>,
+    cursor_manager: State<'_, QueryCursorManager>,
+    row_count_cache: State<'_, TableRowCountCache>,
+    table_cache: State<'_, TableMetadataCache>,
 ) -> Result<FrontendConnection, String> {
     let conn_info: ConnectionInfo = connection
         .try_into()
@@ -81,6 +93,10 @@ pub async fn update_connection(
         .update(conn_info)
         .await
         .map_err(|e| format!("Failed to update connection: {}", e))?;
+    cursor_manager.close_connection(&updated.id).await;
+    row_count_cache.invalidate(&updated.id);
+    table_cache.invalidate(&updated.id);
+    pool_manager.remove(&updated.id).await;
 
     Ok(FrontendConnection::from(updated))
 }
@@ -90,11 +106,20 @@ pub async fn update_connection(
 pub async fn delete_connection(
     id: String,
     service: State<'_, ConnectionService>,
+    pool_manager: State<'_, ConnectionPoolManager>,
+    cursor_manager: State<'_, QueryCursorManager>,
+    row_count_cache: State<'_, TableRowCountCache>,
+    table_cache: State<'_, TableMetadataCache>,
 ) -> Result<(), String> {
     service
         .delete(&id)
         .await
-        .map_err(|e| format!("Failed to delete connection: {}", e))
+        .map_err(|e| format!("Failed to delete connection: {}", e))?;
+    cursor_manager.close_connection(&id).await;
+    row_count_cache.invalidate(&id);
+    table_cache.invalidate(&id);
+    pool_manager.remove(&id).await;
+    Ok(())
 }
 
 /// 最終使用日時を更新
@@ -108,6 +133,39 @@ pub async fn mark_connection_used(
         .map_err(|e| format!("Failed to mark connection as used: {}", e))
 }
 
+/// known_hosts 未登録のため接続を拒否したSSHホスト鍵を信頼する
+///
+/// フィンガープリントをネイティブのダイアログで提示し、利用者が承認した場合のみ known_hosts に登録する。
+/// 確認待ちの鍵がない場合や拒否された場合は `false` を返す
+#[tauri::command]
+pub async fn trust_ssh_host_key(host: String, port: u16, app: AppHandle) -> Result<bool, String> {
+    let Some(fingerprint) = ssh_tunnel::pending_host_key_fingerprint(&host, port) else {
+        return Ok(false);
+    };
+
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    app.dialog()
+        .message(format!(
+            "SSHサーバー {}:{} のホスト鍵は known_hosts に登録されていません。\n\nフィンガープリント: {}\n\nこの鍵を信頼して登録しますか？",
+            host, port, fingerprint
+        ))
+        .title("SSHホスト鍵の確認")
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            "信頼して登録".to_string(),
+            "キャンセル".to_string(),
+        ))
+        .show(move |confirmed| {
+            let _ = sender.send(confirmed);
+        });
+    if !receiver.await.unwrap_or(false) {
+        return Ok(false);
+    }
+
+    ssh_tunnel::trust_pending_host_key(&host, port, &fingerprint).map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
 /// 接続をテスト（実際にDBに接続してみる）
 #[tauri::command]
 pub async fn test_connection(
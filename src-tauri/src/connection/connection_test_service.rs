@@ -1,4 +1,6 @@
-use crate::connection::{ConnectionConfig, ConnectionInfo, DatabaseType};
+use crate::connection::{
+    describe_mssql_tls_error, describe_tls_error, ConnectionConfig, ConnectionInfo, DatabaseType,
+};
 use anyhow::{Context, Result};
 use std::time::{Duration, Instant};
 use tokio::time::timeout;
@@ -21,19 +23,41 @@ pub struct ServerInfo {
     pub database_name: String,
     pub current_user: String,
     pub encoding: Option<String>,
+    /// バージョン文字列から判定したデータベース種別（MySQL互換サーバーのみ）
+    pub detected_type: Option<DatabaseType>,
+}
+
+/// MySQL互換サーバーのバージョン文字列から種別を判定する
+///
+/// MariaDBは `10.11.6-MariaDB-1:10.11.6+maria~ubu2204` のように名前を含む
+/// （古いクライアント向けに `5.5.5-` を前に付けることもある）
+pub fn detect_mysql_server_type(version: &str) -> DatabaseType {
+    if version.to_ascii_lowercase().contains("mariadb") {
+        DatabaseType::MariaDB
+    } else {
+        DatabaseType::MySQL
+    }
 }
 
 fn connection_target_description(connection: &ConnectionInfo) -> String {
     match &connection.connection {
         ConnectionConfig::Network(net) => {
-            let scheme = match connection.database_type {
-                DatabaseType::PostgreSQL => "postgresql",
-                DatabaseType::MySQL => "mysql",
-                DatabaseType::SQLite => "sqlite",
-            };
-            format!("{}://{}:{}/{}", scheme, net.host, net.port, net.database)
+            // 表示用の接続文字列（パスワードは含まない）
+            let target = connection
+                .build_connection_string()
+                .unwrap_or_else(|_| format!("{}:{}/{}", net.host, net.port, net.database));
+            match &net.ssh_tunnel {
+                Some(ssh) => format!(
+                    "{} (SSH経由: {}@{}:{})",
+                    target, ssh.username, ssh.host, ssh.port
+                ),
+                None => target,
+            }
         }
-        ConnectionConfig::File(file) => format!("sqlite:{}", file.file_path),
+        ConnectionConfig::File(file) => match connection.database_type {
+            DatabaseType::DuckDB => format!("duckdb:{}", file.file_path),
+            _ => format!("sqlite:{}", file.file_path),
+        },
     }
 }
 
@@ -45,6 +69,16 @@ fn error_chain_to_string(error: &anyhow::Error) -> String {
         .join("\nCaused by: ")
 }
 
+/// 接続エラーに説明を付ける（証明書エラーは原因を具体的に示す）
+fn connect_error(error: sqlx::Error, context: &str) -> anyhow::Error {
+    let description = describe_tls_error(&error);
+    let error = anyhow::Error::new(error).context(context.to_string());
+    match description {
+        Some(description) => error.context(description),
+        None => error,
+    }
+}
+
 pub struct ConnectionTestService;
 
 impl ConnectionTestService {
@@ -65,7 +99,16 @@ impl ConnectionTestService {
         match result {
             Ok(Ok(server_info)) => Ok(TestConnectionResult {
                 success: true,
-                message: format!("接続に成功しました ({} ms)", duration),
+                message: match server_info.detected_type {
+                    // MariaDBをMySQLとして登録している場合などは種別の変更を促す
+                    Some(detected) if detected != connection.database_type => format!(
+                        "接続に成功しました ({} ms)。サーバーは{}です。データベース種別を{}に変更してください",
+                        duration,
+                        detected.display_name(),
+                        detected.display_name()
+                    ),
+                    _ => format!("接続に成功しました ({} ms)", duration),
+                },
                 duration: Some(duration),
                 server_version: Some(server_info.version.clone()),
                 server_info: Some(server_info),
@@ -101,39 +144,34 @@ impl ConnectionTestService {
     async fn execute_test(connection: &ConnectionInfo) -> Result<ServerInfo> {
         match connection.database_type {
             DatabaseType::PostgreSQL => Self::test_postgresql(connection).await,
-            DatabaseType::MySQL => Self::test_mysql(connection).await,
+            DatabaseType::MySQL | DatabaseType::MariaDB => Self::test_mysql(connection).await,
             DatabaseType::SQLite => Self::test_sqlite(connection).await,
+            DatabaseType::DuckDB => Self::test_duckdb(connection).await,
+            DatabaseType::SqlServer => Self::test_mssql(connection).await,
         }
     }
 
     /// PostgreSQL接続テスト
     async fn test_postgresql(connection: &ConnectionInfo) -> Result<ServerInfo> {
-        use sqlx::postgres::{PgConnectOptions, PgPool};
+        use sqlx::postgres::PgPool;
 
         let network_config = match &connection.connection {
             ConnectionConfig::Network(config) => config,
             _ => return Err(anyhow::anyhow!("Invalid connection config for PostgreSQL")),
         };
 
-        let mut options = PgConnectOptions::new()
-            .host(&network_config.host)
-            .port(network_config.port)
-            .database(&network_config.database)
-            .username(&network_config.username);
+        let mut options =
+            connection.postgres_connect_options(network_config.encrypted_password.as_deref())?;
 
-        if let Some(password) = &network_config.encrypted_password {
-            options = options.password(password);
-        }
-
-        if let Some(ssl_config) = &network_config.ssl_config {
-            if ssl_config.enabled {
-                options = options.ssl_mode(sqlx::postgres::PgSslMode::Require);
-            }
+        // テスト中だけトンネルを保持する（プールを閉じた後に破棄される）
+        let tunnel = connection.open_ssh_tunnel().await?;
+        if let Some(tunnel) = &tunnel {
+            options = tunnel.apply_postgres(options);
         }
 
         let pool = PgPool::connect_with(options)
             .await
-            .context("Failed to connect to PostgreSQL")?;
+            .map_err(|e| connect_error(e, "Failed to connect to PostgreSQL"))?;
 
         // サーバー情報を取得
         let version_query = sqlx::query_scalar::<_, String>("SELECT version()")
@@ -162,37 +200,31 @@ impl ConnectionTestService {
             database_name,
             current_user,
             encoding,
+            detected_type: None,
         })
     }
 
-    /// MySQL接続テスト
+    /// MySQL接続テスト（MariaDBを含む）
     async fn test_mysql(connection: &ConnectionInfo) -> Result<ServerInfo> {
-        use sqlx::mysql::{MySqlConnectOptions, MySqlPool};
+        use sqlx::mysql::MySqlPool;
 
         let network_config = match &connection.connection {
             ConnectionConfig::Network(config) => config,
             _ => return Err(anyhow::anyhow!("Invalid connection config for MySQL")),
         };
 
-        let mut options = MySqlConnectOptions::new()
-            .host(&network_config.host)
-            .port(network_config.port)
-            .database(&network_config.database)
-            .username(&network_config.username);
+        let mut options =
+            connection.mysql_connect_options(network_config.encrypted_password.as_deref())?;
 
-        if let Some(password) = &network_config.encrypted_password {
-            options = options.password(password);
-        }
-
-        if let Some(ssl_config) = &network_config.ssl_config {
-            if ssl_config.enabled {
-                options = options.ssl_mode(sqlx::mysql::MySqlSslMode::Required);
-            }
+        // テスト中だけトンネルを保持する（プールを閉じた後に破棄される）
+        let tunnel = connection.open_ssh_tunnel().await?;
+        if let Some(tunnel) = &tunnel {
+            options = tunnel.apply_mysql(options);
         }
 
         let pool = MySqlPool::connect_with(options)
             .await
-            .context("Failed to connect to MySQL")?;
+            .map_err(|e| connect_error(e, "Failed to connect to MySQL"))?;
 
         // サーバー情報を取得
         let version_query = sqlx::query_scalar::<_, String>("SELECT VERSION()")
@@ -210,6 +242,7 @@ impl ConnectionTestService {
         pool.close().await;
 
         Ok(ServerInfo {
+            detected_type: Some(detect_mysql_server_type(&version_query)),
             version: version_query,
             database_name,
             current_user,
@@ -217,6 +250,60 @@ impl ConnectionTestService {
         })
     }
 
+    /// SQL Server接続テスト
+    async fn test_mssql(connection: &ConnectionInfo) -> Result<ServerInfo> {
+        use crate::database::mssql_executor::connect_mssql;
+
+        let network_config = match &connection.connection {
+            ConnectionConfig::Network(config) => config,
+            _ => return Err(anyhow::anyhow!("Invalid connection config for SQL Server")),
+        };
+
+        let mut config = connection.mssql_config(network_config.encrypted_password.as_deref())?;
+
+        // テスト中だけトンネルを保持する（接続を閉じた後に破棄される）
+        let tunnel = connection.open_ssh_tunnel().await?;
+        if let Some(tunnel) = &tunnel {
+            tunnel.apply_mssql(&mut config);
+        }
+
+        let mut client = connect_mssql(&config).await.map_err(|e| {
+            let description = describe_mssql_tls_error(&e);
+            let error = anyhow::Error::new(e).context("Failed to connect to SQL Server");
+            match description {
+                Some(description) => error.context(description),
+                None => error,
+            }
+        })?;
+
+        // サーバー情報を取得
+        let row = client
+            .simple_query("SELECT @@VERSION AS version, SUSER_SNAME() AS current_user_name, DB_NAME() AS database_name")
+            .await?
+            .into_row()
+            .await?
+            .context("Failed to get server info")?;
+        let text = |column: &str| row.get::<&str, _>(column).unwrap_or_default().to_string();
+
+        // @@VERSION は複数行のため、製品名とバージョンの1行目だけを使う
+        let version = text("version")
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+        let server_info = ServerInfo {
+            version,
+            database_name: text("database_name"),
+            current_user: text("current_user_name"),
+            encoding: None,
+            detected_type: None,
+        };
+
+        client.close().await?;
+        Ok(server_info)
+    }
+
     /// SQLite接続テスト
     async fn test_sqlite(connection: &ConnectionInfo) -> Result<ServerInfo> {
         use sqlx::sqlite::{SqliteConnectOptions, SqlitePool};
@@ -245,6 +332,35 @@ impl ConnectionTestService {
             database_name: database_path.clone(),
             current_user: "local".to_string(),
             encoding: Some("UTF-8".to_string()),
+            detected_type: None,
+        })
+    }
+
+    /// DuckDB接続テスト
+    async fn test_duckdb(connection: &ConnectionInfo) -> Result<ServerInfo> {
+        use crate::database::duckdb_executor::DuckDbDatabase;
+
+        let config = match connection.file_config() {
+            Some(config) => config,
+            None => return Err(anyhow::anyhow!("Invalid connection config for DuckDB")),
+        };
+        let database_name = config.file_path.clone();
+
+        // DuckDBのAPIはブロッキングのため別スレッドで実行する
+        let version = tokio::task::spawn_blocking(move || -> Result<String> {
+            let database =
+                DuckDbDatabase::acquire(&config).context("Failed to open DuckDB database")?;
+            let conn = database.connect()?;
+            Ok(conn.query_row("SELECT version()", [], |row| row.get(0))?)
+        })
+        .await??;
+
+        Ok(ServerInfo {
+            version: format!("DuckDB {}", version),
+            database_name,
+            current_user: "local".to_string(),
+            encoding: Some("UTF-8".to_string()),
+            detected_type: None,
         })
     }
 }
@@ -267,7 +383,9 @@ mod tests {
                 client_cert_path: None,
                 client_key_path: None,
                 verify_server_cert: true,
+                verify_hostname: true,
             }),
+            ssh_tunnel: None,
             options: None,
         });
 
@@ -302,6 +420,23 @@ mod tests {
         assert_eq!(server_info.current_user, "local");
     }
 
+    #[tokio::test]
+    async fn test_duckdb_connection_success() {
+        let config = ConnectionConfig::File(FileConfig {
+            file_path: ":memory:".to_string(),
+            readonly: false,
+        });
+        let connection =
+            ConnectionInfo::new("Test DuckDB".to_string(), DatabaseType::DuckDB, config);
+
+        let result = ConnectionTestService::test_connection(&connection, 30)
+            .await
+            .unwrap();
+
+        assert!(result.success, "{:?}", result.error_details);
+        assert!(result.server_info.unwrap().version.starts_with("DuckDB v"));
+    }
+
     #[tokio::test]
     async fn test_connection_timeout() {
         // 存在しないホストに接続してタイムアウトをテスト
@@ -312,6 +447,7 @@ mod tests {
             username: "test".to_string(),
             encrypted_password: None,
             ssl_config: None,
+            ssh_tunnel: None,
             options: None,
         });
 
@@ -342,6 +478,20 @@ mod tests {
         // 実際にDBがある環境では success == true になる
     }
 
+    #[test]
+    fn test_detect_mysql_server_type() {
+        assert_eq!(
+            detect_mysql_server_type("10.11.6-MariaDB-1:10.11.6+maria~ubu2204"),
+            DatabaseType::MariaDB
+        );
+        assert_eq!(
+            detect_mysql_server_type("5.5.5-10.6.16-MariaDB-log"),
+            DatabaseType::MariaDB
+        );
+        assert_eq!(detect_mysql_server_type("8.0.36"), DatabaseType::MySQL);
+        assert_eq!(detect_mysql_server_type("5.7.44-log"), DatabaseType::MySQL);
+    }
+
     #[test]
     fn test_connection_result_serialization() {
         let result = TestConnectionResult {
@@ -354,6 +504,7 @@ mod tests {
                 database_name: "testdb".to_string(),
                 current_user: "testuser".to_string(),
                 encoding: Some("UTF8".to_string()),
+                detected_type: None,
             }),
             error_details: None,
         };
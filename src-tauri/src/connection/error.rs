@@ -35,4 +35,7 @@ pub enum ConnectionError {
 
     #[error("暗号化エラー: {0}")]
     EncryptionError(String),
+
+    #[error("SSHトンネルエラー: {0}")]
+    SshTunnelError(String),
 }
@@ -1,5 +1,9 @@
-use super::{ConnectionConfig, ConnectionInfo, DatabaseType, EnvironmentType, NetworkConfig};
+use super::{
+    ConnectionConfig, ConnectionInfo, DatabaseType, EnvironmentType, NetworkConfig, SshAuthMethod,
+    SshTunnelConfig, SslVerification,
+};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// フロントエンド互換の接続情報（フラットな構造）
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +23,15 @@ pub struct FrontendConnection {
     #[serde(rename = "type")]
     pub db_type: String,
     pub ssl: bool,
+    /// SSL/TLSの検証方法（"require" / "verify-ca" / "verify-full"）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssl_mode: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssl_ca_cert_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssl_client_cert_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssl_client_key_path: Option<String>,
     pub ssh_tunnel: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ssh_host: Option<String>,
@@ -28,7 +41,23 @@ pub struct FrontendConnection {
     pub ssh_username: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ssh_password: Option<String>,
+    /// SSH認証方式（"password" / "privateKey"）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssh_auth_method: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssh_private_key_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssh_passphrase: Option<String>,
     pub timeout: u32,
+    /// 接続オプション（接続文字列パラメータ・セッション設定）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<HashMap<String, String>>,
+    /// 接続ごとに実行する初期化SQL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_connect_sql: Option<String>,
+    /// 読み取り専用接続
+    #[serde(default)]
+    pub read_only: bool,
     pub created_at: String,
     pub updated_at: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -37,41 +66,64 @@ pub struct FrontendConnection {
 
 impl From<ConnectionInfo> for FrontendConnection {
     fn from(conn: ConnectionInfo) -> Self {
-        let (host, port, database, username, password, ssl) = match conn.connection {
-            ConnectionConfig::Network(ref network) => (
-                network.host.clone(),
-                network.port,
-                network.database.clone(),
-                network.username.clone(),
-                network.encrypted_password.clone().unwrap_or_default(),
-                network
-                    .ssl_config
-                    .as_ref()
-                    .map(|s| s.enabled)
-                    .unwrap_or(false),
-            ),
-            ConnectionConfig::File(ref file) => (
-                String::new(),
-                0,
-                file.file_path.clone(),
-                String::new(),
-                String::new(),
-                false,
-            ),
-        };
+        let (host, port, database, username, password, ssl_config, ssh_tunnel, options) =
+            match conn.connection {
+                ConnectionConfig::Network(ref network) => (
+                    network.host.clone(),
+                    network.port,
+                    network.database.clone(),
+                    network.username.clone(),
+                    network.encrypted_password.clone().unwrap_or_default(),
+                    network.ssl_config.clone(),
+                    network.ssh_tunnel.clone(),
+                    network.options.clone(),
+                ),
+                ConnectionConfig::File(ref file) => (
+                    String::new(),
+                    0,
+                    file.file_path.clone(),
+                    String::new(),
+                    String::new(),
+                    None,
+                    None,
+                    None,
+                ),
+            };
+        let read_only = conn.is_read_only();
+        let ssl = ssl_config.as_ref().map(|s| s.enabled).unwrap_or(false);
+        let ssl_mode = ssl_config.as_ref().map(|s| {
+            match s.verification() {
+                SslVerification::Require => "require",
+                SslVerification::VerifyCa => "verify-ca",
+                SslVerification::VerifyFull => "verify-full",
+            }
+            .to_string()
+        });
+        let ssl_config = ssl_config.unwrap_or_default();
 
-        let environment = match conn.environment.environment_type {
-            EnvironmentType::Development => "development",
-            EnvironmentType::Testing => "test",
-            EnvironmentType::Staging => "staging",
-            EnvironmentType::Production => "production",
-        }
-        .to_string();
+        let (ssh_auth_method, ssh_private_key_path, ssh_password, ssh_passphrase) =
+            match ssh_tunnel.as_ref().map(|t| (&t.auth, t.secret.clone())) {
+                Some((SshAuthMethod::Password, secret)) => {
+                    (Some("password".to_string()), None, secret, None)
+                }
+                Some((SshAuthMethod::PrivateKey { key_path }, secret)) => (
+                    Some("privateKey".to_string()),
+                    Some(key_path.clone()),
+                    None,
+                    secret,
+                ),
+                None => (None, None, None, None),
+            };
+
+        let environment = conn.environment.environment_type.settings_key().to_string();
 
         let db_type = match conn.database_type {
             DatabaseType::PostgreSQL => "postgresql",
             DatabaseType::MySQL => "mysql",
+            DatabaseType::MariaDB => "mariadb",
             DatabaseType::SQLite => "sqlite",
+            DatabaseType::DuckDB => "duckdb",
+            DatabaseType::SqlServer => "sqlserver",
         }
         .to_string();
 
@@ -89,12 +141,22 @@ impl From<ConnectionInfo> for FrontendConnection {
             save_password: true, // デフォルト
             db_type,
             ssl,
-            ssh_tunnel: false, // TODO: SSH設定に対応
-            ssh_host: None,
-            ssh_port: None,
-            ssh_username: None,
-            ssh_password: None,
+            ssl_mode,
+            ssl_ca_cert_path: ssl_config.ca_cert_path,
+            ssl_client_cert_path: ssl_config.client_cert_path,
+            ssl_client_key_path: ssl_config.client_key_path,
+            ssh_tunnel: ssh_tunnel.is_some(),
+            ssh_host: ssh_tunnel.as_ref().map(|t| t.host.clone()),
+            ssh_port: ssh_tunnel.as_ref().map(|t| t.port),
+            ssh_username: ssh_tunnel.as_ref().map(|t| t.username.clone()),
+            ssh_password,
+            ssh_auth_method,
+            ssh_private_key_path,
+            ssh_passphrase,
             timeout: 30, // デフォルト
+            options,
+            read_only,
+            on_connect_sql: conn.on_connect_sql,
             created_at: conn.metadata.created_at.to_rfc3339(),
             updated_at: conn.metadata.updated_at.to_rfc3339(),
             last_used_at: conn.metadata.last_connected_at.map(|dt| dt.to_rfc3339()),
@@ -111,7 +173,10 @@ impl TryFrom<FrontendConnection> for ConnectionInfo {
         let database_type = match frontend.db_type.as_str() {
             "postgresql" => DatabaseType::PostgreSQL,
             "mysql" => DatabaseType::MySQL,
+            "mariadb" => DatabaseType::MariaDB,
             "sqlite" => DatabaseType::SQLite,
+            "duckdb" => DatabaseType::DuckDB,
+            "sqlserver" => DatabaseType::SqlServer,
             _ => return Err(format!("Invalid database type: {}", frontend.db_type)),
         };
 
@@ -140,14 +205,49 @@ impl TryFrom<FrontendConnection> for ConnectionInfo {
                     None
                 },
                 ssl_config: if frontend.ssl {
+                    let (verify_server_cert, verify_hostname) = match frontend.ssl_mode.as_deref() {
+                        Some("require") => (false, false),
+                        Some("verify-ca") => (true, false),
+                        Some("verify-full") | None => (true, true),
+                        Some(mode) => return Err(format!("Invalid SSL mode: {}", mode)),
+                    };
+                    let path = |p: Option<String>| p.filter(|p| !p.trim().is_empty());
                     Some(super::SslConfig {
                         enabled: true,
-                        ..Default::default()
+                        ca_cert_path: path(frontend.ssl_ca_cert_path),
+                        client_cert_path: path(frontend.ssl_client_cert_path),
+                        client_key_path: path(frontend.ssl_client_key_path),
+                        verify_server_cert,
+                        verify_hostname,
+                    })
+                } else {
+                    None
+                },
+                ssh_tunnel: if frontend.ssh_tunnel {
+                    let non_empty = |s: Option<String>| s.filter(|s| !s.is_empty());
+                    let (auth, secret) = match frontend.ssh_auth_method.as_deref() {
+                        Some("password") | None => {
+                            (SshAuthMethod::Password, non_empty(frontend.ssh_password))
+                        }
+                        Some("privateKey") => (
+                            SshAuthMethod::PrivateKey {
+                                key_path: frontend.ssh_private_key_path.unwrap_or_default(),
+                            },
+                            non_empty(frontend.ssh_passphrase),
+                        ),
+                        Some(method) => return Err(format!("Invalid SSH auth method: {}", method)),
+                    };
+                    Some(SshTunnelConfig {
+                        host: frontend.ssh_host.unwrap_or_default(),
+                        port: frontend.ssh_port.unwrap_or(22),
+                        username: frontend.ssh_username.unwrap_or_default(),
+                        auth,
+                        secret,
                     })
                 } else {
                     None
                 },
-                options: None,
+                options: frontend.options.filter(|options| !options.is_empty()),
             })
         };
 
@@ -185,6 +285,8 @@ impl TryFrom<FrontendConnection> for ConnectionInfo {
                 note: None,
                 is_favorite: false,
             },
+            on_connect_sql: frontend.on_connect_sql.filter(|sql| !sql.trim().is_empty()),
+            read_only: frontend.read_only,
         })
     }
 }
@@ -5,7 +5,9 @@ pub mod connection_test_service;
 mod error;
 mod frontend_types;
 pub mod service;
+mod ssh_tunnel;
 pub mod storage;
+mod tls;
 mod types;
 
 pub use builder::ConnectionInfoBuilder;
@@ -14,10 +16,13 @@ pub use connection_test_service::{ConnectionTestService, ServerInfo, TestConnect
 pub use error::ConnectionError;
 pub use frontend_types::FrontendConnection;
 pub use service::ConnectionService;
+pub use ssh_tunnel::SshTunnel;
 pub use storage::ConnectionStorage;
+pub use tls::{describe_mssql_tls_error, describe_tls_error, SslVerification};
 pub use types::{
     ConnectionConfig, ConnectionInfo, ConnectionMetadata, DatabaseType, EnvironmentConfig,
-    EnvironmentType, FileConfig, NetworkConfig, SslConfig, ThemeVariant,
+    EnvironmentType, FileConfig, NetworkConfig, SshAuthMethod, SshTunnelConfig, SslConfig,
+    ThemeVariant,
 };
 
 #[cfg(test)]
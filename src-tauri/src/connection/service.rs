@@ -81,6 +81,17 @@ impl ConnectionService {
             Self::apply_password(&mut connection, &pwd);
         }
 
+        if include_password_decrypted && Self::has_ssh_tunnel(&connection) {
+            let secret = self
+                .credential_storage
+                .get_ssh_passphrase(&connection.id)
+                .await
+                .map_err(|e| ConnectionError::StorageError(e.to_string()))?;
+            if let Some(secret) = secret {
+                Self::apply_ssh_secret(&mut connection, &secret);
+            }
+        }
+
         Ok(Some(connection))
     }
 
@@ -93,11 +104,12 @@ impl ConnectionService {
         connection.validate()?;
 
         let password = Self::take_password_field(&mut connection);
+        let ssh_secret = Self::take_ssh_secret(&mut connection);
         let saved = self.storage.create(connection)?;
 
-        if let Some(pwd) = password {
+        if password.is_some() || ssh_secret.is_some() {
             self.credential_storage
-                .save(&saved.id, Some(&pwd), None, None)
+                .save(&saved.id, password.as_deref(), ssh_secret.as_deref(), None)
                 .await
                 .map_err(|e| ConnectionError::StorageError(e.to_string()))?;
         }
@@ -114,11 +126,13 @@ impl ConnectionService {
         connection.validate()?;
 
         let password = Self::take_password_field(&mut connection);
+        let ssh_secret = Self::take_ssh_secret(&mut connection);
         let updated = self.storage.update(connection)?;
 
-        if let Some(pwd) = password {
+        // 入力されなかった認証情報は保存済みの値を引き継ぐ
+        if password.is_some() || ssh_secret.is_some() {
             self.credential_storage
-                .save(&updated.id, Some(&pwd), None, None)
+                .update(&updated.id, password.as_deref(), ssh_secret.as_deref())
                 .await
                 .map_err(|e| ConnectionError::StorageError(e.to_string()))?;
         }
@@ -160,11 +174,36 @@ impl ConnectionService {
         }
     }
 
-    /// 接続情報からパスワードを除去
-    fn strip_password_field(connection: &mut ConnectionInfo) {
+    /// 接続情報からSSHのパスワード・パスフレーズを取り除き、取り出した値を返す
+    fn take_ssh_secret(connection: &mut ConnectionInfo) -> Option<String> {
         if let ConnectionConfig::Network(ref mut network_config) = connection.connection {
-            network_config.encrypted_password = None;
+            if let Some(tunnel) = network_config.ssh_tunnel.as_mut() {
+                return tunnel.secret.take().filter(|s| !s.is_empty());
+            }
         }
+        None
+    }
+
+    /// 接続情報へSSHのパスワード・パスフレーズを適用
+    fn apply_ssh_secret(connection: &mut ConnectionInfo, secret: &str) {
+        if let ConnectionConfig::Network(ref mut network_config) = connection.connection {
+            if let Some(tunnel) = network_config.ssh_tunnel.as_mut() {
+                tunnel.secret = Some(secret.to_string());
+            }
+        }
+    }
+
+    fn has_ssh_tunnel(connection: &ConnectionInfo) -> bool {
+        matches!(
+            &connection.connection,
+            ConnectionConfig::Network(network_config) if network_config.ssh_tunnel.is_some()
+        )
+    }
+
+    /// 接続情報からパスワード・SSHの認証情報を除去
+    fn strip_password_field(connection: &mut ConnectionInfo) {
+        Self::take_password_field(connection);
+        Self::take_ssh_secret(connection);
     }
 
     /// 旧形式の暗号化パスワードを復号化（MasterKeyManager利用）
@@ -192,7 +231,9 @@ impl ConnectionService {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::connection::{ConnectionConfig, DatabaseType, NetworkConfig};
+    use crate::connection::{
+        ConnectionConfig, DatabaseType, NetworkConfig, SshAuthMethod, SshTunnelConfig,
+    };
     use crate::crypto::{CredentialStorage, SecurityConfigStorage, SecurityProviderManager};
     use crate::storage::FileStorage;
     use std::sync::Arc;
@@ -233,6 +274,7 @@ mod tests {
             username: "testuser".to_string(),
             encrypted_password: None,
             ssl_config: None,
+            ssh_tunnel: None,
             options: None,
         });
 
@@ -309,6 +351,49 @@ mod tests {
         assert_eq!(found.name, "Updated DB");
     }
 
+    #[tokio::test]
+    async fn test_ssh_secret_stored_separately() {
+        let (service, _temp) = create_test_service().await;
+
+        let mut connection = create_test_connection("Tunnel DB");
+        if let ConnectionConfig::Network(ref mut network) = connection.connection {
+            network.encrypted_password = Some("db_password".to_string());
+            network.ssh_tunnel = Some(SshTunnelConfig {
+                host: "bastion.example.com".to_string(),
+                port: 22,
+                username: "deploy".to_string(),
+                auth: SshAuthMethod::Password,
+                secret: Some("ssh_password".to_string()),
+            });
+        }
+        let created = service.create(connection.clone()).await.unwrap();
+
+        // 一覧では認証情報を返さない
+        let listed = service.get_all().unwrap();
+        let ConnectionConfig::Network(ref network) = listed[0].connection else {
+            panic!("network config expected");
+        };
+        assert!(network.encrypted_password.is_none());
+        assert!(network.ssh_tunnel.as_ref().unwrap().secret.is_none());
+
+        // SSHのパスワードだけを変更しても、DBのパスワードは保持される
+        if let ConnectionConfig::Network(ref mut network) = connection.connection {
+            network.encrypted_password = None;
+            network.ssh_tunnel.as_mut().unwrap().secret = Some("new_ssh_password".to_string());
+        }
+        service.update(connection).await.unwrap();
+
+        let found = service.get_by_id(&created.id, true).await.unwrap().unwrap();
+        let ConnectionConfig::Network(network) = found.connection else {
+            panic!("network config expected");
+        };
+        assert_eq!(network.encrypted_password.as_deref(), Some("db_password"));
+        assert_eq!(
+            network.ssh_tunnel.unwrap().secret.as_deref(),
+            Some("new_ssh_password")
+        );
+    }
+
     #[tokio::test]
     async fn test_delete_connection() {
         let (service, _temp) = create_test_service().await;
@@ -0,0 +1,363 @@
+use russh::client;
+use russh::keys::{self, HashAlg, PrivateKeyWithHashAlg, PublicKey};
+use sha2::{Digest, Sha256};
+use sqlx::mysql::MySqlConnectOptions;
+use sqlx::postgres::PgConnectOptions;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex as StdMutex, OnceLock, Weak};
+use std::time::Duration;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+use super::error::ConnectionError;
+use super::types::{SshAuthMethod, SshTunnelConfig};
+
+/// SSHサーバーへの接続・認証のタイムアウト
+const SSH_CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// SSHセッションのキープアライブ間隔
+const SSH_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// 開いているトンネル（SSHサーバー・認証情報・転送先の組み合わせごと）
+///
+/// プール・インスペクター・接続テストが同じ転送先に接続する場合は1つのSSHセッションを共有する。
+/// 利用者がいなくなったトンネルは `Weak` が切れて次回作り直される。
+/// 同じトンネルを二重に開かないよう、検索から登録までロックを保持する
+static TUNNELS: OnceLock<Mutex<HashMap<String, Weak<SshTunnel>>>> = OnceLock::new();
+
+fn tunnels() -> &'static Mutex<HashMap<String, Weak<SshTunnel>>> {
+    TUNNELS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// known_hosts 未登録のため接続を拒否したホスト鍵（「ホスト:ポート」ごと）
+///
+/// 利用者がフィンガープリントを確認して信頼した場合に known_hosts へ登録する
+static PENDING_HOST_KEYS: OnceLock<StdMutex<HashMap<String, PublicKey>>> = OnceLock::new();
+
+fn pending_host_keys() -> &'static StdMutex<HashMap<String, PublicKey>> {
+    PENDING_HOST_KEYS.get_or_init(|| StdMutex::new(HashMap::new()))
+}
+
+fn host_key_id(host: &str, port: u16) -> String {
+    format!("{}:{}", host, port)
+}
+
+/// 接続を拒否した未登録のホスト鍵のフィンガープリント（SHA256）
+pub fn pending_host_key_fingerprint(host: &str, port: u16) -> Option<String> {
+    pending_host_keys()
+        .lock()
+        .unwrap()
+        .get(&host_key_id(host, port))
+        .map(|key| key.fingerprint(HashAlg::Sha256).to_string())
+}
+
+/// 未登録のホスト鍵を known_hosts に登録する
+///
+/// `fingerprint` は利用者に提示したもので、その間に別の鍵で接続が試みられていた場合は登録しない
+pub fn trust_pending_host_key(
+    host: &str,
+    port: u16,
+    fingerprint: &str,
+) -> Result<(), ConnectionError> {
+    let mut pending = pending_host_keys().lock().unwrap();
+    let id = host_key_id(host, port);
+    let key = match pending.get(&id) {
+        Some(key) if key.fingerprint(HashAlg::Sha256).to_string() == fingerprint => key,
+        _ => {
+            return Err(ConnectionError::SshTunnelError(format!(
+                "確認待ちのホスト鍵がありません: {}",
+                id
+            )))
+        }
+    };
+    keys::known_hosts::learn_known_hosts(host, port, key).map_err(|e| {
+        ConnectionError::SshTunnelError(format!("known_hosts に登録できません: {}", e))
+    })?;
+    pending.remove(&id);
+    Ok(())
+}
+
+/// SSHトンネル（ローカルポートフォワード）
+///
+/// `127.0.0.1` の空きポートで待ち受け、受け付けた接続をSSHサーバー経由で転送先へ中継する。
+/// 破棄すると待ち受けを停止し、SSHセッションを閉じる
+pub struct SshTunnel {
+    local_addr: SocketAddr,
+    session: Arc<client::Handle<TunnelHandler>>,
+    forwarder: JoinHandle<()>,
+}
+
+impl SshTunnel {
+    /// トンネルを取得（同じSSHサーバー・認証情報・転送先で開いているものがあれば共有する）
+    pub async fn acquire(
+        config: &SshTunnelConfig,
+        target_host: &str,
+        target_port: u16,
+    ) -> Result<Arc<Self>, ConnectionError> {
+        // 認証情報が異なる接続とは共有しない（キーに平文を残さないようハッシュにする）
+        let secret_hash = Sha256::digest(config.secret.as_deref().unwrap_or_default());
+        let key = format!(
+            "{}@{}:{}/{:?}/{:x}->{}:{}",
+            config.username,
+            config.host,
+            config.port,
+            config.auth,
+            secret_hash,
+            target_host,
+            target_port
+        );
+
+        let mut map = tunnels().lock().await;
+        if let Some(tunnel) = map.get(&key).and_then(Weak::upgrade) {
+            if !tunnel.is_closed() {
+                return Ok(tunnel);
+            }
+        }
+
+        let tunnel = Arc::new(Self::open(config, target_host, target_port).await?);
+        map.retain(|_, t| t.strong_count() > 0);
+        map.insert(key, Arc::downgrade(&tunnel));
+        Ok(tunnel)
+    }
+
+    /// SSHサーバーに接続してトンネルを開く
+    pub async fn open(
+        config: &SshTunnelConfig,
+        target_host: &str,
+        target_port: u16,
+    ) -> Result<Self, ConnectionError> {
+        let ssh_config = Arc::new(client::Config {
+            keepalive_interval: Some(SSH_KEEPALIVE_INTERVAL),
+            ..Default::default()
+        });
+        let rejection = Arc::new(StdMutex::new(None));
+        let handler = TunnelHandler {
+            host: config.host.clone(),
+            port: config.port,
+            rejection: Arc::clone(&rejection),
+        };
+
+        let session = tokio::time::timeout(SSH_CONNECT_TIMEOUT, async {
+            let mut session =
+                client::connect(ssh_config, (config.host.as_str(), config.port), handler)
+                    .await
+                    .map_err(|e| match rejection.lock().unwrap().take() {
+                        Some(message) => ConnectionError::SshTunnelError(message),
+                        None => describe_ssh_error(&e, config),
+                    })?;
+            authenticate(&mut session, config).await?;
+            Ok::<_, ConnectionError>(session)
+        })
+        .await
+        .map_err(|_| {
+            ConnectionError::SshTunnelError(format!(
+                "SSHサーバーへの接続がタイムアウトしました: {}:{}",
+                config.host, config.port
+            ))
+        })??;
+
+        let listener = match TcpListener::bind(("127.0.0.1", 0)).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                let _ = session
+                    .disconnect(russh::Disconnect::ByApplication, "", "")
+                    .await;
+                return Err(ConnectionError::SshTunnelError(format!(
+                    "ローカルポートを開けません: {}",
+                    e
+                )));
+            }
+        };
+        let local_addr = listener
+            .local_addr()
+            .map_err(|e| ConnectionError::SshTunnelError(e.to_string()))?;
+
+        let session = Arc::new(session);
+        let forwarder = tokio::spawn(forward(
+            listener,
+            Arc::clone(&session),
+            target_host.to_string(),
+            target_port,
+        ));
+
+        Ok(Self {
+            local_addr,
+            session,
+            forwarder,
+        })
+    }
+
+    /// ローカルの待ち受けアドレス
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// SSHセッションが切断されているか
+    pub fn is_closed(&self) -> bool {
+        self.session.is_closed() || self.forwarder.is_finished()
+    }
+
+    /// PostgreSQLの接続先をトンネルの待ち受けアドレスに置き換える
+    pub fn apply_postgres(&self, options: PgConnectOptions) -> PgConnectOptions {
+        options
+            .host(&self.local_addr.ip().to_string())
+            .port(self.local_addr.port())
+    }
+
+    /// MySQLの接続先をトンネルの待ち受けアドレスに置き換える
+    pub fn apply_mysql(&self, options: MySqlConnectOptions) -> MySqlConnectOptions {
+        options
+            .host(&self.local_addr.ip().to_string())
+            .port(self.local_addr.port())
+    }
+
+    /// SQL Serverの接続先をトンネルの待ち受けアドレスに置き換える
+    pub fn apply_mssql(&self, config: &mut tiberius::Config) {
+        config.host(self.local_addr.ip());
+        config.port(self.local_addr.port());
+    }
+}
+
+impl Drop for SshTunnel {
+    fn drop(&mut self) {
+        self.forwarder.abort();
+    }
+}
+
+/// SSHクライアントのイベントハンドラ
+struct TunnelHandler {
+    host: String,
+    port: u16,
+    /// ホスト鍵を拒否した理由（接続エラーのメッセージに使う）
+    rejection: Arc<StdMutex<Option<String>>>,
+}
+
+impl client::Handler for TunnelHandler {
+    type Error = russh::Error;
+
+    /// ホスト鍵の確認
+    ///
+    /// `known_hosts` に登録済みの鍵と一致する場合のみ受け入れる。
+    /// 未登録のホストは鍵を確認待ちとして保持し、利用者が信頼するまで接続しない
+    async fn check_server_key(
+        &mut self,
+        server_public_key: &PublicKey,
+    ) -> Result<bool, Self::Error> {
+        let fingerprint = server_public_key.fingerprint(HashAlg::Sha256);
+        let message = match keys::check_known_hosts(&self.host, self.port, server_public_key) {
+            Ok(true) => return Ok(true),
+            Ok(false) => {
+                pending_host_keys().lock().unwrap().insert(
+                    host_key_id(&self.host, self.port),
+                    server_public_key.clone(),
+                );
+                format!(
+                    "SSHサーバーのホスト鍵が known_hosts に登録されていません（{}:{}、{}）。接続テストでフィンガープリントを確認して登録してください",
+                    self.host, self.port, fingerprint
+                )
+            }
+            Err(keys::Error::KeyChanged { line }) => format!(
+                "SSHサーバーのホスト鍵が known_hosts の登録内容（{}行目）と一致しません: {}:{}（{}）",
+                line, self.host, self.port, fingerprint
+            ),
+            Err(e) => format!("known_hosts を確認できません: {}", e),
+        };
+        *self.rejection.lock().unwrap() = Some(message);
+        Ok(false)
+    }
+}
+
+/// 設定された方式でSSH認証を行う
+async fn authenticate(
+    session: &mut client::Handle<TunnelHandler>,
+    config: &SshTunnelConfig,
+) -> Result<(), ConnectionError> {
+    let secret = config.secret.as_deref().filter(|s| !s.is_empty());
+    let result = match &config.auth {
+        SshAuthMethod::Password => {
+            session
+                .authenticate_password(&config.username, secret.unwrap_or_default())
+                .await
+        }
+        SshAuthMethod::PrivateKey { key_path } => {
+            let key = keys::load_secret_key(key_path, secret).map_err(|e| {
+                ConnectionError::SshTunnelError(format!(
+                    "SSH秘密鍵を読み込めません（{}）: {}",
+                    key_path, e
+                ))
+            })?;
+            // RSA鍵はサーバーが対応する署名アルゴリズムを使う
+            let hash_alg = session
+                .best_supported_rsa_hash()
+                .await
+                .map_err(|e| describe_ssh_error(&e, config))?
+                .flatten();
+            session
+                .authenticate_publickey(
+                    &config.username,
+                    PrivateKeyWithHashAlg::new(Arc::new(key), hash_alg),
+                )
+                .await
+        }
+    }
+    .map_err(|e| describe_ssh_error(&e, config))?;
+
+    if !result.success() {
+        return Err(ConnectionError::SshTunnelError(format!(
+            "SSH認証に失敗しました: {}@{}",
+            config.username, config.host
+        )));
+    }
+    Ok(())
+}
+
+/// 受け付けた接続をSSHチャネル経由で転送先へ中継する
+async fn forward(
+    listener: TcpListener,
+    session: Arc<client::Handle<TunnelHandler>>,
+    target_host: String,
+    target_port: u16,
+) {
+    while let Ok((socket, peer)) = listener.accept().await {
+        if session.is_closed() {
+            break;
+        }
+        let channel = match session
+            .channel_open_direct_tcpip(
+                target_host.as_str(),
+                u32::from(target_port),
+                peer.ip().to_string(),
+                u32::from(peer.port()),
+            )
+            .await
+        {
+            Ok(channel) => channel,
+            // 転送先に接続できない場合はソケットを閉じて接続側にエラーを返す
+            Err(_) => continue,
+        };
+        tokio::spawn(relay(socket, channel));
+    }
+}
+
+async fn relay(mut socket: TcpStream, channel: russh::Channel<client::Msg>) {
+    let mut stream = channel.into_stream();
+    let _ = tokio::io::copy_bidirectional(&mut socket, &mut stream).await;
+}
+
+/// SSHのエラーを利用者向けのメッセージにする
+fn describe_ssh_error(err: &russh::Error, config: &SshTunnelConfig) -> ConnectionError {
+    let message = match err {
+        russh::Error::UnknownKey => format!(
+            "SSHサーバーのホスト鍵が known_hosts の登録内容と一致しません: {}:{}",
+            config.host, config.port
+        ),
+        russh::Error::IO(e) => format!(
+            "SSHサーバーに接続できません（{}:{}）: {}",
+            config.host, config.port, e
+        ),
+        _ => format!("SSH接続に失敗しました: {}", err),
+    };
+    ConnectionError::SshTunnelError(message)
+}
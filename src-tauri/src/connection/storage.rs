@@ -239,6 +239,7 @@ mod tests {
             username: "testuser".to_string(),
             encrypted_password: None,
             ssl_config: None,
+            ssh_tunnel: None,
             options: None,
         });
 
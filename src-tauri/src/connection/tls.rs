@@ -0,0 +1,230 @@
+use sqlx::mysql::{MySqlConnectOptions, MySqlSslMode};
+use sqlx::postgres::{PgConnectOptions, PgSslMode};
+use std::path::Path;
+use tiberius::EncryptionLevel;
+
+use super::error::ConnectionError;
+use super::types::SslConfig;
+
+/// SSL/TLSの検証方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SslVerification {
+    /// 暗号化のみ（証明書を検証しない）
+    Require,
+    /// サーバー証明書の発行元を検証する
+    VerifyCa,
+    /// 発行元に加えてホスト名も検証する
+    VerifyFull,
+}
+
+impl SslConfig {
+    /// 検証方法
+    pub fn verification(&self) -> SslVerification {
+        match (self.verify_server_cert, self.verify_hostname) {
+            (false, _) => SslVerification::Require,
+            (true, false) => SslVerification::VerifyCa,
+            (true, true) => SslVerification::VerifyFull,
+        }
+    }
+
+    /// 証明書ファイルの指定を検証する
+    pub fn validate(&self) -> Result<(), ConnectionError> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        let files = [
+            ("CA証明書", &self.ca_cert_path),
+            ("クライアント証明書", &self.client_cert_path),
+            ("クライアント秘密鍵", &self.client_key_path),
+        ];
+        for (label, path) in files {
+            if let Some(path) = path.as_deref().filter(|p| !p.trim().is_empty()) {
+                if !Path::new(path).is_file() {
+                    return Err(ConnectionError::InvalidConfig(format!(
+                        "{}のファイルが見つかりません: {}",
+                        label, path
+                    )));
+                }
+            }
+        }
+
+        // クライアント証明書認証には証明書と秘密鍵の両方が必要
+        if non_empty(&self.client_cert_path).is_some() != non_empty(&self.client_key_path).is_some()
+        {
+            return Err(ConnectionError::InvalidConfig(
+                "クライアント証明書と秘密鍵は両方指定してください".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// PostgreSQLの接続オプションに適用する
+    pub fn apply_postgres(
+        &self,
+        options: PgConnectOptions,
+    ) -> Result<PgConnectOptions, ConnectionError> {
+        if !self.enabled {
+            return Ok(options);
+        }
+        self.validate()?;
+
+        let mut options = options.ssl_mode(match self.verification() {
+            SslVerification::Require => PgSslMode::Require,
+            SslVerification::VerifyCa => PgSslMode::VerifyCa,
+            SslVerification::VerifyFull => PgSslMode::VerifyFull,
+        });
+        if let Some(path) = non_empty(&self.ca_cert_path) {
+            options = options.ssl_root_cert(path);
+        }
+        if let Some(path) = non_empty(&self.client_cert_path) {
+            options = options.ssl_client_cert(path);
+        }
+        if let Some(path) = non_empty(&self.client_key_path) {
+            options = options.ssl_client_key(path);
+        }
+        Ok(options)
+    }
+
+    /// MySQLの接続オプションに適用する
+    pub fn apply_mysql(
+        &self,
+        options: MySqlConnectOptions,
+    ) -> Result<MySqlConnectOptions, ConnectionError> {
+        if !self.enabled {
+            return Ok(options);
+        }
+        self.validate()?;
+
+        let mut options = options.ssl_mode(match self.verification() {
+            SslVerification::Require => MySqlSslMode::Required,
+            SslVerification::VerifyCa => MySqlSslMode::VerifyCa,
+            SslVerification::VerifyFull => MySqlSslMode::VerifyIdentity,
+        });
+        if let Some(path) = non_empty(&self.ca_cert_path) {
+            options = options.ssl_ca(path);
+        }
+        if let Some(path) = non_empty(&self.client_cert_path) {
+            options = options.ssl_client_cert(path);
+        }
+        if let Some(path) = non_empty(&self.client_key_path) {
+            options = options.ssl_client_key(path);
+        }
+        Ok(options)
+    }
+
+    /// SQL Serverの接続設定に適用する
+    ///
+    /// rustlsではホスト名を検証せずに発行元だけを検証できず、クライアント証明書にも対応しないため、
+    /// それらの指定はエラーにする
+    pub fn apply_mssql(&self, config: &mut tiberius::Config) -> Result<(), ConnectionError> {
+        if !self.enabled {
+            config.encryption(EncryptionLevel::Off);
+            config.trust_cert();
+            return Ok(());
+        }
+        self.validate()?;
+
+        if non_empty(&self.client_cert_path).is_some() {
+            return Err(ConnectionError::InvalidConfig(
+                "SQL Serverではクライアント証明書認証を使用できません".to_string(),
+            ));
+        }
+
+        config.encryption(EncryptionLevel::Required);
+        match self.verification() {
+            SslVerification::Require => config.trust_cert(),
+            SslVerification::VerifyCa => {
+                return Err(ConnectionError::InvalidConfig(
+                    "SQL Serverではホスト名を検証せずにサーバー証明書を検証できません".to_string(),
+                ))
+            }
+            SslVerification::VerifyFull => {
+                if let Some(path) = non_empty(&self.ca_cert_path) {
+                    config.trust_cert_ca(path);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn non_empty(path: &Option<String>) -> Option<&str> {
+    path.as_deref().filter(|p| !p.trim().is_empty())
+}
+
+/// TLSに起因する接続エラーを説明する（TLS以外のエラーなら `None`）
+///
+/// ハンドシェイク中の証明書エラーは `Io` として返るため、原因の連鎖のメッセージで判定する
+pub fn describe_tls_error(error: &sqlx::Error) -> Option<String> {
+    let source: &(dyn std::error::Error + 'static) = match error {
+        sqlx::Error::Tls(source) | sqlx::Error::Configuration(source) => source.as_ref(),
+        sqlx::Error::Io(source) => source,
+        _ => return None,
+    };
+    let mut messages = vec![source.to_string()];
+    let mut current = source.source();
+    while let Some(cause) = current {
+        messages.push(cause.to_string());
+        current = cause.source();
+    }
+    let detail = messages.join(": ");
+
+    let reason = match tls_failure_reason(&detail) {
+        Some(reason) => reason,
+        None if matches!(error, sqlx::Error::Tls(_)) => "SSL/TLS接続に失敗しました",
+        None => return None,
+    };
+    Some(format!("{} ({})", reason, detail))
+}
+
+/// SQL Serverの接続エラーのうちTLSに起因するものを説明する（TLS以外のエラーなら `None`）
+///
+/// ハンドシェイク中の証明書エラーは `Io` として返るため、メッセージで判定する
+pub fn describe_mssql_tls_error(error: &tiberius::error::Error) -> Option<String> {
+    let (detail, is_tls) = match error {
+        tiberius::error::Error::Tls(message) => (message.as_str(), true),
+        tiberius::error::Error::Io { message, .. } => (message.as_str(), false),
+        _ => return None,
+    };
+
+    let reason = if detail.contains("CA certificate") {
+        "CA証明書を読み込めません。PEM/CRT/DER形式の証明書を1つだけ含むファイルを指定してください"
+    } else {
+        match tls_failure_reason(detail) {
+            Some(reason) => reason,
+            None if is_tls => "SSL/TLS接続に失敗しました",
+            None => return None,
+        }
+    };
+    Some(format!("{} ({})", reason, detail))
+}
+
+/// TLSライブラリのエラーメッセージから失敗の理由を判定する
+fn tls_failure_reason(detail: &str) -> Option<&'static str> {
+    let reason = if detail.contains("UnknownIssuer") {
+        "サーバー証明書の発行元を信頼できません。CA証明書を指定してください"
+    } else if detail.contains("NotValidForName") {
+        "サーバー証明書のホスト名が接続先と一致しません"
+    } else if detail.contains("Expired") {
+        "サーバー証明書の有効期限が切れています"
+    } else if detail.contains("NotValidYet") {
+        "サーバー証明書の有効期間が開始していません"
+    } else if detail.contains("Revoked") {
+        "サーバー証明書は失効しています"
+    } else if detail.contains("peer certificate") {
+        "サーバー証明書を検証できません"
+    } else if detail.contains("does not support TLS") {
+        "サーバーがSSL/TLS接続に対応していません"
+    } else if detail.contains("Invalid certificate") {
+        "CA証明書を読み込めません"
+    } else if detail.contains("pem") || detail.contains("PEM") {
+        "クライアント証明書または秘密鍵を読み込めません"
+    } else if detail.contains("fatal alert") {
+        "サーバーがTLSハンドシェイクを拒否しました。クライアント証明書を確認してください"
+    } else {
+        return None;
+    };
+    Some(reason)
+}
@@ -1,9 +1,14 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sqlx::mysql::MySqlConnectOptions;
+use sqlx::postgres::PgConnectOptions;
 use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
 use uuid::Uuid;
 
 use super::error::ConnectionError;
+use super::ssh_tunnel::SshTunnel;
 
 /// データベース接続情報
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +30,14 @@ pub struct ConnectionInfo {
 
     /// メタデータ
     pub metadata: ConnectionMetadata,
+
+    /// 接続ごとに実行する初期化SQL（プールが新しい接続を開くたびに実行）
+    #[serde(default)]
+    pub on_connect_sql: Option<String>,
+
+    /// 読み取り専用接続（セッションを読み取り専用で開き、データ変更の実行を拒否する）
+    #[serde(default)]
+    pub read_only: bool,
 }
 
 impl ConnectionInfo {
@@ -37,19 +50,151 @@ impl ConnectionInfo {
             connection,
             environment: EnvironmentConfig::default(),
             metadata: ConnectionMetadata::new(),
+            on_connect_sql: None,
+            read_only: false,
         }
     }
 
+    /// 読み取り専用接続か（ファイル接続の読み取り専用モードを含む）
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+            || matches!(&self.connection, ConnectionConfig::File(config) if config.readonly)
+    }
+
     /// 接続文字列を生成
-    pub fn build_connection_string(
+    ///
+    /// ネットワーク接続ではパスワードを含まない表示用の文字列を返す。
+    /// 実際の接続には `postgres_connect_options` / `mysql_connect_options` を使う
+    pub fn build_connection_string(&self) -> Result<String, ConnectionError> {
+        match &self.connection {
+            ConnectionConfig::Network(config) => config.to_display_string(&self.database_type),
+            ConnectionConfig::File(_) => self
+                .file_config()
+                .ok_or(ConnectionError::InvalidFilePath)?
+                .to_connection_string(),
+        }
+    }
+
+    /// ファイル接続の設定（読み取り専用接続ではファイルも読み取り専用で開く）
+    pub fn file_config(&self) -> Option<FileConfig> {
+        match &self.connection {
+            ConnectionConfig::File(config) => Some(FileConfig {
+                file_path: config.file_path.clone(),
+                readonly: self.is_read_only(),
+            }),
+            ConnectionConfig::Network(_) => None,
+        }
+    }
+
+    /// PostgreSQLの接続オプションを生成（SSL/TLS設定を含む）
+    pub fn postgres_connect_options(
+        &self,
+        password: Option<&str>,
+    ) -> Result<PgConnectOptions, ConnectionError> {
+        let options = self
+            .network_config(DatabaseType::PostgreSQL)?
+            .postgres_connect_options(password)?;
+        match self.ssl_config() {
+            Some(ssl) => ssl.apply_postgres(options),
+            None => Ok(options),
+        }
+    }
+
+    /// MySQLの接続オプションを生成（SSL/TLS設定を含む。MariaDBも同じドライバで接続する）
+    pub fn mysql_connect_options(
         &self,
         password: Option<&str>,
-    ) -> Result<String, ConnectionError> {
+    ) -> Result<MySqlConnectOptions, ConnectionError> {
+        let options = self
+            .network_config(DatabaseType::MySQL)?
+            .mysql_connect_options(password)?;
+        match self.ssl_config() {
+            Some(ssl) => ssl.apply_mysql(options),
+            None => Ok(options),
+        }
+    }
+
+    /// SQL Serverの接続設定を生成（SSL/TLS設定を含む）
+    pub fn mssql_config(
+        &self,
+        password: Option<&str>,
+    ) -> Result<tiberius::Config, ConnectionError> {
+        let mut config = self
+            .network_config(DatabaseType::SqlServer)?
+            .mssql_config(password)?;
+        match self.ssl_config() {
+            Some(ssl) => ssl.apply_mssql(&mut config)?,
+            None => SslConfig::default().apply_mssql(&mut config)?,
+        }
+        Ok(config)
+    }
+
+    /// 指定したデータベース種別のネットワーク接続設定
+    fn network_config(
+        &self,
+        database_type: DatabaseType,
+    ) -> Result<&NetworkConfig, ConnectionError> {
+        match &self.connection {
+            ConnectionConfig::Network(config)
+                if self.database_type == database_type
+                    || (database_type == DatabaseType::MySQL
+                        && self.database_type.is_mysql_compatible()) =>
+            {
+                Ok(config)
+            }
+            _ => Err(ConnectionError::InvalidConfig(format!(
+                "{}のネットワーク接続設定ではありません",
+                database_type.display_name()
+            ))),
+        }
+    }
+
+    /// SSHトンネルが設定されていれば開く（同じ転送先のトンネルは共有される）
+    pub async fn open_ssh_tunnel(&self) -> Result<Option<Arc<SshTunnel>>, ConnectionError> {
+        match &self.connection {
+            ConnectionConfig::Network(config) => match &config.ssh_tunnel {
+                Some(tunnel) => SshTunnel::acquire(tunnel, &config.host, config.port)
+                    .await
+                    .map(Some),
+                None => Ok(None),
+            },
+            ConnectionConfig::File(_) => Ok(None),
+        }
+    }
+
+    fn ssl_config(&self) -> Option<&SslConfig> {
         match &self.connection {
-            ConnectionConfig::Network(config) => {
-                config.to_connection_string(&self.database_type, password)
+            ConnectionConfig::Network(config) => config.ssl_config.as_ref(),
+            ConnectionConfig::File(_) => None,
+        }
+    }
+
+    /// 新しい接続を開いたときに実行するSQL
+    ///
+    /// 読み取り専用の設定、接続文字列で指定できないセッション変数の設定と `on_connect_sql` を連結する。
+    /// 何もなければ `None`
+    pub fn session_init_sql(&self) -> Result<Option<String>, ConnectionError> {
+        let mut statements = Vec::new();
+        if self.is_read_only() {
+            statements.extend(
+                self.database_type
+                    .read_only_session_sql()
+                    .map(str::to_string),
+            );
+        }
+        if let ConnectionConfig::Network(config) = &self.connection {
+            statements.extend(config.session_statements(&self.database_type)?);
+        }
+        if let Some(sql) = self.on_connect_sql.as_deref().map(str::trim) {
+            if !sql.is_empty() {
+                statements.push(sql.to_string());
             }
-            ConnectionConfig::File(config) => config.to_connection_string(),
+        }
+
+        if statements.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(statements.join(";\n")))
         }
     }
 
@@ -74,10 +219,11 @@ pub enum DatabaseType {
     PostgreSQL,
     MySQL,
     SQLite,
+    MariaDB,
+    DuckDB,
+    SqlServer,
     // 将来の拡張
-    // MariaDB,
     // Oracle,
-    // SqlServer,
 }
 
 impl DatabaseType {
@@ -85,8 +231,9 @@ impl DatabaseType {
     pub fn default_port(&self) -> Option<u16> {
         match self {
             Self::PostgreSQL => Some(5432),
-            Self::MySQL => Some(3306),
-            Self::SQLite => None,
+            Self::MySQL | Self::MariaDB => Some(3306),
+            Self::SqlServer => Some(1433),
+            Self::SQLite | Self::DuckDB => None,
         }
     }
 
@@ -96,23 +243,47 @@ impl DatabaseType {
             Self::PostgreSQL => "PostgreSQL",
             Self::MySQL => "MySQL",
             Self::SQLite => "SQLite",
+            Self::MariaDB => "MariaDB",
+            Self::DuckDB => "DuckDB",
+            Self::SqlServer => "SQL Server",
         }
     }
 
+    /// MySQL互換のデータベースか（MySQLのプロトコル・ドライバで接続する）
+    pub fn is_mysql_compatible(&self) -> bool {
+        matches!(self, Self::MySQL | Self::MariaDB)
+    }
+
     /// ファイルベースのデータベースかチェック
     pub fn is_file_based(&self) -> bool {
-        matches!(self, Self::SQLite)
+        matches!(self, Self::SQLite | Self::DuckDB)
+    }
+
+    /// セッションを読み取り専用にするSQL
+    ///
+    /// SQLiteはファイルを `mode=ro` で開いたうえで `query_only` も有効にする。
+    /// DuckDBは `READ_ONLY` のアクセスモードで開くため不要。
+    /// SQL Serverにはセッション単位の読み取り専用モードがないため `None`
+    /// （いずれも `ReadOnlyExecutor` が読み取り専用でない文を拒否する）
+    pub fn read_only_session_sql(&self) -> Option<&'static str> {
+        match self {
+            Self::PostgreSQL => Some("SET SESSION CHARACTERISTICS AS TRANSACTION READ ONLY"),
+            Self::MySQL | Self::MariaDB => Some("SET SESSION TRANSACTION READ ONLY"),
+            Self::SQLite => Some("PRAGMA query_only = ON"),
+            Self::DuckDB | Self::SqlServer => None,
+        }
     }
 }
 
 /// 接続設定（ネットワーク/ファイル）
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "lowercase")]
+#[allow(clippy::large_enum_variant)] // 接続ごとに1つしか持たないため、ネットワーク設定はBox化しない
 pub enum ConnectionConfig {
     /// ネットワーク経由の接続（PostgreSQL、MySQL等）
     Network(NetworkConfig),
 
-    /// ファイルベースの接続（SQLite等）
+    /// ファイルベースの接続（SQLite、DuckDB）
     File(FileConfig),
 }
 
@@ -131,7 +302,7 @@ impl ConnectionConfig {
 /// ネットワーク接続設定
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkConfig {
-    /// ホスト名またはIPアドレス
+    /// ホスト名またはIPアドレス（`/` から始まる場合はUnixドメインソケットのパス）
     pub host: String,
 
     /// ポート番号
@@ -149,6 +320,10 @@ pub struct NetworkConfig {
     /// SSL/TLS設定
     pub ssl_config: Option<SslConfig>,
 
+    /// SSHトンネル設定（指定時はSSHサーバー経由でデータベースに接続する）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssh_tunnel: Option<SshTunnelConfig>,
+
     /// 追加のオプション（接続文字列パラメータ）
     pub options: Option<HashMap<String, String>>,
 }
@@ -171,29 +346,30 @@ impl NetworkConfig {
             return Err(ConnectionError::InvalidUsername);
         }
 
+        if let Some(tunnel) = &self.ssh_tunnel {
+            if self.uses_unix_socket() {
+                return Err(ConnectionError::InvalidConfig(
+                    "Unixドメインソケットで接続する場合はSSHトンネルを使用できません".to_string(),
+                ));
+            }
+            tunnel.validate()?;
+        }
+
         Ok(())
     }
 
-    fn to_connection_string(
-        &self,
-        db_type: &DatabaseType,
-        password: Option<&str>,
-    ) -> Result<String, ConnectionError> {
-        let pwd = password.unwrap_or("");
-
-        let base = match db_type {
-            DatabaseType::PostgreSQL => {
-                format!(
-                    "postgresql://{}:{}@{}:{}/{}",
-                    self.username, pwd, self.host, self.port, self.database
-                )
-            }
-            DatabaseType::MySQL => {
-                format!(
-                    "mysql://{}:{}@{}:{}/{}",
-                    self.username, pwd, self.host, self.port, self.database
-                )
-            }
+    /// Unixドメインソケットで接続するか（ホストに `/` から始まるパスを指定した場合）
+    pub fn uses_unix_socket(&self) -> bool {
+        self.host.starts_with('/')
+    }
+
+    /// パスワードを含まない表示用の接続文字列
+    fn to_display_string(&self, db_type: &DatabaseType) -> Result<String, ConnectionError> {
+        let scheme = match db_type {
+            DatabaseType::PostgreSQL => "postgresql",
+            DatabaseType::MySQL => "mysql",
+            DatabaseType::MariaDB => "mariadb",
+            DatabaseType::SqlServer => "sqlserver",
             _ => {
                 return Err(ConnectionError::InvalidConfig(
                     "ネットワーク接続はこのデータベース種別には使用できません".to_string(),
@@ -201,18 +377,230 @@ impl NetworkConfig {
             }
         };
 
-        // SSL設定やオプションを追加
-        let mut connection_string = base;
-        if let Some(ssl) = &self.ssl_config {
-            if ssl.enabled {
-                connection_string.push_str("?sslmode=require");
+        let user = urlencoding::encode(&self.username);
+        let database = urlencoding::encode(&self.database);
+        if self.uses_unix_socket() {
+            Ok(format!(
+                "{}://{}@{}/{}",
+                scheme,
+                user,
+                urlencoding::encode(&self.host),
+                database
+            ))
+        } else {
+            Ok(format!(
+                "{}://{}@{}:{}/{}",
+                scheme, user, self.host, self.port, database
+            ))
+        }
+    }
+
+    /// PostgreSQLの接続オプション（SSL/TLS設定を除く）
+    ///
+    /// 接続パラメータ以外のオプションはサーバーの実行時パラメータ（`-c キー=値`）として渡す
+    fn postgres_connect_options(
+        &self,
+        password: Option<&str>,
+    ) -> Result<PgConnectOptions, ConnectionError> {
+        let mut options = PgConnectOptions::new_without_pgpass()
+            .port(self.port)
+            .username(&self.username)
+            .database(&self.database);
+        options = if self.uses_unix_socket() {
+            options.socket(&self.host)
+        } else {
+            options.host(&self.host)
+        };
+        if let Some(password) = password {
+            options = options.password(password);
+        }
+
+        for (key, value) in self.sorted_options() {
+            options = match key {
+                "sslmode" => options.ssl_mode(parse_option(key, value)?),
+                "sslrootcert" => options.ssl_root_cert(value),
+                "sslcert" => options.ssl_client_cert(value),
+                "sslkey" => options.ssl_client_key(value),
+                "statement-cache-capacity" => {
+                    options.statement_cache_capacity(parse_option(key, value)?)
+                }
+                "application_name" => options.application_name(value),
+                "options" => options.options(parse_postgres_options(value)?),
+                _ => options.options([(key, escape_postgres_option(value))]),
+            };
+        }
+
+        Ok(options)
+    }
+
+    /// MySQLの接続オプション（SSL/TLS設定を除く）
+    ///
+    /// 接続パラメータ以外のオプションはセッション変数として `session_statements` で設定する
+    fn mysql_connect_options(
+        &self,
+        password: Option<&str>,
+    ) -> Result<MySqlConnectOptions, ConnectionError> {
+        let mut options = MySqlConnectOptions::new()
+            .username(&self.username)
+            .database(&self.database);
+        options = if self.uses_unix_socket() {
+            options.socket(&self.host)
+        } else {
+            options.host(&self.host).port(self.port)
+        };
+        if let Some(password) = password {
+            options = options.password(password);
+        }
+
+        for (key, value) in self.sorted_options() {
+            options = match key {
+                "sslmode" => options.ssl_mode(parse_option(key, value)?),
+                "ssl-ca" => options.ssl_ca(value),
+                "ssl-cert" => options.ssl_client_cert(value),
+                "ssl-key" => options.ssl_client_key(value),
+                "charset" => options.charset(value),
+                "collation" => options.collation(value),
+                "statement-cache-capacity" => {
+                    options.statement_cache_capacity(parse_option(key, value)?)
+                }
+                "socket" => options.socket(value),
+                "timezone" => options.timezone(Some(value.to_string())),
+                _ => options,
+            };
+        }
+
+        Ok(options)
+    }
+
+    /// SQL Serverの接続設定（SSL/TLS設定を除く）
+    ///
+    /// SQL Serverはポート指定で接続するため、Unixドメインソケットには対応しない
+    fn mssql_config(&self, password: Option<&str>) -> Result<tiberius::Config, ConnectionError> {
+        if self.uses_unix_socket() {
+            return Err(ConnectionError::InvalidConfig(
+                "SQL ServerはUnixドメインソケットで接続できません".to_string(),
+            ));
+        }
+
+        let mut config = tiberius::Config::new();
+        config.host(&self.host);
+        config.port(self.port);
+        config.database(&self.database);
+        config.authentication(tiberius::AuthMethod::sql_server(
+            &self.username,
+            password.unwrap_or_default(),
+        ));
+
+        // 名前付きインスタンスはSQL Browserを使わず、ポート番号で接続する
+        for (key, value) in self.sorted_options() {
+            if key == "application_name" {
+                config.application_name(value);
             }
         }
 
-        Ok(connection_string)
+        Ok(config)
+    }
+
+    /// 接続文字列で指定できないオプションを設定する文（MySQLのセッション変数）
+    fn session_statements(&self, db_type: &DatabaseType) -> Result<Vec<String>, ConnectionError> {
+        if !db_type.is_mysql_compatible() {
+            return Ok(Vec::new());
+        }
+
+        self.sorted_options()
+            .into_iter()
+            .filter(|(key, _)| !MYSQL_CONNECT_PARAMETERS.contains(key))
+            .map(|(key, value)| {
+                if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                    return Err(ConnectionError::InvalidConfig(format!(
+                        "不正なセッション変数名です: {}",
+                        key
+                    )));
+                }
+                // 数値はそのまま、それ以外は文字列リテラルとして設定する（inf・NaN は数値にしない）
+                let value = if value.parse::<f64>().is_ok_and(f64::is_finite) {
+                    value.to_string()
+                } else {
+                    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "''"))
+                };
+                Ok(format!("SET SESSION {} = {}", key, value))
+            })
+            .collect()
+    }
+
+    fn sorted_options(&self) -> Vec<(&str, &str)> {
+        let mut options: Vec<(&str, &str)> = self
+            .options
+            .iter()
+            .flatten()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect();
+        options.sort();
+        options
     }
 }
 
+/// MySQLで接続オプションとして設定するキー（それ以外はセッション変数）
+const MYSQL_CONNECT_PARAMETERS: &[&str] = &[
+    "sslmode",
+    "ssl-ca",
+    "ssl-cert",
+    "ssl-key",
+    "charset",
+    "collation",
+    "statement-cache-capacity",
+    "socket",
+    "timezone",
+];
+
+/// 接続オプションの値を解析する
+fn parse_option<T>(key: &str, value: &str) -> Result<T, ConnectionError>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    value.parse().map_err(|e: T::Err| {
+        ConnectionError::InvalidConfig(format!("オプション {} の値が不正です: {}", key, e))
+    })
+}
+
+/// PostgreSQLの `options`（`-c キー=値` の並び）をキーと値の組に分解する
+fn parse_postgres_options(value: &str) -> Result<Vec<(String, String)>, ConnectionError> {
+    let invalid = || {
+        ConnectionError::InvalidConfig(format!(
+            "options は `-c キー=値` の形式で指定してください: {}",
+            value
+        ))
+    };
+
+    let mut pairs = Vec::new();
+    let mut tokens = value.split_whitespace();
+    while let Some(token) = tokens.next() {
+        let setting = match token {
+            "-c" => tokens.next().ok_or_else(invalid)?,
+            _ => token
+                .strip_prefix("-c")
+                .or_else(|| token.strip_prefix("--"))
+                .ok_or_else(invalid)?,
+        };
+        let (key, value) = setting.split_once('=').ok_or_else(invalid)?;
+        pairs.push((key.to_string(), value.to_string()));
+    }
+    Ok(pairs)
+}
+
+/// 実行時パラメータの値をエスケープする（空白とバックスラッシュの前に `\` を付ける）
+fn escape_postgres_option(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c == '\\' || c.is_whitespace() {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
 /// SSL/TLS設定
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SslConfig {
@@ -230,6 +618,14 @@ pub struct SslConfig {
 
     /// サーバー証明書の検証を行うか
     pub verify_server_cert: bool,
+
+    /// サーバー証明書のホスト名を検証するか（`verify_server_cert` が有効な場合のみ）
+    #[serde(default = "default_verify_hostname")]
+    pub verify_hostname: bool,
+}
+
+fn default_verify_hostname() -> bool {
+    true
 }
 
 impl Default for SslConfig {
@@ -240,10 +636,75 @@ impl Default for SslConfig {
             client_cert_path: None,
             client_key_path: None,
             verify_server_cert: true,
+            verify_hostname: true,
         }
     }
 }
 
+/// SSHトンネル設定
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SshTunnelConfig {
+    /// SSHサーバーのホスト名またはIPアドレス
+    pub host: String,
+
+    /// SSHサーバーのポート番号
+    pub port: u16,
+
+    /// SSHユーザー名
+    pub username: String,
+
+    /// 認証方式
+    pub auth: SshAuthMethod,
+
+    /// パスワードまたは秘密鍵のパスフレーズ（平文）
+    ///
+    /// 保存時は `CredentialStorage` に移し、接続情報ファイルには書き込まない
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+}
+
+/// SSH認証方式
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SshAuthMethod {
+    /// パスワード認証
+    Password,
+    /// 公開鍵認証（パスフレーズは `secret` に指定）
+    PrivateKey { key_path: String },
+}
+
+impl SshTunnelConfig {
+    fn validate(&self) -> Result<(), ConnectionError> {
+        if self.host.trim().is_empty() {
+            return Err(ConnectionError::InvalidConfig(
+                "SSHホスト名が指定されていません".to_string(),
+            ));
+        }
+
+        if self.port == 0 {
+            return Err(ConnectionError::InvalidConfig(
+                "SSHポート番号が無効です".to_string(),
+            ));
+        }
+
+        if self.username.trim().is_empty() {
+            return Err(ConnectionError::InvalidConfig(
+                "SSHユーザー名が指定されていません".to_string(),
+            ));
+        }
+
+        if let SshAuthMethod::PrivateKey { key_path } = &self.auth {
+            if key_path.trim().is_empty() {
+                return Err(ConnectionError::InvalidConfig(
+                    "SSH秘密鍵のパスが指定されていません".to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
 /// ファイルベース接続設定
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileConfig {
@@ -368,6 +829,16 @@ impl EnvironmentType {
         }
     }
 
+    /// 安全設定のキー（フロントエンドの環境名と同じ）
+    pub fn settings_key(&self) -> &'static str {
+        match self {
+            Self::Development => "development",
+            Self::Testing => "test",
+            Self::Staging => "staging",
+            Self::Production => "production",
+        }
+    }
+
     /// 本番環境かチェック
     pub fn is_production(&self) -> bool {
         matches!(self, Self::Production)
@@ -19,6 +19,14 @@ impl KeychainEntry {
             username: "master-encryption-key".to_string(),
         }
     }
+
+    /// 監査ログの署名鍵用のエントリを作成
+    pub fn audit_log_key() -> Self {
+        Self {
+            service: "sql-query-builder".to_string(),
+            username: "audit-log-key".to_string(),
+        }
+    }
 }
 
 /// マスターキーを安全に保持する構造体
@@ -26,6 +26,10 @@ pub struct CredentialEntry {
     /// 暗号化されたパスワード
     pub encrypted_password: Option<String>,
 
+    /// SSHのパスワードまたはキーのパスフレーズ（復号化済み。永続化時はスキップ）
+    #[serde(skip)]
+    pub ssh_passphrase: Option<String>,
+
     /// SSHキーのパスフレーズ（暗号化済み）
     pub encrypted_ssh_passphrase: Option<String>,
 
@@ -133,6 +137,7 @@ impl CredentialStorage {
             connection_id.to_string(),
             CredentialEntry {
                 password: None,
+                ssh_passphrase: None,
                 ..entry
             },
         );
@@ -150,6 +155,15 @@ impl CredentialStorage {
         Ok(entry.and_then(|e| e.password))
     }
 
+    /// SSHのパスワードまたはキーのパスフレーズのみを取得
+    pub async fn get_ssh_passphrase(
+        &self,
+        connection_id: &str,
+    ) -> Result<Option<String>, CredentialError> {
+        let entry = self.get(connection_id).await?;
+        Ok(entry.and_then(|e| e.ssh_passphrase))
+    }
+
     /// 認証情報を保存
     pub async fn save(
         &self,
@@ -190,6 +204,7 @@ impl CredentialStorage {
         let entry = CredentialEntry {
             password: None,
             encrypted_password,
+            ssh_passphrase: None,
             encrypted_ssh_passphrase,
             encrypted_ssl_password,
             updated_at: Utc::now(),
@@ -205,6 +220,52 @@ impl CredentialStorage {
         Ok(())
     }
 
+    /// 指定した項目だけを更新（`None` の項目は保存済みの値を保持する）
+    pub async fn update(
+        &self,
+        connection_id: &str,
+        password: Option<&str>,
+        ssh_passphrase: Option<&str>,
+    ) -> Result<(), CredentialError> {
+        let mut collection = self.load_collection().await?;
+
+        let key = self.get_or_init_key().await?;
+        let encrypt = |value: &str| -> Result<Option<String>, CredentialError> {
+            if value.is_empty() {
+                return Ok(None);
+            }
+            encrypt_string(value, &key)
+                .map(Some)
+                .map_err(|e| CredentialError::EncryptionError(e.to_string()))
+        };
+
+        let mut entry = collection
+            .credentials
+            .remove(connection_id)
+            .unwrap_or_else(|| CredentialEntry {
+                password: None,
+                encrypted_password: None,
+                ssh_passphrase: None,
+                encrypted_ssh_passphrase: None,
+                encrypted_ssl_password: None,
+                updated_at: Utc::now(),
+            });
+        if let Some(value) = password {
+            entry.encrypted_password = encrypt(value)?;
+        }
+        if let Some(value) = ssh_passphrase {
+            entry.encrypted_ssh_passphrase = encrypt(value)?;
+        }
+        entry.updated_at = Utc::now();
+
+        collection
+            .credentials
+            .insert(connection_id.to_string(), entry);
+        collection.updated_at = Utc::now();
+
+        self.save_collection(&collection).await
+    }
+
     /// 認証情報を削除
     pub async fn delete(&self, connection_id: &str) -> Result<(), CredentialError> {
         let mut collection = self.load_collection().await?;
@@ -321,17 +382,19 @@ impl CredentialStorage {
     ) -> Result<CredentialEntry, CredentialError> {
         let key = self.get_or_init_key().await?;
 
-        let password = match &entry.encrypted_password {
-            Some(encrypted) => Some(
-                decrypt_string(encrypted, &key)
-                    .map_err(|e| CredentialError::DecryptionError(e.to_string()))?,
-            ),
-            None => None,
+        let decrypt = |value: &Option<String>| match value {
+            Some(encrypted) => decrypt_string(encrypted, &key)
+                .map(Some)
+                .map_err(|e| CredentialError::DecryptionError(e.to_string())),
+            None => Ok(None),
         };
+        let password = decrypt(&entry.encrypted_password)?;
+        let ssh_passphrase = decrypt(&entry.encrypted_ssh_passphrase)?;
 
         Ok(CredentialEntry {
             password,
             encrypted_password: entry.encrypted_password.clone(),
+            ssh_passphrase,
             encrypted_ssh_passphrase: entry.encrypted_ssh_passphrase.clone(),
             encrypted_ssl_password: entry.encrypted_ssl_password.clone(),
             updated_at: entry.updated_at,
@@ -431,6 +494,42 @@ mod tests {
         assert_eq!(password, Some("new_password".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_update_keeps_other_fields() {
+        let (storage, _temp) = create_test_storage().await;
+
+        storage
+            .save("conn-1", Some("db_password"), Some("ssh_secret"), None)
+            .await
+            .unwrap();
+        storage
+            .update("conn-1", Some("new_db_password"), None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            storage.get_password("conn-1").await.unwrap(),
+            Some("new_db_password".to_string())
+        );
+        assert_eq!(
+            storage.get_ssh_passphrase("conn-1").await.unwrap(),
+            Some("ssh_secret".to_string())
+        );
+
+        storage
+            .update("conn-1", None, Some("new_ssh_secret"))
+            .await
+            .unwrap();
+        assert_eq!(
+            storage.get_password("conn-1").await.unwrap(),
+            Some("new_db_password".to_string())
+        );
+        assert_eq!(
+            storage.get_ssh_passphrase("conn-1").await.unwrap(),
+            Some("new_ssh_secret".to_string())
+        );
+    }
+
     #[tokio::test]
     async fn test_multiple_credentials() {
         let (storage, _temp) = create_test_storage().await;
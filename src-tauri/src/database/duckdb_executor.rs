@@ -0,0 +1,678 @@
+use crate::connection::{ConnectionInfo, FileConfig};
+use crate::database::duckdb_types::{convert_duckdb_value, duckdb_param, duckdb_type_name};
+use crate::models::mutation_result::MutationResult;
+use crate::models::query_result::{
+    QueryError, QueryErrorCode, QueryParam, QueryResult, QueryResultColumn, QueryResultRow,
+    ScriptErrorMode, StatementOutcome, StatementResult,
+};
+use crate::services::query_executor::{
+    current_query_id, leading_keyword, locate_statement_error,
+    multiple_statements_with_params_error, returns_rows, CursorEvent, PoolStatus, QueryCursor,
+    QueryExecutor, RunningQueries, RunningQueryGuard, TransactionSession,
+};
+use crate::sql::statement_splitter::{StatementRange, StatementSplitter};
+use async_trait::async_trait;
+use duckdb::{params_from_iter, AccessMode, Config, Connection, InterruptHandle, Row, Statement};
+use sqlparser::ast::Statement as SqlStatement;
+use sqlparser::dialect::DuckDbDialect;
+use sqlparser::parser::Parser;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex as StdMutex, OnceLock, Weak};
+use std::time::{Duration, Instant};
+
+/// 開いているデータベース（ファイルパスと読み取り専用の組み合わせごと）
+///
+/// 同じファイルを複数のインスタンスで開くとロックが競合するため、Executor・インスペクター・接続テストで共有する。
+/// 利用者がいなくなったデータベースは `Weak` が切れて閉じられる
+static DATABASES: OnceLock<StdMutex<HashMap<String, Weak<DuckDbDatabase>>>> = OnceLock::new();
+
+fn databases() -> &'static StdMutex<HashMap<String, Weak<DuckDbDatabase>>> {
+    DATABASES.get_or_init(|| StdMutex::new(HashMap::new()))
+}
+
+/// 分析用に直接開けるデータファイル（拡張子, 読み込み関数）
+///
+/// メモリ上のデータベースを作り、ファイル名のビューとして参照できるようにする
+pub const DUCKDB_DATA_FILE_READERS: &[(&str, &str)] = &[
+    ("parquet", "read_parquet"),
+    ("csv", "read_csv_auto"),
+    ("tsv", "read_csv_auto"),
+];
+
+/// DuckDBのデータベース
+///
+/// DuckDBの接続はスレッド間で共有できないため、文を実行するたびにルート接続を複製して使う
+pub struct DuckDbDatabase {
+    root: StdMutex<Connection>,
+}
+
+impl DuckDbDatabase {
+    /// データベースを取得（同じファイルを開いていれば共有する）
+    pub fn acquire(config: &FileConfig) -> Result<Arc<Self>, duckdb::Error> {
+        let key = format!("{}?readonly={}", config.file_path, config.readonly);
+
+        let mut map = databases().lock().unwrap();
+        if let Some(database) = map.get(&key).and_then(Weak::upgrade) {
+            return Ok(database);
+        }
+
+        let database = Arc::new(Self {
+            root: StdMutex::new(Self::open(config)?),
+        });
+        map.retain(|_, d| d.strong_count() > 0);
+        map.insert(key, Arc::downgrade(&database));
+        Ok(database)
+    }
+
+    fn open(config: &FileConfig) -> Result<Connection, duckdb::Error> {
+        let path = Path::new(&config.file_path);
+        if let Some(reader) = data_file_reader(path) {
+            let view = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_else(|| "data".to_string());
+            let conn = Connection::open_in_memory()?;
+            conn.execute_batch(&format!(
+                "CREATE VIEW \"{}\" AS SELECT * FROM {}('{}')",
+                view.replace('"', "\"\""),
+                reader,
+                config.file_path.replace('\'', "''")
+            ))?;
+            return Ok(conn);
+        }
+
+        let access_mode = if config.readonly {
+            AccessMode::ReadOnly
+        } else {
+            AccessMode::Automatic
+        };
+        Connection::open_with_flags(path, Config::default().access_mode(access_mode)?)
+    }
+
+    /// 新しい接続を開く
+    pub fn connect(&self) -> Result<Connection, duckdb::Error> {
+        self.root
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .try_clone()
+    }
+}
+
+/// 拡張子から分析用のデータファイルの読み込み関数を取得
+pub fn data_file_reader(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_string_lossy().to_ascii_lowercase();
+    DUCKDB_DATA_FILE_READERS
+        .iter()
+        .find(|(ext, _)| *ext == extension)
+        .map(|(_, reader)| *reader)
+}
+
+/// DuckDBのエラーを変換する
+///
+/// DuckDBのエラーコードは種別を区別しないため、メッセージの先頭（`Catalog Error:` など）で判定する
+pub fn map_error(err: duckdb::Error) -> QueryError {
+    let message = match &err {
+        duckdb::Error::DuckDBFailure(_, Some(message)) => message.clone(),
+        _ => err.to_string(),
+    };
+    let (kind, detail) = message
+        .split_once(" Error: ")
+        .unwrap_or(("", message.as_str()));
+
+    let code = match kind {
+        "Parser" => QueryErrorCode::SyntaxError,
+        "Catalog" if detail.starts_with("Table") => QueryErrorCode::TableNotFound,
+        "Catalog" if detail.starts_with("Schema") => QueryErrorCode::SchemaNotFound,
+        "Binder" if detail.contains("column") => QueryErrorCode::ColumnNotFound,
+        "Constraint" if detail.contains("NOT NULL") => QueryErrorCode::NotNullViolation,
+        "Constraint" if detail.contains("foreign key") => QueryErrorCode::ForeignKeyViolation,
+        "Constraint" if detail.contains("CHECK") => QueryErrorCode::CheckViolation,
+        "Constraint" => QueryErrorCode::UniqueViolation,
+        "Conversion" | "Mismatch Type" => QueryErrorCode::InvalidDataType,
+        "Out of Range" => QueryErrorCode::DataTruncation,
+        "INTERRUPT" => QueryErrorCode::QueryCancelled,
+        "Permission" => QueryErrorCode::PermissionDenied,
+        "IO" => QueryErrorCode::ConnectionFailed,
+        "TransactionContext" => QueryErrorCode::TransactionError,
+        _ if detail.contains("read-only") => QueryErrorCode::PermissionDenied,
+        _ => QueryErrorCode::Unknown,
+    };
+
+    let native_code = (!kind.is_empty()).then(|| kind.to_string());
+
+    QueryError {
+        code,
+        message,
+        details: None,
+        native_code,
+    }
+}
+
+/// 結果セットを返す文か（DuckDB固有の構文を含む）
+fn returns_result_set(statement: &str) -> bool {
+    returns_rows(statement)
+        || matches!(
+            leading_keyword(statement).as_str(),
+            "FROM" | "SUMMARIZE" | "PIVOT" | "UNPIVOT" | "CALL"
+        )
+        || has_returning_clause(statement)
+}
+
+/// RETURNING句を持つデータ変更文か
+fn has_returning_clause(statement: &str) -> bool {
+    let Ok(parsed) = Parser::parse_sql(&DuckDbDialect {}, statement) else {
+        return false;
+    };
+    parsed.iter().any(|statement| match statement {
+        SqlStatement::Insert(insert) => insert.returning.is_some(),
+        SqlStatement::Update { returning, .. } => returning.is_some(),
+        SqlStatement::Delete(delete) => delete.returning.is_some(),
+        _ => false,
+    })
+}
+
+/// 実行した文の列情報
+fn build_columns(stmt: &Statement) -> Vec<QueryResultColumn> {
+    (0..stmt.column_count())
+        .map(|i| QueryResultColumn {
+            name: stmt.column_name(i).cloned().unwrap_or_default(),
+            data_type: duckdb_type_name(&stmt.column_logical_type(i)),
+            nullable: true,
+        })
+        .collect()
+}
+
+fn convert_row(row: &Row, columns: &[QueryResultColumn]) -> Result<QueryResultRow, duckdb::Error> {
+    let values = columns
+        .iter()
+        .enumerate()
+        .map(|(i, col)| Ok(convert_duckdb_value(row.get(i)?, &col.data_type)))
+        .collect::<Result<_, duckdb::Error>>()?;
+
+    Ok(QueryResultRow { values })
+}
+
+/// 1文を実行し、結果と影響行数を返す（実行時間は呼び出し側で設定）
+fn run_statement(
+    conn: &Connection,
+    sql: &str,
+    params: &[QueryParam],
+) -> Result<(QueryResult, u64), duckdb::Error> {
+    let mut stmt = conn.prepare(sql)?;
+    let affected_rows = stmt.execute(params_from_iter(params.iter().map(duckdb_param)))?;
+
+    let mut result = QueryResult {
+        columns: vec![],
+        rows: vec![],
+        row_count: 0,
+        execution_time_ms: 0,
+        warnings: vec![],
+    };
+    // 行を返さない文（データ変更・DDL）は影響行数のみ返す
+    if returns_result_set(sql) {
+        result.columns = build_columns(&stmt);
+        let mut rows = stmt.raw_query();
+        while let Some(row) = rows.next()? {
+            result.rows.push(convert_row(row, &result.columns)?);
+        }
+        result.row_count = result.rows.len();
+    }
+
+    Ok((result, affected_rows as u64))
+}
+
+/// 複数文を順次実行し、最後の文の結果を返す（パラメータは単一文のみ）
+///
+/// 失敗した場合は失敗した文の範囲を返す
+fn run_statements(
+    conn: &Connection,
+    sql: &str,
+    statements: &[StatementRange],
+    params: &[QueryParam],
+) -> Result<QueryResult, (StatementRange, duckdb::Error)> {
+    let mut last_result = QueryResult {
+        columns: vec![],
+        rows: vec![],
+        row_count: 0,
+        execution_time_ms: 0,
+        warnings: vec![],
+    };
+    for range in statements {
+        let (result, _) = run_statement(conn, range.text(sql), params).map_err(|e| (*range, e))?;
+        last_result = result;
+    }
+    Ok(last_result)
+}
+
+/// 完了前に破棄された場合（タイムアウト・カーソルの破棄）に実行中の文を中断する
+struct InterruptOnDrop(Option<Arc<InterruptHandle>>);
+
+impl InterruptOnDrop {
+    fn disarm(mut self) {
+        self.0 = None;
+    }
+}
+
+impl Drop for InterruptOnDrop {
+    fn drop(&mut self) {
+        if let Some(handle) = self.0.take() {
+            handle.interrupt();
+        }
+    }
+}
+
+/// 使用中の接続数を数えるガード
+struct ActiveConnection(Arc<AtomicU32>);
+
+impl ActiveConnection {
+    fn new(active: &Arc<AtomicU32>) -> Self {
+        active.fetch_add(1, Ordering::SeqCst);
+        Self(Arc::clone(active))
+    }
+}
+
+impl Drop for ActiveConnection {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// 1つの接続（スクリプト・トランザクションは同じ接続で実行する）
+///
+/// DuckDBのAPIはブロッキングのため、文は `spawn_blocking` で実行する
+struct DuckDbSession {
+    conn: Arc<StdMutex<Connection>>,
+    interrupt: Arc<InterruptHandle>,
+    running: RunningQueries<Arc<InterruptHandle>>,
+    _active: ActiveConnection,
+}
+
+impl DuckDbSession {
+    /// 接続上で処理を実行する
+    async fn run<T, F>(&self, f: F) -> Result<T, QueryError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Connection) -> Result<T, duckdb::Error> + Send + 'static,
+    {
+        self.run_blocking(f).await?.map_err(map_error)
+    }
+
+    /// 複数文を順次実行し、失敗した場合は失敗した文の位置をエラーに含める
+    async fn run_statements(
+        &self,
+        sql: &str,
+        params: &[QueryParam],
+    ) -> Result<QueryResult, QueryError> {
+        let statements = StatementSplitter::new("duckdb").split(sql);
+        if statements.len() > 1 && !params.is_empty() {
+            return Err(multiple_statements_with_params_error());
+        }
+
+        let is_script = statements.len() > 1;
+        let owned_sql = sql.to_string();
+        let params = params.to_vec();
+        self.run_blocking(move |conn| run_statements(conn, &owned_sql, &statements, &params))
+            .await?
+            .map_err(|(range, e)| locate_statement_error(map_error(e), sql, &range, is_script))
+    }
+
+    /// 接続上でブロッキング処理を実行する
+    async fn run_blocking<T, F>(&self, f: F) -> Result<T, QueryError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Connection) -> T + Send + 'static,
+    {
+        let _running = self.track_running();
+        let guard = InterruptOnDrop(Some(Arc::clone(&self.interrupt)));
+        let conn = Arc::clone(&self.conn);
+        let result = tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap_or_else(|e| e.into_inner());
+            f(&conn)
+        })
+        .await;
+        guard.disarm();
+
+        result.map_err(|e| QueryError {
+            code: QueryErrorCode::Unknown,
+            message: format!("DuckDB task failed: {}", e),
+            details: None,
+            native_code: None,
+        })
+    }
+
+    /// 現在のクエリIDがあれば、接続の割り込みハンドルを実行中クエリとして登録する
+    fn track_running(&self) -> Option<RunningQueryGuard<Arc<InterruptHandle>>> {
+        current_query_id()
+            .map(|query_id| self.running.register(query_id, Arc::clone(&self.interrupt)))
+    }
+
+    /// スクリプトの各文を順次実行し、文ごとの結果を返す
+    async fn execute_script(
+        &self,
+        sql: &str,
+        mode: ScriptErrorMode,
+        timeout: Duration,
+    ) -> Vec<StatementResult> {
+        let statements = StatementSplitter::new("duckdb").split(sql);
+        let is_script = statements.len() > 1;
+        let mut results = Vec::with_capacity(statements.len());
+
+        for (index, range) in statements.iter().enumerate() {
+            let start = Instant::now();
+            let statement = range.text(sql).to_string();
+            let is_result_set = returns_result_set(&statement);
+            let execution = self.run(move |conn| run_statement(conn, &statement, &[]));
+
+            let outcome = match tokio::time::timeout(timeout, execution).await {
+                Ok(Ok((mut result, affected_rows))) => {
+                    if is_result_set {
+                        result.execution_time_ms = start.elapsed().as_millis() as u64;
+                        StatementOutcome::ResultSet { result }
+                    } else {
+                        StatementOutcome::Affected { affected_rows }
+                    }
+                }
+                Ok(Err(e)) => StatementOutcome::Error {
+                    error: locate_statement_error(e, sql, range, is_script),
+                },
+                Err(_) => StatementOutcome::Error {
+                    error: QueryError {
+                        code: QueryErrorCode::QueryTimeout,
+                        message: format!("Query timed out after {:?}", timeout),
+                        details: None,
+                        native_code: None,
+                    },
+                },
+            };
+
+            let failed = matches!(outcome, StatementOutcome::Error { .. });
+            results.push(StatementResult {
+                index,
+                range: *range,
+                outcome,
+                execution_time_ms: start.elapsed().as_millis() as u64,
+            });
+
+            if failed && mode == ScriptErrorMode::StopOnError {
+                break;
+            }
+        }
+
+        results
+    }
+}
+
+pub struct DuckDbExecutor {
+    /// 閉じた後は `None`
+    database: StdMutex<Option<Arc<DuckDbDatabase>>>,
+    /// 新しい接続を開いたときに実行するSQL
+    init_sql: Option<String>,
+    /// 実行中クエリの割り込みハンドル（キャンセル用）
+    running: RunningQueries<Arc<InterruptHandle>>,
+    /// 使用中の接続数
+    active: Arc<AtomicU32>,
+}
+
+impl DuckDbExecutor {
+    pub async fn new(connection: &ConnectionInfo) -> Result<Self, QueryError> {
+        let config = match connection.file_config() {
+            Some(config) => config,
+            None => {
+                return Err(QueryError {
+                    code: QueryErrorCode::ConnectionFailed,
+                    message: "Invalid connection config for DuckDB".to_string(),
+                    details: None,
+                    native_code: None,
+                })
+            }
+        };
+        let init_sql = connection.session_init_sql().map_err(|e| QueryError {
+            code: QueryErrorCode::ConnectionFailed,
+            message: format!("Invalid session settings: {}", e),
+            details: None,
+            native_code: None,
+        })?;
+
+        // ファイルを開く処理はブロッキングのため別スレッドで行う
+        let database = tokio::task::spawn_blocking(move || DuckDbDatabase::acquire(&config))
+            .await
+            .map_err(|e| QueryError {
+                code: QueryErrorCode::ConnectionFailed,
+                message: format!("Failed to connect: {}", e),
+                details: None,
+                native_code: None,
+            })?
+            .map_err(|e| {
+                let e = map_error(e);
+                QueryError {
+                    code: QueryErrorCode::ConnectionFailed,
+                    message: format!("Failed to connect: {}", e.message),
+                    ..e
+                }
+            })?;
+
+        Ok(Self {
+            database: StdMutex::new(Some(database)),
+            init_sql,
+            running: RunningQueries::new(),
+            active: Arc::new(AtomicU32::new(0)),
+        })
+    }
+
+    /// 新しい接続を開く（セッション設定を適用する）
+    async fn session(&self) -> Result<DuckDbSession, QueryError> {
+        let database = self
+            .database
+            .lock()
+            .unwrap()
+            .clone()
+            .ok_or_else(|| QueryError {
+                code: QueryErrorCode::ConnectionFailed,
+                message: "Connection is closed".to_string(),
+                details: None,
+                native_code: None,
+            })?;
+
+        let init_sql = self.init_sql.clone();
+        let conn = tokio::task::spawn_blocking(move || {
+            let conn = database.connect()?;
+            if let Some(sql) = &init_sql {
+                conn.execute_batch(sql)?;
+            }
+            Ok(conn)
+        })
+        .await
+        .map_err(|e| QueryError {
+            code: QueryErrorCode::ConnectionFailed,
+            message: format!("Failed to connect: {}", e),
+            details: None,
+            native_code: None,
+        })?
+        .map_err(map_error)?;
+
+        Ok(DuckDbSession {
+            interrupt: conn.interrupt_handle(),
+            conn: Arc::new(StdMutex::new(conn)),
+            running: self.running.clone(),
+            _active: ActiveConnection::new(&self.active),
+        })
+    }
+}
+
+#[async_trait]
+impl QueryExecutor for DuckDbExecutor {
+    async fn execute(&self, sql: &str) -> Result<QueryResult, QueryError> {
+        self.execute_with_params(sql, &[]).await
+    }
+
+    async fn execute_with_params(
+        &self,
+        sql: &str,
+        params: &[QueryParam],
+    ) -> Result<QueryResult, QueryError> {
+        let start = Instant::now();
+
+        // 文単位に分割して同じ接続で順次実行し、最後の文の結果を返す（パラメータは単一文のみ）
+        let session = self.session().await?;
+        let mut result = session.run_statements(sql, params).await?;
+
+        result.execution_time_ms = start.elapsed().as_millis() as u64;
+        Ok(result)
+    }
+
+    async fn execute_mutation(&self, sql: &str) -> Result<MutationResult, QueryError> {
+        let start = Instant::now();
+        let session = self.session().await?;
+        let sql = sql.to_string();
+        let (_, affected_rows) = session
+            .run(move |conn| run_statement(conn, &sql, &[]))
+            .await?;
+
+        Ok(MutationResult {
+            affected_rows,
+            execution_time_ms: start.elapsed().as_millis() as u64,
+            undo: None,
+        })
+    }
+
+    async fn execute_script(
+        &self,
+        sql: &str,
+        mode: ScriptErrorMode,
+        timeout: Duration,
+    ) -> Result<Vec<StatementResult>, QueryError> {
+        let session = self.session().await?;
+        Ok(session.execute_script(sql, mode, timeout).await)
+    }
+
+    async fn open_cursor(&self, sql: &str, buffer_size: usize) -> Result<QueryCursor, QueryError> {
+        let session = self.session().await?;
+        let sql = sql.trim().trim_end_matches(';').to_string();
+
+        Ok(QueryCursor::spawn(buffer_size, move |sender| async move {
+            let error_sender = sender.clone();
+            // カーソルが破棄されると送信に失敗して読み出しを止める（実行中の文は中断される）
+            let result = session
+                .run(move |conn| {
+                    let mut stmt = conn.prepare(&sql)?;
+                    stmt.execute([])?;
+                    let columns = build_columns(&stmt);
+                    if sender
+                        .blocking_send(Ok(CursorEvent::Columns(columns.clone())))
+                        .is_err()
+                    {
+                        return Ok(());
+                    }
+
+                    let mut rows = stmt.raw_query();
+                    while let Some(row) = rows.next()? {
+                        let row = convert_row(row, &columns)?;
+                        if sender.blocking_send(Ok(CursorEvent::Row(row))).is_err() {
+                            break;
+                        }
+                    }
+                    Ok(())
+                })
+                .await;
+
+            if let Err(e) = result {
+                let _ = error_sender.send(Err(e)).await;
+            }
+        }))
+    }
+
+    async fn cancel(&self, query_id: &str) -> Result<bool, QueryError> {
+        match self.running.get(query_id) {
+            Some(handle) => {
+                handle.interrupt();
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn begin_transaction(&self) -> Result<Box<dyn TransactionSession>, QueryError> {
+        let session = self.session().await?;
+        session
+            .run(|conn| conn.execute_batch("BEGIN TRANSACTION"))
+            .await?;
+        Ok(Box::new(DuckDbTransaction { session }))
+    }
+
+    async fn ping(&self) -> Result<(), QueryError> {
+        let session = self.session().await?;
+        session.run(|conn| conn.execute_batch("SELECT 1")).await
+    }
+
+    fn pool_status(&self) -> PoolStatus {
+        // 開いているデータベースを1つのアイドル接続として数える
+        let open = u32::from(self.database.lock().unwrap().is_some());
+        PoolStatus {
+            size: open + self.active.load(Ordering::SeqCst),
+            idle: open,
+        }
+    }
+
+    async fn close(&self) -> Result<(), QueryError> {
+        self.database.lock().unwrap().take();
+        Ok(())
+    }
+}
+
+/// DuckDBのトランザクションセッション（専用接続を保持）
+///
+/// コミット・ロールバックされずに破棄された場合は、接続を閉じる際にロールバックされる
+pub struct DuckDbTransaction {
+    session: DuckDbSession,
+}
+
+#[async_trait]
+impl TransactionSession for DuckDbTransaction {
+    async fn execute_with_params(
+        &mut self,
+        sql: &str,
+        params: &[QueryParam],
+    ) -> Result<QueryResult, QueryError> {
+        let start = Instant::now();
+        let mut result = self.session.run_statements(sql, params).await?;
+
+        result.execution_time_ms = start.elapsed().as_millis() as u64;
+        Ok(result)
+    }
+
+    async fn execute_mutation(&mut self, sql: &str) -> Result<MutationResult, QueryError> {
+        let start = Instant::now();
+        let sql = sql.to_string();
+        let (_, affected_rows) = self
+            .session
+            .run(move |conn| run_statement(conn, &sql, &[]))
+            .await?;
+
+        Ok(MutationResult {
+            affected_rows,
+            execution_time_ms: start.elapsed().as_millis() as u64,
+            undo: None,
+        })
+    }
+
+    async fn execute_script(
+        &mut self,
+        sql: &str,
+        mode: ScriptErrorMode,
+        timeout: Duration,
+    ) -> Vec<StatementResult> {
+        self.session.execute_script(sql, mode, timeout).await
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), QueryError> {
+        self.session.run(|conn| conn.execute_batch("COMMIT")).await
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), QueryError> {
+        self.session
+            .run(|conn| conn.execute_batch("ROLLBACK"))
+            .await
+    }
+}
@@ -0,0 +1,598 @@
+use crate::connection::ConnectionInfo;
+use crate::database::duckdb_executor::DuckDbDatabase;
+use crate::models::database_structure::*;
+use crate::services::database_inspector::{DatabaseInspector, TableForeignKey};
+use async_trait::async_trait;
+use duckdb::types::Value;
+use duckdb::{params_from_iter, Row};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub struct DuckDbInspector {
+    database: Arc<DuckDbDatabase>,
+    database_name: String,
+}
+
+/// 制約（`duckdb_constraints()` の1行）
+#[derive(Clone)]
+struct Constraint {
+    table: String,
+    constraint_type: String,
+    name: Option<String>,
+    columns: Vec<String>,
+    referenced_table: Option<String>,
+    referenced_columns: Vec<String>,
+}
+
+/// 文字列のリスト値を取り出す
+fn string_list(value: Value) -> Vec<String> {
+    match value {
+        Value::List(values) | Value::Array(values) => values
+            .into_iter()
+            .filter_map(|v| match v {
+                Value::Text(s) => Some(s),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+impl DuckDbInspector {
+    pub async fn new(connection: &ConnectionInfo) -> Result<Self, String> {
+        let config = match connection.file_config() {
+            Some(config) => config,
+            None => return Err("Invalid connection config for DuckDB".to_string()),
+        };
+        let database_name = config.file_path.clone();
+
+        let database = tokio::task::spawn_blocking(move || DuckDbDatabase::acquire(&config))
+            .await
+            .map_err(|e| format!("Failed to connect: {}", e))?
+            .map_err(|e| format!("Failed to connect: {}", e))?;
+
+        Ok(Self {
+            database,
+            database_name,
+        })
+    }
+
+    /// クエリを実行し、各行を変換して返す（DuckDBのAPIはブロッキングのため別スレッドで実行）
+    async fn query<T, F>(
+        &self,
+        sql: &'static str,
+        params: Vec<String>,
+        map: F,
+    ) -> Result<Vec<T>, String>
+    where
+        T: Send + 'static,
+        F: Fn(&Row) -> duckdb::Result<T> + Send + 'static,
+    {
+        let database = Arc::clone(&self.database);
+        tokio::task::spawn_blocking(move || {
+            let conn = database.connect().map_err(|e| e.to_string())?;
+            let mut stmt = conn.prepare(sql).map_err(|e| e.to_string())?;
+            let rows = stmt
+                .query_map(params_from_iter(params), |row| map(row))
+                .map_err(|e| e.to_string())?;
+            rows.collect::<Result<Vec<T>, _>>()
+                .map_err(|e| e.to_string())
+        })
+        .await
+        .map_err(|e| e.to_string())?
+    }
+
+    /// スキーマ内の制約を取得する（`table` を指定するとそのテーブルのみ）
+    async fn get_constraints(
+        &self,
+        schema: &str,
+        table: Option<&str>,
+    ) -> Result<Vec<Constraint>, String> {
+        let query = r#"
+            SELECT table_name, constraint_type, constraint_name, constraint_column_names,
+                   referenced_table, referenced_column_names
+            FROM duckdb_constraints()
+            WHERE database_name = current_database()
+              AND schema_name = ?
+              AND (? = '' OR table_name = ?)
+              AND constraint_type IN ('PRIMARY KEY', 'UNIQUE', 'FOREIGN KEY')
+            ORDER BY table_name, constraint_index
+        "#;
+        let table = table.unwrap_or_default().to_string();
+
+        self.query(
+            query,
+            vec![schema.to_string(), table.clone(), table],
+            |row| {
+                Ok(Constraint {
+                    table: row.get(0)?,
+                    constraint_type: row.get(1)?,
+                    name: row.get(2)?,
+                    columns: string_list(row.get(3)?),
+                    referenced_table: row.get(4)?,
+                    referenced_columns: string_list(row.get(5)?),
+                })
+            },
+        )
+        .await
+        .map_err(|e| format!("Failed to get constraints: {}", e))
+    }
+
+    fn primary_key(constraints: &[Constraint]) -> Option<PrimaryKey> {
+        constraints
+            .iter()
+            .find(|c| c.constraint_type == "PRIMARY KEY")
+            .map(|c| PrimaryKey {
+                name: c.name.clone().unwrap_or_else(|| "PRIMARY".to_string()),
+                columns: c.columns.clone(),
+            })
+    }
+
+    fn foreign_keys(schema: &str, constraints: &[Constraint]) -> Vec<ForeignKey> {
+        // DuckDBは参照アクションをサポートしない
+        constraints
+            .iter()
+            .filter(|c| c.constraint_type == "FOREIGN KEY")
+            .enumerate()
+            .map(|(i, c)| ForeignKey {
+                name: c.name.clone().unwrap_or_else(|| format!("fk_{}", i)),
+                columns: c.columns.clone(),
+                referenced_schema: schema.to_string(),
+                referenced_table: c.referenced_table.clone().unwrap_or_default(),
+                referenced_columns: c.referenced_columns.clone(),
+                on_delete: "NO ACTION".to_string(),
+                on_update: "NO ACTION".to_string(),
+            })
+            .collect()
+    }
+
+    /// テーブルを組み立てる（制約はスキーマ全体から絞り込む）
+    fn build_table(
+        schema: &str,
+        name: String,
+        comment: Option<String>,
+        estimated_row_count: Option<i64>,
+        columns: Vec<Column>,
+        indexes: Vec<Index>,
+        constraints: &[Constraint],
+    ) -> Table {
+        let own: Vec<Constraint> = constraints
+            .iter()
+            .filter(|c| c.table == name)
+            .cloned()
+            .collect();
+
+        let primary_key = Self::primary_key(&own);
+        let foreign_keys = Self::foreign_keys(schema, &own);
+        let referenced_by = Self::references(schema, &name, constraints);
+
+        let mut columns = columns;
+        for column in &mut columns {
+            column.is_primary_key = primary_key
+                .as_ref()
+                .is_some_and(|pk| pk.columns.contains(&column.name));
+            column.is_foreign_key = foreign_keys
+                .iter()
+                .any(|fk| fk.columns.contains(&column.name));
+            column.is_unique = own
+                .iter()
+                .any(|c| c.constraint_type == "UNIQUE" && c.columns == [column.name.clone()]);
+        }
+
+        let mut all_indexes = Self::constraint_indexes(&own);
+        all_indexes.extend(indexes);
+
+        Table {
+            name,
+            schema: schema.to_string(),
+            comment,
+            estimated_row_count,
+            columns,
+            primary_key,
+            indexes: all_indexes,
+            foreign_keys,
+            referenced_by,
+            is_system_versioned: false,
+        }
+    }
+
+    /// 主キー・一意制約は暗黙のインデックスとして扱う
+    fn constraint_indexes(constraints: &[Constraint]) -> Vec<Index> {
+        constraints
+            .iter()
+            .filter(|c| c.constraint_type != "FOREIGN KEY")
+            .map(|c| Index {
+                name: c.name.clone().unwrap_or_else(|| c.columns.join("_")),
+                is_unique: true,
+                is_primary: c.constraint_type == "PRIMARY KEY",
+                columns: c.columns.clone(),
+                index_type: "art".to_string(),
+            })
+            .collect()
+    }
+
+    fn references(
+        schema: &str,
+        target: &str,
+        constraints: &[Constraint],
+    ) -> Vec<ForeignKeyReference> {
+        constraints
+            .iter()
+            .filter(|c| {
+                c.constraint_type == "FOREIGN KEY" && c.referenced_table.as_deref() == Some(target)
+            })
+            .enumerate()
+            .map(|(i, c)| ForeignKeyReference {
+                source_schema: schema.to_string(),
+                source_table: c.table.clone(),
+                source_columns: c.columns.clone(),
+                target_columns: c.referenced_columns.clone(),
+                constraint_name: c.name.clone().unwrap_or_else(|| format!("fk_{}", i)),
+            })
+            .collect()
+    }
+}
+
+/// `duckdb_columns()` の行を列に変換する（テーブル名, 列）
+fn column_from_row(row: &Row) -> duckdb::Result<(String, Column)> {
+    let table: String = row.get(0)?;
+    let data_type: String = row.get(2)?;
+    let default_value: Option<String> = row.get(4)?;
+    let ordinal: i64 = row.get(5)?;
+
+    Ok((
+        table,
+        Column {
+            name: row.get(1)?,
+            display_type: data_type.clone(),
+            data_type,
+            nullable: row.get(3)?,
+            is_auto_increment: default_value
+                .as_deref()
+                .is_some_and(|d| d.starts_with("nextval(")),
+            default_value,
+            is_primary_key: false,
+            is_foreign_key: false,
+            is_unique: false,
+            // duckdb_columns() からは生成列を判定できない
+            is_generated: false,
+            ordinal_position: ordinal as i32,
+            comment: row.get(6)?,
+        },
+    ))
+}
+
+const COLUMNS_QUERY: &str = r#"
+    SELECT table_name, column_name, data_type, is_nullable, column_default,
+           column_index, comment
+    FROM duckdb_columns()
+    WHERE database_name = current_database()
+      AND schema_name = ?
+      AND (? = '' OR table_name = ?)
+    ORDER BY table_name, column_index
+"#;
+
+impl DuckDbInspector {
+    /// スキーマ内の列をテーブルごとに取得する
+    async fn columns_by_table(
+        &self,
+        schema: &str,
+        table: Option<&str>,
+    ) -> Result<HashMap<String, Vec<Column>>, String> {
+        let table = table.unwrap_or_default().to_string();
+        let rows = self
+            .query(
+                COLUMNS_QUERY,
+                vec![schema.to_string(), table.clone(), table],
+                column_from_row,
+            )
+            .await
+            .map_err(|e| format!("Failed to get columns: {}", e))?;
+
+        let mut result: HashMap<String, Vec<Column>> = HashMap::new();
+        for (table, column) in rows {
+            result.entry(table).or_default().push(column);
+        }
+        Ok(result)
+    }
+
+    /// スキーマ内の明示的なインデックスをテーブルごとに取得する
+    async fn indexes_by_table(
+        &self,
+        schema: &str,
+        table: Option<&str>,
+    ) -> Result<HashMap<String, Vec<Index>>, String> {
+        let query = r#"
+            SELECT table_name, index_name, is_unique, is_primary, expressions
+            FROM duckdb_indexes()
+            WHERE database_name = current_database()
+              AND schema_name = ?
+              AND (? = '' OR table_name = ?)
+            ORDER BY table_name, index_name
+        "#;
+        let table = table.unwrap_or_default().to_string();
+        let rows = self
+            .query(
+                query,
+                vec![schema.to_string(), table.clone(), table],
+                |row| {
+                    // expressions は `[a, b]` 形式の文字列で返る
+                    let expressions: Option<String> = row.get(4)?;
+                    let columns = expressions
+                        .unwrap_or_default()
+                        .trim_matches(|c| c == '[' || c == ']')
+                        .split(',')
+                        .map(|c| c.trim().trim_matches('\'').trim_matches('"').to_string())
+                        .filter(|c| !c.is_empty())
+                        .collect();
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        Index {
+                            name: row.get(1)?,
+                            is_unique: row.get(2)?,
+                            is_primary: row.get(3)?,
+                            columns,
+                            index_type: "art".to_string(),
+                        },
+                    ))
+                },
+            )
+            .await
+            .map_err(|e| format!("Failed to get indexes: {}", e))?;
+
+        let mut result: HashMap<String, Vec<Index>> = HashMap::new();
+        for (table, index) in rows {
+            result.entry(table).or_default().push(index);
+        }
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl DatabaseInspector for DuckDbInspector {
+    async fn get_schemas(&self) -> Result<Vec<Schema>, String> {
+        let query = r#"
+            SELECT schema_name, internal
+            FROM duckdb_schemas()
+            WHERE database_name = current_database()
+            ORDER BY CASE WHEN schema_name = 'main' THEN 0 ELSE 1 END, schema_name
+        "#;
+
+        let rows = self
+            .query(query, Vec::new(), |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, bool>(1)?))
+            })
+            .await
+            .map_err(|e| format!("Failed to get schemas: {}", e))?;
+
+        let mut schemas = Vec::new();
+        for (name, is_system) in rows {
+            let tables = self.get_tables(&name).await?;
+            let views = self.get_views(&name).await?;
+
+            schemas.push(Schema {
+                name,
+                is_system,
+                tables,
+                views,
+                sequences: Vec::new(),
+            });
+        }
+
+        Ok(schemas)
+    }
+
+    async fn get_tables(&self, schema: &str) -> Result<Vec<Table>, String> {
+        let query = r#"
+            SELECT table_name, comment, estimated_size
+            FROM duckdb_tables()
+            WHERE database_name = current_database()
+              AND schema_name = ?
+            ORDER BY table_name
+        "#;
+
+        let rows = self
+            .query(query, vec![schema.to_string()], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    row.get::<_, Option<i64>>(2)?,
+                ))
+            })
+            .await
+            .map_err(|e| format!("Failed to get tables: {}", e))?;
+
+        let mut columns = self.columns_by_table(schema, None).await?;
+        let mut indexes = self.indexes_by_table(schema, None).await?;
+        let constraints = self.get_constraints(schema, None).await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(name, comment, estimated_row_count)| {
+                let table_columns = columns.remove(&name).unwrap_or_default();
+                let table_indexes = indexes.remove(&name).unwrap_or_default();
+                Self::build_table(
+                    schema,
+                    name,
+                    comment,
+                    estimated_row_count,
+                    table_columns,
+                    table_indexes,
+                    &constraints,
+                )
+            })
+            .collect())
+    }
+
+    async fn get_views(&self, schema: &str) -> Result<Vec<View>, String> {
+        let query = r#"
+            SELECT view_name, comment, sql
+            FROM duckdb_views()
+            WHERE database_name = current_database()
+              AND schema_name = ?
+              AND NOT internal
+            ORDER BY view_name
+        "#;
+
+        let rows = self
+            .query(query, vec![schema.to_string()], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                ))
+            })
+            .await
+            .map_err(|e| format!("Failed to get views: {}", e))?;
+
+        let mut columns = self.columns_by_table(schema, None).await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(name, comment, definition)| View {
+                columns: columns.remove(&name).unwrap_or_default(),
+                name,
+                schema: schema.to_string(),
+                comment,
+                definition,
+            })
+            .collect())
+    }
+
+    async fn get_columns(&self, schema: &str, table: &str) -> Result<Vec<Column>, String> {
+        let mut columns = self.columns_by_table(schema, Some(table)).await?;
+        let mut columns = columns.remove(table).unwrap_or_default();
+
+        let constraints = self.get_constraints(schema, Some(table)).await?;
+        if let Some(pk) = Self::primary_key(&constraints) {
+            for column in &mut columns {
+                column.is_primary_key = pk.columns.contains(&column.name);
+            }
+        }
+        Ok(columns)
+    }
+
+    async fn get_indexes(&self, schema: &str, table: &str) -> Result<Vec<Index>, String> {
+        let constraints = self.get_constraints(schema, Some(table)).await?;
+        let mut indexes = Self::constraint_indexes(&constraints);
+        let mut explicit = self.indexes_by_table(schema, Some(table)).await?;
+        indexes.extend(explicit.remove(table).unwrap_or_default());
+        Ok(indexes)
+    }
+
+    async fn get_foreign_keys(&self, schema: &str, table: &str) -> Result<Vec<ForeignKey>, String> {
+        let constraints = self.get_constraints(schema, Some(table)).await?;
+        Ok(Self::foreign_keys(schema, &constraints))
+    }
+
+    async fn get_foreign_key_references(
+        &self,
+        schema: &str,
+        table: &str,
+    ) -> Result<Vec<ForeignKeyReference>, String> {
+        let constraints = self.get_constraints(schema, None).await?;
+        Ok(Self::references(schema, table, &constraints))
+    }
+
+    async fn get_all_foreign_keys(
+        &self,
+        schema: Option<&str>,
+    ) -> Result<Vec<TableForeignKey>, String> {
+        let schema_name = schema.unwrap_or("main");
+        let constraints = self.get_constraints(schema_name, None).await?;
+
+        let mut all_fks = Vec::new();
+        for constraint in constraints
+            .iter()
+            .filter(|c| c.constraint_type == "FOREIGN KEY")
+        {
+            let table = constraint.table.clone();
+            let fks = Self::foreign_keys(schema_name, std::slice::from_ref(constraint));
+            all_fks.extend(fks.into_iter().map(|foreign_key| TableForeignKey {
+                schema: schema_name.to_string(),
+                table: table.clone(),
+                foreign_key,
+            }));
+        }
+
+        Ok(all_fks)
+    }
+
+    async fn get_columns_by_schema(
+        &self,
+        schema: &str,
+    ) -> Result<HashMap<String, Vec<Column>>, String> {
+        self.columns_by_table(schema, None).await
+    }
+
+    async fn get_table_summaries(&self) -> Result<Vec<SchemaSummary>, String> {
+        let query = r#"
+            SELECT s.schema_name, s.internal, t.name, t.comment, t.estimated_size, t.is_view
+            FROM duckdb_schemas() s
+            LEFT JOIN (
+                SELECT schema_name, table_name AS name, comment, estimated_size, false AS is_view
+                FROM duckdb_tables()
+                WHERE database_name = current_database()
+                UNION ALL
+                SELECT schema_name, view_name, comment, NULL, true
+                FROM duckdb_views()
+                WHERE database_name = current_database() AND NOT internal
+            ) t ON t.schema_name = s.schema_name
+            WHERE s.database_name = current_database()
+            ORDER BY CASE WHEN s.schema_name = 'main' THEN 0 ELSE 1 END, s.schema_name, t.name
+        "#;
+
+        let rows = self
+            .query(query, Vec::new(), |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, bool>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, Option<i64>>(4)?,
+                    row.get::<_, Option<bool>>(5)?,
+                ))
+            })
+            .await
+            .map_err(|e| format!("Failed to get table summaries: {}", e))?;
+
+        let mut schemas: Vec<SchemaSummary> = Vec::new();
+        for (schema, is_system, name, comment, estimated_row_count, is_view) in rows {
+            if schemas.last().map(|s| &s.name) != Some(&schema) {
+                schemas.push(SchemaSummary {
+                    name: schema.clone(),
+                    is_system,
+                    tables: Vec::new(),
+                    views: Vec::new(),
+                });
+            }
+            let Some(name) = name else { continue };
+            let summary = TableSummary {
+                name,
+                schema: schema.clone(),
+                comment,
+                estimated_row_count,
+            };
+            let current = schemas.last_mut().expect("schema pushed above");
+            if is_view == Some(true) {
+                current.views.push(summary);
+            } else {
+                current.tables.push(summary);
+            }
+        }
+
+        Ok(schemas)
+    }
+
+    async fn get_database_structure(&self) -> Result<DatabaseStructure, String> {
+        let schemas = self.get_schemas().await?;
+
+        Ok(DatabaseStructure {
+            connection_id: String::new(), // 呼び出し元で設定
+            database_name: self.database_name.clone(),
+            database_type: "duckdb".to_string(),
+            schemas,
+            fetched_at: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+}
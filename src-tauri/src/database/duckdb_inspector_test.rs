@@ -0,0 +1,70 @@
+use crate::connection::{ConnectionConfig, ConnectionInfo, DatabaseType, FileConfig};
+use crate::database::{DuckDbExecutor, DuckDbInspector};
+use crate::services::database_inspector::DatabaseInspector;
+use crate::services::query_executor::QueryExecutor;
+
+#[tokio::test]
+async fn test_duckdb_inspector_reads_catalog() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let connection = ConnectionInfo::new(
+        "Inspector".to_string(),
+        DatabaseType::DuckDB,
+        ConnectionConfig::File(FileConfig {
+            file_path: dir
+                .path()
+                .join("inspector.duckdb")
+                .to_string_lossy()
+                .to_string(),
+            readonly: false,
+        }),
+    );
+    let executor = DuckDbExecutor::new(&connection).await.unwrap();
+    executor
+        .execute_mutation(
+            "CREATE TABLE users (id INTEGER PRIMARY KEY, email VARCHAR UNIQUE, name VARCHAR NOT NULL);\n\
+             CREATE TABLE posts (id INTEGER PRIMARY KEY, user_id INTEGER REFERENCES users (id), title VARCHAR);\n\
+             CREATE INDEX idx_posts_user ON posts (user_id);\n\
+             CREATE VIEW user_names AS SELECT name FROM users;",
+        )
+        .await
+        .unwrap();
+
+    let inspector = DuckDbInspector::new(&connection).await.unwrap();
+    let tables: Vec<String> = inspector
+        .get_tables("main")
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|t| t.name)
+        .collect();
+    assert_eq!(tables, vec!["posts", "users"]);
+
+    let views = inspector.get_views("main").await.unwrap();
+    assert_eq!(views.len(), 1);
+    assert_eq!(views[0].name, "user_names");
+
+    let columns = inspector.get_columns("main", "users").await.unwrap();
+    let id = columns.iter().find(|c| c.name == "id").unwrap();
+    assert!(id.is_primary_key);
+    assert!(!columns.iter().find(|c| c.name == "name").unwrap().nullable);
+
+    let indexes = inspector.get_indexes("main", "posts").await.unwrap();
+    let index = indexes
+        .iter()
+        .find(|i| i.name == "idx_posts_user")
+        .unwrap_or_else(|| panic!("{:?}", indexes));
+    assert_eq!(index.columns, vec!["user_id"]);
+    assert!(indexes.iter().any(|i| i.is_primary && i.columns == ["id"]));
+
+    let foreign_keys = inspector.get_foreign_keys("main", "posts").await.unwrap();
+    assert_eq!(foreign_keys.len(), 1);
+    assert_eq!(foreign_keys[0].columns, vec!["user_id"]);
+    assert_eq!(foreign_keys[0].referenced_table, "users");
+    assert_eq!(foreign_keys[0].referenced_columns, vec!["id"]);
+
+    let references = inspector
+        .get_foreign_key_references("main", "users")
+        .await
+        .unwrap();
+    assert_eq!(references.len(), 1);
+}
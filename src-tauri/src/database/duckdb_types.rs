@@ -0,0 +1,247 @@
+use crate::database::wkb::wkb_to_wkt;
+use crate::models::query_result::{QueryInterval, QueryParam, QueryValue, TypedParam};
+use chrono::{DateTime, NaiveDate, NaiveTime};
+use duckdb::core::{LogicalTypeHandle, LogicalTypeId};
+use duckdb::types::{TimeUnit, Value};
+
+/// 列の型名（`typeof()` と同じDuckDBの表記）
+///
+/// 別名の付いた型（JSONなど）は別名を返す
+pub fn duckdb_type_name(logical_type: &LogicalTypeHandle) -> String {
+    if let Some(alias) = logical_type.get_alias() {
+        return alias;
+    }
+
+    let name = match logical_type.id() {
+        LogicalTypeId::Boolean => "BOOLEAN",
+        LogicalTypeId::Tinyint => "TINYINT",
+        LogicalTypeId::Smallint => "SMALLINT",
+        LogicalTypeId::Integer => "INTEGER",
+        LogicalTypeId::Bigint => "BIGINT",
+        LogicalTypeId::Hugeint => "HUGEINT",
+        LogicalTypeId::UTinyint => "UTINYINT",
+        LogicalTypeId::USmallint => "USMALLINT",
+        LogicalTypeId::UInteger => "UINTEGER",
+        LogicalTypeId::UBigint => "UBIGINT",
+        LogicalTypeId::UHugeint => "UHUGEINT",
+        LogicalTypeId::Float => "FLOAT",
+        LogicalTypeId::Double => "DOUBLE",
+        LogicalTypeId::Decimal => {
+            return format!(
+                "DECIMAL({},{})",
+                logical_type.decimal_width(),
+                logical_type.decimal_scale()
+            )
+        }
+        LogicalTypeId::Varchar => "VARCHAR",
+        LogicalTypeId::Blob => "BLOB",
+        LogicalTypeId::Bit => "BIT",
+        LogicalTypeId::Bignum => "BIGNUM",
+        LogicalTypeId::Uuid => "UUID",
+        LogicalTypeId::Enum => "ENUM",
+        LogicalTypeId::Date => "DATE",
+        LogicalTypeId::Time => "TIME",
+        LogicalTypeId::TimeNs => "TIME_NS",
+        LogicalTypeId::TimeTZ => "TIME WITH TIME ZONE",
+        LogicalTypeId::Timestamp => "TIMESTAMP",
+        LogicalTypeId::TimestampS => "TIMESTAMP_S",
+        LogicalTypeId::TimestampMs => "TIMESTAMP_MS",
+        LogicalTypeId::TimestampNs => "TIMESTAMP_NS",
+        LogicalTypeId::TimestampTZ => "TIMESTAMP WITH TIME ZONE",
+        LogicalTypeId::Interval => "INTERVAL",
+        LogicalTypeId::Geometry => "GEOMETRY",
+        LogicalTypeId::List | LogicalTypeId::Array => {
+            return format!("{}[]", duckdb_type_name(&logical_type.child(0)))
+        }
+        LogicalTypeId::Map => {
+            return format!(
+                "MAP({}, {})",
+                duckdb_type_name(&logical_type.child(0)),
+                duckdb_type_name(&logical_type.child(1))
+            )
+        }
+        LogicalTypeId::Struct | LogicalTypeId::Union => {
+            let keyword = if logical_type.id() == LogicalTypeId::Struct {
+                "STRUCT"
+            } else {
+                "UNION"
+            };
+            let fields: Vec<String> = (0..logical_type.num_children())
+                .map(|i| {
+                    format!(
+                        "{} {}",
+                        logical_type.child_name(i),
+                        duckdb_type_name(&logical_type.child(i))
+                    )
+                })
+                .collect();
+            return format!("{}({})", keyword, fields.join(", "));
+        }
+        LogicalTypeId::SqlNull => "NULL",
+        _ => "UNKNOWN",
+    };
+    name.to_string()
+}
+
+/// 1つの値を変換する
+///
+/// `data_type` は列の型名（`duckdb_type_name`）。同じ値の表現でも型名で解釈を変える
+/// （TIMESTAMP WITH TIME ZONE はUTCの日時、UUID・JSONは文字列で返る）
+pub fn convert_duckdb_value(value: Value, data_type: &str) -> QueryValue {
+    match value {
+        Value::Null => QueryValue::Null,
+        Value::Boolean(v) => QueryValue::Bool(v),
+        Value::TinyInt(v) => QueryValue::Int(v.into()),
+        Value::SmallInt(v) => QueryValue::Int(v.into()),
+        Value::Int(v) => QueryValue::Int(v.into()),
+        Value::BigInt(v) => QueryValue::Int(v),
+        Value::UTinyInt(v) => QueryValue::Int(v.into()),
+        Value::USmallInt(v) => QueryValue::Int(v.into()),
+        Value::UInt(v) => QueryValue::Int(v.into()),
+        // i64に収まらない値は桁あふれさせず Decimal にする
+        Value::UBigInt(v) => i64::try_from(v)
+            .map(QueryValue::Int)
+            .unwrap_or_else(|_| QueryValue::Decimal(v.to_string())),
+        Value::HugeInt(v) => i64::try_from(v)
+            .map(QueryValue::Int)
+            .unwrap_or_else(|_| QueryValue::Decimal(v.to_string())),
+        Value::UHugeInt(v) => i64::try_from(v)
+            .map(QueryValue::Int)
+            .unwrap_or_else(|_| QueryValue::Decimal(v.to_string())),
+        Value::Float(v) => QueryValue::Float(v.into()),
+        Value::Double(v) => QueryValue::Float(v),
+        Value::Decimal(v) => QueryValue::Decimal(v.to_string()),
+        Value::Text(s) => match data_type {
+            "UUID" => uuid::Uuid::parse_str(&s)
+                .map(QueryValue::Uuid)
+                .unwrap_or(QueryValue::String(s)),
+            "JSON" => serde_json::from_str(&s)
+                .map(QueryValue::Json)
+                .unwrap_or(QueryValue::String(s)),
+            _ => QueryValue::String(s),
+        },
+        Value::Enum(s) => QueryValue::String(s),
+        Value::Blob(bytes) => QueryValue::Bytes(bytes),
+        // WKBをWKTにする。解釈できなければバイト列のまま
+        Value::Geometry(bytes) => match wkb_to_wkt(&bytes) {
+            Some(wkt) => QueryValue::String(wkt),
+            None => QueryValue::Bytes(bytes),
+        },
+        Value::Date32(days) => NaiveDate::from_ymd_opt(1970, 1, 1)
+            .and_then(|epoch| epoch.checked_add_signed(chrono::Duration::days(days.into())))
+            .map(QueryValue::Date)
+            .unwrap_or(QueryValue::Null),
+        Value::Time64(unit, v) => {
+            let micros = unit.to_micros(v);
+            NaiveTime::from_num_seconds_from_midnight_opt(
+                (micros / 1_000_000) as u32,
+                ((micros % 1_000_000) * 1000) as u32,
+            )
+            .map(QueryValue::Time)
+            .unwrap_or(QueryValue::Null)
+        }
+        Value::Timestamp(unit, v) => timestamp_value(unit, v, data_type),
+        Value::Interval {
+            months,
+            days,
+            nanos,
+        } => QueryValue::Interval(QueryInterval {
+            months,
+            days,
+            microseconds: nanos / 1000,
+        }),
+        Value::List(values) | Value::Array(values) => {
+            let element_type = data_type.strip_suffix("[]").unwrap_or("");
+            QueryValue::Array(
+                values
+                    .into_iter()
+                    .map(|v| convert_duckdb_value(v, element_type))
+                    .collect(),
+            )
+        }
+        // 構造体・MAP・UNIONはJSONとして表示する
+        other => QueryValue::Json(to_json(other)),
+    }
+}
+
+/// TIMESTAMP系の値を変換する（TIMESTAMP WITH TIME ZONE はUTC）
+fn timestamp_value(unit: TimeUnit, value: i64, data_type: &str) -> QueryValue {
+    let timestamp = match DateTime::from_timestamp_micros(unit.to_micros(value)) {
+        Some(timestamp) => timestamp,
+        None => return QueryValue::Null,
+    };
+    if data_type == "TIMESTAMP WITH TIME ZONE" {
+        QueryValue::TimestampTz(timestamp.fixed_offset())
+    } else {
+        QueryValue::Timestamp(timestamp.naive_utc())
+    }
+}
+
+/// 入れ子の値をJSONに変換する
+fn to_json(value: Value) -> serde_json::Value {
+    use serde_json::Value as Json;
+
+    match value {
+        Value::Struct(fields) => Json::Object(
+            fields
+                .iter()
+                .map(|(key, value)| (key.clone(), to_json(value.clone())))
+                .collect(),
+        ),
+        // キーは文字列にする（文字列以外のキーは表示形式）
+        Value::Map(entries) => Json::Object(
+            entries
+                .iter()
+                .map(|(key, value)| {
+                    let key = match to_json(key.clone()) {
+                        Json::String(s) => s,
+                        other => other.to_string(),
+                    };
+                    (key, to_json(value.clone()))
+                })
+                .collect(),
+        ),
+        Value::Union(value) => to_json(*value),
+        Value::List(values) | Value::Array(values) => {
+            Json::Array(values.into_iter().map(to_json).collect())
+        }
+        // 型付きの値（`{"type": ..., "value": ...}`）は値だけにする
+        other => match serde_json::to_value(convert_duckdb_value(other, "")) {
+            Ok(Json::Object(mut typed)) => typed.remove("value").unwrap_or(Json::Null),
+            Ok(json) => json,
+            Err(_) => Json::Null,
+        },
+    }
+}
+
+/// バインドパラメータをDuckDBの値にする
+pub fn duckdb_param(param: &QueryParam) -> Value {
+    match param {
+        QueryParam::Null => Value::Null,
+        QueryParam::Bool(b) => Value::Boolean(*b),
+        QueryParam::Int(i) => Value::BigInt(*i),
+        QueryParam::Float(f) => Value::Double(*f),
+        QueryParam::String(s) => Value::Text(s.clone()),
+        // 日時はDuckDBの型で渡し、それ以外は文字列から列の型へ変換させる
+        QueryParam::Typed(typed) => match typed {
+            TypedParam::Date(d) => {
+                Value::Date32(d.signed_duration_since(NaiveDate::default()).num_days() as i32)
+            }
+            TypedParam::Time(t) => Value::Time64(
+                TimeUnit::Microsecond,
+                t.signed_duration_since(NaiveTime::MIN)
+                    .num_microseconds()
+                    .unwrap_or_default(),
+            ),
+            TypedParam::Timestamp(t) => {
+                Value::Timestamp(TimeUnit::Microsecond, t.and_utc().timestamp_micros())
+            }
+            TypedParam::TimestampTz(t) => {
+                Value::Timestamp(TimeUnit::Microsecond, t.timestamp_micros())
+            }
+            TypedParam::Decimal(d) => Value::Text(d.to_string()),
+            TypedParam::Json(v) => Value::Text(v.to_string()),
+            TypedParam::Uuid(u) => Value::Text(u.hyphenated().to_string()),
+        },
+    }
+}
@@ -0,0 +1,279 @@
+use crate::connection::{ConnectionConfig, ConnectionInfo, DatabaseType, FileConfig};
+use crate::database::DuckDbExecutor;
+use crate::models::query_result::{
+    QueryErrorCode, QueryInterval, QueryParam, QueryValue, ScriptErrorMode, StatementOutcome,
+    TypedParam,
+};
+use crate::services::query_executor::QueryExecutor;
+use chrono::{NaiveDate, NaiveTime};
+use std::time::Duration;
+
+/// docker/duckdb の型確認用フィクスチャ
+const TYPE_TEST_SQL: &str = include_str!("../../../docker/duckdb/type-test.sql");
+
+async fn type_test_executor(dir: &tempfile::TempDir) -> DuckDbExecutor {
+    let path = dir.path().join("type-test.duckdb");
+    let connection = ConnectionInfo::new(
+        "Type Test".to_string(),
+        DatabaseType::DuckDB,
+        ConnectionConfig::File(FileConfig {
+            file_path: path.to_string_lossy().to_string(),
+            readonly: false,
+        }),
+    );
+    let executor = DuckDbExecutor::new(&connection).await.unwrap();
+    let results = executor
+        .execute_script(
+            TYPE_TEST_SQL,
+            ScriptErrorMode::StopOnError,
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+    assert!(
+        results.len() == 2
+            && results
+                .iter()
+                .all(|r| !matches!(r.outcome, StatementOutcome::Error { .. })),
+        "{:?}",
+        results
+    );
+    executor
+}
+
+async fn type_test_rows(sql: &str) -> (Vec<String>, Vec<Vec<QueryValue>>) {
+    let dir = tempfile::TempDir::new().unwrap();
+    let executor = type_test_executor(&dir).await;
+    let result = executor.execute(sql).await.unwrap();
+    (
+        result.columns.into_iter().map(|c| c.data_type).collect(),
+        result.rows.into_iter().map(|row| row.values).collect(),
+    )
+}
+
+#[tokio::test]
+async fn test_duckdb_scalar_types() {
+    let (types, rows) = type_test_rows(
+        "SELECT bool_val, ubig_val, huge_val, dec_val, dbl_val, uuid_val, blob_val \
+         FROM type_test ORDER BY id",
+    )
+    .await;
+
+    assert_eq!(
+        types,
+        vec![
+            "BOOLEAN",
+            "UBIGINT",
+            "HUGEINT",
+            "DECIMAL(10,2)",
+            "DOUBLE",
+            "UUID",
+            "BLOB"
+        ]
+    );
+    assert_eq!(
+        rows[0],
+        vec![
+            QueryValue::Bool(true),
+            QueryValue::Int(42),
+            QueryValue::Int(42),
+            QueryValue::Decimal("1234.50".to_string()),
+            QueryValue::Float(1.5),
+            QueryValue::Uuid(
+                uuid::Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap()
+            ),
+            QueryValue::Bytes(vec![0x00, 0xFF]),
+        ]
+    );
+    // i64に収まらない値は桁を落とさず Decimal になる
+    assert_eq!(
+        rows[1][..4],
+        [
+            QueryValue::Bool(false),
+            QueryValue::Decimal("18446744073709551615".to_string()),
+            QueryValue::Decimal("170141183460469231731687303715884105727".to_string()),
+            QueryValue::Decimal("-0.01".to_string()),
+        ]
+    );
+    assert!(rows[2].iter().all(|v| *v == QueryValue::Null));
+}
+
+#[tokio::test]
+async fn test_duckdb_temporal_types() {
+    let (types, rows) = type_test_rows(
+        "SELECT date_val, time_val, ts_val, tstz_val, iv_val FROM type_test ORDER BY id",
+    )
+    .await;
+    let date = NaiveDate::from_ymd_opt(2026, 3, 15).unwrap();
+    let time = NaiveTime::from_hms_opt(10, 0, 0).unwrap();
+
+    assert_eq!(types[3], "TIMESTAMP WITH TIME ZONE");
+    assert_eq!(rows[0][0], QueryValue::Date(date));
+    assert_eq!(rows[0][1], QueryValue::Time(time));
+    assert_eq!(rows[0][2], QueryValue::Timestamp(date.and_time(time)));
+    // TIMESTAMPTZ はUTCで返る
+    match &rows[0][3] {
+        QueryValue::TimestampTz(ts) => {
+            assert_eq!(ts.naive_utc(), date.and_hms_opt(1, 0, 0).unwrap());
+        }
+        other => panic!("unexpected value: {:?}", other),
+    }
+    assert_eq!(
+        rows[0][4],
+        QueryValue::Interval(QueryInterval {
+            months: 1,
+            days: 2,
+            microseconds: 3_000_000,
+        })
+    );
+    assert_eq!(
+        rows[1][1],
+        QueryValue::Time(NaiveTime::from_hms_micro_opt(23, 59, 59, 999_999).unwrap())
+    );
+}
+
+#[tokio::test]
+async fn test_duckdb_nested_types() {
+    let (types, rows) =
+        type_test_rows("SELECT list_val, struct_val, map_val FROM type_test ORDER BY id").await;
+
+    assert_eq!(
+        types,
+        vec![
+            "INTEGER[]",
+            "STRUCT(a INTEGER, b VARCHAR)",
+            "MAP(VARCHAR, INTEGER)"
+        ]
+    );
+    assert_eq!(
+        rows[0],
+        vec![
+            QueryValue::Array(vec![
+                QueryValue::Int(1),
+                QueryValue::Int(2),
+                QueryValue::Int(3)
+            ]),
+            QueryValue::Json(serde_json::json!({"a": 1, "b": "x"})),
+            QueryValue::Json(serde_json::json!({"k": 1})),
+        ]
+    );
+    assert_eq!(
+        rows[1],
+        vec![
+            QueryValue::Array(vec![]),
+            QueryValue::Json(serde_json::json!({"a": null, "b": null})),
+            QueryValue::Json(serde_json::json!({})),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_duckdb_mutation_and_errors() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let executor = type_test_executor(&dir).await;
+
+    let result = executor
+        .execute_mutation("UPDATE type_test SET label = 'x' WHERE id <= 2")
+        .await
+        .unwrap();
+    assert_eq!(result.affected_rows, 2);
+
+    // 文字列中の RETURNING は結果セットを返す文とみなさない
+    let results = executor
+        .execute_script(
+            "UPDATE type_test SET label = 'RETURNING' WHERE id = 1;\n\
+             DELETE FROM type_test WHERE id = 3 RETURNING id",
+            ScriptErrorMode::StopOnError,
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+    assert!(
+        matches!(
+            results[0].outcome,
+            StatementOutcome::Affected { affected_rows: 1 }
+        ),
+        "{:?}",
+        results
+    );
+    assert!(
+        matches!(&results[1].outcome, StatementOutcome::ResultSet { result } if result.row_count == 1),
+        "{:?}",
+        results
+    );
+
+    let err = executor.execute("SELECT * FROM missing").await.unwrap_err();
+    assert!(
+        matches!(err.code, QueryErrorCode::TableNotFound),
+        "{:?}",
+        err
+    );
+    let err = executor
+        .execute("INSERT INTO type_test (id) VALUES (1)")
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err.code, QueryErrorCode::UniqueViolation),
+        "{:?}",
+        err
+    );
+}
+
+#[tokio::test]
+async fn test_duckdb_typed_params() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let executor = type_test_executor(&dir).await;
+
+    let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+    let params = vec![
+        QueryParam::Typed(TypedParam::Date(date)),
+        QueryParam::Typed(TypedParam::Timestamp(
+            date.and_time(NaiveTime::from_hms_opt(9, 30, 0).unwrap()),
+        )),
+    ];
+    let result = executor
+        .execute_with_params(
+            "SELECT ? = DATE '2024-03-15' AS same_date, \
+             ? = TIMESTAMP '2024-03-15 09:30:00' AS same_timestamp",
+            &params,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        result.rows[0].values,
+        vec![QueryValue::Bool(true), QueryValue::Bool(true)]
+    );
+}
+
+#[tokio::test]
+async fn test_duckdb_opens_parquet_file_as_view() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let executor = type_test_executor(&dir).await;
+    let parquet = dir.path().join("events.parquet");
+    executor
+        .execute_mutation(&format!(
+            "COPY (SELECT id, label FROM type_test ORDER BY id) TO '{}' (FORMAT parquet)",
+            parquet.to_string_lossy()
+        ))
+        .await
+        .unwrap();
+
+    // ファイル名と同名のビューとして参照できる
+    let connection = ConnectionInfo::new(
+        "Parquet".to_string(),
+        DatabaseType::DuckDB,
+        ConnectionConfig::File(FileConfig {
+            file_path: parquet.to_string_lossy().to_string(),
+            readonly: true,
+        }),
+    );
+    let parquet_executor = DuckDbExecutor::new(&connection).await.unwrap();
+    let result = parquet_executor
+        .execute("SELECT count(*) AS n, max(label) AS label FROM events")
+        .await
+        .unwrap();
+    assert_eq!(
+        result.rows[0].values,
+        vec![QueryValue::Int(3), QueryValue::String("通常値".to_string())]
+    );
+}
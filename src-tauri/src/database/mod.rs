@@ -1,13 +1,47 @@
+pub mod duckdb_executor;
+pub mod duckdb_inspector;
+pub mod duckdb_types;
+pub mod mssql_executor;
+pub mod mssql_inspector;
+pub mod mssql_types;
 pub mod mysql_executor;
 pub mod mysql_inspector;
+pub mod mysql_types;
 pub mod postgresql_executor;
 pub mod postgresql_inspector;
 pub mod sqlite_executor;
 pub mod sqlite_inspector;
+pub mod sqlite_types;
+pub mod wkb;
 
+pub use duckdb_executor::*;
+pub use duckdb_inspector::*;
+pub use mssql_executor::*;
+pub use mssql_inspector::*;
 pub use mysql_executor::*;
 pub use mysql_inspector::*;
 pub use postgresql_executor::*;
 pub use postgresql_inspector::*;
 pub use sqlite_executor::*;
 pub use sqlite_inspector::*;
+
+#[cfg(test)]
+mod duckdb_inspector_test;
+
+#[cfg(test)]
+mod duckdb_types_test;
+
+#[cfg(test)]
+mod mssql_inspector_test;
+
+#[cfg(test)]
+mod mssql_types_test;
+
+#[cfg(test)]
+mod mysql_inspector_test;
+
+#[cfg(test)]
+mod mysql_types_test;
+
+#[cfg(test)]
+mod sqlite_types_test;
@@ -0,0 +1,857 @@
+use crate::connection::{describe_mssql_tls_error, ConnectionInfo, SshTunnel};
+use crate::database::mssql_types::{
+    bind_mssql_params, convert_mssql_value, is_inexact_money, mssql_type_name,
+    MONEY_PRECISION_WARNING,
+};
+use crate::models::connection_pool::PoolSettings;
+use crate::models::mutation_result::MutationResult;
+use crate::models::query_result::{
+    QueryError, QueryErrorCode, QueryParam, QueryResult, QueryResultColumn, QueryResultRow,
+    ScriptErrorMode, StatementOutcome, StatementResult,
+};
+use crate::services::query_executor::{
+    current_query_id, leading_keyword, locate_statement_error,
+    multiple_statements_with_params_error, returns_rows, CursorEvent, CursorSender, PoolStatus,
+    QueryCursor, QueryExecutor, RunningQueries, RunningQueryGuard, TransactionSession,
+};
+use crate::sql::statement_splitter::StatementSplitter;
+use async_trait::async_trait;
+use futures_util::TryStreamExt;
+use sqlparser::dialect::MsSqlDialect;
+use sqlparser::tokenizer::{Token, Tokenizer};
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::{Duration, Instant};
+use tiberius::error::Error as TdsError;
+use tiberius::{Client, Column, Config, Query, QueryItem, QueryStream, Row};
+use tokio::net::TcpStream;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio_util::compat::{Compat, TokioAsyncWriteCompatExt};
+
+/// SQL Serverのクライアント
+pub type MssqlClient = Client<Compat<TcpStream>>;
+
+/// SQL Serverに接続する
+///
+/// Azure SQLなどで別のホストへの転送を指示された場合は、指示された接続先に接続し直す
+pub async fn connect_mssql(config: &Config) -> tiberius::Result<MssqlClient> {
+    match connect_to(config.clone()).await {
+        Err(TdsError::Routing { host, port }) => {
+            let mut config = config.clone();
+            config.host(host);
+            config.port(port);
+            connect_to(config).await
+        }
+        result => result,
+    }
+}
+
+async fn connect_to(config: Config) -> tiberius::Result<MssqlClient> {
+    let tcp = TcpStream::connect(config.get_addr()).await?;
+    tcp.set_nodelay(true)?;
+    Client::connect(config, tcp.compat_write()).await
+}
+
+/// SQL Serverのエラーを変換する
+fn map_error(err: TdsError) -> QueryError {
+    match &err {
+        TdsError::Server(token) => QueryError {
+            code: parse_mssql_error(token.code()),
+            message: token.message().to_string(),
+            details: None,
+            native_code: Some(token.code().to_string()),
+        },
+        TdsError::Io { .. } | TdsError::Tls(_) => QueryError {
+            code: QueryErrorCode::ConnectionFailed,
+            message: describe_mssql_tls_error(&err).unwrap_or_else(|| err.to_string()),
+            details: None,
+            native_code: None,
+        },
+        TdsError::Conversion(_) => QueryError {
+            code: QueryErrorCode::InvalidDataType,
+            message: err.to_string(),
+            details: None,
+            native_code: None,
+        },
+        _ => QueryError {
+            code: QueryErrorCode::Unknown,
+            message: err.to_string(),
+            details: None,
+            native_code: None,
+        },
+    }
+}
+
+fn parse_mssql_error(code: u32) -> QueryErrorCode {
+    match code {
+        // Connection
+        18456 => QueryErrorCode::AuthenticationFailed,
+        4060 | 911 => QueryErrorCode::DatabaseNotFound,
+
+        // Syntax
+        102 | 156 | 105 => QueryErrorCode::SyntaxError,
+
+        // Objects
+        208 => QueryErrorCode::TableNotFound,
+        207 => QueryErrorCode::ColumnNotFound,
+        2760 | 15151 => QueryErrorCode::SchemaNotFound,
+
+        // Permissions
+        229 | 230 | 262 | 3906 => QueryErrorCode::PermissionDenied,
+
+        // Constraints
+        2627 | 2601 => QueryErrorCode::UniqueViolation,
+        547 => QueryErrorCode::ForeignKeyViolation,
+        515 => QueryErrorCode::NotNullViolation,
+
+        // Data
+        2628 | 8152 => QueryErrorCode::DataTruncation,
+        8134 => QueryErrorCode::DivisionByZero,
+        245 | 8114 | 241 => QueryErrorCode::InvalidDataType,
+
+        // Transactions (deadlock victim, lock timeout)
+        1205 | 1222 => QueryErrorCode::TransactionError,
+
+        _ => QueryErrorCode::Unknown,
+    }
+}
+
+/// 結果セットを返す文か（ストアドプロシージャの呼び出し・OUTPUT句を含む）
+fn returns_result_set(statement: &str) -> bool {
+    returns_rows(statement)
+        || matches!(leading_keyword(statement).as_str(), "EXEC" | "EXECUTE")
+        || has_output_clause(statement)
+}
+
+/// OUTPUT句を持つ文か
+///
+/// sqlparser はOUTPUT句を構文解析できないため、字句解析した結果から引用符なしの `OUTPUT` を探す
+/// （文字列リテラル・コメント・引用符付きの識別子は対象外）
+fn has_output_clause(statement: &str) -> bool {
+    let Ok(tokens) = Tokenizer::new(&MsSqlDialect {}, statement).tokenize() else {
+        return false;
+    };
+    tokens.iter().any(|token| {
+        matches!(token, Token::Word(word)
+            if word.quote_style.is_none() && word.value.eq_ignore_ascii_case("OUTPUT"))
+    })
+}
+
+/// 影響行数を返すデータ変更文か
+fn is_data_change(statement: &str) -> bool {
+    matches!(
+        leading_keyword(statement).as_str(),
+        "INSERT" | "UPDATE" | "DELETE" | "MERGE"
+    )
+}
+
+fn empty_result() -> QueryResult {
+    QueryResult {
+        columns: vec![],
+        rows: vec![],
+        row_count: 0,
+        execution_time_ms: 0,
+        warnings: vec![],
+    }
+}
+
+fn build_columns(columns: &[Column]) -> Vec<QueryResultColumn> {
+    columns
+        .iter()
+        .map(|col| QueryResultColumn {
+            name: col.name().to_string(),
+            data_type: mssql_type_name(col.column_type()).to_string(),
+            nullable: true,
+        })
+        .collect()
+}
+
+fn convert_row(row: Row) -> QueryResultRow {
+    let types: Vec<_> = row.columns().iter().map(Column::column_type).collect();
+    let values = row
+        .into_iter()
+        .zip(types)
+        .map(|(data, column_type)| convert_mssql_value(data, column_type))
+        .collect();
+
+    QueryResultRow { values }
+}
+
+/// 最初の結果セットを読み出す（残りの結果セットは読み捨てる）
+async fn collect_first_result(mut stream: QueryStream<'_>) -> tiberius::Result<QueryResult> {
+    let mut result = empty_result();
+    while let Some(item) = stream.try_next().await? {
+        match item {
+            QueryItem::Metadata(meta) if meta.result_index() == 0 => {
+                result.columns = build_columns(meta.columns());
+            }
+            QueryItem::Row(row) if row.result_index() == 0 => {
+                let inexact = row
+                    .cells()
+                    .any(|(column, data)| is_inexact_money(data, column.column_type()));
+                if inexact && result.warnings.is_empty() {
+                    result.warnings.push(MONEY_PRECISION_WARNING.to_string());
+                }
+                result.rows.push(convert_row(row));
+            }
+            _ => {}
+        }
+    }
+    result.row_count = result.rows.len();
+    Ok(result)
+}
+
+/// 1文を実行し、結果と影響行数を返す（実行時間は呼び出し側で設定）
+///
+/// パラメータ付きの文とデータ変更文は `sp_executesql` で、それ以外はバッチとして実行する
+/// （USE・SETの効果をセッションに残すため）
+async fn run_statement(
+    client: &mut MssqlClient,
+    sql: &str,
+    params: &[QueryParam],
+) -> tiberius::Result<(QueryResult, u64)> {
+    if returns_result_set(sql) {
+        let stream = if params.is_empty() {
+            client.simple_query(sql).await?
+        } else {
+            let mut query = Query::new(sql);
+            bind_mssql_params(&mut query, params);
+            query.query(client).await?
+        };
+        let result = collect_first_result(stream).await?;
+        let row_count = result.row_count as u64;
+        return Ok((result, row_count));
+    }
+
+    if is_data_change(sql) || !params.is_empty() {
+        let mut query = Query::new(sql);
+        bind_mssql_params(&mut query, params);
+        let affected_rows = query.execute(client).await?.total();
+        return Ok((empty_result(), affected_rows));
+    }
+
+    client.simple_query(sql).await?.into_results().await?;
+    Ok((empty_result(), 0))
+}
+
+/// カーソル用に行をチャネルへ転送する。受信側が閉じられて途中でやめた場合は `false`
+async fn forward_rows(
+    client: &mut MssqlClient,
+    sql: &str,
+    sender: &CursorSender,
+) -> tiberius::Result<bool> {
+    let mut stream = client.simple_query(sql).await?;
+    while let Some(item) = stream.try_next().await? {
+        let event = match item {
+            QueryItem::Metadata(meta) if meta.result_index() == 0 => {
+                CursorEvent::Columns(build_columns(meta.columns()))
+            }
+            QueryItem::Row(row) if row.result_index() == 0 => CursorEvent::Row(convert_row(row)),
+            _ => continue,
+        };
+        if sender.send(Ok(event)).await.is_err() {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// 実行中のセッション（別の接続から `KILL` で停止する）
+pub struct MssqlSession {
+    spid: i16,
+    cancelled: AtomicBool,
+}
+
+/// プールが管理する1つの接続
+struct MssqlConnection {
+    client: MssqlClient,
+    session: Arc<MssqlSession>,
+    /// 文の実行中（完了前に中断された場合は `true` のまま残る）
+    busy: bool,
+    /// 通信エラーで使えなくなった
+    broken: bool,
+    /// コミット・ロールバックしていないトランザクションがある
+    in_transaction: bool,
+}
+
+impl MssqlConnection {
+    /// 文の実行に失敗した際のエラー（サーバーのエラーなら接続は引き続き使える）
+    fn fail(&mut self, err: TdsError) -> QueryError {
+        self.busy = false;
+        if matches!(err, TdsError::Server(_)) {
+            return map_error(err);
+        }
+
+        self.broken = true;
+        if self.session.cancelled.load(Ordering::SeqCst) {
+            return QueryError {
+                code: QueryErrorCode::QueryCancelled,
+                message: "Query was cancelled".to_string(),
+                details: None,
+                native_code: None,
+            };
+        }
+        map_error(err)
+    }
+
+    /// 1文を実行する
+    async fn statement(
+        &mut self,
+        sql: &str,
+        params: &[QueryParam],
+    ) -> Result<(QueryResult, u64), QueryError> {
+        if self.busy || self.broken {
+            return Err(unusable_connection_error());
+        }
+
+        self.busy = true;
+        match run_statement(&mut self.client, sql, params).await {
+            Ok(output) => {
+                self.busy = false;
+                Ok(output)
+            }
+            Err(e) => Err(self.fail(e)),
+        }
+    }
+
+    /// 複数文を順次実行し、最後の文の結果を返す（パラメータは単一文のみ）
+    async fn run_statements(
+        &mut self,
+        sql: &str,
+        params: &[QueryParam],
+    ) -> Result<QueryResult, QueryError> {
+        let statements = StatementSplitter::new("sqlserver").split(sql);
+        if statements.len() > 1 && !params.is_empty() {
+            return Err(multiple_statements_with_params_error());
+        }
+
+        let is_script = statements.len() > 1;
+        let mut last_result = empty_result();
+        for range in statements.iter() {
+            let (result, _) = self
+                .statement(range.text(sql), params)
+                .await
+                .map_err(|e| locate_statement_error(e, sql, range, is_script))?;
+            last_result = result;
+        }
+        Ok(last_result)
+    }
+
+    /// スクリプトの各文を順次実行し、文ごとの結果を返す
+    ///
+    /// タイムアウトした文は停止を待たずに打ち切るため、以降の文は実行しない
+    async fn execute_script(
+        &mut self,
+        sql: &str,
+        mode: ScriptErrorMode,
+        timeout: Duration,
+    ) -> Vec<StatementResult> {
+        let statements = StatementSplitter::new("sqlserver").split(sql);
+        let is_script = statements.len() > 1;
+        let mut results = Vec::with_capacity(statements.len());
+
+        for (index, range) in statements.iter().enumerate() {
+            let start = Instant::now();
+            let statement = range.text(sql);
+            let is_result_set = returns_result_set(statement);
+
+            let mut timed_out = false;
+            let outcome = match tokio::time::timeout(timeout, self.statement(statement, &[])).await
+            {
+                Ok(Ok((mut result, affected_rows))) => {
+                    if is_result_set {
+                        result.execution_time_ms = start.elapsed().as_millis() as u64;
+                        StatementOutcome::ResultSet { result }
+                    } else {
+                        StatementOutcome::Affected { affected_rows }
+                    }
+                }
+                Ok(Err(e)) => StatementOutcome::Error {
+                    error: locate_statement_error(e, sql, range, is_script),
+                },
+                Err(_) => {
+                    timed_out = true;
+                    StatementOutcome::Error {
+                        error: QueryError {
+                            code: QueryErrorCode::QueryTimeout,
+                            message: format!("Query timed out after {:?}", timeout),
+                            details: None,
+                            native_code: None,
+                        },
+                    }
+                }
+            };
+
+            let failed = matches!(outcome, StatementOutcome::Error { .. });
+            results.push(StatementResult {
+                index,
+                range: *range,
+                outcome,
+                execution_time_ms: start.elapsed().as_millis() as u64,
+            });
+
+            if timed_out || (failed && mode == ScriptErrorMode::StopOnError) {
+                break;
+            }
+        }
+
+        results
+    }
+}
+
+/// 前の文が中断された接続を使おうとした場合のエラー
+fn unusable_connection_error() -> QueryError {
+    QueryError {
+        code: QueryErrorCode::ConnectionFailed,
+        message: "Connection is no longer usable because a previous statement was interrupted"
+            .to_string(),
+        details: None,
+        native_code: None,
+    }
+}
+
+fn closed_error() -> QueryError {
+    QueryError {
+        code: QueryErrorCode::ConnectionFailed,
+        message: "Connection is closed".to_string(),
+        details: None,
+        native_code: None,
+    }
+}
+
+/// アイドル状態の接続
+struct IdleConnection {
+    conn: MssqlConnection,
+    since: Instant,
+}
+
+/// 接続プール
+///
+/// tiberiusはプールを持たないため、接続数の上限をセマフォで管理し、返却された接続を再利用する
+struct MssqlPool {
+    config: Config,
+    /// 新しい接続を開いたときに実行するSQL
+    init_sql: Option<String>,
+    idle: StdMutex<Vec<IdleConnection>>,
+    permits: Arc<Semaphore>,
+    idle_timeout: Duration,
+    /// 開いている接続数
+    size: AtomicU32,
+    closed: AtomicBool,
+}
+
+impl MssqlPool {
+    async fn acquire(self: &Arc<Self>) -> Result<PooledConnection, QueryError> {
+        if self.closed.load(Ordering::SeqCst) {
+            return Err(closed_error());
+        }
+        let permit = Arc::clone(&self.permits)
+            .acquire_owned()
+            .await
+            .map_err(|_| closed_error())?;
+
+        let conn = match self.take_idle() {
+            Some(conn) => conn,
+            None => self.open().await?,
+        };
+        Ok(PooledConnection {
+            conn: Some(conn),
+            pool: Arc::clone(self),
+            _permit: permit,
+        })
+    }
+
+    /// 期限内のアイドル接続を取り出す（期限切れの接続は閉じる）
+    fn take_idle(&self) -> Option<MssqlConnection> {
+        let mut idle = self.idle.lock().unwrap();
+        while let Some(entry) = idle.pop() {
+            if entry.since.elapsed() < self.idle_timeout {
+                return Some(entry.conn);
+            }
+            self.size.fetch_sub(1, Ordering::SeqCst);
+        }
+        None
+    }
+
+    /// 新しい接続を開き、セッションIDの取得と初期化SQLの実行を行う
+    async fn open(&self) -> Result<MssqlConnection, QueryError> {
+        let connect_error = |e: TdsError| {
+            let e = map_error(e);
+            QueryError {
+                code: match e.code {
+                    QueryErrorCode::AuthenticationFailed | QueryErrorCode::DatabaseNotFound => {
+                        e.code
+                    }
+                    _ => QueryErrorCode::ConnectionFailed,
+                },
+                message: format!("Failed to connect: {}", e.message),
+                ..e
+            }
+        };
+
+        let mut client = connect_mssql(&self.config).await.map_err(connect_error)?;
+        let spid = client
+            .simple_query("SELECT CAST(@@SPID AS smallint)")
+            .await
+            .map_err(connect_error)?
+            .into_row()
+            .await
+            .map_err(connect_error)?
+            .and_then(|row| row.get::<i16, _>(0))
+            .unwrap_or_default();
+        if let Some(sql) = &self.init_sql {
+            client
+                .simple_query(sql.as_str())
+                .await
+                .map_err(map_error)?
+                .into_results()
+                .await
+                .map_err(map_error)?;
+        }
+
+        self.size.fetch_add(1, Ordering::SeqCst);
+        Ok(MssqlConnection {
+            client,
+            session: Arc::new(MssqlSession {
+                spid,
+                cancelled: AtomicBool::new(false),
+            }),
+            busy: false,
+            broken: false,
+            in_transaction: false,
+        })
+    }
+
+    /// 返却された接続をアイドルに戻す
+    ///
+    /// 文の実行中に破棄された接続はサーバー側の実行を止めてから閉じる。
+    /// 通信エラー・未完了のトランザクションがある接続は閉じる（トランザクションはサーバーがロールバックする）
+    fn release(self: &Arc<Self>, conn: MssqlConnection) {
+        if conn.busy && !conn.broken {
+            if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+                let pool = Arc::clone(self);
+                // 停止するまで接続を保持し、同じセッションIDが別の接続に再利用されないようにする
+                runtime.spawn(async move {
+                    let _ = pool.kill(conn.session.spid).await;
+                    drop(conn);
+                    pool.size.fetch_sub(1, Ordering::SeqCst);
+                });
+                return;
+            }
+        }
+
+        if conn.busy || conn.broken || conn.in_transaction || self.closed.load(Ordering::SeqCst) {
+            self.size.fetch_sub(1, Ordering::SeqCst);
+            return;
+        }
+        self.idle.lock().unwrap().push(IdleConnection {
+            conn,
+            since: Instant::now(),
+        });
+    }
+
+    /// 別の接続からセッションを停止する（プールの上限に関係なく専用の接続を使う）
+    async fn kill(&self, spid: i16) -> tiberius::Result<()> {
+        let mut client = connect_mssql(&self.config).await?;
+        client
+            .simple_query(format!("KILL {}", spid))
+            .await?
+            .into_results()
+            .await?;
+        Ok(())
+    }
+
+    fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+        let closed = std::mem::take(&mut *self.idle.lock().unwrap());
+        self.size.fetch_sub(closed.len() as u32, Ordering::SeqCst);
+        self.permits.close();
+    }
+}
+
+/// プールから借りた接続（破棄されるとプールに返却される）
+struct PooledConnection {
+    conn: Option<MssqlConnection>,
+    pool: Arc<MssqlPool>,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl Deref for PooledConnection {
+    type Target = MssqlConnection;
+
+    fn deref(&self) -> &Self::Target {
+        self.conn
+            .as_ref()
+            .expect("connection is present until dropped")
+    }
+}
+
+impl DerefMut for PooledConnection {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.conn
+            .as_mut()
+            .expect("connection is present until dropped")
+    }
+}
+
+impl Drop for PooledConnection {
+    fn drop(&mut self) {
+        if let Some(conn) = self.conn.take() {
+            self.pool.release(conn);
+        }
+    }
+}
+
+pub struct MssqlExecutor {
+    pool: Arc<MssqlPool>,
+    /// SSHトンネル（プールが使う間は開いたままにする）
+    _tunnel: Option<Arc<SshTunnel>>,
+    /// 実行中クエリのセッション（サーバー側キャンセル用）
+    running: RunningQueries<Arc<MssqlSession>>,
+}
+
+impl MssqlExecutor {
+    pub async fn new(
+        connection: &ConnectionInfo,
+        password: Option<&str>,
+    ) -> Result<Self, QueryError> {
+        Self::with_pool_settings(connection, password, &PoolSettings::default()).await
+    }
+
+    /// プール設定を指定して接続する
+    pub async fn with_pool_settings(
+        connection: &ConnectionInfo,
+        password: Option<&str>,
+        settings: &PoolSettings,
+    ) -> Result<Self, QueryError> {
+        let mut config = connection.mssql_config(password).map_err(|e| QueryError {
+            code: QueryErrorCode::ConnectionFailed,
+            message: format!("Failed to build connection options: {}", e),
+            details: None,
+            native_code: None,
+        })?;
+
+        let tunnel = connection.open_ssh_tunnel().await.map_err(|e| QueryError {
+            code: QueryErrorCode::ConnectionFailed,
+            message: e.to_string(),
+            details: None,
+            native_code: None,
+        })?;
+        if let Some(tunnel) = &tunnel {
+            tunnel.apply_mssql(&mut config);
+        }
+
+        let init_sql = connection.session_init_sql().map_err(|e| QueryError {
+            code: QueryErrorCode::ConnectionFailed,
+            message: format!("Invalid session settings: {}", e),
+            details: None,
+            native_code: None,
+        })?;
+
+        let pool = Arc::new(MssqlPool {
+            config,
+            init_sql,
+            idle: StdMutex::new(Vec::new()),
+            permits: Arc::new(Semaphore::new(settings.max_connections.max(1) as usize)),
+            idle_timeout: Duration::from_secs(settings.idle_timeout_seconds),
+            size: AtomicU32::new(0),
+            closed: AtomicBool::new(false),
+        });
+
+        // 接続できることを確認し、最初の接続をプールに残す
+        drop(pool.acquire().await?);
+
+        Ok(Self {
+            pool,
+            _tunnel: tunnel,
+            running: RunningQueries::new(),
+        })
+    }
+
+    /// 現在のクエリIDがあれば、接続のセッションを実行中クエリとして登録する
+    fn track_running(
+        running: &RunningQueries<Arc<MssqlSession>>,
+        conn: &MssqlConnection,
+    ) -> Option<RunningQueryGuard<Arc<MssqlSession>>> {
+        current_query_id().map(|query_id| running.register(query_id, Arc::clone(&conn.session)))
+    }
+}
+
+#[async_trait]
+impl QueryExecutor for MssqlExecutor {
+    async fn execute(&self, sql: &str) -> Result<QueryResult, QueryError> {
+        self.execute_with_params(sql, &[]).await
+    }
+
+    async fn execute_with_params(
+        &self,
+        sql: &str,
+        params: &[QueryParam],
+    ) -> Result<QueryResult, QueryError> {
+        let start = Instant::now();
+
+        // 文単位に分割して同じ接続で順次実行し、最後の文の結果を返す（パラメータは単一文のみ）
+        let mut conn = self.pool.acquire().await?;
+        let _running = Self::track_running(&self.running, &conn);
+        let mut result = conn.run_statements(sql, params).await?;
+
+        result.execution_time_ms = start.elapsed().as_millis() as u64;
+        Ok(result)
+    }
+
+    async fn execute_mutation(&self, sql: &str) -> Result<MutationResult, QueryError> {
+        let start = Instant::now();
+        let mut conn = self.pool.acquire().await?;
+        let _running = Self::track_running(&self.running, &conn);
+        let (_, affected_rows) = conn.statement(sql, &[]).await?;
+
+        Ok(MutationResult {
+            affected_rows,
+            execution_time_ms: start.elapsed().as_millis() as u64,
+            undo: None,
+        })
+    }
+
+    async fn execute_with_timeout(
+        &self,
+        sql: &str,
+        timeout: Duration,
+    ) -> Result<QueryResult, QueryError> {
+        tokio::time::timeout(timeout, self.execute(sql))
+            .await
+            .map_err(|_| QueryError {
+                code: QueryErrorCode::QueryTimeout,
+                message: format!("Query timed out after {:?}", timeout),
+                details: None,
+                native_code: None,
+            })?
+    }
+
+    async fn execute_script(
+        &self,
+        sql: &str,
+        mode: ScriptErrorMode,
+        timeout: Duration,
+    ) -> Result<Vec<StatementResult>, QueryError> {
+        let mut conn = self.pool.acquire().await?;
+        let _running = Self::track_running(&self.running, &conn);
+        Ok(conn.execute_script(sql, mode, timeout).await)
+    }
+
+    async fn open_cursor(&self, sql: &str, buffer_size: usize) -> Result<QueryCursor, QueryError> {
+        let mut conn = self.pool.acquire().await?;
+        let sql = sql.trim().trim_end_matches(';').to_string();
+
+        Ok(QueryCursor::spawn(buffer_size, move |sender| async move {
+            // カーソルが途中で破棄された場合は実行中のまま返却され、サーバー側の実行も止める
+            conn.busy = true;
+            match forward_rows(&mut conn.client, &sql, &sender).await {
+                Ok(true) => conn.busy = false,
+                Ok(false) => {}
+                Err(e) => {
+                    let error = conn.fail(e);
+                    let _ = sender.send(Err(error)).await;
+                }
+            }
+        }))
+    }
+
+    async fn cancel(&self, query_id: &str) -> Result<bool, QueryError> {
+        let session = match self.running.get(query_id) {
+            Some(session) => session,
+            None => return Ok(false),
+        };
+
+        // SQL Serverは実行中の文だけを止められないため、セッションごと停止する
+        // （実行していたトランザクションはロールバックされ、接続は破棄される）
+        session.cancelled.store(true, Ordering::SeqCst);
+        self.pool.kill(session.spid).await.map_err(map_error)?;
+        Ok(true)
+    }
+
+    async fn begin_transaction(&self) -> Result<Box<dyn TransactionSession>, QueryError> {
+        let mut conn = self.pool.acquire().await?;
+        conn.statement("BEGIN TRANSACTION", &[]).await?;
+        conn.in_transaction = true;
+        Ok(Box::new(MssqlTransaction {
+            conn,
+            running: self.running.clone(),
+        }))
+    }
+
+    async fn ping(&self) -> Result<(), QueryError> {
+        let mut conn = self.pool.acquire().await?;
+        conn.statement("SELECT 1", &[]).await.map(|_| ())
+    }
+
+    fn pool_status(&self) -> PoolStatus {
+        PoolStatus {
+            size: self.pool.size.load(Ordering::SeqCst),
+            idle: self.pool.idle.lock().unwrap().len() as u32,
+        }
+    }
+
+    async fn close(&self) -> Result<(), QueryError> {
+        self.pool.close();
+        Ok(())
+    }
+}
+
+/// SQL Serverのトランザクションセッション（専用接続を保持）
+///
+/// コミット・ロールバックされずに破棄された場合は接続を閉じ、サーバー側でロールバックされる
+pub struct MssqlTransaction {
+    conn: PooledConnection,
+    running: RunningQueries<Arc<MssqlSession>>,
+}
+
+#[async_trait]
+impl TransactionSession for MssqlTransaction {
+    async fn execute_with_params(
+        &mut self,
+        sql: &str,
+        params: &[QueryParam],
+    ) -> Result<QueryResult, QueryError> {
+        let start = Instant::now();
+        let _running = MssqlExecutor::track_running(&self.running, &self.conn);
+        let mut result = self.conn.run_statements(sql, params).await?;
+
+        result.execution_time_ms = start.elapsed().as_millis() as u64;
+        Ok(result)
+    }
+
+    async fn execute_mutation(&mut self, sql: &str) -> Result<MutationResult, QueryError> {
+        let start = Instant::now();
+        let _running = MssqlExecutor::track_running(&self.running, &self.conn);
+        let (_, affected_rows) = self.conn.statement(sql, &[]).await?;
+
+        Ok(MutationResult {
+            affected_rows,
+            execution_time_ms: start.elapsed().as_millis() as u64,
+            undo: None,
+        })
+    }
+
+    async fn execute_script(
+        &mut self,
+        sql: &str,
+        mode: ScriptErrorMode,
+        timeout: Duration,
+    ) -> Vec<StatementResult> {
+        let _running = MssqlExecutor::track_running(&self.running, &self.conn);
+        self.conn.execute_script(sql, mode, timeout).await
+    }
+
+    async fn commit(mut self: Box<Self>) -> Result<(), QueryError> {
+        self.conn.statement("COMMIT TRANSACTION", &[]).await?;
+        self.conn.in_transaction = false;
+        Ok(())
+    }
+
+    async fn rollback(mut self: Box<Self>) -> Result<(), QueryError> {
+        self.conn.statement("ROLLBACK TRANSACTION", &[]).await?;
+        self.conn.in_transaction = false;
+        Ok(())
+    }
+}
@@ -0,0 +1,776 @@
+use crate::connection::{describe_mssql_tls_error, ConnectionConfig, ConnectionInfo, SshTunnel};
+use crate::database::mssql_executor::{connect_mssql, MssqlClient};
+use crate::models::database_structure::*;
+use crate::services::database_inspector::{DatabaseInspector, TableForeignKey};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tiberius::{Query, Row};
+use tokio::sync::Mutex;
+
+/// SQL Serverのインスペクター（`sys.*` カタログビューから取得する）
+pub struct MssqlInspector {
+    client: Mutex<MssqlClient>,
+    database_name: String,
+    /// SSHトンネル（接続が使う間は開いたままにする）
+    _tunnel: Option<Arc<SshTunnel>>,
+}
+
+/// システムスキーマの判定式（`db_owner` などの固定ロールのスキーマはIDが16384以上）
+const IS_SYSTEM_SCHEMA: &str = "CAST(CASE WHEN s.name IN ('sys', 'INFORMATION_SCHEMA', 'guest') OR s.schema_id >= 16384 THEN 1 ELSE 0 END AS bit)";
+
+/// 文字列の列を取り出す
+fn text(row: &Row, column: &str) -> Option<String> {
+    row.try_get::<&str, _>(column)
+        .ok()
+        .flatten()
+        .map(str::to_string)
+}
+
+/// BIT の列を取り出す
+fn flag(row: &Row, column: &str) -> bool {
+    row.try_get::<bool, _>(column)
+        .ok()
+        .flatten()
+        .unwrap_or(false)
+}
+
+/// 空のコメントは `None` にする
+fn comment(row: &Row) -> Option<String> {
+    text(row, "comment").filter(|c| !c.is_empty())
+}
+
+/// 参照動作を他のデータベースと同じ表記にする（`NO_ACTION` → `NO ACTION`）
+fn referential_action(row: &Row, column: &str) -> String {
+    text(row, column)
+        .map(|action| action.replace('_', " "))
+        .unwrap_or_else(|| "NO ACTION".to_string())
+}
+
+/// 列の型を長さ・精度付きで表示する（`nvarchar(50)`、`decimal(10,2)` など）
+///
+/// `max_length` はバイト数のため、Unicode文字列型は2で割る。`-1` は `max`
+pub fn format_mssql_column_type(
+    data_type: &str,
+    max_length: i16,
+    precision: u8,
+    scale: u8,
+) -> String {
+    let length = |bytes: i16| {
+        if bytes == -1 {
+            "max".to_string()
+        } else {
+            bytes.to_string()
+        }
+    };
+
+    match data_type {
+        "char" | "varchar" | "binary" | "varbinary" => {
+            format!("{}({})", data_type, length(max_length))
+        }
+        "nchar" | "nvarchar" => {
+            let chars = if max_length == -1 { -1 } else { max_length / 2 };
+            format!("{}({})", data_type, length(chars))
+        }
+        "decimal" | "numeric" => format!("{}({},{})", data_type, precision, scale),
+        "datetime2" | "datetimeoffset" | "time" => format!("{}({})", data_type, scale),
+        _ => data_type.to_string(),
+    }
+}
+
+/// デフォルト制約の定義を他のデータベースと同じ表記にする
+///
+/// `sys.default_constraints` は `((0))` や `(N'active')` のように括弧で囲んだ式を返すため、
+/// 外側の括弧と文字列リテラルの引用符を外す
+pub fn normalize_mssql_default(value: Option<String>) -> Option<String> {
+    let mut value = value?.trim().to_string();
+    while let Some(inner) = strip_outer_parens(&value) {
+        value = inner.to_string();
+    }
+
+    let literal = value.strip_prefix('N').unwrap_or(&value);
+    match literal
+        .strip_prefix('\'')
+        .and_then(|inner| inner.strip_suffix('\''))
+    {
+        Some(inner) if !inner.replace("''", "").contains('\'') => Some(inner.replace("''", "'")),
+        _ => Some(value),
+    }
+}
+
+/// 式全体を囲む括弧を1組外す（`(a) + (b)` のように全体を囲んでいない場合は `None`）
+fn strip_outer_parens(value: &str) -> Option<&str> {
+    let inner = value.strip_prefix('(')?.strip_suffix(')')?;
+    let mut depth = 0i32;
+    let mut in_string = false;
+    for c in inner.chars() {
+        match c {
+            '\'' => in_string = !in_string,
+            '(' if !in_string => depth += 1,
+            ')' if !in_string => {
+                depth -= 1;
+                if depth < 0 {
+                    return None;
+                }
+            }
+            _ => {}
+        }
+    }
+    (depth == 0).then_some(inner)
+}
+
+impl MssqlInspector {
+    pub async fn new(connection: &ConnectionInfo, password: Option<&str>) -> Result<Self, String> {
+        let mut config = connection
+            .mssql_config(password)
+            .map_err(|e| format!("Failed to build connection options: {}", e))?;
+
+        let tunnel = connection
+            .open_ssh_tunnel()
+            .await
+            .map_err(|e| e.to_string())?;
+        if let Some(tunnel) = &tunnel {
+            tunnel.apply_mssql(&mut config);
+        }
+
+        let client = connect_mssql(&config).await.map_err(|e| {
+            describe_mssql_tls_error(&e).unwrap_or_else(|| format!("Failed to connect: {}", e))
+        })?;
+
+        let database_name = match &connection.connection {
+            ConnectionConfig::Network(cfg) => cfg.database.clone(),
+            _ => return Err("Invalid connection config for SQL Server".to_string()),
+        };
+
+        Ok(Self {
+            client: Mutex::new(client),
+            database_name,
+            _tunnel: tunnel,
+        })
+    }
+
+    /// クエリを実行し、最初の結果セットの行を返す（パラメータは `@P1` から順に対応する）
+    async fn query(&self, sql: &str, params: &[&str]) -> tiberius::Result<Vec<Row>> {
+        let mut client = self.client.lock().await;
+        let mut query = Query::new(sql);
+        for param in params {
+            query.bind(*param);
+        }
+        let rows = query.query(&mut *client).await?.into_first_result().await?;
+        Ok(rows)
+    }
+
+    /// スキーマ一覧を既定のスキーマ（通常は `dbo`）を先頭にして取得
+    async fn get_schema_names(&self) -> Result<Vec<(String, bool)>, String> {
+        let query = format!(
+            r#"
+            SELECT s.name AS name, {} AS is_system
+            FROM sys.schemas s
+            ORDER BY
+                CASE WHEN s.name = SCHEMA_NAME() THEN 0 ELSE 1 END,
+                s.name
+            "#,
+            IS_SYSTEM_SCHEMA
+        );
+
+        let rows = self
+            .query(&query, &[])
+            .await
+            .map_err(|e| format!("Failed to get schemas: {}", e))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| Some((text(row, "name")?, flag(row, "is_system"))))
+            .collect())
+    }
+
+    /// シーケンス一覧を取得
+    async fn get_sequences(&self, schema: &str) -> Result<Vec<Sequence>, String> {
+        let query = r#"
+            SELECT
+                seq.name AS sequence_name,
+                TRY_CAST(seq.start_value AS bigint) AS start_value,
+                TRY_CAST(seq.increment AS bigint) AS increment,
+                TRY_CAST(seq.minimum_value AS bigint) AS minimum_value,
+                TRY_CAST(seq.maximum_value AS bigint) AS maximum_value,
+                seq.is_cycling AS is_cycling
+            FROM sys.sequences seq
+            JOIN sys.schemas s ON s.schema_id = seq.schema_id
+            WHERE s.name = @P1
+            ORDER BY seq.name
+        "#;
+
+        let rows = self
+            .query(query, &[schema])
+            .await
+            .map_err(|e| format!("Failed to get sequences: {}", e))?;
+
+        let value = |row: &Row, column: &str| row.try_get::<i64, _>(column).ok().flatten();
+        Ok(rows
+            .iter()
+            .filter_map(|row| {
+                Some(Sequence {
+                    name: text(row, "sequence_name")?,
+                    schema: schema.to_string(),
+                    start_value: value(row, "start_value"),
+                    increment: value(row, "increment"),
+                    min_value: value(row, "minimum_value"),
+                    max_value: value(row, "maximum_value"),
+                    cycle: flag(row, "is_cycling"),
+                })
+            })
+            .collect())
+    }
+
+    /// スキーマ内の全テーブル・ビューのカラムを一括取得
+    async fn get_all_columns_in_schema(
+        &self,
+        schema: &str,
+    ) -> Result<HashMap<String, Vec<Column>>, String> {
+        let query = r#"
+            SELECT
+                o.name AS table_name,
+                c.name AS column_name,
+                ty.name AS data_type,
+                c.max_length AS max_length,
+                c.precision AS precision,
+                c.scale AS scale,
+                c.is_nullable AS nullable,
+                dc.definition AS column_default,
+                CAST(CASE WHEN EXISTS (
+                    SELECT 1 FROM sys.index_columns ic
+                    JOIN sys.indexes i ON i.object_id = ic.object_id AND i.index_id = ic.index_id
+                    WHERE ic.object_id = c.object_id AND ic.column_id = c.column_id AND i.is_primary_key = 1
+                ) THEN 1 ELSE 0 END AS bit) AS is_primary_key,
+                CAST(CASE WHEN EXISTS (
+                    SELECT 1 FROM sys.foreign_key_columns fkc
+                    WHERE fkc.parent_object_id = c.object_id AND fkc.parent_column_id = c.column_id
+                ) THEN 1 ELSE 0 END AS bit) AS is_foreign_key,
+                CAST(CASE WHEN EXISTS (
+                    SELECT 1 FROM sys.index_columns ic
+                    JOIN sys.indexes i ON i.object_id = ic.object_id AND i.index_id = ic.index_id
+                    WHERE ic.object_id = c.object_id AND ic.column_id = c.column_id
+                      AND i.is_unique = 1 AND i.is_primary_key = 0
+                      AND (SELECT COUNT(*) FROM sys.index_columns ic2
+                           WHERE ic2.object_id = i.object_id AND ic2.index_id = i.index_id
+                             AND ic2.is_included_column = 0) = 1
+                ) THEN 1 ELSE 0 END AS bit) AS is_unique,
+                c.is_identity AS is_auto_increment,
+                c.is_computed AS is_generated,
+                CAST(c.column_id AS int) AS ordinal_position,
+                CAST(ep.value AS nvarchar(max)) AS comment
+            FROM sys.columns c
+            JOIN sys.objects o ON o.object_id = c.object_id
+            JOIN sys.schemas s ON s.schema_id = o.schema_id
+            JOIN sys.types ty ON ty.user_type_id = c.user_type_id
+            LEFT JOIN sys.default_constraints dc ON dc.object_id = c.default_object_id
+            LEFT JOIN sys.extended_properties ep
+                ON ep.class = 1 AND ep.major_id = c.object_id AND ep.minor_id = c.column_id
+               AND ep.name = 'MS_Description'
+            WHERE s.name = @P1
+              AND o.type IN ('U', 'V')
+            ORDER BY o.name, c.column_id
+        "#;
+
+        let rows = self
+            .query(query, &[schema])
+            .await
+            .map_err(|e| format!("Failed to get columns: {}", e))?;
+
+        let mut columns_map: HashMap<String, Vec<Column>> = HashMap::new();
+        for row in &rows {
+            let (Some(table_name), Some(name), Some(data_type)) = (
+                text(row, "table_name"),
+                text(row, "column_name"),
+                text(row, "data_type"),
+            ) else {
+                continue;
+            };
+            let max_length = row.try_get::<i16, _>("max_length").ok().flatten();
+            let precision = row.try_get::<u8, _>("precision").ok().flatten();
+            let scale = row.try_get::<u8, _>("scale").ok().flatten();
+
+            columns_map.entry(table_name).or_default().push(Column {
+                name,
+                display_type: format_mssql_column_type(
+                    &data_type,
+                    max_length.unwrap_or(0),
+                    precision.unwrap_or(0),
+                    scale.unwrap_or(0),
+                ),
+                data_type,
+                nullable: flag(row, "nullable"),
+                default_value: normalize_mssql_default(text(row, "column_default")),
+                is_primary_key: flag(row, "is_primary_key"),
+                is_foreign_key: flag(row, "is_foreign_key"),
+                is_unique: flag(row, "is_unique"),
+                is_auto_increment: flag(row, "is_auto_increment"),
+                is_generated: flag(row, "is_generated"),
+                ordinal_position: row
+                    .try_get::<i32, _>("ordinal_position")
+                    .ok()
+                    .flatten()
+                    .unwrap_or(0),
+                comment: comment(row),
+            });
+        }
+
+        Ok(columns_map)
+    }
+
+    /// スキーマ内の全テーブルのインデックスを一括取得
+    async fn get_all_indexes_in_schema(
+        &self,
+        schema: &str,
+    ) -> Result<HashMap<String, Vec<Index>>, String> {
+        let query = r#"
+            SELECT
+                t.name AS table_name,
+                i.name AS index_name,
+                i.is_unique AS is_unique,
+                i.is_primary_key AS is_primary,
+                i.type_desc AS index_type,
+                c.name AS column_name
+            FROM sys.indexes i
+            JOIN sys.tables t ON t.object_id = i.object_id
+            JOIN sys.schemas s ON s.schema_id = t.schema_id
+            JOIN sys.index_columns ic
+                ON ic.object_id = i.object_id AND ic.index_id = i.index_id
+               AND ic.is_included_column = 0
+            JOIN sys.columns c ON c.object_id = ic.object_id AND c.column_id = ic.column_id
+            WHERE s.name = @P1
+              AND i.type > 0
+            ORDER BY t.name, i.name, ic.key_ordinal
+        "#;
+
+        let rows = self
+            .query(query, &[schema])
+            .await
+            .map_err(|e| format!("Failed to get indexes: {}", e))?;
+
+        let mut indexes_map: HashMap<String, Vec<Index>> = HashMap::new();
+        for row in &rows {
+            let (Some(table_name), Some(index_name), Some(column_name)) = (
+                text(row, "table_name"),
+                text(row, "index_name"),
+                text(row, "column_name"),
+            ) else {
+                continue;
+            };
+
+            let indexes = indexes_map.entry(table_name).or_default();
+            match indexes.iter_mut().find(|index| index.name == index_name) {
+                Some(index) => index.columns.push(column_name),
+                None => indexes.push(Index {
+                    name: index_name,
+                    is_unique: flag(row, "is_unique"),
+                    is_primary: flag(row, "is_primary"),
+                    columns: vec![column_name],
+                    index_type: text(row, "index_type").unwrap_or_default(),
+                }),
+            }
+        }
+
+        Ok(indexes_map)
+    }
+
+    /// スキーマ内の外部キーを一括取得
+    ///
+    /// `referenced` が false なら参照元テーブルのスキーマ、true なら参照先テーブルのスキーマで絞り込む
+    async fn get_foreign_key_rows(
+        &self,
+        schema: &str,
+        referenced: bool,
+    ) -> Result<Vec<Row>, String> {
+        let filter = if referenced { "rs.name" } else { "s.name" };
+        let query = format!(
+            r#"
+            SELECT
+                s.name AS source_schema,
+                tp.name AS source_table,
+                fk.name AS constraint_name,
+                cp.name AS column_name,
+                rs.name AS referenced_schema,
+                tr.name AS referenced_table,
+                cr.name AS referenced_column,
+                fk.delete_referential_action_desc AS on_delete,
+                fk.update_referential_action_desc AS on_update
+            FROM sys.foreign_keys fk
+            JOIN sys.foreign_key_columns fkc ON fkc.constraint_object_id = fk.object_id
+            JOIN sys.tables tp ON tp.object_id = fk.parent_object_id
+            JOIN sys.schemas s ON s.schema_id = tp.schema_id
+            JOIN sys.columns cp
+                ON cp.object_id = fkc.parent_object_id AND cp.column_id = fkc.parent_column_id
+            JOIN sys.tables tr ON tr.object_id = fk.referenced_object_id
+            JOIN sys.schemas rs ON rs.schema_id = tr.schema_id
+            JOIN sys.columns cr
+                ON cr.object_id = fkc.referenced_object_id AND cr.column_id = fkc.referenced_column_id
+            WHERE {} = @P1
+            ORDER BY tp.name, fk.name, fkc.constraint_column_id
+            "#,
+            filter
+        );
+
+        self.query(&query, &[schema])
+            .await
+            .map_err(|e| format!("Failed to get foreign keys: {}", e))
+    }
+
+    /// スキーマ内の全テーブルの外部キーを一括取得
+    async fn get_all_foreign_keys_in_schema(
+        &self,
+        schema: &str,
+    ) -> Result<HashMap<String, Vec<ForeignKey>>, String> {
+        let rows = self.get_foreign_key_rows(schema, false).await?;
+
+        let mut fks_map: HashMap<String, Vec<ForeignKey>> = HashMap::new();
+        for row in &rows {
+            let (Some(table_name), Some(name), Some(column), Some(referenced_column)) = (
+                text(row, "source_table"),
+                text(row, "constraint_name"),
+                text(row, "column_name"),
+                text(row, "referenced_column"),
+            ) else {
+                continue;
+            };
+
+            let fks = fks_map.entry(table_name).or_default();
+            match fks.iter_mut().find(|fk| fk.name == name) {
+                Some(fk) => {
+                    fk.columns.push(column);
+                    fk.referenced_columns.push(referenced_column);
+                }
+                None => fks.push(ForeignKey {
+                    name,
+                    columns: vec![column],
+                    referenced_schema: text(row, "referenced_schema").unwrap_or_default(),
+                    referenced_table: text(row, "referenced_table").unwrap_or_default(),
+                    referenced_columns: vec![referenced_column],
+                    on_delete: referential_action(row, "on_delete"),
+                    on_update: referential_action(row, "on_update"),
+                }),
+            }
+        }
+
+        Ok(fks_map)
+    }
+
+    /// スキーマ内の全テーブルへの外部キー参照を一括取得（参照先テーブル名がキー）
+    async fn get_all_foreign_key_references_in_schema(
+        &self,
+        schema: &str,
+    ) -> Result<HashMap<String, Vec<ForeignKeyReference>>, String> {
+        let rows = self.get_foreign_key_rows(schema, true).await?;
+
+        let mut refs_map: HashMap<String, Vec<ForeignKeyReference>> = HashMap::new();
+        for row in &rows {
+            let (Some(target_table), Some(constraint_name), Some(column), Some(target_column)) = (
+                text(row, "referenced_table"),
+                text(row, "constraint_name"),
+                text(row, "column_name"),
+                text(row, "referenced_column"),
+            ) else {
+                continue;
+            };
+            let source_schema = text(row, "source_schema").unwrap_or_default();
+            let source_table = text(row, "source_table").unwrap_or_default();
+
+            let refs = refs_map.entry(target_table).or_default();
+            match refs.iter_mut().find(|r| {
+                r.constraint_name == constraint_name
+                    && r.source_schema == source_schema
+                    && r.source_table == source_table
+            }) {
+                Some(reference) => {
+                    reference.source_columns.push(column);
+                    reference.target_columns.push(target_column);
+                }
+                None => refs.push(ForeignKeyReference {
+                    source_schema,
+                    source_table,
+                    source_columns: vec![column],
+                    target_columns: vec![target_column],
+                    constraint_name,
+                }),
+            }
+        }
+
+        Ok(refs_map)
+    }
+}
+
+#[async_trait]
+impl DatabaseInspector for MssqlInspector {
+    async fn get_schemas(&self) -> Result<Vec<Schema>, String> {
+        let mut schemas = Vec::new();
+        for (name, is_system) in self.get_schema_names().await? {
+            let tables = self.get_tables(&name).await?;
+            let views = self.get_views(&name).await?;
+            let sequences = self.get_sequences(&name).await?;
+
+            schemas.push(Schema {
+                name,
+                is_system,
+                tables,
+                views,
+                sequences,
+            });
+        }
+
+        Ok(schemas)
+    }
+
+    async fn get_tables(&self, schema: &str) -> Result<Vec<Table>, String> {
+        let query = r#"
+            SELECT
+                t.name AS table_name,
+                CAST(ep.value AS nvarchar(max)) AS comment,
+                CAST((
+                    SELECT SUM(p.rows) FROM sys.partitions p
+                    WHERE p.object_id = t.object_id AND p.index_id IN (0, 1)
+                ) AS bigint) AS estimated_rows,
+                CAST(CASE WHEN t.temporal_type = 2 THEN 1 ELSE 0 END AS bit) AS is_system_versioned
+            FROM sys.tables t
+            JOIN sys.schemas s ON s.schema_id = t.schema_id
+            LEFT JOIN sys.extended_properties ep
+                ON ep.class = 1 AND ep.major_id = t.object_id AND ep.minor_id = 0
+               AND ep.name = 'MS_Description'
+            WHERE s.name = @P1
+              AND t.is_ms_shipped = 0
+            ORDER BY t.name
+        "#;
+
+        let rows = self
+            .query(query, &[schema])
+            .await
+            .map_err(|e| format!("Failed to get tables: {}", e))?;
+
+        let mut columns_map = self.get_all_columns_in_schema(schema).await?;
+        let mut indexes_map = self.get_all_indexes_in_schema(schema).await?;
+        let mut fks_map = self.get_all_foreign_keys_in_schema(schema).await?;
+        let mut refs_map = self
+            .get_all_foreign_key_references_in_schema(schema)
+            .await?;
+
+        let mut tables = Vec::new();
+        for row in &rows {
+            let Some(name) = text(row, "table_name") else {
+                continue;
+            };
+
+            let indexes = indexes_map.remove(&name).unwrap_or_default();
+            let primary_key =
+                indexes
+                    .iter()
+                    .find(|index| index.is_primary)
+                    .map(|index| PrimaryKey {
+                        name: index.name.clone(),
+                        columns: index.columns.clone(),
+                    });
+
+            tables.push(Table {
+                columns: columns_map.remove(&name).unwrap_or_default(),
+                foreign_keys: fks_map.remove(&name).unwrap_or_default(),
+                referenced_by: refs_map.remove(&name).unwrap_or_default(),
+                name,
+                schema: schema.to_string(),
+                comment: comment(row),
+                estimated_row_count: row.try_get::<i64, _>("estimated_rows").ok().flatten(),
+                primary_key,
+                indexes,
+                is_system_versioned: flag(row, "is_system_versioned"),
+            });
+        }
+
+        Ok(tables)
+    }
+
+    async fn get_views(&self, schema: &str) -> Result<Vec<View>, String> {
+        let query = r#"
+            SELECT
+                v.name AS view_name,
+                CAST(ep.value AS nvarchar(max)) AS comment,
+                m.definition AS definition
+            FROM sys.views v
+            JOIN sys.schemas s ON s.schema_id = v.schema_id
+            LEFT JOIN sys.sql_modules m ON m.object_id = v.object_id
+            LEFT JOIN sys.extended_properties ep
+                ON ep.class = 1 AND ep.major_id = v.object_id AND ep.minor_id = 0
+               AND ep.name = 'MS_Description'
+            WHERE s.name = @P1
+              AND v.is_ms_shipped = 0
+            ORDER BY v.name
+        "#;
+
+        let rows = self
+            .query(query, &[schema])
+            .await
+            .map_err(|e| format!("Failed to get views: {}", e))?;
+
+        let mut columns_map = self.get_all_columns_in_schema(schema).await?;
+
+        let mut views = Vec::new();
+        for row in &rows {
+            let Some(name) = text(row, "view_name") else {
+                continue;
+            };
+
+            views.push(View {
+                columns: columns_map.remove(&name).unwrap_or_default(),
+                name,
+                schema: schema.to_string(),
+                comment: comment(row),
+                // 暗号化されたビューは定義を取得できない
+                definition: text(row, "definition"),
+            });
+        }
+
+        Ok(views)
+    }
+
+    async fn get_columns(&self, schema: &str, table: &str) -> Result<Vec<Column>, String> {
+        Ok(self
+            .get_all_columns_in_schema(schema)
+            .await?
+            .remove(table)
+            .unwrap_or_default())
+    }
+
+    async fn get_indexes(&self, schema: &str, table: &str) -> Result<Vec<Index>, String> {
+        Ok(self
+            .get_all_indexes_in_schema(schema)
+            .await?
+            .remove(table)
+            .unwrap_or_default())
+    }
+
+    async fn get_foreign_keys(&self, schema: &str, table: &str) -> Result<Vec<ForeignKey>, String> {
+        Ok(self
+            .get_all_foreign_keys_in_schema(schema)
+            .await?
+            .remove(table)
+            .unwrap_or_default())
+    }
+
+    async fn get_foreign_key_references(
+        &self,
+        schema: &str,
+        table: &str,
+    ) -> Result<Vec<ForeignKeyReference>, String> {
+        Ok(self
+            .get_all_foreign_key_references_in_schema(schema)
+            .await?
+            .remove(table)
+            .unwrap_or_default())
+    }
+
+    async fn get_all_foreign_keys(
+        &self,
+        schema: Option<&str>,
+    ) -> Result<Vec<TableForeignKey>, String> {
+        let schema_name = schema.unwrap_or("dbo");
+        let fks_map = self.get_all_foreign_keys_in_schema(schema_name).await?;
+
+        let mut all_fks = Vec::new();
+        for (table_name, fks) in fks_map {
+            for fk in fks {
+                all_fks.push(TableForeignKey {
+                    schema: schema_name.to_string(),
+                    table: table_name.clone(),
+                    foreign_key: fk,
+                });
+            }
+        }
+
+        Ok(all_fks)
+    }
+
+    async fn get_columns_by_schema(
+        &self,
+        schema: &str,
+    ) -> Result<HashMap<String, Vec<Column>>, String> {
+        self.get_all_columns_in_schema(schema).await
+    }
+
+    async fn get_table_summaries(&self) -> Result<Vec<SchemaSummary>, String> {
+        let mut schemas = Vec::new();
+        let mut schema_index = HashMap::new();
+        for (name, is_system) in self.get_schema_names().await? {
+            schema_index.insert(name.clone(), schemas.len());
+            schemas.push(SchemaSummary {
+                name,
+                is_system,
+                tables: Vec::new(),
+                views: Vec::new(),
+            });
+        }
+
+        let tables_query = r#"
+            SELECT
+                s.name AS schema_name,
+                o.name AS table_name,
+                CAST(CASE WHEN o.type = 'V' THEN 1 ELSE 0 END AS bit) AS is_view,
+                CAST(ep.value AS nvarchar(max)) AS comment,
+                CAST((
+                    SELECT SUM(p.rows) FROM sys.partitions p
+                    WHERE p.object_id = o.object_id AND p.index_id IN (0, 1)
+                ) AS bigint) AS estimated_rows
+            FROM sys.objects o
+            JOIN sys.schemas s ON s.schema_id = o.schema_id
+            LEFT JOIN sys.extended_properties ep
+                ON ep.class = 1 AND ep.major_id = o.object_id AND ep.minor_id = 0
+               AND ep.name = 'MS_Description'
+            WHERE o.type IN ('U', 'V')
+              AND o.is_ms_shipped = 0
+            ORDER BY s.name, o.name
+        "#;
+
+        let table_rows = self
+            .query(tables_query, &[])
+            .await
+            .map_err(|e| format!("Failed to get table summaries: {}", e))?;
+
+        for row in &table_rows {
+            let (Some(schema_name), Some(name)) =
+                (text(row, "schema_name"), text(row, "table_name"))
+            else {
+                continue;
+            };
+            let Some(idx) = schema_index.get(&schema_name) else {
+                continue;
+            };
+
+            let is_view = flag(row, "is_view");
+            let summary = TableSummary {
+                name,
+                schema: schema_name,
+                comment: comment(row),
+                estimated_row_count: if is_view {
+                    None
+                } else {
+                    row.try_get::<i64, _>("estimated_rows").ok().flatten()
+                },
+            };
+
+            if is_view {
+                schemas[*idx].views.push(summary);
+            } else {
+                schemas[*idx].tables.push(summary);
+            }
+        }
+
+        Ok(schemas)
+    }
+
+    async fn get_database_structure(&self) -> Result<DatabaseStructure, String> {
+        let schemas = self.get_schemas().await?;
+
+        Ok(DatabaseStructure {
+            connection_id: String::new(), // 呼び出し元で設定
+            database_name: self.database_name.clone(),
+            database_type: "sqlserver".to_string(),
+            schemas,
+            fetched_at: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+}
@@ -0,0 +1,67 @@
+use crate::database::mssql_inspector::{format_mssql_column_type, normalize_mssql_default};
+
+#[test]
+fn test_format_mssql_column_type() {
+    // Unicode文字列型の max_length はバイト数
+    assert_eq!(
+        format_mssql_column_type("nvarchar", 100, 0, 0),
+        "nvarchar(50)"
+    );
+    assert_eq!(
+        format_mssql_column_type("varchar", 100, 0, 0),
+        "varchar(100)"
+    );
+    assert_eq!(
+        format_mssql_column_type("nvarchar", -1, 0, 0),
+        "nvarchar(max)"
+    );
+    assert_eq!(
+        format_mssql_column_type("varbinary", -1, 0, 0),
+        "varbinary(max)"
+    );
+
+    assert_eq!(
+        format_mssql_column_type("decimal", 9, 10, 4),
+        "decimal(10,4)"
+    );
+    assert_eq!(
+        format_mssql_column_type("datetime2", 8, 27, 3),
+        "datetime2(3)"
+    );
+    assert_eq!(format_mssql_column_type("int", 4, 10, 0), "int");
+}
+
+#[test]
+fn test_normalize_mssql_default() {
+    assert_eq!(normalize_mssql_default(None), None);
+
+    // 外側の括弧を外す
+    assert_eq!(
+        normalize_mssql_default(Some("((0))".to_string())).as_deref(),
+        Some("0")
+    );
+    assert_eq!(
+        normalize_mssql_default(Some("(getdate())".to_string())).as_deref(),
+        Some("getdate()")
+    );
+
+    // 文字列リテラルは引用符を外す
+    assert_eq!(
+        normalize_mssql_default(Some("(N'active')".to_string())).as_deref(),
+        Some("active")
+    );
+    assert_eq!(
+        normalize_mssql_default(Some("('it''s')".to_string())).as_deref(),
+        Some("it's")
+    );
+
+    // 全体を囲んでいない括弧・複数のリテラルはそのまま
+    assert_eq!(
+        normalize_mssql_default(Some("((1)+(2))".to_string())).as_deref(),
+        Some("(1)+(2)")
+    );
+    assert_eq!(
+        normalize_mssql_default(Some("('a'+'b')".to_string())).as_deref(),
+        Some("'a'+'b'")
+    );
+}
@@ -0,0 +1,143 @@
+use crate::models::query_result::{QueryParam, QueryValue, TypedParam};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
+use std::borrow::Cow;
+use tiberius::numeric::Numeric;
+use tiberius::{ColumnData, ColumnType, FromSql, Query};
+
+/// 列の型名（`sys.types` の表記）
+///
+/// TDSの型情報には長さ・精度が含まれず、NULL許容の整数などは `Intn` のようにまとめて返るため、
+/// 同じ系統の代表的な型名にする
+pub fn mssql_type_name(column_type: ColumnType) -> &'static str {
+    match column_type {
+        ColumnType::Null => "null",
+        ColumnType::Bit | ColumnType::Bitn => "bit",
+        ColumnType::Int1 => "tinyint",
+        ColumnType::Int2 => "smallint",
+        ColumnType::Int4 | ColumnType::Intn => "int",
+        ColumnType::Int8 => "bigint",
+        ColumnType::Float4 => "real",
+        ColumnType::Float8 | ColumnType::Floatn => "float",
+        ColumnType::Money => "money",
+        ColumnType::Money4 => "smallmoney",
+        ColumnType::Decimaln => "decimal",
+        ColumnType::Numericn => "numeric",
+        ColumnType::Datetime4 => "smalldatetime",
+        ColumnType::Datetime | ColumnType::Datetimen => "datetime",
+        ColumnType::Daten => "date",
+        ColumnType::Timen => "time",
+        ColumnType::Datetime2 => "datetime2",
+        ColumnType::DatetimeOffsetn => "datetimeoffset",
+        ColumnType::Guid => "uniqueidentifier",
+        ColumnType::BigChar => "char",
+        ColumnType::BigVarChar => "varchar",
+        ColumnType::NChar => "nchar",
+        ColumnType::NVarchar => "nvarchar",
+        ColumnType::Text => "text",
+        ColumnType::NText => "ntext",
+        ColumnType::BigBinary => "binary",
+        ColumnType::BigVarBin => "varbinary",
+        ColumnType::Image => "image",
+        ColumnType::Xml => "xml",
+        ColumnType::Udt => "udt",
+        ColumnType::SSVariant => "sql_variant",
+    }
+}
+
+/// MONEYを正確に復元できる絶対値の上限
+///
+/// TDSのMONEYは1万倍した整数だが、tiberius は浮動小数点に変換して返すため、
+/// 2^53 / 10000 を超える値は下位の桁が失われている可能性がある
+pub const MONEY_EXACT_LIMIT: f64 = 9_007_199_254_740_992.0 / 1e4;
+
+/// MONEYの精度が失われている可能性がある場合の警告
+pub const MONEY_PRECISION_WARNING: &str =
+    "MONEY の値が大きいため下位の桁が正確でない可能性があります。正確な値は CAST(列 AS DECIMAL(19,4)) で取得してください";
+
+/// 1つの値を変換する
+///
+/// `column_type` は列の型。MONEYは浮動小数点で返るため、1万倍した整数に戻して小数4桁の Decimal にする
+pub fn convert_mssql_value(data: ColumnData<'static>, column_type: ColumnType) -> QueryValue {
+    match data {
+        ColumnData::U8(v) => v.map(|v| QueryValue::Int(v.into())),
+        ColumnData::I16(v) => v.map(|v| QueryValue::Int(v.into())),
+        ColumnData::I32(v) => v.map(|v| QueryValue::Int(v.into())),
+        ColumnData::I64(v) => v.map(QueryValue::Int),
+        ColumnData::F32(v) => v.map(|v| QueryValue::Float(v.into())),
+        ColumnData::F64(v) => v.map(|v| match column_type {
+            ColumnType::Money | ColumnType::Money4 => QueryValue::Decimal(format_numeric(
+                Numeric::new_with_scale((v * 1e4).round() as i128, 4),
+            )),
+            _ => QueryValue::Float(v),
+        }),
+        ColumnData::Bit(v) => v.map(QueryValue::Bool),
+        ColumnData::String(v) => v.map(|s| QueryValue::String(s.into_owned())),
+        ColumnData::Guid(v) => v.map(QueryValue::Uuid),
+        ColumnData::Binary(v) => v.map(|bytes| QueryValue::Bytes(bytes.into_owned())),
+        ColumnData::Numeric(v) => v.map(|n| QueryValue::Decimal(format_numeric(n))),
+        ColumnData::Xml(v) => v.map(|xml| QueryValue::String(xml.into_owned().into_string())),
+        ref temporal @ (ColumnData::DateTime(_)
+        | ColumnData::SmallDateTime(_)
+        | ColumnData::DateTime2(_)) => NaiveDateTime::from_sql(temporal)
+            .ok()
+            .flatten()
+            .map(QueryValue::Timestamp),
+        ref date @ ColumnData::Date(_) => NaiveDate::from_sql(date)
+            .ok()
+            .flatten()
+            .map(QueryValue::Date),
+        ref time @ ColumnData::Time(_) => NaiveTime::from_sql(time)
+            .ok()
+            .flatten()
+            .map(QueryValue::Time),
+        ref offset @ ColumnData::DateTimeOffset(_) => DateTime::<FixedOffset>::from_sql(offset)
+            .ok()
+            .flatten()
+            .map(QueryValue::TimestampTz),
+    }
+    .unwrap_or(QueryValue::Null)
+}
+
+/// 精度が失われている可能性のあるMONEYの値か
+pub fn is_inexact_money(data: &ColumnData<'static>, column_type: ColumnType) -> bool {
+    matches!(column_type, ColumnType::Money | ColumnType::Money4)
+        && matches!(data, ColumnData::F64(Some(v)) if v.abs() > MONEY_EXACT_LIMIT)
+}
+
+/// DECIMAL/NUMERIC を文字列にする（整数値とスケールから組み立てて精度を保つ）
+pub fn format_numeric(numeric: Numeric) -> String {
+    let scale = usize::from(numeric.scale());
+    let value = numeric.value();
+    let digits = value.unsigned_abs().to_string();
+    let sign = if value < 0 { "-" } else { "" };
+    if scale == 0 {
+        return format!("{}{}", sign, digits);
+    }
+
+    let digits = format!("{:0>width$}", digits, width = scale + 1);
+    let (int_part, frac_part) = digits.split_at(digits.len() - scale);
+    format!("{}{}.{}", sign, int_part, frac_part)
+}
+
+/// バインドパラメータをクエリに追加する（プレースホルダーは `@P1` から順に対応する）
+pub fn bind_mssql_params<'a>(query: &mut Query<'a>, params: &'a [QueryParam]) {
+    for param in params {
+        match param {
+            QueryParam::Null => query.bind(Option::<Cow<'a, str>>::None),
+            QueryParam::Bool(b) => query.bind(*b),
+            QueryParam::Int(i) => query.bind(*i),
+            QueryParam::Float(f) => query.bind(*f),
+            QueryParam::String(s) => query.bind(s.as_str()),
+            QueryParam::Typed(typed) => match typed {
+                // NVARCHARはDECIMAL・JSON文字列の比較時に暗黙変換される
+                TypedParam::Decimal(d) => query.bind(d.to_string()),
+                TypedParam::Date(d) => query.bind(*d),
+                TypedParam::Time(t) => query.bind(*t),
+                TypedParam::Timestamp(t) => query.bind(*t),
+                TypedParam::TimestampTz(t) => query.bind(*t),
+                TypedParam::Json(v) => query.bind(v.to_string()),
+                TypedParam::Uuid(u) => query.bind(u),
+            },
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use crate::connection::{ConnectionConfig, ConnectionInfo, DatabaseType, NetworkConfig};
+use crate::database::mssql_types::{
+    convert_mssql_value, format_numeric, is_inexact_money, mssql_type_name,
+};
+use crate::database::MssqlExecutor;
+use crate::models::query_result::{QueryParam, QueryValue};
+use crate::services::query_executor::QueryExecutor;
+use chrono::{NaiveDate, NaiveTime};
+use std::borrow::Cow;
+use tiberius::numeric::Numeric;
+use tiberius::{ColumnData, ColumnType};
+
+#[test]
+fn test_format_numeric_keeps_scale() {
+    assert_eq!(
+        format_numeric(Numeric::new_with_scale(12345678, 4)),
+        "1234.5678"
+    );
+    assert_eq!(format_numeric(Numeric::new_with_scale(-1, 4)), "-0.0001");
+    assert_eq!(format_numeric(Numeric::new_with_scale(500, 2)), "5.00");
+    assert_eq!(format_numeric(Numeric::new_with_scale(42, 0)), "42");
+    assert_eq!(
+        format_numeric(Numeric::new_with_scale(i128::from(i64::MAX) * 10, 0)),
+        "92233720368547758070"
+    );
+}
+
+#[test]
+fn test_convert_mssql_value() {
+    assert_eq!(
+        convert_mssql_value(ColumnData::U8(Some(255)), ColumnType::Int1),
+        QueryValue::Int(255)
+    );
+    assert_eq!(
+        convert_mssql_value(ColumnData::I32(None), ColumnType::Intn),
+        QueryValue::Null
+    );
+    assert_eq!(
+        convert_mssql_value(ColumnData::Bit(Some(true)), ColumnType::Bitn),
+        QueryValue::Bool(true)
+    );
+    assert_eq!(
+        convert_mssql_value(
+            ColumnData::String(Some(Cow::Borrowed("日本語"))),
+            ColumnType::NVarchar
+        ),
+        QueryValue::String("日本語".to_string())
+    );
+
+    // MONEYは浮動小数点で返るため小数4桁のDecimalにする
+    assert_eq!(
+        convert_mssql_value(ColumnData::F64(Some(12.34)), ColumnType::Money),
+        QueryValue::Decimal("12.3400".to_string())
+    );
+    assert_eq!(
+        convert_mssql_value(ColumnData::F64(Some(-0.0001)), ColumnType::Money4),
+        QueryValue::Decimal("-0.0001".to_string())
+    );
+    // 1万倍した整数に戻すため、浮動小数点の表記誤差が残らない
+    assert_eq!(
+        convert_mssql_value(ColumnData::F64(Some(123456789.1235)), ColumnType::Money),
+        QueryValue::Decimal("123456789.1235".to_string())
+    );
+    assert!(!is_inexact_money(
+        &ColumnData::F64(Some(123456789.1235)),
+        ColumnType::Money
+    ));
+    assert!(is_inexact_money(
+        &ColumnData::F64(Some(922337203685477.0)),
+        ColumnType::Money
+    ));
+    assert!(!is_inexact_money(
+        &ColumnData::F64(Some(922337203685477.0)),
+        ColumnType::Floatn
+    ));
+    assert_eq!(
+        convert_mssql_value(ColumnData::F64(Some(1.5)), ColumnType::Floatn),
+        QueryValue::Float(1.5)
+    );
+}
+
+#[test]
+fn test_mssql_type_name() {
+    assert_eq!(mssql_type_name(ColumnType::Intn), "int");
+    assert_eq!(mssql_type_name(ColumnType::NVarchar), "nvarchar");
+    assert_eq!(
+        mssql_type_name(ColumnType::DatetimeOffsetn),
+        "datetimeoffset"
+    );
+    assert_eq!(mssql_type_name(ColumnType::Guid), "uniqueidentifier");
+}
+
+/// docker/sqlserver のフィクスチャに接続する（docker-compose で起動しておく）
+async fn docker_executor() -> MssqlExecutor {
+    let connection = ConnectionInfo::new(
+        "Docker SQL Server".to_string(),
+        DatabaseType::SqlServer,
+        ConnectionConfig::Network(NetworkConfig {
+            host: "127.0.0.1".to_string(),
+            port: 14333,
+            database: "benchmark_small".to_string(),
+            username: "sa".to_string(),
+            encrypted_password: None,
+            ssl_config: None,
+            ssh_tunnel: None,
+            options: None,
+        }),
+    );
+    MssqlExecutor::new(&connection, Some("Benchmark#2026"))
+        .await
+        .expect("docker/sqlserver のコンテナを起動してください")
+}
+
+#[tokio::test]
+#[ignore = "docker/sqlserver のコンテナが必要"]
+async fn test_mssql_type_test_fixture() {
+    let executor = docker_executor().await;
+    let result = executor
+        .execute(
+            "SELECT label, bit_val, tiny_val, big_val, dec_val, money_val, \
+             guid_val, date_val, time_val FROM type_test ORDER BY id",
+        )
+        .await
+        .unwrap();
+
+    let first = &result.rows[0].values;
+    assert_eq!(first[0], QueryValue::String("通常値".to_string()));
+    assert_eq!(first[1], QueryValue::Bool(true));
+    assert_eq!(first[2], QueryValue::Int(255));
+    assert_eq!(first[3], QueryValue::Int(i64::MAX));
+    assert_eq!(first[4], QueryValue::Decimal("1234.5678".to_string()));
+    assert_eq!(first[5], QueryValue::Decimal("12.3400".to_string()));
+    assert_eq!(
+        first[6],
+        QueryValue::Uuid("550e8400-e29b-41d4-a716-446655440000".parse().unwrap())
+    );
+    assert_eq!(
+        first[7],
+        QueryValue::Date(NaiveDate::from_ymd_opt(2026, 3, 15).unwrap())
+    );
+    assert_eq!(
+        first[8],
+        QueryValue::Time(NaiveTime::from_hms_milli_opt(10, 0, 0, 123).unwrap())
+    );
+
+    let second = &result.rows[1].values;
+    assert_eq!(second[4], QueryValue::Decimal("-0.0001".to_string()));
+    assert_eq!(second[5], QueryValue::Decimal("-12.5000".to_string()));
+
+    assert!(result.rows[2].values[1..]
+        .iter()
+        .all(|value| *value == QueryValue::Null));
+}
+
+#[tokio::test]
+#[ignore = "docker/sqlserver のコンテナが必要"]
+async fn test_mssql_bind_params() {
+    let executor = docker_executor().await;
+    let result = executor
+        .execute_with_params(
+            "SELECT label FROM type_test WHERE tiny_val = @P1 OR label = @P2 ORDER BY id",
+            &[QueryParam::Int(0), QueryParam::String("NULL".to_string())],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(result.rows.len(), 2);
+    assert_eq!(
+        result.rows[0].values[0],
+        QueryValue::String("負の値".to_string())
+    );
+}
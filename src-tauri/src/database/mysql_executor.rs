@@ -1,17 +1,29 @@
-use crate::connection::ConnectionInfo;
+use crate::connection::{describe_tls_error, ConnectionInfo, SshTunnel};
+use crate::database::mysql_types::convert_mysql_value;
+use crate::models::connection_pool::PoolSettings;
 use crate::models::mutation_result::MutationResult;
 use crate::models::query_result::{
-    QueryError, QueryErrorCode, QueryErrorDetails, QueryResult, QueryResultColumn, QueryResultRow,
-    QueryValue,
+    QueryError, QueryErrorCode, QueryErrorDetails, QueryParam, QueryResult, QueryResultColumn,
+    QueryResultRow, ScriptErrorMode, StatementResult,
 };
-use crate::services::query_executor::QueryExecutor;
+use crate::services::query_executor::{
+    bind_params, current_query_id, execute_statements, locate_statement_error,
+    multiple_statements_with_params_error, PoolStatus, QueryCursor, QueryExecutor, RunningQueries,
+    RunningQueryGuard, RunningStatements, TransactionSession,
+};
+use crate::sql::statement_splitter::StatementSplitter;
 use async_trait::async_trait;
-use sqlx::mysql::{MySqlPool, MySqlRow};
-use sqlx::{Column, Row, TypeInfo, ValueRef};
+use sqlx::mysql::{MySql, MySqlPool, MySqlPoolOptions, MySqlRow};
+use sqlx::{Column, Connection, Executor, Row, Transaction, TypeInfo};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 pub struct MysqlExecutor {
     pool: MySqlPool,
+    /// SSHトンネル（プールが使う間は開いたままにする）
+    _tunnel: Option<Arc<SshTunnel>>,
+    /// 実行中クエリの文（サーバー側キャンセル用）
+    running: RunningQueries<RunningStatements>,
 }
 
 impl MysqlExecutor {
@@ -19,26 +31,72 @@ impl MysqlExecutor {
         connection: &ConnectionInfo,
         password: Option<&str>,
     ) -> Result<Self, QueryError> {
-        let connection_string =
+        Self::with_pool_settings(connection, password, &PoolSettings::default()).await
+    }
+
+    /// プール設定を指定して接続する
+    pub async fn with_pool_settings(
+        connection: &ConnectionInfo,
+        password: Option<&str>,
+        settings: &PoolSettings,
+    ) -> Result<Self, QueryError> {
+        let mut connect_options =
             connection
-                .build_connection_string(password)
+                .mysql_connect_options(password)
                 .map_err(|e| QueryError {
                     code: QueryErrorCode::ConnectionFailed,
-                    message: format!("Failed to build connection string: {}", e),
+                    message: format!("Failed to build connection options: {}", e),
                     details: None,
                     native_code: None,
                 })?;
 
-        let pool = MySqlPool::connect(&connection_string)
+        let tunnel = connection.open_ssh_tunnel().await.map_err(|e| QueryError {
+            code: QueryErrorCode::ConnectionFailed,
+            message: e.to_string(),
+            details: None,
+            native_code: None,
+        })?;
+        if let Some(tunnel) = &tunnel {
+            connect_options = tunnel.apply_mysql(connect_options);
+        }
+
+        let init_sql = connection.session_init_sql().map_err(|e| QueryError {
+            code: QueryErrorCode::ConnectionFailed,
+            message: format!("Invalid session settings: {}", e),
+            details: None,
+            native_code: None,
+        })?;
+
+        let mut options = MySqlPoolOptions::new()
+            .max_connections(settings.max_connections)
+            .idle_timeout(Duration::from_secs(settings.idle_timeout_seconds));
+        if let Some(sql) = init_sql {
+            // プールが開くすべての接続を同じセッション設定にする
+            options = options.after_connect(move |conn, _meta| {
+                let sql = sql.clone();
+                Box::pin(async move {
+                    conn.execute(sql.as_str()).await?;
+                    Ok(())
+                })
+            });
+        }
+
+        let pool = options
+            .connect_with(connect_options)
             .await
             .map_err(|e| QueryError {
                 code: QueryErrorCode::ConnectionFailed,
-                message: format!("Failed to connect: {}", e),
+                message: describe_tls_error(&e)
+                    .unwrap_or_else(|| format!("Failed to connect: {}", e)),
                 details: None,
                 native_code: None,
             })?;
 
-        Ok(Self { pool })
+        Ok(Self {
+            pool,
+            _tunnel: tunnel,
+            running: RunningQueries::new(),
+        })
     }
 
     fn map_error(err: sqlx::Error) -> QueryError {
@@ -128,195 +186,138 @@ impl MysqlExecutor {
                 "1406" => QueryErrorCode::DataTruncation,
                 "1365" => QueryErrorCode::DivisionByZero,
 
+                // Cancellation (KILL QUERY)
+                "1317" | "70100" => QueryErrorCode::QueryCancelled,
+
                 _ => QueryErrorCode::Unknown,
             },
             None => QueryErrorCode::Unknown,
         }
     }
 
-    fn convert_row(row: &MySqlRow, columns: &[QueryResultColumn]) -> QueryResultRow {
-        let values = columns
+    /// 行からカラム情報を生成
+    fn build_columns(row: &MySqlRow) -> Vec<QueryResultColumn> {
+        row.columns()
             .iter()
-            .enumerate()
-            .map(|(i, _col)| {
-                let raw_val = row.try_get_raw(i).ok();
-
-                if let Some(val) = raw_val {
-                    if val.is_null() {
-                        return QueryValue::Null;
-                    }
-
-                    let type_info = val.type_info();
-                    // MySQL type names: https://docs.rs/sqlx-mysql/latest/sqlx_mysql/struct.MySqlTypeInfo.html
-                    // names match standard SQL types usually
-                    match type_info.name() {
-                        "BOOLEAN" | "TINYINT" => {
-                            // TINYINT(1) is boolean often, but can be int
-                            row.try_get::<bool, _>(i)
-                                .ok()
-                                .map(QueryValue::Bool)
-                                .or_else(|| row.try_get::<i64, _>(i).ok().map(QueryValue::Int))
-                                .unwrap_or(QueryValue::Null)
-                        }
-                        "TINYINT UNSIGNED" => row
-                            .try_get::<u8, _>(i)
-                            .ok()
-                            .map(|v| QueryValue::Int(v as i64))
-                            .unwrap_or(QueryValue::Null),
-                        "SMALLINT" | "INT" | "INTEGER" | "BIGINT" | "MEDIUMINT" => row
-                            .try_get::<i64, _>(i)
-                            .ok()
-                            .map(QueryValue::Int)
-                            .unwrap_or(QueryValue::Null),
-                        "SMALLINT UNSIGNED" => row
-                            .try_get::<u16, _>(i)
-                            .ok()
-                            .map(|v| QueryValue::Int(v as i64))
-                            .unwrap_or(QueryValue::Null),
-                        "MEDIUMINT UNSIGNED" | "INT UNSIGNED" | "INTEGER UNSIGNED" => row
-                            .try_get::<u32, _>(i)
-                            .ok()
-                            .map(|v| QueryValue::Int(v as i64))
-                            .unwrap_or(QueryValue::Null),
-                        "BIGINT UNSIGNED" => row
-                            .try_get::<u64, _>(i)
-                            .ok()
-                            .map(|v| QueryValue::Int(v as i64))
-                            .unwrap_or(QueryValue::Null),
-                        "FLOAT" | "DOUBLE" | "REAL" => row
-                            .try_get::<f64, _>(i)
-                            .ok()
-                            .map(QueryValue::Float)
-                            .unwrap_or(QueryValue::Null),
-                        "DECIMAL" | "NUMERIC" | "NEWDECIMAL" => row
-                            .try_get::<bigdecimal::BigDecimal, _>(i)
-                            .ok()
-                            .map(|v| QueryValue::String(v.to_string()))
-                            .unwrap_or(QueryValue::Null),
-                        "VARCHAR" | "CHAR" | "TEXT" | "LONGTEXT" | "TINYTEXT" | "MEDIUMTEXT"
-                        | "ENUM" | "SET" => row
-                            .try_get::<String, _>(i)
-                            .ok()
-                            .map(QueryValue::String)
-                            .unwrap_or(QueryValue::Null),
-                        "BLOB" | "LONGBLOB" | "TINYBLOB" | "MEDIUMBLOB" | "BINARY"
-                        | "VARBINARY" => row
-                            .try_get::<Vec<u8>, _>(i)
-                            .ok()
-                            .map(QueryValue::Bytes)
-                            .unwrap_or(QueryValue::Null),
-                        "DATE" => row
-                            .try_get::<chrono::NaiveDate, _>(i)
-                            .ok()
-                            .map(|d| QueryValue::String(d.format("%Y-%m-%d").to_string()))
-                            .unwrap_or(QueryValue::Null),
-                        "TIME" => row
-                            .try_get::<chrono::NaiveTime, _>(i)
-                            .ok()
-                            .map(|t| QueryValue::String(t.format("%H:%M:%S").to_string()))
-                            .unwrap_or(QueryValue::Null),
-                        "DATETIME" => row
-                            .try_get::<chrono::NaiveDateTime, _>(i)
-                            .ok()
-                            .map(|dt| {
-                                QueryValue::String(
-                                    dt.format("%Y-%m-%d %H:%M:%S").to_string(),
-                                )
-                            })
-                            .unwrap_or(QueryValue::Null),
-                        "TIMESTAMP" => row
-                            .try_get::<chrono::DateTime<chrono::Utc>, _>(i)
-                            .ok()
-                            .map(|dt| {
-                                QueryValue::String(
-                                    dt.format("%Y-%m-%d %H:%M:%S").to_string(),
-                                )
-                            })
-                            .unwrap_or(QueryValue::Null),
-                        "YEAR" => row
-                            .try_get::<u16, _>(i)
-                            .ok()
-                            .map(|y| QueryValue::Int(y as i64))
-                            .unwrap_or(QueryValue::Null),
-                        "JSON" => row
-                            .try_get::<serde_json::Value, _>(i)
-                            .ok()
-                            .map(|v| QueryValue::String(v.to_string()))
-                            .or_else(|| {
-                                row.try_get::<String, _>(i).ok().map(QueryValue::String)
-                            })
-                            .unwrap_or(QueryValue::Null),
-                        "BIT" => row
-                            .try_get::<u64, _>(i)
-                            .ok()
-                            .map(|v| QueryValue::String(format!("{:#b}", v)))
-                            .unwrap_or(QueryValue::Null),
-                        _ => row
-                            .try_get::<String, _>(i)
-                            .ok()
-                            .map(QueryValue::String)
-                            .unwrap_or(QueryValue::Null),
-                    }
-                } else {
-                    QueryValue::Null
-                }
+            .map(|col| QueryResultColumn {
+                name: col.name().to_string(),
+                data_type: col.type_info().name().to_string(),
+                nullable: true,
             })
+            .collect()
+    }
+
+    fn convert_row(row: &MySqlRow, columns: &[QueryResultColumn]) -> QueryResultRow {
+        let values = (0..columns.len())
+            .map(|i| convert_mysql_value(row, i))
             .collect();
 
         QueryResultRow { values }
     }
-}
-
-#[async_trait]
-impl QueryExecutor for MysqlExecutor {
-    async fn execute(&self, sql: &str) -> Result<QueryResult, QueryError> {
-        let start = std::time::Instant::now();
 
-        let rows = sqlx::query(sql)
-            .fetch_all(&self.pool)
-            .await
-            .map_err(Self::map_error)?;
+    /// 現在のクエリIDがあれば、実行する文を実行中クエリとして登録する
+    fn track_running<'a>(
+        running: &RunningQueries<RunningStatements>,
+        statements: impl IntoIterator<Item = &'a str>,
+    ) -> Option<RunningQueryGuard<RunningStatements>> {
+        let query_id = current_query_id()?;
+        let statements = statements.into_iter().map(str::to_string).collect();
+        Some(running.register(query_id, Arc::new(statements)))
+    }
 
-        let execution_time_ms = start.elapsed().as_millis() as u64;
-
-        let columns = if let Some(first_row) = rows.first() {
-            first_row
-                .columns()
-                .iter()
-                .map(|col| QueryResultColumn {
-                    name: col.name().to_string(),
-                    data_type: col.type_info().name().to_string(),
-                    nullable: true,
-                })
-                .collect()
-        } else {
-            vec![]
-        };
+    /// 取得した行から結果を組み立てる（実行時間は呼び出し側で設定）
+    fn build_result(rows: &[MySqlRow]) -> QueryResult {
+        let columns = rows.first().map(Self::build_columns).unwrap_or_default();
 
         let result_rows: Vec<QueryResultRow> = rows
             .iter()
             .map(|row| Self::convert_row(row, &columns))
             .collect();
 
-        Ok(QueryResult {
+        QueryResult {
             columns,
             rows: result_rows,
             row_count: rows.len(),
-            execution_time_ms,
+            execution_time_ms: 0,
             warnings: vec![],
-        })
+        }
+    }
+}
+
+#[async_trait]
+impl QueryExecutor for MysqlExecutor {
+    async fn execute(&self, sql: &str) -> Result<QueryResult, QueryError> {
+        let start = Instant::now();
+
+        // 文単位に分割して同じ接続で順次実行し、最後の文の結果を返す
+        let statements = StatementSplitter::new("mysql").split(sql);
+        let is_script = statements.len() > 1;
+        let mut conn = self.pool.acquire().await.map_err(Self::map_error)?;
+        let _running = Self::track_running(
+            &self.running,
+            statements.iter().map(|range| range.text(sql)),
+        );
+
+        let mut last_result = Self::build_result(&[]);
+        for range in statements.iter() {
+            let rows = sqlx::query(range.text(sql))
+                .fetch_all(&mut *conn)
+                .await
+                .map_err(|e| locate_statement_error(Self::map_error(e), sql, range, is_script))?;
+            last_result = Self::build_result(&rows);
+        }
+
+        last_result.execution_time_ms = start.elapsed().as_millis() as u64;
+        Ok(last_result)
+    }
+
+    async fn execute_with_params(
+        &self,
+        sql: &str,
+        params: &[QueryParam],
+    ) -> Result<QueryResult, QueryError> {
+        let start = Instant::now();
+
+        // バインドパラメータは単一文のみ対応
+        let statements = StatementSplitter::new("mysql").split(sql);
+        if statements.len() > 1 {
+            if params.is_empty() {
+                return self.execute(sql).await;
+            }
+            return Err(multiple_statements_with_params_error());
+        }
+        let statement = statements
+            .first()
+            .map(|range| range.text(sql))
+            .unwrap_or(sql);
+
+        let mut conn = self.pool.acquire().await.map_err(Self::map_error)?;
+        let _running = Self::track_running(&self.running, [statement]);
+
+        let rows = bind_params(sqlx::query(statement), params)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(Self::map_error)?;
+
+        let mut result = Self::build_result(&rows);
+        result.execution_time_ms = start.elapsed().as_millis() as u64;
+        Ok(result)
     }
 
     async fn execute_mutation(&self, sql: &str) -> Result<MutationResult, QueryError> {
         let start = Instant::now();
+        let mut conn = self.pool.acquire().await.map_err(Self::map_error)?;
+        let _running = Self::track_running(&self.running, [sql]);
+
         let result = sqlx::query(sql)
-            .execute(&self.pool)
+            .execute(&mut *conn)
             .await
             .map_err(Self::map_error)?;
 
         Ok(MutationResult {
             affected_rows: result.rows_affected(),
             execution_time_ms: start.elapsed().as_millis() as u64,
+            undo: None,
         })
     }
 
@@ -335,8 +336,196 @@ impl QueryExecutor for MysqlExecutor {
             })?
     }
 
+    async fn execute_script(
+        &self,
+        sql: &str,
+        mode: ScriptErrorMode,
+        timeout: Duration,
+    ) -> Result<Vec<StatementResult>, QueryError> {
+        let statements = StatementSplitter::new("mysql").split(sql);
+        let mut conn = self.pool.acquire().await.map_err(Self::map_error)?;
+        let _running = Self::track_running(
+            &self.running,
+            statements.iter().map(|range| range.text(sql)),
+        );
+
+        Ok(execute_statements::<MySql>(
+            &mut conn,
+            sql,
+            &statements,
+            mode,
+            timeout,
+            Self::build_result,
+            Self::map_error,
+        )
+        .await)
+    }
+
+    async fn open_cursor(&self, sql: &str, buffer_size: usize) -> Result<QueryCursor, QueryError> {
+        let pool = self.pool.clone();
+        let sql = sql.trim().trim_end_matches(';').to_string();
+
+        Ok(QueryCursor::spawn(buffer_size, move |sender| async move {
+            let stream = sqlx::query(&sql).fetch(&pool);
+            QueryCursor::forward_rows(
+                stream,
+                sender,
+                Self::build_columns,
+                Self::convert_row,
+                Self::map_error,
+            )
+            .await;
+        }))
+    }
+
+    async fn cancel(&self, query_id: &str) -> Result<bool, QueryError> {
+        let statements = match self.running.get(query_id) {
+            Some(statements) => statements,
+            None => return Ok(false),
+        };
+
+        // 登録した文を実行中の接続を探す。文が終わって接続がプールに戻っていれば見つからず、
+        // 同じ文を実行中の接続が複数ある場合はどれか特定できないため停止しない
+        let placeholders = vec!["?"; statements.len()].join(", ");
+        let sql = format!(
+            "SELECT ID FROM information_schema.PROCESSLIST \
+             WHERE ID <> CONNECTION_ID() AND INFO IN ({})",
+            placeholders
+        );
+        let mut query = sqlx::query_scalar::<_, u64>(&sql);
+        for statement in statements.iter() {
+            query = query.bind(statement);
+        }
+        let connection_ids = query.fetch_all(&self.pool).await.map_err(Self::map_error)?;
+        let connection_id = match connection_ids.as_slice() {
+            [connection_id] => *connection_id,
+            _ => return Ok(false),
+        };
+
+        // 検索中に文が終わっていれば停止しない
+        if self.running.get(query_id).is_none() {
+            return Ok(false);
+        }
+
+        // 別の接続から実行中の文のみを停止する（接続自体は維持される）
+        sqlx::raw_sql(&format!("KILL QUERY {}", connection_id))
+            .execute(&self.pool)
+            .await
+            .map_err(Self::map_error)?;
+        Ok(true)
+    }
+
+    async fn begin_transaction(&self) -> Result<Box<dyn TransactionSession>, QueryError> {
+        let tx = self.pool.begin().await.map_err(Self::map_error)?;
+        Ok(Box::new(MysqlTransaction {
+            tx,
+            running: self.running.clone(),
+        }))
+    }
+
+    async fn ping(&self) -> Result<(), QueryError> {
+        let mut conn = self.pool.acquire().await.map_err(Self::map_error)?;
+        conn.ping().await.map_err(Self::map_error)
+    }
+
+    fn pool_status(&self) -> PoolStatus {
+        PoolStatus {
+            size: self.pool.size(),
+            idle: self.pool.num_idle() as u32,
+        }
+    }
+
     async fn close(&self) -> Result<(), QueryError> {
         self.pool.close().await;
         Ok(())
     }
 }
+
+/// MySQLのトランザクションセッション（専用接続を保持）
+pub struct MysqlTransaction {
+    tx: Transaction<'static, MySql>,
+    running: RunningQueries<RunningStatements>,
+}
+
+#[async_trait]
+impl TransactionSession for MysqlTransaction {
+    async fn execute_with_params(
+        &mut self,
+        sql: &str,
+        params: &[QueryParam],
+    ) -> Result<QueryResult, QueryError> {
+        let start = Instant::now();
+
+        // 複数文は順次実行し、最後の文の結果を返す（パラメータは単一文のみ）
+        let statements = StatementSplitter::new("mysql").split(sql);
+        let is_script = statements.len() > 1;
+        if is_script && !params.is_empty() {
+            return Err(multiple_statements_with_params_error());
+        }
+
+        let _running = MysqlExecutor::track_running(
+            &self.running,
+            statements.iter().map(|range| range.text(sql)),
+        );
+        let mut result = MysqlExecutor::build_result(&[]);
+        for range in statements.iter() {
+            let rows = bind_params(sqlx::query(range.text(sql)), params)
+                .fetch_all(&mut *self.tx)
+                .await
+                .map_err(|e| {
+                    locate_statement_error(MysqlExecutor::map_error(e), sql, range, is_script)
+                })?;
+            result = MysqlExecutor::build_result(&rows);
+        }
+
+        result.execution_time_ms = start.elapsed().as_millis() as u64;
+        Ok(result)
+    }
+
+    async fn execute_mutation(&mut self, sql: &str) -> Result<MutationResult, QueryError> {
+        let start = Instant::now();
+        let _running = MysqlExecutor::track_running(&self.running, [sql]);
+        let result = sqlx::query(sql)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(MysqlExecutor::map_error)?;
+
+        Ok(MutationResult {
+            affected_rows: result.rows_affected(),
+            execution_time_ms: start.elapsed().as_millis() as u64,
+            undo: None,
+        })
+    }
+
+    async fn execute_script(
+        &mut self,
+        sql: &str,
+        mode: ScriptErrorMode,
+        timeout: Duration,
+    ) -> Vec<StatementResult> {
+        let statements = StatementSplitter::new("mysql").split(sql);
+        let _running = MysqlExecutor::track_running(
+            &self.running,
+            statements.iter().map(|range| range.text(sql)),
+        );
+
+        execute_statements::<MySql>(
+            &mut self.tx,
+            sql,
+            &statements,
+            mode,
+            timeout,
+            MysqlExecutor::build_result,
+            MysqlExecutor::map_error,
+        )
+        .await
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), QueryError> {
+        self.tx.commit().await.map_err(MysqlExecutor::map_error)
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), QueryError> {
+        self.tx.rollback().await.map_err(MysqlExecutor::map_error)
+    }
+}
@@ -1,25 +1,45 @@
-use crate::connection::{ConnectionConfig, ConnectionInfo};
+use crate::connection::{
+    describe_tls_error, ConnectionConfig, ConnectionInfo, DatabaseType, SshTunnel,
+};
 use crate::models::database_structure::*;
 use crate::services::database_inspector::{DatabaseInspector, TableForeignKey};
 use async_trait::async_trait;
 use sqlx::mysql::MySqlPool;
 use sqlx::Row;
 use std::collections::HashMap;
+use std::sync::Arc;
 
+/// MySQL互換データベースのインスペクター
+///
+/// MariaDBもこのインスペクターで扱い、シーケンス・システムバージョニングテーブル・
+/// カラムのデフォルト値の表記などの差異を吸収する
 pub struct MysqlInspector {
     pool: MySqlPool,
     database_name: String,
+    database_type: DatabaseType,
+    /// SSHトンネル（プールが使う間は開いたままにする）
+    _tunnel: Option<Arc<SshTunnel>>,
 }
 
 impl MysqlInspector {
     pub async fn new(connection: &ConnectionInfo, password: Option<&str>) -> Result<Self, String> {
-        let connection_string = connection
-            .build_connection_string(password)
-            .map_err(|e| format!("Failed to build connection string: {}", e))?;
+        let mut connect_options = connection
+            .mysql_connect_options(password)
+            .map_err(|e| format!("Failed to build connection options: {}", e))?;
 
-        let pool = MySqlPool::connect(&connection_string)
+        let tunnel = connection
+            .open_ssh_tunnel()
             .await
-            .map_err(|e| format!("Failed to connect: {}", e))?;
+            .map_err(|e| e.to_string())?;
+        if let Some(tunnel) = &tunnel {
+            connect_options = tunnel.apply_mysql(connect_options);
+        }
+
+        let pool = MySqlPool::connect_with(connect_options)
+            .await
+            .map_err(|e| {
+                describe_tls_error(&e).unwrap_or_else(|| format!("Failed to connect: {}", e))
+            })?;
 
         let database_name = match &connection.connection {
             ConnectionConfig::Network(cfg) => cfg.database.clone(),
@@ -29,9 +49,72 @@ impl MysqlInspector {
         Ok(Self {
             pool,
             database_name,
+            database_type: connection.database_type,
+            _tunnel: tunnel,
         })
     }
 
+    fn is_mariadb(&self) -> bool {
+        self.database_type == DatabaseType::MariaDB
+    }
+
+    /// カラムのデフォルト値を方言に関係なく同じ表記にする
+    fn column_default(&self, value: Option<String>) -> Option<String> {
+        if self.is_mariadb() {
+            normalize_mariadb_default(value)
+        } else {
+            value
+        }
+    }
+
+    /// シーケンス一覧を取得（MariaDB 10.3以降）
+    async fn get_sequences(&self, schema: &str) -> Result<Vec<Sequence>, String> {
+        let query = r#"
+            SELECT CAST(TABLE_NAME AS CHAR) as sequence_name
+            FROM information_schema.tables
+            WHERE TABLE_SCHEMA = ?
+              AND TABLE_TYPE = 'SEQUENCE'
+            ORDER BY TABLE_NAME
+        "#;
+
+        let rows = sqlx::query(query)
+            .bind(schema)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to get sequences: {}", e))?;
+
+        let mut sequences = Vec::new();
+        for row in rows {
+            let name: String = row.get("sequence_name");
+
+            // 設定値はシーケンス自体をSELECTして取得する（権限がなければ名前だけ返す）
+            let detail_query = format!(
+                "SELECT start_value, increment, minimum_value, maximum_value, cycle_option FROM `{}`.`{}`",
+                schema.replace('`', "``"),
+                name.replace('`', "``")
+            );
+            let detail = sqlx::query(&detail_query)
+                .fetch_optional(&self.pool)
+                .await
+                .ok()
+                .flatten();
+
+            let field = |column: &str| detail.as_ref().and_then(|d| d.try_get(column).ok());
+            let cycle: Option<i64> = field("cycle_option");
+            sequences.push(Sequence {
+                name,
+                schema: schema.to_string(),
+                start_value: field("start_value"),
+                increment: field("increment"),
+                min_value: field("minimum_value"),
+                max_value: field("maximum_value"),
+                cycle: cycle.is_some_and(|c| c != 0),
+            });
+        }
+
+        Ok(sequences)
+    }
+
     async fn get_all_columns_in_schema(
         &self,
         schema: &str,
@@ -48,6 +131,7 @@ impl MysqlInspector {
                 COLUMN_KEY = 'MUL' as is_foreign_key,
                 COLUMN_KEY = 'UNI' as is_unique,
                 EXTRA LIKE '%auto_increment%' as is_auto_increment,
+                (EXTRA LIKE '%VIRTUAL GENERATED%' OR EXTRA LIKE '%STORED GENERATED%') as is_generated,
                 CAST(ORDINAL_POSITION AS SIGNED) as ordinal_position,
                 CAST(COLUMN_COMMENT AS CHAR) as comment
             FROM information_schema.columns
@@ -69,6 +153,7 @@ impl MysqlInspector {
             let is_foreign_int: i32 = row.get("is_foreign_key");
             let is_unique_int: i32 = row.get("is_unique");
             let is_auto_int: i32 = row.get("is_auto_increment");
+            let is_generated_int: i32 = row.get("is_generated");
             let comment: String = row.get("comment");
 
             let column = Column {
@@ -76,11 +161,12 @@ impl MysqlInspector {
                 data_type: row.get("data_type"),
                 display_type: row.get("display_type"),
                 nullable: nullable_int != 0,
-                default_value: row.get("column_default"),
+                default_value: self.column_default(row.get("column_default")),
                 is_primary_key: is_primary_int != 0,
                 is_foreign_key: is_foreign_int != 0,
                 is_unique: is_unique_int != 0,
                 is_auto_increment: is_auto_int != 0,
+                is_generated: is_generated_int != 0,
                 ordinal_position: row.get("ordinal_position"),
                 comment: if comment.is_empty() {
                     None
@@ -334,12 +420,18 @@ impl DatabaseInspector for MysqlInspector {
 
             let tables = self.get_tables(&name).await?;
             let views = self.get_views(&name).await?;
+            let sequences = if self.is_mariadb() {
+                self.get_sequences(&name).await?
+            } else {
+                Vec::new()
+            };
 
             schemas.push(Schema {
                 name,
                 is_system,
                 tables,
                 views,
+                sequences,
             });
         }
 
@@ -351,10 +443,11 @@ impl DatabaseInspector for MysqlInspector {
             SELECT
                 CAST(t.TABLE_NAME AS CHAR) as table_name,
                 CAST(t.TABLE_COMMENT AS CHAR) as comment,
-                CAST(t.TABLE_ROWS AS SIGNED) as estimated_rows
+                CAST(t.TABLE_ROWS AS SIGNED) as estimated_rows,
+                t.TABLE_TYPE = 'SYSTEM VERSIONED' as is_system_versioned
             FROM information_schema.tables t
             WHERE t.TABLE_SCHEMA = ?
-              AND t.TABLE_TYPE = 'BASE TABLE'
+              AND t.TABLE_TYPE IN ('BASE TABLE', 'SYSTEM VERSIONED')
             ORDER BY t.TABLE_NAME
         "#;
 
@@ -375,6 +468,7 @@ impl DatabaseInspector for MysqlInspector {
             let name: String = row.get("table_name");
             let comment: Option<String> = row.get("comment");
             let estimated_row_count: Option<i64> = row.try_get("estimated_rows").ok();
+            let is_system_versioned_int: i32 = row.get("is_system_versioned");
 
             let columns = columns_map.get(&name).cloned().unwrap_or_default();
             let indexes = indexes_map.get(&name).cloned().unwrap_or_default();
@@ -396,6 +490,7 @@ impl DatabaseInspector for MysqlInspector {
                 indexes,
                 foreign_keys,
                 referenced_by,
+                is_system_versioned: is_system_versioned_int != 0,
             });
         }
 
@@ -452,6 +547,7 @@ impl DatabaseInspector for MysqlInspector {
                 COLUMN_KEY = 'MUL' as is_foreign_key,
                 COLUMN_KEY = 'UNI' as is_unique,
                 EXTRA LIKE '%auto_increment%' as is_auto_increment,
+                (EXTRA LIKE '%VIRTUAL GENERATED%' OR EXTRA LIKE '%STORED GENERATED%') as is_generated,
                 CAST(ORDINAL_POSITION AS SIGNED) as ordinal_position,
                 CAST(COLUMN_COMMENT AS CHAR) as comment
             FROM information_schema.columns
@@ -475,6 +571,7 @@ impl DatabaseInspector for MysqlInspector {
                 let is_foreign_int: i32 = row.get("is_foreign_key");
                 let is_unique_int: i32 = row.get("is_unique");
                 let is_auto_int: i32 = row.get("is_auto_increment");
+                let is_generated_int: i32 = row.get("is_generated");
                 let comment: String = row.get("comment");
 
                 Column {
@@ -482,11 +579,12 @@ impl DatabaseInspector for MysqlInspector {
                     data_type: row.get("data_type"),
                     display_type: row.get("display_type"),
                     nullable: nullable_int != 0,
-                    default_value: row.get("column_default"),
+                    default_value: self.column_default(row.get("column_default")),
                     is_primary_key: is_primary_int != 0,
                     is_foreign_key: is_foreign_int != 0,
                     is_unique: is_unique_int != 0,
                     is_auto_increment: is_auto_int != 0,
+                    is_generated: is_generated_int != 0,
                     ordinal_position: row.get("ordinal_position"),
                     comment: if comment.is_empty() {
                         None
@@ -714,7 +812,7 @@ impl DatabaseInspector for MysqlInspector {
                 CAST(TABLE_COMMENT AS CHAR) as comment,
                 CAST(TABLE_ROWS AS SIGNED) as estimated_rows
             FROM information_schema.tables
-            WHERE TABLE_TYPE IN ('BASE TABLE', 'VIEW')
+            WHERE TABLE_TYPE IN ('BASE TABLE', 'SYSTEM VERSIONED', 'VIEW')
             ORDER BY
                 CASE WHEN TABLE_SCHEMA = ? THEN 0 ELSE 1 END,
                 TABLE_SCHEMA,
@@ -756,13 +854,37 @@ impl DatabaseInspector for MysqlInspector {
 
     async fn get_database_structure(&self) -> Result<DatabaseStructure, String> {
         let schemas = self.get_schemas().await?;
+        let database_type = if self.is_mariadb() {
+            "mariadb"
+        } else {
+            "mysql"
+        };
 
         Ok(DatabaseStructure {
             connection_id: String::new(), // 呼び出し元で設定
             database_name: self.database_name.clone(),
-            database_type: "mysql".to_string(),
+            database_type: database_type.to_string(),
             schemas,
             fetched_at: chrono::Utc::now().to_rfc3339(),
         })
     }
 }
+
+/// MariaDBのカラムのデフォルト値をMySQLと同じ表記にする
+///
+/// MariaDB 10.2.7以降はデフォルト値を式として返すため、文字列は引用符付き、
+/// デフォルトなしは `NULL` になる
+pub fn normalize_mariadb_default(value: Option<String>) -> Option<String> {
+    let value = value?;
+    if value == "NULL" {
+        return None;
+    }
+
+    match value
+        .strip_prefix('\'')
+        .and_then(|inner| inner.strip_suffix('\''))
+    {
+        Some(inner) => Some(inner.replace("''", "'").replace("\\\\", "\\")),
+        None => Some(value),
+    }
+}
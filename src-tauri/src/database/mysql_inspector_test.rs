@@ -0,0 +1,32 @@
+use crate::database::mysql_inspector::normalize_mariadb_default;
+
+#[test]
+fn test_normalize_mariadb_default() {
+    // デフォルトなしは `NULL` という文字列で返る
+    assert_eq!(normalize_mariadb_default(Some("NULL".to_string())), None);
+    assert_eq!(normalize_mariadb_default(None), None);
+
+    // 文字列リテラルは引用符を外す
+    assert_eq!(
+        normalize_mariadb_default(Some("'active'".to_string())).as_deref(),
+        Some("active")
+    );
+    assert_eq!(
+        normalize_mariadb_default(Some("'it''s'".to_string())).as_deref(),
+        Some("it's")
+    );
+    assert_eq!(
+        normalize_mariadb_default(Some("'NULL'".to_string())).as_deref(),
+        Some("NULL")
+    );
+
+    // 数値・式はそのまま
+    assert_eq!(
+        normalize_mariadb_default(Some("0".to_string())).as_deref(),
+        Some("0")
+    );
+    assert_eq!(
+        normalize_mariadb_default(Some("current_timestamp()".to_string())).as_deref(),
+        Some("current_timestamp()")
+    );
+}
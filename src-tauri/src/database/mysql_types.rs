@@ -0,0 +1,131 @@
+use crate::database::wkb::mysql_geometry_to_wkt;
+use crate::models::query_result::QueryValue;
+use sqlx::mysql::{MySql, MySqlRow};
+use sqlx::{Decode, Row, TypeInfo, ValueRef};
+
+/// 値の変換関数（取得できなければ None）
+type Converter = fn(&MySqlRow, usize) -> Option<QueryValue>;
+
+/// MySQLの型変換表（型名は sqlx の `MySqlTypeInfo::name()`）
+///
+/// sqlxの型互換チェックは UNSIGNED フラグやSET型などで厳しすぎるため、変換は型名で決めて取得時のチェックは行わない
+pub const MYSQL_TYPE_CONVERSIONS: &[(&[&str], Converter)] = &[
+    // TINYINT(1)
+    (&["BOOLEAN"], |row, i| {
+        get::<bool>(row, i).map(QueryValue::Bool)
+    }),
+    (
+        &["TINYINT", "SMALLINT", "MEDIUMINT", "INT", "BIGINT"],
+        |row, i| get::<i64>(row, i).map(QueryValue::Int),
+    ),
+    (
+        &[
+            "TINYINT UNSIGNED",
+            "SMALLINT UNSIGNED",
+            "MEDIUMINT UNSIGNED",
+            "INT UNSIGNED",
+            "BIGINT UNSIGNED",
+            "YEAR",
+        ],
+        |row, i| get::<u64>(row, i).map(unsigned_value),
+    ),
+    (&["FLOAT"], |row, i| {
+        get::<f32>(row, i).map(|v| QueryValue::Float(v as f64))
+    }),
+    (&["DOUBLE"], |row, i| {
+        get::<f64>(row, i).map(QueryValue::Float)
+    }),
+    (&["DECIMAL"], |row, i| {
+        get::<bigdecimal::BigDecimal>(row, i).map(|v| QueryValue::Decimal(v.to_string()))
+    }),
+    (
+        &[
+            "CHAR",
+            "VARCHAR",
+            "TINYTEXT",
+            "TEXT",
+            "MEDIUMTEXT",
+            "LONGTEXT",
+            "ENUM",
+            "SET",
+        ],
+        |row, i| get::<String>(row, i).map(QueryValue::String),
+    ),
+    (
+        &[
+            "BINARY",
+            "VARBINARY",
+            "TINYBLOB",
+            "BLOB",
+            "MEDIUMBLOB",
+            "LONGBLOB",
+        ],
+        |row, i| get::<Vec<u8>>(row, i).map(QueryValue::Bytes),
+    ),
+    (&["DATE"], |row, i| {
+        get::<chrono::NaiveDate>(row, i).map(QueryValue::Date)
+    }),
+    (&["TIME"], |row, i| {
+        get::<chrono::NaiveTime>(row, i).map(QueryValue::Time)
+    }),
+    (&["DATETIME"], |row, i| {
+        get::<chrono::NaiveDateTime>(row, i).map(QueryValue::Timestamp)
+    }),
+    // TIMESTAMP はUTCで返る
+    (&["TIMESTAMP"], |row, i| {
+        get::<chrono::DateTime<chrono::Utc>>(row, i)
+            .map(|v| QueryValue::TimestampTz(v.fixed_offset()))
+    }),
+    (&["JSON"], |row, i| {
+        get::<serde_json::Value>(row, i)
+            .map(QueryValue::Json)
+            .or_else(|| get::<String>(row, i).map(QueryValue::String))
+    }),
+    (&["BIT"], |row, i| {
+        get::<u64>(row, i).map(|v| QueryValue::String(format!("{:#b}", v)))
+    }),
+    // 内部形式（SRID + WKB）をWKTにする。解釈できなければバイト列のまま
+    (&["GEOMETRY"], |row, i| {
+        get::<Vec<u8>>(row, i).map(|bytes| match mysql_geometry_to_wkt(&bytes) {
+            Some(wkt) => QueryValue::String(wkt),
+            None => QueryValue::Bytes(bytes),
+        })
+    }),
+];
+
+/// 1つの値を変換する
+pub fn convert_mysql_value(row: &MySqlRow, index: usize) -> QueryValue {
+    let raw = match row.try_get_raw(index) {
+        Ok(raw) => raw,
+        Err(_) => return QueryValue::Null,
+    };
+    if raw.is_null() {
+        return QueryValue::Null;
+    }
+
+    let type_info = raw.type_info();
+    let type_name = type_info.name();
+    let converter = MYSQL_TYPE_CONVERSIONS
+        .iter()
+        .find(|(names, _)| names.contains(&type_name))
+        .map(|(_, converter)| *converter);
+
+    match converter {
+        Some(converter) => converter(row, index),
+        // 未対応の型は文字列として取得を試みる
+        None => get::<String>(row, index).map(QueryValue::String),
+    }
+    .unwrap_or(QueryValue::Null)
+}
+
+/// 符号なし整数を変換する（i64に収まらない値は桁あふれさせず Decimal にする）
+pub fn unsigned_value(value: u64) -> QueryValue {
+    match i64::try_from(value) {
+        Ok(value) => QueryValue::Int(value),
+        Err(_) => QueryValue::Decimal(value.to_string()),
+    }
+}
+
+fn get<'r, T: Decode<'r, MySql>>(row: &'r MySqlRow, index: usize) -> Option<T> {
+    row.try_get_unchecked::<T, _>(index).ok()
+}
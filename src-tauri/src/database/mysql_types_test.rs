@@ -0,0 +1,200 @@
+use crate::connection::{ConnectionConfig, ConnectionInfo, DatabaseType, NetworkConfig};
+use crate::database::mysql_types::unsigned_value;
+use crate::database::wkb::{mysql_geometry_to_wkt, wkb_to_wkt};
+use crate::database::MysqlExecutor;
+use crate::models::query_result::QueryValue;
+use crate::services::query_executor::QueryExecutor;
+
+fn wkb_point(x: f64, y: f64) -> Vec<u8> {
+    let mut bytes = vec![1];
+    bytes.extend(1u32.to_le_bytes());
+    bytes.extend(x.to_le_bytes());
+    bytes.extend(y.to_le_bytes());
+    bytes
+}
+
+#[test]
+fn test_unsigned_value_does_not_overflow() {
+    assert_eq!(unsigned_value(42), QueryValue::Int(42));
+    assert_eq!(unsigned_value(i64::MAX as u64), QueryValue::Int(i64::MAX));
+    assert_eq!(
+        unsigned_value(9_223_372_036_854_775_808),
+        QueryValue::Decimal("9223372036854775808".to_string())
+    );
+    assert_eq!(
+        unsigned_value(u64::MAX),
+        QueryValue::Decimal("18446744073709551615".to_string())
+    );
+}
+
+#[test]
+fn test_wkb_point_and_srid() {
+    assert_eq!(
+        wkb_to_wkt(&wkb_point(1.0, 2.5)).as_deref(),
+        Some("POINT(1 2.5)")
+    );
+
+    // ビッグエンディアン
+    let mut big_endian = vec![0];
+    big_endian.extend(1u32.to_be_bytes());
+    big_endian.extend((-3.0f64).to_be_bytes());
+    big_endian.extend(4.0f64.to_be_bytes());
+    assert_eq!(wkb_to_wkt(&big_endian).as_deref(), Some("POINT(-3 4)"));
+
+    // MySQLの内部形式は先頭4バイトがSRID
+    let mut internal = 4326u32.to_le_bytes().to_vec();
+    internal.extend(wkb_point(139.7, 35.6));
+    assert_eq!(
+        mysql_geometry_to_wkt(&internal).as_deref(),
+        Some("POINT(139.7 35.6)")
+    );
+}
+
+#[test]
+fn test_wkb_collections() {
+    let mut multipoint = vec![1];
+    multipoint.extend(4u32.to_le_bytes());
+    multipoint.extend(2u32.to_le_bytes());
+    multipoint.extend(wkb_point(1.0, 1.0));
+    multipoint.extend(wkb_point(2.0, 3.0));
+    assert_eq!(
+        wkb_to_wkt(&multipoint).as_deref(),
+        Some("MULTIPOINT((1 1),(2 3))")
+    );
+
+    let mut polygon = vec![1];
+    polygon.extend(3u32.to_le_bytes());
+    polygon.extend(1u32.to_le_bytes());
+    polygon.extend(4u32.to_le_bytes());
+    for (x, y) in [(0.0f64, 0.0f64), (1.0, 0.0), (1.0, 1.0), (0.0, 0.0)] {
+        polygon.extend(x.to_le_bytes());
+        polygon.extend(y.to_le_bytes());
+    }
+    assert_eq!(
+        wkb_to_wkt(&polygon).as_deref(),
+        Some("POLYGON((0 0,1 0,1 1,0 0))")
+    );
+
+    let mut collection = vec![1];
+    collection.extend(7u32.to_le_bytes());
+    collection.extend(2u32.to_le_bytes());
+    collection.extend(wkb_point(1.0, 1.0));
+    collection.extend(&polygon);
+    assert_eq!(
+        wkb_to_wkt(&collection).as_deref(),
+        Some("GEOMETRYCOLLECTION(POINT(1 1),POLYGON((0 0,1 0,1 1,0 0)))")
+    );
+}
+
+#[test]
+fn test_wkb_rejects_malformed_data() {
+    assert_eq!(wkb_to_wkt(&[]), None);
+    assert_eq!(wkb_to_wkt(&wkb_point(1.0, 2.0)[..10]), None);
+    // 要素数が実際のデータより多い
+    let mut linestring = vec![1];
+    linestring.extend(2u32.to_le_bytes());
+    linestring.extend(1000u32.to_le_bytes());
+    assert_eq!(wkb_to_wkt(&linestring), None);
+    assert_eq!(mysql_geometry_to_wkt(&[0, 0]), None);
+}
+
+/// docker/mysql のフィクスチャに接続する（docker-compose で起動しておく）
+async fn docker_executor(database: &str) -> MysqlExecutor {
+    let connection = ConnectionInfo::new(
+        "Docker MySQL".to_string(),
+        DatabaseType::MySQL,
+        ConnectionConfig::Network(NetworkConfig {
+            host: "127.0.0.1".to_string(),
+            port: 3307,
+            database: database.to_string(),
+            username: "benchmark".to_string(),
+            encrypted_password: None,
+            ssl_config: None,
+            ssh_tunnel: None,
+            options: None,
+        }),
+    );
+    MysqlExecutor::new(&connection, Some("benchmark"))
+        .await
+        .expect("docker/mysql のコンテナを起動してください")
+}
+
+#[tokio::test]
+#[ignore = "docker/mysql のコンテナが必要"]
+async fn test_mysql_unsigned_types_fixture() {
+    let executor = docker_executor("test_unsigned").await;
+    let result = executor
+        .execute("SELECT tiny_u, int_u, bigint_u, bigint_s FROM unsigned_types ORDER BY id")
+        .await
+        .unwrap();
+
+    let last = &result.rows[3].values;
+    assert_eq!(last[0], QueryValue::Int(255));
+    assert_eq!(last[1], QueryValue::Int(4_294_967_295));
+    assert_eq!(
+        last[2],
+        QueryValue::Decimal("9223372036854775808".to_string())
+    );
+    assert_eq!(last[3], QueryValue::Int(-1));
+    assert_eq!(result.rows[2].values[2], QueryValue::Int(i64::MAX));
+}
+
+#[tokio::test]
+#[ignore = "docker/mysql のコンテナが必要"]
+async fn test_mysql_type_test_fixture() {
+    let executor = docker_executor("benchmark_small").await;
+    let result = executor
+        .execute(
+            "SELECT d_val, yr_val, dec_val, enum_val, set_val, json_val, bit_val \
+             FROM type_test ORDER BY id",
+        )
+        .await
+        .unwrap();
+
+    let first = &result.rows[0].values;
+    assert_eq!(
+        first[0],
+        QueryValue::Date(chrono::NaiveDate::from_ymd_opt(2026, 3, 15).unwrap())
+    );
+    assert_eq!(first[1], QueryValue::Int(2026));
+    assert_eq!(first[2], QueryValue::Decimal("1234.5678".to_string()));
+    assert_eq!(first[3], QueryValue::String("banana".to_string()));
+    assert_eq!(first[4], QueryValue::String("red,blue".to_string()));
+    assert_eq!(
+        first[5],
+        QueryValue::Json(serde_json::json!({"key": "value", "num": 42}))
+    );
+    assert_eq!(first[6], QueryValue::String("0b10101010".to_string()));
+
+    assert!(result.rows[2]
+        .values
+        .iter()
+        .all(|value| *value == QueryValue::Null));
+}
+
+#[tokio::test]
+#[ignore = "docker/mysql のコンテナが必要"]
+async fn test_mysql_spatial_fixture() {
+    let executor = docker_executor("benchmark_small").await;
+    let result = executor
+        .execute("SELECT geom_val FROM spatial_test ORDER BY id")
+        .await
+        .unwrap();
+
+    let values: Vec<QueryValue> = result
+        .rows
+        .into_iter()
+        .map(|row| row.values[0].clone())
+        .collect();
+    let expected = [
+        "POINT(1 2)",
+        "LINESTRING(0 0,1 1,2 0)",
+        "POLYGON((0 0,4 0,4 4,0 4,0 0),(1 1,2 1,2 2,1 1))",
+        "MULTIPOINT((1 1),(2.5 3))",
+        "GEOMETRYCOLLECTION(POINT(1 1),LINESTRING(0 0,1 1))",
+    ];
+    for (value, wkt) in values.iter().zip(expected) {
+        assert_eq!(*value, QueryValue::String(wkt.to_string()));
+    }
+    assert_eq!(values[5], QueryValue::Null);
+}
@@ -1,17 +1,28 @@
-use crate::connection::ConnectionInfo;
+use crate::connection::{describe_tls_error, ConnectionInfo, SshTunnel};
+use crate::models::connection_pool::PoolSettings;
 use crate::models::mutation_result::MutationResult;
 use crate::models::query_result::{
-    QueryError, QueryErrorCode, QueryErrorDetails, QueryResult, QueryResultColumn, QueryResultRow,
-    QueryValue,
+    QueryError, QueryErrorCode, QueryErrorDetails, QueryInterval, QueryParam, QueryResult,
+    QueryResultColumn, QueryResultRow, QueryValue, ScriptErrorMode, StatementResult,
 };
-use crate::services::query_executor::QueryExecutor;
+use crate::services::query_executor::{
+    bind_params, current_query_id, execute_statements, locate_statement_error,
+    multiple_statements_with_params_error, PoolStatus, QueryCursor, QueryExecutor, RunningQueries,
+    RunningQueryGuard, RunningStatements, TransactionSession,
+};
+use crate::sql::statement_splitter::StatementSplitter;
 use async_trait::async_trait;
-use sqlx::postgres::{PgPool, PgRow};
-use sqlx::{Column, Row, TypeInfo, ValueRef};
+use sqlx::postgres::{PgPool, PgPoolOptions, PgRow, Postgres};
+use sqlx::{Column, Connection, Executor, Row, Transaction, TypeInfo, ValueRef};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 pub struct PostgresExecutor {
     pool: PgPool,
+    /// SSHトンネル（プールが使う間は開いたままにする）
+    _tunnel: Option<Arc<SshTunnel>>,
+    /// 実行中クエリの文（サーバー側キャンセル用）
+    running: RunningQueries<RunningStatements>,
 }
 
 impl PostgresExecutor {
@@ -19,37 +30,76 @@ impl PostgresExecutor {
         connection: &ConnectionInfo,
         password: Option<&str>,
     ) -> Result<Self, QueryError> {
-        let connection_string =
+        Self::with_pool_settings(connection, password, &PoolSettings::default()).await
+    }
+
+    /// プール設定を指定して接続する
+    pub async fn with_pool_settings(
+        connection: &ConnectionInfo,
+        password: Option<&str>,
+        settings: &PoolSettings,
+    ) -> Result<Self, QueryError> {
+        let mut connect_options =
             connection
-                .build_connection_string(password)
+                .postgres_connect_options(password)
                 .map_err(|e| QueryError {
                     code: QueryErrorCode::ConnectionFailed,
-                    message: format!("Failed to build connection string: {}", e),
+                    message: format!("Failed to build connection options: {}", e),
                     details: None,
                     native_code: None,
                 })?;
 
-        let pool = PgPool::connect(&connection_string)
+        let tunnel = connection.open_ssh_tunnel().await.map_err(|e| QueryError {
+            code: QueryErrorCode::ConnectionFailed,
+            message: e.to_string(),
+            details: None,
+            native_code: None,
+        })?;
+        if let Some(tunnel) = &tunnel {
+            connect_options = tunnel.apply_postgres(connect_options);
+        }
+
+        let init_sql = connection.session_init_sql().map_err(|e| QueryError {
+            code: QueryErrorCode::ConnectionFailed,
+            message: format!("Invalid session settings: {}", e),
+            details: None,
+            native_code: None,
+        })?;
+
+        let mut options = PgPoolOptions::new()
+            .max_connections(settings.max_connections)
+            .idle_timeout(Duration::from_secs(settings.idle_timeout_seconds));
+        if let Some(sql) = init_sql {
+            // プールが開くすべての接続を同じセッション設定にする
+            options = options.after_connect(move |conn, _meta| {
+                let sql = sql.clone();
+                Box::pin(async move {
+                    conn.execute(sql.as_str()).await?;
+                    Ok(())
+                })
+            });
+        }
+
+        let pool = options
+            .connect_with(connect_options)
             .await
             .map_err(|e| QueryError {
                 code: QueryErrorCode::ConnectionFailed,
-                message: format!("Failed to connect: {}", e),
+                message: describe_tls_error(&e)
+                    .unwrap_or_else(|| format!("Failed to connect: {}", e)),
                 details: None,
                 native_code: None,
             })?;
 
-        Ok(Self { pool })
+        Ok(Self {
+            pool,
+            _tunnel: tunnel,
+            running: RunningQueries::new(),
+        })
     }
 
     /// SQL文をセミコロンで分割（簡易的な実装）
     /// TODO: 文字列リテラル内のセミコロンを考慮した高度な分割が必要な場合は改善
-    fn split_sql_statements(sql: &str) -> Vec<String> {
-        sql.split(';')
-            .map(|s| s.trim().to_string())
-            .filter(|s| !s.is_empty())
-            .collect()
-    }
-
     fn map_error(err: sqlx::Error) -> QueryError {
         match &err {
             sqlx::Error::Database(db_err) => {
@@ -140,6 +190,9 @@ impl PostgresExecutor {
             // Authentication (28xxx)
             Some(c) if c.starts_with("28") => QueryErrorCode::AuthenticationFailed,
 
+            // Operator Intervention (57xxx)
+            Some(c) if c.starts_with("57014") => QueryErrorCode::QueryCancelled,
+
             _ => QueryErrorCode::Unknown,
         };
 
@@ -171,6 +224,18 @@ impl PostgresExecutor {
         None
     }
 
+    /// 行からカラム情報を生成
+    fn build_columns(row: &PgRow) -> Vec<QueryResultColumn> {
+        row.columns()
+            .iter()
+            .map(|col| QueryResultColumn {
+                name: col.name().to_string(),
+                data_type: col.type_info().name().to_string(),
+                nullable: true, // PgRowからは正確なnullable取得が難しいためtrue
+            })
+            .collect()
+    }
+
     fn convert_row(row: &PgRow, columns: &[QueryResultColumn]) -> QueryResultRow {
         let values = columns
             .iter()
@@ -211,7 +276,12 @@ impl PostgresExecutor {
                             .ok()
                             .map(QueryValue::Int)
                             .unwrap_or(QueryValue::Null),
-                        "FLOAT4" | "FLOAT8" => row
+                        "FLOAT4" => row
+                            .try_get::<f32, _>(i)
+                            .ok()
+                            .map(|v| QueryValue::Float(v as f64))
+                            .unwrap_or(QueryValue::Null),
+                        "FLOAT8" => row
                             .try_get::<f64, _>(i)
                             .ok()
                             .map(QueryValue::Float)
@@ -219,7 +289,7 @@ impl PostgresExecutor {
                         "NUMERIC" => row
                             .try_get::<bigdecimal::BigDecimal, _>(i)
                             .ok()
-                            .map(|v| QueryValue::String(v.to_string()))
+                            .map(|v| QueryValue::Decimal(v.to_string()))
                             .unwrap_or(QueryValue::Null),
                         "MONEY" => {
                             // MONEY はバイナリプロトコルで 8 バイト big-endian i64（1/100 通貨単位）
@@ -235,7 +305,7 @@ impl PostgresExecutor {
                                     let dollars = abs_cents / 100;
                                     let remaining_cents = abs_cents % 100;
                                     let sign = if is_negative { "-" } else { "" };
-                                    QueryValue::String(format!(
+                                    QueryValue::Decimal(format!(
                                         "{}{}.{:02}",
                                         sign, dollars, remaining_cents
                                     ))
@@ -259,32 +329,32 @@ impl PostgresExecutor {
                         "UUID" => row
                             .try_get::<uuid::Uuid, _>(i)
                             .ok()
-                            .map(|u| QueryValue::String(u.to_string()))
+                            .map(QueryValue::Uuid)
                             .unwrap_or(QueryValue::Null),
                         "DATE" => row
                             .try_get::<chrono::NaiveDate, _>(i)
                             .ok()
-                            .map(|d| QueryValue::String(d.to_string()))
+                            .map(QueryValue::Date)
                             .unwrap_or(QueryValue::Null),
                         "TIME" => row
                             .try_get::<chrono::NaiveTime, _>(i)
                             .ok()
-                            .map(|t| QueryValue::String(t.to_string()))
+                            .map(QueryValue::Time)
                             .unwrap_or(QueryValue::Null),
                         "TIMESTAMP" => row
                             .try_get::<chrono::NaiveDateTime, _>(i)
                             .ok()
-                            .map(|t| QueryValue::String(t.to_string()))
+                            .map(QueryValue::Timestamp)
                             .unwrap_or(QueryValue::Null),
                         "TIMESTAMPTZ" => row
-                            .try_get::<chrono::DateTime<chrono::Utc>, _>(i)
+                            .try_get::<chrono::DateTime<chrono::FixedOffset>, _>(i)
                             .ok()
-                            .map(|t| QueryValue::String(t.to_string()))
+                            .map(QueryValue::TimestampTz)
                             .unwrap_or(QueryValue::Null),
                         "JSON" | "JSONB" => row
                             .try_get::<serde_json::Value, _>(i)
                             .ok()
-                            .map(|v| QueryValue::String(v.to_string()))
+                            .map(QueryValue::Json)
                             .or_else(|| {
                                 row.try_get::<String, _>(i).ok().map(QueryValue::String)
                             })
@@ -337,50 +407,17 @@ impl PostgresExecutor {
                             .ok()
                             .map(QueryValue::String)
                             .unwrap_or(QueryValue::Null),
-                        "INTERVAL" => {
-                            row.try_get::<sqlx::postgres::types::PgInterval, _>(i)
-                                .ok()
-                                .map(|v| {
-                                    let mut parts: Vec<String> = Vec::new();
-                                    if v.months != 0 {
-                                        let years = v.months / 12;
-                                        let months = v.months % 12;
-                                        if years != 0 {
-                                            parts.push(format!("{} years", years));
-                                        }
-                                        if months != 0 {
-                                            parts.push(format!("{} months", months));
-                                        }
-                                    }
-                                    if v.days != 0 {
-                                        parts.push(format!("{} days", v.days));
-                                    }
-                                    if v.microseconds != 0 {
-                                        let total_secs = v.microseconds / 1_000_000;
-                                        let micros = v.microseconds.abs() % 1_000_000;
-                                        let hours = total_secs / 3600;
-                                        let mins = (total_secs % 3600) / 60;
-                                        let secs = total_secs % 60;
-                                        if micros != 0 {
-                                            parts.push(format!(
-                                                "{:02}:{:02}:{:02}.{:06}",
-                                                hours, mins, secs, micros
-                                            ));
-                                        } else {
-                                            parts.push(format!(
-                                                "{:02}:{:02}:{:02}",
-                                                hours, mins, secs
-                                            ));
-                                        }
-                                    }
-                                    QueryValue::String(if parts.is_empty() {
-                                        "00:00:00".to_string()
-                                    } else {
-                                        parts.join(" ")
-                                    })
+                        "INTERVAL" => row
+                            .try_get::<sqlx::postgres::types::PgInterval, _>(i)
+                            .ok()
+                            .map(|v| {
+                                QueryValue::Interval(QueryInterval {
+                                    months: v.months,
+                                    days: v.days,
+                                    microseconds: v.microseconds,
                                 })
-                                .unwrap_or(QueryValue::Null)
-                        }
+                            })
+                            .unwrap_or(QueryValue::Null),
                         "XML" | "BIT" | "VARBIT" | "TSVECTOR" | "TSQUERY" => row
                             .try_get::<String, _>(i)
                             .ok()
@@ -411,81 +448,42 @@ impl PostgresExecutor {
                             // PostgreSQL 配列型: sqlx の display_name() は "INT4[]", "TEXT[]" 形式で返す
                             let element_type = &name[..name.len() - 2];
                             match element_type {
-                                "INT2" => row
-                                    .try_get::<Vec<Option<i16>>, _>(i)
-                                    .ok()
-                                    .map(|v| {
-                                        let s = v
-                                            .iter()
-                                            .map(|x| x.map_or("null".to_string(), |n| n.to_string()))
-                                            .collect::<Vec<_>>()
-                                            .join(", ");
-                                        QueryValue::String(format!("[{}]", s))
-                                    })
-                                    .unwrap_or(QueryValue::Null),
-                                "INT4" => row
-                                    .try_get::<Vec<Option<i32>>, _>(i)
-                                    .ok()
-                                    .map(|v| {
-                                        let s = v
-                                            .iter()
-                                            .map(|x| x.map_or("null".to_string(), |n| n.to_string()))
-                                            .collect::<Vec<_>>()
-                                            .join(", ");
-                                        QueryValue::String(format!("[{}]", s))
-                                    })
-                                    .unwrap_or(QueryValue::Null),
-                                "INT8" => row
-                                    .try_get::<Vec<Option<i64>>, _>(i)
-                                    .ok()
-                                    .map(|v| {
-                                        let s = v
-                                            .iter()
-                                            .map(|x| x.map_or("null".to_string(), |n| n.to_string()))
-                                            .collect::<Vec<_>>()
-                                            .join(", ");
-                                        QueryValue::String(format!("[{}]", s))
-                                    })
-                                    .unwrap_or(QueryValue::Null),
-                                "FLOAT4" | "FLOAT8" => row
-                                    .try_get::<Vec<Option<f64>>, _>(i)
-                                    .ok()
-                                    .map(|v| {
-                                        let s = v
-                                            .iter()
-                                            .map(|x| x.map_or("null".to_string(), |n| n.to_string()))
-                                            .collect::<Vec<_>>()
-                                            .join(", ");
-                                        QueryValue::String(format!("[{}]", s))
-                                    })
-                                    .unwrap_or(QueryValue::Null),
-                                "TEXT" | "VARCHAR" | "CHAR" | "NAME" => row
-                                    .try_get::<Vec<Option<String>>, _>(i)
-                                    .ok()
-                                    .map(|v| {
-                                        let s = v
-                                            .iter()
-                                            .map(|x| {
-                                                x.as_deref()
-                                                    .map_or("null".to_string(), |s| format!("\"{}\"", s))
-                                            })
-                                            .collect::<Vec<_>>()
-                                            .join(", ");
-                                        QueryValue::String(format!("[{}]", s))
-                                    })
-                                    .unwrap_or(QueryValue::Null),
-                                "BOOL" => row
-                                    .try_get::<Vec<Option<bool>>, _>(i)
-                                    .ok()
-                                    .map(|v| {
-                                        let s = v
-                                            .iter()
-                                            .map(|x| x.map_or("null".to_string(), |b| b.to_string()))
-                                            .collect::<Vec<_>>()
-                                            .join(", ");
-                                        QueryValue::String(format!("[{}]", s))
-                                    })
-                                    .unwrap_or(QueryValue::Null),
+                                "INT2" => array_value::<i16>(row, i, |v| QueryValue::Int(v as i64)),
+                                "INT4" => array_value::<i32>(row, i, |v| QueryValue::Int(v as i64)),
+                                "INT8" => array_value::<i64>(row, i, QueryValue::Int),
+                                "FLOAT4" => {
+                                    array_value::<f32>(row, i, |v| QueryValue::Float(v as f64))
+                                }
+                                "FLOAT8" => array_value::<f64>(row, i, QueryValue::Float),
+                                "NUMERIC" => array_value::<bigdecimal::BigDecimal>(row, i, |v| {
+                                    QueryValue::Decimal(v.to_string())
+                                }),
+                                "TEXT" | "VARCHAR" | "CHAR" | "BPCHAR" | "NAME" => {
+                                    array_value::<String>(row, i, QueryValue::String)
+                                }
+                                "BOOL" => array_value::<bool>(row, i, QueryValue::Bool),
+                                "UUID" => array_value::<uuid::Uuid>(row, i, QueryValue::Uuid),
+                                "DATE" => {
+                                    array_value::<chrono::NaiveDate>(row, i, QueryValue::Date)
+                                }
+                                "TIME" => {
+                                    array_value::<chrono::NaiveTime>(row, i, QueryValue::Time)
+                                }
+                                "TIMESTAMP" => array_value::<chrono::NaiveDateTime>(
+                                    row,
+                                    i,
+                                    QueryValue::Timestamp,
+                                ),
+                                "TIMESTAMPTZ" => {
+                                    array_value::<chrono::DateTime<chrono::FixedOffset>>(
+                                        row,
+                                        i,
+                                        QueryValue::TimestampTz,
+                                    )
+                                }
+                                "JSON" | "JSONB" => {
+                                    array_value::<serde_json::Value>(row, i, QueryValue::Json)
+                                }
                                 other => QueryValue::String(format!(
                                     "[array<{}>]",
                                     other.to_lowercase().trim_end_matches("[]")
@@ -508,6 +506,54 @@ impl PostgresExecutor {
 
         QueryResultRow { values }
     }
+
+    /// 現在のクエリIDがあれば、実行する文を実行中クエリとして登録する
+    fn track_running<'a>(
+        running: &RunningQueries<RunningStatements>,
+        statements: impl IntoIterator<Item = &'a str>,
+    ) -> Option<RunningQueryGuard<RunningStatements>> {
+        let query_id = current_query_id()?;
+        let statements = statements.into_iter().map(str::to_string).collect();
+        Some(running.register(query_id, Arc::new(statements)))
+    }
+
+    /// 取得した行から結果を組み立てる（実行時間は呼び出し側で設定）
+    fn build_result(rows: &[PgRow]) -> QueryResult {
+        // カラム情報を取得
+        let columns = rows.first().map(Self::build_columns).unwrap_or_default();
+
+        // 行データを変換
+        let result_rows: Vec<QueryResultRow> = rows
+            .iter()
+            .map(|row| Self::convert_row(row, &columns))
+            .collect();
+
+        QueryResult {
+            columns,
+            rows: result_rows,
+            row_count: rows.len(),
+            execution_time_ms: 0,
+            warnings: vec![],
+        }
+    }
+}
+
+/// 配列型の値を要素ごとに変換する（NULL要素は `QueryValue::Null`）
+fn array_value<T>(row: &PgRow, index: usize, element: fn(T) -> QueryValue) -> QueryValue
+where
+    Vec<Option<T>>: for<'r> sqlx::Decode<'r, Postgres> + sqlx::Type<Postgres>,
+{
+    row.try_get::<Vec<Option<T>>, _>(index)
+        .ok()
+        .map(|values| {
+            QueryValue::Array(
+                values
+                    .into_iter()
+                    .map(|value| value.map_or(QueryValue::Null, element))
+                    .collect(),
+            )
+        })
+        .unwrap_or(QueryValue::Null)
 }
 
 #[async_trait]
@@ -515,9 +561,15 @@ impl QueryExecutor for PostgresExecutor {
     async fn execute(&self, sql: &str) -> Result<QueryResult, QueryError> {
         let start = std::time::Instant::now();
 
-        // セミコロンで分割して複数のSQL文を順次実行
-        // 最後の文の結果を返す（SET文などは結果を返さないため）
-        let statements = Self::split_sql_statements(sql);
+        // 文単位に分割して順次実行し、最後の文の結果を返す（SET文などは結果を返さないため）
+        // SET文の効果を後続の文に引き継ぐため、同じ接続で実行する
+        let statements = StatementSplitter::new("postgresql").split(sql);
+        let is_script = statements.len() > 1;
+        let mut conn = self.pool.acquire().await.map_err(Self::map_error)?;
+        let _running = Self::track_running(
+            &self.running,
+            statements.iter().map(|range| range.text(sql)),
+        );
 
         let mut last_result = QueryResult {
             columns: vec![],
@@ -527,11 +579,8 @@ impl QueryExecutor for PostgresExecutor {
             warnings: vec![],
         };
 
-        for statement in statements.iter() {
-            let trimmed = statement.trim();
-            if trimmed.is_empty() {
-                continue;
-            }
+        for range in statements.iter() {
+            let trimmed = range.text(sql);
 
             // SET文かどうかを判定
             let is_set_statement = trimmed.to_uppercase().starts_with("SET ");
@@ -539,44 +588,21 @@ impl QueryExecutor for PostgresExecutor {
             if is_set_statement {
                 // SET文は結果を返さないため、executeで実行
                 sqlx::query(trimmed)
-                    .execute(&self.pool)
+                    .execute(&mut *conn)
                     .await
-                    .map_err(Self::map_error)?;
+                    .map_err(|e| {
+                        locate_statement_error(Self::map_error(e), sql, range, is_script)
+                    })?;
             } else {
                 // SELECT文など、結果を返すクエリ
                 let rows = sqlx::query(trimmed)
-                    .fetch_all(&self.pool)
+                    .fetch_all(&mut *conn)
                     .await
-                    .map_err(Self::map_error)?;
-
-                // カラム情報を取得
-                let columns = if let Some(first_row) = rows.first() {
-                    first_row
-                        .columns()
-                        .iter()
-                        .map(|col| QueryResultColumn {
-                            name: col.name().to_string(),
-                            data_type: col.type_info().name().to_string(),
-                            nullable: true, // PgRowからは正確なnullable取得が難しいためtrue
-                        })
-                        .collect()
-                } else {
-                    vec![]
-                };
-
-                // 行データを変換
-                let result_rows: Vec<QueryResultRow> = rows
-                    .iter()
-                    .map(|row| Self::convert_row(row, &columns))
-                    .collect();
-
-                last_result = QueryResult {
-                    columns,
-                    rows: result_rows,
-                    row_count: rows.len(),
-                    execution_time_ms: 0, // 後で設定
-                    warnings: vec![],
-                };
+                    .map_err(|e| {
+                        locate_statement_error(Self::map_error(e), sql, range, is_script)
+                    })?;
+
+                last_result = Self::build_result(&rows);
             }
         }
 
@@ -584,16 +610,53 @@ impl QueryExecutor for PostgresExecutor {
         Ok(last_result)
     }
 
+    async fn execute_with_params(
+        &self,
+        sql: &str,
+        params: &[QueryParam],
+    ) -> Result<QueryResult, QueryError> {
+        let start = Instant::now();
+
+        // バインドパラメータは単一文のみ対応
+        let statements = StatementSplitter::new("postgresql").split(sql);
+        if statements.len() > 1 {
+            if params.is_empty() {
+                return self.execute(sql).await;
+            }
+            return Err(multiple_statements_with_params_error());
+        }
+        let statement = statements
+            .first()
+            .map(|range| range.text(sql))
+            .unwrap_or(sql);
+
+        let mut conn = self.pool.acquire().await.map_err(Self::map_error)?;
+        let _running = Self::track_running(&self.running, [statement]);
+
+        let rows = bind_params(sqlx::query(statement), params)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(Self::map_error)?;
+
+        let mut result = Self::build_result(&rows);
+        result.execution_time_ms = start.elapsed().as_millis() as u64;
+        Ok(result)
+    }
+
     async fn execute_mutation(&self, sql: &str) -> Result<MutationResult, QueryError> {
         let start = Instant::now();
+        let mut conn = self.pool.acquire().await.map_err(Self::map_error)?;
+        let _running = Self::track_running(&self.running, [sql]);
+
         let result = sqlx::query(sql)
-            .execute(&self.pool)
+            .execute(&mut *conn)
             .await
             .map_err(Self::map_error)?;
 
         Ok(MutationResult {
             affected_rows: result.rows_affected(),
             execution_time_ms: start.elapsed().as_millis() as u64,
+            undo: None,
         })
     }
 
@@ -612,8 +675,185 @@ impl QueryExecutor for PostgresExecutor {
             })?
     }
 
+    async fn execute_script(
+        &self,
+        sql: &str,
+        mode: ScriptErrorMode,
+        timeout: Duration,
+    ) -> Result<Vec<StatementResult>, QueryError> {
+        let statements = StatementSplitter::new("postgresql").split(sql);
+        let mut conn = self.pool.acquire().await.map_err(Self::map_error)?;
+        let _running = Self::track_running(
+            &self.running,
+            statements.iter().map(|range| range.text(sql)),
+        );
+
+        Ok(execute_statements::<Postgres>(
+            &mut conn,
+            sql,
+            &statements,
+            mode,
+            timeout,
+            Self::build_result,
+            Self::map_error,
+        )
+        .await)
+    }
+
+    async fn open_cursor(&self, sql: &str, buffer_size: usize) -> Result<QueryCursor, QueryError> {
+        let pool = self.pool.clone();
+        let sql = sql.trim().trim_end_matches(';').to_string();
+
+        Ok(QueryCursor::spawn(buffer_size, move |sender| async move {
+            let stream = sqlx::query(&sql).fetch(&pool);
+            QueryCursor::forward_rows(
+                stream,
+                sender,
+                Self::build_columns,
+                Self::convert_row,
+                Self::map_error,
+            )
+            .await;
+        }))
+    }
+
+    async fn cancel(&self, query_id: &str) -> Result<bool, QueryError> {
+        let statements = match self.running.get(query_id) {
+            Some(statements) => statements,
+            None => return Ok(false),
+        };
+
+        // 別の接続から、登録した文を実行中のバックエンドにだけキャンセルを要求する。
+        // 文が終わって接続がプールに戻っていれば対象は見つからず、無関係なクエリは止めない。
+        // 同じ文を実行中のバックエンドが複数ある場合はどれか特定できないため送らない
+        let cancelled = sqlx::query_scalar::<_, bool>(
+            "SELECT pg_cancel_backend(pid) FROM ( \
+                 SELECT pid, count(*) OVER () AS matches FROM pg_stat_activity \
+                 WHERE state = 'active' AND pid <> pg_backend_pid() \
+                   AND datname = current_database() AND usename = current_user \
+                   AND EXISTS ( \
+                     SELECT 1 FROM unnest($1::text[]) AS s(statement) \
+                     WHERE left(s.statement, length(query)) = query) \
+             ) AS candidates WHERE matches = 1",
+        )
+        .bind(statements.as_slice())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(Self::map_error)?;
+        Ok(cancelled.unwrap_or(false))
+    }
+
+    async fn begin_transaction(&self) -> Result<Box<dyn TransactionSession>, QueryError> {
+        let tx = self.pool.begin().await.map_err(Self::map_error)?;
+        Ok(Box::new(PostgresTransaction {
+            tx,
+            running: self.running.clone(),
+        }))
+    }
+
+    async fn ping(&self) -> Result<(), QueryError> {
+        let mut conn = self.pool.acquire().await.map_err(Self::map_error)?;
+        conn.ping().await.map_err(Self::map_error)
+    }
+
+    fn pool_status(&self) -> PoolStatus {
+        PoolStatus {
+            size: self.pool.size(),
+            idle: self.pool.num_idle() as u32,
+        }
+    }
+
     async fn close(&self) -> Result<(), QueryError> {
         self.pool.close().await;
         Ok(())
     }
 }
+
+/// PostgreSQLのトランザクションセッション（専用接続を保持）
+pub struct PostgresTransaction {
+    tx: Transaction<'static, Postgres>,
+    running: RunningQueries<RunningStatements>,
+}
+
+#[async_trait]
+impl TransactionSession for PostgresTransaction {
+    async fn execute_with_params(
+        &mut self,
+        sql: &str,
+        params: &[QueryParam],
+    ) -> Result<QueryResult, QueryError> {
+        let start = Instant::now();
+
+        // 複数文は順次実行し、最後の文の結果を返す（パラメータは単一文のみ）
+        let statements = StatementSplitter::new("postgresql").split(sql);
+        let is_script = statements.len() > 1;
+        if is_script && !params.is_empty() {
+            return Err(multiple_statements_with_params_error());
+        }
+
+        let _running = PostgresExecutor::track_running(
+            &self.running,
+            statements.iter().map(|range| range.text(sql)),
+        );
+        let mut result = PostgresExecutor::build_result(&[]);
+        for range in statements.iter() {
+            let rows = bind_params(sqlx::query(range.text(sql)), params)
+                .fetch_all(&mut *self.tx)
+                .await
+                .map_err(|e| {
+                    locate_statement_error(PostgresExecutor::map_error(e), sql, range, is_script)
+                })?;
+            result = PostgresExecutor::build_result(&rows);
+        }
+
+        result.execution_time_ms = start.elapsed().as_millis() as u64;
+        Ok(result)
+    }
+
+    async fn execute_mutation(&mut self, sql: &str) -> Result<MutationResult, QueryError> {
+        let start = Instant::now();
+        let _running = PostgresExecutor::track_running(&self.running, [sql]);
+        let result = sqlx::query(sql)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(PostgresExecutor::map_error)?;
+
+        Ok(MutationResult {
+            affected_rows: result.rows_affected(),
+            execution_time_ms: start.elapsed().as_millis() as u64,
+            undo: None,
+        })
+    }
+
+    async fn execute_script(
+        &mut self,
+        sql: &str,
+        mode: ScriptErrorMode,
+        timeout: Duration,
+    ) -> Vec<StatementResult> {
+        let statements = StatementSplitter::new("postgresql").split(sql);
+        let _running = PostgresExecutor::track_running(
+            &self.running,
+            statements.iter().map(|range| range.text(sql)),
+        );
+
+        execute_statements::<Postgres>(
+            &mut self.tx,
+            sql,
+            &statements,
+            mode,
+            timeout,
+            PostgresExecutor::build_result,
+            PostgresExecutor::map_error,
+        )
+        .await
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), QueryError> {
+        self.tx.commit().await.map_err(PostgresExecutor::map_error)
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), QueryError> {
+        self.tx.rollback().await.map_err(PostgresExecutor::map_error)
+    }
+}
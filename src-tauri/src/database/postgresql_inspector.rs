@@ -1,25 +1,38 @@
-use crate::connection::{ConnectionConfig, ConnectionInfo};
+use crate::connection::{describe_tls_error, ConnectionConfig, ConnectionInfo, SshTunnel};
 use crate::models::database_structure::*;
 use crate::services::database_inspector::{DatabaseInspector, TableForeignKey};
 use async_trait::async_trait;
 use sqlx::postgres::PgPool;
 use sqlx::Row;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 pub struct PostgresqlInspector {
     pool: PgPool,
     database_name: String,
+    /// SSHトンネル（プールが使う間は開いたままにする）
+    _tunnel: Option<Arc<SshTunnel>>,
 }
 
 impl PostgresqlInspector {
     pub async fn new(connection: &ConnectionInfo, password: Option<&str>) -> Result<Self, String> {
-        let connection_string = connection
-            .build_connection_string(password)
-            .map_err(|e| format!("Failed to build connection string: {}", e))?;
+        let mut connect_options = connection
+            .postgres_connect_options(password)
+            .map_err(|e| format!("Failed to build connection options: {}", e))?;
 
-        let pool = PgPool::connect(&connection_string)
+        let tunnel = connection
+            .open_ssh_tunnel()
             .await
-            .map_err(|e| format!("Failed to connect: {}", e))?;
+            .map_err(|e| e.to_string())?;
+        if let Some(tunnel) = &tunnel {
+            connect_options = tunnel.apply_postgres(connect_options);
+        }
+
+        let pool = PgPool::connect_with(connect_options)
+            .await
+            .map_err(|e| {
+                describe_tls_error(&e).unwrap_or_else(|| format!("Failed to connect: {}", e))
+            })?;
 
         let database_name = match &connection.connection {
             ConnectionConfig::Network(cfg) => cfg.database.clone(),
@@ -29,6 +42,7 @@ impl PostgresqlInspector {
         Ok(Self {
             pool,
             database_name,
+            _tunnel: tunnel,
         })
     }
 
@@ -85,6 +99,7 @@ impl PostgresqlInspector {
                     c.is_identity = 'YES' OR
                     COALESCE(c.column_default LIKE '%nextval%', false)
                 ) as is_auto_increment,
+                c.is_generated = 'ALWAYS' as is_generated,
                 c.ordinal_position,
                 col_description(
                     (quote_ident(c.table_schema) || '.' || quote_ident(c.table_name))::regclass,
@@ -114,6 +129,7 @@ impl PostgresqlInspector {
                 is_foreign_key: row.get("is_foreign_key"),
                 is_unique: row.get("is_unique"),
                 is_auto_increment: row.get("is_auto_increment"),
+                is_generated: row.get("is_generated"),
                 ordinal_position: row.get("ordinal_position"),
                 comment: row.get("comment"),
             };
@@ -349,6 +365,7 @@ impl DatabaseInspector for PostgresqlInspector {
                 is_system,
                 tables,
                 views,
+                sequences: Vec::new(),
             });
         }
 
@@ -404,6 +421,7 @@ impl DatabaseInspector for PostgresqlInspector {
                 indexes,
                 foreign_keys,
                 referenced_by,
+                is_system_versioned: false,
             });
         }
 
@@ -498,6 +516,7 @@ impl DatabaseInspector for PostgresqlInspector {
                     c.is_identity = 'YES' OR
                     COALESCE(c.column_default LIKE '%nextval%', false)
                 ) as is_auto_increment,
+                c.is_generated = 'ALWAYS' as is_generated,
                 c.ordinal_position,
                 col_description(
                     (quote_ident(c.table_schema) || '.' || quote_ident(c.table_name))::regclass,
@@ -528,6 +547,7 @@ impl DatabaseInspector for PostgresqlInspector {
                 is_foreign_key: row.get("is_foreign_key"),
                 is_unique: row.get("is_unique"),
                 is_auto_increment: row.get("is_auto_increment"),
+                is_generated: row.get("is_generated"),
                 ordinal_position: row.get("ordinal_position"),
                 comment: row.get("comment"),
             })
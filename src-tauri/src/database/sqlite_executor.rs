@@ -1,25 +1,47 @@
 use crate::connection::ConnectionInfo;
+use crate::database::sqlite_types::convert_sqlite_value;
+use crate::models::connection_pool::PoolSettings;
 use crate::models::mutation_result::MutationResult;
 use crate::models::query_result::{
-    QueryError, QueryErrorCode, QueryErrorDetails, QueryResult, QueryResultColumn, QueryResultRow,
-    QueryValue,
+    QueryError, QueryErrorCode, QueryErrorDetails, QueryParam, QueryResult, QueryResultColumn,
+    QueryResultRow, ScriptErrorMode, StatementResult,
 };
-use crate::services::query_executor::QueryExecutor;
+use crate::services::query_executor::{
+    bind_params, current_query_id, execute_statements, locate_statement_error,
+    multiple_statements_with_params_error, PoolStatus, QueryCursor, QueryExecutor, RunningQueries,
+    RunningQueryGuard, TransactionSession,
+};
+use crate::sql::statement_splitter::StatementSplitter;
 use async_trait::async_trait;
-use sqlx::sqlite::{SqlitePool, SqliteRow};
-use sqlx::{Column, Row, TypeInfo, ValueRef};
+use sqlx::sqlite::{Sqlite, SqliteConnection, SqlitePool, SqlitePoolOptions, SqliteRow};
+use sqlx::{Column, Connection, Executor, Row, Transaction, TypeInfo};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+/// キャンセル確認のためにプログレスハンドラーを呼び出す間隔（VM命令数）
+const PROGRESS_HANDLER_OPS: i32 = 1000;
+
 pub struct SqliteExecutor {
     pool: SqlitePool,
+    /// 実行中クエリの割り込みフラグ（キャンセル用）
+    running: RunningQueries<Arc<AtomicBool>>,
 }
 
 impl SqliteExecutor {
     pub async fn new(connection: &ConnectionInfo) -> Result<Self, QueryError> {
+        Self::with_pool_settings(connection, &PoolSettings::default()).await
+    }
+
+    /// プール設定を指定して接続する
+    pub async fn with_pool_settings(
+        connection: &ConnectionInfo,
+        settings: &PoolSettings,
+    ) -> Result<Self, QueryError> {
         // SQLite has no password
         let connection_string =
             connection
-                .build_connection_string(None)
+                .build_connection_string()
                 .map_err(|e| QueryError {
                     code: QueryErrorCode::ConnectionFailed,
                     message: format!("Failed to build connection string: {}", e),
@@ -27,7 +49,29 @@ impl SqliteExecutor {
                     native_code: None,
                 })?;
 
-        let pool = SqlitePool::connect(&connection_string)
+        let init_sql = connection.session_init_sql().map_err(|e| QueryError {
+            code: QueryErrorCode::ConnectionFailed,
+            message: format!("Invalid session settings: {}", e),
+            details: None,
+            native_code: None,
+        })?;
+
+        let mut options = SqlitePoolOptions::new()
+            .max_connections(settings.max_connections)
+            .idle_timeout(Duration::from_secs(settings.idle_timeout_seconds));
+        if let Some(sql) = init_sql {
+            // プールが開くすべての接続を同じセッション設定にする
+            options = options.after_connect(move |conn, _meta| {
+                let sql = sql.clone();
+                Box::pin(async move {
+                    conn.execute(sql.as_str()).await?;
+                    Ok(())
+                })
+            });
+        }
+
+        let pool = options
+            .connect(&connection_string)
             .await
             .map_err(|e| QueryError {
                 code: QueryErrorCode::ConnectionFailed,
@@ -36,7 +80,10 @@ impl SqliteExecutor {
                 native_code: None,
             })?;
 
-        Ok(Self { pool })
+        Ok(Self {
+            pool,
+            running: RunningQueries::new(),
+        })
     }
 
     fn map_error(err: sqlx::Error) -> QueryError {
@@ -106,6 +153,7 @@ impl SqliteExecutor {
                 "5" | "6" => QueryErrorCode::ConnectionFailed, // SQLITE_BUSY, SQLITE_LOCKED
                 "19" => QueryErrorCode::UniqueViolation,       // SQLITE_CONSTRAINT
                 "8" => QueryErrorCode::PermissionDenied,       // SQLITE_READONLY
+                "9" => QueryErrorCode::QueryCancelled,         // SQLITE_INTERRUPT
                 "14" => QueryErrorCode::ConnectionFailed,      // SQLITE_CANTOPEN
                 "26" => QueryErrorCode::DatabaseNotFound,      // SQLITE_NOTADB
                 _ => QueryErrorCode::Unknown,
@@ -114,111 +162,142 @@ impl SqliteExecutor {
         }
     }
 
+    /// 行からカラム情報を生成
+    fn build_columns(row: &SqliteRow) -> Vec<QueryResultColumn> {
+        row.columns()
+            .iter()
+            .map(|col| QueryResultColumn {
+                name: col.name().to_string(),
+                data_type: col.type_info().name().to_string(),
+                nullable: true,
+            })
+            .collect()
+    }
+
     fn convert_row(row: &SqliteRow, columns: &[QueryResultColumn]) -> QueryResultRow {
         let values = columns
             .iter()
             .enumerate()
-            .map(|(i, _col)| {
-                let raw_val = row.try_get_raw(i).ok();
-
-                if let Some(val) = raw_val {
-                    if val.is_null() {
-                        return QueryValue::Null;
-                    }
-
-                    let type_info = val.type_info();
-                    match type_info.name() {
-                        "BOOLEAN" => row
-                            .try_get::<bool, _>(i)
-                            .ok()
-                            .map(QueryValue::Bool)
-                            .unwrap_or(QueryValue::Null),
-                        "INTEGER" | "INT" | "BIGINT" => row
-                            .try_get::<i64, _>(i)
-                            .ok()
-                            .map(QueryValue::Int)
-                            .unwrap_or(QueryValue::Null),
-                        "REAL" | "FLOAT" | "DOUBLE" => row
-                            .try_get::<f64, _>(i)
-                            .ok()
-                            .map(QueryValue::Float)
-                            .unwrap_or(QueryValue::Null),
-                        "TEXT" | "VARCHAR" => row
-                            .try_get::<String, _>(i)
-                            .ok()
-                            .map(QueryValue::String)
-                            .unwrap_or(QueryValue::Null),
-                        "BLOB" => row
-                            .try_get::<Vec<u8>, _>(i)
-                            .ok()
-                            .map(QueryValue::Bytes)
-                            .unwrap_or(QueryValue::Null),
-                        _ => row
-                            .try_get::<String, _>(i)
-                            .ok()
-                            .map(QueryValue::String)
-                            .unwrap_or(QueryValue::Null),
-                    }
-                } else {
-                    QueryValue::Null
-                }
-            })
+            .map(|(i, col)| convert_sqlite_value(row, i, &col.data_type))
             .collect();
 
         QueryResultRow { values }
     }
-}
 
-#[async_trait]
-impl QueryExecutor for SqliteExecutor {
-    async fn execute(&self, sql: &str) -> Result<QueryResult, QueryError> {
-        let start = std::time::Instant::now();
+    /// 現在のクエリIDがあれば、割り込みフラグを実行中クエリとして登録する
+    ///
+    /// プログレスハンドラーがフラグを監視し、立っていれば実行中の文を中断させる（sqlite3_interrupt 相当）
+    async fn track_running(
+        running: &RunningQueries<Arc<AtomicBool>>,
+        conn: &mut SqliteConnection,
+    ) -> Result<Option<RunningQueryGuard<Arc<AtomicBool>>>, QueryError> {
+        let query_id = match current_query_id() {
+            Some(query_id) => query_id,
+            None => return Ok(None),
+        };
 
-        let rows = sqlx::query(sql)
-            .fetch_all(&self.pool)
+        let interrupted = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&interrupted);
+        // 割り込み後はフラグを戻すため、接続がプールに戻った後の文には影響しない
+        conn.lock_handle()
             .await
-            .map_err(Self::map_error)?;
-
-        let execution_time_ms = start.elapsed().as_millis() as u64;
+            .map_err(Self::map_error)?
+            .set_progress_handler(PROGRESS_HANDLER_OPS, move || {
+                !flag.swap(false, Ordering::SeqCst)
+            });
+        Ok(Some(running.register(query_id, interrupted)))
+    }
 
-        let columns = if let Some(first_row) = rows.first() {
-            first_row
-                .columns()
-                .iter()
-                .map(|col| QueryResultColumn {
-                    name: col.name().to_string(),
-                    data_type: col.type_info().name().to_string(),
-                    nullable: true,
-                })
-                .collect()
-        } else {
-            vec![]
-        };
+    /// 取得した行から結果を組み立てる（実行時間は呼び出し側で設定）
+    fn build_result(rows: &[SqliteRow]) -> QueryResult {
+        let columns = rows.first().map(Self::build_columns).unwrap_or_default();
 
         let result_rows: Vec<QueryResultRow> = rows
             .iter()
             .map(|row| Self::convert_row(row, &columns))
             .collect();
 
-        Ok(QueryResult {
+        QueryResult {
             columns,
             rows: result_rows,
             row_count: rows.len(),
-            execution_time_ms,
+            execution_time_ms: 0,
             warnings: vec![],
-        })
+        }
+    }
+}
+
+#[async_trait]
+impl QueryExecutor for SqliteExecutor {
+    async fn execute(&self, sql: &str) -> Result<QueryResult, QueryError> {
+        let start = Instant::now();
+
+        // 文単位に分割して同じ接続で順次実行し、最後の文の結果を返す
+        let statements = StatementSplitter::new("sqlite").split(sql);
+        let is_script = statements.len() > 1;
+        let mut conn = self.pool.acquire().await.map_err(Self::map_error)?;
+        let _running = Self::track_running(&self.running, &mut conn).await?;
+
+        let mut last_result = Self::build_result(&[]);
+        for range in statements.iter() {
+            let rows = sqlx::query(range.text(sql))
+                .fetch_all(&mut *conn)
+                .await
+                .map_err(|e| locate_statement_error(Self::map_error(e), sql, range, is_script))?;
+            last_result = Self::build_result(&rows);
+        }
+
+        last_result.execution_time_ms = start.elapsed().as_millis() as u64;
+        Ok(last_result)
+    }
+
+    async fn execute_with_params(
+        &self,
+        sql: &str,
+        params: &[QueryParam],
+    ) -> Result<QueryResult, QueryError> {
+        let start = Instant::now();
+
+        // バインドパラメータは単一文のみ対応
+        let statements = StatementSplitter::new("sqlite").split(sql);
+        if statements.len() > 1 {
+            if params.is_empty() {
+                return self.execute(sql).await;
+            }
+            return Err(multiple_statements_with_params_error());
+        }
+        let statement = statements
+            .first()
+            .map(|range| range.text(sql))
+            .unwrap_or(sql);
+
+        let mut conn = self.pool.acquire().await.map_err(Self::map_error)?;
+        let _running = Self::track_running(&self.running, &mut conn).await?;
+
+        let rows = bind_params(sqlx::query(statement), params)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(Self::map_error)?;
+
+        let mut result = Self::build_result(&rows);
+        result.execution_time_ms = start.elapsed().as_millis() as u64;
+        Ok(result)
     }
 
     async fn execute_mutation(&self, sql: &str) -> Result<MutationResult, QueryError> {
         let start = Instant::now();
+        let mut conn = self.pool.acquire().await.map_err(Self::map_error)?;
+        let _running = Self::track_running(&self.running, &mut conn).await?;
+
         let result = sqlx::query(sql)
-            .execute(&self.pool)
+            .execute(&mut *conn)
             .await
             .map_err(Self::map_error)?;
 
         Ok(MutationResult {
             affected_rows: result.rows_affected(),
             execution_time_ms: start.elapsed().as_millis() as u64,
+            undo: None,
         })
     }
 
@@ -237,8 +316,164 @@ impl QueryExecutor for SqliteExecutor {
             })?
     }
 
+    async fn execute_script(
+        &self,
+        sql: &str,
+        mode: ScriptErrorMode,
+        timeout: Duration,
+    ) -> Result<Vec<StatementResult>, QueryError> {
+        let statements = StatementSplitter::new("sqlite").split(sql);
+        let mut conn = self.pool.acquire().await.map_err(Self::map_error)?;
+        let _running = Self::track_running(&self.running, &mut conn).await?;
+
+        Ok(execute_statements::<Sqlite>(
+            &mut conn,
+            sql,
+            &statements,
+            mode,
+            timeout,
+            Self::build_result,
+            Self::map_error,
+        )
+        .await)
+    }
+
+    async fn open_cursor(&self, sql: &str, buffer_size: usize) -> Result<QueryCursor, QueryError> {
+        let pool = self.pool.clone();
+        let sql = sql.trim().trim_end_matches(';').to_string();
+
+        Ok(QueryCursor::spawn(buffer_size, move |sender| async move {
+            let stream = sqlx::query(&sql).fetch(&pool);
+            QueryCursor::forward_rows(
+                stream,
+                sender,
+                Self::build_columns,
+                Self::convert_row,
+                Self::map_error,
+            )
+            .await;
+        }))
+    }
+
+    async fn cancel(&self, query_id: &str) -> Result<bool, QueryError> {
+        match self.running.get(query_id) {
+            Some(interrupted) => {
+                interrupted.store(true, Ordering::SeqCst);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn begin_transaction(&self) -> Result<Box<dyn TransactionSession>, QueryError> {
+        let tx = self.pool.begin().await.map_err(Self::map_error)?;
+        Ok(Box::new(SqliteTransaction {
+            tx,
+            running: self.running.clone(),
+        }))
+    }
+
+    async fn ping(&self) -> Result<(), QueryError> {
+        let mut conn = self.pool.acquire().await.map_err(Self::map_error)?;
+        conn.ping().await.map_err(Self::map_error)
+    }
+
+    fn pool_status(&self) -> PoolStatus {
+        PoolStatus {
+            size: self.pool.size(),
+            idle: self.pool.num_idle() as u32,
+        }
+    }
+
     async fn close(&self) -> Result<(), QueryError> {
         self.pool.close().await;
         Ok(())
     }
 }
+
+/// SQLiteのトランザクションセッション（専用接続を保持）
+pub struct SqliteTransaction {
+    tx: Transaction<'static, Sqlite>,
+    running: RunningQueries<Arc<AtomicBool>>,
+}
+
+#[async_trait]
+impl TransactionSession for SqliteTransaction {
+    async fn execute_with_params(
+        &mut self,
+        sql: &str,
+        params: &[QueryParam],
+    ) -> Result<QueryResult, QueryError> {
+        let start = Instant::now();
+
+        // 複数文は順次実行し、最後の文の結果を返す（パラメータは単一文のみ）
+        let statements = StatementSplitter::new("sqlite").split(sql);
+        let is_script = statements.len() > 1;
+        if is_script && !params.is_empty() {
+            return Err(multiple_statements_with_params_error());
+        }
+
+        let _running = SqliteExecutor::track_running(&self.running, &mut self.tx).await?;
+        let mut result = SqliteExecutor::build_result(&[]);
+        for range in statements.iter() {
+            let rows = bind_params(sqlx::query(range.text(sql)), params)
+                .fetch_all(&mut *self.tx)
+                .await
+                .map_err(|e| {
+                    locate_statement_error(SqliteExecutor::map_error(e), sql, range, is_script)
+                })?;
+            result = SqliteExecutor::build_result(&rows);
+        }
+
+        result.execution_time_ms = start.elapsed().as_millis() as u64;
+        Ok(result)
+    }
+
+    async fn execute_mutation(&mut self, sql: &str) -> Result<MutationResult, QueryError> {
+        let start = Instant::now();
+        let _running = SqliteExecutor::track_running(&self.running, &mut self.tx).await?;
+        let result = sqlx::query(sql)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(SqliteExecutor::map_error)?;
+
+        Ok(MutationResult {
+            affected_rows: result.rows_affected(),
+            execution_time_ms: start.elapsed().as_millis() as u64,
+            undo: None,
+        })
+    }
+
+    async fn execute_script(
+        &mut self,
+        sql: &str,
+        mode: ScriptErrorMode,
+        timeout: Duration,
+    ) -> Vec<StatementResult> {
+        let statements = StatementSplitter::new("sqlite").split(sql);
+        // 登録に失敗してもスクリプトは実行する（キャンセルできないだけ）
+        let _running = SqliteExecutor::track_running(&self.running, &mut self.tx)
+            .await
+            .ok()
+            .flatten();
+
+        execute_statements::<Sqlite>(
+            &mut self.tx,
+            sql,
+            &statements,
+            mode,
+            timeout,
+            SqliteExecutor::build_result,
+            SqliteExecutor::map_error,
+        )
+        .await
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), QueryError> {
+        self.tx.commit().await.map_err(SqliteExecutor::map_error)
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), QueryError> {
+        self.tx.rollback().await.map_err(SqliteExecutor::map_error)
+    }
+}
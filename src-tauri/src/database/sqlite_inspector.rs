@@ -14,7 +14,7 @@ pub struct SqliteInspector {
 impl SqliteInspector {
     pub async fn new(connection: &ConnectionInfo) -> Result<Self, String> {
         let connection_string = connection
-            .build_connection_string(None)
+            .build_connection_string()
             .map_err(|e| format!("Failed to build connection string: {}", e))?;
 
         let pool = SqlitePool::connect(&connection_string)
@@ -76,6 +76,7 @@ impl DatabaseInspector for SqliteInspector {
             is_system: false,
             tables,
             views,
+            sequences: Vec::new(),
         }])
     }
 
@@ -113,6 +114,7 @@ impl DatabaseInspector for SqliteInspector {
                 indexes,
                 foreign_keys,
                 referenced_by,
+                is_system_versioned: false,
             });
         }
 
@@ -152,15 +154,18 @@ impl DatabaseInspector for SqliteInspector {
     }
 
     async fn get_columns(&self, _schema: &str, table: &str) -> Result<Vec<Column>, String> {
-        let query = format!("PRAGMA table_info({})", table);
+        // table_info は生成列を返さないため table_xinfo を使う
+        let query = format!("PRAGMA table_xinfo({})", table);
 
         let rows = sqlx::query(&query)
             .fetch_all(&self.pool)
             .await
             .map_err(|e| format!("Failed to get columns: {}", e))?;
 
+        // hidden: 1 は仮想テーブルの隠し列、2・3 は生成列
         let columns = rows
             .iter()
+            .filter(|row| row.get::<i32, _>("hidden") != 1)
             .enumerate()
             .map(|(idx, row)| {
                 let name: String = row.get("name");
@@ -168,6 +173,7 @@ impl DatabaseInspector for SqliteInspector {
                 let notnull: i32 = row.get("notnull");
                 let default_value: Option<String> = row.get("dflt_value");
                 let pk: i32 = row.get("pk");
+                let hidden: i32 = row.get("hidden");
 
                 Column {
                     name,
@@ -179,6 +185,7 @@ impl DatabaseInspector for SqliteInspector {
                     is_foreign_key: false,    // 外部キー情報は別途取得
                     is_unique: false,         // インデックス情報から判定が必要
                     is_auto_increment: false, // AUTOINCREMENT判定が必要
+                    is_generated: hidden > 1,
                     ordinal_position: (idx + 1) as i32,
                     comment: None,
                 }
@@ -0,0 +1,76 @@
+use crate::models::query_result::QueryValue;
+use sqlx::sqlite::{Sqlite, SqliteRow};
+use sqlx::{Decode, Row, TypeInfo, ValueRef};
+
+/// 値の変換関数（取得できなければ None）
+type Converter = fn(&SqliteRow, usize) -> Option<QueryValue>;
+
+/// 宣言型による変換表（宣言型, 対象とする格納クラス, 変換）
+///
+/// SQLiteは列の宣言型と異なる格納クラスの値も保存できるため、格納クラスが合うときだけ宣言型で解釈する。
+/// 宣言型は sqlx が解釈できるもの（BOOLEAN・DATE・TIME・DATETIME）のみ判別できる
+pub const SQLITE_DECLARED_CONVERSIONS: &[(&str, &[&str], Converter)] = &[
+    ("BOOLEAN", &["INTEGER"], |row, i| {
+        get::<i64>(row, i).and_then(|v| match v {
+            0 => Some(QueryValue::Bool(false)),
+            1 => Some(QueryValue::Bool(true)),
+            _ => None,
+        })
+    }),
+    ("DATE", &["TEXT"], |row, i| {
+        get::<chrono::NaiveDate>(row, i).map(QueryValue::Date)
+    }),
+    ("TIME", &["TEXT"], |row, i| {
+        get::<chrono::NaiveTime>(row, i).map(QueryValue::Time)
+    }),
+    // 整数・実数はUNIX時刻として解釈する
+    ("DATETIME", &["TEXT", "INTEGER", "REAL"], |row, i| {
+        get::<chrono::NaiveDateTime>(row, i).map(QueryValue::Timestamp)
+    }),
+];
+
+/// 格納クラスによる変換表
+pub const SQLITE_STORAGE_CONVERSIONS: &[(&str, Converter)] = &[
+    ("INTEGER", |row, i| get::<i64>(row, i).map(QueryValue::Int)),
+    ("REAL", |row, i| get::<f64>(row, i).map(QueryValue::Float)),
+    ("TEXT", |row, i| {
+        get::<String>(row, i).map(QueryValue::String)
+    }),
+    ("BLOB", |row, i| {
+        get::<Vec<u8>>(row, i).map(QueryValue::Bytes)
+    }),
+];
+
+/// 1つの値を変換する
+///
+/// `declared_type` は列の宣言型（sqlx の `SqliteTypeInfo::name()`）
+pub fn convert_sqlite_value(row: &SqliteRow, index: usize, declared_type: &str) -> QueryValue {
+    let raw = match row.try_get_raw(index) {
+        Ok(raw) => raw,
+        Err(_) => return QueryValue::Null,
+    };
+    if raw.is_null() {
+        return QueryValue::Null;
+    }
+    // 値の型情報は格納クラス（INTEGER・REAL・TEXT・BLOB）
+    let storage_type_info = raw.type_info();
+    let storage = storage_type_info.name();
+
+    let declared = SQLITE_DECLARED_CONVERSIONS
+        .iter()
+        .find(|(name, storages, _)| *name == declared_type && storages.contains(&storage))
+        .and_then(|(_, _, converter)| converter(row, index));
+    if let Some(value) = declared {
+        return value;
+    }
+
+    SQLITE_STORAGE_CONVERSIONS
+        .iter()
+        .find(|(name, _)| *name == storage)
+        .and_then(|(_, converter)| converter(row, index))
+        .unwrap_or(QueryValue::Null)
+}
+
+fn get<'r, T: Decode<'r, Sqlite>>(row: &'r SqliteRow, index: usize) -> Option<T> {
+    row.try_get_unchecked::<T, _>(index).ok()
+}
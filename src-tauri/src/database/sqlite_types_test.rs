@@ -0,0 +1,101 @@
+use crate::connection::{ConnectionConfig, ConnectionInfo, DatabaseType, FileConfig};
+use crate::database::SqliteExecutor;
+use crate::models::query_result::{QueryValue, ScriptErrorMode};
+use crate::services::query_executor::QueryExecutor;
+use chrono::{NaiveDate, NaiveTime};
+use std::time::Duration;
+
+/// docker/sqlite の型確認用フィクスチャ
+const TYPE_TEST_SQL: &str = include_str!("../../../docker/sqlite/type-test.sql");
+
+async fn type_test_rows() -> Vec<Vec<QueryValue>> {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("type-test.db");
+    std::fs::File::create(&path).unwrap();
+    let connection = ConnectionInfo::new(
+        "Type Test".to_string(),
+        DatabaseType::SQLite,
+        ConnectionConfig::File(FileConfig {
+            file_path: path.to_string_lossy().to_string(),
+            readonly: false,
+        }),
+    );
+    let executor = SqliteExecutor::new(&connection).await.unwrap();
+    executor
+        .execute_script(
+            TYPE_TEST_SQL,
+            ScriptErrorMode::StopOnError,
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+
+    let result = executor
+        .execute(
+            "SELECT bool_val, int_val, real_val, text_val, blob_val, date_val, time_val, dt_val, num_val \
+             FROM type_test ORDER BY id",
+        )
+        .await
+        .unwrap();
+    result.rows.into_iter().map(|row| row.values).collect()
+}
+
+#[tokio::test]
+async fn test_sqlite_declared_types() {
+    let rows = type_test_rows().await;
+    let date = NaiveDate::from_ymd_opt(2026, 3, 15).unwrap();
+    let time = NaiveTime::from_hms_opt(10, 0, 0).unwrap();
+
+    assert_eq!(
+        rows[0],
+        vec![
+            QueryValue::Bool(true),
+            QueryValue::Int(i64::MAX),
+            QueryValue::Float(1.5),
+            QueryValue::String("text".to_string()),
+            QueryValue::Bytes(vec![0x00, 0xFF]),
+            QueryValue::Date(date),
+            QueryValue::Time(time),
+            QueryValue::Timestamp(date.and_time(time)),
+            QueryValue::Float(1234.5),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_sqlite_storage_class_mismatch() {
+    let rows = type_test_rows().await;
+
+    // 宣言型と合わない格納クラスの値は格納値のまま返す
+    assert_eq!(rows[1][0], QueryValue::String("yes".to_string()));
+    assert_eq!(rows[1][1], QueryValue::Float(1.25));
+    assert_eq!(rows[1][2], QueryValue::String("not real".to_string()));
+    // TEXT親和性の列では数値も文字列として格納される
+    assert_eq!(rows[1][3], QueryValue::String("42".to_string()));
+    assert_eq!(rows[1][4], QueryValue::String("blob as text".to_string()));
+    assert_eq!(rows[1][5], QueryValue::Int(20260315));
+    assert_eq!(rows[1][6], QueryValue::String("noon".to_string()));
+    // DATETIME列の整数はUNIX時刻
+    let expected = NaiveDate::from_ymd_opt(2026, 3, 15)
+        .unwrap()
+        .and_hms_opt(10, 0, 0)
+        .unwrap();
+    assert_eq!(rows[1][7], QueryValue::Timestamp(expected));
+    assert_eq!(rows[1][8], QueryValue::String("abc".to_string()));
+}
+
+#[tokio::test]
+async fn test_sqlite_out_of_range_values() {
+    let rows = type_test_rows().await;
+
+    // BOOLEAN列の 0/1 以外は整数のまま
+    assert_eq!(rows[2][0], QueryValue::Int(2));
+    assert_eq!(rows[2][1], QueryValue::Int(i64::MIN));
+    assert_eq!(rows[2][4], QueryValue::Bytes(vec![]));
+    // 解釈できない日付・時刻は文字列のまま
+    assert_eq!(rows[2][5], QueryValue::String("not a date".to_string()));
+    assert_eq!(rows[2][6], QueryValue::String("25:61:00".to_string()));
+    assert_eq!(rows[2][7], QueryValue::String("2026-13-45".to_string()));
+
+    assert!(rows[3].iter().all(|value| *value == QueryValue::Null));
+}
@@ -0,0 +1,117 @@
+/// WKB（Well-Known Binary）をWKTに変換する
+///
+/// 2次元のジオメトリのみ対応。MySQLの `ST_AsText` と同じく区切りのカンマの後に空白を入れない
+pub fn wkb_to_wkt(bytes: &[u8]) -> Option<String> {
+    let mut reader = WkbReader {
+        bytes,
+        pos: 0,
+        little_endian: true,
+    };
+    let wkt = reader.geometry()?;
+    // 余分なバイトがあれば不正なデータとみなす
+    (reader.pos == bytes.len()).then_some(wkt)
+}
+
+/// MySQLの内部形式（先頭4バイトのSRID + WKB）をWKTに変換する
+pub fn mysql_geometry_to_wkt(bytes: &[u8]) -> Option<String> {
+    if bytes.len() < 4 {
+        return None;
+    }
+    wkb_to_wkt(&bytes[4..])
+}
+
+struct WkbReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    little_endian: bool,
+}
+
+impl WkbReader<'_> {
+    fn take<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let slice = self.bytes.get(self.pos..self.pos + N)?;
+        self.pos += N;
+        slice.try_into().ok()
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        let bytes = self.take::<4>()?;
+        Some(if self.little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+
+    fn f64(&mut self) -> Option<f64> {
+        let bytes = self.take::<8>()?;
+        Some(if self.little_endian {
+            f64::from_le_bytes(bytes)
+        } else {
+            f64::from_be_bytes(bytes)
+        })
+    }
+
+    fn point(&mut self) -> Option<String> {
+        let x = self.f64()?;
+        let y = self.f64()?;
+        Some(format!("{} {}", x, y))
+    }
+
+    /// 要素数に続く要素の列を `,` で連結する
+    fn list(&mut self, mut item: impl FnMut(&mut Self) -> Option<String>) -> Option<String> {
+        let count = self.u32()? as usize;
+        // 要素数が残りのバイト数を超える場合は不正なデータ
+        if count > self.bytes.len() - self.pos {
+            return None;
+        }
+        let items = (0..count).map(|_| item(self)).collect::<Option<Vec<_>>>()?;
+        Some(items.join(","))
+    }
+
+    fn geometry(&mut self) -> Option<String> {
+        self.little_endian = match self.take::<1>()?[0] {
+            0 => false,
+            1 => true,
+            _ => return None,
+        };
+
+        match self.u32()? {
+            1 => Some(format!("POINT({})", self.point()?)),
+            2 => Some(format!("LINESTRING({})", self.list(Self::point)?)),
+            3 => Some(format!(
+                "POLYGON({})",
+                self.list(|r| Some(format!("({})", r.list(Self::point)?)))?
+            )),
+            4 => Some(format!(
+                "MULTIPOINT({})",
+                self.list(|r| Some(format!("({})", r.nested("POINT")?)))?
+            )),
+            5 => Some(format!(
+                "MULTILINESTRING({})",
+                self.list(|r| Some(format!("({})", r.nested("LINESTRING")?)))?
+            )),
+            6 => Some(format!(
+                "MULTIPOLYGON({})",
+                self.list(|r| Some(format!("({})", r.nested("POLYGON")?)))?
+            )),
+            7 => {
+                let items = self.list(Self::geometry)?;
+                if items.is_empty() {
+                    Some("GEOMETRYCOLLECTION EMPTY".to_string())
+                } else {
+                    Some(format!("GEOMETRYCOLLECTION({})", items))
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// マルチ系の要素（それ自体がWKB）を読み、型名と外側の括弧を除いた中身を返す
+    fn nested(&mut self, kind: &str) -> Option<String> {
+        let wkt = self.geometry()?;
+        wkt.strip_prefix(kind)?
+            .strip_prefix('(')?
+            .strip_suffix(')')
+            .map(str::to_string)
+    }
+}
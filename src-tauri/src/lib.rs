@@ -18,13 +18,21 @@ use crypto::{
     CredentialStorage, MasterKeyManager, ProviderSwitcher, SecurityConfigStorage,
     SecurityProviderManager,
 };
-use services::query_executor::{ConnectionPoolManager, QueryCancellationManager};
+use services::audit_log::AuditLogService;
+use services::database_inspector::TableMetadataCache;
+use services::query_executor::{
+    ConnectionPoolManager, QueryCancellationManager, QueryCursorManager,
+};
+use services::query_plan::TableRowCountCache;
 use services::query_storage::QueryStorage;
+use services::safety_guard::SafetyGuard;
 use services::sql_editor_query_storage::SqlEditorQueryStorage;
+use services::transaction_manager::TransactionManager;
 use services::WindowManager;
 use std::sync::Arc;
 use storage::{FileStorage, PathManager};
 use tauri::{Manager, State, WindowEvent};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
 
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
@@ -161,6 +169,12 @@ pub fn run() {
     // QueryExecutor関連のマネージャーを初期化
     let connection_pool_manager = ConnectionPoolManager::new();
     let query_cancellation_manager = QueryCancellationManager::new();
+    let query_cursor_manager = QueryCursorManager::new();
+    let table_row_count_cache = TableRowCountCache::new();
+    let table_metadata_cache = TableMetadataCache::new();
+    let transaction_manager = TransactionManager::new();
+    let safety_guard = SafetyGuard::new();
+    let audit_log = AuditLogService::new(&path_manager);
 
     // QueryStorageを初期化
     let query_storage = Arc::new(QueryStorage::new(Arc::clone(&saved_queries_storage)));
@@ -179,14 +193,52 @@ pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
-        .on_window_event(|window, event| {
-            if matches!(event, WindowEvent::Destroyed) {
+        .on_window_event(|window, event| match event {
+            WindowEvent::CloseRequested { api, .. } => {
+                // 未確定のトランザクションがある場合は閉じる前に確認する
+                let transaction_manager = window.state::<TransactionManager>();
+                if transaction_manager.list_by_window(window.label()).is_empty() {
+                    return;
+                }
+                api.prevent_close();
+
+                let window = window.clone();
+                window
+                    .dialog()
+                    .message("コミットされていないトランザクションがあります。ロールバックしてウィンドウを閉じますか？")
+                    .title("未確定のトランザクション")
+                    .kind(MessageDialogKind::Warning)
+                    .buttons(MessageDialogButtons::OkCancelCustom(
+                        "ロールバックして閉じる".to_string(),
+                        "キャンセル".to_string(),
+                    ))
+                    .show(move |confirmed| {
+                        if !confirmed {
+                            return;
+                        }
+                        tauri::async_runtime::spawn(async move {
+                            let transaction_manager = window.state::<TransactionManager>();
+                            transaction_manager.rollback_window(window.label()).await;
+                            let _ = window.destroy();
+                        });
+                    });
+            }
+            WindowEvent::Destroyed => {
+                // 確認を経ずに破棄された場合も接続を残さないようロールバックする
+                let label = window.label().to_string();
+                let app_handle = window.app_handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    let transaction_manager = app_handle.state::<TransactionManager>();
+                    transaction_manager.rollback_window(&label).await;
+                });
+
                 let windows = window.app_handle().webview_windows();
                 if windows.len() <= 1 {
                     let connection_service = window.state::<ConnectionService>();
                     connection_service.clear_password_cache();
                 }
             }
+            _ => {}
         })
         .manage(WindowManager::new())
         .manage(file_storage_for_commands)
@@ -194,6 +246,12 @@ pub fn run() {
         .manage(connection_service)
         .manage(connection_pool_manager)
         .manage(query_cancellation_manager)
+        .manage(query_cursor_manager)
+        .manage(table_row_count_cache)
+        .manage(table_metadata_cache)
+        .manage(transaction_manager)
+        .manage(safety_guard)
+        .manage(audit_log)
         .manage(query_storage)
         .manage(sql_editor_query_storage)
         .manage(query_history_state)
@@ -219,6 +277,7 @@ pub fn run() {
             connection::commands::delete_connection,
             connection::commands::mark_connection_used,
             connection::commands::test_connection,
+            connection::commands::trust_ssh_host_key,
             commands::database_structure::get_database_structure,
             commands::database_structure::get_database_structure_summary,
             commands::database_structure::get_schemas,
@@ -229,12 +288,26 @@ pub fn run() {
             commands::join_suggestions::get_join_suggestions,
             commands::query::generate_sql,
             commands::query::generate_sql_formatted,
+            commands::query::generate_sql_with_params,
             commands::query::execute_query,
+            commands::query::execute_script,
+            commands::query::explain_query,
             commands::query::cancel_query,
+            commands::query::open_query_cursor,
+            commands::query::fetch_query_cursor,
+            commands::query::close_query_cursor,
             commands::mutation_commands::generate_insert_sql,
             commands::mutation_commands::generate_update_sql,
             commands::mutation_commands::generate_delete_sql,
             commands::mutation_commands::execute_mutation,
+            commands::mutation_commands::preview_mutation,
+            commands::transaction_commands::begin_transaction,
+            commands::transaction_commands::commit_transaction,
+            commands::transaction_commands::rollback_transaction,
+            commands::transaction_commands::get_transaction_status,
+            commands::connection_pool_commands::get_pool_stats,
+            commands::connection_pool_commands::get_pool_settings,
+            commands::connection_pool_commands::update_pool_settings,
             commands::security::get_security_provider_info,
             commands::security::get_available_providers,
             commands::security::get_security_config,
@@ -272,6 +345,10 @@ pub fn run() {
             commands::safety::get_safety_settings,
             commands::safety::update_environment_safety,
             commands::safety::reset_safety_settings,
+            commands::safety::issue_confirmation_token,
+            commands::audit_log::search_audit_log,
+            commands::audit_log::export_audit_log,
+            commands::audit_log::verify_audit_log,
             commands::query_storage_commands::save_query,
             commands::query_storage_commands::load_query,
             commands::query_storage_commands::delete_query,
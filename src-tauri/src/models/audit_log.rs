@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+
+use crate::connection::EnvironmentType;
+use crate::models::query_analysis::RiskLevel;
+
+/// 監査ログのエントリ
+///
+/// `hash` は `hash` を空にしたエントリのJSON（直前のエントリのハッシュ `prev_hash` を含む）のHMACで計算する。
+/// 途中のエントリを書き換える・削除するとチェーンが切れるため改ざんを検出できる
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogEntry {
+    /// 通し番号（1始まり、ファイルをまたいで連続する）
+    pub sequence: u64,
+    /// 実行日時（RFC3339、UTC）
+    pub timestamp: String,
+    pub connection_id: String,
+    pub connection_name: String,
+    pub environment: EnvironmentType,
+    pub operation: AuditOperation,
+    pub sql: String,
+    pub risk_level: RiskLevel,
+    /// 影響行数（SELECTの場合は取得行数）
+    pub affected_rows: Option<u64>,
+    pub outcome: AuditOutcome,
+    pub error_message: Option<String>,
+    /// 実行したOSユーザー
+    pub os_user: String,
+    /// 直前のエントリのハッシュ（最初のエントリは0埋め）
+    pub prev_hash: String,
+    /// このエントリのハッシュ（HMAC-SHA256、16進）
+    pub hash: String,
+}
+
+/// 監査対象の操作
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditOperation {
+    /// `execute_query`
+    Query,
+    /// `execute_script`
+    Script,
+    /// `execute_mutation`
+    Mutation,
+    /// `open_query_cursor`
+    Cursor,
+    /// `explain_query`（ANALYZE で実際に実行したもの）
+    Explain,
+    /// `preview_mutation`
+    Preview,
+}
+
+/// 実行結果
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditOutcome {
+    Success,
+    Error,
+    /// 安全設定で拒否した
+    Rejected,
+}
+
+/// 監査ログに記録する実行内容
+#[derive(Debug, Clone)]
+pub struct AuditRecord {
+    pub connection_id: String,
+    pub connection_name: String,
+    pub environment: EnvironmentType,
+    pub operation: AuditOperation,
+    pub sql: String,
+    pub risk_level: RiskLevel,
+    pub affected_rows: Option<u64>,
+    pub outcome: AuditOutcome,
+    pub error_message: Option<String>,
+}
+
+/// 監査ログ検索リクエスト
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchAuditLogRequest {
+    /// 開始日（YYYY-MM-DD、UTC、この日を含む）
+    pub from: Option<String>,
+    /// 終了日（YYYY-MM-DD、UTC、この日を含む）
+    pub to: Option<String>,
+    pub connection_id: Option<String>,
+    pub environment: Option<EnvironmentType>,
+    pub outcome: Option<AuditOutcome>,
+    /// SQLの部分一致（大文字小文字を区別しない）
+    pub keyword: Option<String>,
+    pub limit: Option<usize>,
+}
+
+/// ハッシュチェーンの検証結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogVerification {
+    pub valid: bool,
+    /// 検証したエントリ数
+    pub checked_entries: u64,
+    /// 最初に不整合が見つかったファイル名
+    pub invalid_file: Option<String>,
+    /// 最初に不整合が見つかった行（1始まり）
+    pub invalid_line: Option<usize>,
+    pub message: Option<String>,
+}
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+/// 接続プールの設定
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolSettings {
+    /// 1つのプールで保持する最大接続数
+    pub max_connections: u32,
+    /// アイドル状態がこの秒数を超えた接続・プールを閉じる
+    pub idle_timeout_seconds: u64,
+    /// ヘルスチェックの間隔（秒）
+    pub health_check_interval_seconds: u64,
+}
+
+impl Default for PoolSettings {
+    fn default() -> Self {
+        Self {
+            max_connections: 10,
+            idle_timeout_seconds: 300,
+            health_check_interval_seconds: 60,
+        }
+    }
+}
+
+/// 接続プールの状態
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolStats {
+    /// 接続ID
+    pub connection_id: String,
+    /// 開いている接続数
+    pub open: u32,
+    /// アイドル状態の接続数
+    pub idle: u32,
+    /// 使用中の接続数
+    pub in_use: u32,
+    /// 最大接続数
+    pub max_connections: u32,
+    /// プールの作成日時（RFC3339）
+    pub created_at: String,
+    /// 最終使用日時（RFC3339）
+    pub last_used_at: String,
+}
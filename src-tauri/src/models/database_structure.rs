@@ -50,6 +50,9 @@ pub struct Schema {
     pub is_system: bool,
     pub tables: Vec<Table>,
     pub views: Vec<View>,
+    /// シーケンス（MariaDB・SQL Serverのみ取得する）
+    #[serde(default)]
+    pub sequences: Vec<Sequence>,
 }
 
 /// テーブル情報
@@ -65,6 +68,9 @@ pub struct Table {
     pub indexes: Vec<Index>,
     pub foreign_keys: Vec<ForeignKey>,
     pub referenced_by: Vec<ForeignKeyReference>,
+    /// システムバージョニングされたテーブル（MariaDBの `WITH SYSTEM VERSIONING`、SQL Serverのテンポラルテーブル）
+    #[serde(default)]
+    pub is_system_versioned: bool,
 }
 
 /// ビュー情報
@@ -78,6 +84,19 @@ pub struct View {
     pub definition: Option<String>,
 }
 
+/// シーケンス情報
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Sequence {
+    pub name: String,
+    pub schema: String,
+    pub start_value: Option<i64>,
+    pub increment: Option<i64>,
+    pub min_value: Option<i64>,
+    pub max_value: Option<i64>,
+    pub cycle: bool,
+}
+
 /// カラム情報
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -91,6 +110,9 @@ pub struct Column {
     pub is_foreign_key: bool,
     pub is_unique: bool,
     pub is_auto_increment: bool,
+    /// 生成列（計算列）か。値を書き込めない
+    #[serde(default)]
+    pub is_generated: bool,
     pub ordinal_position: i32,
     pub comment: Option<String>,
 }
@@ -33,6 +33,7 @@ fn test_schema_with_tables_and_views() {
             indexes: vec![],
             foreign_keys: vec![],
             referenced_by: vec![],
+            is_system_versioned: false,
         }],
         views: vec![View {
             name: "active_users".to_string(),
@@ -41,6 +42,7 @@ fn test_schema_with_tables_and_views() {
             columns: vec![],
             definition: Some("SELECT * FROM users WHERE active = true".to_string()),
         }],
+        sequences: Vec::new(),
     };
 
     assert_eq!(schema.tables.len(), 1);
@@ -61,6 +63,7 @@ fn test_column_properties() {
         is_foreign_key: false,
         is_unique: true,
         is_auto_increment: true,
+        is_generated: false,
         ordinal_position: 1,
         comment: Some("Primary key".to_string()),
     };
@@ -166,6 +169,7 @@ fn test_table_with_full_metadata() {
                 is_foreign_key: false,
                 is_unique: true,
                 is_auto_increment: true,
+                is_generated: false,
                 ordinal_position: 1,
                 comment: None,
             },
@@ -179,6 +183,7 @@ fn test_table_with_full_metadata() {
                 is_foreign_key: false,
                 is_unique: true,
                 is_auto_increment: false,
+                is_generated: false,
                 ordinal_position: 2,
                 comment: Some("User email address".to_string()),
             },
@@ -196,6 +201,7 @@ fn test_table_with_full_metadata() {
         }],
         foreign_keys: vec![],
         referenced_by: vec![],
+        is_system_versioned: false,
     };
 
     assert_eq!(table.name, "users");
@@ -221,6 +227,7 @@ fn test_view_structure() {
             is_foreign_key: false,
             is_unique: false,
             is_auto_increment: false,
+            is_generated: false,
             ordinal_position: 1,
             comment: None,
         }],
@@ -252,14 +259,17 @@ fn test_complete_database_structure() {
                     indexes: vec![],
                     foreign_keys: vec![],
                     referenced_by: vec![],
+                    is_system_versioned: false,
                 }],
                 views: vec![],
+                sequences: Vec::new(),
             },
             Schema {
                 name: "pg_catalog".to_string(),
                 is_system: true,
                 tables: vec![],
                 views: vec![],
+                sequences: Vec::new(),
             },
         ],
         fetched_at: "2024-01-01T12:00:00Z".to_string(),
@@ -284,6 +294,7 @@ fn test_json_serialization_with_optional_fields() {
         indexes: vec![],
         foreign_keys: vec![],
         referenced_by: vec![],
+        is_system_versioned: false,
     };
 
     let json = serde_json::to_string(&table).unwrap();
@@ -308,6 +319,7 @@ fn test_column_default_value_types() {
         is_foreign_key: false,
         is_unique: false,
         is_auto_increment: false,
+        is_generated: false,
         ordinal_position: 1,
         comment: None,
     };
@@ -323,6 +335,7 @@ fn test_column_default_value_types() {
         is_foreign_key: false,
         is_unique: false,
         is_auto_increment: false,
+        is_generated: false,
         ordinal_position: 2,
         comment: None,
     };
@@ -338,6 +351,7 @@ fn test_column_default_value_types() {
         is_foreign_key: false,
         is_unique: false,
         is_auto_increment: false,
+        is_generated: false,
         ordinal_position: 3,
         comment: None,
     };
@@ -1,3 +1,4 @@
+pub mod connection_pool;
 pub mod database_structure;
 pub mod expression_node;
 pub mod join_suggestion;
@@ -5,14 +6,17 @@ pub mod mutation_result;
 pub mod query;
 pub mod window;
 
+pub mod audit_log;
 pub mod export;
 pub mod query_analysis;
 pub mod query_history;
+pub mod query_plan;
 pub mod query_result;
 pub mod safety_settings;
 pub mod saved_query;
 pub mod sql_editor_history;
 pub mod sql_editor_query;
+pub mod transaction;
 
 pub use database_structure::*;
 pub use window::*;
@@ -22,3 +26,6 @@ mod database_structure_test;
 
 #[cfg(test)]
 mod window_test;
+
+#[cfg(test)]
+mod query_result_test;
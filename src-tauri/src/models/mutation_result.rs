@@ -1,11 +1,31 @@
 use serde::{Deserialize, Serialize};
 
+use crate::models::query_result::QueryResult;
+use crate::query::mutation::{DeleteQueryModel, UpdateQueryModel};
+
 /// データ変更クエリ実行結果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MutationResult {
     pub affected_rows: u64,
     pub execution_time_ms: u64,
+    /// 元に戻すSQL（`capture_undo` を指定した場合のみ）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub undo: Option<MutationUndo>,
+}
+
+/// 実行したUPDATE/DELETEを元に戻すSQLの生成結果
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(
+    tag = "status",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum MutationUndo {
+    /// 変更前の行を復元するSQL（1行につき1文）
+    Available { sql: String, row_count: usize },
+    /// 生成できなかった（対象行なし・主キーなし・行数の上限超過など）
+    Unavailable { reason: String },
 }
 
 /// データ変更クエリ実行リクエスト
@@ -15,4 +35,45 @@ pub struct MutationExecuteRequest {
     pub connection_id: String,
     pub sql: String,
     pub timeout_seconds: Option<u32>,
+    /// クエリID（実行中にキャンセルする場合に呼び出し側で指定）
+    pub query_id: Option<String>,
+    /// 確認トークン（安全設定で確認が必要な文は `issue_confirmation_token` で取得したものを渡す）
+    pub confirmation_token: Option<String>,
+    /// 実行前に対象行を取得し、元に戻すSQLを生成する（UPDATE/DELETEのみ）
+    #[serde(default)]
+    pub capture_undo: bool,
+}
+
+/// UPDATE/DELETEの対象行プレビューリクエスト
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MutationPreviewRequest {
+    pub connection_id: String,
+    pub target: MutationPreviewTarget,
+    #[serde(default)]
+    pub smart_quote: bool,
+    /// サンプルとして取得する行数（省略時は10行）
+    pub sample_limit: Option<u32>,
+    pub timeout_seconds: Option<u32>,
+}
+
+/// プレビュー対象（ビルダーのモデルまたはエディタのSQL）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum MutationPreviewTarget {
+    Update { query: UpdateQueryModel },
+    Delete { query: DeleteQueryModel },
+    Sql { sql: String },
+}
+
+/// UPDATE/DELETEの対象行プレビュー
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MutationPreview {
+    /// 対象行数（`SELECT COUNT(*)` の結果。実行時点までに変わる可能性がある）
+    pub affected_rows: u64,
+    /// 対象行のサンプル
+    pub sample: QueryResult,
+    pub count_sql: String,
+    pub sample_sql: String,
 }
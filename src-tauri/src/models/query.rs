@@ -208,6 +208,9 @@ pub struct WhereCondition {
 pub struct WhereConditionColumn {
     pub table_alias: String,
     pub column_name: String,
+    /// カラムの型（バインドするパラメータの型変換に使う）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_type: Option<String>,
 }
 
 /// WHERE値
@@ -1,3 +1,4 @@
+use crate::sql::statement_splitter::StatementRange;
 use serde::{Deserialize, Serialize};
 
 /// クエリ種別
@@ -22,6 +23,8 @@ pub enum RiskLevel {
     Safe,
     Warning,
     Danger,
+    /// 構文を解析できなかった（内容を判定できないため最も高く扱う）
+    Unparseable,
 }
 
 /// 危険度要因
@@ -33,6 +36,9 @@ pub struct RiskFactor {
 }
 
 /// クエリ解析結果
+///
+/// 複数文の場合、種別とWHERE句の有無は最も危険度の高い文のもの、危険度は全文の最大、
+/// 危険度要因と対象テーブルは全文分をまとめたもの
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QueryAnalysisResult {
@@ -41,6 +47,21 @@ pub struct QueryAnalysisResult {
     pub risk_factors: Vec<RiskFactor>,
     pub affected_tables: Vec<String>,
     pub has_where_clause: bool,
+    /// 文ごとの解析結果
+    #[serde(default)]
+    pub statements: Vec<StatementAnalysis>,
+}
+
+/// 文ごとの解析結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatementAnalysis {
+    /// 文の番号（0始まり）
+    pub index: usize,
+    /// 元のSQL中での文の範囲
+    pub range: StatementRange,
+    #[serde(flatten)]
+    pub analysis: QueryAnalysisResult,
 }
 
 impl QueryAnalysisResult {
@@ -51,6 +72,7 @@ impl QueryAnalysisResult {
             risk_factors: vec![],
             affected_tables: vec![],
             has_where_clause: false,
+            statements: vec![],
         }
     }
 
@@ -61,6 +83,76 @@ impl QueryAnalysisResult {
             risk_factors: vec![],
             affected_tables: vec![],
             has_where_clause: false,
+            statements: vec![],
+        }
+    }
+
+    /// 構文を解析できなかった文
+    pub fn unparseable(reason: &str) -> Self {
+        Self {
+            query_type: QueryType::Unknown,
+            risk_level: RiskLevel::Unparseable,
+            risk_factors: vec![RiskFactor {
+                code: "unparseable".to_string(),
+                message: format!(
+                    "SQLを解析できませんでした。内容を確認してから実行してください（{}）",
+                    reason
+                ),
+            }],
+            affected_tables: vec![],
+            has_where_clause: false,
+            statements: vec![],
         }
     }
+
+    /// 文ごとの結果をまとめる（文がなければ `unknown()`）
+    pub fn from_statements(statements: Vec<StatementAnalysis>) -> Self {
+        // 危険度が同じ場合は先頭の文を代表にする
+        let Some(riskiest) = statements
+            .iter()
+            .rev()
+            .max_by_key(|statement| &statement.analysis.risk_level)
+        else {
+            return Self::unknown();
+        };
+
+        let mut affected_tables: Vec<String> = Vec::new();
+        for table in statements
+            .iter()
+            .flat_map(|statement| &statement.analysis.affected_tables)
+        {
+            if !affected_tables.contains(table) {
+                affected_tables.push(table.clone());
+            }
+        }
+
+        Self {
+            query_type: riskiest.analysis.query_type.clone(),
+            risk_level: riskiest.analysis.risk_level.clone(),
+            risk_factors: statements
+                .iter()
+                .flat_map(|statement| statement.analysis.risk_factors.clone())
+                .collect(),
+            affected_tables,
+            has_where_clause: riskiest.analysis.has_where_clause,
+            statements,
+        }
+    }
+
+    /// 指定した種別の文を含むか
+    pub fn contains(&self, query_type: &QueryType) -> bool {
+        self.statements
+            .iter()
+            .any(|statement| &statement.analysis.query_type == query_type)
+    }
+
+    /// テーブル構造を変える可能性のある文を含むか（種別を判定できない文も含める）
+    pub fn may_change_schema(&self) -> bool {
+        self.statements.iter().any(|statement| {
+            matches!(
+                statement.analysis.query_type,
+                QueryType::Create | QueryType::Alter | QueryType::Drop | QueryType::Unknown
+            )
+        })
+    }
 }
@@ -13,6 +13,9 @@ pub struct QueryHistory {
     pub result_count: Option<u64>,
     pub execution_time_ms: Option<u64>,
     pub error_message: Option<String>,
+    /// 実行したUPDATE/DELETEを元に戻すSQL
+    #[serde(default)]
+    pub undo_sql: Option<String>,
 }
 
 /// クエリ履歴のメタデータ（一覧表示用）
@@ -26,6 +29,7 @@ pub struct QueryHistoryMetadata {
     pub success: bool,
     pub result_count: Option<u64>,
     pub execution_time_ms: Option<u64>,
+    pub undo_sql: Option<String>,
 }
 
 impl From<&QueryHistory> for QueryHistoryMetadata {
@@ -38,6 +42,7 @@ impl From<&QueryHistory> for QueryHistoryMetadata {
             success: history.success,
             result_count: history.result_count,
             execution_time_ms: history.execution_time_ms,
+            undo_sql: history.undo_sql.clone(),
         }
     }
 }
@@ -53,6 +58,8 @@ pub struct AddHistoryRequest {
     pub result_count: Option<u64>,
     pub execution_time_ms: Option<u64>,
     pub error_message: Option<String>,
+    #[serde(default)]
+    pub undo_sql: Option<String>,
 }
 
 /// 履歴検索リクエスト
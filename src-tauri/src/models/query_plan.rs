@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+
+/// 実行計画リクエスト
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExplainRequest {
+    /// 接続ID
+    pub connection_id: String,
+    /// 対象のSQL（単一文）
+    pub sql: String,
+    /// 実際に実行して計測する（EXPLAIN ANALYZE）
+    #[serde(default)]
+    pub analyze: bool,
+    /// タイムアウト（秒、オプション）
+    pub timeout_seconds: Option<u32>,
+    /// 確認トークン（`analyze` で確認が必要な文を実行する場合に渡す）
+    #[serde(default)]
+    pub confirmation_token: Option<String>,
+}
+
+/// 実行計画ノードの種類（DBごとの演算名を正規化したもの）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanNodeKind {
+    /// テーブルの全件走査
+    SeqScan,
+    /// インデックスを使った走査・検索
+    IndexScan,
+    NestedLoop,
+    HashJoin,
+    MergeJoin,
+    Sort,
+    Aggregate,
+    Other,
+}
+
+/// 実行計画ノード
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryPlanNode {
+    /// 演算名（DBの表記のまま）
+    pub operation: String,
+    /// 演算の種類
+    pub kind: PlanNodeKind,
+    /// 対象テーブル
+    pub relation: Option<String>,
+    /// 対象テーブルのスキーマ
+    pub schema: Option<String>,
+    /// 使用インデックス
+    pub index_name: Option<String>,
+    /// 推定行数
+    pub estimated_rows: Option<f64>,
+    /// 推定コスト
+    pub estimated_cost: Option<f64>,
+    /// 実際の行数（ANALYZE時、1ループあたり）
+    pub actual_rows: Option<f64>,
+    /// 実際の所要時間（ANALYZE時、ミリ秒）
+    pub actual_time_ms: Option<f64>,
+    /// ループ回数（ANALYZE時）
+    pub actual_loops: Option<f64>,
+    /// 条件などの補足情報
+    pub detail: Option<String>,
+    /// 子ノード
+    pub children: Vec<QueryPlanNode>,
+}
+
+impl QueryPlanNode {
+    pub fn new(operation: impl Into<String>, kind: PlanNodeKind) -> Self {
+        Self {
+            operation: operation.into(),
+            kind,
+            relation: None,
+            schema: None,
+            index_name: None,
+            estimated_rows: None,
+            estimated_cost: None,
+            actual_rows: None,
+            actual_time_ms: None,
+            actual_loops: None,
+            detail: None,
+            children: vec![],
+        }
+    }
+}
+
+/// 実行計画の注意点
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanWarning {
+    pub code: String,
+    pub message: String,
+    /// 対象テーブル
+    pub relation: Option<String>,
+}
+
+/// 実行計画
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryPlan {
+    /// ルートノード
+    pub root: QueryPlanNode,
+    /// 実際に実行して計測したか
+    pub analyzed: bool,
+    /// 注意点
+    pub warnings: Vec<PlanWarning>,
+    /// DBが返した実行計画（JSON・テキスト）
+    pub raw: String,
+    /// 実行時間（ミリ秒）
+    pub execution_time_ms: u64,
+}
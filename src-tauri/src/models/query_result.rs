@@ -1,4 +1,10 @@
-use serde::{Deserialize, Serialize};
+use crate::sql::statement_splitter::StatementRange;
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
+use std::fmt;
+use uuid::Uuid;
 
 /// クエリ実行結果
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,8 +42,11 @@ pub struct QueryResultRow {
 }
 
 /// 値の型
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(untagged)]
+///
+/// 基本的な値はそのままシリアライズし、型付きの値は `{"type": "date", "value": "2024-01-01"}`
+/// の形でシリアライズする（エクスポート時に型を復元できるようにするため）
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(from = "QueryValueRepr")]
 pub enum QueryValue {
     Null,
     Bool(bool),
@@ -45,7 +54,181 @@ pub enum QueryValue {
     Float(f64),
     String(String),
     Bytes(Vec<u8>),
-    // 日付・時刻は文字列でシリアライズ
+    /// 任意精度の数値（精度を保つため文字列で保持）
+    Decimal(String),
+    Date(NaiveDate),
+    Time(NaiveTime),
+    /// タイムゾーンなしの日時
+    Timestamp(NaiveDateTime),
+    /// タイムゾーン付きの日時
+    TimestampTz(DateTime<FixedOffset>),
+    Json(serde_json::Value),
+    Array(Vec<QueryValue>),
+    Uuid(Uuid),
+    Interval(QueryInterval),
+}
+
+/// 期間（PostgreSQLの INTERVAL と同じく月・日・マイクロ秒で保持）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueryInterval {
+    pub months: i32,
+    pub days: i32,
+    pub microseconds: i64,
+}
+
+impl fmt::Display for QueryInterval {
+    /// `1 years 2 months 3 days 04:05:06` 形式
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts: Vec<String> = Vec::new();
+        let years = self.months / 12;
+        let months = self.months % 12;
+        if years != 0 {
+            parts.push(format!("{} years", years));
+        }
+        if months != 0 {
+            parts.push(format!("{} months", months));
+        }
+        if self.days != 0 {
+            parts.push(format!("{} days", self.days));
+        }
+        if self.microseconds != 0 {
+            let sign = if self.microseconds < 0 { "-" } else { "" };
+            let total_micros = self.microseconds.unsigned_abs();
+            let total_secs = total_micros / 1_000_000;
+            let micros = total_micros % 1_000_000;
+            let time = format!(
+                "{}{:02}:{:02}:{:02}",
+                sign,
+                total_secs / 3600,
+                (total_secs % 3600) / 60,
+                total_secs % 60
+            );
+            if micros != 0 {
+                parts.push(format!("{}.{:06}", time, micros));
+            } else {
+                parts.push(time);
+            }
+        }
+
+        if parts.is_empty() {
+            write!(f, "00:00:00")
+        } else {
+            write!(f, "{}", parts.join(" "))
+        }
+    }
+}
+
+impl Serialize for QueryValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        fn typed<S: Serializer, T: Serialize + ?Sized>(
+            serializer: S,
+            tag: &'static str,
+            value: &T,
+        ) -> Result<S::Ok, S::Error> {
+            let mut state = serializer.serialize_struct("QueryValue", 2)?;
+            state.serialize_field("type", tag)?;
+            state.serialize_field("value", value)?;
+            state.end()
+        }
+
+        match self {
+            QueryValue::Null => serializer.serialize_unit(),
+            QueryValue::Bool(b) => serializer.serialize_bool(*b),
+            QueryValue::Int(i) => serializer.serialize_i64(*i),
+            QueryValue::Float(f) => serializer.serialize_f64(*f),
+            QueryValue::String(s) => serializer.serialize_str(s),
+            QueryValue::Bytes(b) => b.serialize(serializer),
+            QueryValue::Decimal(d) => typed(serializer, "decimal", d),
+            QueryValue::Date(d) => typed(serializer, "date", d),
+            QueryValue::Time(t) => typed(serializer, "time", t),
+            QueryValue::Timestamp(t) => typed(serializer, "timestamp", t),
+            QueryValue::TimestampTz(t) => typed(serializer, "timestamp_tz", t),
+            QueryValue::Json(v) => typed(serializer, "json", v),
+            QueryValue::Array(values) => typed(serializer, "array", values),
+            QueryValue::Uuid(u) => typed(serializer, "uuid", u),
+            QueryValue::Interval(i) => typed(serializer, "interval", i),
+        }
+    }
+}
+
+/// `QueryValue` のデシリアライズ用の表現
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum QueryValueRepr {
+    Typed(TypedValue),
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Bytes(Vec<u8>),
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+enum TypedValue {
+    Decimal(String),
+    Date(NaiveDate),
+    Time(NaiveTime),
+    Timestamp(NaiveDateTime),
+    TimestampTz(DateTime<FixedOffset>),
+    Json(serde_json::Value),
+    Array(Vec<QueryValue>),
+    Uuid(Uuid),
+    Interval(QueryInterval),
+}
+
+impl From<QueryValueRepr> for QueryValue {
+    fn from(repr: QueryValueRepr) -> Self {
+        match repr {
+            QueryValueRepr::Typed(typed) => match typed {
+                TypedValue::Decimal(d) => QueryValue::Decimal(d),
+                TypedValue::Date(d) => QueryValue::Date(d),
+                TypedValue::Time(t) => QueryValue::Time(t),
+                TypedValue::Timestamp(t) => QueryValue::Timestamp(t),
+                TypedValue::TimestampTz(t) => QueryValue::TimestampTz(t),
+                TypedValue::Json(v) => QueryValue::Json(v),
+                TypedValue::Array(values) => QueryValue::Array(values),
+                TypedValue::Uuid(u) => QueryValue::Uuid(u),
+                TypedValue::Interval(i) => QueryValue::Interval(i),
+            },
+            QueryValueRepr::Null => QueryValue::Null,
+            QueryValueRepr::Bool(b) => QueryValue::Bool(b),
+            QueryValueRepr::Int(i) => QueryValue::Int(i),
+            QueryValueRepr::Float(f) => QueryValue::Float(f),
+            QueryValueRepr::String(s) => QueryValue::String(s),
+            QueryValueRepr::Bytes(b) => QueryValue::Bytes(b),
+        }
+    }
+}
+
+/// バインドパラメータの値
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum QueryParam {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    /// 型を指定した値（`{"type": "date", "value": "2024-01-01"}`）
+    Typed(TypedParam),
+}
+
+/// 型を指定したバインドパラメータ
+///
+/// 文字列のままバインドすると、PostgreSQLでは日付・UUID・数値などの列と比較できない
+/// （operator does not exist）ため、各DBの対応する型でバインドする
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum TypedParam {
+    Decimal(BigDecimal),
+    Date(NaiveDate),
+    Time(NaiveTime),
+    Timestamp(NaiveDateTime),
+    TimestampTz(DateTime<FixedOffset>),
+    Json(serde_json::Value),
+    Uuid(Uuid),
 }
 
 /// クエリ実行リクエスト
@@ -56,8 +239,15 @@ pub struct QueryExecuteRequest {
     pub connection_id: String,
     /// 実行するSQL
     pub sql: String,
+    /// バインドパラメータ（プレースホルダー順）
+    #[serde(default)]
+    pub params: Vec<QueryParam>,
     /// タイムアウト（秒、オプション）
     pub timeout_seconds: Option<u32>,
+    /// クエリID（実行完了前にキャンセルする場合に呼び出し側で指定）
+    pub query_id: Option<String>,
+    /// 確認トークン（安全設定で確認が必要な文は `issue_confirmation_token` で取得したものを渡す）
+    pub confirmation_token: Option<String>,
 }
 
 /// クエリ実行レスポンス
@@ -70,6 +260,116 @@ pub struct QueryExecuteResponse {
     pub result: QueryResult,
 }
 
+/// スクリプト実行時のエラー処理
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScriptErrorMode {
+    /// 最初のエラーで中断する
+    #[default]
+    StopOnError,
+    /// エラーが発生しても残りの文を実行する
+    ContinueOnError,
+}
+
+/// スクリプト実行リクエスト（複数文を文ごとの結果で返す）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptExecuteRequest {
+    /// 接続ID
+    pub connection_id: String,
+    /// 実行するSQL（複数文）
+    pub sql: String,
+    /// エラー時の動作
+    #[serde(default)]
+    pub error_mode: ScriptErrorMode,
+    /// 1文あたりのタイムアウト（秒、オプション）
+    pub timeout_seconds: Option<u32>,
+    /// クエリID（実行完了前にキャンセルする場合に呼び出し側で指定）
+    pub query_id: Option<String>,
+    /// 確認トークン（安全設定で確認が必要な文は `issue_confirmation_token` で取得したものを渡す）
+    pub confirmation_token: Option<String>,
+}
+
+/// 文ごとの実行結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatementResult {
+    /// スクリプト内での文の番号（0始まり）
+    pub index: usize,
+    /// 元のSQL中での文の範囲
+    pub range: StatementRange,
+    /// 実行結果
+    pub outcome: StatementOutcome,
+    /// 実行時間（ミリ秒）
+    pub execution_time_ms: u64,
+}
+
+/// 文の実行結果の種類
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(
+    tag = "type",
+    rename_all = "snake_case",
+    rename_all_fields = "camelCase"
+)]
+pub enum StatementOutcome {
+    /// 結果セットを返した
+    ResultSet { result: QueryResult },
+    /// 行を変更した（DDL等は0件）
+    Affected { affected_rows: u64 },
+    /// エラーになった
+    Error { error: QueryError },
+}
+
+/// スクリプト実行レスポンス
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptExecuteResponse {
+    /// クエリID（キャンセルトークン用）
+    pub query_id: String,
+    /// 文ごとの結果（中断した場合は実行した文まで）
+    pub results: Vec<StatementResult>,
+    /// 文の総数
+    pub statement_count: usize,
+    /// 全体の実行時間（ミリ秒）
+    pub execution_time_ms: u64,
+}
+
+/// カーソルオープンリクエスト（ストリーミング取得用）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryCursorOpenRequest {
+    /// 接続ID
+    pub connection_id: String,
+    /// 実行するSQL
+    pub sql: String,
+    /// 1ページあたりの取得行数（オプション）
+    pub page_size: Option<usize>,
+    /// タイムアウト（秒、オプション）
+    pub timeout_seconds: Option<u32>,
+    /// 確認トークン（安全設定で確認が必要な文は `issue_confirmation_token` で取得したものを渡す）
+    pub confirmation_token: Option<String>,
+}
+
+/// カーソルから取得した1ページ分の結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryResultPage {
+    /// カーソルID
+    pub cursor_id: String,
+    /// カラム情報
+    pub columns: Vec<QueryResultColumn>,
+    /// このページの行データ
+    pub rows: Vec<QueryResultRow>,
+    /// このページの行数
+    pub row_count: usize,
+    /// これまでに取得した累計行数
+    pub total_fetched: usize,
+    /// 続きの行があるか
+    pub has_more: bool,
+    /// このページの取得時間（ミリ秒）
+    pub execution_time_ms: u64,
+}
+
 /// クエリエラー
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -101,6 +401,8 @@ pub enum QueryErrorCode {
 
     // 権限関連
     PermissionDenied,
+    /// 安全設定により、実行前に確認トークンが必要
+    ConfirmationRequired,
 
     // オブジェクト関連
     TableNotFound,
@@ -119,6 +421,9 @@ pub enum QueryErrorCode {
     DivisionByZero,
     InvalidDataType,
 
+    // トランザクション関連
+    TransactionError,
+
     // その他
     Unknown,
 }
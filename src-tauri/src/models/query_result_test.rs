@@ -0,0 +1,119 @@
+use super::query_result::*;
+use chrono::{NaiveDate, NaiveTime};
+use serde_json::json;
+
+#[test]
+fn test_query_value_plain_serialization() {
+    // 基本的な値は従来どおりそのままの形
+    let values = vec![
+        QueryValue::Null,
+        QueryValue::Bool(true),
+        QueryValue::Int(42),
+        QueryValue::Float(1.5),
+        QueryValue::String("text".to_string()),
+        QueryValue::Bytes(vec![1, 2]),
+    ];
+    let json = serde_json::to_value(&values).unwrap();
+    assert_eq!(json, json!([null, true, 42, 1.5, "text", [1, 2]]));
+
+    let deserialized: Vec<QueryValue> = serde_json::from_value(json).unwrap();
+    assert_eq!(deserialized, values);
+}
+
+#[test]
+fn test_query_value_typed_serialization() {
+    let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+    let time = NaiveTime::from_hms_opt(9, 30, 0).unwrap();
+    let values = vec![
+        QueryValue::Decimal("12345678901234567890.12".to_string()),
+        QueryValue::Date(date),
+        QueryValue::Time(time),
+        QueryValue::Timestamp(date.and_time(time)),
+        QueryValue::TimestampTz(
+            chrono::DateTime::parse_from_rfc3339("2024-03-15T09:30:00+09:00").unwrap(),
+        ),
+        QueryValue::Json(json!({"tags": ["a", "b"], "n": 1})),
+        QueryValue::Array(vec![QueryValue::Int(1), QueryValue::Null]),
+        QueryValue::Uuid(uuid::Uuid::nil()),
+        QueryValue::Interval(QueryInterval {
+            months: 14,
+            days: 3,
+            microseconds: 3_723_000_000,
+        }),
+    ];
+
+    let json = serde_json::to_value(&values).unwrap();
+    assert_eq!(
+        json[0],
+        json!({"type": "decimal", "value": "12345678901234567890.12"})
+    );
+    assert_eq!(json[1], json!({"type": "date", "value": "2024-03-15"}));
+    assert_eq!(
+        json[3],
+        json!({"type": "timestamp", "value": "2024-03-15T09:30:00"})
+    );
+    assert_eq!(
+        json[4],
+        json!({"type": "timestamp_tz", "value": "2024-03-15T09:30:00+09:00"})
+    );
+    // JSONと配列は入れ子のまま
+    assert_eq!(json[5]["value"]["tags"][1], "b");
+    assert_eq!(json[6], json!({"type": "array", "value": [1, null]}));
+
+    // エクスポート時にフロントエンドから戻ってきても型を復元できる
+    let deserialized: Vec<QueryValue> = serde_json::from_value(json).unwrap();
+    assert_eq!(deserialized, values);
+}
+
+#[test]
+fn test_query_param_deserialization() {
+    let params: Vec<QueryParam> = serde_json::from_value(json!([
+        null,
+        "2024-03-15",
+        { "type": "date", "value": "2024-03-15" },
+        { "type": "uuid", "value": "67e55044-10b1-426f-9247-bb680e5fe0c8" },
+        { "type": "decimal", "value": "12345678901234567890.12" }
+    ]))
+    .unwrap();
+
+    // 型を指定しない文字列は文字列のまま
+    assert_eq!(params[0], QueryParam::Null);
+    assert_eq!(params[1], QueryParam::String("2024-03-15".to_string()));
+    assert_eq!(
+        params[2],
+        QueryParam::Typed(TypedParam::Date(
+            NaiveDate::from_ymd_opt(2024, 3, 15).unwrap()
+        ))
+    );
+    assert!(matches!(params[3], QueryParam::Typed(TypedParam::Uuid(_))));
+    assert!(
+        matches!(&params[4], QueryParam::Typed(TypedParam::Decimal(d)) if d.to_string() == "12345678901234567890.12")
+    );
+}
+
+#[test]
+fn test_query_interval_display() {
+    let interval = QueryInterval {
+        months: 14,
+        days: 3,
+        microseconds: 3_723_500_000,
+    };
+    assert_eq!(
+        interval.to_string(),
+        "1 years 2 months 3 days 01:02:03.500000"
+    );
+
+    let negative = QueryInterval {
+        months: 0,
+        days: 0,
+        microseconds: -90_000_000,
+    };
+    assert_eq!(negative.to_string(), "-00:01:30");
+
+    let zero = QueryInterval {
+        months: 0,
+        days: 0,
+        microseconds: 0,
+    };
+    assert_eq!(zero.to_string(), "00:00:00");
+}
@@ -1,3 +1,4 @@
+use crate::connection::EnvironmentType;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -22,6 +23,9 @@ pub struct EnvironmentSafetyConfig {
     pub countdown_seconds: u8,
     pub disable_drop: bool,
     pub disable_truncate: bool,
+    /// 監査ログに記録できない場合は実行を拒否する
+    #[serde(default)]
+    pub require_audit_log: bool,
 }
 
 impl Default for EnvironmentSafetyConfig {
@@ -32,6 +36,7 @@ impl Default for EnvironmentSafetyConfig {
             countdown_seconds: 3,
             disable_drop: false,
             disable_truncate: false,
+            require_audit_log: false,
         }
     }
 }
@@ -55,6 +60,7 @@ impl Default for SafetySettings {
                 countdown_seconds: 0,
                 disable_drop: false,
                 disable_truncate: false,
+                require_audit_log: false,
             },
         );
 
@@ -66,6 +72,7 @@ impl Default for SafetySettings {
                 countdown_seconds: 0,
                 disable_drop: false,
                 disable_truncate: false,
+                require_audit_log: false,
             },
         );
 
@@ -77,6 +84,7 @@ impl Default for SafetySettings {
                 countdown_seconds: 3,
                 disable_drop: false,
                 disable_truncate: false,
+                require_audit_log: false,
             },
         );
 
@@ -88,6 +96,7 @@ impl Default for SafetySettings {
                 countdown_seconds: 5,
                 disable_drop: true,
                 disable_truncate: true,
+                require_audit_log: true,
             },
         );
 
@@ -97,3 +106,25 @@ impl Default for SafetySettings {
         }
     }
 }
+
+impl SafetySettings {
+    /// 環境の安全設定を取得（未設定の環境はデフォルト設定を使う）
+    pub fn config_for(&self, environment: EnvironmentType) -> EnvironmentSafetyConfig {
+        let key = environment.settings_key();
+        self.environments
+            .get(key)
+            .cloned()
+            .or_else(|| Self::default().environments.remove(key))
+            .unwrap_or_default()
+    }
+}
+
+/// 危険な文の実行を許可する確認トークン
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfirmationToken {
+    /// 実行リクエストの `confirmationToken` に渡す値
+    pub token: String,
+    /// 有効期限（Unix秒）
+    pub expires_at: i64,
+}
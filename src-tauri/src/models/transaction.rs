@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+/// 開いているトランザクションの情報
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionInfo {
+    /// トランザクションID
+    pub transaction_id: String,
+    /// 接続ID
+    pub connection_id: String,
+    /// トランザクションを開始したウィンドウのラベル
+    pub window_label: String,
+    /// 開始日時（RFC3339）
+    pub started_at: String,
+    /// アイドルタイムアウト（秒）。超過すると自動的にロールバックされる
+    pub timeout_seconds: u64,
+}
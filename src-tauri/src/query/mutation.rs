@@ -1,8 +1,19 @@
-use crate::models::query::WhereClause;
-use crate::sql_generator::builder::{QuoteStyle, SqlBuilder};
+use crate::models::query::{JoinClause, JoinConditionColumn, QueryModel, WhereClause};
+use crate::sql_generator::builder::{ParamCollector, QuoteStyle, SqlBuilder};
 use crate::sql_generator::{reserved_words, Dialect};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use sqlparser::ast::{
+    AssignmentTarget, Expr, FromTable, JoinConstraint, JoinOperator, ObjectName, Statement,
+    TableFactor, TableWithJoins, Value as SqlValue,
+};
+use sqlparser::dialect::{
+    Dialect as ParserDialect, DuckDbDialect, MsSqlDialect, MySqlDialect, PostgreSqlDialect,
+    SQLiteDialect,
+};
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::Parser;
+use sqlparser::tokenizer::{Token, Tokenizer};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -12,6 +23,34 @@ pub struct InsertQueryModel {
     pub table: String,
     pub columns: Vec<String>,
     pub values: Vec<Value>,
+    /// 主キー・一意インデックスと重複した場合の動作（UPSERT）
+    #[serde(default)]
+    pub on_conflict: Option<OnConflict>,
+    /// 挿入する行を取得するSELECT（INSERT ... SELECT）。指定した場合 `values` は空にする
+    #[serde(default)]
+    pub source: Option<QueryModel>,
+}
+
+/// INSERTが主キー・一意インデックスと重複した場合の動作
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OnConflict {
+    /// 重複を判定するカラム（主キーまたは一意インデックスのカラムと一致する必要がある）
+    pub target: Vec<String>,
+    pub action: OnConflictAction,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum OnConflictAction {
+    /// 既存の行を残す
+    DoNothing,
+    /// 既存の行の指定カラムを挿入しようとした値で更新する
+    DoUpdate { columns: Vec<String> },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +61,9 @@ pub struct UpdateQueryModel {
     pub table: String,
     pub set_clause: Value,
     pub where_clause: Option<WhereClause>,
+    /// 更新対象の行を絞り込むために結合するテーブル（INNER JOINのみ）
+    #[serde(default)]
+    pub joins: Vec<JoinClause>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -38,12 +80,37 @@ pub struct DeleteQueryModel {
     pub query_type: String,
     pub table: String,
     pub where_clause: Option<WhereClause>,
+    /// 削除対象の行を絞り込むために結合するテーブル（INNER JOINのみ）
+    #[serde(default)]
+    pub joins: Vec<JoinClause>,
+}
+
+/// UPDATE/DELETEの対象行を確認するSELECT
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MutationPreviewSql {
+    /// 対象行数を数える `SELECT COUNT(*)`
+    pub count_sql: String,
+    /// 対象行のサンプルを取得するSELECT
+    pub sample_sql: String,
 }
 
 pub fn generate_insert_sql(
     model: &InsertQueryModel,
     dialect: &dyn Dialect,
     smart_quote: bool,
+) -> Result<String, String> {
+    generate_insert_sql_for_server(model, dialect, smart_quote, None)
+}
+
+/// 接続先のバージョン（`SELECT VERSION()` の結果）に合わせてINSERT SQLを生成
+///
+/// MySQL 8.0.19 以降は非推奨の `VALUES(col)` の代わりに行エイリアスで挿入する値を参照する
+pub fn generate_insert_sql_for_server(
+    model: &InsertQueryModel,
+    dialect: &dyn Dialect,
+    smart_quote: bool,
+    server_version: Option<&str>,
 ) -> Result<String, String> {
     if model.table.trim().is_empty() {
         return Err("Table name is required".to_string());
@@ -53,8 +120,12 @@ pub fn generate_insert_sql(
         return Err("Insert columns are required".to_string());
     }
 
-    if model.values.is_empty() {
-        return Err("Insert values are required".to_string());
+    match (&model.source, model.values.is_empty()) {
+        (Some(_), false) => {
+            return Err("Insert values cannot be combined with a source query".to_string());
+        }
+        (None, true) => return Err("Insert values are required".to_string()),
+        _ => {}
     }
 
     let quote_style = if smart_quote {
@@ -72,17 +143,236 @@ pub fn generate_insert_sql(
         .collect::<Vec<_>>()
         .join(", ");
 
-    let values_clause = model
-        .values
+    // 挿入する行（VALUES または SELECT）
+    let rows = match &model.source {
+        Some(source) => SqlBuilder::new(dialect)
+            .smart_quote(smart_quote)
+            .compact()
+            .build(source)?,
+        None => format!(
+            "VALUES {}",
+            model
+                .values
+                .iter()
+                .map(|row| build_row_values(row, &model.columns, dialect))
+                .collect::<Result<Vec<_>, _>>()?
+                .join(", ")
+        ),
+    };
+
+    let Some(on_conflict) = &model.on_conflict else {
+        return Ok(format!(
+            "INSERT INTO {} ({}) {};",
+            table_name, columns, rows
+        ));
+    };
+
+    if on_conflict.target.is_empty() {
+        return Err("Conflict target columns are required".to_string());
+    }
+    let update_columns: &[String] = match &on_conflict.action {
+        OnConflictAction::DoNothing => &[],
+        OnConflictAction::DoUpdate { columns } if columns.is_empty() => {
+            return Err("Update columns are required for DO UPDATE".to_string());
+        }
+        OnConflictAction::DoUpdate { columns } => columns,
+    };
+    if let Some(column) = on_conflict
+        .target
         .iter()
-        .map(|row| build_row_values(row, &model.columns, dialect))
-        .collect::<Result<Vec<_>, _>>()?
-        .join(", ");
+        .chain(update_columns)
+        .find(|column| !model.columns.contains(column))
+    {
+        return Err(format!("Column {} is not in the insert columns", column));
+    }
 
-    Ok(format!(
-        "INSERT INTO {} ({}) VALUES {};",
-        table_name, columns, values_clause
-    ))
+    let quote = |column: &str| quote_identifier_path(column, dialect, quote_style);
+
+    match dialect.dialect_name() {
+        // 重複判定のカラムは指定できず、主キー・一意インデックスのいずれかで重複すると更新になる。
+        // DO NOTHING は他のエラーまで無視する INSERT IGNORE ではなく、値を変えない更新で表す
+        "mysql" | "mariadb" => {
+            // 行エイリアスは VALUES の後ろにしか書けないため、INSERT ... SELECT では使わない
+            let row_alias = (dialect.dialect_name() == "mysql"
+                && model.source.is_none()
+                && server_version.is_some_and(supports_row_alias))
+            .then(|| dialect.quote_identifier("new"));
+            let assignments = match &on_conflict.action {
+                OnConflictAction::DoNothing => {
+                    let column = quote(&on_conflict.target[0]);
+                    format!("{} = {}", column, column)
+                }
+                OnConflictAction::DoUpdate { .. } => update_columns
+                    .iter()
+                    .map(|column| {
+                        let column = quote(column);
+                        match &row_alias {
+                            Some(alias) => format!("{} = {}.{}", column, alias, column),
+                            None => format!("{} = VALUES({})", column, column),
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            };
+            let rows = match &row_alias {
+                Some(alias) if !update_columns.is_empty() => format!("{} AS {}", rows, alias),
+                _ => rows,
+            };
+            Ok(format!(
+                "INSERT INTO {} ({}) {} ON DUPLICATE KEY UPDATE {};",
+                table_name, columns, rows, assignments
+            ))
+        }
+        // SQL Serverには ON CONFLICT がないため MERGE で表す
+        "sqlserver" => {
+            let target = dialect.quote_identifier("target");
+            let source = dialect.quote_identifier("source");
+            let column_ref = |alias: &str, column: &str| format!("{}.{}", alias, quote(column));
+
+            let condition = on_conflict
+                .target
+                .iter()
+                .map(|column| {
+                    format!(
+                        "{} = {}",
+                        column_ref(&target, column),
+                        column_ref(&source, column)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(" AND ");
+
+            // HOLDLOCK で一致判定から挿入までの間に他の接続が同じキーを挿入しないようにする
+            let mut sql = format!(
+                "MERGE INTO {} WITH (HOLDLOCK) AS {} USING ({}) AS {} ({}) ON {}",
+                table_name, target, rows, source, columns, condition
+            );
+            if !update_columns.is_empty() {
+                let assignments = update_columns
+                    .iter()
+                    .map(|column| format!("{} = {}", quote(column), column_ref(&source, column)))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                sql.push_str(&format!(" WHEN MATCHED THEN UPDATE SET {}", assignments));
+            }
+            let source_values = model
+                .columns
+                .iter()
+                .map(|column| column_ref(&source, column))
+                .collect::<Vec<_>>()
+                .join(", ");
+            sql.push_str(&format!(
+                " WHEN NOT MATCHED THEN INSERT ({}) VALUES ({});",
+                columns, source_values
+            ));
+            Ok(sql)
+        }
+        _ => {
+            let action = match &on_conflict.action {
+                OnConflictAction::DoNothing => "DO NOTHING".to_string(),
+                OnConflictAction::DoUpdate { .. } => format!(
+                    "DO UPDATE SET {}",
+                    update_columns
+                        .iter()
+                        .map(|column| {
+                            let column = quote(column);
+                            format!("{} = EXCLUDED.{}", column, column)
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            };
+            let target = on_conflict
+                .target
+                .iter()
+                .map(|column| quote(column))
+                .collect::<Vec<_>>()
+                .join(", ");
+            // SQLiteは SELECT の後ろの ON を結合条件と区別できないため、WHERE付きのSELECTで包む
+            let rows = if model.source.is_some() && dialect.dialect_name() == "sqlite" {
+                format!("SELECT * FROM ({}) WHERE true", rows)
+            } else {
+                rows
+            };
+            Ok(format!(
+                "INSERT INTO {} ({}) {} ON CONFLICT ({}) {};",
+                table_name, columns, rows, target, action
+            ))
+        }
+    }
+}
+
+/// MySQLのバージョン文字列（`8.0.36-log` など）が行エイリアスに対応するか（8.0.19 以降）
+fn supports_row_alias(version: &str) -> bool {
+    let numbers = version
+        .split(|c: char| !c.is_ascii_digit())
+        .take(3)
+        .map(|part| part.parse::<u32>().unwrap_or(0))
+        .collect::<Vec<_>>();
+    match numbers.as_slice() {
+        [major, minor, patch] => (*major, *minor, *patch) >= (8, 0, 19),
+        _ => false,
+    }
+}
+
+/// 重複判定のカラムが主キーまたは一意インデックスのカラムと一致するか検証
+///
+/// `unique_keys` はインスペクターから取得した主キーと一意インデックスのカラム。
+/// カラムの順序と大文字小文字は区別しない
+pub fn validate_conflict_target(
+    target: &[String],
+    unique_keys: &[Vec<String>],
+) -> Result<(), String> {
+    let target_key = normalize_key(target);
+    if unique_keys
+        .iter()
+        .any(|key| normalize_key(key) == target_key)
+    {
+        Ok(())
+    } else {
+        Err(format!(
+            "Conflict target ({}) does not match the primary key or a unique index",
+            target.join(", ")
+        ))
+    }
+}
+
+/// MySQL/MariaDBの `ON DUPLICATE KEY UPDATE` の重複判定のカラムを検証
+///
+/// 重複判定のカラムを指定できず、どの主キー・一意インデックスで重複しても更新になるため、
+/// 重複判定のカラムがテーブルの唯一の一意キーである必要がある
+pub fn validate_duplicate_key_target(
+    target: &[String],
+    unique_keys: &[Vec<String>],
+) -> Result<(), String> {
+    validate_conflict_target(target, unique_keys)?;
+
+    // 主キーが一意インデックスとしても返される場合があるため、同じカラムの組は1つとみなす
+    let mut keys = unique_keys
+        .iter()
+        .map(|key| normalize_key(key))
+        .collect::<Vec<_>>();
+    keys.sort();
+    keys.dedup();
+    if keys.len() > 1 {
+        return Err(format!(
+            "Conflict target ({}) must be the only unique key of the table, \
+             because ON DUPLICATE KEY UPDATE matches any unique key",
+            target.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+/// キーのカラムを比較用に正規化（順序と大文字小文字を区別しない）
+fn normalize_key(columns: &[String]) -> Vec<String> {
+    let mut columns = columns
+        .iter()
+        .map(|column| column.to_lowercase())
+        .collect::<Vec<_>>();
+    columns.sort();
+    columns.dedup();
+    columns
 }
 
 pub fn generate_update_sql(
@@ -109,19 +399,69 @@ pub fn generate_update_sql(
         QuoteStyle::Always
     };
 
+    validate_joins(&model.joins)?;
+
+    let builder = SqlBuilder::new(dialect).smart_quote(smart_quote);
     let table_name = quote_identifier_path(&model.table, dialect, quote_style);
+    let target_name = target_table_name(&model.table);
+    let target_alias = builder.quote_identifier(target_name);
+    let dialect_name = dialect.dialect_name();
+    let has_joins = !model.joins.is_empty();
+
+    // SQLiteは結合を相関サブクエリに書き換える
+    let correlated = if has_joins && dialect_name == "sqlite" {
+        Some(build_join_filter(
+            &builder,
+            &model.joins,
+            model.where_clause.as_ref(),
+        )?)
+    } else {
+        None
+    };
 
     let set_items = set_clause_obj
         .iter()
         .map(|(column, config)| {
-            let column_name = quote_identifier_path(column, dialect, quote_style);
+            let mut column_name = quote_identifier_path(column, dialect, quote_style);
+            // MySQLで結合する場合は、どのテーブルのカラムかを明示する
+            if has_joins && matches!(dialect_name, "mysql" | "mariadb") {
+                column_name = format!("{}.{}", target_alias, column_name);
+            }
+
             let is_null = config
                 .get("isNull")
                 .and_then(|v| v.as_bool())
                 .unwrap_or(false);
+            let column_ref = config.get("column").filter(|v| !v.is_null());
 
             let value_str = if is_null {
                 "NULL".to_string()
+            } else if let Some(column_ref) = column_ref {
+                let column_ref: JoinConditionColumn = serde_json::from_value(column_ref.clone())
+                    .map_err(|e| format!("Invalid column reference: {}", e))?;
+                if column_ref.table_alias != target_name
+                    && !model
+                        .joins
+                        .iter()
+                        .any(|join| join.table.alias == column_ref.table_alias)
+                {
+                    return Err(format!(
+                        "Unknown table alias in set clause: {}",
+                        column_ref.table_alias
+                    ));
+                }
+                let column_sql = format!(
+                    "{}.{}",
+                    builder.quote_identifier(&column_ref.table_alias),
+                    builder.quote_identifier(&column_ref.column_name)
+                );
+                match &correlated {
+                    Some((tables, conditions)) if column_ref.table_alias != target_name => format!(
+                        "(SELECT {} FROM {} WHERE {})",
+                        column_sql, tables, conditions
+                    ),
+                    _ => column_sql,
+                }
             } else {
                 let value = config.get("value").unwrap_or(&Value::Null);
                 format_value(value, dialect)
@@ -132,21 +472,52 @@ pub fn generate_update_sql(
         .collect::<Result<Vec<_>, String>>()?
         .join(", ");
 
-    let mut sql = format!("UPDATE {} SET {}", table_name, set_items);
-
-    let has_where_clause = model.where_clause.is_some();
-    if let Some(ref where_clause) = model.where_clause {
-        let builder = SqlBuilder::new(dialect).smart_quote(smart_quote);
-        let where_sql = builder.build_where(where_clause)?;
-        sql.push(' ');
-        sql.push_str(&where_sql);
-    }
-
-    sql.push(';');
+    let where_sql = model
+        .where_clause
+        .as_ref()
+        .map(|where_clause| builder.build_where(where_clause, &mut ParamCollector::inline()))
+        .transpose()?
+        .map(|where_sql| format!(" {}", where_sql))
+        .unwrap_or_default();
+
+    let sql = if let Some((tables, conditions)) = correlated {
+        format!(
+            "UPDATE {} SET {} WHERE EXISTS (SELECT 1 FROM {} WHERE {});",
+            table_name, set_items, tables, conditions
+        )
+    } else if !has_joins {
+        format!("UPDATE {} SET {}{};", table_name, set_items, where_sql)
+    } else {
+        match dialect_name {
+            "mysql" | "mariadb" => format!(
+                "UPDATE {} {} SET {}{};",
+                table_name,
+                build_inner_joins(&builder, &model.joins)?,
+                set_items,
+                where_sql
+            ),
+            "sqlserver" => format!(
+                "UPDATE {} SET {} FROM {} {}{};",
+                target_alias,
+                set_items,
+                table_name,
+                build_inner_joins(&builder, &model.joins)?,
+                where_sql
+            ),
+            _ => {
+                let (tables, conditions) =
+                    build_join_filter(&builder, &model.joins, model.where_clause.as_ref())?;
+                format!(
+                    "UPDATE {} SET {} FROM {} WHERE {};",
+                    table_name, set_items, tables, conditions
+                )
+            }
+        }
+    };
 
     Ok(UpdateSqlResult {
         sql,
-        has_where_clause,
+        has_where_clause: model.where_clause.is_some(),
     })
 }
 
@@ -165,23 +536,569 @@ pub fn generate_delete_sql(
         QuoteStyle::Always
     };
 
+    validate_joins(&model.joins)?;
+
+    let builder = SqlBuilder::new(dialect).smart_quote(smart_quote);
     let table_name = quote_identifier_path(&model.table, dialect, quote_style);
 
-    let mut sql = format!("DELETE FROM {}", table_name);
+    let where_sql = model
+        .where_clause
+        .as_ref()
+        .map(|where_clause| builder.build_where(where_clause, &mut ParamCollector::inline()))
+        .transpose()?
+        .map(|where_sql| format!(" {}", where_sql))
+        .unwrap_or_default();
+
+    if model.joins.is_empty() {
+        return Ok(format!("DELETE FROM {}{};", table_name, where_sql));
+    }
+
+    match dialect.dialect_name() {
+        "mysql" | "mariadb" | "sqlserver" => Ok(format!(
+            "DELETE {} FROM {} {}{};",
+            builder.quote_identifier(target_table_name(&model.table)),
+            table_name,
+            build_inner_joins(&builder, &model.joins)?,
+            where_sql
+        )),
+        // SQLiteは結合を相関サブクエリに書き換える
+        "sqlite" => {
+            let (tables, conditions) =
+                build_join_filter(&builder, &model.joins, model.where_clause.as_ref())?;
+            Ok(format!(
+                "DELETE FROM {} WHERE EXISTS (SELECT 1 FROM {} WHERE {});",
+                table_name, tables, conditions
+            ))
+        }
+        _ => {
+            let (tables, conditions) =
+                build_join_filter(&builder, &model.joins, model.where_clause.as_ref())?;
+            Ok(format!(
+                "DELETE FROM {} USING {} WHERE {};",
+                table_name, tables, conditions
+            ))
+        }
+    }
+}
+
+/// UPDATE/DELETEで結合するテーブルを検証（対象行の絞り込みに使うためINNER JOINのみ）
+fn validate_joins(joins: &[JoinClause]) -> Result<(), String> {
+    for join in joins {
+        if join.join_type != "INNER" {
+            return Err(format!(
+                "Only INNER JOIN is supported in UPDATE/DELETE: {}",
+                join.join_type
+            ));
+        }
+        if join.conditions.is_empty() {
+            return Err(format!(
+                "Join conditions are required for {}",
+                join.table.name
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// スキーマ修飾を除いたテーブル名（結合条件・WHEREで対象テーブルを指す名前）
+fn target_table_name(table: &str) -> &str {
+    table.rsplit('.').next().unwrap_or(table)
+}
+
+/// 結合するテーブルを `INNER JOIN ... ON ...` の並びで生成
+fn build_inner_joins(builder: &SqlBuilder, joins: &[JoinClause]) -> Result<String, String> {
+    Ok(joins
+        .iter()
+        .map(|join| builder.build_join(join))
+        .collect::<Result<Vec<_>, _>>()?
+        .join(" "))
+}
+
+/// 結合するテーブルの一覧と、結合条件・WHERE条件をANDでつないだ条件を生成
+///
+/// `UPDATE ... FROM` / `DELETE ... USING` と相関サブクエリで使う
+fn build_join_filter(
+    builder: &SqlBuilder,
+    joins: &[JoinClause],
+    where_clause: Option<&WhereClause>,
+) -> Result<(String, String), String> {
+    let tables = joins
+        .iter()
+        .map(|join| builder.build_table_reference(&join.table))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let mut conditions = joins
+        .iter()
+        .map(|join| {
+            let conditions = builder.build_join_conditions(join);
+            if join.conditions.len() > 1 {
+                format!("({})", conditions)
+            } else {
+                conditions
+            }
+        })
+        .collect::<Vec<_>>();
+    if let Some(where_clause) = where_clause {
+        let where_conditions = builder.build_where_conditions(
+            &where_clause.conditions,
+            &where_clause.logic,
+            &mut ParamCollector::inline(),
+        )?;
+        conditions.push(format!("({})", where_conditions));
+    }
+
+    Ok((tables, conditions.join(" AND ")))
+}
 
-    if let Some(ref where_clause) = model.where_clause {
-        let builder = SqlBuilder::new(dialect).smart_quote(smart_quote);
-        let where_sql = builder.build_where(where_clause)?;
-        sql.push(' ');
-        sql.push_str(&where_sql);
+/// UPDATE/DELETEモデルと同じWHERE・結合で対象行を確認するSQLを生成
+pub fn generate_preview_sql(
+    table: &str,
+    where_clause: Option<&WhereClause>,
+    joins: &[JoinClause],
+    dialect: &dyn Dialect,
+    smart_quote: bool,
+    sample_limit: u64,
+) -> Result<MutationPreviewSql, String> {
+    if table.trim().is_empty() {
+        return Err("Table name is required".to_string());
     }
 
-    sql.push(';');
+    let quote_style = if smart_quote {
+        QuoteStyle::Smart
+    } else {
+        QuoteStyle::Always
+    };
 
-    Ok(sql)
+    validate_joins(joins)?;
+
+    let builder = SqlBuilder::new(dialect).smart_quote(smart_quote);
+    let table_name = quote_identifier_path(table, dialect, quote_style);
+    // 結合する場合は、どの方言でも対象行を重複なく数えられる相関サブクエリにする
+    let where_sql = if joins.is_empty() {
+        where_clause
+            .map(|where_clause| builder.build_where(where_clause, &mut ParamCollector::inline()))
+            .transpose()?
+    } else {
+        let (tables, conditions) = build_join_filter(&builder, joins, where_clause)?;
+        Some(format!(
+            "WHERE EXISTS (SELECT 1 FROM {} WHERE {})",
+            tables, conditions
+        ))
+    };
+
+    Ok(build_preview_sql(
+        &table_name,
+        where_sql.as_deref(),
+        None,
+        dialect,
+        sample_limit,
+    ))
+}
+
+/// エディタで入力した1文のUPDATE/DELETEから対象行を確認するSQLを生成
+///
+/// JOIN・FROM・USINGで結合する文は、変更するテーブルの行を結合先との EXISTS で絞り込む。
+/// `DELETE ... ORDER BY ... LIMIT n` は件数を n 件までとし、サンプルも同じ順序で取得する
+pub fn generate_preview_sql_for_statement(
+    sql: &str,
+    dialect: &dyn Dialect,
+    sample_limit: u64,
+) -> Result<MutationPreviewSql, String> {
+    statement_preview_sql(sql, dialect, sample_limit, false)
 }
 
-fn quote_identifier_path(identifier: &str, dialect: &dyn Dialect, quote_style: QuoteStyle) -> String {
+/// エディタで入力した1文のUPDATE/DELETEの変更前の行を取得するSELECTを生成
+///
+/// データ変更と同じトランザクションで実行し、取得した行を変更まで他の接続から変更されないようロックする。
+/// SQLiteとDuckDBには行ロックがないため、トランザクションの分離に任せる
+pub fn generate_snapshot_sql_for_statement(
+    sql: &str,
+    dialect: &dyn Dialect,
+    row_limit: u64,
+) -> Result<String, String> {
+    statement_preview_sql(sql, dialect, row_limit, true).map(|preview| preview.sample_sql)
+}
+
+fn statement_preview_sql(
+    sql: &str,
+    dialect: &dyn Dialect,
+    sample_limit: u64,
+    lock_rows: bool,
+) -> Result<MutationPreviewSql, String> {
+    let statements = match parse_statements(sql, dialect) {
+        Ok(statements) => statements,
+        // MySQLの `UPDATE ... ORDER BY ... LIMIT` はパーサーが解釈できない
+        Err(_) if is_update_with_limit(sql, dialect) => {
+            return Err("Preview is not supported for UPDATE with ORDER BY or LIMIT".to_string());
+        }
+        Err(e) => return Err(e),
+    };
+    let [statement] = statements.as_slice() else {
+        return Err("Preview requires exactly one UPDATE or DELETE statement".to_string());
+    };
+
+    let target = statement_target(statement, dialect)?;
+    let (order_by, limit) = match statement {
+        Statement::Delete(delete) => (delete.order_by.as_slice(), delete.limit.as_ref()),
+        _ => (&[][..], None),
+    };
+    // SQL Serverはテーブルヒントでロックする（別名の後に書く）
+    let table = match dialect.dialect_name() {
+        "sqlserver" if lock_rows => format!("{} WITH (UPDLOCK, HOLDLOCK)", target.relation),
+        _ => target.relation,
+    };
+
+    let row_limit = limit
+        .map(|limit| match limit {
+            Expr::Value(SqlValue::Number(number, _)) => number
+                .parse::<u64>()
+                .map_err(|_| format!("Invalid LIMIT for preview: {}", number)),
+            _ => Err("Preview requires LIMIT to be a number".to_string()),
+        })
+        .transpose()?;
+    let where_sql = target.filter.map(|filter| format!("WHERE {}", filter));
+    let order_sql = (!order_by.is_empty()).then(|| {
+        let order_by = order_by
+            .iter()
+            .map(|expr| expr.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("ORDER BY {}", order_by)
+    });
+
+    let mut preview = match row_limit {
+        None => build_preview_sql(
+            &table,
+            where_sql.as_deref(),
+            order_sql.as_deref(),
+            dialect,
+            sample_limit,
+        ),
+        // LIMIT 付きの文は、変更されるのと同じ行（同じ順序で先頭から n 件）だけを数える
+        Some(row_limit) => {
+            let preview = build_preview_sql(
+                &table,
+                where_sql.as_deref(),
+                order_sql.as_deref(),
+                dialect,
+                sample_limit.min(row_limit),
+            );
+            let limited = build_preview_sql(
+                &table,
+                where_sql.as_deref(),
+                order_sql.as_deref(),
+                dialect,
+                row_limit,
+            );
+            MutationPreviewSql {
+                count_sql: format!(
+                    "SELECT COUNT(*) AS affected_rows FROM ({}) AS limited_rows",
+                    limited.sample_sql
+                ),
+                sample_sql: preview.sample_sql,
+            }
+        }
+    };
+
+    if lock_rows && matches!(dialect.dialect_name(), "postgresql" | "mysql" | "mariadb") {
+        preview.sample_sql.push_str(" FOR UPDATE");
+    }
+
+    Ok(preview)
+}
+
+/// ORDER BY または LIMIT を指定したUPDATEか（トークン単位で判定する）
+fn is_update_with_limit(sql: &str, dialect: &dyn Dialect) -> bool {
+    if !matches!(dialect.dialect_name(), "mysql" | "mariadb") {
+        return false;
+    }
+    let Ok(tokens) = Tokenizer::new(&MySqlDialect {}, sql).tokenize() else {
+        return false;
+    };
+    let mut words = tokens.iter().filter_map(|token| match token {
+        Token::Word(word) if word.quote_style.is_none() => Some(word.keyword),
+        _ => None,
+    });
+    words.next() == Some(Keyword::UPDATE)
+        && words.any(|keyword| matches!(keyword, Keyword::LIMIT | Keyword::ORDER))
+}
+
+/// 接続先の方言でSQLを構文解析する
+pub(crate) fn parse_statements(sql: &str, dialect: &dyn Dialect) -> Result<Vec<Statement>, String> {
+    let parser_dialect: Box<dyn ParserDialect> = match dialect.dialect_name() {
+        "mysql" | "mariadb" => Box::new(MySqlDialect {}),
+        "sqlite" => Box::new(SQLiteDialect {}),
+        "duckdb" => Box::new(DuckDbDialect {}),
+        "sqlserver" => Box::new(MsSqlDialect {}),
+        _ => Box::new(PostgreSqlDialect {}),
+    };
+
+    Parser::parse_sql(&*parser_dialect, sql).map_err(|e| e.to_string())
+}
+
+/// 1文のUPDATE/DELETEで変更するテーブルと対象行の条件
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatementTarget {
+    /// テーブル名（`schema.table` の場合は2要素、引用符なし）
+    pub table: Vec<String>,
+    /// FROMに書くテーブル（別名を含む）
+    relation: String,
+    /// 対象行の条件（結合するテーブルは相関サブクエリの EXISTS にする）
+    filter: Option<String>,
+}
+
+/// 1文のUPDATE/DELETEから変更するテーブルを特定
+///
+/// 結合は INNER JOIN（カンマ区切り・CROSS JOIN を含む）のみ対応する。
+/// UPDATEのSETは変更するテーブルのカラムだけを指定できる
+pub fn statement_target(
+    statement: &Statement,
+    dialect: &dyn Dialect,
+) -> Result<StatementTarget, String> {
+    match statement {
+        Statement::Update {
+            table,
+            assignments,
+            from,
+            selection,
+            ..
+        } => {
+            let (relations, target) = match from {
+                None => (join_relations(std::slice::from_ref(table))?, 0),
+                Some(_) if !table.joins.is_empty() => {
+                    return Err("UPDATE with both JOIN and FROM is not supported".to_string());
+                }
+                Some(from) => {
+                    let from_relations = join_relations(std::slice::from_ref(from))?;
+                    // SQL Serverは UPDATE の後ろに FROM 内のテーブル（別名）を書ける
+                    let position = match (&table.relation, dialect.dialect_name()) {
+                        (
+                            TableFactor::Table {
+                                name, alias: None, ..
+                            },
+                            "sqlserver",
+                        ) => from_relations
+                            .iter()
+                            .position(|(relation, _)| refers_to(relation, name)),
+                        _ => None,
+                    };
+                    match position {
+                        Some(position) => (from_relations, position),
+                        None => {
+                            let mut relations = vec![(&table.relation, None)];
+                            relations.extend(from_relations);
+                            (relations, 0)
+                        }
+                    }
+                }
+            };
+
+            // 結合先のカラムを変更する文は、変更される行を特定できない
+            let target_relation = relations[target].0;
+            for assignment in assignments {
+                let names = match &assignment.target {
+                    AssignmentTarget::ColumnName(name) => vec![name],
+                    AssignmentTarget::Tuple(names) => names.iter().collect(),
+                };
+                if let Some(name) = names.iter().find(|name| {
+                    name.0.len() > 1
+                        && !refers_to(
+                            target_relation,
+                            &ObjectName(name.0[..name.0.len() - 1].to_vec()),
+                        )
+                }) {
+                    return Err(format!(
+                        "UPDATE can only change columns of {}: {}",
+                        target_relation, name
+                    ));
+                }
+            }
+
+            build_statement_target(&relations, target, selection.as_ref())
+        }
+        Statement::Delete(delete) => {
+            let from = match &delete.from {
+                FromTable::WithFromKeyword(tables) | FromTable::WithoutKeyword(tables) => tables,
+            };
+            let (relations, target) = match (delete.tables.as_slice(), &delete.using) {
+                ([], None) if from.len() == 1 && from[0].joins.is_empty() => {
+                    (join_relations(from)?, 0)
+                }
+                // MySQL/SQL Serverの `DELETE t FROM t JOIN ...`
+                ([name], None) => {
+                    let relations = join_relations(from)?;
+                    let position = relations
+                        .iter()
+                        .position(|(relation, _)| refers_to(relation, name))
+                        .ok_or_else(|| format!("Table {} is not in the FROM clause", name))?;
+                    (relations, position)
+                }
+                // MySQLの `DELETE FROM t USING t JOIN ...`
+                ([], Some(using)) if matches!(dialect.dialect_name(), "mysql" | "mariadb") => {
+                    let name = match from.as_slice() {
+                        [TableWithJoins {
+                            relation: TableFactor::Table { name, .. },
+                            joins,
+                        }] if joins.is_empty() => name,
+                        _ => return Err("DELETE must target a single table".to_string()),
+                    };
+                    let relations = join_relations(using)?;
+                    let position = relations
+                        .iter()
+                        .position(|(relation, _)| refers_to(relation, name))
+                        .ok_or_else(|| format!("Table {} is not in the USING clause", name))?;
+                    (relations, position)
+                }
+                // PostgreSQLの `DELETE FROM t USING u`
+                ([], Some(using)) if from.len() == 1 && from[0].joins.is_empty() => {
+                    let mut relations = join_relations(from)?;
+                    relations.extend(join_relations(using)?);
+                    (relations, 0)
+                }
+                _ => return Err("DELETE must target a single table".to_string()),
+            };
+
+            build_statement_target(&relations, target, delete.selection.as_ref())
+        }
+        _ => Err("Only UPDATE and DELETE are supported".to_string()),
+    }
+}
+
+/// 結合するテーブルと、INNER JOIN の結合条件を並べる
+fn join_relations(tables: &[TableWithJoins]) -> Result<Vec<(&TableFactor, Option<&Expr>)>, String> {
+    let mut relations = Vec::new();
+    for table in tables {
+        relations.push((&table.relation, None));
+        for join in &table.joins {
+            let condition = match &join.join_operator {
+                JoinOperator::Inner(JoinConstraint::On(condition)) => Some(condition),
+                JoinOperator::Inner(JoinConstraint::None) | JoinOperator::CrossJoin => None,
+                _ => return Err("Only INNER JOIN is supported in UPDATE/DELETE".to_string()),
+            };
+            relations.push((&join.relation, condition));
+        }
+    }
+    Ok(relations)
+}
+
+/// テーブルが `name`（別名があれば別名、なければテーブル名）で参照されるか
+fn refers_to(relation: &TableFactor, name: &ObjectName) -> bool {
+    let TableFactor::Table {
+        name: table_name,
+        alias,
+        ..
+    } = relation
+    else {
+        return false;
+    };
+    match (alias, name.0.as_slice()) {
+        (Some(alias), [ident]) => ident.value.eq_ignore_ascii_case(&alias.name.value),
+        (Some(_), _) => false,
+        (None, [ident]) => table_name
+            .0
+            .last()
+            .is_some_and(|last| last.value.eq_ignore_ascii_case(&ident.value)),
+        (None, _) => {
+            table_name.0.len() == name.0.len()
+                && table_name
+                    .0
+                    .iter()
+                    .zip(&name.0)
+                    .all(|(a, b)| a.value.eq_ignore_ascii_case(&b.value))
+        }
+    }
+}
+
+/// 変更するテーブル以外を EXISTS の相関サブクエリにまとめる
+fn build_statement_target(
+    relations: &[(&TableFactor, Option<&Expr>)],
+    target: usize,
+    selection: Option<&Expr>,
+) -> Result<StatementTarget, String> {
+    let TableFactor::Table { name, .. } = relations[target].0 else {
+        return Err(format!("Cannot change {}", relations[target].0));
+    };
+
+    let sources = relations
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| *index != target)
+        .map(|(_, (relation, _))| relation.to_string())
+        .collect::<Vec<_>>();
+    let filter = if sources.is_empty() {
+        selection.map(|selection| selection.to_string())
+    } else {
+        let conditions = relations
+            .iter()
+            .filter_map(|(_, condition)| *condition)
+            .chain(selection)
+            .map(|condition| format!("({})", condition))
+            .collect::<Vec<_>>();
+        let where_sql = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!(" WHERE {}", conditions.join(" AND "))
+        };
+        Some(format!(
+            "EXISTS (SELECT 1 FROM {}{})",
+            sources.join(", "),
+            where_sql
+        ))
+    };
+
+    Ok(StatementTarget {
+        table: name.0.iter().map(|ident| ident.value.clone()).collect(),
+        relation: relations[target].0.to_string(),
+        filter,
+    })
+}
+
+/// `order_sql` はサンプルの取得にだけ使う（件数には影響しないため）
+fn build_preview_sql(
+    table: &str,
+    where_sql: Option<&str>,
+    order_sql: Option<&str>,
+    dialect: &dyn Dialect,
+    sample_limit: u64,
+) -> MutationPreviewSql {
+    let where_suffix = where_sql
+        .map(|where_sql| format!(" {}", where_sql))
+        .unwrap_or_default();
+    let order_suffix = order_sql
+        .map(|order_sql| format!(" {}", order_sql))
+        .unwrap_or_default();
+
+    let count_sql = format!(
+        "SELECT COUNT(*) AS affected_rows FROM {}{}",
+        table, where_suffix
+    );
+
+    let sample_sql = match dialect.top_clause(sample_limit, None) {
+        Some(top) => format!(
+            "SELECT {} * FROM {}{}{}",
+            top, table, where_suffix, order_suffix
+        ),
+        None => format!(
+            "SELECT * FROM {}{}{} {}",
+            table,
+            where_suffix,
+            order_suffix,
+            dialect.limit_offset(sample_limit, None)
+        ),
+    };
+
+    MutationPreviewSql {
+        count_sql,
+        sample_sql,
+    }
+}
+
+fn quote_identifier_path(
+    identifier: &str,
+    dialect: &dyn Dialect,
+    quote_style: QuoteStyle,
+) -> String {
     identifier
         .split('.')
         .map(|part| quote_identifier(part, dialect, quote_style))
@@ -194,7 +1111,7 @@ fn quote_identifier(identifier: &str, dialect: &dyn Dialect, quote_style: QuoteS
     match quote_style {
         QuoteStyle::Always => dialect.quote_identifier(identifier),
         QuoteStyle::Smart => {
-            if needs_quoting(identifier) {
+            if needs_quoting(identifier, dialect.dialect_name()) {
                 dialect.quote_identifier(identifier)
             } else {
                 identifier.to_string()
@@ -204,7 +1121,7 @@ fn quote_identifier(identifier: &str, dialect: &dyn Dialect, quote_style: QuoteS
 }
 
 /// 識別子に引用符が必要かどうかを判定
-fn needs_quoting(identifier: &str) -> bool {
+fn needs_quoting(identifier: &str, dialect_name: &str) -> bool {
     // 空文字列または空白を含む場合は必要
     if identifier.is_empty() || identifier.chars().any(|c| c.is_whitespace()) {
         return true;
@@ -226,7 +1143,7 @@ fn needs_quoting(identifier: &str) -> bool {
     }
 
     // 予約語の場合は必要
-    if reserved_words::simple_impl::is_reserved(identifier) {
+    if reserved_words::is_reserved_in(identifier, dialect_name) {
         return true;
     }
 
@@ -273,10 +1190,12 @@ fn format_value(value: &Value, dialect: &dyn Dialect) -> String {
 mod tests {
     use super::*;
     use crate::models::query::{
-        LiteralValue, WhereClause, WhereCondition, WhereConditionColumn, WhereConditionItem,
-        WhereValue,
+        FromClause, JoinCondition, LiteralValue, SelectClause, SelectColumn, TableReference,
+        WhereClause, WhereCondition, WhereConditionColumn, WhereConditionItem, WhereValue,
+    };
+    use crate::sql_generator::dialects::{
+        MariadbDialect, MssqlDialect, MysqlDialect, PostgresDialect, SqliteDialect,
     };
-    use crate::sql_generator::dialects::{MysqlDialect, PostgresDialect, SqliteDialect};
 
     #[test]
     fn test_generate_insert_sql_single_row() {
@@ -288,6 +1207,8 @@ mod tests {
                 "name": "Alice",
                 "email": "alice@example.com"
             })],
+            on_conflict: None,
+            source: None,
         };
 
         let dialect = PostgresDialect;
@@ -317,6 +1238,8 @@ mod tests {
                 serde_json::json!({"name": "Alice", "email": "alice@example.com"}),
                 serde_json::json!({"name": "Bob", "email": "bob@example.com"}),
             ],
+            on_conflict: None,
+            source: None,
         };
 
         let dialect = PostgresDialect;
@@ -337,6 +1260,8 @@ mod tests {
                 "name": "Alice",
                 "email": null
             })],
+            on_conflict: None,
+            source: None,
         };
 
         let dialect = PostgresDialect;
@@ -355,6 +1280,8 @@ mod tests {
                 "name": "Alice",
                 "email": "alice@example.com"
             })],
+            on_conflict: None,
+            source: None,
         };
 
         let dialect = MysqlDialect;
@@ -372,6 +1299,8 @@ mod tests {
             values: vec![serde_json::json!({
                 "name": "Alice"
             })],
+            on_conflict: None,
+            source: None,
         };
 
         let dialect = SqliteDialect;
@@ -380,6 +1309,196 @@ mod tests {
         assert!(sql.contains("INSERT INTO \"users\""));
     }
 
+    #[test]
+    fn test_generate_insert_sql_on_conflict() {
+        let mut model = InsertQueryModel {
+            query_type: "INSERT".to_string(),
+            table: "users".to_string(),
+            columns: vec!["id".to_string(), "name".to_string()],
+            values: vec![serde_json::json!({"id": 1, "name": "Alice"})],
+            on_conflict: Some(OnConflict {
+                target: vec!["id".to_string()],
+                action: OnConflictAction::DoUpdate {
+                    columns: vec!["name".to_string()],
+                },
+            }),
+            source: None,
+        };
+
+        let sql = generate_insert_sql(&model, &PostgresDialect, true).unwrap();
+        assert_eq!(
+            sql,
+            "INSERT INTO users (id, name) VALUES (1, 'Alice') ON CONFLICT (id) DO UPDATE SET name = EXCLUDED.name;"
+        );
+
+        let sql = generate_insert_sql(&model, &MysqlDialect, false).unwrap();
+        assert_eq!(
+            sql,
+            "INSERT INTO `users` (`id`, `name`) VALUES (1, 'Alice') ON DUPLICATE KEY UPDATE `name` = VALUES(`name`);"
+        );
+
+        // MySQL 8.0.19 以降は行エイリアスで参照する（MariaDBは VALUES() のまま）
+        let sql = generate_insert_sql_for_server(&model, &MysqlDialect, false, Some("8.0.36-log"))
+            .unwrap();
+        assert_eq!(
+            sql,
+            "INSERT INTO `users` (`id`, `name`) VALUES (1, 'Alice') AS `new` ON DUPLICATE KEY UPDATE `name` = `new`.`name`;"
+        );
+        let sql =
+            generate_insert_sql_for_server(&model, &MysqlDialect, false, Some("8.0.18")).unwrap();
+        assert!(sql.ends_with("`name` = VALUES(`name`);"));
+        let sql =
+            generate_insert_sql_for_server(&model, &MariadbDialect, false, Some("10.11.6-MariaDB"))
+                .unwrap();
+        assert!(sql.ends_with("`name` = VALUES(`name`);"));
+
+        let sql = generate_insert_sql(&model, &MssqlDialect, false).unwrap();
+        assert_eq!(
+            sql,
+            "MERGE INTO [users] WITH (HOLDLOCK) AS [target] USING (VALUES (1, N'Alice')) AS [source] ([id], [name]) \
+             ON [target].[id] = [source].[id] \
+             WHEN MATCHED THEN UPDATE SET [name] = [source].[name] \
+             WHEN NOT MATCHED THEN INSERT ([id], [name]) VALUES ([source].[id], [source].[name]);"
+        );
+
+        model.on_conflict = Some(OnConflict {
+            target: vec!["id".to_string()],
+            action: OnConflictAction::DoNothing,
+        });
+
+        let sql = generate_insert_sql(&model, &SqliteDialect, true).unwrap();
+        assert_eq!(
+            sql,
+            "INSERT INTO users (id, name) VALUES (1, 'Alice') ON CONFLICT (id) DO NOTHING;"
+        );
+
+        let sql = generate_insert_sql(&model, &MysqlDialect, true).unwrap();
+        assert!(sql.ends_with("ON DUPLICATE KEY UPDATE id = id;"));
+
+        let sql = generate_insert_sql(&model, &MssqlDialect, true).unwrap();
+        assert!(!sql.contains("WHEN MATCHED"));
+
+        // 挿入しないカラムは指定できない
+        model.on_conflict = Some(OnConflict {
+            target: vec!["email".to_string()],
+            action: OnConflictAction::DoNothing,
+        });
+        assert!(generate_insert_sql(&model, &PostgresDialect, true).is_err());
+
+        model.on_conflict = Some(OnConflict {
+            target: vec!["id".to_string()],
+            action: OnConflictAction::DoUpdate { columns: vec![] },
+        });
+        assert!(generate_insert_sql(&model, &PostgresDialect, true).is_err());
+    }
+
+    #[test]
+    fn test_generate_insert_sql_with_source() {
+        let column = |name: &str| SelectColumn::Column {
+            table_alias: "a".to_string(),
+            column_name: name.to_string(),
+            alias: None,
+        };
+        let mut model = InsertQueryModel {
+            query_type: "INSERT".to_string(),
+            table: "users".to_string(),
+            columns: vec!["id".to_string(), "name".to_string()],
+            values: vec![],
+            on_conflict: None,
+            source: Some(QueryModel {
+                id: None,
+                name: None,
+                description: None,
+                connection_id: "test".to_string(),
+                select: SelectClause {
+                    distinct: false,
+                    columns: vec![column("id"), column("name")],
+                },
+                from: FromClause {
+                    table: TableReference {
+                        schema: "archive".to_string(),
+                        name: "archived_users".to_string(),
+                        alias: "a".to_string(),
+                    },
+                },
+                joins: vec![],
+                where_clause: None,
+                group_by: None,
+                having: None,
+                order_by: None,
+                limit: None,
+                created_at: None,
+                updated_at: None,
+            }),
+        };
+
+        let sql = generate_insert_sql(&model, &PostgresDialect, true).unwrap();
+        assert_eq!(
+            sql,
+            "INSERT INTO users (id, name) SELECT a.id, a.name FROM archive.archived_users a;"
+        );
+
+        model.on_conflict = Some(OnConflict {
+            target: vec!["id".to_string()],
+            action: OnConflictAction::DoNothing,
+        });
+
+        // SQLiteはWHERE付きのSELECTで包む
+        let sql = generate_insert_sql(&model, &SqliteDialect, true).unwrap();
+        assert_eq!(
+            sql,
+            "INSERT INTO users (id, name) SELECT * FROM (SELECT a.id, a.name FROM archive.archived_users a) WHERE true ON CONFLICT (id) DO NOTHING;"
+        );
+
+        let sql = generate_insert_sql(&model, &MssqlDialect, true).unwrap();
+        assert!(sql.starts_with(
+            "MERGE INTO users WITH (HOLDLOCK) AS [target] USING (SELECT a.id, a.name FROM archive.archived_users a) AS [source] (id, name)"
+        ));
+
+        // VALUESとSELECTは同時に指定できない
+        model.values = vec![serde_json::json!({"id": 1, "name": "Alice"})];
+        assert!(generate_insert_sql(&model, &PostgresDialect, true).is_err());
+    }
+
+    #[test]
+    fn test_validate_conflict_target() {
+        let unique_keys = vec![
+            vec!["id".to_string()],
+            vec!["tenant_id".to_string(), "email".to_string()],
+        ];
+
+        assert!(validate_conflict_target(&["ID".to_string()], &unique_keys).is_ok());
+        assert!(validate_conflict_target(
+            &["email".to_string(), "tenant_id".to_string()],
+            &unique_keys
+        )
+        .is_ok());
+        assert!(validate_conflict_target(&["email".to_string()], &unique_keys).is_err());
+        assert!(validate_conflict_target(&["id".to_string()], &[]).is_err());
+    }
+
+    #[test]
+    fn test_validate_duplicate_key_target() {
+        // 主キーがインデックスとしても返される場合は同じキーとみなす
+        let unique_keys = vec![vec!["id".to_string()], vec!["ID".to_string()]];
+        assert!(validate_duplicate_key_target(&["id".to_string()], &unique_keys).is_ok());
+
+        // 他の一意キーで重複した場合も更新になるため指定できない
+        let unique_keys = vec![vec!["id".to_string()], vec!["email".to_string()]];
+        assert!(validate_duplicate_key_target(&["id".to_string()], &unique_keys).is_err());
+        assert!(validate_duplicate_key_target(&["name".to_string()], &unique_keys).is_err());
+    }
+
+    #[test]
+    fn test_supports_row_alias() {
+        assert!(supports_row_alias("8.0.19"));
+        assert!(supports_row_alias("8.4.0-commercial"));
+        assert!(supports_row_alias("9.1.0"));
+        assert!(!supports_row_alias("8.0.18-log"));
+        assert!(!supports_row_alias("5.7.44"));
+        assert!(!supports_row_alias("unknown"));
+    }
+
     #[test]
     fn test_generate_update_sql_with_where() {
         let model = UpdateQueryModel {
@@ -396,6 +1515,7 @@ mod tests {
                     column: WhereConditionColumn {
                         table_alias: "users".to_string(),
                         column_name: "id".to_string(),
+                        data_type: None,
                     },
                     operator: "=".to_string(),
                     value: WhereValue::Literal {
@@ -403,6 +1523,7 @@ mod tests {
                     },
                 })],
             }),
+            joins: vec![],
         };
 
         let dialect = PostgresDialect;
@@ -424,6 +1545,7 @@ mod tests {
                 "is_active": { "value": true, "isNull": false }
             }),
             where_clause: None,
+            joins: vec![],
         };
 
         let dialect = PostgresDialect;
@@ -444,6 +1566,7 @@ mod tests {
                 "email": { "value": null, "isNull": true }
             }),
             where_clause: None,
+            joins: vec![],
         };
 
         let dialect = PostgresDialect;
@@ -464,6 +1587,7 @@ mod tests {
                     column: WhereConditionColumn {
                         table_alias: "users".to_string(),
                         column_name: "id".to_string(),
+                        data_type: None,
                     },
                     operator: "=".to_string(),
                     value: WhereValue::Literal {
@@ -471,6 +1595,7 @@ mod tests {
                     },
                 })],
             }),
+            joins: vec![],
         };
 
         let dialect = PostgresDialect;
@@ -486,6 +1611,7 @@ mod tests {
             query_type: "DELETE".to_string(),
             table: "users".to_string(),
             where_clause: None,
+            joins: vec![],
         };
 
         let dialect = PostgresDialect;
@@ -494,4 +1620,353 @@ mod tests {
         assert!(sql.contains("DELETE FROM users"));
         assert!(!sql.contains("WHERE"));
     }
+
+    /// `users.id = o.user_id` で orders を結合する
+    fn orders_join() -> JoinClause {
+        JoinClause {
+            id: "join-1".to_string(),
+            join_type: "INNER".to_string(),
+            table: TableReference {
+                schema: "shop".to_string(),
+                name: "orders".to_string(),
+                alias: "o".to_string(),
+            },
+            conditions: vec![JoinCondition {
+                left: JoinConditionColumn {
+                    table_alias: "users".to_string(),
+                    column_name: "id".to_string(),
+                },
+                operator: "=".to_string(),
+                right: JoinConditionColumn {
+                    table_alias: "o".to_string(),
+                    column_name: "user_id".to_string(),
+                },
+            }],
+            condition_logic: "AND".to_string(),
+        }
+    }
+
+    /// `o.status = 'canceled'`
+    fn canceled_orders() -> WhereClause {
+        WhereClause {
+            logic: "AND".to_string(),
+            conditions: vec![WhereConditionItem::Condition(WhereCondition {
+                id: "cond-1".to_string(),
+                column: WhereConditionColumn {
+                    table_alias: "o".to_string(),
+                    column_name: "status".to_string(),
+                    data_type: None,
+                },
+                operator: "=".to_string(),
+                value: WhereValue::Literal {
+                    value: LiteralValue::String("canceled".to_string()),
+                },
+            })],
+        }
+    }
+
+    #[test]
+    fn test_generate_update_sql_with_joins() {
+        let model = UpdateQueryModel {
+            query_type: "UPDATE".to_string(),
+            table: "users".to_string(),
+            set_clause: serde_json::json!({
+                "is_active": { "value": false, "isNull": false },
+                "note": {
+                    "value": null,
+                    "isNull": false,
+                    "column": { "tableAlias": "o", "columnName": "memo" }
+                }
+            }),
+            where_clause: Some(canceled_orders()),
+            joins: vec![orders_join()],
+        };
+
+        let result = generate_update_sql(&model, &PostgresDialect, true).unwrap();
+        assert_eq!(
+            result.sql,
+            "UPDATE users SET is_active = FALSE, note = o.memo FROM shop.orders o \
+             WHERE users.id = o.user_id AND (o.status = 'canceled');"
+        );
+        assert!(result.has_where_clause);
+
+        let result = generate_update_sql(&model, &MysqlDialect, true).unwrap();
+        assert_eq!(
+            result.sql,
+            "UPDATE users INNER JOIN shop.orders o ON users.id = o.user_id \
+             SET users.is_active = FALSE, users.note = o.memo WHERE o.status = 'canceled';"
+        );
+
+        let result = generate_update_sql(&model, &MssqlDialect, true).unwrap();
+        assert_eq!(
+            result.sql,
+            "UPDATE users SET is_active = FALSE, note = o.memo \
+             FROM users INNER JOIN shop.orders o ON users.id = o.user_id \
+             WHERE o.status = N'canceled';"
+        );
+
+        // SQLiteは相関サブクエリに書き換える
+        let result = generate_update_sql(&model, &SqliteDialect, true).unwrap();
+        assert_eq!(
+            result.sql,
+            "UPDATE users SET is_active = FALSE, \
+             note = (SELECT o.memo FROM shop.orders o WHERE users.id = o.user_id AND (o.status = 'canceled')) \
+             WHERE EXISTS (SELECT 1 FROM shop.orders o WHERE users.id = o.user_id AND (o.status = 'canceled'));"
+        );
+
+        // 絞り込みに使えないJOINはエラー
+        let mut model = model;
+        model.joins[0].join_type = "LEFT".to_string();
+        assert!(generate_update_sql(&model, &PostgresDialect, true).is_err());
+
+        // 対象テーブルにも結合先にもない別名は参照できない
+        model.joins[0].join_type = "INNER".to_string();
+        model.set_clause = serde_json::json!({
+            "note": {
+                "value": null,
+                "isNull": false,
+                "column": { "tableAlias": "p", "columnName": "memo" }
+            }
+        });
+        assert_eq!(
+            generate_update_sql(&model, &PostgresDialect, true).unwrap_err(),
+            "Unknown table alias in set clause: p"
+        );
+    }
+
+    #[test]
+    fn test_generate_delete_sql_with_joins() {
+        let model = DeleteQueryModel {
+            query_type: "DELETE".to_string(),
+            table: "users".to_string(),
+            where_clause: Some(canceled_orders()),
+            joins: vec![orders_join()],
+        };
+
+        let sql = generate_delete_sql(&model, &PostgresDialect, true).unwrap();
+        assert_eq!(
+            sql,
+            "DELETE FROM users USING shop.orders o \
+             WHERE users.id = o.user_id AND (o.status = 'canceled');"
+        );
+
+        let sql = generate_delete_sql(&model, &MysqlDialect, true).unwrap();
+        assert_eq!(
+            sql,
+            "DELETE users FROM users INNER JOIN shop.orders o ON users.id = o.user_id \
+             WHERE o.status = 'canceled';"
+        );
+
+        let sql = generate_delete_sql(&model, &SqliteDialect, true).unwrap();
+        assert_eq!(
+            sql,
+            "DELETE FROM users WHERE EXISTS (SELECT 1 FROM shop.orders o \
+             WHERE users.id = o.user_id AND (o.status = 'canceled'));"
+        );
+
+        // 結合のみで絞り込む場合
+        let model = DeleteQueryModel {
+            where_clause: None,
+            ..model
+        };
+        let sql = generate_delete_sql(&model, &PostgresDialect, true).unwrap();
+        assert_eq!(
+            sql,
+            "DELETE FROM users USING shop.orders o WHERE users.id = o.user_id;"
+        );
+    }
+
+    #[test]
+    fn test_generate_preview_sql_with_joins() {
+        let preview = generate_preview_sql(
+            "users",
+            Some(&canceled_orders()),
+            &[orders_join()],
+            &PostgresDialect,
+            true,
+            5,
+        )
+        .unwrap();
+        assert_eq!(
+            preview.count_sql,
+            "SELECT COUNT(*) AS affected_rows FROM users WHERE EXISTS (SELECT 1 FROM shop.orders o \
+             WHERE users.id = o.user_id AND (o.status = 'canceled'))"
+        );
+    }
+
+    #[test]
+    fn test_generate_preview_sql() {
+        let where_clause = WhereClause {
+            logic: "AND".to_string(),
+            conditions: vec![WhereConditionItem::Condition(WhereCondition {
+                id: "cond-1".to_string(),
+                column: WhereConditionColumn {
+                    table_alias: "users".to_string(),
+                    column_name: "id".to_string(),
+                    data_type: None,
+                },
+                operator: ">".to_string(),
+                value: WhereValue::Literal {
+                    value: LiteralValue::Number(10.0),
+                },
+            })],
+        };
+
+        let preview =
+            generate_preview_sql("users", Some(&where_clause), &[], &PostgresDialect, true, 5)
+                .unwrap();
+        assert_eq!(
+            preview.count_sql,
+            "SELECT COUNT(*) AS affected_rows FROM users WHERE users.id > 10"
+        );
+        assert_eq!(
+            preview.sample_sql,
+            "SELECT * FROM users WHERE users.id > 10 LIMIT 5"
+        );
+
+        // SQL ServerはTOPで件数を制限する
+        let preview = generate_preview_sql("users", None, &[], &MssqlDialect, false, 5).unwrap();
+        assert_eq!(
+            preview.count_sql,
+            "SELECT COUNT(*) AS affected_rows FROM [users]"
+        );
+        assert_eq!(preview.sample_sql, "SELECT TOP 5 * FROM [users]");
+    }
+
+    #[test]
+    fn test_generate_preview_sql_for_statement() {
+        let preview = generate_preview_sql_for_statement(
+            "UPDATE users u SET active = false WHERE u.last_login < '2020-01-01'",
+            &PostgresDialect,
+            10,
+        )
+        .unwrap();
+        assert_eq!(
+            preview.count_sql,
+            "SELECT COUNT(*) AS affected_rows FROM users AS u WHERE u.last_login < '2020-01-01'"
+        );
+
+        let preview = generate_preview_sql_for_statement(
+            "DELETE FROM `orders` WHERE status = 'cancelled';",
+            &MysqlDialect,
+            10,
+        )
+        .unwrap();
+        assert_eq!(
+            preview.sample_sql,
+            "SELECT * FROM `orders` WHERE status = 'cancelled' LIMIT 10"
+        );
+
+        let preview =
+            generate_preview_sql_for_statement("DELETE FROM logs", &SqliteDialect, 10).unwrap();
+        assert_eq!(
+            preview.count_sql,
+            "SELECT COUNT(*) AS affected_rows FROM logs"
+        );
+
+        // LIMIT 付きのDELETEは同じ順序で先頭から n 件だけを数える
+        let preview = generate_preview_sql_for_statement(
+            "DELETE FROM logs WHERE level = 'debug' ORDER BY created_at LIMIT 100",
+            &MysqlDialect,
+            10,
+        )
+        .unwrap();
+        assert_eq!(
+            preview.count_sql,
+            "SELECT COUNT(*) AS affected_rows FROM (SELECT * FROM logs WHERE level = 'debug' ORDER BY created_at LIMIT 100) AS limited_rows"
+        );
+        assert_eq!(
+            preview.sample_sql,
+            "SELECT * FROM logs WHERE level = 'debug' ORDER BY created_at LIMIT 10"
+        );
+
+        let preview =
+            generate_preview_sql_for_statement("DELETE FROM logs LIMIT 3", &MysqlDialect, 10)
+                .unwrap();
+        assert_eq!(preview.sample_sql, "SELECT * FROM logs LIMIT 3");
+
+        // 対応しない文
+        assert!(generate_preview_sql_for_statement("SELECT 1", &PostgresDialect, 10).is_err());
+        assert_eq!(
+            generate_preview_sql_for_statement(
+                "UPDATE users SET active = 0 ORDER BY id LIMIT 10",
+                &MysqlDialect,
+                10
+            )
+            .unwrap_err(),
+            "Preview is not supported for UPDATE with ORDER BY or LIMIT"
+        );
+        assert!(generate_preview_sql_for_statement(
+            "DELETE FROM a; DELETE FROM b",
+            &PostgresDialect,
+            10
+        )
+        .is_err());
+        // 結合先のカラムを変更する文・外部結合する文
+        assert!(generate_preview_sql_for_statement(
+            "UPDATE a JOIN b ON a.id = b.id SET b.x = 1",
+            &MysqlDialect,
+            10
+        )
+        .is_err());
+        assert!(generate_preview_sql_for_statement(
+            "DELETE a FROM a LEFT JOIN b ON a.id = b.id WHERE b.id IS NULL",
+            &MysqlDialect,
+            10
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_generate_preview_sql_for_joined_statement() {
+        // 結合先は EXISTS にして、変更するテーブルの行だけを数える
+        let preview = generate_preview_sql_for_statement(
+            "UPDATE users SET is_active = FALSE, note = o.memo FROM shop.orders o \
+             WHERE users.id = o.user_id AND (o.status = 'canceled')",
+            &PostgresDialect,
+            10,
+        )
+        .unwrap();
+        assert_eq!(
+            preview.count_sql,
+            "SELECT COUNT(*) AS affected_rows FROM users WHERE EXISTS (SELECT 1 FROM shop.orders AS o \
+             WHERE (users.id = o.user_id AND (o.status = 'canceled')))"
+        );
+
+        let expected = "SELECT * FROM users WHERE EXISTS (SELECT 1 FROM shop.orders AS o \
+                        WHERE (users.id = o.user_id) AND (o.status = 'canceled')) LIMIT 10";
+        for sql in [
+            "UPDATE users INNER JOIN shop.orders o ON users.id = o.user_id \
+             SET users.is_active = FALSE, users.note = o.memo WHERE o.status = 'canceled'",
+            "DELETE users FROM users INNER JOIN shop.orders o ON users.id = o.user_id \
+             WHERE o.status = 'canceled'",
+        ] {
+            let preview = generate_preview_sql_for_statement(sql, &MysqlDialect, 10).unwrap();
+            assert_eq!(preview.sample_sql, expected);
+        }
+
+        // SQL Serverは UPDATE の後ろのテーブルを FROM から探す
+        let preview = generate_preview_sql_for_statement(
+            "UPDATE u SET is_active = 0 FROM users u INNER JOIN orders o ON u.id = o.user_id",
+            &MssqlDialect,
+            10,
+        )
+        .unwrap();
+        assert_eq!(
+            preview.sample_sql,
+            "SELECT TOP 10 * FROM users AS u WHERE EXISTS (SELECT 1 FROM orders AS o WHERE (u.id = o.user_id))"
+        );
+
+        let preview = generate_preview_sql_for_statement(
+            "DELETE FROM users USING orders WHERE users.id = orders.user_id",
+            &PostgresDialect,
+            10,
+        )
+        .unwrap();
+        assert_eq!(
+            preview.count_sql,
+            "SELECT COUNT(*) AS affected_rows FROM users \
+             WHERE EXISTS (SELECT 1 FROM orders WHERE (users.id = orders.user_id))"
+        );
+    }
 }
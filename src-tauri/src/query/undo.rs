@@ -0,0 +1,582 @@
+use crate::models::database_structure::Column;
+use crate::models::query_result::{QueryResult, QueryValue};
+use crate::query::mutation::{
+    generate_snapshot_sql_for_statement, parse_statements, statement_target,
+};
+use crate::sql_generator::Dialect;
+use sqlparser::ast::{AssignmentTarget, ObjectName, Statement};
+
+/// 実行前のスナップショットから元に戻すSQLを生成する対象（1文のUPDATE/DELETE）
+#[derive(Debug, Clone, PartialEq)]
+pub struct UndoTarget {
+    /// テーブル名（`schema.table` の場合は2要素、引用符なし）
+    pub table: Vec<String>,
+    pub kind: UndoKind,
+    /// 変更前の行を取得するSELECT（対象行をロックする）
+    pub snapshot_sql: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum UndoKind {
+    /// SETで変更するカラム（引用符なし）
+    Update {
+        columns: Vec<String>,
+    },
+    Delete,
+}
+
+impl UndoTarget {
+    /// スキーマ名（指定がなければ None）
+    pub fn schema(&self) -> Option<&str> {
+        match self.table.as_slice() {
+            [.., schema, _] => Some(schema),
+            _ => None,
+        }
+    }
+
+    /// テーブル名
+    pub fn table_name(&self) -> &str {
+        self.table.last().map(String::as_str).unwrap_or_default()
+    }
+}
+
+/// UPDATE/DELETEを解析し、変更前の行を取得するSELECTを組み立てる
+///
+/// スナップショットは `snapshot_limit` + 1 行まで取得する（上限を超えたかを判定するため）
+pub fn plan_undo(
+    sql: &str,
+    dialect: &dyn Dialect,
+    snapshot_limit: u64,
+) -> Result<UndoTarget, String> {
+    let statements = parse_statements(sql, dialect)?;
+    let [statement] = statements.as_slice() else {
+        return Err("Undo requires exactly one UPDATE or DELETE statement".to_string());
+    };
+
+    let kind = match statement {
+        Statement::Update { assignments, .. } => {
+            let columns = assignments
+                .iter()
+                .flat_map(|assignment| match &assignment.target {
+                    AssignmentTarget::ColumnName(name) => vec![name],
+                    AssignmentTarget::Tuple(names) => names.iter().collect(),
+                })
+                .map(column_name)
+                .collect();
+            UndoKind::Update { columns }
+        }
+        Statement::Delete(_) => UndoKind::Delete,
+        _ => return Err("Undo is only supported for UPDATE and DELETE".to_string()),
+    };
+    // 結合する文は、変更するテーブルの行だけを取得する
+    let table = statement_target(statement, dialect)?.table;
+
+    let snapshot_sql = generate_snapshot_sql_for_statement(sql, dialect, snapshot_limit + 1)?;
+
+    Ok(UndoTarget {
+        table,
+        kind,
+        snapshot_sql,
+    })
+}
+
+/// 変更前の行から元に戻すSQLを生成（1行につき1文）
+///
+/// DELETEは削除した行のINSERT、UPDATEは主キーで行を特定して変更前の値に戻すUPDATEになる。
+/// INSERTでは `columns`（テーブルのカラム情報）の生成列を除き、自動採番の列も元の値で戻す。
+/// 対象行がなければ None を返す
+pub fn generate_undo_sql(
+    target: &UndoTarget,
+    snapshot: &QueryResult,
+    primary_key: &[String],
+    columns: &[Column],
+    dialect: &dyn Dialect,
+) -> Result<Option<String>, String> {
+    if snapshot.rows.is_empty() {
+        return Ok(None);
+    }
+
+    let table = target
+        .table
+        .iter()
+        .map(|part| dialect.quote_identifier(part))
+        .collect::<Vec<_>>()
+        .join(".");
+
+    let statements = match &target.kind {
+        UndoKind::Delete => {
+            let column_info = |name: &str| {
+                columns
+                    .iter()
+                    .find(|column| column.name.eq_ignore_ascii_case(name))
+            };
+            let indexes = (0..snapshot.columns.len())
+                .filter(|&index| {
+                    !column_info(&snapshot.columns[index].name)
+                        .is_some_and(|column| column.is_generated)
+                })
+                .collect::<Vec<_>>();
+            let has_identity = indexes.iter().any(|&index| {
+                column_info(&snapshot.columns[index].name)
+                    .is_some_and(|column| column.is_auto_increment)
+            });
+
+            let column_list = indexes
+                .iter()
+                .map(|&index| dialect.quote_identifier(&snapshot.columns[index].name))
+                .collect::<Vec<_>>()
+                .join(", ");
+            // PostgreSQLの GENERATED ALWAYS の列は OVERRIDING SYSTEM VALUE で値を指定できる
+            let overriding = match dialect.dialect_name() {
+                "postgresql" if has_identity => " OVERRIDING SYSTEM VALUE",
+                _ => "",
+            };
+
+            let mut statements = snapshot
+                .rows
+                .iter()
+                .map(|row| {
+                    let values = indexes
+                        .iter()
+                        .map(|&index| {
+                            let value = row
+                                .values
+                                .get(index)
+                                .ok_or("Snapshot row is missing a column")?;
+                            format_literal(value, dialect)
+                        })
+                        .collect::<Result<Vec<_>, String>>()?;
+                    Ok(format!(
+                        "INSERT INTO {} ({}){} VALUES ({});",
+                        table,
+                        column_list,
+                        overriding,
+                        values.join(", ")
+                    ))
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+
+            // SQL ServerのIDENTITY列は IDENTITY_INSERT を有効にしている間だけ値を指定できる
+            if has_identity && dialect.dialect_name() == "sqlserver" {
+                statements.insert(0, format!("SET IDENTITY_INSERT {} ON;", table));
+                statements.push(format!("SET IDENTITY_INSERT {} OFF;", table));
+            }
+            statements
+        }
+        UndoKind::Update { columns } => {
+            if primary_key.is_empty() {
+                return Err(format!("Table {} has no primary key", target.table_name()));
+            }
+            if let Some(column) = columns.iter().find(|column| {
+                primary_key
+                    .iter()
+                    .any(|key| key.eq_ignore_ascii_case(column))
+            }) {
+                return Err(format!("Primary key column {} is updated", column));
+            }
+
+            let set_indexes = columns
+                .iter()
+                .map(|column| column_index(snapshot, column))
+                .collect::<Result<Vec<_>, _>>()?;
+            let key_indexes = primary_key
+                .iter()
+                .map(|column| column_index(snapshot, column))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            snapshot
+                .rows
+                .iter()
+                .map(|row| {
+                    let assignments = set_indexes
+                        .iter()
+                        .map(|&index| assignment(snapshot, row.values.get(index), index, dialect))
+                        .collect::<Result<Vec<_>, String>>()?;
+                    let conditions = key_indexes
+                        .iter()
+                        .map(|&index| assignment(snapshot, row.values.get(index), index, dialect))
+                        .collect::<Result<Vec<_>, String>>()?;
+                    Ok(format!(
+                        "UPDATE {} SET {} WHERE {};",
+                        table,
+                        assignments.join(", "),
+                        conditions.join(" AND ")
+                    ))
+                })
+                .collect::<Result<Vec<_>, String>>()?
+        }
+    };
+
+    Ok(Some(statements.join("\n")))
+}
+
+/// 値をSQLリテラルに変換
+///
+/// 配列は方言ごとに書式が異なるため対応しない
+pub fn format_literal(value: &QueryValue, dialect: &dyn Dialect) -> Result<String, String> {
+    let literal = match value {
+        QueryValue::Null => "NULL".to_string(),
+        QueryValue::Bool(value) => dialect.boolean_literal(*value),
+        QueryValue::Int(value) => value.to_string(),
+        QueryValue::Float(value) if value.is_finite() => value.to_string(),
+        QueryValue::Float(value) => {
+            let text = if value.is_nan() {
+                "NaN"
+            } else if value.is_sign_positive() {
+                "Infinity"
+            } else {
+                "-Infinity"
+            };
+            string_literal(text, dialect)
+        }
+        QueryValue::Decimal(value) => value.clone(),
+        QueryValue::String(value) => string_literal(value, dialect),
+        QueryValue::Bytes(bytes) => {
+            let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+            match dialect.dialect_name() {
+                "postgresql" => format!("'\\x{}'", hex),
+                "sqlserver" => format!("0x{}", hex),
+                "duckdb" => format!("from_hex('{}')", hex),
+                _ => format!("X'{}'", hex),
+            }
+        }
+        QueryValue::Date(value) => string_literal(&value.to_string(), dialect),
+        QueryValue::Time(value) => string_literal(&value.to_string(), dialect),
+        QueryValue::Timestamp(value) => string_literal(&value.to_string(), dialect),
+        QueryValue::TimestampTz(value) => string_literal(&value.to_rfc3339(), dialect),
+        QueryValue::Json(value) => string_literal(&value.to_string(), dialect),
+        QueryValue::Uuid(value) => string_literal(&value.to_string(), dialect),
+        QueryValue::Interval(value) => string_literal(&value.to_string(), dialect),
+        QueryValue::Array(_) => return Err("Array values are not supported".to_string()),
+    };
+
+    Ok(literal)
+}
+
+/// 文字列リテラル（MySQL/MariaDBはバックスラッシュもエスケープする）
+fn string_literal(value: &str, dialect: &dyn Dialect) -> String {
+    match dialect.dialect_name() {
+        "mysql" | "mariadb" => dialect.escape_string(&value.replace('\\', "\\\\")),
+        _ => dialect.escape_string(value),
+    }
+}
+
+/// `column = value`（SETと主キー条件で共通）
+fn assignment(
+    snapshot: &QueryResult,
+    value: Option<&QueryValue>,
+    index: usize,
+    dialect: &dyn Dialect,
+) -> Result<String, String> {
+    let value = value.ok_or("Snapshot row is missing a column")?;
+    Ok(format!(
+        "{} = {}",
+        dialect.quote_identifier(&snapshot.columns[index].name),
+        format_literal(value, dialect)?
+    ))
+}
+
+/// スナップショット内のカラム位置（大文字小文字は区別しない）
+fn column_index(snapshot: &QueryResult, column: &str) -> Result<usize, String> {
+    snapshot
+        .columns
+        .iter()
+        .position(|c| c.name == column)
+        .or_else(|| {
+            snapshot
+                .columns
+                .iter()
+                .position(|c| c.name.eq_ignore_ascii_case(column))
+        })
+        .ok_or_else(|| format!("Column {} is not in the snapshot", column))
+}
+
+/// `alias.column` のような修飾付きの名前からカラム名を取り出す
+fn column_name(name: &ObjectName) -> String {
+    name.0
+        .last()
+        .map(|ident| ident.value.clone())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::query_result::{QueryResultColumn, QueryResultRow};
+    use crate::sql_generator::dialects::{
+        MssqlDialect, MysqlDialect, PostgresDialect, SqliteDialect,
+    };
+
+    fn column(name: &str, is_auto_increment: bool, is_generated: bool) -> Column {
+        Column {
+            name: name.to_string(),
+            data_type: "int".to_string(),
+            display_type: "int".to_string(),
+            nullable: true,
+            default_value: None,
+            is_primary_key: false,
+            is_foreign_key: false,
+            is_unique: false,
+            is_auto_increment,
+            is_generated,
+            ordinal_position: 0,
+            comment: None,
+        }
+    }
+
+    fn snapshot(columns: &[&str], rows: Vec<Vec<QueryValue>>) -> QueryResult {
+        QueryResult {
+            columns: columns
+                .iter()
+                .map(|name| QueryResultColumn {
+                    name: name.to_string(),
+                    data_type: "text".to_string(),
+                    nullable: true,
+                })
+                .collect(),
+            row_count: rows.len(),
+            rows: rows
+                .into_iter()
+                .map(|values| QueryResultRow { values })
+                .collect(),
+            execution_time_ms: 0,
+            warnings: vec![],
+        }
+    }
+
+    #[test]
+    fn test_plan_undo() {
+        let dialect = PostgresDialect;
+
+        let target = plan_undo(
+            "UPDATE public.users AS u SET u.name = 'x', (a, b) = (1, 2) WHERE u.id > 10",
+            &dialect,
+            100,
+        )
+        .unwrap();
+        assert_eq!(target.table, vec!["public", "users"]);
+        assert_eq!(target.schema(), Some("public"));
+        assert_eq!(target.table_name(), "users");
+        assert_eq!(
+            target.kind,
+            UndoKind::Update {
+                columns: vec!["name".to_string(), "a".to_string(), "b".to_string()]
+            }
+        );
+        assert_eq!(
+            target.snapshot_sql,
+            "SELECT * FROM public.users AS u WHERE u.id > 10 LIMIT 101 FOR UPDATE"
+        );
+
+        // SQL Serverはテーブルヒントで対象行をロックする
+        let target = plan_undo(
+            "DELETE FROM dbo.logs WHERE level = 'debug'",
+            &MssqlDialect,
+            100,
+        )
+        .unwrap();
+        assert_eq!(
+            target.snapshot_sql,
+            "SELECT TOP 101 * FROM dbo.logs WITH (UPDLOCK, HOLDLOCK) WHERE level = 'debug'"
+        );
+        let target = plan_undo("DELETE FROM logs", &SqliteDialect, 100).unwrap();
+        assert_eq!(target.snapshot_sql, "SELECT * FROM logs LIMIT 101");
+
+        let target = plan_undo("DELETE FROM \"Users\"", &dialect, 100).unwrap();
+        assert_eq!(target.table, vec!["Users"]);
+        assert_eq!(target.schema(), None);
+        assert_eq!(target.kind, UndoKind::Delete);
+
+        assert!(plan_undo("INSERT INTO users (id) VALUES (1)", &dialect, 100).is_err());
+        assert!(plan_undo("DELETE FROM a; DELETE FROM b", &dialect, 100).is_err());
+
+        // 結合する文は変更するテーブルの行だけを取得する
+        let target = plan_undo(
+            "UPDATE a SET x = b.x FROM b WHERE a.id = b.id",
+            &dialect,
+            100,
+        )
+        .unwrap();
+        assert_eq!(target.table, vec!["a"]);
+        assert_eq!(
+            target.kind,
+            UndoKind::Update {
+                columns: vec!["x".to_string()]
+            }
+        );
+        assert_eq!(
+            target.snapshot_sql,
+            "SELECT * FROM a WHERE EXISTS (SELECT 1 FROM b WHERE (a.id = b.id)) LIMIT 101 FOR UPDATE"
+        );
+        let target = plan_undo(
+            "DELETE o FROM shop.orders o JOIN users u ON u.id = o.user_id WHERE u.banned",
+            &MysqlDialect,
+            100,
+        )
+        .unwrap();
+        assert_eq!(target.table, vec!["shop", "orders"]);
+        assert_eq!(target.kind, UndoKind::Delete);
+    }
+
+    #[test]
+    fn test_generate_undo_sql_for_delete() {
+        let dialect = PostgresDialect;
+        let target = plan_undo(
+            "DELETE FROM public.users WHERE active = false",
+            &dialect,
+            100,
+        )
+        .unwrap();
+        let rows = snapshot(
+            &["id", "name", "active"],
+            vec![
+                vec![
+                    QueryValue::Int(1),
+                    QueryValue::String("O'Brien".to_string()),
+                    QueryValue::Bool(false),
+                ],
+                vec![
+                    QueryValue::Int(2),
+                    QueryValue::Null,
+                    QueryValue::Bool(false),
+                ],
+            ],
+        );
+
+        let sql = generate_undo_sql(&target, &rows, &[], &[], &dialect)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            sql,
+            "INSERT INTO \"public\".\"users\" (\"id\", \"name\", \"active\") VALUES (1, 'O''Brien', FALSE);\n\
+             INSERT INTO \"public\".\"users\" (\"id\", \"name\", \"active\") VALUES (2, NULL, FALSE);"
+        );
+
+        let empty = snapshot(&["id"], vec![]);
+        assert_eq!(
+            generate_undo_sql(&target, &empty, &[], &[], &dialect).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_generate_undo_sql_for_delete_with_identity() {
+        let rows = snapshot(
+            &["id", "price", "total"],
+            vec![vec![
+                QueryValue::Int(5),
+                QueryValue::Int(100),
+                QueryValue::Int(110),
+            ]],
+        );
+        let columns = vec![
+            column("id", true, false),
+            column("price", false, false),
+            column("total", false, true),
+        ];
+
+        // 生成列は戻さず、IDENTITY列は元の値で戻す
+        let target = plan_undo("DELETE FROM items", &PostgresDialect, 100).unwrap();
+        let sql = generate_undo_sql(&target, &rows, &[], &columns, &PostgresDialect)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            sql,
+            "INSERT INTO \"items\" (\"id\", \"price\") OVERRIDING SYSTEM VALUE VALUES (5, 100);"
+        );
+
+        let target = plan_undo("DELETE FROM items", &MssqlDialect, 100).unwrap();
+        let sql = generate_undo_sql(&target, &rows, &[], &columns, &MssqlDialect)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            sql,
+            "SET IDENTITY_INSERT [items] ON;\n\
+             INSERT INTO [items] ([id], [price]) VALUES (5, 100);\n\
+             SET IDENTITY_INSERT [items] OFF;"
+        );
+
+        // 自動採番の列がなければ IDENTITY_INSERT は不要
+        let sql = generate_undo_sql(&target, &rows, &[], &columns[1..], &MssqlDialect)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sql, "INSERT INTO [items] ([id], [price]) VALUES (5, 100);");
+    }
+
+    #[test]
+    fn test_generate_undo_sql_for_update() {
+        let dialect = MysqlDialect;
+        let target = plan_undo(
+            "UPDATE orders SET status = 'done' WHERE id IN (1, 2)",
+            &dialect,
+            100,
+        )
+        .unwrap();
+        let rows = snapshot(
+            &["shop_id", "id", "status"],
+            vec![
+                vec![
+                    QueryValue::Int(7),
+                    QueryValue::Int(1),
+                    QueryValue::String("new\\path".to_string()),
+                ],
+                vec![QueryValue::Int(7), QueryValue::Int(2), QueryValue::Null],
+            ],
+        );
+        let primary_key = vec!["shop_id".to_string(), "ID".to_string()];
+
+        let sql = generate_undo_sql(&target, &rows, &primary_key, &[], &dialect)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            sql,
+            "UPDATE `orders` SET `status` = 'new\\\\path' WHERE `shop_id` = 7 AND `id` = 1;\n\
+             UPDATE `orders` SET `status` = NULL WHERE `shop_id` = 7 AND `id` = 2;"
+        );
+
+        // 主キーがない・主キーを変更する場合は生成しない
+        assert!(generate_undo_sql(&target, &rows, &[], &[], &dialect).is_err());
+        let target = plan_undo("UPDATE orders SET id = id + 100", &dialect, 100).unwrap();
+        assert!(generate_undo_sql(&target, &rows, &primary_key, &[], &dialect).is_err());
+    }
+
+    #[test]
+    fn test_format_literal() {
+        let postgres = PostgresDialect;
+        let sqlite = SqliteDialect;
+
+        assert_eq!(
+            format_literal(&QueryValue::Float(1.5), &postgres).unwrap(),
+            "1.5"
+        );
+        assert_eq!(
+            format_literal(&QueryValue::Float(f64::NAN), &postgres).unwrap(),
+            "'NaN'"
+        );
+        assert_eq!(
+            format_literal(&QueryValue::Decimal("12.30".to_string()), &postgres).unwrap(),
+            "12.30"
+        );
+        assert_eq!(
+            format_literal(&QueryValue::Bytes(vec![0xde, 0xad]), &postgres).unwrap(),
+            "'\\xdead'"
+        );
+        assert_eq!(
+            format_literal(&QueryValue::Bytes(vec![0xde, 0xad]), &sqlite).unwrap(),
+            "X'dead'"
+        );
+        assert_eq!(
+            format_literal(
+                &QueryValue::Date(chrono::NaiveDate::from_ymd_opt(2024, 1, 2).unwrap()),
+                &postgres
+            )
+            .unwrap(),
+            "'2024-01-02'"
+        );
+        assert_eq!(
+            format_literal(&QueryValue::Json(serde_json::json!({"a": 1})), &postgres).unwrap(),
+            "'{\"a\":1}'"
+        );
+        assert!(format_literal(&QueryValue::Array(vec![]), &postgres).is_err());
+    }
+}
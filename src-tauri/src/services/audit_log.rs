@@ -0,0 +1,646 @@
+use crate::connection::ConnectionInfo;
+use crate::crypto::master_key::keychain::{Keychain, OsKeychain};
+use crate::crypto::master_key::{KeychainEntry, MasterKey};
+use crate::models::audit_log::{
+    AuditLogEntry, AuditLogVerification, AuditOperation, AuditOutcome, AuditRecord,
+    SearchAuditLogRequest,
+};
+use crate::models::export::{ExportFormat, ExportOptions, ExportResult};
+use crate::models::query_analysis::RiskLevel;
+use crate::models::query_result::{QueryError, QueryErrorCode};
+use crate::services::query_analyzer::QueryAnalyzer;
+use crate::services::safety_guard::{dialect_name, load_settings, safety_error};
+use crate::storage::path_manager::PathManager;
+use chrono::{NaiveDate, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 最初のエントリの `prev_hash`
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+const FILE_PREFIX: &str = "audit-";
+const FILE_EXTENSION: &str = ".jsonl";
+
+/// 最新エントリの記録（ログファイルとは別に保存する）
+const HEAD_FILE: &str = "head.json";
+
+/// 最後に書き込んだエントリの通し番号とハッシュ
+///
+/// 末尾のエントリやファイルを削除してもチェーン自体は切れないため、
+/// ログとは別に記録しておき検証時に照合する
+#[derive(Debug, Serialize, Deserialize)]
+struct AuditLogHead {
+    sequence: u64,
+    hash: String,
+    /// `sequence` と `hash` の署名
+    mac: String,
+}
+
+/// 実行した文の監査ログ
+///
+/// `logs/audit/audit-YYYY-MM-DD.jsonl` に日付（UTC）ごとに追記する。
+/// エントリはファイルをまたいでハッシュチェーンでつながり、
+/// 各ハッシュはキーチェーンに保存した鍵によるHMACで計算する
+pub struct AuditLogService {
+    dir: PathBuf,
+    keychain: Arc<dyn Keychain>,
+    /// 署名鍵（初回の使用時にキーチェーンから読み込む）
+    key: Mutex<Option<MasterKey>>,
+    /// 最後に書き込んだエントリの通し番号とハッシュ（初回の書き込み時にファイルから読み込む）
+    last: Mutex<Option<(u64, String)>>,
+}
+
+impl AuditLogService {
+    pub fn new(path_manager: &PathManager) -> Self {
+        Self::with_keychain(path_manager.audit_logs_dir(), Arc::new(OsKeychain))
+    }
+
+    /// カスタムキーチェーン実装を使用して作成（テスト用）
+    pub fn with_keychain(dir: PathBuf, keychain: Arc<dyn Keychain>) -> Self {
+        Self {
+            dir,
+            keychain,
+            key: Mutex::new(None),
+            last: Mutex::new(None),
+        }
+    }
+
+    /// 監査ログが必須の環境で、記録できない状態なら実行を拒否する
+    ///
+    /// 実行前に呼び、鍵や最新エントリが読めない・ログファイルに書けない場合はエラーを返す
+    pub fn ensure_recordable(&self, connection: &ConnectionInfo) -> Result<(), String> {
+        let config = load_settings().config_for(connection.environment.environment_type);
+        if !config.require_audit_log {
+            return Ok(());
+        }
+        self.prepare().map_err(|e| {
+            safety_error(
+                QueryErrorCode::PermissionDenied,
+                format!(
+                    "The audit log is required for this environment but cannot be written: {}",
+                    e
+                ),
+            )
+        })
+    }
+
+    /// 接続に対する実行結果を記録する
+    ///
+    /// 書き込みに失敗しても実行結果には影響させず、標準エラーに出力する
+    /// （監査ログが必須の環境では `ensure_recordable` で実行前に確認する）
+    pub fn record_execution(
+        &self,
+        connection: &ConnectionInfo,
+        operation: AuditOperation,
+        sql: &str,
+        risk_level: RiskLevel,
+        result: Result<Option<u64>, &str>,
+    ) {
+        let (affected_rows, outcome, error_message) = match result {
+            Ok(affected_rows) => (affected_rows, AuditOutcome::Success, None),
+            Err(message) => (None, AuditOutcome::Error, Some(message.to_string())),
+        };
+        self.record(
+            connection,
+            operation,
+            sql,
+            risk_level,
+            affected_rows,
+            outcome,
+            error_message,
+        );
+    }
+
+    /// 安全設定で拒否した実行を記録する（`error` はコマンドが返すエラー文字列）
+    pub fn record_rejection(
+        &self,
+        connection: &ConnectionInfo,
+        operation: AuditOperation,
+        sql: &str,
+        error: &str,
+    ) {
+        let risk_level =
+            QueryAnalyzer::analyze(sql, dialect_name(&connection.database_type)).risk_level;
+        let message = command_error_message(error);
+        self.record(
+            connection,
+            operation,
+            sql,
+            risk_level,
+            None,
+            AuditOutcome::Rejected,
+            Some(message),
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn record(
+        &self,
+        connection: &ConnectionInfo,
+        operation: AuditOperation,
+        sql: &str,
+        risk_level: RiskLevel,
+        affected_rows: Option<u64>,
+        outcome: AuditOutcome,
+        error_message: Option<String>,
+    ) {
+        let result = self.append(AuditRecord {
+            connection_id: connection.id.clone(),
+            connection_name: connection.name.clone(),
+            environment: connection.environment.environment_type,
+            operation,
+            sql: sql.to_string(),
+            risk_level,
+            affected_rows,
+            outcome,
+            error_message,
+        });
+        if let Err(e) = result {
+            eprintln!("Failed to write audit log: {}", e);
+        }
+    }
+
+    /// エントリを追記する
+    pub fn append(&self, record: AuditRecord) -> Result<AuditLogEntry, String> {
+        let key = self.key()?;
+        let mut last = self.last.lock().map_err(|e| e.to_string())?;
+        self.load_last(&key, &mut last)?;
+        let (prev_sequence, prev_hash) = last
+            .clone()
+            .unwrap_or_else(|| (0, GENESIS_HASH.to_string()));
+
+        let now = Utc::now();
+        let mut entry = AuditLogEntry {
+            sequence: prev_sequence + 1,
+            timestamp: now.to_rfc3339(),
+            connection_id: record.connection_id,
+            connection_name: record.connection_name,
+            environment: record.environment,
+            operation: record.operation,
+            sql: record.sql,
+            risk_level: record.risk_level,
+            affected_rows: record.affected_rows,
+            outcome: record.outcome,
+            error_message: record.error_message,
+            os_user: whoami::username(),
+            prev_hash,
+            hash: String::new(),
+        };
+        entry.hash = compute_hash(&key, &entry)?;
+
+        let mut file = self.open_log_file(now.date_naive())?;
+        let json = serde_json::to_string(&entry).map_err(|e| e.to_string())?;
+        writeln!(file, "{}", json).map_err(|e| e.to_string())?;
+        file.sync_data().map_err(|e| e.to_string())?;
+
+        *last = Some((entry.sequence, entry.hash.clone()));
+        self.write_head(&key, entry.sequence, &entry.hash)?;
+        Ok(entry)
+    }
+
+    /// 条件に一致するエントリを新しい順に返す
+    pub fn search(&self, request: &SearchAuditLogRequest) -> Result<Vec<AuditLogEntry>, String> {
+        let from = parse_date(request.from.as_deref())?;
+        let to = parse_date(request.to.as_deref())?;
+        let keyword = request.keyword.as_ref().map(|k| k.to_lowercase());
+
+        let mut entries = Vec::new();
+        for (date, path) in self.log_files()? {
+            if from.is_some_and(|from| date < from) || to.is_some_and(|to| date > to) {
+                continue;
+            }
+
+            for line in read_lines(&path)? {
+                let Ok(entry) = serde_json::from_str::<AuditLogEntry>(&line) else {
+                    continue;
+                };
+                if request
+                    .connection_id
+                    .as_ref()
+                    .is_some_and(|id| &entry.connection_id != id)
+                {
+                    continue;
+                }
+                if request
+                    .environment
+                    .is_some_and(|environment| entry.environment != environment)
+                {
+                    continue;
+                }
+                if request
+                    .outcome
+                    .is_some_and(|outcome| entry.outcome != outcome)
+                {
+                    continue;
+                }
+                if let Some(ref keyword) = keyword {
+                    if !entry.sql.to_lowercase().contains(keyword) {
+                        continue;
+                    }
+                }
+                entries.push(entry);
+            }
+        }
+
+        entries.reverse();
+        if let Some(limit) = request.limit {
+            entries.truncate(limit);
+        }
+
+        Ok(entries)
+    }
+
+    /// 条件に一致するエントリを古い順にCSV/JSONへ書き出す
+    pub fn export(
+        &self,
+        request: &SearchAuditLogRequest,
+        options: &ExportOptions,
+    ) -> Result<ExportResult, String> {
+        let mut entries = self.search(request)?;
+        entries.reverse();
+
+        match options.format {
+            ExportFormat::Csv => export_csv(&entries, &options.path)?,
+            ExportFormat::Json => {
+                let file = File::create(&options.path).map_err(|e| e.to_string())?;
+                serde_json::to_writer_pretty(file, &entries).map_err(|e| e.to_string())?;
+            }
+            ExportFormat::Excel => {
+                return Err("Audit logs can only be exported as CSV or JSON".to_string());
+            }
+        }
+
+        Ok(ExportResult {
+            success: true,
+            message: None,
+            rows_affected: entries.len(),
+        })
+    }
+
+    /// 全ファイルのハッシュチェーンを検証し、最新エントリの記録と照合する
+    pub fn verify(&self) -> Result<AuditLogVerification, String> {
+        let key = self.key()?;
+        let mut prev_sequence = 0;
+        let mut prev_hash = GENESIS_HASH.to_string();
+        let mut checked_entries = 0;
+
+        for (_, path) in self.log_files()? {
+            let file_name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+
+            for (index, line) in read_lines(&path)?.into_iter().enumerate() {
+                let invalid = |message: &str| AuditLogVerification {
+                    valid: false,
+                    checked_entries,
+                    invalid_file: Some(file_name.clone()),
+                    invalid_line: Some(index + 1),
+                    message: Some(message.to_string()),
+                };
+
+                let Ok(entry) = serde_json::from_str::<AuditLogEntry>(&line) else {
+                    return Ok(invalid("Entry could not be parsed"));
+                };
+                if entry.sequence != prev_sequence + 1 {
+                    return Ok(invalid("Sequence number is not contiguous"));
+                }
+                if entry.prev_hash != prev_hash {
+                    return Ok(invalid("Entry does not chain to the previous entry"));
+                }
+                if compute_hash(&key, &entry)? != entry.hash {
+                    return Ok(invalid("Entry hash does not match its contents"));
+                }
+
+                prev_sequence = entry.sequence;
+                prev_hash = entry.hash;
+                checked_entries += 1;
+            }
+        }
+
+        // 末尾のエントリやファイルの削除はチェーンからは分からないため、最新エントリの記録と照合する
+        let head_invalid = |message: &str| AuditLogVerification {
+            valid: false,
+            checked_entries,
+            invalid_file: Some(HEAD_FILE.to_string()),
+            invalid_line: None,
+            message: Some(message.to_string()),
+        };
+        match self.read_head() {
+            Ok(None) if checked_entries > 0 => {
+                return Ok(head_invalid("The record of the latest entry is missing"));
+            }
+            Ok(None) => {}
+            Ok(Some(head)) if !head.is_signed_by(&key) => {
+                return Ok(head_invalid(
+                    "The record of the latest entry has been modified",
+                ));
+            }
+            Ok(Some(head)) if head.sequence != prev_sequence || head.hash != prev_hash => {
+                return Ok(head_invalid(
+                    "The log does not end at the latest recorded entry; entries may have been deleted",
+                ));
+            }
+            Ok(Some(_)) => {}
+            Err(_) => {
+                return Ok(head_invalid(
+                    "The record of the latest entry could not be read",
+                ));
+            }
+        }
+
+        Ok(AuditLogVerification {
+            valid: true,
+            checked_entries,
+            invalid_file: None,
+            invalid_line: None,
+            message: None,
+        })
+    }
+
+    fn file_path(&self, date: NaiveDate) -> PathBuf {
+        self.dir.join(format!(
+            "{}{}{}",
+            FILE_PREFIX,
+            date.format("%Y-%m-%d"),
+            FILE_EXTENSION
+        ))
+    }
+
+    /// ログファイルを日付の古い順に返す
+    fn log_files(&self) -> Result<Vec<(NaiveDate, PathBuf)>, String> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut files = Vec::new();
+        for dir_entry in fs::read_dir(&self.dir).map_err(|e| e.to_string())? {
+            let path = dir_entry.map_err(|e| e.to_string())?.path();
+            let date = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_prefix(FILE_PREFIX))
+                .and_then(|name| name.strip_suffix(FILE_EXTENSION))
+                .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok());
+            if let Some(date) = date {
+                files.push((date, path));
+            }
+        }
+        files.sort();
+
+        Ok(files)
+    }
+
+    /// 追記できる状態か確認する（鍵と最新エントリを読み込み、今日のログファイルを開く）
+    fn prepare(&self) -> Result<(), String> {
+        let key = self.key()?;
+        let mut last = self.last.lock().map_err(|e| e.to_string())?;
+        self.load_last(&key, &mut last)?;
+        self.open_log_file(Utc::now().date_naive())?;
+        Ok(())
+    }
+
+    /// 最後に書き込んだエントリをまだ読み込んでいなければ、ログと最新エントリの記録から読み込む
+    fn load_last(&self, key: &MasterKey, last: &mut Option<(u64, String)>) -> Result<(), String> {
+        if last.is_some() {
+            return Ok(());
+        }
+        let last_entry = self.read_last_entry()?;
+        // 末尾が削除されていても記録済みの最新エントリから続け、検証でチェーンの切れ目が分かるようにする
+        let head = self
+            .read_head()?
+            .filter(|head| head.is_signed_by(key))
+            .map(|head| (head.sequence, head.hash));
+        *last = match (last_entry, head) {
+            (Some(entry), Some(head)) => Some(if head.0 > entry.0 { head } else { entry }),
+            (entry, head) => entry.or(head),
+        };
+        Ok(())
+    }
+
+    /// 最新のファイルから最後に読めるエントリを読み込む
+    ///
+    /// 書き込み途中で切れた行などは飛ばす（検証でチェーンの切れ目として検出される）
+    fn read_last_entry(&self) -> Result<Option<(u64, String)>, String> {
+        for (_, path) in self.log_files()?.into_iter().rev() {
+            let last_entry = read_lines(&path)?
+                .iter()
+                .rev()
+                .find_map(|line| serde_json::from_str::<AuditLogEntry>(line).ok());
+            if let Some(entry) = last_entry {
+                return Ok(Some((entry.sequence, entry.hash)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// 日付のログファイルを追記用に開く
+    ///
+    /// 最後の行が改行で終わっていない（書き込み途中で切れた）場合は、
+    /// 次のエントリが同じ行につながらないよう改行を補う
+    fn open_log_file(&self, date: NaiveDate) -> Result<File, String> {
+        fs::create_dir_all(&self.dir).map_err(|e| e.to_string())?;
+        let path = self.file_path(date);
+        let mut file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| e.to_string())?;
+        set_secure_permissions(&path)?;
+
+        if file.metadata().map_err(|e| e.to_string())?.len() > 0 {
+            let mut last_byte = [0u8; 1];
+            file.seek(SeekFrom::End(-1)).map_err(|e| e.to_string())?;
+            file.read_exact(&mut last_byte).map_err(|e| e.to_string())?;
+            if last_byte[0] != b'\n' {
+                file.write_all(b"\n").map_err(|e| e.to_string())?;
+            }
+        }
+        Ok(file)
+    }
+
+    /// キーチェーンから署名鍵を読み込む（なければ生成して保存する）
+    fn key(&self) -> Result<MasterKey, String> {
+        let mut key = self.key.lock().map_err(|e| e.to_string())?;
+        if let Some(key) = key.as_ref() {
+            return Ok(key.clone());
+        }
+
+        let entry = KeychainEntry::audit_log_key();
+        let loaded = if self.keychain.exists(&entry) {
+            self.keychain
+                .retrieve_key(&entry)
+                .map_err(|e| format!("Failed to load the audit log key: {}", e))?
+        } else {
+            let generated = MasterKey::generate();
+            self.keychain
+                .store_key(&entry, &generated)
+                .map_err(|e| format!("Failed to store the audit log key: {}", e))?;
+            generated
+        };
+        *key = Some(loaded.clone());
+        Ok(loaded)
+    }
+
+    fn read_head(&self) -> Result<Option<AuditLogHead>, String> {
+        let path = self.dir.join(HEAD_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+        serde_json::from_str(&content)
+            .map(Some)
+            .map_err(|e| e.to_string())
+    }
+
+    /// 最新エントリの記録を置き換える（書き込み途中で壊れないよう一時ファイルから移動する）
+    fn write_head(&self, key: &MasterKey, sequence: u64, hash: &str) -> Result<(), String> {
+        let head = AuditLogHead {
+            sequence,
+            hash: hash.to_string(),
+            mac: head_mac(key, sequence, hash),
+        };
+        let path = self.dir.join(HEAD_FILE);
+        let temp_path = self.dir.join(format!("{}.tmp", HEAD_FILE));
+        let json = serde_json::to_string(&head).map_err(|e| e.to_string())?;
+        fs::write(&temp_path, json).map_err(|e| e.to_string())?;
+        set_secure_permissions(&temp_path)?;
+        fs::rename(&temp_path, &path).map_err(|e| e.to_string())
+    }
+}
+
+impl AuditLogHead {
+    fn is_signed_by(&self, key: &MasterKey) -> bool {
+        head_mac(key, self.sequence, &self.hash) == self.mac
+    }
+}
+
+/// `hash` を空にしたエントリのJSONのHMACを計算する（`prev_hash` もJSONに含まれる）
+fn compute_hash(key: &MasterKey, entry: &AuditLogEntry) -> Result<String, String> {
+    let unsigned = AuditLogEntry {
+        hash: String::new(),
+        ..entry.clone()
+    };
+    let json = serde_json::to_string(&unsigned).map_err(|e| e.to_string())?;
+    Ok(hmac_hex(key, json.as_bytes()))
+}
+
+fn head_mac(key: &MasterKey, sequence: u64, hash: &str) -> String {
+    hmac_hex(key, format!("{}\n{}", sequence, hash).as_bytes())
+}
+
+fn hmac_hex(key: &MasterKey, data: &[u8]) -> String {
+    let mut mac = HmacSha256::new_from_slice(key.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(data);
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// コマンドのエラー文字列（シリアライズした QueryError またはメッセージ）からメッセージを取り出す
+pub fn command_error_message(error: &str) -> String {
+    serde_json::from_str::<QueryError>(error)
+        .map(|e| e.message)
+        .unwrap_or_else(|_| error.to_string())
+}
+
+fn parse_date(date: Option<&str>) -> Result<Option<NaiveDate>, String> {
+    date.map(|date| {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|_| format!("Invalid date (expected YYYY-MM-DD): {}", date))
+    })
+    .transpose()
+}
+
+fn read_lines(path: &Path) -> Result<Vec<String>, String> {
+    let file = File::open(path).map_err(|e| e.to_string())?;
+    BufReader::new(file)
+        .lines()
+        .filter(|line| !line.as_ref().is_ok_and(|line| line.trim().is_empty()))
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.to_string())
+}
+
+fn export_csv(entries: &[AuditLogEntry], path: &Path) -> Result<(), String> {
+    let file = File::create(path).map_err(|e| e.to_string())?;
+    let mut wtr = csv::WriterBuilder::new()
+        .quote_style(csv::QuoteStyle::Necessary)
+        .from_writer(file);
+
+    wtr.write_record([
+        "sequence",
+        "timestamp",
+        "connection_id",
+        "connection_name",
+        "environment",
+        "operation",
+        "sql",
+        "risk_level",
+        "affected_rows",
+        "outcome",
+        "error_message",
+        "os_user",
+        "prev_hash",
+        "hash",
+    ])
+    .map_err(|e| e.to_string())?;
+
+    for entry in entries {
+        wtr.write_record([
+            entry.sequence.to_string(),
+            entry.timestamp.clone(),
+            entry.connection_id.clone(),
+            entry.connection_name.clone(),
+            enum_name(&entry.environment)?,
+            enum_name(&entry.operation)?,
+            entry.sql.clone(),
+            enum_name(&entry.risk_level)?,
+            entry
+                .affected_rows
+                .map(|rows| rows.to_string())
+                .unwrap_or_default(),
+            enum_name(&entry.outcome)?,
+            entry.error_message.clone().unwrap_or_default(),
+            entry.os_user.clone(),
+            entry.prev_hash.clone(),
+            entry.hash.clone(),
+        ])
+        .map_err(|e| e.to_string())?;
+    }
+
+    wtr.flush().map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// シリアライズ時の名前（`production` など）
+fn enum_name<T: serde::Serialize>(value: &T) -> Result<String, String> {
+    match serde_json::to_value(value).map_err(|e| e.to_string())? {
+        serde_json::Value::String(name) => Ok(name),
+        other => Ok(other.to_string()),
+    }
+}
+
+#[cfg(unix)]
+fn set_secure_permissions(path: &Path) -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut permissions = fs::metadata(path).map_err(|e| e.to_string())?.permissions();
+    permissions.set_mode(0o600);
+    fs::set_permissions(path, permissions).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn set_secure_permissions(_path: &Path) -> Result<(), String> {
+    Ok(())
+}
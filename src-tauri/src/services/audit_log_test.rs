@@ -0,0 +1,322 @@
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use tempfile::TempDir;
+
+use crate::connection::EnvironmentType;
+use crate::crypto::master_key::keychain::tests::MockKeychain;
+use crate::models::audit_log::{AuditOperation, AuditOutcome, AuditRecord, SearchAuditLogRequest};
+use crate::models::export::{ExportFormat, ExportOptions};
+use crate::models::query_analysis::RiskLevel;
+
+use super::audit_log::AuditLogService;
+
+fn record(connection_id: &str, environment: EnvironmentType, sql: &str) -> AuditRecord {
+    AuditRecord {
+        connection_id: connection_id.to_string(),
+        connection_name: format!("{} name", connection_id),
+        environment,
+        operation: AuditOperation::Mutation,
+        sql: sql.to_string(),
+        risk_level: RiskLevel::Warning,
+        affected_rows: Some(1),
+        outcome: AuditOutcome::Success,
+        error_message: None,
+    }
+}
+
+fn new_service(dir: &Path) -> AuditLogService {
+    AuditLogService::with_keychain(dir.to_path_buf(), Arc::new(MockKeychain::new()))
+}
+
+fn log_file(dir: &Path) -> std::path::PathBuf {
+    fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|path| path.to_string_lossy().ends_with(".jsonl"))
+        .unwrap()
+}
+
+#[test]
+fn test_append_chains_entries() {
+    let temp_dir = TempDir::new().unwrap();
+    let keychain = Arc::new(MockKeychain::new());
+    let service = AuditLogService::with_keychain(temp_dir.path().to_path_buf(), keychain.clone());
+
+    let first = service
+        .append(record(
+            "conn-1",
+            EnvironmentType::Production,
+            "DELETE FROM a WHERE id = 1",
+        ))
+        .unwrap();
+    let second = service
+        .append(record(
+            "conn-1",
+            EnvironmentType::Production,
+            "DELETE FROM a WHERE id = 2",
+        ))
+        .unwrap();
+
+    assert_eq!(first.sequence, 1);
+    assert_eq!(first.prev_hash, "0".repeat(64));
+    assert_eq!(second.sequence, 2);
+    assert_eq!(second.prev_hash, first.hash);
+    assert!(!second.os_user.is_empty());
+
+    // 再起動後もファイルの最後のエントリからチェーンを続ける
+    let reopened = AuditLogService::with_keychain(temp_dir.path().to_path_buf(), keychain);
+    let third = reopened
+        .append(record("conn-1", EnvironmentType::Production, "SELECT 1"))
+        .unwrap();
+    assert_eq!(third.sequence, 3);
+    assert_eq!(third.prev_hash, second.hash);
+
+    let verification = reopened.verify().unwrap();
+    assert!(verification.valid);
+    assert_eq!(verification.checked_entries, 3);
+
+    // 別の鍵ではハッシュを再計算できない
+    let verification = new_service(temp_dir.path()).verify().unwrap();
+    assert!(!verification.valid);
+    assert_eq!(verification.invalid_line, Some(1));
+}
+
+#[test]
+fn test_verify_detects_tampering() {
+    let temp_dir = TempDir::new().unwrap();
+    let service = new_service(temp_dir.path());
+    for id in 1..=3 {
+        service
+            .append(record(
+                "conn-1",
+                EnvironmentType::Production,
+                &format!("DELETE FROM a WHERE id = {}", id),
+            ))
+            .unwrap();
+    }
+
+    // 2件目のSQLを書き換える
+    let path = log_file(temp_dir.path());
+    let content = fs::read_to_string(&path).unwrap();
+    fs::write(&path, content.replace("id = 2", "id = 20")).unwrap();
+
+    let verification = service.verify().unwrap();
+    assert!(!verification.valid);
+    assert_eq!(verification.checked_entries, 1);
+    assert_eq!(verification.invalid_line, Some(2));
+
+    // 1件目を削除するとチェーンの先頭が合わない
+    let lines: Vec<&str> = content.lines().skip(1).collect();
+    fs::write(&path, lines.join("\n")).unwrap();
+
+    let verification = service.verify().unwrap();
+    assert!(!verification.valid);
+    assert_eq!(verification.invalid_line, Some(1));
+}
+
+#[test]
+fn test_verify_detects_deleted_tail() {
+    let temp_dir = TempDir::new().unwrap();
+    let service = new_service(temp_dir.path());
+    for id in 1..=3 {
+        service
+            .append(record(
+                "conn-1",
+                EnvironmentType::Production,
+                &format!("DELETE FROM a WHERE id = {}", id),
+            ))
+            .unwrap();
+    }
+
+    // 最後のエントリを削除してもチェーンは切れないが、最新エントリの記録と合わない
+    let path = log_file(temp_dir.path());
+    let content = fs::read_to_string(&path).unwrap();
+    let lines: Vec<&str> = content.lines().take(2).collect();
+    fs::write(&path, lines.join("\n")).unwrap();
+
+    let verification = service.verify().unwrap();
+    assert!(!verification.valid);
+    assert_eq!(verification.checked_entries, 2);
+    assert_eq!(verification.invalid_file.as_deref(), Some("head.json"));
+
+    // 削除後に追記しても通し番号は記録済みの最新エントリから続く
+    let entry = service
+        .append(record("conn-1", EnvironmentType::Production, "SELECT 1"))
+        .unwrap();
+    assert_eq!(entry.sequence, 4);
+    assert!(!service.verify().unwrap().valid);
+
+    // ファイルごと削除しても検出する
+    fs::remove_file(&path).unwrap();
+    let verification = service.verify().unwrap();
+    assert!(!verification.valid);
+    assert_eq!(verification.invalid_file.as_deref(), Some("head.json"));
+}
+
+#[test]
+fn test_append_after_truncated_entry() {
+    let temp_dir = TempDir::new().unwrap();
+    let keychain = Arc::new(MockKeychain::new());
+    let service = AuditLogService::with_keychain(temp_dir.path().to_path_buf(), keychain.clone());
+    service
+        .append(record("conn-1", EnvironmentType::Production, "SELECT 1"))
+        .unwrap();
+
+    // 書き込み途中で切れた行（改行なし）が末尾に残っている
+    let path = log_file(temp_dir.path());
+    let mut content = fs::read_to_string(&path).unwrap();
+    content.push_str("{\"sequence\": 2, \"trunc");
+    fs::write(&path, content).unwrap();
+
+    // 切れた行を飛ばして最新エントリから続け、別の行に追記する
+    let reopened = AuditLogService::with_keychain(temp_dir.path().to_path_buf(), keychain);
+    let entry = reopened
+        .append(record("conn-1", EnvironmentType::Production, "SELECT 2"))
+        .unwrap();
+    assert_eq!(entry.sequence, 2);
+
+    let content = fs::read_to_string(&path).unwrap();
+    let lines: Vec<&str> = content.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[1], "{\"sequence\": 2, \"trunc");
+    assert!(lines[2].contains("SELECT 2"));
+
+    // 切れた行はチェーンの切れ目として検出される
+    let verification = reopened.verify().unwrap();
+    assert!(!verification.valid);
+    assert_eq!(verification.checked_entries, 1);
+    assert_eq!(verification.invalid_line, Some(2));
+}
+
+#[test]
+fn test_search_filters() {
+    let temp_dir = TempDir::new().unwrap();
+    let service = new_service(temp_dir.path());
+    service
+        .append(record(
+            "conn-1",
+            EnvironmentType::Production,
+            "DELETE FROM users",
+        ))
+        .unwrap();
+    service
+        .append(record(
+            "conn-2",
+            EnvironmentType::Development,
+            "UPDATE orders SET x = 1",
+        ))
+        .unwrap();
+    service
+        .append(AuditRecord {
+            outcome: AuditOutcome::Error,
+            error_message: Some("syntax error".to_string()),
+            affected_rows: None,
+            ..record("conn-1", EnvironmentType::Production, "DELETE FROM orders")
+        })
+        .unwrap();
+
+    let all = service.search(&SearchAuditLogRequest::default()).unwrap();
+    assert_eq!(all.len(), 3);
+    assert_eq!(all[0].sql, "DELETE FROM orders");
+
+    let production = service
+        .search(&SearchAuditLogRequest {
+            environment: Some(EnvironmentType::Production),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(production.len(), 2);
+
+    let errors = service
+        .search(&SearchAuditLogRequest {
+            outcome: Some(AuditOutcome::Error),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].error_message.as_deref(), Some("syntax error"));
+
+    let keyword = service
+        .search(&SearchAuditLogRequest {
+            connection_id: Some("conn-1".to_string()),
+            keyword: Some("users".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(keyword.len(), 1);
+
+    let tomorrow = (chrono::Utc::now() + chrono::Duration::days(1))
+        .format("%Y-%m-%d")
+        .to_string();
+    let future = service
+        .search(&SearchAuditLogRequest {
+            from: Some(tomorrow),
+            ..Default::default()
+        })
+        .unwrap();
+    assert!(future.is_empty());
+
+    assert!(service
+        .search(&SearchAuditLogRequest {
+            from: Some("2024/01/01".to_string()),
+            ..Default::default()
+        })
+        .is_err());
+}
+
+#[test]
+fn test_export() {
+    let temp_dir = TempDir::new().unwrap();
+    let service = new_service(&temp_dir.path().join("audit"));
+    service
+        .append(record(
+            "conn-1",
+            EnvironmentType::Production,
+            "DELETE FROM users",
+        ))
+        .unwrap();
+    service
+        .append(record(
+            "conn-1",
+            EnvironmentType::Production,
+            "DELETE FROM orders",
+        ))
+        .unwrap();
+
+    let csv_path = temp_dir.path().join("audit.csv");
+    let result = service
+        .export(
+            &SearchAuditLogRequest::default(),
+            &ExportOptions {
+                path: csv_path.clone(),
+                format: ExportFormat::Csv,
+            },
+        )
+        .unwrap();
+    assert_eq!(result.rows_affected, 2);
+
+    let csv = fs::read_to_string(&csv_path).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("sequence,timestamp"));
+    // 古い順に書き出す
+    assert!(lines[1].starts_with("1,"));
+    assert!(lines[1].contains(",production,mutation,DELETE FROM users,warning,1,success,"));
+
+    let json_path = temp_dir.path().join("audit.json");
+    service
+        .export(
+            &SearchAuditLogRequest::default(),
+            &ExportOptions {
+                path: json_path.clone(),
+                format: ExportFormat::Json,
+            },
+        )
+        .unwrap();
+    let json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&json_path).unwrap()).unwrap();
+    assert_eq!(json[1]["sql"], "DELETE FROM orders");
+    assert_eq!(json[1]["environment"], "production");
+}
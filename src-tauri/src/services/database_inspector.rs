@@ -2,6 +2,11 @@ use crate::connection::ConnectionInfo;
 use crate::models::database_structure::*;
 use async_trait::async_trait;
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// テーブル情報をキャッシュする期間
+pub const TABLE_METADATA_CACHE_TTL: Duration = Duration::from_secs(300);
 
 /// テーブルに紐づく外部キー情報
 #[derive(Debug, Clone)]
@@ -73,7 +78,8 @@ impl DatabaseInspectorFactory {
                     PostgresqlInspector::new(connection, password).await?,
                 ))
             }
-            crate::connection::DatabaseType::MySQL => {
+            // MariaDBはMySQLのインスペクターで差異を吸収する
+            crate::connection::DatabaseType::MySQL | crate::connection::DatabaseType::MariaDB => {
                 use crate::database::mysql_inspector::MysqlInspector;
                 Ok(Box::new(MysqlInspector::new(connection, password).await?))
             }
@@ -81,6 +87,77 @@ impl DatabaseInspectorFactory {
                 use crate::database::sqlite_inspector::SqliteInspector;
                 Ok(Box::new(SqliteInspector::new(connection).await?))
             }
+            crate::connection::DatabaseType::DuckDB => {
+                use crate::database::duckdb_inspector::DuckDbInspector;
+                Ok(Box::new(DuckDbInspector::new(connection).await?))
+            }
+            crate::connection::DatabaseType::SqlServer => {
+                use crate::database::mssql_inspector::MssqlInspector;
+                Ok(Box::new(MssqlInspector::new(connection, password).await?))
+            }
+        }
+    }
+}
+
+/// 取得した時刻とテーブル一覧
+type CachedTables = (Instant, Arc<Vec<Table>>);
+
+/// 接続・スキーマごとのテーブル情報（主キー・インデックスを含む）のキャッシュ
+///
+/// データ変更のたびにインスペクターの接続（SSHトンネルを含む）を作り直さないよう、一定期間保持する
+pub struct TableMetadataCache {
+    entries: Mutex<HashMap<(String, String), CachedTables>>,
+    ttl: Duration,
+}
+
+impl TableMetadataCache {
+    pub fn new() -> Self {
+        Self::with_ttl(TABLE_METADATA_CACHE_TTL)
+    }
+
+    /// 保持期間を指定して作成
+    pub fn with_ttl(ttl: Duration) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            ttl,
         }
     }
+
+    /// キャッシュから取得（期限切れなら削除してNoneを返す）
+    pub fn get(&self, connection_id: &str, schema: &str) -> Option<Arc<Vec<Table>>> {
+        let mut entries = self.entries.lock().unwrap();
+        let key = (connection_id.to_string(), schema.to_string());
+        match entries.get(&key) {
+            Some((cached_at, tables)) if cached_at.elapsed() < self.ttl => Some(Arc::clone(tables)),
+            Some(_) => {
+                entries.remove(&key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// キャッシュに保存
+    pub fn insert(&self, connection_id: &str, schema: &str, tables: Vec<Table>) -> Arc<Vec<Table>> {
+        let tables = Arc::new(tables);
+        self.entries.lock().unwrap().insert(
+            (connection_id.to_string(), schema.to_string()),
+            (Instant::now(), Arc::clone(&tables)),
+        );
+        tables
+    }
+
+    /// 接続のキャッシュを破棄
+    pub fn invalidate(&self, connection_id: &str) {
+        self.entries
+            .lock()
+            .unwrap()
+            .retain(|(cached_connection_id, _), _| cached_connection_id != connection_id);
+    }
+}
+
+impl Default for TableMetadataCache {
+    fn default() -> Self {
+        Self::new()
+    }
 }
@@ -1,6 +1,8 @@
 use crate::connection::{
     ConnectionConfig, ConnectionInfo, DatabaseType, FileConfig, NetworkConfig,
 };
+use crate::models::database_structure::Table;
+use crate::services::database_inspector::TableMetadataCache;
 
 #[test]
 fn test_inspector_factory_requires_valid_connection_type() {
@@ -15,6 +17,7 @@ fn test_inspector_factory_requires_valid_connection_type() {
             username: "testuser".to_string(),
             encrypted_password: None,
             ssl_config: None,
+            ssh_tunnel: None,
             options: None,
         }),
     );
@@ -38,6 +41,7 @@ fn test_inspector_factory_requires_valid_connection_type() {
             username: "testuser".to_string(),
             encrypted_password: None,
             ssl_config: None,
+            ssh_tunnel: None,
             options: None,
         }),
     );
@@ -79,6 +83,7 @@ fn test_connection_info_for_postgresql() {
             username: "pguser".to_string(),
             encrypted_password: Some("encrypted_password_here".to_string()),
             ssl_config: None,
+            ssh_tunnel: None,
             options: None,
         }),
     );
@@ -109,6 +114,7 @@ fn test_connection_info_for_mysql() {
             username: "mysqluser".to_string(),
             encrypted_password: None,
             ssl_config: None,
+            ssh_tunnel: None,
             options: None,
         }),
     );
@@ -178,11 +184,14 @@ fn test_connection_string_building() {
             username: "user".to_string(),
             encrypted_password: None,
             ssl_config: None,
+            ssh_tunnel: None,
             options: None,
         }),
     );
 
-    let conn_str = pg_conn.build_connection_string(Some("password")).unwrap();
+    let conn_str = pg_conn.build_connection_string().unwrap();
+    // パスワードは接続文字列に含めない
+    assert!(!conn_str.contains("password"));
     assert!(conn_str.contains("postgresql://"));
     assert!(conn_str.contains("localhost:5432"));
     assert!(conn_str.contains("testdb"));
@@ -198,13 +207,12 @@ fn test_connection_string_building() {
             username: "user".to_string(),
             encrypted_password: None,
             ssl_config: None,
+            ssh_tunnel: None,
             options: None,
         }),
     );
 
-    let conn_str = mysql_conn
-        .build_connection_string(Some("password"))
-        .unwrap();
+    let conn_str = mysql_conn.build_connection_string().unwrap();
     assert!(conn_str.contains("mysql://"));
     assert!(conn_str.contains("localhost:3306"));
 
@@ -218,7 +226,7 @@ fn test_connection_string_building() {
         }),
     );
 
-    let conn_str = sqlite_conn.build_connection_string(None).unwrap();
+    let conn_str = sqlite_conn.build_connection_string().unwrap();
     assert!(conn_str.contains("file:/tmp/test.db"));
 }
 
@@ -234,6 +242,7 @@ fn test_password_handling() {
             username: "admin".to_string(),
             encrypted_password: Some("encrypted_pass_123".to_string()),
             ssl_config: None,
+            ssh_tunnel: None,
             options: None,
         }),
     );
@@ -256,6 +265,7 @@ fn test_password_handling() {
             username: "guest".to_string(),
             encrypted_password: None,
             ssl_config: None,
+            ssh_tunnel: None,
             options: None,
         }),
     );
@@ -264,3 +274,41 @@ fn test_password_handling() {
         assert!(cfg.encrypted_password.is_none());
     }
 }
+
+fn table(name: &str) -> Table {
+    Table {
+        name: name.to_string(),
+        schema: "public".to_string(),
+        comment: None,
+        estimated_row_count: None,
+        columns: vec![],
+        primary_key: None,
+        indexes: vec![],
+        foreign_keys: vec![],
+        referenced_by: vec![],
+        is_system_versioned: false,
+    }
+}
+
+#[test]
+fn test_table_metadata_cache() {
+    let cache = TableMetadataCache::new();
+    assert!(cache.get("conn-1", "public").is_none());
+
+    cache.insert("conn-1", "public", vec![table("users")]);
+    cache.insert("conn-1", "audit", vec![table("events")]);
+    cache.insert("conn-2", "public", vec![table("orders")]);
+    assert_eq!(cache.get("conn-1", "public").unwrap()[0].name, "users");
+    assert!(cache.get("conn-1", "other").is_none());
+
+    // 接続のキャッシュはスキーマをまとめて破棄し、他の接続には影響しない
+    cache.invalidate("conn-1");
+    assert!(cache.get("conn-1", "public").is_none());
+    assert!(cache.get("conn-1", "audit").is_none());
+    assert_eq!(cache.get("conn-2", "public").unwrap()[0].name, "orders");
+
+    // 期限切れのキャッシュは返さない
+    let expired = TableMetadataCache::with_ttl(std::time::Duration::ZERO);
+    expired.insert("conn-1", "public", vec![table("users")]);
+    assert!(expired.get("conn-1", "public").is_none());
+}
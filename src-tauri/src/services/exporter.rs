@@ -48,6 +48,9 @@ fn export_to_excel(data: &QueryResult, path: &Path) -> Result<ExportResult, Stri
 
     // Write headers
     let header_format = rust_xlsxwriter::Format::new().set_bold();
+    let date_format = rust_xlsxwriter::Format::new().set_num_format("yyyy-mm-dd");
+    let time_format = rust_xlsxwriter::Format::new().set_num_format("hh:mm:ss");
+    let datetime_format = rust_xlsxwriter::Format::new().set_num_format("yyyy-mm-dd hh:mm:ss");
     for (i, col) in data.columns.iter().enumerate() {
         worksheet
             .write_string_with_format(0, i as u16, &col.name, &header_format)
@@ -95,6 +98,61 @@ fn export_to_excel(data: &QueryResult, path: &Path) -> Result<ExportResult, Stri
                         .write_string(row_num, col_num, &s)
                         .map_err(|e| e.to_string())?;
                 }
+                QueryValue::Decimal(d) => match d.parse::<f64>() {
+                    Ok(n) if n.is_finite() => {
+                        worksheet
+                            .write_number(row_num, col_num, n)
+                            .map_err(|e| e.to_string())?;
+                    }
+                    _ => {
+                        worksheet
+                            .write_string(row_num, col_num, d)
+                            .map_err(|e| e.to_string())?;
+                    }
+                },
+                QueryValue::Date(_)
+                | QueryValue::Time(_)
+                | QueryValue::Timestamp(_)
+                | QueryValue::TimestampTz(_) => {
+                    // Excel has no time zones, so timestamps are written as their local wall-clock time
+                    let (datetime, format) = match value {
+                        QueryValue::Date(d) => (excel_datetime(Some(*d), None), &date_format),
+                        QueryValue::Time(t) => (excel_datetime(None, Some(*t)), &time_format),
+                        QueryValue::Timestamp(t) => (
+                            excel_datetime(Some(t.date()), Some(t.time())),
+                            &datetime_format,
+                        ),
+                        QueryValue::TimestampTz(t) => {
+                            let local = t.naive_local();
+                            (
+                                excel_datetime(Some(local.date()), Some(local.time())),
+                                &datetime_format,
+                            )
+                        }
+                        _ => unreachable!(),
+                    };
+                    match datetime {
+                        Some(datetime) => {
+                            worksheet
+                                .write_datetime_with_format(row_num, col_num, &datetime, format)
+                                .map_err(|e| e.to_string())?;
+                        }
+                        // Out of Excel's date range (before 1900 etc.)
+                        None => {
+                            worksheet
+                                .write_string(row_num, col_num, value_to_string(value))
+                                .map_err(|e| e.to_string())?;
+                        }
+                    }
+                }
+                QueryValue::Json(_)
+                | QueryValue::Array(_)
+                | QueryValue::Uuid(_)
+                | QueryValue::Interval(_) => {
+                    worksheet
+                        .write_string(row_num, col_num, value_to_string(value))
+                        .map_err(|e| e.to_string())?;
+                }
             }
         }
     }
@@ -128,31 +186,7 @@ fn export_to_json(data: &QueryResult, path: &Path) -> Result<ExportResult, Strin
         let mut map = serde_json::Map::new();
         for (i, val) in row.values.iter().enumerate() {
             if let Some(col) = data.columns.get(i) {
-                // Convert QueryValue to serde_json::Value
-                let json_val = match val {
-                    QueryValue::Null => serde_json::Value::Null,
-                    QueryValue::Bool(b) => serde_json::Value::Bool(*b),
-                    QueryValue::Int(i) => serde_json::Value::Number(serde_json::Number::from(*i)),
-                    QueryValue::Float(f) => {
-                        // Handle conversion to Number (f64 usually needs check for infinity/NaN)
-                        if let Some(n) = serde_json::Number::from_f64(*f) {
-                            serde_json::Value::Number(n)
-                        } else {
-                            serde_json::Value::Null // or string representation?
-                        }
-                    }
-                    QueryValue::String(s) => serde_json::Value::String(s.clone()),
-                    QueryValue::Bytes(b) => {
-                        // Convert bytes to hex string
-                        let hex_string = b
-                            .iter()
-                            .map(|byte| format!("{:02x}", byte))
-                            .collect::<Vec<_>>()
-                            .join("");
-                        serde_json::Value::String(format!("0x{}", hex_string))
-                    }
-                };
-                map.insert(col.name.clone(), json_val);
+                map.insert(col.name.clone(), value_to_json(val));
             }
         }
         json_rows.push(serde_json::Value::Object(map));
@@ -167,6 +201,58 @@ fn export_to_json(data: &QueryResult, path: &Path) -> Result<ExportResult, Strin
     })
 }
 
+/// Convert QueryValue to serde_json::Value (JSON columns and arrays stay nested)
+fn value_to_json(value: &QueryValue) -> serde_json::Value {
+    match value {
+        QueryValue::Null => serde_json::Value::Null,
+        QueryValue::Bool(b) => serde_json::Value::Bool(*b),
+        QueryValue::Int(i) => serde_json::Value::Number(serde_json::Number::from(*i)),
+        QueryValue::Float(f) => {
+            // Handle conversion to Number (f64 usually needs check for infinity/NaN)
+            if let Some(n) = serde_json::Number::from_f64(*f) {
+                serde_json::Value::Number(n)
+            } else {
+                serde_json::Value::Null // or string representation?
+            }
+        }
+        QueryValue::Json(v) => v.clone(),
+        QueryValue::Array(values) => {
+            serde_json::Value::Array(values.iter().map(value_to_json).collect())
+        }
+        // Decimals stay strings to keep their precision
+        _ => serde_json::Value::String(value_to_string(value)),
+    }
+}
+
+/// Convert a date and/or time to an Excel datetime (None when Excel cannot represent it)
+fn excel_datetime(
+    date: Option<chrono::NaiveDate>,
+    time: Option<chrono::NaiveTime>,
+) -> Option<rust_xlsxwriter::ExcelDateTime> {
+    use chrono::{Datelike, Timelike};
+
+    let datetime = match date {
+        Some(date) => rust_xlsxwriter::ExcelDateTime::from_ymd(
+            u16::try_from(date.year()).ok()?,
+            date.month() as u8,
+            date.day() as u8,
+        )
+        .ok()?,
+        None => rust_xlsxwriter::ExcelDateTime::default(),
+    };
+    match time {
+        Some(time) => datetime
+            .and_hms_milli(
+                time.hour() as u16,
+                time.minute() as u8,
+                time.second() as u8,
+                (time.nanosecond() / 1_000_000).min(999) as u16,
+            )
+            .ok(),
+        None => Some(datetime),
+    }
+}
+
 fn value_to_string(value: &QueryValue) -> String {
     match value {
         QueryValue::Null => "".to_string(),
@@ -183,6 +269,15 @@ fn value_to_string(value: &QueryValue) -> String {
                 .join("");
             format!("0x{}", hex_string)
         }
+        QueryValue::Decimal(d) => d.clone(),
+        QueryValue::Date(d) => d.to_string(),
+        QueryValue::Time(t) => t.to_string(),
+        QueryValue::Timestamp(t) => t.to_string(),
+        QueryValue::TimestampTz(t) => t.to_rfc3339(),
+        QueryValue::Json(v) => v.to_string(),
+        QueryValue::Array(_) => value_to_json(value).to_string(),
+        QueryValue::Uuid(u) => u.to_string(),
+        QueryValue::Interval(i) => i.to_string(),
     }
 }
 
@@ -380,4 +475,95 @@ mod tests {
 
         std::fs::remove_file(path).ok();
     }
+
+    fn create_typed_result() -> QueryResult {
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+        QueryResult {
+            columns: vec![
+                QueryResultColumn {
+                    name: "price".to_string(),
+                    data_type: "NUMERIC".to_string(),
+                    nullable: false,
+                },
+                QueryResultColumn {
+                    name: "created_on".to_string(),
+                    data_type: "DATE".to_string(),
+                    nullable: false,
+                },
+                QueryResultColumn {
+                    name: "updated_at".to_string(),
+                    data_type: "TIMESTAMP".to_string(),
+                    nullable: false,
+                },
+                QueryResultColumn {
+                    name: "payload".to_string(),
+                    data_type: "JSONB".to_string(),
+                    nullable: true,
+                },
+                QueryResultColumn {
+                    name: "tags".to_string(),
+                    data_type: "TEXT[]".to_string(),
+                    nullable: true,
+                },
+            ],
+            rows: vec![QueryResultRow {
+                values: vec![
+                    QueryValue::Decimal("19.99".to_string()),
+                    QueryValue::Date(date),
+                    QueryValue::Timestamp(date.and_hms_opt(12, 0, 0).unwrap()),
+                    QueryValue::Json(serde_json::json!({"items": [1, 2]})),
+                    QueryValue::Array(vec![QueryValue::String("a".to_string()), QueryValue::Null]),
+                ],
+            }],
+            row_count: 1,
+            execution_time_ms: 0,
+            warnings: vec![],
+        }
+    }
+
+    #[test]
+    fn test_json_export_typed_values() {
+        let result = create_typed_result();
+        let temp_dir = std::env::temp_dir();
+        let path = temp_dir.join("test_export_typed.json");
+
+        export_to_json(&result, &path).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+
+        // Decimals keep their precision as strings
+        assert_eq!(json[0]["price"], "19.99");
+        assert_eq!(json[0]["created_on"], "2024-03-15");
+        // JSON columns and arrays are nested, not stringified
+        assert_eq!(json[0]["payload"]["items"][1], 2);
+        assert_eq!(json[0]["tags"], serde_json::json!(["a", null]));
+
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_excel_export_typed_values() {
+        let result = create_typed_result();
+        let temp_dir = std::env::temp_dir();
+        let path = temp_dir.join("test_export_typed.xlsx");
+
+        assert!(export_to_excel(&result, &path).is_ok());
+        assert!(path.exists());
+
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_excel_datetime_conversion() {
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+        let time = chrono::NaiveTime::from_hms_milli_opt(12, 30, 15, 250).unwrap();
+
+        assert!(excel_datetime(Some(date), Some(time)).is_some());
+        assert!(excel_datetime(None, Some(time)).is_some());
+
+        // Excel cannot represent dates before 1900
+        let old = chrono::NaiveDate::from_ymd_opt(1850, 1, 1).unwrap();
+        assert!(excel_datetime(Some(old), None).is_none());
+    }
 }
@@ -192,6 +192,7 @@ mod tests {
             is_foreign_key: false,
             is_unique: false,
             is_auto_increment: false,
+            is_generated: false,
             ordinal_position: 1,
             comment: None,
         }
@@ -1,24 +1,38 @@
+pub mod audit_log;
 pub mod database_inspector;
 pub mod exporter;
 pub mod join_suggestion_engine;
 pub mod query_analyzer;
 pub mod query_executor;
 pub mod query_history;
+pub mod query_plan;
 pub mod query_storage;
 pub mod sql_editor_query_storage;
 pub mod sql_editor_history;
 pub mod safety_config;
+pub mod safety_guard;
+pub mod transaction_manager;
 pub mod window_manager;
 
 pub use database_inspector::*;
 pub use window_manager::WindowManager;
 
+#[cfg(test)]
+mod audit_log_test;
 #[cfg(test)]
 mod database_inspector_test;
 #[cfg(test)]
 mod query_analyzer_test;
 #[cfg(test)]
 mod safety_config_test;
+#[cfg(test)]
+mod safety_guard_test;
 
 #[cfg(test)]
 mod query_executor_test;
+
+#[cfg(test)]
+mod query_plan_test;
+
+#[cfg(test)]
+mod transaction_manager_test;
@@ -1,32 +1,264 @@
-use sqlparser::ast::{FromTable, ObjectType, Statement};
-use sqlparser::dialect::{Dialect, MySqlDialect, PostgreSqlDialect, SQLiteDialect};
+use sqlparser::ast::{
+    FromTable, ObjectType, Query, SetExpr, Statement, TransactionAccessMode, TransactionMode,
+};
+use sqlparser::dialect::{
+    Dialect, DuckDbDialect, MsSqlDialect, MySqlDialect, PostgreSqlDialect, SQLiteDialect,
+};
 use sqlparser::parser::Parser;
+use sqlparser::tokenizer::{Token, Tokenizer};
 
-use crate::models::query_analysis::{QueryAnalysisResult, QueryType, RiskFactor, RiskLevel};
+use crate::models::query_analysis::{
+    QueryAnalysisResult, QueryType, RiskFactor, RiskLevel, StatementAnalysis,
+};
+use crate::sql::statement_splitter::StatementSplitter;
+
+/// 引数を取っても状態を変えないSQLiteのPRAGMA
+const READ_ONLY_PRAGMAS: &[&str] = &[
+    "table_info",
+    "table_xinfo",
+    "table_list",
+    "index_list",
+    "index_info",
+    "index_xinfo",
+    "foreign_key_list",
+    "foreign_key_check",
+    "integrity_check",
+    "quick_check",
+];
+
+/// SELECTから呼べてもセッションやサーバーの状態を変える関数
+const STATE_CHANGING_FUNCTIONS: &[&str] = &[
+    "set_config",
+    "nextval",
+    "setval",
+    "pg_notify",
+    "pg_cancel_backend",
+    "pg_terminate_backend",
+    "pg_reload_conf",
+    "pg_rotate_logfile",
+    "pg_switch_wal",
+    "pg_promote",
+    "pg_logical_emit_message",
+    "pg_file_write",
+    "pg_file_rename",
+    "pg_file_unlink",
+    "pg_import_system_collations",
+    "load_extension",
+];
+
+/// 状態を変える関数が多い接頭辞（統計のリセット、レプリケーション、ラージオブジェクトなど）
+const STATE_CHANGING_FUNCTION_PREFIXES: &[&str] = &[
+    "pg_stat_reset",
+    "pg_create_",
+    "pg_drop_",
+    "pg_replication_",
+    "lo_",
+    "dblink",
+];
 
 pub struct QueryAnalyzer;
 
 impl QueryAnalyzer {
     /// SQLクエリを解析して危険度を判定
+    ///
+    /// 複数文のスクリプトは文ごとに解析し、最も危険度の高い文を全体の結果とする
     pub fn analyze(sql: &str, dialect: &str) -> QueryAnalysisResult {
-        let dialect_box: Box<dyn Dialect> = match dialect {
+        let dialect_box = Self::parser_dialect(dialect);
+
+        // 共通の分割器で文単位に分割してから解析する（DELIMITER構文などはパーサーが解釈できないため）
+        let statements = StatementSplitter::new(dialect)
+            .split(sql)
+            .into_iter()
+            .enumerate()
+            .map(|(index, range)| StatementAnalysis {
+                index,
+                range,
+                analysis: Self::analyze_text(&*dialect_box, range.text(sql)),
+            })
+            .collect();
+
+        QueryAnalysisResult::from_statements(statements)
+    }
+
+    /// すべての文が読み取り専用か（読み取り専用接続で実行してよいか）
+    ///
+    /// 解析できない文やセッションの読み取り専用設定を変える文、`set_config` のような
+    /// 状態を変える関数を呼ぶ文は読み取り専用とみなさない
+    pub fn is_read_only(sql: &str, dialect: &str) -> bool {
+        let dialect_box = Self::parser_dialect(dialect);
+        StatementSplitter::new(dialect)
+            .split(sql)
+            .into_iter()
+            .all(|range| {
+                let text = range.text(sql);
+                // MySQL/MariaDBの `EXPLAIN FORMAT=JSON` はパーサーが解釈できないため対象の文で判定する
+                if matches!(dialect, "mysql" | "mariadb") {
+                    if let Some((analyze, statement)) = Self::split_format_explain(text) {
+                        return !analyze || Self::is_read_only(statement, dialect);
+                    }
+                }
+                match Parser::parse_sql(&*dialect_box, text) {
+                    Ok(ast) => {
+                        ast.iter().all(Self::is_read_only_statement)
+                            && !Self::calls_state_changing_function(&*dialect_box, text)
+                    }
+                    Err(_) => false,
+                }
+            })
+    }
+
+    /// 文のどこか（副問い合わせやCTEを含む）で状態を変える関数を呼んでいるか
+    fn calls_state_changing_function(dialect: &dyn Dialect, text: &str) -> bool {
+        let tokens = match Tokenizer::new(dialect, text).tokenize() {
+            Ok(tokens) => tokens,
+            Err(_) => return true,
+        };
+        let mut tokens = tokens
+            .iter()
+            .filter(|token| !matches!(token, Token::Whitespace(_)))
+            .peekable();
+
+        while let Some(token) = tokens.next() {
+            let Token::Word(word) = token else {
+                continue;
+            };
+            if !matches!(tokens.peek(), Some(Token::LParen)) {
+                continue;
+            }
+            let name = word.value.to_ascii_lowercase();
+            if STATE_CHANGING_FUNCTIONS.contains(&name.as_str())
+                || STATE_CHANGING_FUNCTION_PREFIXES
+                    .iter()
+                    .any(|prefix| name.starts_with(prefix))
+            {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// `EXPLAIN|ANALYZE FORMAT=<形式> <文>` を（ANALYZEか, 文）に分ける
+    fn split_format_explain(text: &str) -> Option<(bool, &str)> {
+        let (keyword, rest) = text.trim_start().split_once(char::is_whitespace)?;
+        let analyze = if keyword.eq_ignore_ascii_case("EXPLAIN") {
+            false
+        } else if keyword.eq_ignore_ascii_case("ANALYZE") {
+            true
+        } else {
+            return None;
+        };
+        let rest = rest.trim_start();
+        if !rest.get(..6)?.eq_ignore_ascii_case("FORMAT") {
+            return None;
+        }
+        let rest = rest[6..].trim_start().strip_prefix('=')?.trim_start();
+        let format_end = rest.find(|c: char| !c.is_ascii_alphanumeric())?;
+        Some((analyze, &rest[format_end..]))
+    }
+
+    fn parser_dialect(dialect: &str) -> Box<dyn Dialect> {
+        match dialect {
             "postgresql" => Box::new(PostgreSqlDialect {}),
-            "mysql" => Box::new(MySqlDialect {}),
+            "mysql" | "mariadb" => Box::new(MySqlDialect {}),
             "sqlite" => Box::new(SQLiteDialect {}),
+            "duckdb" => Box::new(DuckDbDialect {}),
+            "sqlserver" => Box::new(MsSqlDialect {}),
             _ => Box::new(PostgreSqlDialect {}),
-        };
+        }
+    }
 
-        let ast = match Parser::parse_sql(&*dialect_box, sql) {
-            Ok(statements) => statements,
-            Err(_) => return QueryAnalysisResult::unknown(),
-        };
+    fn is_read_only_statement(stmt: &Statement) -> bool {
+        match stmt {
+            Statement::Query(query) => Self::is_read_only_query(query),
+            // EXPLAIN ANALYZE は対象の文を実際に実行する
+            Statement::Explain {
+                analyze,
+                statement,
+                options,
+                ..
+            } => {
+                let analyze = *analyze
+                    || options
+                        .iter()
+                        .flatten()
+                        .any(|option| option.name.value.eq_ignore_ascii_case("ANALYZE"));
+                !analyze || Self::is_read_only_statement(statement)
+            }
+            Statement::ExplainTable { .. }
+            | Statement::ShowFunctions { .. }
+            | Statement::ShowVariable { .. }
+            | Statement::ShowStatus { .. }
+            | Statement::ShowVariables { .. }
+            | Statement::ShowCreate { .. }
+            | Statement::ShowColumns { .. }
+            | Statement::ShowDatabases { .. }
+            | Statement::ShowSchemas { .. }
+            | Statement::ShowTables { .. }
+            | Statement::ShowViews { .. }
+            | Statement::ShowCollation { .. }
+            | Statement::Commit { .. }
+            | Statement::Rollback { .. }
+            | Statement::Savepoint { .. }
+            | Statement::ReleaseSavepoint { .. }
+            | Statement::SetTimeZone { .. }
+            | Statement::SetNames { .. }
+            | Statement::SetNamesDefault {}
+            | Statement::Use(_)
+            | Statement::Fetch { .. } => true,
+            // 読み書きモードのトランザクションは開始させない
+            Statement::StartTransaction { modes, .. } => !modes.contains(
+                &TransactionMode::AccessMode(TransactionAccessMode::ReadWrite),
+            ),
+            // トランザクション特性を変える変数（transaction_read_only 等）は変更させない
+            Statement::SetVariable { variables, .. } => variables.iter().all(|name| {
+                let name = name.to_string().to_ascii_lowercase();
+                !name.contains("read_only") && !name.contains("transaction")
+            }),
+            // 値を指定するPRAGMAは参照系のものだけ許可する（query_only を戻させない）
+            Statement::Pragma { name, value, .. } => {
+                value.is_none()
+                    || name.0.last().is_some_and(|ident| {
+                        READ_ONLY_PRAGMAS.contains(&ident.value.to_ascii_lowercase().as_str())
+                    })
+            }
+            _ => false,
+        }
+    }
 
-        if ast.is_empty() {
-            return QueryAnalysisResult::unknown();
+    fn is_read_only_query(query: &Query) -> bool {
+        query.locks.is_empty()
+            && query.with.as_ref().is_none_or(|with| {
+                with.cte_tables
+                    .iter()
+                    .all(|cte| Self::is_read_only_query(&cte.query))
+            })
+            && Self::is_read_only_set_expr(&query.body)
+    }
+
+    fn is_read_only_set_expr(expr: &SetExpr) -> bool {
+        match expr {
+            // SELECT ... INTO はテーブルを作成する
+            SetExpr::Select(select) => select.into.is_none(),
+            SetExpr::Query(query) => Self::is_read_only_query(query),
+            SetExpr::SetOperation { left, right, .. } => {
+                Self::is_read_only_set_expr(left) && Self::is_read_only_set_expr(right)
+            }
+            SetExpr::Values(_) | SetExpr::Table(_) => true,
+            SetExpr::Insert(_) | SetExpr::Update(_) => false,
         }
+    }
 
-        // 最初の文のみ解析
-        Self::analyze_statement(&ast[0])
+    /// 分割済みの1文を解析（パーサーが複数文として解釈した場合は最も危険度の高いもの）
+    fn analyze_text(dialect: &dyn Dialect, text: &str) -> QueryAnalysisResult {
+        match Parser::parse_sql(dialect, text) {
+            Ok(ast) => ast
+                .iter()
+                .map(Self::analyze_statement)
+                .rev()
+                .max_by(|a, b| a.risk_level.cmp(&b.risk_level))
+                .unwrap_or_else(QueryAnalysisResult::unknown),
+            Err(e) => QueryAnalysisResult::unparseable(&e.to_string()),
+        }
     }
 
     fn analyze_statement(stmt: &Statement) -> QueryAnalysisResult {
@@ -62,6 +294,7 @@ impl QueryAnalyzer {
             }],
             affected_tables: vec![table_name.to_string()],
             has_where_clause: false,
+            statements: vec![],
         }
     }
 
@@ -96,6 +329,7 @@ impl QueryAnalyzer {
             risk_factors,
             affected_tables: vec![table_name],
             has_where_clause: has_where,
+            statements: vec![],
         }
     }
 
@@ -133,6 +367,7 @@ impl QueryAnalyzer {
             risk_factors,
             affected_tables: table_names,
             has_where_clause: has_where,
+            statements: vec![],
         }
     }
 
@@ -157,6 +392,7 @@ impl QueryAnalyzer {
             }],
             affected_tables: names.iter().map(|n| n.to_string()).collect(),
             has_where_clause: false,
+            statements: vec![],
         }
     }
 
@@ -172,6 +408,7 @@ impl QueryAnalyzer {
             }],
             affected_tables: table_names.iter().map(|t| t.name.to_string()).collect(),
             has_where_clause: false,
+            statements: vec![],
         }
     }
 
@@ -185,6 +422,7 @@ impl QueryAnalyzer {
             }],
             affected_tables: vec![name.to_string()],
             has_where_clause: false,
+            statements: vec![],
         }
     }
 
@@ -195,6 +433,7 @@ impl QueryAnalyzer {
             risk_factors: vec![],
             affected_tables: vec![name.to_string()],
             has_where_clause: false,
+            statements: vec![],
         }
     }
 
@@ -205,6 +444,7 @@ impl QueryAnalyzer {
             risk_factors: vec![],
             affected_tables: name.as_ref().map(|n| n.to_string()).into_iter().collect(),
             has_where_clause: false,
+            statements: vec![],
         }
     }
 }
@@ -84,5 +84,119 @@ mod tests {
     fn test_analyze_invalid_sql() {
         let result = QueryAnalyzer::analyze("NOT A VALID SQL", "postgresql");
         assert_eq!(result.query_type, QueryType::Unknown);
+        assert_eq!(result.risk_level, RiskLevel::Unparseable);
+        assert_eq!(result.risk_factors[0].code, "unparseable");
+    }
+
+    #[test]
+    fn test_analyze_empty_sql() {
+        let result = QueryAnalyzer::analyze("  -- comment only\n", "postgresql");
+        assert_eq!(result.query_type, QueryType::Unknown);
+        assert_eq!(result.risk_level, RiskLevel::Safe);
+        assert!(result.statements.is_empty());
+    }
+
+    #[test]
+    fn test_analyze_every_statement() {
+        let sql = "SELECT 1; DROP TABLE users; UPDATE orders SET paid = true WHERE id = 1";
+        let result = QueryAnalyzer::analyze(sql, "postgresql");
+
+        // 全体の結果は最も危険な文のもの
+        assert_eq!(result.query_type, QueryType::Drop);
+        assert_eq!(result.risk_level, RiskLevel::Danger);
+        assert_eq!(result.affected_tables, vec!["users", "orders"]);
+        assert_eq!(result.risk_factors.len(), 2);
+
+        let statements = &result.statements;
+        assert_eq!(statements.len(), 3);
+        assert_eq!(statements[0].analysis.risk_level, RiskLevel::Safe);
+        assert_eq!(statements[1].index, 1);
+        assert_eq!(statements[1].range.text(sql), "DROP TABLE users");
+        assert_eq!(statements[2].analysis.query_type, QueryType::Update);
+        assert!(statements[2].analysis.has_where_clause);
+    }
+
+    #[test]
+    fn test_analyze_unparseable_statement_in_script() {
+        let sql = "DELETE FROM users WHERE id = 1; FROBNICATE users;";
+        let result = QueryAnalyzer::analyze(sql, "postgresql");
+
+        assert_eq!(result.risk_level, RiskLevel::Unparseable);
+        assert_eq!(result.statements.len(), 2);
+        assert_eq!(result.statements[0].analysis.query_type, QueryType::Delete);
+        assert_eq!(
+            result.statements[1].analysis.risk_level,
+            RiskLevel::Unparseable
+        );
+    }
+
+    #[test]
+    fn test_analyze_mysql_delimiter_script() {
+        let sql = "DELIMITER //\nDELETE FROM users WHERE note = 'a;b'//\nDELIMITER ;";
+        let result = QueryAnalyzer::analyze(sql, "mysql");
+        assert_eq!(result.query_type, QueryType::Delete);
+        assert!(result.has_where_clause);
+    }
+
+    #[test]
+    fn test_is_read_only() {
+        for (sql, dialect) in [
+            ("SELECT * FROM users; SHOW TABLES", "mysql"),
+            ("WITH a AS (SELECT 1) SELECT * FROM a UNION SELECT 2", "postgresql"),
+            ("EXPLAIN (FORMAT JSON) SELECT * FROM users", "postgresql"),
+            ("EXPLAIN FORMAT=JSON SELECT * FROM users", "mysql"),
+            ("ANALYZE FORMAT=JSON SELECT * FROM users", "mariadb"),
+            ("EXPLAIN (ANALYZE, FORMAT JSON) SELECT * FROM users", "postgresql"),
+            ("EXPLAIN QUERY PLAN SELECT * FROM users", "sqlite"),
+            ("PRAGMA table_info('users')", "sqlite"),
+            ("BEGIN; SELECT 1; COMMIT", "postgresql"),
+            ("SET search_path TO app", "postgresql"),
+            ("SELECT TOP 10 * FROM users", "sqlserver"),
+            (
+                "SELECT current_setting('default_transaction_read_only'), 'set_config(x)'",
+                "postgresql",
+            ),
+        ] {
+            assert!(QueryAnalyzer::is_read_only(sql, dialect), "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_is_not_read_only() {
+        for (sql, dialect) in [
+            ("SELECT 1; DELETE FROM users", "postgresql"),
+            (
+                "SELECT set_config('default_transaction_read_only', 'off', false)",
+                "postgresql",
+            ),
+            (
+                "SELECT * FROM users WHERE id IN (SELECT pg_catalog.set_config('a.b', 'c', true))",
+                "postgresql",
+            ),
+            (
+                "WITH s AS (SELECT nextval('users_id_seq')) SELECT * FROM s",
+                "postgresql",
+            ),
+            ("SELECT load_extension('./evil')", "sqlite"),
+            ("SELECT * INTO backup FROM users", "sqlserver"),
+            ("SELECT * FROM users FOR UPDATE", "postgresql"),
+            ("EXPLAIN ANALYZE DELETE FROM users", "postgresql"),
+            ("EXPLAIN (ANALYZE, FORMAT JSON) DELETE FROM users", "postgresql"),
+            ("ANALYZE FORMAT=JSON DELETE FROM users", "mariadb"),
+            ("SET TRANSACTION READ WRITE", "postgresql"),
+            (
+                "SET SESSION CHARACTERISTICS AS TRANSACTION READ WRITE",
+                "postgresql",
+            ),
+            ("SET default_transaction_read_only = off", "postgresql"),
+            ("SET SESSION TRANSACTION READ WRITE", "mysql"),
+            ("SET SESSION transaction_read_only = 0", "mysql"),
+            ("START TRANSACTION READ WRITE", "mysql"),
+            ("PRAGMA query_only = OFF", "sqlite"),
+            ("PRAGMA journal_mode(WAL)", "sqlite"),
+            ("FROBNICATE users", "postgresql"),
+        ] {
+            assert!(!QueryAnalyzer::is_read_only(sql, dialect), "{}", sql);
+        }
     }
 }
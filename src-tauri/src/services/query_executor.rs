@@ -1,16 +1,29 @@
 use crate::connection::ConnectionInfo;
 use crate::connection::DatabaseType;
+use crate::models::connection_pool::{PoolSettings, PoolStats};
 use crate::models::mutation_result::MutationResult;
-use crate::models::query_result::{QueryError, QueryErrorCode, QueryResult};
+use crate::models::query_result::{
+    QueryError, QueryErrorCode, QueryErrorDetails, QueryParam, QueryResult, QueryResultColumn,
+    QueryResultPage, QueryResultRow, ScriptErrorMode, StatementOutcome, StatementResult,
+    TypedParam,
+};
+use crate::services::query_analyzer::QueryAnalyzer;
+use crate::services::safety_guard::dialect_name;
+use crate::sql::statement_splitter::{StatementRange, StatementSplitter};
 use async_trait::async_trait;
+use futures_util::{Stream, StreamExt, TryStreamExt};
 use std::collections::HashMap;
-use std::sync::Arc;
-use std::time::Duration;
-use tokio::sync::RwLock;
+use std::future::Future;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, Mutex, RwLock};
+use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
-use crate::database::{MysqlExecutor, PostgresExecutor, SqliteExecutor};
+use crate::database::{
+    DuckDbExecutor, MssqlExecutor, MysqlExecutor, PostgresExecutor, SqliteExecutor,
+};
 
 /// クエリ実行トレイト
 #[async_trait]
@@ -34,6 +47,32 @@ pub trait QueryExecutor: Send + Sync {
             })?
     }
 
+    /// バインドパラメータ付きでSQLを実行
+    ///
+    /// プレースホルダーは方言に従う（PostgreSQL: `$1`、MySQL/SQLite: `?`）
+    async fn execute_with_params(
+        &self,
+        sql: &str,
+        params: &[QueryParam],
+    ) -> Result<QueryResult, QueryError>;
+
+    /// バインドパラメータ付きでSQLを実行（タイムアウト付き）
+    async fn execute_with_params_and_timeout(
+        &self,
+        sql: &str,
+        params: &[QueryParam],
+        timeout: Duration,
+    ) -> Result<QueryResult, QueryError> {
+        tokio::time::timeout(timeout, self.execute_with_params(sql, params))
+            .await
+            .map_err(|_| QueryError {
+                code: QueryErrorCode::QueryTimeout,
+                message: format!("Query timed out after {:?}", timeout),
+                details: None,
+                native_code: None,
+            })?
+    }
+
     /// SQLを実行（データ変更用）
     async fn execute_mutation(&self, sql: &str) -> Result<MutationResult, QueryError>;
 
@@ -53,10 +92,610 @@ pub trait QueryExecutor: Send + Sync {
             })?
     }
 
+    /// 複数文のSQLを同じ接続で順次実行し、文ごとの結果を返す
+    ///
+    /// `timeout` は1文あたりのタイムアウト。文のエラーは結果に含め、接続の確保に失敗した場合のみ `Err` を返す
+    async fn execute_script(
+        &self,
+        sql: &str,
+        mode: ScriptErrorMode,
+        timeout: Duration,
+    ) -> Result<Vec<StatementResult>, QueryError>;
+
+    /// カーソルを開いて結果をストリーミング取得する
+    ///
+    /// `buffer_size` はメモリ上に先読みしておく最大行数（`MAX_CURSOR_BUFFERED_ROWS` が上限）
+    async fn open_cursor(&self, sql: &str, buffer_size: usize) -> Result<QueryCursor, QueryError>;
+
+    /// 実行中のクエリをサーバー側でキャンセルする
+    ///
+    /// `query_id` は実行時に `CURRENT_QUERY_ID` に設定したID。該当するクエリが実行中でなければ `false` を返す
+    async fn cancel(&self, query_id: &str) -> Result<bool, QueryError>;
+
+    /// トランザクションを開始する（プールから専用の接続を確保する）
+    async fn begin_transaction(&self) -> Result<Box<dyn TransactionSession>, QueryError>;
+
+    /// プールの接続が生きているか確認する
+    async fn ping(&self) -> Result<(), QueryError>;
+
+    /// プールの接続数
+    fn pool_status(&self) -> PoolStatus;
+
     /// 接続をクローズ
     async fn close(&self) -> Result<(), QueryError>;
 }
 
+/// プールの接続数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PoolStatus {
+    /// 開いている接続数
+    pub size: u32,
+    /// アイドル状態の接続数
+    pub idle: u32,
+}
+
+/// トランザクションセッション
+///
+/// 開始時に確保した接続上で文を実行する。コミット・ロールバックされずに破棄された場合は
+/// 接続がプールに戻る際にロールバックされる。
+#[async_trait]
+pub trait TransactionSession: Send {
+    /// バインドパラメータ付きでSQLを実行
+    async fn execute_with_params(
+        &mut self,
+        sql: &str,
+        params: &[QueryParam],
+    ) -> Result<QueryResult, QueryError>;
+
+    /// SQLを実行（データ変更用）
+    async fn execute_mutation(&mut self, sql: &str) -> Result<MutationResult, QueryError>;
+
+    /// 複数文のSQLを順次実行し、文ごとの結果を返す
+    async fn execute_script(
+        &mut self,
+        sql: &str,
+        mode: ScriptErrorMode,
+        timeout: Duration,
+    ) -> Vec<StatementResult>;
+
+    /// コミット
+    async fn commit(self: Box<Self>) -> Result<(), QueryError>;
+
+    /// ロールバック
+    async fn rollback(self: Box<Self>) -> Result<(), QueryError>;
+}
+
+/// 文単位で発生したエラーの位置を、SQL全体での位置に変換する
+///
+/// DBが位置を返さない場合、複数文のSQL（`is_script`）であれば失敗した文の先頭を位置とする
+pub fn locate_statement_error(
+    mut error: QueryError,
+    sql: &str,
+    range: &StatementRange,
+    is_script: bool,
+) -> QueryError {
+    let offset = range.char_offset(sql) as u32;
+
+    match error.details.as_mut() {
+        Some(details) => match details.position {
+            Some(position) => details.position = Some(position + offset),
+            None if is_script => details.position = Some(offset + 1),
+            None => {}
+        },
+        None if is_script => {
+            error.details = Some(QueryErrorDetails {
+                line: None,
+                column: None,
+                sql_snippet: None,
+                position: Some(offset + 1),
+                object_name: None,
+                context: None,
+            });
+        }
+        None => {}
+    }
+
+    error
+}
+
+/// 複数文にバインドパラメータが指定された場合のエラー
+pub fn multiple_statements_with_params_error() -> QueryError {
+    QueryError {
+        code: QueryErrorCode::SyntaxError,
+        message: "Bind parameters can only be used with a single statement".to_string(),
+        details: None,
+        native_code: None,
+    }
+}
+
+/// sqlxのクエリ（引数付き）
+pub type SqlxQuery<'q, DB> = sqlx::query::Query<'q, DB, <DB as sqlx::Database>::Arguments<'q>>;
+
+/// 型を指定したバインドパラメータの設定（DBごとに対応する型が異なる）
+pub trait BindTypedParam: sqlx::Database {
+    fn bind_typed<'q>(query: SqlxQuery<'q, Self>, param: &'q TypedParam) -> SqlxQuery<'q, Self>;
+}
+
+impl BindTypedParam for sqlx::Postgres {
+    fn bind_typed<'q>(query: SqlxQuery<'q, Self>, param: &'q TypedParam) -> SqlxQuery<'q, Self> {
+        match param {
+            TypedParam::Decimal(d) => query.bind(d),
+            TypedParam::Date(d) => query.bind(*d),
+            TypedParam::Time(t) => query.bind(*t),
+            TypedParam::Timestamp(t) => query.bind(*t),
+            TypedParam::TimestampTz(t) => query.bind(*t),
+            TypedParam::Json(v) => query.bind(sqlx::types::Json(v)),
+            TypedParam::Uuid(u) => query.bind(*u),
+        }
+    }
+}
+
+impl BindTypedParam for sqlx::MySql {
+    fn bind_typed<'q>(query: SqlxQuery<'q, Self>, param: &'q TypedParam) -> SqlxQuery<'q, Self> {
+        match param {
+            TypedParam::Decimal(d) => query.bind(d),
+            TypedParam::Date(d) => query.bind(*d),
+            TypedParam::Time(t) => query.bind(*t),
+            TypedParam::Timestamp(t) => query.bind(*t),
+            TypedParam::TimestampTz(t) => query.bind(t.with_timezone(&chrono::Utc)),
+            TypedParam::Json(v) => query.bind(sqlx::types::Json(v)),
+            // UUID型がないため、CHAR(36) の列と比較できる文字列にする
+            TypedParam::Uuid(u) => query.bind(u.hyphenated().to_string()),
+        }
+    }
+}
+
+impl BindTypedParam for sqlx::Sqlite {
+    fn bind_typed<'q>(query: SqlxQuery<'q, Self>, param: &'q TypedParam) -> SqlxQuery<'q, Self> {
+        // 日時は sqlx がテキスト表現でバインドする。UUID（既定はBLOB）と数値は保存形式に合わせて文字列にする
+        match param {
+            TypedParam::Decimal(d) => query.bind(d.to_string()),
+            TypedParam::Date(d) => query.bind(*d),
+            TypedParam::Time(t) => query.bind(*t),
+            TypedParam::Timestamp(t) => query.bind(*t),
+            TypedParam::TimestampTz(t) => query.bind(*t),
+            TypedParam::Json(v) => query.bind(v.to_string()),
+            TypedParam::Uuid(u) => query.bind(u.hyphenated().to_string()),
+        }
+    }
+}
+
+/// バインドパラメータをsqlxのクエリに設定
+pub fn bind_params<'q, DB>(
+    mut query: SqlxQuery<'q, DB>,
+    params: &'q [QueryParam],
+) -> SqlxQuery<'q, DB>
+where
+    DB: BindTypedParam,
+    bool: sqlx::Encode<'q, DB> + sqlx::Type<DB>,
+    i64: sqlx::Encode<'q, DB> + sqlx::Type<DB>,
+    f64: sqlx::Encode<'q, DB> + sqlx::Type<DB>,
+    &'q str: sqlx::Encode<'q, DB> + sqlx::Type<DB>,
+    Option<&'q str>: sqlx::Encode<'q, DB> + sqlx::Type<DB>,
+{
+    for param in params {
+        query = match param {
+            QueryParam::Null => query.bind(None::<&str>),
+            QueryParam::Bool(b) => query.bind(*b),
+            QueryParam::Int(i) => query.bind(*i),
+            QueryParam::Float(f) => query.bind(*f),
+            QueryParam::String(s) => query.bind(s.as_str()),
+            QueryParam::Typed(typed) => DB::bind_typed(query, typed),
+        };
+    }
+    query
+}
+
+/// sqlxの実行結果から影響行数を取得する
+pub trait AffectedRows {
+    fn affected_rows(&self) -> u64;
+}
+
+impl AffectedRows for sqlx::postgres::PgQueryResult {
+    fn affected_rows(&self) -> u64 {
+        self.rows_affected()
+    }
+}
+
+impl AffectedRows for sqlx::mysql::MySqlQueryResult {
+    fn affected_rows(&self) -> u64 {
+        self.rows_affected()
+    }
+}
+
+impl AffectedRows for sqlx::sqlite::SqliteQueryResult {
+    fn affected_rows(&self) -> u64 {
+        self.rows_affected()
+    }
+}
+
+/// スクリプトの各文を同じ接続で順次実行し、文ごとの結果を返す
+///
+/// `StopOnError` の場合は最初にエラーになった文までの結果を返す
+pub async fn execute_statements<DB>(
+    conn: &mut DB::Connection,
+    sql: &str,
+    statements: &[StatementRange],
+    mode: ScriptErrorMode,
+    timeout: Duration,
+    build_result: fn(&[DB::Row]) -> QueryResult,
+    map_error: fn(sqlx::Error) -> QueryError,
+) -> Vec<StatementResult>
+where
+    DB: sqlx::Database,
+    DB::QueryResult: AffectedRows,
+    for<'c> &'c mut DB::Connection: sqlx::Executor<'c, Database = DB>,
+    for<'q> DB::Arguments<'q>: sqlx::IntoArguments<'q, DB>,
+{
+    let is_script = statements.len() > 1;
+    let mut results = Vec::with_capacity(statements.len());
+
+    for (index, range) in statements.iter().enumerate() {
+        let start = Instant::now();
+        let statement = range.text(sql);
+
+        let outcome =
+            match tokio::time::timeout(timeout, fetch_statement::<DB>(conn, statement)).await {
+                Ok(Ok((rows, affected_rows))) => {
+                    if !rows.is_empty() || returns_rows(statement) {
+                        let mut result = build_result(&rows);
+                        result.execution_time_ms = start.elapsed().as_millis() as u64;
+                        StatementOutcome::ResultSet { result }
+                    } else {
+                        StatementOutcome::Affected { affected_rows }
+                    }
+                }
+                Ok(Err(e)) => StatementOutcome::Error {
+                    error: locate_statement_error(map_error(e), sql, range, is_script),
+                },
+                Err(_) => StatementOutcome::Error {
+                    error: QueryError {
+                        code: QueryErrorCode::QueryTimeout,
+                        message: format!("Query timed out after {:?}", timeout),
+                        details: None,
+                        native_code: None,
+                    },
+                },
+            };
+
+        let failed = matches!(outcome, StatementOutcome::Error { .. });
+        results.push(StatementResult {
+            index,
+            range: *range,
+            outcome,
+            execution_time_ms: start.elapsed().as_millis() as u64,
+        });
+
+        if failed && mode == ScriptErrorMode::StopOnError {
+            break;
+        }
+    }
+
+    results
+}
+
+/// 1文を実行し、取得した行と影響行数を返す
+async fn fetch_statement<DB>(
+    conn: &mut DB::Connection,
+    statement: &str,
+) -> Result<(Vec<DB::Row>, u64), sqlx::Error>
+where
+    DB: sqlx::Database,
+    DB::QueryResult: AffectedRows,
+    for<'c> &'c mut DB::Connection: sqlx::Executor<'c, Database = DB>,
+    for<'q> DB::Arguments<'q>: sqlx::IntoArguments<'q, DB>,
+{
+    use sqlx::Executor;
+
+    let mut stream = conn.fetch_many(sqlx::query::<DB>(statement));
+    let mut rows = Vec::new();
+    let mut affected_rows = 0;
+    while let Some(item) = stream.try_next().await? {
+        match item {
+            sqlx::Either::Left(done) => affected_rows += done.affected_rows(),
+            sqlx::Either::Right(row) => rows.push(row),
+        }
+    }
+
+    Ok((rows, affected_rows))
+}
+
+/// 行を返さなくても結果セットとして扱う文か（0件のSELECTなど）
+pub fn returns_rows(statement: &str) -> bool {
+    matches!(
+        leading_keyword(statement).as_str(),
+        "SELECT"
+            | "WITH"
+            | "VALUES"
+            | "TABLE"
+            | "SHOW"
+            | "EXPLAIN"
+            | "DESCRIBE"
+            | "DESC"
+            | "PRAGMA"
+    )
+}
+
+/// 先頭のコメントを除いた最初のキーワード（大文字）
+pub fn leading_keyword(statement: &str) -> String {
+    let mut rest = statement.trim_start();
+    // 先頭のコメントを読み飛ばす
+    loop {
+        if let Some(after) = rest.strip_prefix("--") {
+            rest = after
+                .split_once('\n')
+                .map(|(_, r)| r)
+                .unwrap_or("")
+                .trim_start();
+        } else if let Some(after) = rest.strip_prefix("/*") {
+            rest = after
+                .split_once("*/")
+                .map(|(_, r)| r)
+                .unwrap_or("")
+                .trim_start();
+        } else {
+            break;
+        }
+    }
+
+    rest.chars()
+        .take_while(|c| c.is_ascii_alphabetic())
+        .collect::<String>()
+        .to_ascii_uppercase()
+}
+
+tokio::task_local! {
+    /// 実行中のクエリID
+    ///
+    /// コマンド側で実行をこのスコープで包むと、Executorが実行中の文をクエリIDに紐づけて
+    /// サーバー側キャンセル（`QueryExecutor::cancel`）の対象にする
+    pub static CURRENT_QUERY_ID: String;
+}
+
+/// 現在のタスクに設定されたクエリID
+pub fn current_query_id() -> Option<String> {
+    CURRENT_QUERY_ID.try_with(|query_id| query_id.clone()).ok()
+}
+
+/// 実行中クエリの一覧（クエリID → 実行を特定する情報）
+///
+/// PostgreSQL/MySQLは実行する文、SQLiteは割り込みフラグを保持する
+pub struct RunningQueries<T> {
+    queries: Arc<StdMutex<HashMap<String, T>>>,
+}
+
+impl<T: Clone> RunningQueries<T> {
+    pub fn new() -> Self {
+        Self {
+            queries: Arc::new(StdMutex::new(HashMap::new())),
+        }
+    }
+
+    /// 登録する。返されたガードが破棄されると登録が解除される
+    pub fn register(&self, query_id: String, handle: T) -> RunningQueryGuard<T> {
+        self.queries
+            .lock()
+            .unwrap()
+            .insert(query_id.clone(), handle);
+        RunningQueryGuard {
+            queries: Arc::clone(&self.queries),
+            query_id,
+        }
+    }
+
+    /// 実行中のクエリの情報を取得
+    pub fn get(&self, query_id: &str) -> Option<T> {
+        self.queries.lock().unwrap().get(query_id).cloned()
+    }
+}
+
+impl<T: Clone> Default for RunningQueries<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for RunningQueries<T> {
+    fn clone(&self) -> Self {
+        Self {
+            queries: Arc::clone(&self.queries),
+        }
+    }
+}
+
+/// 実行中の文の一覧
+///
+/// 接続のバックエンドを取得する往復を避けるため、キャンセル時はサーバー側で
+/// この文を実行中のセッションを探す。文が終わっていれば対象は見つからない
+pub type RunningStatements = Arc<Vec<String>>;
+
+/// 実行中クエリの登録ガード
+pub struct RunningQueryGuard<T> {
+    queries: Arc<StdMutex<HashMap<String, T>>>,
+    query_id: String,
+}
+
+impl<T> Drop for RunningQueryGuard<T> {
+    fn drop(&mut self) {
+        if let Ok(mut queries) = self.queries.lock() {
+            queries.remove(&self.query_id);
+        }
+    }
+}
+
+/// カーソルが先読みバッファに保持できる行数の上限
+pub const MAX_CURSOR_BUFFERED_ROWS: usize = 10_000;
+
+/// カーソルのデフォルトページサイズ
+pub const DEFAULT_CURSOR_PAGE_SIZE: usize = 500;
+
+/// カーソルのバックグラウンドタスクから送られるイベント
+pub enum CursorEvent {
+    /// カラム情報（最初の行の直前に1度だけ送られる）
+    Columns(Vec<QueryResultColumn>),
+    /// 行データ
+    Row(QueryResultRow),
+}
+
+/// カーソルへイベントを送るチャネル
+pub type CursorSender = mpsc::Sender<Result<CursorEvent, QueryError>>;
+
+/// ストリーミング取得用カーソル
+///
+/// バックグラウンドタスクが sqlx の `fetch` ストリームから行を読み出し、
+/// 容量付きチャネルに積む。チャネルが満杯の間は読み出しが止まるため、
+/// メモリ上に保持される行数はバッファサイズを超えない。
+pub struct QueryCursor {
+    columns: Vec<QueryResultColumn>,
+    receiver: mpsc::Receiver<Result<CursorEvent, QueryError>>,
+    /// 続きの有無を判定するために先読みした行（またはエラー）
+    pending: Option<Result<QueryResultRow, QueryError>>,
+    handle: JoinHandle<()>,
+    total_fetched: usize,
+    finished: bool,
+}
+
+impl QueryCursor {
+    /// 行を送出するタスクを起動してカーソルを作成
+    pub fn spawn<F, Fut>(buffer_size: usize, producer: F) -> Self
+    where
+        F: FnOnce(CursorSender) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let capacity = buffer_size.clamp(1, MAX_CURSOR_BUFFERED_ROWS);
+        let (sender, receiver) = mpsc::channel(capacity);
+        let handle = tokio::spawn(producer(sender));
+
+        Self {
+            columns: vec![],
+            receiver,
+            pending: None,
+            handle,
+            total_fetched: 0,
+            finished: false,
+        }
+    }
+
+    /// sqlx の行ストリームを変換しながらチャネルへ転送する
+    ///
+    /// 受信側（カーソル）が閉じられた時点で読み出しを止める。
+    pub async fn forward_rows<R, S>(
+        mut stream: S,
+        sender: CursorSender,
+        build_columns: fn(&R) -> Vec<QueryResultColumn>,
+        convert_row: fn(&R, &[QueryResultColumn]) -> QueryResultRow,
+        map_error: fn(sqlx::Error) -> QueryError,
+    ) where
+        S: Stream<Item = Result<R, sqlx::Error>> + Unpin,
+    {
+        let mut columns: Option<Vec<QueryResultColumn>> = None;
+
+        while let Some(result) = stream.next().await {
+            let row = match result {
+                Ok(row) => row,
+                Err(e) => {
+                    let _ = sender.send(Err(map_error(e))).await;
+                    return;
+                }
+            };
+
+            if columns.is_none() {
+                let built = build_columns(&row);
+                if sender
+                    .send(Ok(CursorEvent::Columns(built.clone())))
+                    .await
+                    .is_err()
+                {
+                    return;
+                }
+                columns = Some(built);
+            }
+
+            let converted = convert_row(&row, columns.as_deref().unwrap_or_default());
+            if sender.send(Ok(CursorEvent::Row(converted))).await.is_err() {
+                return;
+            }
+        }
+    }
+
+    /// カラム情報（まだ行を受信していない場合は空）
+    pub fn columns(&self) -> &[QueryResultColumn] {
+        &self.columns
+    }
+
+    /// これまでに取得した累計行数
+    pub fn total_fetched(&self) -> usize {
+        self.total_fetched
+    }
+
+    /// 次の行を受信する（カラム情報はここで取り込む）
+    async fn next_row(&mut self) -> Option<Result<QueryResultRow, QueryError>> {
+        if let Some(pending) = self.pending.take() {
+            return Some(pending);
+        }
+
+        if self.finished {
+            return None;
+        }
+
+        loop {
+            match self.receiver.recv().await {
+                Some(Ok(CursorEvent::Columns(columns))) => self.columns = columns,
+                Some(Ok(CursorEvent::Row(row))) => return Some(Ok(row)),
+                Some(Err(e)) => {
+                    self.finished = true;
+                    return Some(Err(e));
+                }
+                None => {
+                    self.finished = true;
+                    return None;
+                }
+            }
+        }
+    }
+
+    /// 最大 `limit` 行を取得し、続きの行があるかどうかを返す
+    pub async fn fetch(&mut self, limit: usize) -> Result<(Vec<QueryResultRow>, bool), QueryError> {
+        let limit = limit.clamp(1, MAX_CURSOR_BUFFERED_ROWS);
+        let mut rows = Vec::with_capacity(limit);
+
+        while rows.len() < limit {
+            match self.next_row().await {
+                Some(Ok(row)) => rows.push(row),
+                Some(Err(e)) => {
+                    if rows.is_empty() {
+                        return Err(e);
+                    }
+                    // 取得済みの行を先に返し、エラーは次回の取得で返す
+                    self.pending = Some(Err(e));
+                    break;
+                }
+                None => break,
+            }
+        }
+
+        // 1行先読みして続きの有無を判定
+        let has_more = if self.pending.is_some() {
+            true
+        } else if rows.len() == limit {
+            self.pending = self.next_row().await;
+            self.pending.is_some()
+        } else {
+            false
+        };
+
+        self.total_fetched += rows.len();
+        Ok((rows, has_more))
+    }
+}
+
+impl Drop for QueryCursor {
+    fn drop(&mut self) {
+        // 読み出しタスクを停止してコネクションをプールへ返す
+        self.handle.abort();
+    }
+}
+
 /// Executorファクトリ
 pub struct QueryExecutorFactory;
 
@@ -64,32 +703,296 @@ impl QueryExecutorFactory {
     pub async fn create(
         connection: &ConnectionInfo,
         password: Option<&str>,
+        settings: &PoolSettings,
     ) -> Result<Box<dyn QueryExecutor>, QueryError> {
-        match connection.database_type {
-            DatabaseType::PostgreSQL => {
-                Ok(Box::new(PostgresExecutor::new(connection, password).await?))
+        let executor: Box<dyn QueryExecutor> = match connection.database_type {
+            DatabaseType::PostgreSQL => Box::new(
+                PostgresExecutor::with_pool_settings(connection, password, settings).await?,
+            ),
+            // MariaDBはMySQLのドライバで実行する
+            DatabaseType::MySQL | DatabaseType::MariaDB => {
+                Box::new(MysqlExecutor::with_pool_settings(connection, password, settings).await?)
+            }
+            DatabaseType::SQLite => {
+                Box::new(SqliteExecutor::with_pool_settings(connection, settings).await?)
+            }
+            DatabaseType::DuckDB => Box::new(DuckDbExecutor::new(connection).await?),
+            DatabaseType::SqlServer => {
+                Box::new(MssqlExecutor::with_pool_settings(connection, password, settings).await?)
             }
-            DatabaseType::MySQL => Ok(Box::new(MysqlExecutor::new(connection, password).await?)),
-            DatabaseType::SQLite => Ok(Box::new(SqliteExecutor::new(connection).await?)),
+        };
+
+        if connection.is_read_only() {
+            Ok(Box::new(ReadOnlyExecutor::new(
+                executor,
+                &connection.database_type,
+            )))
+        } else {
+            Ok(executor)
+        }
+    }
+}
+
+/// 読み取り専用接続で更新系の実行を拒否したときのエラー
+fn read_only_error() -> QueryError {
+    QueryError {
+        code: QueryErrorCode::PermissionDenied,
+        message: "This connection is read-only; data changes are not allowed".to_string(),
+        details: None,
+        native_code: None,
+    }
+}
+
+/// 読み取り専用接続のExecutor
+///
+/// `execute_mutation` を拒否し、それ以外のSQLも文ごとに解析して読み取り専用の文だけを
+/// 内部のExecutorに委譲する。セッションもDB側で読み取り専用にする
+/// （セッション単位の読み取り専用モードがないSQL Serverは解析による拒否のみ）
+pub struct ReadOnlyExecutor {
+    inner: Box<dyn QueryExecutor>,
+    dialect: &'static str,
+}
+
+impl ReadOnlyExecutor {
+    pub fn new(inner: Box<dyn QueryExecutor>, database_type: &DatabaseType) -> Self {
+        Self {
+            inner,
+            dialect: dialect_name(database_type),
+        }
+    }
+}
+
+#[async_trait]
+impl QueryExecutor for ReadOnlyExecutor {
+    async fn execute(&self, sql: &str) -> Result<QueryResult, QueryError> {
+        if !QueryAnalyzer::is_read_only(sql, self.dialect) {
+            return Err(read_only_error());
+        }
+        self.inner.execute(sql).await
+    }
+
+    async fn execute_with_timeout(
+        &self,
+        sql: &str,
+        timeout: Duration,
+    ) -> Result<QueryResult, QueryError> {
+        if !QueryAnalyzer::is_read_only(sql, self.dialect) {
+            return Err(read_only_error());
+        }
+        self.inner.execute_with_timeout(sql, timeout).await
+    }
+
+    async fn execute_with_params(
+        &self,
+        sql: &str,
+        params: &[QueryParam],
+    ) -> Result<QueryResult, QueryError> {
+        if !QueryAnalyzer::is_read_only(sql, self.dialect) {
+            return Err(read_only_error());
+        }
+        self.inner.execute_with_params(sql, params).await
+    }
+
+    async fn execute_with_params_and_timeout(
+        &self,
+        sql: &str,
+        params: &[QueryParam],
+        timeout: Duration,
+    ) -> Result<QueryResult, QueryError> {
+        if !QueryAnalyzer::is_read_only(sql, self.dialect) {
+            return Err(read_only_error());
+        }
+        self.inner
+            .execute_with_params_and_timeout(sql, params, timeout)
+            .await
+    }
+
+    async fn execute_mutation(&self, _sql: &str) -> Result<MutationResult, QueryError> {
+        Err(read_only_error())
+    }
+
+    async fn execute_mutation_with_timeout(
+        &self,
+        _sql: &str,
+        _timeout: Duration,
+    ) -> Result<MutationResult, QueryError> {
+        Err(read_only_error())
+    }
+
+    async fn execute_script(
+        &self,
+        sql: &str,
+        mode: ScriptErrorMode,
+        timeout: Duration,
+    ) -> Result<Vec<StatementResult>, QueryError> {
+        if !QueryAnalyzer::is_read_only(sql, self.dialect) {
+            return Err(read_only_error());
+        }
+        self.inner.execute_script(sql, mode, timeout).await
+    }
+
+    async fn open_cursor(&self, sql: &str, buffer_size: usize) -> Result<QueryCursor, QueryError> {
+        if !QueryAnalyzer::is_read_only(sql, self.dialect) {
+            return Err(read_only_error());
         }
+        self.inner.open_cursor(sql, buffer_size).await
     }
+
+    async fn cancel(&self, query_id: &str) -> Result<bool, QueryError> {
+        self.inner.cancel(query_id).await
+    }
+
+    async fn begin_transaction(&self) -> Result<Box<dyn TransactionSession>, QueryError> {
+        let inner = self.inner.begin_transaction().await?;
+        Ok(Box::new(ReadOnlyTransaction {
+            inner,
+            dialect: self.dialect,
+        }))
+    }
+
+    async fn ping(&self) -> Result<(), QueryError> {
+        self.inner.ping().await
+    }
+
+    fn pool_status(&self) -> PoolStatus {
+        self.inner.pool_status()
+    }
+
+    async fn close(&self) -> Result<(), QueryError> {
+        self.inner.close().await
+    }
+}
+
+/// 読み取り専用接続のトランザクション（読み取り専用でない文を拒否する）
+struct ReadOnlyTransaction {
+    inner: Box<dyn TransactionSession>,
+    dialect: &'static str,
 }
 
+#[async_trait]
+impl TransactionSession for ReadOnlyTransaction {
+    async fn execute_with_params(
+        &mut self,
+        sql: &str,
+        params: &[QueryParam],
+    ) -> Result<QueryResult, QueryError> {
+        if !QueryAnalyzer::is_read_only(sql, self.dialect) {
+            return Err(read_only_error());
+        }
+        self.inner.execute_with_params(sql, params).await
+    }
+
+    async fn execute_mutation(&mut self, _sql: &str) -> Result<MutationResult, QueryError> {
+        Err(read_only_error())
+    }
+
+    async fn execute_script(
+        &mut self,
+        sql: &str,
+        mode: ScriptErrorMode,
+        timeout: Duration,
+    ) -> Vec<StatementResult> {
+        // 読み取り専用でない文があれば1文も実行せず、その文のエラーを返す
+        let statements = StatementSplitter::new(self.dialect).split(sql);
+        let rejected = statements
+            .iter()
+            .enumerate()
+            .find(|(_, range)| !QueryAnalyzer::is_read_only(range.text(sql), self.dialect));
+        if let Some((index, range)) = rejected {
+            return vec![StatementResult {
+                index,
+                range: *range,
+                outcome: StatementOutcome::Error {
+                    error: locate_statement_error(
+                        read_only_error(),
+                        sql,
+                        range,
+                        statements.len() > 1,
+                    ),
+                },
+                execution_time_ms: 0,
+            }];
+        }
+        self.inner.execute_script(sql, mode, timeout).await
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), QueryError> {
+        self.inner.commit().await
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), QueryError> {
+        self.inner.rollback().await
+    }
+}
+
+/// ヘルスチェックの応答を待つ最大時間
+const PING_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// プールに登録されたExecutor
+struct PoolEntry {
+    executor: Arc<dyn QueryExecutor>,
+    max_connections: u32,
+    created_at: chrono::DateTime<chrono::Utc>,
+    last_used: StdMutex<(Instant, chrono::DateTime<chrono::Utc>)>,
+}
+
+impl PoolEntry {
+    /// 最終使用時刻を更新
+    fn touch(&self) {
+        *self.last_used.lock().unwrap() = (Instant::now(), chrono::Utc::now());
+    }
+
+    fn stats(&self, connection_id: &str) -> PoolStats {
+        let status = self.executor.pool_status();
+        PoolStats {
+            connection_id: connection_id.to_string(),
+            open: status.size,
+            idle: status.idle,
+            in_use: status.size.saturating_sub(status.idle),
+            max_connections: self.max_connections,
+            created_at: self.created_at.to_rfc3339(),
+            last_used_at: self.last_used.lock().unwrap().1.to_rfc3339(),
+        }
+    }
+}
+
+type PoolMap = Arc<RwLock<HashMap<String, Arc<PoolEntry>>>>;
+
 /// 接続プールマネージャー
+///
+/// 一定時間使われていないプールを閉じ、定期的なヘルスチェックで切断されたプールを取り除く。
+/// 取り除いたプールは次回の `get_or_create` で再接続される。
 pub struct ConnectionPoolManager {
     /// 接続ID -> Executor のマップ
-    pools: RwLock<HashMap<String, Arc<dyn QueryExecutor>>>,
-    // idle_timeout: Duration, // 将来的に使用
+    pools: PoolMap,
+    settings: Arc<StdMutex<PoolSettings>>,
+    /// 定期メンテナンスタスク（最初のプール作成時に起動）
+    maintenance: StdMutex<Option<JoinHandle<()>>>,
 }
 
 impl ConnectionPoolManager {
     pub fn new() -> Self {
+        Self::with_settings(PoolSettings::default())
+    }
+
+    pub fn with_settings(settings: PoolSettings) -> Self {
         Self {
-            pools: RwLock::new(HashMap::new()),
-            // idle_timeout: Duration::from_secs(300),
+            pools: Arc::new(RwLock::new(HashMap::new())),
+            settings: Arc::new(StdMutex::new(settings)),
+            maintenance: StdMutex::new(None),
         }
     }
 
+    /// 現在のプール設定
+    pub fn settings(&self) -> PoolSettings {
+        self.settings.lock().unwrap().clone()
+    }
+
+    /// プール設定を更新（最大接続数は以降に作成するプールから適用）
+    pub fn update_settings(&self, settings: PoolSettings) {
+        *self.settings.lock().unwrap() = settings;
+    }
+
     /// Executorを取得または作成
     pub async fn get_or_create(
         &self,
@@ -100,35 +1003,361 @@ impl ConnectionPoolManager {
         // 既存のプールをチェック
         {
             let pools = self.pools.read().await;
-            if let Some(executor) = pools.get(connection_id) {
-                return Ok(Arc::clone(executor));
+            if let Some(entry) = pools.get(connection_id) {
+                entry.touch();
+                return Ok(Arc::clone(&entry.executor));
             }
         }
 
         // 新規作成
-        let executor = QueryExecutorFactory::create(connection, password).await?;
+        let settings = self.settings();
+        let executor = QueryExecutorFactory::create(connection, password, &settings).await?;
         let executor: Arc<dyn QueryExecutor> = Arc::from(executor);
+        let entry = Arc::new(PoolEntry {
+            executor: Arc::clone(&executor),
+            max_connections: settings.max_connections,
+            created_at: chrono::Utc::now(),
+            last_used: StdMutex::new((Instant::now(), chrono::Utc::now())),
+        });
 
         {
             let mut pools = self.pools.write().await;
-            pools.insert(connection_id.to_string(), Arc::clone(&executor));
+            pools.insert(connection_id.to_string(), entry);
         }
+        self.ensure_maintenance();
 
         Ok(executor)
     }
 
+    /// 作成済みのExecutorを取得
+    pub async fn get(&self, connection_id: &str) -> Option<Arc<dyn QueryExecutor>> {
+        self.pools
+            .read()
+            .await
+            .get(connection_id)
+            .map(|entry| Arc::clone(&entry.executor))
+    }
+
     /// 接続を削除
     pub async fn remove(&self, connection_id: &str) {
         let mut pools = self.pools.write().await;
-        if let Some(executor) = pools.remove(connection_id) {
-            let _ = executor.close().await;
+        if let Some(entry) = pools.remove(connection_id) {
+            let _ = entry.executor.close().await;
+        }
+    }
+
+    /// プールごとの接続状況（接続ID順）
+    pub async fn stats(&self) -> Vec<PoolStats> {
+        let pools = self.pools.read().await;
+        let mut stats: Vec<PoolStats> = pools.iter().map(|(id, entry)| entry.stats(id)).collect();
+        stats.sort_by(|a, b| a.connection_id.cmp(&b.connection_id));
+        stats
+    }
+
+    /// アイドルプールの解放とヘルスチェックを1回行い、取り除いた接続IDを返す
+    pub async fn run_maintenance(&self) -> Vec<String> {
+        let settings = self.settings();
+        Self::maintain(&self.pools, &settings).await
+    }
+
+    async fn maintain(pools: &PoolMap, settings: &PoolSettings) -> Vec<String> {
+        let idle_timeout = Duration::from_secs(settings.idle_timeout_seconds);
+        let entries: Vec<(String, Arc<PoolEntry>)> = pools
+            .read()
+            .await
+            .iter()
+            .map(|(id, entry)| (id.clone(), Arc::clone(entry)))
+            .collect();
+
+        let mut removed = Vec::new();
+        for (connection_id, entry) in entries {
+            let status = entry.executor.pool_status();
+            // 使用中の接続（トランザクション・カーソルを含む）があるプールは解放しない
+            let in_use = status.size > status.idle;
+            let idle = entry.last_used.lock().unwrap().0.elapsed() >= idle_timeout;
+
+            let evict = if idle && !in_use {
+                true
+            } else if status.idle > 0 || status.size == 0 {
+                // すべての接続が使用中の場合は疎通を確認しない
+                !matches!(
+                    tokio::time::timeout(PING_TIMEOUT, entry.executor.ping()).await,
+                    Ok(Ok(()))
+                )
+            } else {
+                false
+            };
+            if !evict {
+                continue;
+            }
+
+            // 確認中に再作成された場合は新しいプールを残す
+            let taken = {
+                let mut pools = pools.write().await;
+                match pools.get(&connection_id) {
+                    Some(current) if Arc::ptr_eq(current, &entry) => {
+                        pools.remove(&connection_id);
+                        true
+                    }
+                    _ => false,
+                }
+            };
+            if taken {
+                // 使用中の接続が返却されるまで待つため、クローズは別タスクで行う
+                let executor = Arc::clone(&entry.executor);
+                tokio::spawn(async move {
+                    let _ = executor.close().await;
+                });
+                removed.push(connection_id);
+            }
+        }
+        removed
+    }
+
+    /// 定期メンテナンスタスクを起動（起動済みなら何もしない）
+    fn ensure_maintenance(&self) {
+        let mut maintenance = self.maintenance.lock().unwrap();
+        if maintenance.is_some() {
+            return;
+        }
+
+        let pools = Arc::clone(&self.pools);
+        let settings = Arc::clone(&self.settings);
+        *maintenance = Some(tokio::spawn(async move {
+            loop {
+                let interval = settings.lock().unwrap().health_check_interval_seconds;
+                tokio::time::sleep(Duration::from_secs(interval.max(1))).await;
+                let current = settings.lock().unwrap().clone();
+                Self::maintain(&pools, &current).await;
+            }
+        }));
+    }
+}
+
+impl Drop for ConnectionPoolManager {
+    fn drop(&mut self) {
+        if let Some(handle) = self.maintenance.lock().unwrap().take() {
+            handle.abort();
         }
     }
 }
 
+/// 最後の取得からこの時間が経過したカーソルは自動的に閉じる
+pub const CURSOR_IDLE_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// 1つの接続で同時に開けるカーソル数の上限
+pub const MAX_CURSORS_PER_CONNECTION: usize = 8;
+
+/// アイドルカーソルを確認する間隔
+const CURSOR_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 登録されたカーソル
+struct CursorEntry {
+    connection_id: String,
+    cursor: Arc<Mutex<QueryCursor>>,
+    last_access: StdMutex<Instant>,
+}
+
+type CursorMap = Arc<RwLock<HashMap<String, Arc<CursorEntry>>>>;
+
+/// カーソル管理
+///
+/// カーソルはコネクションと読み出し済みの行を保持し続けるため、
+/// 一定時間取得されないカーソルを閉じ、接続ごとの同時オープン数を制限する。
+pub struct QueryCursorManager {
+    /// カーソルID -> カーソル のマップ
+    cursors: CursorMap,
+    idle_timeout: Duration,
+    max_per_connection: usize,
+    /// アイドルカーソルを閉じるタスク（最初のカーソル登録時に起動）
+    sweeper: StdMutex<Option<JoinHandle<()>>>,
+}
+
+impl QueryCursorManager {
+    pub fn new() -> Self {
+        Self::with_limits(CURSOR_IDLE_TIMEOUT, MAX_CURSORS_PER_CONNECTION)
+    }
+
+    pub fn with_limits(idle_timeout: Duration, max_per_connection: usize) -> Self {
+        Self {
+            cursors: Arc::new(RwLock::new(HashMap::new())),
+            idle_timeout,
+            max_per_connection,
+            sweeper: StdMutex::new(None),
+        }
+    }
+
+    /// カーソルを登録してIDを払い出す
+    ///
+    /// 接続ごとの上限に達している場合はエラー（カーソルは閉じられる）
+    pub async fn register(
+        &self,
+        connection_id: &str,
+        cursor: QueryCursor,
+    ) -> Result<String, QueryError> {
+        let cursor_id = Uuid::new_v4().to_string();
+        {
+            let mut cursors = self.cursors.write().await;
+            Self::evict_idle(&mut cursors, self.idle_timeout);
+
+            let open = cursors
+                .values()
+                .filter(|entry| entry.connection_id == connection_id)
+                .count();
+            if open >= self.max_per_connection {
+                return Err(QueryError {
+                    code: QueryErrorCode::Unknown,
+                    message: format!(
+                        "Too many open cursors for this connection (max {})",
+                        self.max_per_connection
+                    ),
+                    details: None,
+                    native_code: None,
+                });
+            }
+
+            cursors.insert(
+                cursor_id.clone(),
+                Arc::new(CursorEntry {
+                    connection_id: connection_id.to_string(),
+                    cursor: Arc::new(Mutex::new(cursor)),
+                    last_access: StdMutex::new(Instant::now()),
+                }),
+            );
+        }
+        self.ensure_sweeper();
+
+        Ok(cursor_id)
+    }
+
+    /// 次のページを取得（全行を読み終えたカーソルは自動的に閉じる）
+    pub async fn fetch(
+        &self,
+        cursor_id: &str,
+        page_size: usize,
+    ) -> Result<QueryResultPage, QueryError> {
+        let entry = {
+            let cursors = self.cursors.read().await;
+            cursors.get(cursor_id).cloned()
+        }
+        .ok_or_else(|| QueryError {
+            code: QueryErrorCode::Unknown,
+            message: format!("Cursor not found: {}", cursor_id),
+            details: None,
+            native_code: None,
+        })?;
+
+        let start = Instant::now();
+        let mut cursor = entry.cursor.lock().await;
+        let result = cursor.fetch(page_size).await;
+
+        let page = match result {
+            Ok((rows, has_more)) => QueryResultPage {
+                cursor_id: cursor_id.to_string(),
+                columns: cursor.columns().to_vec(),
+                row_count: rows.len(),
+                rows,
+                total_fetched: cursor.total_fetched(),
+                has_more,
+                execution_time_ms: start.elapsed().as_millis() as u64,
+            },
+            Err(e) => {
+                drop(cursor);
+                self.close(cursor_id).await;
+                return Err(e);
+            }
+        };
+
+        drop(cursor);
+        *entry.last_access.lock().unwrap() = Instant::now();
+        if !page.has_more {
+            self.close(cursor_id).await;
+        }
+
+        Ok(page)
+    }
+
+    /// カーソルを閉じる
+    pub async fn close(&self, cursor_id: &str) -> bool {
+        let mut cursors = self.cursors.write().await;
+        cursors.remove(cursor_id).is_some()
+    }
+
+    /// 接続のカーソルをすべて閉じ、閉じた数を返す（接続の更新・削除時）
+    pub async fn close_connection(&self, connection_id: &str) -> usize {
+        let mut cursors = self.cursors.write().await;
+        let before = cursors.len();
+        cursors.retain(|_, entry| entry.connection_id != connection_id);
+        before - cursors.len()
+    }
+
+    /// アイドル時間を超えたカーソルを閉じ、閉じたカーソルIDを返す
+    pub async fn close_idle(&self) -> Vec<String> {
+        let mut cursors = self.cursors.write().await;
+        Self::evict_idle(&mut cursors, self.idle_timeout)
+    }
+
+    fn evict_idle(
+        cursors: &mut HashMap<String, Arc<CursorEntry>>,
+        idle_timeout: Duration,
+    ) -> Vec<String> {
+        let expired: Vec<String> = cursors
+            .iter()
+            .filter(|(_, entry)| {
+                // 取得中のカーソルは閉じない
+                entry.last_access.lock().unwrap().elapsed() >= idle_timeout
+                    && entry.cursor.try_lock().is_ok()
+            })
+            .map(|(cursor_id, _)| cursor_id.clone())
+            .collect();
+        for cursor_id in &expired {
+            cursors.remove(cursor_id);
+        }
+        expired
+    }
+
+    /// アイドルカーソルを閉じるタスクを起動（起動済みなら何もしない）
+    fn ensure_sweeper(&self) {
+        let mut sweeper = self.sweeper.lock().unwrap();
+        if sweeper.is_some() {
+            return;
+        }
+
+        let cursors = Arc::clone(&self.cursors);
+        let idle_timeout = self.idle_timeout;
+        *sweeper = Some(tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(CURSOR_SWEEP_INTERVAL).await;
+                Self::evict_idle(&mut *cursors.write().await, idle_timeout);
+            }
+        }));
+    }
+}
+
+impl Drop for QueryCursorManager {
+    fn drop(&mut self) {
+        if let Some(handle) = self.sweeper.lock().unwrap().take() {
+            handle.abort();
+        }
+    }
+}
+
+impl Default for QueryCursorManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// クエリキャンセル管理
 pub struct QueryCancellationManager {
-    tokens: RwLock<HashMap<String, CancellationToken>>,
+    tokens: RwLock<HashMap<String, CancellableQuery>>,
+}
+
+/// キャンセル可能な実行中クエリ
+struct CancellableQuery {
+    token: CancellationToken,
+    /// 実行している接続ID（サーバー側キャンセル用）
+    connection_id: Option<String>,
 }
 
 impl QueryCancellationManager {
@@ -140,22 +1369,55 @@ impl QueryCancellationManager {
 
     /// 新しいキャンセルトークンを作成
     pub async fn create_token(&self) -> (String, CancellationToken) {
-        let query_id = Uuid::new_v4().to_string();
+        self.insert(Uuid::new_v4().to_string(), None).await
+    }
+
+    /// 接続に紐づくキャンセルトークンを作成
+    ///
+    /// 実行完了前にキャンセルできるよう、呼び出し側が `query_id` を指定できる
+    pub async fn create_token_for(
+        &self,
+        query_id: Option<String>,
+        connection_id: &str,
+    ) -> (String, CancellationToken) {
+        let query_id = query_id.unwrap_or_else(|| Uuid::new_v4().to_string());
+        self.insert(query_id, Some(connection_id.to_string())).await
+    }
+
+    async fn insert(
+        &self,
+        query_id: String,
+        connection_id: Option<String>,
+    ) -> (String, CancellationToken) {
         let token = CancellationToken::new();
 
         {
             let mut tokens = self.tokens.write().await;
-            tokens.insert(query_id.clone(), token.clone());
+            tokens.insert(
+                query_id.clone(),
+                CancellableQuery {
+                    token: token.clone(),
+                    connection_id,
+                },
+            );
         }
 
         (query_id, token)
     }
 
+    /// クエリを実行している接続ID
+    pub async fn connection_of(&self, query_id: &str) -> Option<String> {
+        let tokens = self.tokens.read().await;
+        tokens
+            .get(query_id)
+            .and_then(|query| query.connection_id.clone())
+    }
+
     /// クエリをキャンセル
     pub async fn cancel(&self, query_id: &str) -> bool {
         let tokens = self.tokens.read().await;
-        if let Some(token) = tokens.get(query_id) {
-            token.cancel();
+        if let Some(query) = tokens.get(query_id) {
+            query.token.cancel();
             true
         } else {
             false
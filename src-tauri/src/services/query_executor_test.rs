@@ -1,4 +1,15 @@
-use crate::services::query_executor::QueryCancellationManager;
+use crate::connection::{ConnectionConfig, ConnectionInfo, DatabaseType, FileConfig};
+use crate::database::SqliteExecutor;
+use crate::models::connection_pool::PoolSettings;
+use crate::models::query_result::{
+    QueryError, QueryErrorCode, QueryParam, QueryResultColumn, QueryResultRow, QueryValue,
+    ScriptErrorMode, StatementOutcome, TypedParam,
+};
+use crate::services::query_executor::{
+    ConnectionPoolManager, CursorEvent, QueryCancellationManager, QueryCursor, QueryCursorManager,
+    QueryExecutor, QueryExecutorFactory, RunningQueries, CURRENT_QUERY_ID,
+};
+use std::sync::Arc;
 use tokio::time::Duration;
 
 #[tokio::test]
@@ -43,3 +54,591 @@ async fn test_cancel_async_wait() {
     let result = handle.await.unwrap();
     assert_eq!(result, "cancelled");
 }
+
+fn sqlite_memory_connection() -> ConnectionInfo {
+    ConnectionInfo::new(
+        "Test SQLite".to_string(),
+        DatabaseType::SQLite,
+        ConnectionConfig::File(FileConfig {
+            file_path: ":memory:".to_string(),
+            readonly: false,
+        }),
+    )
+}
+
+fn int_row(value: i64) -> QueryResultRow {
+    QueryResultRow {
+        values: vec![QueryValue::Int(value)],
+    }
+}
+
+#[tokio::test]
+async fn test_cursor_fetch_pages() {
+    let mut cursor = QueryCursor::spawn(4, |sender| async move {
+        let _ = sender
+            .send(Ok(CursorEvent::Columns(vec![QueryResultColumn {
+                name: "n".to_string(),
+                data_type: "INTEGER".to_string(),
+                nullable: true,
+            }])))
+            .await;
+        for i in 0..5 {
+            if sender.send(Ok(CursorEvent::Row(int_row(i)))).await.is_err() {
+                return;
+            }
+        }
+    });
+
+    let (rows, has_more) = cursor.fetch(2).await.unwrap();
+    assert_eq!(rows.len(), 2);
+    assert!(has_more);
+    assert_eq!(cursor.columns().len(), 1);
+
+    let (rows, has_more) = cursor.fetch(2).await.unwrap();
+    assert_eq!(rows.len(), 2);
+    assert!(has_more);
+
+    let (rows, has_more) = cursor.fetch(2).await.unwrap();
+    assert_eq!(rows.len(), 1);
+    assert!(!has_more);
+    assert_eq!(cursor.total_fetched(), 5);
+}
+
+#[tokio::test]
+async fn test_cursor_returns_fetched_rows_before_error() {
+    let mut cursor = QueryCursor::spawn(4, |sender| async move {
+        let _ = sender.send(Ok(CursorEvent::Row(int_row(1)))).await;
+        let _ = sender
+            .send(Err(QueryError {
+                code: QueryErrorCode::Unknown,
+                message: "boom".to_string(),
+                details: None,
+                native_code: None,
+            }))
+            .await;
+    });
+
+    let (rows, has_more) = cursor.fetch(10).await.unwrap();
+    assert_eq!(rows.len(), 1);
+    assert!(has_more);
+
+    let err = cursor.fetch(10).await.unwrap_err();
+    assert_eq!(err.message, "boom");
+}
+
+#[tokio::test]
+async fn test_cursor_buffer_is_capped() {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    let _cursor = QueryCursor::spawn(3, |sender| async move {
+        let mut sent = 0;
+        // バッファが満杯になるまで送信し、以降はブロックされることを確認する
+        while sent < 10 {
+            match tokio::time::timeout(
+                Duration::from_millis(50),
+                sender.send(Ok(CursorEvent::Row(int_row(sent)))),
+            )
+            .await
+            {
+                Ok(Ok(())) => sent += 1,
+                _ => break,
+            }
+        }
+        let _ = tx.send(sent);
+    });
+
+    assert_eq!(rx.await.unwrap(), 3);
+}
+
+#[tokio::test]
+async fn test_sqlite_cursor_streams_rows() {
+    let connection = sqlite_memory_connection();
+    let executor = SqliteExecutor::new(&connection).await.unwrap();
+
+    let sql = "WITH RECURSIVE n(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM n WHERE x < 25) \
+               SELECT x FROM n;";
+    let cursor = executor.open_cursor(sql, 10).await.unwrap();
+
+    let manager = QueryCursorManager::new();
+    let cursor_id = manager.register("sqlite", cursor).await.unwrap();
+
+    let page = manager.fetch(&cursor_id, 10).await.unwrap();
+    assert_eq!(page.row_count, 10);
+    assert_eq!(page.columns.len(), 1);
+    assert_eq!(page.columns[0].name, "x");
+    assert!(page.has_more);
+
+    let page = manager.fetch(&cursor_id, 10).await.unwrap();
+    assert_eq!(page.total_fetched, 20);
+    assert!(page.has_more);
+
+    let page = manager.fetch(&cursor_id, 10).await.unwrap();
+    assert_eq!(page.row_count, 5);
+    assert_eq!(page.total_fetched, 25);
+    assert!(!page.has_more);
+
+    // 読み終えたカーソルは自動的に閉じられる
+    assert!(!manager.close(&cursor_id).await);
+    assert!(manager.fetch(&cursor_id, 10).await.is_err());
+}
+
+#[tokio::test]
+async fn test_cursor_manager_limits_and_eviction() {
+    let connection = sqlite_memory_connection();
+    let executor = SqliteExecutor::new(&connection).await.unwrap();
+    let sql = "WITH RECURSIVE n(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM n WHERE x < 25) \
+               SELECT x FROM n;";
+
+    // 接続ごとの上限を超えるカーソルは開けない
+    let manager = QueryCursorManager::with_limits(Duration::from_secs(600), 1);
+    let first = manager
+        .register("a", executor.open_cursor(sql, 10).await.unwrap())
+        .await
+        .unwrap();
+    assert!(manager
+        .register("a", executor.open_cursor(sql, 10).await.unwrap())
+        .await
+        .is_err());
+    let other = manager
+        .register("b", executor.open_cursor(sql, 10).await.unwrap())
+        .await
+        .unwrap();
+
+    // 接続の更新・削除時はその接続のカーソルだけを閉じる
+    assert_eq!(manager.close_connection("a").await, 1);
+    assert!(manager.fetch(&first, 10).await.is_err());
+    assert!(manager.fetch(&other, 10).await.is_ok());
+
+    // アイドル時間を超えたカーソルは閉じられる
+    let manager = QueryCursorManager::with_limits(Duration::ZERO, 8);
+    let cursor_id = manager
+        .register("a", executor.open_cursor(sql, 10).await.unwrap())
+        .await
+        .unwrap();
+    assert_eq!(manager.close_idle().await, vec![cursor_id.clone()]);
+    assert!(manager.fetch(&cursor_id, 10).await.is_err());
+}
+
+#[tokio::test]
+async fn test_sqlite_execute_with_params() {
+    let connection = sqlite_memory_connection();
+    let executor = SqliteExecutor::new(&connection).await.unwrap();
+
+    let params = vec![
+        QueryParam::String("O'Brien; DROP TABLE users".to_string()),
+        QueryParam::Int(42),
+        QueryParam::Null,
+    ];
+    let result = executor
+        .execute_with_params(
+            "SELECT ? AS name, ? + 1 AS next, ? IS NULL AS missing",
+            &params,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(result.row_count, 1);
+    let values = &result.rows[0].values;
+    assert!(matches!(&values[0], QueryValue::String(s) if s == "O'Brien; DROP TABLE users"));
+    assert!(matches!(values[1], QueryValue::Int(43)));
+    assert!(matches!(
+        values[2],
+        QueryValue::Int(1) | QueryValue::Bool(true)
+    ));
+}
+
+#[tokio::test]
+async fn test_sqlite_execute_with_typed_params() {
+    let connection = sqlite_memory_connection();
+    let executor = SqliteExecutor::new(&connection).await.unwrap();
+
+    // SQLiteは日付・UUIDをテキストで保存するため、テキストとして比較できる形でバインドされる
+    let params = vec![
+        QueryParam::Typed(TypedParam::Date(
+            chrono::NaiveDate::from_ymd_opt(2024, 3, 15).unwrap(),
+        )),
+        QueryParam::Typed(TypedParam::Uuid(
+            "67e55044-10b1-426f-9247-bb680e5fe0c8".parse().unwrap(),
+        )),
+    ];
+    let result = executor
+        .execute_with_params(
+            "SELECT ? = '2024-03-15' AS same_date, ? = '67e55044-10b1-426f-9247-bb680e5fe0c8' AS same_uuid",
+            &params,
+        )
+        .await
+        .unwrap();
+
+    let values = &result.rows[0].values;
+    assert!(matches!(
+        values[0],
+        QueryValue::Int(1) | QueryValue::Bool(true)
+    ));
+    assert!(matches!(
+        values[1],
+        QueryValue::Int(1) | QueryValue::Bool(true)
+    ));
+}
+
+#[tokio::test]
+async fn test_sqlite_execute_script_with_semicolons_in_literals() {
+    let connection = sqlite_memory_connection();
+    let executor = SqliteExecutor::new(&connection).await.unwrap();
+
+    // 同じ接続で順次実行されるため、インメモリDBのテーブルが後続の文から見える
+    let sql = "CREATE TABLE notes (body TEXT);\n\
+               INSERT INTO notes VALUES ('a;b'); -- comment;\n\
+               SELECT body FROM notes;";
+    let result = executor.execute(sql).await.unwrap();
+
+    assert_eq!(result.row_count, 1);
+    assert!(matches!(&result.rows[0].values[0], QueryValue::String(s) if s == "a;b"));
+}
+
+#[tokio::test]
+async fn test_sqlite_script_error_position_points_to_statement() {
+    let connection = sqlite_memory_connection();
+    let executor = SqliteExecutor::new(&connection).await.unwrap();
+
+    let sql = "SELECT 1;\nSELECT * FROM missing_table;";
+    let err = executor.execute(sql).await.unwrap_err();
+
+    let position = err.details.and_then(|d| d.position).unwrap();
+    assert_eq!(position, 11);
+}
+
+#[tokio::test]
+async fn test_sqlite_execute_script_returns_each_result() {
+    let connection = sqlite_memory_connection();
+    let executor = SqliteExecutor::new(&connection).await.unwrap();
+
+    let sql = "CREATE TABLE items (id INTEGER);\n\
+               INSERT INTO items VALUES (1), (2), (3);\n\
+               SELECT count(*) FROM items;\n\
+               SELECT id FROM items WHERE id > 10;";
+    let results = executor
+        .execute_script(sql, ScriptErrorMode::StopOnError, Duration::from_secs(5))
+        .await
+        .unwrap();
+
+    assert_eq!(results.len(), 4);
+    assert!(matches!(
+        results[1].outcome,
+        StatementOutcome::Affected { affected_rows: 3 }
+    ));
+    match &results[2].outcome {
+        StatementOutcome::ResultSet { result } => {
+            assert!(matches!(result.rows[0].values[0], QueryValue::Int(3)))
+        }
+        other => panic!("unexpected outcome: {:?}", other),
+    }
+    // 0件のSELECTも結果セットとして返す
+    assert!(matches!(
+        &results[3].outcome,
+        StatementOutcome::ResultSet { result } if result.row_count == 0
+    ));
+    assert_eq!(results[2].range.text(sql), "SELECT count(*) FROM items");
+}
+
+#[tokio::test]
+async fn test_sqlite_execute_script_error_modes() {
+    let connection = sqlite_memory_connection();
+    let executor = SqliteExecutor::new(&connection).await.unwrap();
+    let sql = "SELECT 1;\nSELECT * FROM missing_table;\nSELECT 3;";
+
+    let results = executor
+        .execute_script(sql, ScriptErrorMode::StopOnError, Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert_eq!(results.len(), 2);
+    match &results[1].outcome {
+        StatementOutcome::Error { error } => {
+            assert_eq!(error.details.as_ref().and_then(|d| d.position), Some(11))
+        }
+        other => panic!("unexpected outcome: {:?}", other),
+    }
+
+    let results = executor
+        .execute_script(
+            sql,
+            ScriptErrorMode::ContinueOnError,
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+    assert_eq!(results.len(), 3);
+    assert!(matches!(results[1].outcome, StatementOutcome::Error { .. }));
+    assert!(matches!(
+        results[2].outcome,
+        StatementOutcome::ResultSet { .. }
+    ));
+    assert_eq!(results[2].index, 2);
+}
+
+#[tokio::test]
+async fn test_sqlite_declared_date_columns_are_typed() {
+    let connection = sqlite_memory_connection();
+    let executor = SqliteExecutor::new(&connection).await.unwrap();
+
+    let sql = "CREATE TABLE events (day DATE, at DATETIME, note TEXT);\n\
+               INSERT INTO events VALUES ('2024-03-15', '2024-03-15 09:30:00', 'x'), ('not a date', NULL, 'y');\n\
+               SELECT day, at, note FROM events;";
+    let results = executor
+        .execute_script(sql, ScriptErrorMode::StopOnError, Duration::from_secs(5))
+        .await
+        .unwrap();
+
+    let result = match &results[2].outcome {
+        StatementOutcome::ResultSet { result } => result,
+        other => panic!("unexpected outcome: {:?}", other),
+    };
+    let date = chrono::NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+    assert_eq!(result.rows[0].values[0], QueryValue::Date(date));
+    assert_eq!(
+        result.rows[0].values[1],
+        QueryValue::Timestamp(date.and_hms_opt(9, 30, 0).unwrap())
+    );
+    // 解釈できない値は格納値のまま
+    assert_eq!(
+        result.rows[1].values[0],
+        QueryValue::String("not a date".to_string())
+    );
+}
+
+#[test]
+fn test_running_queries_guard_unregisters() {
+    let running = RunningQueries::new();
+    {
+        let _guard = running.register("q-1".to_string(), 42);
+        assert_eq!(running.get("q-1"), Some(42));
+    }
+    assert_eq!(running.get("q-1"), None);
+}
+
+#[tokio::test]
+async fn test_sqlite_cancel_interrupts_running_query() {
+    let connection = sqlite_memory_connection();
+    let executor = Arc::new(SqliteExecutor::new(&connection).await.unwrap());
+
+    // 実行中でないクエリIDはキャンセル対象にならない
+    assert!(!executor.cancel("missing").await.unwrap());
+
+    let sql = "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n) \
+               SELECT count(*) FROM n";
+    let running = Arc::clone(&executor);
+    let handle = tokio::spawn(
+        CURRENT_QUERY_ID.scope("q-1".to_string(), async move { running.execute(sql).await }),
+    );
+
+    let mut cancelled = false;
+    for _ in 0..50 {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        if executor.cancel("q-1").await.unwrap() {
+            cancelled = true;
+            break;
+        }
+    }
+    assert!(cancelled);
+
+    let err = tokio::time::timeout(Duration::from_secs(5), handle)
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap_err();
+    assert!(matches!(err.code, QueryErrorCode::QueryCancelled));
+
+    // 割り込み後も同じ接続で次の文を実行できる
+    let result = executor.execute("SELECT 1").await.unwrap();
+    assert_eq!(result.row_count, 1);
+}
+
+fn idle_pool_manager() -> ConnectionPoolManager {
+    ConnectionPoolManager::with_settings(PoolSettings {
+        max_connections: 3,
+        idle_timeout_seconds: 0,
+        health_check_interval_seconds: 60,
+    })
+}
+
+/// 接続のプールへの返却は非同期に行われるため、使用中の接続がなくなるまで待つ
+async fn wait_pool_released(manager: &ConnectionPoolManager) {
+    for _ in 0..50 {
+        if manager.stats().await.iter().all(|stats| stats.in_use == 0) {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    panic!("pool connections were not released");
+}
+
+#[tokio::test]
+async fn test_pool_manager_evicts_idle_pool() {
+    let manager = idle_pool_manager();
+    let connection = sqlite_memory_connection();
+
+    let executor = manager
+        .get_or_create("conn-1", &connection, None)
+        .await
+        .unwrap();
+    executor.execute("SELECT 1").await.unwrap();
+    wait_pool_released(&manager).await;
+
+    let stats = manager.stats().await;
+    assert_eq!(stats.len(), 1);
+    assert_eq!(stats[0].connection_id, "conn-1");
+    assert_eq!(stats[0].max_connections, 3);
+
+    assert_eq!(manager.run_maintenance().await, vec!["conn-1".to_string()]);
+    assert!(manager.stats().await.is_empty());
+
+    // 次回の取得で再接続される
+    let executor = manager
+        .get_or_create("conn-1", &connection, None)
+        .await
+        .unwrap();
+    assert_eq!(executor.execute("SELECT 1").await.unwrap().row_count, 1);
+}
+
+#[tokio::test]
+async fn test_pool_manager_keeps_pool_in_use() {
+    let manager = idle_pool_manager();
+    let executor = manager
+        .get_or_create("conn-1", &sqlite_memory_connection(), None)
+        .await
+        .unwrap();
+
+    // トランザクションが接続を保持している間は解放しない
+    let transaction = executor.begin_transaction().await.unwrap();
+    assert!(manager.run_maintenance().await.is_empty());
+    assert_eq!(manager.stats().await[0].in_use, 1);
+
+    transaction.rollback().await.unwrap();
+    wait_pool_released(&manager).await;
+}
+
+#[tokio::test]
+async fn test_pool_manager_reconnects_broken_pool() {
+    let manager = ConnectionPoolManager::new();
+    let connection = sqlite_memory_connection();
+
+    let broken = manager
+        .get_or_create("conn-1", &connection, None)
+        .await
+        .unwrap();
+    broken.close().await.unwrap();
+    assert!(broken.ping().await.is_err());
+
+    // 疎通確認に失敗したプールは取り除かれ、次回の取得で作り直される
+    assert_eq!(manager.run_maintenance().await, vec!["conn-1".to_string()]);
+    let executor = manager
+        .get_or_create("conn-1", &connection, None)
+        .await
+        .unwrap();
+    assert!(!Arc::ptr_eq(&broken, &executor));
+    executor.ping().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_sqlite_on_connect_sql_runs_for_each_connection() {
+    let mut connection = sqlite_memory_connection();
+    connection.on_connect_sql =
+        Some("CREATE TEMP TABLE session_marker AS SELECT 42 AS v".to_string());
+    let executor = SqliteExecutor::new(&connection).await.unwrap();
+
+    // 一時テーブルは接続ごとに作られるため、複数の接続を同時に使っても参照できる
+    let first = executor.begin_transaction().await.unwrap();
+    let second = executor.begin_transaction().await.unwrap();
+    let result = executor
+        .execute("SELECT v FROM session_marker")
+        .await
+        .unwrap();
+    assert!(matches!(result.rows[0].values[0], QueryValue::Int(42)));
+
+    first.rollback().await.unwrap();
+    second.rollback().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_read_only_connection_refuses_mutations() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("report.db");
+    std::fs::File::create(&path).unwrap();
+    let mut connection = ConnectionInfo::new(
+        "Report".to_string(),
+        DatabaseType::SQLite,
+        ConnectionConfig::File(FileConfig {
+            file_path: path.to_string_lossy().to_string(),
+            readonly: false,
+        }),
+    );
+    let writer = SqliteExecutor::new(&connection).await.unwrap();
+    writer
+        .execute_mutation("CREATE TABLE t (id INTEGER)")
+        .await
+        .unwrap();
+    writer.close().await.unwrap();
+
+    connection.read_only = true;
+    let executor = QueryExecutorFactory::create(&connection, None, &PoolSettings::default())
+        .await
+        .unwrap();
+    assert_eq!(
+        executor
+            .execute("SELECT COUNT(*) FROM t")
+            .await
+            .unwrap()
+            .row_count,
+        1
+    );
+
+    let error = executor
+        .execute_mutation("INSERT INTO t VALUES (1)")
+        .await
+        .unwrap_err();
+    assert!(matches!(error.code, QueryErrorCode::PermissionDenied));
+
+    let mut transaction = executor.begin_transaction().await.unwrap();
+    let error = transaction
+        .execute_mutation("DELETE FROM t")
+        .await
+        .unwrap_err();
+    assert!(matches!(error.code, QueryErrorCode::PermissionDenied));
+    // 読み取り専用でない文はどの経路でも実行前に拒否する
+    let results = transaction
+        .execute_script(
+            "SELECT 1; DELETE FROM t",
+            ScriptErrorMode::StopOnError,
+            Duration::from_secs(5),
+        )
+        .await;
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].index, 1);
+    assert!(matches!(
+        &results[0].outcome,
+        StatementOutcome::Error { error } if matches!(error.code, QueryErrorCode::PermissionDenied)
+    ));
+    transaction.rollback().await.unwrap();
+
+    let error = executor
+        .execute_script(
+            "SELECT 1; INSERT INTO t VALUES (1)",
+            ScriptErrorMode::StopOnError,
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(error.code, QueryErrorCode::PermissionDenied));
+    for sql in ["DELETE FROM t", "PRAGMA query_only = OFF"] {
+        let error = executor.execute(sql).await.unwrap_err();
+        assert!(matches!(error.code, QueryErrorCode::PermissionDenied));
+    }
+    assert!(executor
+        .open_cursor("UPDATE t SET id = 2", 10)
+        .await
+        .is_err());
+
+    // セッションも query_only で開くため、DB側でも書き込めない
+    assert_eq!(
+        executor.execute("PRAGMA query_only").await.unwrap().rows[0].values,
+        vec![QueryValue::Int(1)]
+    );
+}
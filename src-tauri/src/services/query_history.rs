@@ -59,6 +59,7 @@ impl QueryHistoryService {
             result_count: request.result_count,
             execution_time_ms: request.execution_time_ms,
             error_message: request.error_message,
+            undo_sql: request.undo_sql,
         };
 
         // 先頭に追加（最新が最初）
@@ -0,0 +1,912 @@
+use crate::models::database_structure::SchemaSummary;
+use crate::models::query_plan::{PlanNodeKind, PlanWarning, QueryPlan, QueryPlanNode};
+use crate::models::query_result::{QueryError, QueryErrorCode, QueryResult, QueryValue};
+use crate::services::query_executor::QueryExecutor;
+use crate::sql::statement_splitter::StatementSplitter;
+use serde_json::Value;
+use sqlparser::ast::{Query, SetExpr, Statement, TableFactor, TableWithJoins};
+use sqlparser::dialect::{Dialect, DuckDbDialect, MySqlDialect, PostgreSqlDialect, SQLiteDialect};
+use sqlparser::parser::Parser;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// 全件走査を注意する対象とするテーブルの行数
+pub const LARGE_TABLE_ROWS: f64 = 10_000.0;
+
+/// 入れ子ループを注意する対象とする推定行数（外側 × 内側）
+pub const NESTED_LOOP_ROWS: f64 = 100_000.0;
+
+/// テーブルの推定行数をキャッシュする期間
+pub const TABLE_ROWS_CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// テーブル名ごとの推定行数
+pub type TableRowCounts = HashMap<String, f64>;
+
+/// 接続ごとのテーブル推定行数のキャッシュ
+///
+/// 実行計画を取得するたびにインスペクターの接続を作り直さないよう、一定期間保持する
+pub struct TableRowCountCache {
+    entries: Mutex<HashMap<String, (Instant, Arc<TableRowCounts>)>>,
+    ttl: Duration,
+}
+
+impl TableRowCountCache {
+    pub fn new() -> Self {
+        Self::with_ttl(TABLE_ROWS_CACHE_TTL)
+    }
+
+    /// 保持期間を指定して作成
+    pub fn with_ttl(ttl: Duration) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            ttl,
+        }
+    }
+
+    /// キャッシュから取得（期限切れなら削除してNoneを返す）
+    pub fn get(&self, connection_id: &str) -> Option<Arc<TableRowCounts>> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(connection_id) {
+            Some((cached_at, rows)) if cached_at.elapsed() < self.ttl => Some(Arc::clone(rows)),
+            Some(_) => {
+                entries.remove(connection_id);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// キャッシュに保存
+    pub fn insert(&self, connection_id: &str, rows: TableRowCounts) -> Arc<TableRowCounts> {
+        let rows = Arc::new(rows);
+        self.entries.lock().unwrap().insert(
+            connection_id.to_string(),
+            (Instant::now(), Arc::clone(&rows)),
+        );
+        rows
+    }
+
+    /// 接続のキャッシュを破棄
+    pub fn invalidate(&self, connection_id: &str) {
+        self.entries.lock().unwrap().remove(connection_id);
+    }
+}
+
+impl Default for TableRowCountCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 実行計画の取得と解析
+///
+/// DBごとの EXPLAIN 出力を `QueryPlanNode` のツリーに正規化し、注意すべき箇所を検出する
+pub struct QueryPlanAnalyzer;
+
+impl QueryPlanAnalyzer {
+    /// 実行計画を取得する
+    ///
+    /// `analyze` の場合は文を実際に実行するため、トランザクション内で実行してロールバックする。
+    /// `table_rows` はテーブルの推定行数（`table_row_counts` で作成）
+    pub async fn explain(
+        executor: &dyn QueryExecutor,
+        dialect: &str,
+        sql: &str,
+        analyze: bool,
+        table_rows: &HashMap<String, f64>,
+    ) -> Result<QueryPlan, QueryError> {
+        let start = Instant::now();
+
+        // SQL Serverは EXPLAIN 文がなく、実行計画はXMLのSHOWPLANで返るため未対応
+        if dialect == "sqlserver" {
+            return Err(QueryError {
+                code: QueryErrorCode::Unknown,
+                message: "Execution plans are not supported for SQL Server".to_string(),
+                details: None,
+                native_code: None,
+            });
+        }
+
+        let statements = StatementSplitter::new(dialect).split(sql);
+        if statements.len() != 1 {
+            return Err(QueryError {
+                code: QueryErrorCode::SyntaxError,
+                message: "EXPLAIN requires exactly one statement".to_string(),
+                details: None,
+                native_code: None,
+            });
+        }
+        let statement = statements[0].text(sql);
+
+        // SQLiteは EXPLAIN QUERY PLAN のみ（実測値は取得できない）
+        let analyze = analyze && dialect != "sqlite";
+        let explain_sql = Self::explain_sql(dialect, statement, analyze);
+
+        let result = if analyze {
+            let mut session = executor.begin_transaction().await?;
+            let result = session.execute_with_params(&explain_sql, &[]).await;
+            session.rollback().await?;
+            result?
+        } else {
+            executor.execute(&explain_sql).await?
+        };
+
+        let (mut root, raw) =
+            Self::parse_result(dialect, analyze, &result).map_err(|e| QueryError {
+                code: QueryErrorCode::Unknown,
+                message: format!("Failed to parse execution plan: {}", e),
+                details: None,
+                native_code: None,
+            })?;
+        // MySQL・SQLiteは別名で出力するため、テーブル名に置き換えてから判定する
+        resolve_aliases(&mut root, &Self::table_aliases(dialect, statement));
+        let warnings = Self::detect_warnings(&root, table_rows);
+
+        Ok(QueryPlan {
+            root,
+            analyzed: analyze,
+            warnings,
+            raw,
+            execution_time_ms: start.elapsed().as_millis() as u64,
+        })
+    }
+
+    /// 方言ごとの EXPLAIN 文を組み立てる
+    pub fn explain_sql(dialect: &str, statement: &str, analyze: bool) -> String {
+        match (dialect, analyze) {
+            ("mysql", true) => format!("EXPLAIN ANALYZE {}", statement),
+            ("mysql", false) => format!("EXPLAIN FORMAT=JSON {}", statement),
+            // MariaDBは EXPLAIN ANALYZE に対応せず、ANALYZE 文でJSONを返す
+            ("mariadb", true) => format!("ANALYZE FORMAT=JSON {}", statement),
+            ("mariadb", false) => format!("EXPLAIN FORMAT=JSON {}", statement),
+            ("sqlite", _) => format!("EXPLAIN QUERY PLAN {}", statement),
+            ("duckdb", true) => format!("EXPLAIN (ANALYZE, FORMAT json) {}", statement),
+            ("duckdb", false) => format!("EXPLAIN (FORMAT json) {}", statement),
+            (_, true) => format!("EXPLAIN (ANALYZE, FORMAT JSON) {}", statement),
+            (_, false) => format!("EXPLAIN (FORMAT JSON) {}", statement),
+        }
+    }
+
+    /// EXPLAIN の結果を解析し、ルートノードと元の出力を返す
+    pub fn parse_result(
+        dialect: &str,
+        analyze: bool,
+        result: &QueryResult,
+    ) -> Result<(QueryPlanNode, String), String> {
+        match dialect {
+            "sqlite" => {
+                let column = |name: &str| result.columns.iter().position(|c| c.name == name);
+                let (id, parent, detail) = match (column("id"), column("parent"), column("detail"))
+                {
+                    (Some(id), Some(parent), Some(detail)) => (id, parent, detail),
+                    _ => return Err("unexpected EXPLAIN QUERY PLAN columns".to_string()),
+                };
+
+                let rows: Vec<(i64, i64, String)> = result
+                    .rows
+                    .iter()
+                    .map(|row| {
+                        (
+                            value_as_i64(&row.values[id]),
+                            value_as_i64(&row.values[parent]),
+                            value_as_string(&row.values[detail]),
+                        )
+                    })
+                    .collect();
+                let raw = rows
+                    .iter()
+                    .map(|(id, parent, detail)| format!("{}|{}|{}", id, parent, detail))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+
+                Ok((Self::parse_sqlite(&rows), raw))
+            }
+            "duckdb" => {
+                // DuckDBは (explain_key, explain_value) の行で返し、計画は最後の列に入る
+                let raw = result
+                    .rows
+                    .iter()
+                    .filter_map(|row| row.values.last())
+                    .map(value_as_string)
+                    .collect::<Vec<_>>()
+                    .join("\n");
+
+                Ok((Self::parse_duckdb_json(&raw)?, raw))
+            }
+            _ => {
+                // PostgreSQL・MySQLは1行1列で計画全体を返す
+                let raw = result
+                    .rows
+                    .iter()
+                    .filter_map(|row| row.values.first())
+                    .map(value_as_string)
+                    .collect::<Vec<_>>()
+                    .join("\n");
+
+                let root = match (dialect, analyze) {
+                    ("mysql", true) => Self::parse_mysql_tree(&raw)?,
+                    ("mysql", false) | ("mariadb", _) => Self::parse_mysql_json(&raw)?,
+                    _ => Self::parse_postgres_json(&raw)?,
+                };
+                Ok((root, raw))
+            }
+        }
+    }
+
+    /// PostgreSQLの `EXPLAIN (FORMAT JSON)` を解析
+    pub fn parse_postgres_json(raw: &str) -> Result<QueryPlanNode, String> {
+        let json: Value = serde_json::from_str(raw).map_err(|e| e.to_string())?;
+        let plan = json
+            .get(0)
+            .and_then(|entry| entry.get("Plan"))
+            .ok_or_else(|| "missing \"Plan\"".to_string())?;
+
+        Ok(postgres_node(plan))
+    }
+
+    /// MySQLの `EXPLAIN FORMAT=JSON` を解析（MariaDBの `ANALYZE FORMAT=JSON` も同じ形式）
+    pub fn parse_mysql_json(raw: &str) -> Result<QueryPlanNode, String> {
+        let json: Value = serde_json::from_str(raw).map_err(|e| e.to_string())?;
+        let block = json
+            .get("query_block")
+            .ok_or_else(|| "missing \"query_block\"".to_string())?;
+
+        Ok(mysql_query_block(block))
+    }
+
+    /// DuckDBの `EXPLAIN (FORMAT json)` を解析
+    ///
+    /// EXPLAINは演算子の配列、EXPLAIN ANALYZEはプロファイル（`children` に演算子を持つ）を返す
+    pub fn parse_duckdb_json(raw: &str) -> Result<QueryPlanNode, String> {
+        let json: Value = serde_json::from_str(raw).map_err(|e| e.to_string())?;
+        let mut roots: Vec<QueryPlanNode> = match &json {
+            Value::Array(nodes) => nodes.iter().map(duckdb_node).collect(),
+            Value::Object(_) => json
+                .get("children")
+                .and_then(Value::as_array)
+                .map(|nodes| nodes.iter().map(duckdb_node).collect())
+                .unwrap_or_default(),
+            _ => return Err("unexpected DuckDB plan".to_string()),
+        };
+
+        match roots.len() {
+            0 => Err("empty plan".to_string()),
+            1 => Ok(roots.remove(0)),
+            _ => {
+                let mut root = QueryPlanNode::new("Query", PlanNodeKind::Other);
+                root.children = roots;
+                Ok(root)
+            }
+        }
+    }
+
+    /// MySQLの `EXPLAIN ANALYZE`（ツリー形式）を解析
+    pub fn parse_mysql_tree(raw: &str) -> Result<QueryPlanNode, String> {
+        // (インデント, ノード) のスタック。浅いノードが現れたら深いノードを親に付け替える
+        let mut stack: Vec<(usize, QueryPlanNode)> = Vec::new();
+        let mut roots = Vec::new();
+
+        for line in raw.lines() {
+            let trimmed = line.trim_start();
+            let body = match trimmed.strip_prefix("-> ") {
+                Some(body) => body,
+                None => continue,
+            };
+            let depth = line.len() - trimmed.len();
+
+            while stack.last().is_some_and(|(d, _)| *d >= depth) {
+                let (_, done) = stack.pop().unwrap();
+                attach_child(&mut stack, &mut roots, done);
+            }
+            stack.push((depth, mysql_tree_node(body)));
+        }
+        while let Some((_, done)) = stack.pop() {
+            attach_child(&mut stack, &mut roots, done);
+        }
+
+        match roots.len() {
+            0 => Err("empty plan".to_string()),
+            1 => Ok(roots.remove(0)),
+            _ => {
+                let mut root = QueryPlanNode::new("Query", PlanNodeKind::Other);
+                root.children = roots;
+                Ok(root)
+            }
+        }
+    }
+
+    /// SQLiteの `EXPLAIN QUERY PLAN` の行 (id, parent, detail) を解析
+    pub fn parse_sqlite(rows: &[(i64, i64, String)]) -> QueryPlanNode {
+        let mut root = QueryPlanNode::new("Query Plan", PlanNodeKind::Other);
+        root.children = sqlite_children(rows, 0);
+        root
+    }
+
+    /// SQL中のテーブル別名（小文字）→ (スキーマ, テーブル名)
+    ///
+    /// 解析できない場合は空を返す
+    pub fn table_aliases(
+        dialect: &str,
+        statement: &str,
+    ) -> HashMap<String, (Option<String>, String)> {
+        let dialect_box: Box<dyn Dialect> = match dialect {
+            "mysql" | "mariadb" => Box::new(MySqlDialect {}),
+            "sqlite" => Box::new(SQLiteDialect {}),
+            "duckdb" => Box::new(DuckDbDialect {}),
+            _ => Box::new(PostgreSqlDialect {}),
+        };
+
+        let mut aliases = HashMap::new();
+        if let Ok(statements) = Parser::parse_sql(&*dialect_box, statement) {
+            for statement in &statements {
+                if let Statement::Query(query) = statement {
+                    collect_query_aliases(query, &mut aliases);
+                }
+            }
+        }
+        aliases
+    }
+
+    /// インスペクターのテーブルサマリーから推定行数の表を作成する
+    ///
+    /// キーはテーブル名と「スキーマ.テーブル名」（小文字）
+    pub fn table_row_counts(schemas: &[SchemaSummary]) -> HashMap<String, f64> {
+        let mut counts = HashMap::new();
+        for schema in schemas {
+            for table in &schema.tables {
+                let rows = match table.estimated_row_count {
+                    Some(rows) if rows >= 0 => rows as f64,
+                    _ => continue,
+                };
+                let name = table.name.to_lowercase();
+                let qualified = format!("{}.{}", schema.name.to_lowercase(), name);
+                counts.insert(qualified, rows);
+
+                let entry = counts.entry(name).or_insert(rows);
+                *entry = entry.max(rows);
+            }
+        }
+        counts
+    }
+
+    /// 大きなテーブルの全件走査と、推定行数の多い入れ子ループを検出する
+    pub fn detect_warnings(
+        root: &QueryPlanNode,
+        table_rows: &HashMap<String, f64>,
+    ) -> Vec<PlanWarning> {
+        let mut warnings = Vec::new();
+        collect_warnings(root, table_rows, &mut warnings);
+        warnings
+    }
+}
+
+fn collect_warnings(
+    node: &QueryPlanNode,
+    table_rows: &HashMap<String, f64>,
+    warnings: &mut Vec<PlanWarning>,
+) {
+    match node.kind {
+        PlanNodeKind::SeqScan => {
+            if let Some(relation) = &node.relation {
+                // インスペクターの推定行数を優先し、なければ計画上の推定行数を使う
+                let rows = lookup_table_rows(table_rows, node.schema.as_deref(), relation)
+                    .or(node.estimated_rows);
+                if let Some(rows) = rows.filter(|rows| *rows >= LARGE_TABLE_ROWS) {
+                    warnings.push(PlanWarning {
+                        code: "seq_scan_large_table".to_string(),
+                        message: format!(
+                            "大きなテーブル {} (約{}行) を全件走査しています",
+                            relation, rows as i64
+                        ),
+                        relation: Some(relation.clone()),
+                    });
+                }
+            }
+        }
+        PlanNodeKind::NestedLoop => {
+            let product = node
+                .children
+                .iter()
+                .map(|child| child.estimated_rows)
+                .try_fold(1.0, |acc, rows| rows.map(|rows| acc * rows));
+            let rows = match (node.estimated_rows, product) {
+                (Some(own), Some(product)) => Some(own.max(product)),
+                (own, product) => own.or(product),
+            };
+            if let Some(rows) = rows.filter(|rows| *rows >= NESTED_LOOP_ROWS) {
+                warnings.push(PlanWarning {
+                    code: "nested_loop_high_rows".to_string(),
+                    message: format!(
+                        "入れ子ループの推定行数が多くなっています (約{}行)",
+                        rows as i64
+                    ),
+                    relation: None,
+                });
+            }
+        }
+        _ => {}
+    }
+
+    for child in &node.children {
+        collect_warnings(child, table_rows, warnings);
+    }
+}
+
+fn collect_query_aliases(query: &Query, aliases: &mut HashMap<String, (Option<String>, String)>) {
+    if let Some(with) = &query.with {
+        for cte in &with.cte_tables {
+            collect_query_aliases(&cte.query, aliases);
+        }
+    }
+    collect_set_expr_aliases(&query.body, aliases);
+}
+
+fn collect_set_expr_aliases(
+    body: &SetExpr,
+    aliases: &mut HashMap<String, (Option<String>, String)>,
+) {
+    match body {
+        SetExpr::Select(select) => {
+            for table in &select.from {
+                collect_table_aliases(table, aliases);
+            }
+        }
+        SetExpr::Query(query) => collect_query_aliases(query, aliases),
+        SetExpr::SetOperation { left, right, .. } => {
+            collect_set_expr_aliases(left, aliases);
+            collect_set_expr_aliases(right, aliases);
+        }
+        _ => {}
+    }
+}
+
+fn collect_table_aliases(
+    table: &TableWithJoins,
+    aliases: &mut HashMap<String, (Option<String>, String)>,
+) {
+    collect_table_factor_aliases(&table.relation, aliases);
+    for join in &table.joins {
+        collect_table_factor_aliases(&join.relation, aliases);
+    }
+}
+
+fn collect_table_factor_aliases(
+    factor: &TableFactor,
+    aliases: &mut HashMap<String, (Option<String>, String)>,
+) {
+    match factor {
+        TableFactor::Table {
+            name,
+            alias: Some(alias),
+            ..
+        } => {
+            let mut parts: Vec<String> = name.0.iter().map(|ident| ident.value.clone()).collect();
+            if let Some(table) = parts.pop() {
+                aliases.insert(alias.name.value.to_lowercase(), (parts.pop(), table));
+            }
+        }
+        TableFactor::Derived { subquery, .. } => collect_query_aliases(subquery, aliases),
+        TableFactor::NestedJoin {
+            table_with_joins, ..
+        } => collect_table_aliases(table_with_joins, aliases),
+        _ => {}
+    }
+}
+
+/// 別名で出力されたノードの対象テーブルを実際のテーブル名に置き換える
+fn resolve_aliases(node: &mut QueryPlanNode, aliases: &HashMap<String, (Option<String>, String)>) {
+    if let Some(relation) = &node.relation {
+        if let Some((schema, table)) = aliases.get(&relation.to_lowercase()) {
+            if node.schema.is_none() {
+                node.schema = schema.clone();
+            }
+            node.relation = Some(table.clone());
+        }
+    }
+    for child in &mut node.children {
+        resolve_aliases(child, aliases);
+    }
+}
+
+fn lookup_table_rows(
+    table_rows: &HashMap<String, f64>,
+    schema: Option<&str>,
+    relation: &str,
+) -> Option<f64> {
+    let relation = relation.to_lowercase();
+    schema
+        .and_then(|schema| table_rows.get(&format!("{}.{}", schema.to_lowercase(), relation)))
+        .or_else(|| table_rows.get(&relation))
+        .copied()
+}
+
+fn value_as_string(value: &QueryValue) -> String {
+    match value {
+        QueryValue::String(s) => s.clone(),
+        QueryValue::Int(i) => i.to_string(),
+        QueryValue::Float(f) => f.to_string(),
+        QueryValue::Bool(b) => b.to_string(),
+        QueryValue::Bytes(bytes) => String::from_utf8_lossy(bytes).to_string(),
+        QueryValue::Json(json) => json.to_string(),
+        _ => String::new(),
+    }
+}
+
+fn value_as_i64(value: &QueryValue) -> i64 {
+    match value {
+        QueryValue::Int(i) => *i,
+        QueryValue::String(s) => s.parse().unwrap_or(0),
+        _ => 0,
+    }
+}
+
+/// JSONの数値フィールド（MySQLは数値を文字列で返すことがある）
+fn number_field(value: &Value, key: &str) -> Option<f64> {
+    match value.get(key)? {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn string_field(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+fn postgres_node(plan: &Value) -> QueryPlanNode {
+    let node_type = plan
+        .get("Node Type")
+        .and_then(Value::as_str)
+        .unwrap_or("Unknown");
+    let kind = match node_type {
+        "Seq Scan" => PlanNodeKind::SeqScan,
+        "Index Scan" | "Index Only Scan" | "Bitmap Index Scan" | "Bitmap Heap Scan" => {
+            PlanNodeKind::IndexScan
+        }
+        "Nested Loop" => PlanNodeKind::NestedLoop,
+        "Hash Join" => PlanNodeKind::HashJoin,
+        "Merge Join" => PlanNodeKind::MergeJoin,
+        "Sort" | "Incremental Sort" => PlanNodeKind::Sort,
+        "Aggregate" => PlanNodeKind::Aggregate,
+        _ => PlanNodeKind::Other,
+    };
+
+    let mut node = QueryPlanNode::new(node_type, kind);
+    node.relation = string_field(plan, "Relation Name");
+    node.schema = string_field(plan, "Schema");
+    node.index_name = string_field(plan, "Index Name");
+    node.estimated_rows = number_field(plan, "Plan Rows");
+    node.estimated_cost = number_field(plan, "Total Cost");
+    node.actual_rows = number_field(plan, "Actual Rows");
+    node.actual_time_ms = number_field(plan, "Actual Total Time");
+    node.actual_loops = number_field(plan, "Actual Loops");
+
+    let details: Vec<String> = [
+        "Join Type",
+        "Strategy",
+        "Index Cond",
+        "Hash Cond",
+        "Merge Cond",
+        "Join Filter",
+        "Filter",
+    ]
+    .iter()
+    .filter_map(|key| string_field(plan, key).map(|value| format!("{}: {}", key, value)))
+    .collect();
+    if !details.is_empty() {
+        node.detail = Some(details.join(", "));
+    }
+
+    node.children = plan
+        .get("Plans")
+        .and_then(Value::as_array)
+        .map(|plans| plans.iter().map(postgres_node).collect())
+        .unwrap_or_default();
+    node
+}
+
+fn duckdb_node(plan: &Value) -> QueryPlanNode {
+    // EXPLAINは `name`、EXPLAIN ANALYZEは `operator_name`（末尾に空白が付く場合がある）
+    let name = string_field(plan, "name")
+        .or_else(|| string_field(plan, "operator_name"))
+        .unwrap_or_else(|| "Unknown".to_string());
+    let name = name.trim().to_string();
+    let extra = plan.get("extra_info").cloned().unwrap_or(Value::Null);
+
+    let kind = match name.as_str() {
+        "SEQ_SCAN" | "TABLE_SCAN" => {
+            if string_field(&extra, "Type").is_some_and(|t| t.contains("Index")) {
+                PlanNodeKind::IndexScan
+            } else {
+                PlanNodeKind::SeqScan
+            }
+        }
+        "INDEX_SCAN" => PlanNodeKind::IndexScan,
+        "NESTED_LOOP_JOIN" | "BLOCKWISE_NL_JOIN" | "CROSS_PRODUCT" => PlanNodeKind::NestedLoop,
+        "HASH_JOIN" => PlanNodeKind::HashJoin,
+        "PIECEWISE_MERGE_JOIN" => PlanNodeKind::MergeJoin,
+        "ORDER_BY" | "TOP_N" => PlanNodeKind::Sort,
+        "HASH_GROUP_BY" | "PERFECT_HASH_GROUP_BY" | "UNGROUPED_AGGREGATE" => {
+            PlanNodeKind::Aggregate
+        }
+        _ => PlanNodeKind::Other,
+    };
+
+    let mut node = QueryPlanNode::new(name, kind);
+    // テーブル名は `catalog.schema.table` で出力される
+    node.relation = string_field(&extra, "Table")
+        .map(|table| table.rsplit('.').next().unwrap_or_default().to_string());
+    node.estimated_rows = number_field(&extra, "Estimated Cardinality");
+    node.actual_rows = number_field(plan, "operator_cardinality");
+    // 所要時間は秒で出力される
+    node.actual_time_ms = number_field(plan, "operator_timing").map(|seconds| seconds * 1000.0);
+
+    let details: Vec<String> = ["Join Type", "Conditions", "Filters", "Order By"]
+        .iter()
+        .filter_map(|key| {
+            extra.get(*key).map(|value| match value {
+                Value::String(s) => format!("{}: {}", key, s),
+                Value::Array(items) => format!(
+                    "{}: {}",
+                    key,
+                    items
+                        .iter()
+                        .map(|item| {
+                            item.as_str()
+                                .map(str::to_string)
+                                .unwrap_or_else(|| item.to_string())
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                other => format!("{}: {}", key, other),
+            })
+        })
+        .collect();
+    if !details.is_empty() {
+        node.detail = Some(details.join(", "));
+    }
+
+    node.children = plan
+        .get("children")
+        .and_then(Value::as_array)
+        .map(|plans| plans.iter().map(duckdb_node).collect())
+        .unwrap_or_default();
+    node
+}
+
+fn mysql_query_block(block: &Value) -> QueryPlanNode {
+    let mut node = QueryPlanNode::new("Query Block", PlanNodeKind::Other);
+    node.estimated_cost = block
+        .get("cost_info")
+        .and_then(|cost| number_field(cost, "query_cost"));
+    node.detail = block
+        .get("select_id")
+        .map(|id| format!("select_id: {}", id));
+    node.children = mysql_children(block);
+    node
+}
+
+/// MySQLのJSON計画から子ノードを集める
+fn mysql_children(value: &Value) -> Vec<QueryPlanNode> {
+    let mut children = Vec::new();
+
+    if let Some(block) = value.get("query_block") {
+        children.push(mysql_query_block(block));
+    }
+    if let Some(table) = value.get("table") {
+        children.push(mysql_table(table));
+    }
+    if let Some(items) = value.get("nested_loop").and_then(Value::as_array) {
+        let mut node = QueryPlanNode::new("Nested Loop", PlanNodeKind::NestedLoop);
+        node.children = items.iter().flat_map(mysql_children).collect();
+        children.push(node);
+    }
+
+    // MariaDBはジョインバッファ・一時テーブルを経由してテーブルを出力する
+    for key in ["block-nl-join", "temporary_table"] {
+        if let Some(inner) = value.get(key) {
+            children.extend(mysql_children(inner));
+        }
+    }
+
+    let operations = [
+        ("ordering_operation", "Sort", PlanNodeKind::Sort),
+        ("filesort", "Sort", PlanNodeKind::Sort),
+        ("grouping_operation", "Aggregate", PlanNodeKind::Aggregate),
+        ("duplicates_removal", "Distinct", PlanNodeKind::Other),
+        ("windowing", "Window", PlanNodeKind::Other),
+    ];
+    for (key, operation, kind) in operations {
+        if let Some(inner) = value.get(key) {
+            let mut node = QueryPlanNode::new(operation, kind);
+            let flags: Vec<&str> = ["using_filesort", "using_temporary_table"]
+                .into_iter()
+                .filter(|flag| inner.get(*flag).and_then(Value::as_bool) == Some(true))
+                .collect();
+            if !flags.is_empty() {
+                node.detail = Some(flags.join(", "));
+            }
+            node.children = mysql_children(inner);
+            children.push(node);
+        }
+    }
+
+    if let Some(union) = value.get("union_result") {
+        let mut node = QueryPlanNode::new("Union", PlanNodeKind::Other);
+        node.children = union
+            .get("query_specifications")
+            .and_then(Value::as_array)
+            .map(|specs| specs.iter().flat_map(mysql_children).collect())
+            .unwrap_or_default();
+        children.push(node);
+    }
+    if let Some(subquery) = value.get("materialized_from_subquery") {
+        children.extend(mysql_children(subquery));
+    }
+    for key in ["attached_subqueries", "optimized_away_subqueries"] {
+        if let Some(items) = value.get(key).and_then(Value::as_array) {
+            children.extend(items.iter().flat_map(mysql_children));
+        }
+    }
+
+    children
+}
+
+fn mysql_table(table: &Value) -> QueryPlanNode {
+    let access_type = table
+        .get("access_type")
+        .and_then(Value::as_str)
+        .unwrap_or("");
+    let (operation, kind) = match access_type {
+        "ALL" => ("Full Table Scan".to_string(), PlanNodeKind::SeqScan),
+        "index" => ("Full Index Scan".to_string(), PlanNodeKind::IndexScan),
+        "range" => ("Index Range Scan".to_string(), PlanNodeKind::IndexScan),
+        "ref" | "eq_ref" | "ref_or_null" | "index_merge" | "fulltext" => {
+            ("Index Lookup".to_string(), PlanNodeKind::IndexScan)
+        }
+        "const" | "system" => ("Constant Lookup".to_string(), PlanNodeKind::Other),
+        "" => ("Table".to_string(), PlanNodeKind::Other),
+        other => (other.to_string(), PlanNodeKind::Other),
+    };
+
+    let mut node = QueryPlanNode::new(operation, kind);
+    node.relation = string_field(table, "table_name");
+    node.index_name = string_field(table, "key");
+    // MariaDBは推定行数を `rows`、実測値を `r_` で始まる項目で出力する
+    node.estimated_rows =
+        number_field(table, "rows_examined_per_scan").or_else(|| number_field(table, "rows"));
+    node.estimated_cost = table
+        .get("cost_info")
+        .and_then(|cost| number_field(cost, "prefix_cost"));
+    node.actual_rows = number_field(table, "r_rows");
+    node.actual_time_ms = number_field(table, "r_total_time_ms");
+    node.actual_loops = number_field(table, "r_loops");
+    node.detail = string_field(table, "attached_condition");
+    node.children = mysql_children(table);
+    node
+}
+
+/// ツリー形式の1行（`-> ` 以降）をノードに変換する
+fn mysql_tree_node(body: &str) -> QueryPlanNode {
+    // 例: Table scan on t1  (cost=1.25 rows=10) (actual time=0.0305..0.0355 rows=10 loops=1)
+    let operation = body
+        .find("  (")
+        .map(|index| &body[..index])
+        .unwrap_or(body)
+        .trim();
+    let lower = operation.to_lowercase();
+
+    let kind = if lower.starts_with("table scan on ") {
+        PlanNodeKind::SeqScan
+    } else if lower.contains("index") && lower.contains(" on ") {
+        PlanNodeKind::IndexScan
+    } else if lower.starts_with("nested loop") {
+        PlanNodeKind::NestedLoop
+    } else if lower.contains("hash join") {
+        PlanNodeKind::HashJoin
+    } else if lower.starts_with("sort") {
+        PlanNodeKind::Sort
+    } else if lower.contains("aggregate") {
+        PlanNodeKind::Aggregate
+    } else {
+        PlanNodeKind::Other
+    };
+
+    let mut node = QueryPlanNode::new(operation, kind);
+    if matches!(kind, PlanNodeKind::SeqScan | PlanNodeKind::IndexScan) {
+        node.relation = word_after(operation, " on ");
+        node.index_name = word_after(operation, " using ");
+    }
+
+    if let Some(cost) = parenthesized(body, "(cost=") {
+        node.estimated_cost = key_value(cost, "cost");
+        node.estimated_rows = key_value(cost, "rows");
+    }
+    if let Some(actual) = parenthesized(body, "(actual ") {
+        // time=開始..終了 の終了時刻を所要時間とする
+        node.actual_time_ms = actual
+            .split_whitespace()
+            .find_map(|part| part.strip_prefix("time="))
+            .and_then(|time| time.split("..").last())
+            .and_then(|time| time.parse().ok());
+        node.actual_rows = key_value(actual, "rows");
+        node.actual_loops = key_value(actual, "loops");
+    }
+    node
+}
+
+fn attach_child(
+    stack: &mut [(usize, QueryPlanNode)],
+    roots: &mut Vec<QueryPlanNode>,
+    node: QueryPlanNode,
+) {
+    match stack.last_mut() {
+        Some((_, parent)) => parent.children.push(node),
+        None => roots.push(node),
+    }
+}
+
+/// `prefix` で始まる括弧の中身
+fn parenthesized<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    let start = text.find(prefix)? + 1;
+    let end = text[start..].find(')')? + start;
+    Some(&text[start..end])
+}
+
+/// `key=value` 形式の数値
+fn key_value(text: &str, key: &str) -> Option<f64> {
+    text.split_whitespace()
+        .filter_map(|part| part.split_once('='))
+        .find(|(k, _)| *k == key)
+        .and_then(|(_, value)| value.parse().ok())
+}
+
+fn word_after(text: &str, marker: &str) -> Option<String> {
+    let start = text.find(marker)? + marker.len();
+    text[start..]
+        .split_whitespace()
+        .next()
+        .map(|word| word.trim_matches(|c| c == '`' || c == '"').to_string())
+}
+
+fn sqlite_children(rows: &[(i64, i64, String)], parent: i64) -> Vec<QueryPlanNode> {
+    rows.iter()
+        .filter(|(id, row_parent, _)| *row_parent == parent && *id != parent)
+        .map(|(id, _, detail)| {
+            let mut node = sqlite_node(detail);
+            node.children = sqlite_children(rows, *id);
+            node
+        })
+        .collect()
+}
+
+fn sqlite_node(detail: &str) -> QueryPlanNode {
+    let upper = detail.to_uppercase();
+    let kind = if upper.starts_with("SEARCH ") {
+        PlanNodeKind::IndexScan
+    } else if upper.starts_with("SCAN ") {
+        if upper.contains(" USING ") && upper.contains("INDEX") {
+            PlanNodeKind::IndexScan
+        } else {
+            PlanNodeKind::SeqScan
+        }
+    } else if upper.contains("TEMP B-TREE") {
+        PlanNodeKind::Sort
+    } else {
+        PlanNodeKind::Other
+    };
+
+    let mut node = QueryPlanNode::new(detail, kind);
+    if matches!(kind, PlanNodeKind::SeqScan | PlanNodeKind::IndexScan) {
+        // 古いSQLiteは "SCAN TABLE t" と出力する
+        node.relation = detail
+            .split_whitespace()
+            .skip(1)
+            .find(|word| !word.eq_ignore_ascii_case("TABLE"))
+            .map(str::to_string);
+        node.index_name = word_after(detail, " INDEX ");
+    }
+    node
+}
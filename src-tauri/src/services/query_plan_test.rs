@@ -0,0 +1,349 @@
+use crate::connection::{ConnectionConfig, ConnectionInfo, DatabaseType, FileConfig};
+use crate::database::{DuckDbExecutor, SqliteExecutor};
+use crate::models::database_structure::{SchemaSummary, TableSummary};
+use crate::models::query_plan::PlanNodeKind;
+use crate::services::query_executor::QueryExecutor;
+use crate::services::query_plan::{QueryPlanAnalyzer, TableRowCountCache};
+use std::collections::HashMap;
+
+fn summaries(tables: &[(&str, Option<i64>)]) -> Vec<SchemaSummary> {
+    vec![SchemaSummary {
+        name: "public".to_string(),
+        is_system: false,
+        tables: tables
+            .iter()
+            .map(|(name, rows)| TableSummary {
+                name: name.to_string(),
+                schema: "public".to_string(),
+                comment: None,
+                estimated_row_count: *rows,
+            })
+            .collect(),
+        views: vec![],
+    }]
+}
+
+const POSTGRES_PLAN: &str = r#"[
+  {
+    "Plan": {
+      "Node Type": "Nested Loop",
+      "Join Type": "Inner",
+      "Total Cost": 12345.67,
+      "Plan Rows": 500,
+      "Actual Rows": 480,
+      "Actual Total Time": 12.5,
+      "Actual Loops": 1,
+      "Plans": [
+        {
+          "Node Type": "Seq Scan",
+          "Relation Name": "orders",
+          "Schema": "public",
+          "Total Cost": 1000.0,
+          "Plan Rows": 2000,
+          "Filter": "(status = 'open'::text)"
+        },
+        {
+          "Node Type": "Index Scan",
+          "Relation Name": "customers",
+          "Index Name": "customers_pkey",
+          "Total Cost": 0.3,
+          "Plan Rows": 100,
+          "Index Cond": "(id = orders.customer_id)"
+        }
+      ]
+    },
+    "Planning Time": 0.2,
+    "Execution Time": 12.9
+  }
+]"#;
+
+#[test]
+fn test_parse_postgres_json_plan() {
+    let root = QueryPlanAnalyzer::parse_postgres_json(POSTGRES_PLAN).unwrap();
+
+    assert_eq!(root.operation, "Nested Loop");
+    assert_eq!(root.kind, PlanNodeKind::NestedLoop);
+    assert_eq!(root.estimated_rows, Some(500.0));
+    assert_eq!(root.actual_rows, Some(480.0));
+    assert_eq!(root.actual_time_ms, Some(12.5));
+    assert_eq!(root.children.len(), 2);
+
+    let scan = &root.children[0];
+    assert_eq!(scan.kind, PlanNodeKind::SeqScan);
+    assert_eq!(scan.relation.as_deref(), Some("orders"));
+    assert_eq!(scan.schema.as_deref(), Some("public"));
+    assert!(scan.detail.as_deref().unwrap().contains("Filter"));
+
+    let index = &root.children[1];
+    assert_eq!(index.kind, PlanNodeKind::IndexScan);
+    assert_eq!(index.index_name.as_deref(), Some("customers_pkey"));
+}
+
+#[test]
+fn test_warnings_use_inspector_row_counts() {
+    let root = QueryPlanAnalyzer::parse_postgres_json(POSTGRES_PLAN).unwrap();
+
+    // 計画上は2000行でも、テーブル自体が大きければ全件走査として注意する
+    let table_rows = QueryPlanAnalyzer::table_row_counts(&summaries(&[
+        ("orders", Some(250_000)),
+        ("customers", None),
+    ]));
+    let warnings = QueryPlanAnalyzer::detect_warnings(&root, &table_rows);
+    let codes: Vec<&str> = warnings.iter().map(|w| w.code.as_str()).collect();
+    assert_eq!(codes, vec!["nested_loop_high_rows", "seq_scan_large_table"]);
+    assert_eq!(warnings[1].relation.as_deref(), Some("orders"));
+
+    // 小さなテーブルの全件走査は注意しない
+    let table_rows = QueryPlanAnalyzer::table_row_counts(&summaries(&[("orders", Some(50))]));
+    let warnings = QueryPlanAnalyzer::detect_warnings(&root, &table_rows);
+    assert!(warnings.iter().all(|w| w.code != "seq_scan_large_table"));
+}
+
+#[test]
+fn test_parse_mysql_json_plan() {
+    let raw = r#"{
+      "query_block": {
+        "select_id": 1,
+        "cost_info": { "query_cost": "45.20" },
+        "ordering_operation": {
+          "using_filesort": true,
+          "nested_loop": [
+            {
+              "table": {
+                "table_name": "o",
+                "access_type": "ALL",
+                "rows_examined_per_scan": 40000,
+                "cost_info": { "prefix_cost": "4000.25" },
+                "attached_condition": "(`o`.`status` = 'open')"
+              }
+            },
+            {
+              "table": {
+                "table_name": "c",
+                "access_type": "eq_ref",
+                "key": "PRIMARY",
+                "rows_examined_per_scan": 1
+              }
+            }
+          ]
+        }
+      }
+    }"#;
+
+    let root = QueryPlanAnalyzer::parse_mysql_json(raw).unwrap();
+    assert_eq!(root.operation, "Query Block");
+    assert_eq!(root.estimated_cost, Some(45.2));
+
+    let sort = &root.children[0];
+    assert_eq!(sort.kind, PlanNodeKind::Sort);
+    assert_eq!(sort.detail.as_deref(), Some("using_filesort"));
+
+    let join = &sort.children[0];
+    assert_eq!(join.kind, PlanNodeKind::NestedLoop);
+    assert_eq!(join.children[0].kind, PlanNodeKind::SeqScan);
+    assert_eq!(join.children[0].relation.as_deref(), Some("o"));
+    assert_eq!(join.children[1].index_name.as_deref(), Some("PRIMARY"));
+
+    // インスペクターの行数がなくても計画上の推定行数で判定する
+    let warnings = QueryPlanAnalyzer::detect_warnings(&root, &HashMap::new());
+    assert!(warnings.iter().any(|w| w.code == "seq_scan_large_table"));
+    assert!(!warnings.iter().any(|w| w.code == "nested_loop_high_rows"));
+}
+
+#[test]
+fn test_parse_mariadb_analyze_json() {
+    let raw = r#"{
+      "query_block": {
+        "select_id": 1,
+        "r_loops": 1,
+        "r_total_time_ms": 0.412,
+        "filesort": {
+          "sort_key": "o.created_at",
+          "temporary_table": {
+            "table": {
+              "table_name": "o",
+              "access_type": "ALL",
+              "r_loops": 1,
+              "rows": 40000,
+              "r_rows": 39876,
+              "r_total_time_ms": 0.3,
+              "filtered": 100,
+              "attached_condition": "o.`status` = 'open'"
+            },
+            "block-nl-join": {
+              "table": {
+                "table_name": "c",
+                "access_type": "eq_ref",
+                "key": "PRIMARY",
+                "rows": 1
+              }
+            }
+          }
+        }
+      }
+    }"#;
+
+    let sql = QueryPlanAnalyzer::explain_sql("mariadb", "SELECT 1", true);
+    assert_eq!(sql, "ANALYZE FORMAT=JSON SELECT 1");
+
+    let root = QueryPlanAnalyzer::parse_mysql_json(raw).unwrap();
+    let sort = &root.children[0];
+    assert_eq!(sort.kind, PlanNodeKind::Sort);
+    assert_eq!(sort.children.len(), 2);
+
+    let scan = &sort.children[0];
+    assert_eq!(scan.kind, PlanNodeKind::SeqScan);
+    assert_eq!(scan.estimated_rows, Some(40000.0));
+    assert_eq!(scan.actual_rows, Some(39876.0));
+    assert_eq!(scan.actual_loops, Some(1.0));
+    assert_eq!(sort.children[1].index_name.as_deref(), Some("PRIMARY"));
+}
+
+#[test]
+fn test_parse_mysql_analyze_tree() {
+    let raw = "-> Nested loop inner join  (cost=4.70 rows=10) (actual time=0.0535..0.0748 rows=10 loops=1)\n    -> Filter: (t1.a is not null)  (cost=1.25 rows=10) (actual time=0.0318..0.0381 rows=10 loops=1)\n        -> Table scan on t1  (cost=1.25 rows=10) (actual time=0.0305..0.0355 rows=10 loops=1)\n    -> Single-row index lookup on t2 using PRIMARY (id=t1.a)  (cost=0.26 rows=1) (actual time=0.00315..0.00318 rows=1 loops=10)\n";
+
+    let root = QueryPlanAnalyzer::parse_mysql_tree(raw).unwrap();
+    assert_eq!(root.kind, PlanNodeKind::NestedLoop);
+    assert_eq!(root.estimated_cost, Some(4.7));
+    assert_eq!(root.actual_time_ms, Some(0.0748));
+    assert_eq!(root.children.len(), 2);
+
+    let scan = &root.children[0].children[0];
+    assert_eq!(scan.kind, PlanNodeKind::SeqScan);
+    assert_eq!(scan.relation.as_deref(), Some("t1"));
+
+    let lookup = &root.children[1];
+    assert_eq!(lookup.kind, PlanNodeKind::IndexScan);
+    assert_eq!(lookup.relation.as_deref(), Some("t2"));
+    assert_eq!(lookup.index_name.as_deref(), Some("PRIMARY"));
+    assert_eq!(lookup.actual_loops, Some(10.0));
+}
+
+#[tokio::test]
+async fn test_sqlite_explain_query_plan() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("plan.db");
+    std::fs::File::create(&path).unwrap();
+    let connection = ConnectionInfo::new(
+        "Test SQLite".to_string(),
+        DatabaseType::SQLite,
+        ConnectionConfig::File(FileConfig {
+            file_path: path.to_string_lossy().to_string(),
+            readonly: false,
+        }),
+    );
+    let executor = SqliteExecutor::new(&connection).await.unwrap();
+    executor
+        .execute(
+            "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT);\n\
+             CREATE TABLE posts (id INTEGER PRIMARY KEY, user_id INTEGER, title TEXT);\n\
+             CREATE INDEX idx_posts_user ON posts (user_id);",
+        )
+        .await
+        .unwrap();
+
+    let table_rows = QueryPlanAnalyzer::table_row_counts(&summaries(&[("posts", Some(20_000))]));
+    let plan = QueryPlanAnalyzer::explain(
+        &executor,
+        "sqlite",
+        "SELECT * FROM users u JOIN posts p ON p.user_id = u.id ORDER BY u.name",
+        true,
+        &table_rows,
+    )
+    .await
+    .unwrap();
+
+    // SQLiteでは実測値は取得しない
+    assert!(!plan.analyzed);
+    let kinds: Vec<PlanNodeKind> = plan.root.children.iter().map(|node| node.kind).collect();
+    assert!(kinds.contains(&PlanNodeKind::SeqScan));
+    assert!(kinds.contains(&PlanNodeKind::IndexScan));
+    // 別名はテーブル名に置き換える
+    assert!(plan
+        .root
+        .children
+        .iter()
+        .any(|node| node.relation.as_deref() == Some("users")));
+    assert!(plan
+        .warnings
+        .iter()
+        .any(|w| w.code == "seq_scan_large_table" && w.relation.as_deref() == Some("posts")));
+
+    // 複数文は対象外
+    assert!(QueryPlanAnalyzer::explain(
+        &executor,
+        "sqlite",
+        "SELECT 1; SELECT 2",
+        false,
+        &table_rows
+    )
+    .await
+    .is_err());
+}
+
+#[tokio::test]
+async fn test_duckdb_explain_json() {
+    let connection = ConnectionInfo::new(
+        "Test DuckDB".to_string(),
+        DatabaseType::DuckDB,
+        ConnectionConfig::File(FileConfig {
+            file_path: ":memory:".to_string(),
+            readonly: false,
+        }),
+    );
+    let executor = DuckDbExecutor::new(&connection).await.unwrap();
+    executor
+        .execute_mutation(
+            "CREATE TABLE users AS SELECT range AS id, 'user' || range AS name FROM range(100)",
+        )
+        .await
+        .unwrap();
+
+    let table_rows = QueryPlanAnalyzer::table_row_counts(&summaries(&[("users", Some(20_000))]));
+    for analyze in [false, true] {
+        let plan = QueryPlanAnalyzer::explain(
+            &executor,
+            "duckdb",
+            "SELECT name FROM users WHERE id > 10 ORDER BY name",
+            analyze,
+            &table_rows,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(plan.analyzed, analyze);
+        let mut nodes = vec![&plan.root];
+        let mut scan = None;
+        while let Some(node) = nodes.pop() {
+            if node.kind == PlanNodeKind::SeqScan {
+                scan = Some(node);
+            }
+            nodes.extend(node.children.iter());
+        }
+        let scan = scan.unwrap_or_else(|| panic!("no scan node: {}", plan.raw));
+        assert_eq!(scan.relation.as_deref(), Some("users"));
+        if analyze {
+            assert_eq!(scan.actual_rows, Some(89.0));
+        }
+    }
+}
+
+#[test]
+fn test_table_row_count_cache() {
+    let cache = TableRowCountCache::new();
+    assert!(cache.get("conn-1").is_none());
+
+    let rows = QueryPlanAnalyzer::table_row_counts(&summaries(&[("orders", Some(50_000))]));
+    cache.insert("conn-1", rows);
+    assert_eq!(cache.get("conn-1").unwrap().get("orders"), Some(&50_000.0));
+    assert!(cache.get("conn-2").is_none());
+
+    cache.invalidate("conn-1");
+    assert!(cache.get("conn-1").is_none());
+
+    // 期限切れのキャッシュは返さない
+    let expired = TableRowCountCache::with_ttl(std::time::Duration::ZERO);
+    expired.insert("conn-1", HashMap::new());
+    assert!(expired.get("conn-1").is_none());
+}
@@ -0,0 +1,248 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::sync::Mutex as StdMutex;
+
+use crate::connection::{ConnectionInfo, DatabaseType};
+use crate::models::query_analysis::{QueryAnalysisResult, QueryType, RiskLevel};
+use crate::models::query_result::{QueryError, QueryErrorCode};
+use crate::models::safety_settings::{
+    ConfirmationThreshold, ConfirmationToken, EnvironmentSafetyConfig, SafetySettings,
+};
+use crate::services::query_analyzer::QueryAnalyzer;
+use crate::services::safety_config::SafetyConfigStorage;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 確認トークンの有効期間（秒）
+const CONFIRMATION_TOKEN_TTL_SECONDS: i64 = 300;
+
+/// 安全設定による判定
+#[derive(Debug, Clone, PartialEq)]
+pub enum SafetyDecision {
+    /// そのまま実行できる
+    Allow,
+    /// 確認トークンがあれば実行できる
+    RequireConfirmation,
+    /// 環境の設定で禁止されている
+    Reject(String),
+}
+
+/// 実行前の安全チェック
+///
+/// フロントエンドの確認ダイアログとは別に、コマンド側でも同じ安全設定を適用する。
+/// 確認が必要な文は、起動ごとに生成する鍵で署名した確認トークン
+/// （接続ID・SQL・有効期限に紐づく）がなければ実行しない。トークンは1回だけ使える
+pub struct SafetyGuard {
+    key: [u8; 32],
+    /// 使用済みトークン（有効期限まで保持する）
+    redeemed: StdMutex<HashMap<String, i64>>,
+}
+
+impl SafetyGuard {
+    pub fn new() -> Self {
+        let mut key = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut key);
+        Self {
+            key,
+            redeemed: StdMutex::new(HashMap::new()),
+        }
+    }
+
+    /// 解析結果を環境の安全設定で判定する
+    pub fn decide(
+        config: &EnvironmentSafetyConfig,
+        analysis: &QueryAnalysisResult,
+    ) -> SafetyDecision {
+        if config.disable_drop && analysis.contains(&QueryType::Drop) {
+            return SafetyDecision::Reject(
+                "DROP statements are disabled for this environment".to_string(),
+            );
+        }
+        if config.disable_truncate && analysis.contains(&QueryType::Truncate) {
+            return SafetyDecision::Reject(
+                "TRUNCATE statements are disabled for this environment".to_string(),
+            );
+        }
+        if !config.confirmation_enabled {
+            return SafetyDecision::Allow;
+        }
+
+        // 解析できない文は最も高い危険度なので、どちらの閾値でも確認が必要になる
+        let needs_confirmation = match config.confirmation_threshold {
+            ConfirmationThreshold::Warning => analysis.risk_level >= RiskLevel::Warning,
+            ConfirmationThreshold::Danger => analysis.risk_level >= RiskLevel::Danger,
+        };
+        if needs_confirmation {
+            SafetyDecision::RequireConfirmation
+        } else {
+            SafetyDecision::Allow
+        }
+    }
+
+    /// SQLを解析し、設定と確認トークンから実行可否をチェックする
+    ///
+    /// 実行できる場合は解析結果を返す
+    pub fn check(
+        &self,
+        config: &EnvironmentSafetyConfig,
+        connection_id: &str,
+        dialect: &str,
+        sql: &str,
+        token: Option<&str>,
+    ) -> Result<QueryAnalysisResult, String> {
+        let analysis = QueryAnalyzer::analyze(sql, dialect);
+        match Self::decide(config, &analysis) {
+            SafetyDecision::Allow => Ok(analysis),
+            SafetyDecision::Reject(message) => {
+                Err(safety_error(QueryErrorCode::PermissionDenied, message))
+            }
+            SafetyDecision::RequireConfirmation => {
+                if token.is_some_and(|token| self.redeem_token(token, connection_id, sql)) {
+                    Ok(analysis)
+                } else {
+                    Err(safety_error(
+                        QueryErrorCode::ConfirmationRequired,
+                        "This statement requires confirmation before it can be executed"
+                            .to_string(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// 保存されている安全設定で接続に対するチェックを行う
+    ///
+    /// 設定ファイルが読めない場合はデフォルト設定を使う
+    pub fn check_connection(
+        &self,
+        connection: &ConnectionInfo,
+        sql: &str,
+        token: Option<&str>,
+    ) -> Result<QueryAnalysisResult, String> {
+        let config = load_settings().config_for(connection.environment.environment_type);
+        self.check(
+            &config,
+            &connection.id,
+            dialect_name(&connection.database_type),
+            sql,
+            token,
+        )
+    }
+
+    /// 環境の設定で禁止されている文でないか確認する
+    pub fn ensure_allowed(&self, connection: &ConnectionInfo, sql: &str) -> Result<(), String> {
+        let config = load_settings().config_for(connection.environment.environment_type);
+        let analysis = QueryAnalyzer::analyze(sql, dialect_name(&connection.database_type));
+        match Self::decide(&config, &analysis) {
+            SafetyDecision::Reject(message) => {
+                Err(safety_error(QueryErrorCode::PermissionDenied, message))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// 確認トークンを発行する（設定で禁止されている文には発行しない）
+    pub fn issue_token(
+        &self,
+        connection: &ConnectionInfo,
+        sql: &str,
+    ) -> Result<ConfirmationToken, String> {
+        self.ensure_allowed(connection, sql)?;
+
+        let expires_at = chrono::Utc::now().timestamp() + CONFIRMATION_TOKEN_TTL_SECONDS;
+        Ok(ConfirmationToken {
+            token: self.sign(&connection.id, sql, expires_at),
+            expires_at,
+        })
+    }
+
+    /// トークンを `<有効期限>.<署名>` の形式で作る
+    pub fn sign(&self, connection_id: &str, sql: &str, expires_at: i64) -> String {
+        let signature = self
+            .mac(connection_id, sql, expires_at)
+            .finalize()
+            .into_bytes();
+        format!("{}.{}", expires_at, URL_SAFE_NO_PAD.encode(signature))
+    }
+
+    /// トークンの署名と有効期限を検証する
+    pub fn verify_token(&self, token: &str, connection_id: &str, sql: &str) -> bool {
+        let Some((expires_at, signature)) = token.split_once('.') else {
+            return false;
+        };
+        let (Ok(expires_at), Ok(signature)) =
+            (expires_at.parse::<i64>(), URL_SAFE_NO_PAD.decode(signature))
+        else {
+            return false;
+        };
+        if expires_at < chrono::Utc::now().timestamp() {
+            return false;
+        }
+
+        self.mac(connection_id, sql, expires_at)
+            .verify_slice(&signature)
+            .is_ok()
+    }
+
+    /// トークンを検証し、使用済みにする（同じトークンは2回目以降は無効）
+    pub fn redeem_token(&self, token: &str, connection_id: &str, sql: &str) -> bool {
+        if !self.verify_token(token, connection_id, sql) {
+            return false;
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        let mut redeemed = self.redeemed.lock().unwrap();
+        redeemed.retain(|_, expires_at| *expires_at >= now);
+        let expires_at = token
+            .split_once('.')
+            .and_then(|(expires_at, _)| expires_at.parse::<i64>().ok())
+            .unwrap_or(now);
+        redeemed.insert(token.to_string(), expires_at).is_none()
+    }
+
+    fn mac(&self, connection_id: &str, sql: &str, expires_at: i64) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.key).expect("HMAC accepts any key length");
+        mac.update(connection_id.as_bytes());
+        mac.update(b"\n");
+        mac.update(expires_at.to_string().as_bytes());
+        mac.update(b"\n");
+        mac.update(sql.as_bytes());
+        mac
+    }
+}
+
+impl Default for SafetyGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub(crate) fn load_settings() -> SafetySettings {
+    SafetyConfigStorage::new().load().unwrap_or_default()
+}
+
+/// QueryAnalyzer / StatementSplitter の方言名
+pub(crate) fn dialect_name(database_type: &DatabaseType) -> &'static str {
+    match database_type {
+        DatabaseType::PostgreSQL => "postgresql",
+        DatabaseType::MySQL => "mysql",
+        DatabaseType::MariaDB => "mariadb",
+        DatabaseType::SQLite => "sqlite",
+        DatabaseType::DuckDB => "duckdb",
+        DatabaseType::SqlServer => "sqlserver",
+    }
+}
+
+/// コマンドのエラー形式（シリアライズした QueryError）にする
+pub(crate) fn safety_error(code: QueryErrorCode, message: String) -> String {
+    let error = QueryError {
+        code,
+        message,
+        details: None,
+        native_code: None,
+    };
+    serde_json::to_string(&error).unwrap_or(error.message)
+}
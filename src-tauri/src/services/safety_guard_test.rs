@@ -0,0 +1,175 @@
+use crate::connection::EnvironmentType;
+use crate::models::query_analysis::{QueryAnalysisResult, QueryType, RiskLevel};
+use crate::models::query_result::{QueryError, QueryErrorCode};
+use crate::models::safety_settings::{EnvironmentSafetyConfig, SafetySettings};
+use crate::services::query_analyzer::QueryAnalyzer;
+
+use super::safety_guard::{SafetyDecision, SafetyGuard};
+
+fn config(environment: EnvironmentType) -> EnvironmentSafetyConfig {
+    SafetySettings::default().config_for(environment)
+}
+
+fn decide(environment: EnvironmentType, sql: &str) -> SafetyDecision {
+    SafetyGuard::decide(
+        &config(environment),
+        &QueryAnalyzer::analyze(sql, "postgresql"),
+    )
+}
+
+fn error_code(error: &str) -> QueryErrorCode {
+    serde_json::from_str::<QueryError>(error).unwrap().code
+}
+
+#[test]
+fn test_decide_by_environment() {
+    // 本番環境ではDROP/TRUNCATEは禁止
+    assert!(matches!(
+        decide(EnvironmentType::Production, "DROP TABLE users"),
+        SafetyDecision::Reject(_)
+    ));
+    assert!(matches!(
+        decide(EnvironmentType::Production, "TRUNCATE TABLE users"),
+        SafetyDecision::Reject(_)
+    ));
+    assert_eq!(
+        decide(EnvironmentType::Production, "SELECT * FROM users"),
+        SafetyDecision::Allow
+    );
+
+    // ステージングは Warning 以上で確認が必要
+    assert_eq!(
+        decide(
+            EnvironmentType::Staging,
+            "UPDATE users SET active = false WHERE id = 1"
+        ),
+        SafetyDecision::RequireConfirmation
+    );
+
+    // 開発環境は Danger のみ確認が必要
+    assert_eq!(
+        decide(
+            EnvironmentType::Development,
+            "UPDATE users SET active = false WHERE id = 1"
+        ),
+        SafetyDecision::Allow
+    );
+    assert_eq!(
+        decide(EnvironmentType::Development, "DROP TABLE users"),
+        SafetyDecision::RequireConfirmation
+    );
+}
+
+#[test]
+fn test_decide_checks_every_statement() {
+    // 先頭が SELECT でも後続の DROP で判定する
+    assert!(matches!(
+        decide(EnvironmentType::Production, "SELECT 1; DROP TABLE users"),
+        SafetyDecision::Reject(_)
+    ));
+
+    // 解析できない文は閾値が Danger の環境でも確認が必要
+    assert_eq!(
+        decide(EnvironmentType::Development, "SELECT 1; FROBNICATE users"),
+        SafetyDecision::RequireConfirmation
+    );
+}
+
+#[test]
+fn test_decide_without_confirmation() {
+    let config = EnvironmentSafetyConfig {
+        confirmation_enabled: false,
+        ..EnvironmentSafetyConfig::default()
+    };
+    let analysis = QueryAnalysisResult {
+        query_type: QueryType::Delete,
+        risk_level: RiskLevel::Danger,
+        risk_factors: vec![],
+        affected_tables: vec!["users".to_string()],
+        has_where_clause: false,
+        statements: vec![],
+    };
+
+    assert_eq!(
+        SafetyGuard::decide(&config, &analysis),
+        SafetyDecision::Allow
+    );
+}
+
+#[test]
+fn test_check_requires_signed_token() {
+    let guard = SafetyGuard::new();
+    let config = config(EnvironmentType::Production);
+    let sql = "DELETE FROM users WHERE id = 1";
+
+    let error = guard
+        .check(&config, "conn-1", "postgresql", sql, None)
+        .unwrap_err();
+    assert!(matches!(
+        error_code(&error),
+        QueryErrorCode::ConfirmationRequired
+    ));
+
+    let expires_at = chrono::Utc::now().timestamp() + 60;
+    let token = guard.sign("conn-1", sql, expires_at);
+    assert!(guard
+        .check(&config, "conn-1", "postgresql", sql, Some(&token))
+        .is_ok());
+
+    // 使用済みのトークンは有効期限内でも再利用できない
+    let error = guard
+        .check(&config, "conn-1", "postgresql", sql, Some(&token))
+        .unwrap_err();
+    assert!(matches!(
+        error_code(&error),
+        QueryErrorCode::ConfirmationRequired
+    ));
+
+    // 別のSQL・別の接続・別の鍵では使えない
+    assert!(guard
+        .check(
+            &config,
+            "conn-1",
+            "postgresql",
+            "DELETE FROM users",
+            Some(&token)
+        )
+        .is_err());
+    assert!(guard
+        .check(&config, "conn-2", "postgresql", sql, Some(&token))
+        .is_err());
+    assert!(SafetyGuard::new()
+        .check(&config, "conn-1", "postgresql", sql, Some(&token))
+        .is_err());
+
+    // 有効期限を書き換えると署名が合わない
+    let (_, signature) = token.split_once('.').unwrap();
+    let forged = format!("{}.{}", expires_at + 3600, signature);
+    assert!(!guard.verify_token(&forged, "conn-1", sql));
+}
+
+#[test]
+fn test_check_rejects_expired_token() {
+    let guard = SafetyGuard::new();
+    let sql = "DELETE FROM users WHERE id = 1";
+    let token = guard.sign("conn-1", sql, chrono::Utc::now().timestamp() - 1);
+
+    assert!(!guard.verify_token(&token, "conn-1", sql));
+    assert!(!guard.verify_token("not-a-token", "conn-1", sql));
+}
+
+#[test]
+fn test_forbidden_statement_ignores_token() {
+    let guard = SafetyGuard::new();
+    let config = config(EnvironmentType::Production);
+    let sql = "DROP TABLE users";
+    let token = guard.sign("conn-1", sql, chrono::Utc::now().timestamp() + 60);
+
+    let error = guard
+        .check(&config, "conn-1", "postgresql", sql, Some(&token))
+        .unwrap_err();
+    assert!(matches!(
+        error_code(&error),
+        QueryErrorCode::PermissionDenied
+    ));
+}
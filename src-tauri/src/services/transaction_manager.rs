@@ -0,0 +1,461 @@
+use crate::models::mutation_result::MutationResult;
+use crate::models::query_result::{
+    QueryError, QueryErrorCode, QueryParam, QueryResult, ScriptErrorMode, StatementResult,
+};
+use crate::models::transaction::TransactionInfo;
+use crate::services::query_executor::{QueryExecutor, TransactionSession};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex as StdMutex, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+/// トランザクションのアイドルタイムアウトの既定値（秒）
+pub const DEFAULT_TRANSACTION_TIMEOUT_SECS: u32 = 300;
+
+/// 開いているトランザクション
+struct OpenTransaction {
+    info: TransactionInfo,
+    session: Mutex<Option<Box<dyn TransactionSession>>>,
+    last_activity: StdMutex<Instant>,
+    watchdog: StdMutex<Option<JoinHandle<()>>>,
+}
+
+impl OpenTransaction {
+    /// 最終操作時刻を更新
+    fn touch(&self) {
+        *self.last_activity.lock().unwrap() = Instant::now();
+    }
+
+    /// 最後の操作からの経過時間
+    fn idle_time(&self) -> Duration {
+        self.last_activity.lock().unwrap().elapsed()
+    }
+
+    /// タイムアウト監視タスクを停止
+    fn stop_watchdog(&self) {
+        if let Some(handle) = self.watchdog.lock().unwrap().take() {
+            handle.abort();
+        }
+    }
+}
+
+type TransactionMap = Arc<RwLock<HashMap<String, Arc<OpenTransaction>>>>;
+type ExpiredMap = Arc<RwLock<HashMap<String, TransactionInfo>>>;
+
+/// トランザクション管理
+///
+/// 接続IDごとに1つのトランザクションを保持し、開始したウィンドウからの実行のみを
+/// トランザクション内で処理する。ウィンドウイベントから同期的に参照するため、
+/// マップは std の RwLock で保護する（ロック中に await しない）。
+pub struct TransactionManager {
+    transactions: TransactionMap,
+    /// タイムアウトでロールバックされたトランザクション（確認されるまで保持）
+    expired: ExpiredMap,
+}
+
+impl TransactionManager {
+    pub fn new() -> Self {
+        Self {
+            transactions: Arc::new(RwLock::new(HashMap::new())),
+            expired: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// トランザクションを開始
+    pub async fn begin(
+        &self,
+        connection_id: &str,
+        window_label: &str,
+        executor: &dyn QueryExecutor,
+        idle_timeout: Duration,
+    ) -> Result<TransactionInfo, QueryError> {
+        if let Some(existing) = self.status(connection_id) {
+            return Err(already_open_error(&existing));
+        }
+
+        let session = executor.begin_transaction().await?;
+
+        let info = TransactionInfo {
+            transaction_id: Uuid::new_v4().to_string(),
+            connection_id: connection_id.to_string(),
+            window_label: window_label.to_string(),
+            started_at: chrono::Utc::now().to_rfc3339(),
+            timeout_seconds: idle_timeout.as_secs(),
+        };
+        let entry = Arc::new(OpenTransaction {
+            info: info.clone(),
+            session: Mutex::new(Some(session)),
+            last_activity: StdMutex::new(Instant::now()),
+            watchdog: StdMutex::new(None),
+        });
+
+        {
+            let mut transactions = self.transactions.write().unwrap();
+            if let Some(existing) = transactions.get(connection_id) {
+                // 同時に開始された場合、後から来た方の接続は破棄時にロールバックされる
+                return Err(already_open_error(&existing.info));
+            }
+            transactions.insert(connection_id.to_string(), Arc::clone(&entry));
+        }
+        self.expired.write().unwrap().remove(connection_id);
+
+        let watchdog = self.spawn_watchdog(Arc::clone(&entry), idle_timeout);
+        *entry.watchdog.lock().unwrap() = Some(watchdog);
+
+        Ok(info)
+    }
+
+    /// 指定ウィンドウでトランザクションが開いているか
+    ///
+    /// タイムアウトでロールバック済みの場合は、自動コミットで実行されてしまわないようエラーを返す
+    pub async fn is_active_in_window(
+        &self,
+        connection_id: &str,
+        window_label: &str,
+    ) -> Result<bool, QueryError> {
+        if let Some(entry) = self.transactions.read().unwrap().get(connection_id) {
+            return Ok(entry.info.window_label == window_label);
+        }
+
+        match self.expired.read().unwrap().get(connection_id) {
+            Some(info) if info.window_label == window_label => Err(expired_error(info)),
+            _ => Ok(false),
+        }
+    }
+
+    /// 指定ウィンドウでトランザクションが開いていないことを確認
+    ///
+    /// トランザクションの接続を使えない操作（カーソルなど）が、未コミットの変更を見ないまま実行されるのを防ぐ
+    pub async fn ensure_inactive_in_window(
+        &self,
+        connection_id: &str,
+        window_label: &str,
+    ) -> Result<(), QueryError> {
+        if self
+            .is_active_in_window(connection_id, window_label)
+            .await?
+        {
+            return Err(transaction_error(
+                "Not supported while a transaction is open; commit or roll back first".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// トランザクション内でSQLを実行
+    pub async fn execute_with_params(
+        &self,
+        connection_id: &str,
+        sql: &str,
+        params: &[QueryParam],
+        timeout: Duration,
+    ) -> Result<QueryResult, QueryError> {
+        let entry = self
+            .get_entry(connection_id)
+            .ok_or_else(|| self.missing_error(connection_id))?;
+        let mut guard = entry.session.lock().await;
+        let session = guard
+            .as_mut()
+            .ok_or_else(|| not_open_error(connection_id))?;
+
+        entry.touch();
+        let result = tokio::time::timeout(timeout, session.execute_with_params(sql, params))
+            .await
+            .map_err(|_| timeout_error(timeout))?;
+        entry.touch();
+
+        result
+    }
+
+    /// トランザクション内でSQLを実行（データ変更用）
+    pub async fn execute_mutation(
+        &self,
+        connection_id: &str,
+        sql: &str,
+        timeout: Duration,
+    ) -> Result<MutationResult, QueryError> {
+        let entry = self
+            .get_entry(connection_id)
+            .ok_or_else(|| self.missing_error(connection_id))?;
+        let mut guard = entry.session.lock().await;
+        let session = guard
+            .as_mut()
+            .ok_or_else(|| not_open_error(connection_id))?;
+
+        entry.touch();
+        let result = tokio::time::timeout(timeout, session.execute_mutation(sql))
+            .await
+            .map_err(|_| timeout_error(timeout))?;
+        entry.touch();
+
+        result
+    }
+
+    /// トランザクション内で複数文を順次実行し、文ごとの結果を返す（`timeout` は1文あたり）
+    pub async fn execute_script(
+        &self,
+        connection_id: &str,
+        sql: &str,
+        mode: ScriptErrorMode,
+        timeout: Duration,
+    ) -> Result<Vec<StatementResult>, QueryError> {
+        let entry = self
+            .get_entry(connection_id)
+            .ok_or_else(|| self.missing_error(connection_id))?;
+        let mut guard = entry.session.lock().await;
+        let session = guard
+            .as_mut()
+            .ok_or_else(|| not_open_error(connection_id))?;
+
+        entry.touch();
+        let results = session.execute_script(sql, mode, timeout).await;
+        entry.touch();
+
+        Ok(results)
+    }
+
+    /// コミット
+    ///
+    /// トランザクションを開始したウィンドウ以外からは実行できない
+    pub async fn commit(&self, connection_id: &str, window_label: &str) -> Result<(), QueryError> {
+        let taken = self
+            .take_entry(connection_id, window_label)
+            .map_err(not_owner_error)?;
+        let entry = match taken {
+            Some(entry) => entry,
+            None => {
+                let expired = self
+                    .take_expired(connection_id, window_label)
+                    .map_err(not_owner_error)?;
+                return Err(match expired {
+                    Some(info) => expired_error(&info),
+                    None => not_open_error(connection_id),
+                });
+            }
+        };
+
+        entry.stop_watchdog();
+        let session = entry.session.lock().await.take();
+        match session {
+            Some(session) => session.commit().await,
+            None => Err(not_open_error(connection_id)),
+        }
+    }
+
+    /// ロールバック
+    ///
+    /// タイムアウトで既にロールバックされている場合は、その通知を確認済みとして成功を返す。
+    /// トランザクションを開始したウィンドウ以外からは実行できない
+    pub async fn rollback(
+        &self,
+        connection_id: &str,
+        window_label: &str,
+    ) -> Result<(), QueryError> {
+        let taken = self
+            .take_entry(connection_id, window_label)
+            .map_err(not_owner_error)?;
+        let entry = match taken {
+            Some(entry) => entry,
+            None => {
+                let expired = self
+                    .take_expired(connection_id, window_label)
+                    .map_err(not_owner_error)?;
+                return match expired {
+                    Some(_) => Ok(()),
+                    None => Err(not_open_error(connection_id)),
+                };
+            }
+        };
+
+        entry.stop_watchdog();
+        let session = entry.session.lock().await.take();
+        match session {
+            Some(session) => session.rollback().await,
+            None => Ok(()),
+        }
+    }
+
+    /// 指定ウィンドウで開いているトランザクションをすべてロールバック
+    pub async fn rollback_window(&self, window_label: &str) {
+        for info in self.list_by_window(window_label) {
+            let _ = self.rollback(&info.connection_id, window_label).await;
+        }
+        self.expired
+            .write()
+            .unwrap()
+            .retain(|_, info| info.window_label != window_label);
+    }
+
+    /// トランザクションの状態を取得
+    pub fn status(&self, connection_id: &str) -> Option<TransactionInfo> {
+        self.transactions
+            .read()
+            .unwrap()
+            .get(connection_id)
+            .map(|entry| entry.info.clone())
+    }
+
+    /// 指定ウィンドウで開いているトランザクション一覧
+    pub fn list_by_window(&self, window_label: &str) -> Vec<TransactionInfo> {
+        self.transactions
+            .read()
+            .unwrap()
+            .values()
+            .filter(|entry| entry.info.window_label == window_label)
+            .map(|entry| entry.info.clone())
+            .collect()
+    }
+
+    fn get_entry(&self, connection_id: &str) -> Option<Arc<OpenTransaction>> {
+        self.transactions
+            .read()
+            .unwrap()
+            .get(connection_id)
+            .map(Arc::clone)
+    }
+
+    /// トランザクションが見つからない場合のエラー
+    fn missing_error(&self, connection_id: &str) -> QueryError {
+        match self.expired.read().unwrap().get(connection_id) {
+            Some(info) => expired_error(info),
+            None => not_open_error(connection_id),
+        }
+    }
+
+    /// 開始したウィンドウからの操作であれば、トランザクションをマップから取り出す
+    ///
+    /// 別のウィンドウが開始していた場合は、そのウィンドウのラベルを返す
+    fn take_entry(
+        &self,
+        connection_id: &str,
+        window_label: &str,
+    ) -> Result<Option<Arc<OpenTransaction>>, String> {
+        let mut transactions = self.transactions.write().unwrap();
+        match transactions.get(connection_id) {
+            Some(entry) if entry.info.window_label != window_label => {
+                Err(entry.info.window_label.clone())
+            }
+            Some(_) => Ok(transactions.remove(connection_id)),
+            None => Ok(None),
+        }
+    }
+
+    /// 開始したウィンドウからの操作であれば、タイムアウト済みの通知を取り出す
+    fn take_expired(
+        &self,
+        connection_id: &str,
+        window_label: &str,
+    ) -> Result<Option<TransactionInfo>, String> {
+        let mut expired = self.expired.write().unwrap();
+        match expired.get(connection_id) {
+            Some(info) if info.window_label != window_label => Err(info.window_label.clone()),
+            Some(_) => Ok(expired.remove(connection_id)),
+            None => Ok(None),
+        }
+    }
+
+    /// アイドルタイムアウトを監視し、超過したらロールバックするタスクを起動
+    fn spawn_watchdog(
+        &self,
+        entry: Arc<OpenTransaction>,
+        idle_timeout: Duration,
+    ) -> JoinHandle<()> {
+        let transactions = Arc::clone(&self.transactions);
+        let expired = Arc::clone(&self.expired);
+
+        tokio::spawn(async move {
+            loop {
+                // 実行中の文がある間はアイドルとみなさない
+                if entry.session.try_lock().is_err() {
+                    tokio::time::sleep(idle_timeout).await;
+                    continue;
+                }
+
+                let idle = entry.idle_time();
+                if idle >= idle_timeout {
+                    break;
+                }
+                tokio::time::sleep(idle_timeout - idle).await;
+            }
+
+            // コミット・ロールバックと競合した場合は、マップから取り除いた側が後処理を行う
+            let removed = {
+                let mut transactions = transactions.write().unwrap();
+                match transactions.get(&entry.info.connection_id) {
+                    Some(current) if Arc::ptr_eq(current, &entry) => {
+                        transactions.remove(&entry.info.connection_id);
+                        true
+                    }
+                    _ => false,
+                }
+            };
+            if !removed {
+                return;
+            }
+
+            expired
+                .write()
+                .unwrap()
+                .insert(entry.info.connection_id.clone(), entry.info.clone());
+
+            let session = entry.session.lock().await.take();
+            if let Some(session) = session {
+                let _ = session.rollback().await;
+            }
+        })
+    }
+}
+
+impl Default for TransactionManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn transaction_error(message: String) -> QueryError {
+    QueryError {
+        code: QueryErrorCode::TransactionError,
+        message,
+        details: None,
+        native_code: None,
+    }
+}
+
+fn already_open_error(info: &TransactionInfo) -> QueryError {
+    transaction_error(format!(
+        "A transaction is already open for this connection (window: {})",
+        info.window_label
+    ))
+}
+
+fn not_open_error(connection_id: &str) -> QueryError {
+    transaction_error(format!(
+        "No open transaction for connection: {}",
+        connection_id
+    ))
+}
+
+fn not_owner_error(window_label: String) -> QueryError {
+    transaction_error(format!(
+        "The transaction was opened by another window (window: {})",
+        window_label
+    ))
+}
+
+fn expired_error(info: &TransactionInfo) -> QueryError {
+    transaction_error(format!(
+        "Transaction was rolled back after {} seconds of inactivity",
+        info.timeout_seconds
+    ))
+}
+
+pub(crate) fn timeout_error(timeout: Duration) -> QueryError {
+    QueryError {
+        code: QueryErrorCode::QueryTimeout,
+        message: format!("Query timed out after {:?}", timeout),
+        details: None,
+        native_code: None,
+    }
+}
@@ -0,0 +1,188 @@
+use crate::connection::{ConnectionConfig, ConnectionInfo, DatabaseType, FileConfig};
+use crate::database::SqliteExecutor;
+use crate::models::query_result::{QueryErrorCode, QueryValue};
+use crate::services::query_executor::QueryExecutor;
+use crate::services::transaction_manager::TransactionManager;
+use tempfile::TempDir;
+use tokio::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// テーブル作成済みのSQLiteファイルに接続する
+async fn sqlite_executor(dir: &TempDir) -> SqliteExecutor {
+    let path = dir.path().join("transaction.db");
+    std::fs::File::create(&path).unwrap();
+
+    let connection = ConnectionInfo::new(
+        "Test SQLite".to_string(),
+        DatabaseType::SQLite,
+        ConnectionConfig::File(FileConfig {
+            file_path: path.to_string_lossy().to_string(),
+            readonly: false,
+        }),
+    );
+    let executor = SqliteExecutor::new(&connection).await.unwrap();
+    executor
+        .execute_mutation("CREATE TABLE items (id INTEGER PRIMARY KEY, name TEXT)")
+        .await
+        .unwrap();
+    executor
+}
+
+async fn count_items(executor: &SqliteExecutor) -> i64 {
+    let result = executor
+        .execute("SELECT COUNT(*) AS n FROM items")
+        .await
+        .unwrap();
+    match result.rows[0].values[0] {
+        QueryValue::Int(n) => n,
+        ref other => panic!("unexpected value: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_commit_persists_changes() {
+    let dir = TempDir::new().unwrap();
+    let executor = sqlite_executor(&dir).await;
+    let manager = TransactionManager::new();
+
+    let info = manager
+        .begin("conn-1", "sql-editor-1", &executor, TIMEOUT)
+        .await
+        .unwrap();
+    assert_eq!(info.connection_id, "conn-1");
+    assert_eq!(info.window_label, "sql-editor-1");
+
+    let result = manager
+        .execute_mutation("conn-1", "INSERT INTO items (name) VALUES ('a')", TIMEOUT)
+        .await
+        .unwrap();
+    assert_eq!(result.affected_rows, 1);
+
+    // トランザクション内からは未コミットの行が見える
+    let result = manager
+        .execute_with_params("conn-1", "SELECT COUNT(*) FROM items", &[], TIMEOUT)
+        .await
+        .unwrap();
+    assert!(matches!(result.rows[0].values[0], QueryValue::Int(1)));
+
+    manager.commit("conn-1", "sql-editor-1").await.unwrap();
+    assert!(manager.status("conn-1").is_none());
+    assert_eq!(count_items(&executor).await, 1);
+}
+
+#[tokio::test]
+async fn test_rollback_discards_changes() {
+    let dir = TempDir::new().unwrap();
+    let executor = sqlite_executor(&dir).await;
+    let manager = TransactionManager::new();
+
+    manager
+        .begin("conn-1", "sql-editor-1", &executor, TIMEOUT)
+        .await
+        .unwrap();
+    manager
+        .execute_mutation("conn-1", "INSERT INTO items (name) VALUES ('a')", TIMEOUT)
+        .await
+        .unwrap();
+    manager.rollback("conn-1", "sql-editor-1").await.unwrap();
+
+    assert_eq!(count_items(&executor).await, 0);
+
+    // 開いていないトランザクションのコミット・ロールバックはエラー
+    let err = manager.commit("conn-1", "sql-editor-1").await.unwrap_err();
+    assert!(matches!(err.code, QueryErrorCode::TransactionError));
+    assert!(manager.rollback("conn-1", "sql-editor-1").await.is_err());
+}
+
+#[tokio::test]
+async fn test_transaction_is_scoped_to_window() {
+    let dir = TempDir::new().unwrap();
+    let executor = sqlite_executor(&dir).await;
+    let manager = TransactionManager::new();
+
+    manager
+        .begin("conn-1", "sql-editor-1", &executor, TIMEOUT)
+        .await
+        .unwrap();
+
+    assert!(manager
+        .is_active_in_window("conn-1", "sql-editor-1")
+        .await
+        .unwrap());
+    assert!(!manager
+        .is_active_in_window("conn-1", "sql-editor-2")
+        .await
+        .unwrap());
+    assert!(!manager
+        .is_active_in_window("conn-2", "sql-editor-1")
+        .await
+        .unwrap());
+    assert_eq!(manager.list_by_window("sql-editor-1").len(), 1);
+
+    // トランザクションを使えない操作は、開始したウィンドウでだけ拒否する
+    let err = manager
+        .ensure_inactive_in_window("conn-1", "sql-editor-1")
+        .await
+        .unwrap_err();
+    assert!(matches!(err.code, QueryErrorCode::TransactionError));
+    assert!(manager
+        .ensure_inactive_in_window("conn-1", "sql-editor-2")
+        .await
+        .is_ok());
+
+    // 同じ接続で二重に開始はできない
+    let err = manager
+        .begin("conn-1", "sql-editor-2", &executor, TIMEOUT)
+        .await
+        .unwrap_err();
+    assert!(matches!(err.code, QueryErrorCode::TransactionError));
+
+    // 開始したウィンドウ以外からはコミット・ロールバックできない
+    let err = manager.commit("conn-1", "sql-editor-2").await.unwrap_err();
+    assert!(matches!(err.code, QueryErrorCode::TransactionError));
+    assert!(manager.rollback("conn-1", "sql-editor-2").await.is_err());
+    assert!(manager.status("conn-1").is_some());
+
+    manager.rollback_window("sql-editor-1").await;
+    assert!(manager.list_by_window("sql-editor-1").is_empty());
+}
+
+#[tokio::test]
+async fn test_idle_transaction_is_rolled_back() {
+    let dir = TempDir::new().unwrap();
+    let executor = sqlite_executor(&dir).await;
+    let manager = TransactionManager::new();
+
+    manager
+        .begin(
+            "conn-1",
+            "sql-editor-1",
+            &executor,
+            Duration::from_millis(100),
+        )
+        .await
+        .unwrap();
+    manager
+        .execute_mutation("conn-1", "INSERT INTO items (name) VALUES ('a')", TIMEOUT)
+        .await
+        .unwrap();
+
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    assert!(manager.status("conn-1").is_none());
+    assert_eq!(count_items(&executor).await, 0);
+
+    // 自動コミットで実行されないよう、確認されるまではエラーを返す
+    let err = manager
+        .is_active_in_window("conn-1", "sql-editor-1")
+        .await
+        .unwrap_err();
+    assert!(matches!(err.code, QueryErrorCode::TransactionError));
+
+    manager.rollback("conn-1", "sql-editor-1").await.unwrap();
+    assert!(!manager
+        .is_active_in_window("conn-1", "sql-editor-1")
+        .await
+        .unwrap());
+}
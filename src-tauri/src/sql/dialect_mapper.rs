@@ -21,9 +21,10 @@ impl DialectMapper {
 
     fn init_mappings(&mut self) {
         match self.db_type.as_str() {
-            "postgresql" => self.init_postgresql(),
-            "mysql" => self.init_mysql(),
+            "postgresql" | "duckdb" => self.init_postgresql(),
+            "mysql" | "mariadb" => self.init_mysql(),
             "sqlite" => self.init_sqlite(),
+            "sqlserver" => self.init_sqlserver(),
             _ => {}
         }
     }
@@ -49,6 +50,15 @@ impl DialectMapper {
             .insert("SUBSTRING".to_string(), "SUBSTR".to_string());
     }
 
+    fn init_sqlserver(&mut self) {
+        self.function_map
+            .insert("CONCAT".to_string(), "CONCAT".to_string());
+        self.function_map
+            .insert("SUBSTRING".to_string(), "SUBSTRING".to_string());
+        self.function_map
+            .insert("LENGTH".to_string(), "LEN".to_string());
+    }
+
     /// 関数名をデータベース方言に応じてマッピング
     pub fn map_function(
         &self,
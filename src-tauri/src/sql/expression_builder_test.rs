@@ -82,6 +82,22 @@ mod tests {
         assert_eq!(sql, "u.first_name || ' ' || u.last_name");
     }
 
+    #[test]
+    fn test_sqlserver_length() {
+        let builder = ExpressionBuilder::new("sqlserver");
+        let node = ExpressionNode::Function(FunctionCall {
+            name: "LENGTH".to_string(),
+            category: FunctionCategory::String,
+            arguments: vec![ExpressionNode::Column(ColumnReference {
+                table: Some("u".to_string()),
+                column: "name".to_string(),
+            })],
+        });
+
+        let sql = builder.build(&node).unwrap();
+        assert_eq!(sql, "LEN(u.name)");
+    }
+
     #[test]
     fn test_depth_limit() {
         let builder = ExpressionBuilder::new("postgresql");
@@ -194,6 +210,7 @@ mod tests {
                         column: WhereConditionColumn {
                             table_alias: "orders".to_string(),
                             column_name: "user_id".to_string(),
+                            data_type: None,
                         },
                         operator: "=".to_string(),
                         value: WhereValue::Column {
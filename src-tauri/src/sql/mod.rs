@@ -1,5 +1,8 @@
 pub mod dialect_mapper;
 pub mod expression_builder;
+pub mod statement_splitter;
 
 #[cfg(test)]
 mod expression_builder_test;
+#[cfg(test)]
+mod statement_splitter_test;
@@ -0,0 +1,453 @@
+use serde::{Deserialize, Serialize};
+
+/// SQL文の範囲（元のSQL文字列中のバイト位置）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatementRange {
+    /// 開始位置（バイト、含む）
+    pub start: usize,
+    /// 終了位置（バイト、含まない）。区切り文字は含まない
+    pub end: usize,
+}
+
+impl StatementRange {
+    /// 範囲内のSQL文を取得
+    pub fn text<'a>(&self, sql: &'a str) -> &'a str {
+        &sql[self.start..self.end]
+    }
+
+    /// 開始位置の文字オフセット（0始まり）
+    pub fn char_offset(&self, sql: &str) -> usize {
+        sql[..self.start].chars().count()
+    }
+}
+
+/// SQL文分割器
+///
+/// 文字列リテラル・引用識別子・コメント・ドル引用符（PostgreSQL）・DELIMITERコマンド（MySQL）を
+/// 字句レベルで解釈し、区切り文字の位置で文を分割する。
+/// `CREATE TRIGGER ... BEGIN ... END` のような本体に `;` を含む文も1文として扱う。
+/// SQL Serverでは `GO` 行でバッチを区切り、CREATE PROCEDURE 等の本体はバッチの終わりまでとする。
+pub struct StatementSplitter {
+    /// 文字列内のバックスラッシュエスケープ（MySQL）
+    backslash_escapes: bool,
+    /// `#` 行コメント（MySQL）
+    hash_comments: bool,
+    /// バッククォート識別子（MySQL, SQLite）
+    backtick_quotes: bool,
+    /// `[...]` 識別子（SQLite, SQL Server）
+    bracket_quotes: bool,
+    /// `$tag$...$tag$` 引用符（PostgreSQL）
+    dollar_quotes: bool,
+    /// ネストしたブロックコメント（PostgreSQL, SQL Server）
+    nested_comments: bool,
+    /// DELIMITERコマンド（MySQL）
+    delimiter_command: bool,
+    /// `GO` 行によるバッチ区切り（SQL Server）
+    go_batches: bool,
+}
+
+impl StatementSplitter {
+    /// 方言名（"postgresql" / "mysql" / "mariadb" / "sqlite" / "duckdb" / "sqlserver"）から分割器を作成
+    ///
+    /// DuckDBはPostgreSQLと同じ字句規則で分割する
+    pub fn new(dialect: &str) -> Self {
+        match dialect {
+            "mysql" | "mariadb" => Self {
+                backslash_escapes: true,
+                hash_comments: true,
+                backtick_quotes: true,
+                bracket_quotes: false,
+                dollar_quotes: false,
+                nested_comments: false,
+                delimiter_command: true,
+                go_batches: false,
+            },
+            "sqlite" => Self {
+                backslash_escapes: false,
+                hash_comments: false,
+                backtick_quotes: true,
+                bracket_quotes: true,
+                dollar_quotes: false,
+                nested_comments: false,
+                delimiter_command: false,
+                go_batches: false,
+            },
+            "sqlserver" => Self {
+                backslash_escapes: false,
+                hash_comments: false,
+                backtick_quotes: false,
+                bracket_quotes: true,
+                dollar_quotes: false,
+                nested_comments: true,
+                delimiter_command: false,
+                go_batches: true,
+            },
+            _ => Self {
+                backslash_escapes: false,
+                hash_comments: false,
+                backtick_quotes: false,
+                bracket_quotes: false,
+                dollar_quotes: true,
+                nested_comments: true,
+                delimiter_command: false,
+                go_batches: false,
+            },
+        }
+    }
+
+    /// SQLを文ごとに分割し、各文の範囲を返す
+    ///
+    /// 空白・コメントのみの文は除外する。範囲は前後の空白を除いたもの。
+    pub fn split(&self, sql: &str) -> Vec<StatementRange> {
+        let bytes = sql.as_bytes();
+        let mut ranges = Vec::new();
+        let mut delimiter = b";".to_vec();
+        let mut statement_start = 0;
+        let mut has_content = false;
+        let mut at_line_start = true;
+        let mut block = self.block_tracker();
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let b = bytes[i];
+
+            // DELIMITERコマンド（行頭のみ、文の途中では解釈しない）
+            if self.delimiter_command && at_line_start && !has_content {
+                if let Some((new_delimiter, next)) = parse_delimiter_command(bytes, i) {
+                    delimiter = new_delimiter;
+                    i = next;
+                    statement_start = i;
+                    continue;
+                }
+            }
+
+            // GO行（行頭のみ）。ブロックの途中でもバッチを終える
+            if self.go_batches && at_line_start {
+                if let Some(next) = parse_go_separator(bytes, i) {
+                    if has_content {
+                        ranges.push(trim_range(bytes, statement_start, i));
+                    }
+                    i = next;
+                    statement_start = i;
+                    has_content = false;
+                    block = self.block_tracker();
+                    continue;
+                }
+            }
+
+            if b == b'\n' {
+                at_line_start = true;
+                i += 1;
+                continue;
+            }
+            if b.is_ascii_whitespace() {
+                i += 1;
+                continue;
+            }
+            at_line_start = false;
+
+            // 区切り文字
+            if bytes[i..].starts_with(&delimiter) {
+                block.flush();
+                // BEGIN ... END ブロック内・ルーチン本体の `;` では分割しない
+                if (block.depth == 0 && !block.routine_body) || delimiter != b";" {
+                    if has_content {
+                        ranges.push(trim_range(bytes, statement_start, i));
+                    }
+                    i += delimiter.len();
+                    statement_start = i;
+                    has_content = false;
+                    block = self.block_tracker();
+                    continue;
+                }
+            }
+
+            // コメント
+            if bytes[i..].starts_with(b"--") || (self.hash_comments && b == b'#') {
+                i = skip_line(bytes, i);
+                continue;
+            }
+            if bytes[i..].starts_with(b"/*") {
+                // MySQLの実行可能コメント（/*! ... */）は文の内容として扱う
+                if bytes[i..].starts_with(b"/*!") {
+                    has_content = true;
+                }
+                i = skip_block_comment(bytes, i, self.nested_comments);
+                continue;
+            }
+
+            // 識別子・キーワード
+            if is_word_byte(b) && !b.is_ascii_digit() {
+                let end = skip_word(bytes, i, self.dollar_quotes);
+                block.on_word(&sql[i..end]);
+                has_content = true;
+                i = end;
+                continue;
+            }
+
+            block.flush();
+            has_content = true;
+
+            i = match b {
+                b'\'' => {
+                    let escapes = self.backslash_escapes || is_escape_string_prefix(bytes, i);
+                    skip_quoted(bytes, i, b'\'', escapes)
+                }
+                b'"' => skip_quoted(bytes, i, b'"', self.backslash_escapes),
+                b'`' if self.backtick_quotes => skip_quoted(bytes, i, b'`', false),
+                b'[' if self.bracket_quotes => skip_until(bytes, i + 1, b"]"),
+                b'$' if self.dollar_quotes => skip_dollar_quoted(bytes, i),
+                _ => i + 1,
+            };
+        }
+
+        if has_content {
+            ranges.push(trim_range(bytes, statement_start, bytes.len()));
+        }
+
+        ranges
+    }
+
+    fn block_tracker(&self) -> BlockTracker {
+        BlockTracker {
+            routine_batches: self.go_batches,
+            ..Default::default()
+        }
+    }
+}
+
+/// ルーチンの種類を探すCREATE文の先頭の語数（`CREATE DEFINER = user@host TRIGGER` など）
+const ROUTINE_HEADER_WORDS: usize = 6;
+
+/// ルーチン定義（トリガー・プロシージャ・関数・イベント）の本体に含まれる
+/// BEGIN ... END / CASE ... END の深さを追跡する
+#[derive(Default)]
+struct BlockTracker {
+    words: usize,
+    is_create: bool,
+    /// CREATE文の先頭でルーチンの種類を読んだか
+    is_routine: bool,
+    depth: usize,
+    pending_end: bool,
+    /// CREATE/ALTER PROCEDURE 等の本体をバッチの終わりまでとするか（SQL Server）
+    routine_batches: bool,
+    /// ルーチン定義の本体の中か
+    routine_body: bool,
+}
+
+impl BlockTracker {
+    fn on_word(&mut self, word: &str) {
+        let upper = word.to_ascii_uppercase();
+        if self.words == 0 {
+            // BEGIN（トランザクション開始）などと区別するため、CREATE文のみ対象にする
+            self.is_create = upper == "CREATE" || (self.routine_batches && upper == "ALTER");
+        }
+        self.words += 1;
+        if !self.is_create {
+            return;
+        }
+
+        // `CREATE TABLE t (begin int)` の列名などを誤判定しないよう、ルーチン定義の本体だけを追跡する
+        if !self.is_routine {
+            match upper.as_str() {
+                "PROC" | "PROCEDURE" | "FUNCTION" | "TRIGGER" | "EVENT" => {
+                    self.is_routine = true;
+                    // CREATE [OR ALTER] PROCEDURE などはバッチ内の唯一の文になる
+                    self.routine_body = self.routine_batches;
+                }
+                "TABLE" | "VIEW" | "INDEX" | "SEQUENCE" | "SCHEMA" | "DATABASE" | "TYPE"
+                | "DOMAIN" => self.is_create = false,
+                _ if self.words >= ROUTINE_HEADER_WORDS => self.is_create = false,
+                _ => {}
+            }
+            return;
+        }
+
+        if self.pending_end {
+            self.pending_end = false;
+            // END IF / END LOOP などはブロックを閉じない（MySQLの複合文）
+            if matches!(upper.as_str(), "IF" | "LOOP" | "WHILE" | "REPEAT") {
+                return;
+            }
+            self.depth = self.depth.saturating_sub(1);
+            // END CASE は直前の END でCASEを閉じ済み
+            if upper == "CASE" {
+                return;
+            }
+        }
+
+        match upper.as_str() {
+            "BEGIN" | "CASE" => self.depth += 1,
+            "END" => self.pending_end = true,
+            _ => {}
+        }
+    }
+
+    /// 保留中の END を確定する
+    fn flush(&mut self) {
+        if self.pending_end {
+            self.pending_end = false;
+            self.depth = self.depth.saturating_sub(1);
+        }
+    }
+}
+
+/// 前後の空白を除いた範囲
+fn trim_range(bytes: &[u8], mut start: usize, mut end: usize) -> StatementRange {
+    while start < end && bytes[start].is_ascii_whitespace() {
+        start += 1;
+    }
+    while end > start && bytes[end - 1].is_ascii_whitespace() {
+        end -= 1;
+    }
+    StatementRange { start, end }
+}
+
+/// 識別子を構成するバイト（非ASCIIも識別子の一部とみなす）
+fn is_word_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b >= 0x80
+}
+
+/// 識別子を読み飛ばす（PostgreSQLでは `$` も識別子に含まれる）
+fn skip_word(bytes: &[u8], start: usize, allow_dollar: bool) -> usize {
+    let mut i = start;
+    while i < bytes.len() && (is_word_byte(bytes[i]) || (allow_dollar && bytes[i] == b'$')) {
+        i += 1;
+    }
+    i
+}
+
+/// 行末まで読み飛ばす（改行は含めない）
+fn skip_line(bytes: &[u8], start: usize) -> usize {
+    let mut i = start;
+    while i < bytes.len() && bytes[i] != b'\n' {
+        i += 1;
+    }
+    i
+}
+
+fn skip_block_comment(bytes: &[u8], start: usize, nested: bool) -> usize {
+    let mut depth = 0;
+    let mut i = start;
+    while i < bytes.len() {
+        if bytes[i..].starts_with(b"/*") {
+            depth += 1;
+            i += 2;
+            if !nested && depth > 1 {
+                depth = 1;
+            }
+        } else if bytes[i..].starts_with(b"*/") {
+            depth -= 1;
+            i += 2;
+            if depth == 0 {
+                return i;
+            }
+        } else {
+            i += 1;
+        }
+    }
+    bytes.len()
+}
+
+/// 引用符で囲まれた部分を読み飛ばす（引用符の二重化によるエスケープに対応）
+fn skip_quoted(bytes: &[u8], start: usize, quote: u8, backslash_escapes: bool) -> usize {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        let b = bytes[i];
+        if backslash_escapes && b == b'\\' {
+            i += 2;
+        } else if b == quote {
+            if bytes.get(i + 1) == Some(&quote) {
+                i += 2;
+            } else {
+                return i + 1;
+            }
+        } else {
+            i += 1;
+        }
+    }
+    bytes.len()
+}
+
+fn skip_until(bytes: &[u8], start: usize, terminator: &[u8]) -> usize {
+    let mut i = start;
+    while i < bytes.len() {
+        if bytes[i..].starts_with(terminator) {
+            return i + terminator.len();
+        }
+        i += 1;
+    }
+    bytes.len()
+}
+
+/// PostgreSQLのエスケープ文字列（E'...'）か
+fn is_escape_string_prefix(bytes: &[u8], quote_pos: usize) -> bool {
+    quote_pos >= 1
+        && matches!(bytes[quote_pos - 1], b'E' | b'e')
+        && (quote_pos < 2 || !is_word_byte(bytes[quote_pos - 2]))
+}
+
+/// `$tag$...$tag$` を読み飛ばす。ドル引用符でなければ `$` のみ進める
+fn skip_dollar_quoted(bytes: &[u8], start: usize) -> usize {
+    // 識別子の一部（foo$bar）や位置パラメータ（$1）はドル引用符ではない
+    if start > 0 && is_word_byte(bytes[start - 1]) {
+        return start + 1;
+    }
+
+    let mut i = start + 1;
+    if i < bytes.len() && bytes[i].is_ascii_digit() {
+        return start + 1;
+    }
+    while i < bytes.len() && is_word_byte(bytes[i]) {
+        i += 1;
+    }
+    if bytes.get(i) != Some(&b'$') {
+        return start + 1;
+    }
+
+    let tag = &bytes[start..=i];
+    skip_until(bytes, i + 1, tag)
+}
+
+/// `GO [回数]` 行なら行末の位置を返す
+fn parse_go_separator(bytes: &[u8], start: usize) -> Option<usize> {
+    let end = start + 2;
+    if end > bytes.len() || !bytes[start..end].eq_ignore_ascii_case(b"GO") {
+        return None;
+    }
+
+    let line_end = skip_line(bytes, end);
+    let rest = std::str::from_utf8(&bytes[end..line_end]).ok()?;
+    let rest = rest.split("--").next().unwrap_or_default().trim();
+    if rest.is_empty() || rest.bytes().all(|b| b.is_ascii_digit()) {
+        Some(line_end)
+    } else {
+        None
+    }
+}
+
+/// `DELIMITER xx` 行を解釈し、新しい区切り文字と行末の位置を返す
+fn parse_delimiter_command(bytes: &[u8], start: usize) -> Option<(Vec<u8>, usize)> {
+    const KEYWORD: &[u8] = b"DELIMITER";
+    let end = start + KEYWORD.len();
+    if end >= bytes.len() || !bytes[start..end].eq_ignore_ascii_case(KEYWORD) {
+        return None;
+    }
+    if !matches!(bytes[end], b' ' | b'\t') {
+        return None;
+    }
+
+    let line_end = skip_line(bytes, end);
+    let delimiter: Vec<u8> = bytes[end..line_end]
+        .iter()
+        .copied()
+        .skip_while(|b| b.is_ascii_whitespace())
+        .take_while(|b| !b.is_ascii_whitespace())
+        .collect();
+    if delimiter.is_empty() {
+        return None;
+    }
+
+    Some((delimiter, line_end))
+}
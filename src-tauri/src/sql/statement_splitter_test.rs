@@ -0,0 +1,189 @@
+#[cfg(test)]
+mod tests {
+    use crate::sql::statement_splitter::{StatementRange, StatementSplitter};
+
+    fn split<'a>(sql: &'a str, dialect: &str) -> Vec<&'a str> {
+        StatementSplitter::new(dialect)
+            .split(sql)
+            .iter()
+            .map(|range| range.text(sql))
+            .collect()
+    }
+
+    #[test]
+    fn test_split_simple_statements() {
+        let statements = split("SELECT 1; SELECT 2;\n\nSELECT 3", "postgresql");
+        assert_eq!(statements, vec!["SELECT 1", "SELECT 2", "SELECT 3"]);
+    }
+
+    #[test]
+    fn test_split_returns_byte_ranges() {
+        let sql = "  SELECT 1;\n  SELECT 'あ';";
+        let ranges = StatementSplitter::new("postgresql").split(sql);
+
+        assert_eq!(ranges[0], StatementRange { start: 2, end: 10 });
+        assert_eq!(ranges[1].text(sql), "SELECT 'あ'");
+        assert_eq!(ranges[1].char_offset(sql), 14);
+    }
+
+    #[test]
+    fn test_semicolon_in_string_literal() {
+        let statements = split(
+            "INSERT INTO t VALUES ('a;b', 'it''s;'); SELECT 2",
+            "postgresql",
+        );
+        assert_eq!(
+            statements,
+            vec!["INSERT INTO t VALUES ('a;b', 'it''s;')", "SELECT 2"]
+        );
+    }
+
+    #[test]
+    fn test_semicolon_in_quoted_identifier() {
+        let statements = split(r#"SELECT "a;b" FROM t; SELECT `c;d` FROM t"#, "mysql");
+        assert_eq!(statements.len(), 2);
+        assert_eq!(statements[1], "SELECT `c;d` FROM t");
+    }
+
+    #[test]
+    fn test_semicolon_in_comments() {
+        let sql = "SELECT 1 -- comment;\n; /* block; comment */ SELECT 2; -- trailing;";
+        let statements = split(sql, "postgresql");
+        assert_eq!(
+            statements,
+            vec!["SELECT 1 -- comment;", "/* block; comment */ SELECT 2"]
+        );
+    }
+
+    #[test]
+    fn test_comment_only_input() {
+        assert!(split("-- nothing here;\n/* ; */", "postgresql").is_empty());
+        assert!(split(" ; ;; ", "sqlite").is_empty());
+    }
+
+    #[test]
+    fn test_postgres_nested_block_comment() {
+        let statements = split(
+            "/* outer /* inner; */ still; */ SELECT 1; SELECT 2",
+            "postgresql",
+        );
+        assert_eq!(statements.len(), 2);
+        assert!(statements[0].ends_with("SELECT 1"));
+    }
+
+    #[test]
+    fn test_postgres_dollar_quoted_function_body() {
+        let sql = "CREATE FUNCTION f() RETURNS void AS $body$\nBEGIN\n  UPDATE t SET a = 1;\n  DELETE FROM t;\nEND;\n$body$ LANGUAGE plpgsql;\nSELECT f();";
+        let statements = split(sql, "postgresql");
+        assert_eq!(statements.len(), 2);
+        assert!(statements[0].ends_with("LANGUAGE plpgsql"));
+        assert_eq!(statements[1], "SELECT f()");
+
+        let statements = split("DO $$ BEGIN PERFORM 1; END $$; SELECT $1", "postgresql");
+        assert_eq!(
+            statements,
+            vec!["DO $$ BEGIN PERFORM 1; END $$", "SELECT $1"]
+        );
+    }
+
+    #[test]
+    fn test_postgres_escape_string() {
+        let statements = split(r"SELECT E'a\';b'; SELECT 'c\'; SELECT 3", "postgresql");
+        assert_eq!(
+            statements,
+            vec![r"SELECT E'a\';b'", r"SELECT 'c\'", "SELECT 3"]
+        );
+    }
+
+    #[test]
+    fn test_mysql_backslash_escape_and_hash_comment() {
+        let statements = split("SELECT 'a\\';b'; # comment;\nSELECT 2", "mysql");
+        assert_eq!(statements, vec!["SELECT 'a\\';b'", "# comment;\nSELECT 2"]);
+    }
+
+    #[test]
+    fn test_mysql_delimiter_blocks() {
+        let sql = "DELIMITER $$\nCREATE PROCEDURE p()\nBEGIN\n  SELECT 1;\n  SELECT 2;\nEND$$\nDELIMITER ;\nCALL p();";
+        let statements = split(sql, "mysql");
+        assert_eq!(statements.len(), 2);
+        assert!(statements[0].starts_with("CREATE PROCEDURE"));
+        assert!(statements[0].ends_with("END"));
+        assert_eq!(statements[1], "CALL p()");
+    }
+
+    #[test]
+    fn test_sqlite_trigger_body() {
+        let sql = "CREATE TRIGGER trg AFTER INSERT ON t\nBEGIN\n  UPDATE t SET a = CASE WHEN a IS NULL THEN 0 ELSE a END;\n  DELETE FROM u;\nEND;\nSELECT 1;";
+        let statements = split(sql, "sqlite");
+        assert_eq!(statements.len(), 2);
+        assert!(statements[0].ends_with("END"));
+        assert_eq!(statements[1], "SELECT 1");
+    }
+
+    #[test]
+    fn test_create_table_with_block_keyword_columns() {
+        // begin / case / end は PostgreSQL・SQLite では引用符なしの列名に使える
+        let sql = "CREATE TABLE t (begin int, case_no int, \"end\" int); SELECT 1";
+        for dialect in ["postgresql", "sqlite", "mysql"] {
+            let statements = split(sql, dialect);
+            assert_eq!(statements.len(), 2, "{}", dialect);
+            assert_eq!(statements[1], "SELECT 1");
+        }
+        let statements = split("CREATE TABLE t (begin int, case int); SELECT 1", "sqlite");
+        assert_eq!(statements.len(), 2);
+        let statements = split("CREATE TABLE function (begin int); SELECT 1", "postgresql");
+        assert_eq!(statements.len(), 2);
+    }
+
+    #[test]
+    fn test_mysql_event_and_definer_trigger_bodies() {
+        let sql = "CREATE DEFINER = root@localhost TRIGGER trg BEFORE INSERT ON t FOR EACH ROW \
+                   BEGIN SET NEW.a = 1; SET NEW.b = 2; END; \
+                   CREATE EVENT e ON SCHEDULE EVERY 1 DAY DO BEGIN DELETE FROM t; DELETE FROM u; END; \
+                   SELECT 1";
+        let statements = split(sql, "mysql");
+        assert_eq!(statements.len(), 3);
+        assert!(statements[1].starts_with("CREATE EVENT"));
+        assert_eq!(statements[2], "SELECT 1");
+    }
+
+    #[test]
+    fn test_transaction_begin_is_not_a_block() {
+        let statements = split("BEGIN; UPDATE t SET a = 1; END;", "sqlite");
+        assert_eq!(statements, vec!["BEGIN", "UPDATE t SET a = 1", "END"]);
+    }
+
+    #[test]
+    fn test_mysql_compound_statement_without_delimiter() {
+        let sql = "CREATE PROCEDURE p() BEGIN IF 1 THEN SELECT 1; END IF; END; SELECT 2";
+        let statements = split(sql, "mysql");
+        assert_eq!(statements.len(), 2);
+        assert_eq!(statements[1], "SELECT 2");
+    }
+
+    #[test]
+    fn test_sqlserver_brackets_and_go_batches() {
+        let sql = "SELECT [a;b] FROM t;\nGO\nINSERT INTO t VALUES (N'x;y')\n  go 2 -- twice\nSELECT 1\nGOTO done";
+        let statements = split(sql, "sqlserver");
+        assert_eq!(
+            statements,
+            vec![
+                "SELECT [a;b] FROM t",
+                "INSERT INTO t VALUES (N'x;y')",
+                "SELECT 1\nGOTO done"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sqlserver_procedure_body_runs_to_batch_end() {
+        let sql = "CREATE OR ALTER PROCEDURE dbo.p AS\nSET NOCOUNT ON;\nSELECT 1;\nGO\nEXEC dbo.p;";
+        let statements = split(sql, "sqlserver");
+        assert_eq!(statements.len(), 2);
+        assert!(statements[0].ends_with("SELECT 1;"));
+        assert_eq!(statements[1], "EXEC dbo.p");
+
+        // 他の方言ではGOを区切りとして扱わない
+        assert_eq!(split("SELECT 1\nGO", "postgresql").len(), 1);
+    }
+}
@@ -1,6 +1,8 @@
 use super::dialect::Dialect;
 use super::reserved_words;
 use crate::models::query::*;
+use crate::models::query_result::QueryParam;
+use serde::Serialize;
 
 /// 引用符のスタイル
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -11,6 +13,43 @@ pub enum QuoteStyle {
     Smart,
 }
 
+/// プレースホルダー付きSQLとバインドパラメータ
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BoundSql {
+    /// プレースホルダー付きSQL
+    pub sql: String,
+    /// バインドパラメータ（プレースホルダー順）
+    pub params: Vec<QueryParam>,
+}
+
+/// 値の出力先
+///
+/// バインドモードでは値をプレースホルダーに置き換えてパラメータを集め、それ以外はリテラルを埋め込む
+#[derive(Debug, Default)]
+pub struct ParamCollector {
+    params: Option<Vec<QueryParam>>,
+}
+
+impl ParamCollector {
+    /// 値をリテラルとしてSQLに埋め込む
+    pub fn inline() -> Self {
+        Self { params: None }
+    }
+
+    /// 値をバインドパラメータとして集める
+    pub fn bind() -> Self {
+        Self {
+            params: Some(Vec::new()),
+        }
+    }
+
+    /// 集めたパラメータ（プレースホルダー順）
+    pub fn into_params(self) -> Vec<QueryParam> {
+        self.params.unwrap_or_default()
+    }
+}
+
 /// SQLビルダー
 pub struct SqlBuilder<'a> {
     pub(crate) dialect: &'a dyn Dialect,
@@ -48,10 +87,35 @@ impl<'a> SqlBuilder<'a> {
 
     /// クエリモデルからSQLを生成
     pub fn build(&self, query: &QueryModel) -> Result<String, String> {
+        self.build_query(query, &mut ParamCollector::inline())
+    }
+
+    /// クエリモデルからプレースホルダー付きSQLとバインドパラメータを生成
+    ///
+    /// 値はSQLに埋め込まず、方言のプレースホルダーに置き換える（NULLはそのまま埋め込む）
+    pub fn build_with_params(&self, query: &QueryModel) -> Result<BoundSql, String> {
+        let mut params = ParamCollector::bind();
+        let sql = self.build_query(query, &mut params)?;
+
+        Ok(BoundSql {
+            sql,
+            params: params.into_params(),
+        })
+    }
+
+    fn build_query(
+        &self,
+        query: &QueryModel,
+        params: &mut ParamCollector,
+    ) -> Result<String, String> {
         let mut parts: Vec<String> = Vec::new();
+        let top = query
+            .limit
+            .as_ref()
+            .and_then(|limit| self.dialect.top_clause(limit.limit, limit.offset));
 
         // SELECT句
-        parts.push(self.build_select(&query.select)?);
+        parts.push(self.build_select(&query.select, top.as_deref())?);
 
         // FROM句
         parts.push(self.build_from(&query.from)?);
@@ -63,7 +127,7 @@ impl<'a> SqlBuilder<'a> {
 
         // WHERE句
         if let Some(ref where_clause) = query.where_clause {
-            parts.push(self.build_where(where_clause)?);
+            parts.push(self.build_where(where_clause, params)?);
         }
 
         // GROUP BY句
@@ -73,7 +137,7 @@ impl<'a> SqlBuilder<'a> {
 
         // HAVING句
         if let Some(ref having) = query.having {
-            parts.push(self.build_having(having)?);
+            parts.push(self.build_having(having, params)?);
         }
 
         // ORDER BY句
@@ -83,19 +147,52 @@ impl<'a> SqlBuilder<'a> {
 
         // LIMIT句
         if let Some(ref limit) = query.limit {
-            parts.push(self.build_limit(limit)?);
+            if top.is_none() {
+                if query.order_by.is_none() && self.dialect.offset_requires_order_by() {
+                    parts.push("ORDER BY (SELECT NULL)".to_string());
+                }
+                parts.push(self.build_limit(limit)?);
+            }
         }
 
         // 結合
         let separator = if self.pretty { "\n" } else { " " };
         Ok(parts.join(separator))
     }
-    pub(crate) fn build_literal_value(&self, value: &LiteralValue) -> Result<String, String> {
+
+    /// リテラル値を生成
+    ///
+    /// バインドモードでは、比較先のカラムの型（`data_type`）が分かれば方言に応じてプレースホルダーを型変換する
+    pub(crate) fn build_literal_value(
+        &self,
+        value: &LiteralValue,
+        data_type: Option<&str>,
+        params: &mut ParamCollector,
+    ) -> Result<String, String> {
+        if let Some(params) = params.params.as_mut() {
+            let param = match value {
+                LiteralValue::Null => return Ok("NULL".to_string()),
+                LiteralValue::String(s) => QueryParam::String(s.clone()),
+                LiteralValue::Number(n) if n.fract() == 0.0 && n.abs() < i64::MAX as f64 => {
+                    QueryParam::Int(*n as i64)
+                }
+                LiteralValue::Number(n) => QueryParam::Float(*n),
+                LiteralValue::Boolean(b) => QueryParam::Bool(*b),
+            };
+            params.push(param);
+            let placeholder = self.dialect.placeholder(params.len());
+            let cast_type = data_type.and_then(|t| self.dialect.bind_cast_type(t));
+            return Ok(match cast_type {
+                Some(cast_type) => format!("CAST({} AS {})", placeholder, cast_type),
+                None => placeholder,
+            });
+        }
+
         match value {
             LiteralValue::Null => Ok("NULL".to_string()),
             LiteralValue::String(s) => Ok(self.dialect.escape_string(s)),
             LiteralValue::Number(n) => Ok(n.to_string()),
-            LiteralValue::Boolean(b) => Ok(if *b { "TRUE" } else { "FALSE" }.to_string()),
+            LiteralValue::Boolean(b) => Ok(self.dialect.boolean_literal(*b)),
         }
     }
 
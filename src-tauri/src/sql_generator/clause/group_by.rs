@@ -1,5 +1,5 @@
 use crate::models::query::*;
-use crate::sql_generator::builder::SqlBuilder;
+use crate::sql_generator::builder::{ParamCollector, SqlBuilder};
 
 impl<'a> SqlBuilder<'a> {
     /// GROUP BY句を生成
@@ -20,13 +20,17 @@ impl<'a> SqlBuilder<'a> {
     }
 
     /// HAVING句を生成
-    pub fn build_having(&self, having: &HavingClause) -> Result<String, String> {
+    pub fn build_having(
+        &self,
+        having: &HavingClause,
+        params: &mut ParamCollector,
+    ) -> Result<String, String> {
         let conditions: Vec<String> = having
             .conditions
             .iter()
             .map(|cond| {
                 let agg = self.build_aggregate(&cond.aggregate)?;
-                let value = self.build_literal_value(&cond.value)?;
+                let value = self.build_literal_value(&cond.value, None, params)?;
                 Ok(format!("{} {} {}", agg, cond.operator, value))
             })
             .collect::<Result<Vec<_>, String>>()?;
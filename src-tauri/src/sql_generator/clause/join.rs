@@ -13,18 +13,32 @@ impl<'a> SqlBuilder<'a> {
             _ => return Err(format!("Unknown join type: {}", join.join_type)),
         };
 
-        let table = &join.table;
-        let table_sql = format!(
-            "{}.{} {}",
-            self.quote_identifier(&table.schema),
-            self.quote_identifier(&table.name),
-            self.quote_identifier(&table.alias)
-        );
+        let table_sql = self.build_table_reference(&join.table);
 
         if join.conditions.is_empty() {
             return Ok(format!("{} {}", join_type, table_sql));
         }
 
+        Ok(format!(
+            "{} {} ON {}",
+            join_type,
+            table_sql,
+            self.build_join_conditions(join)
+        ))
+    }
+
+    /// 別名付きのテーブル参照を生成
+    pub(crate) fn build_table_reference(&self, table: &TableReference) -> String {
+        format!(
+            "{}.{} {}",
+            self.quote_identifier(&table.schema),
+            self.quote_identifier(&table.name),
+            self.quote_identifier(&table.alias)
+        )
+    }
+
+    /// JOINの結合条件（ONの後ろ）を生成
+    pub(crate) fn build_join_conditions(&self, join: &JoinClause) -> String {
         let conditions: Vec<String> = join
             .conditions
             .iter()
@@ -41,8 +55,6 @@ impl<'a> SqlBuilder<'a> {
             .collect();
 
         let logic = &join.condition_logic;
-        let on_clause = conditions.join(&format!(" {} ", logic));
-
-        Ok(format!("{} {} ON {}", join_type, table_sql, on_clause))
+        conditions.join(&format!(" {} ", logic))
     }
 }
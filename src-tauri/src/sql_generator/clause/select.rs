@@ -4,14 +4,19 @@ use crate::sql_generator::builder::SqlBuilder;
 use crate::validators::expression_validator::ExpressionValidator;
 
 impl<'a> SqlBuilder<'a> {
-    /// SELECT句を生成
-    pub fn build_select(&self, select: &SelectClause) -> Result<String, String> {
+    /// SELECT句を生成（`top` は方言の `top_clause`）
+    pub fn build_select(&self, select: &SelectClause, top: Option<&str>) -> Result<String, String> {
         let mut sql = String::from("SELECT");
 
         if select.distinct {
             sql.push_str(" DISTINCT");
         }
 
+        if let Some(top) = top {
+            sql.push(' ');
+            sql.push_str(top);
+        }
+
         let columns: Vec<String> = select
             .columns
             .iter()
@@ -1,23 +1,28 @@
 use crate::models::query::*;
-use crate::sql_generator::builder::SqlBuilder;
+use crate::sql_generator::builder::{ParamCollector, SqlBuilder};
 
 impl<'a> SqlBuilder<'a> {
     /// WHERE句を生成
-    pub fn build_where(&self, where_clause: &WhereClause) -> Result<String, String> {
+    pub fn build_where(
+        &self,
+        where_clause: &WhereClause,
+        params: &mut ParamCollector,
+    ) -> Result<String, String> {
         let conditions =
-            self.build_where_conditions(&where_clause.conditions, &where_clause.logic)?;
+            self.build_where_conditions(&where_clause.conditions, &where_clause.logic, params)?;
         Ok(format!("WHERE {}", conditions))
     }
 
     /// WHERE条件を再帰的に生成
-    fn build_where_conditions(
+    pub(crate) fn build_where_conditions(
         &self,
         conditions: &[WhereConditionItem],
         logic: &str,
+        params: &mut ParamCollector,
     ) -> Result<String, String> {
         let parts: Vec<String> = conditions
             .iter()
-            .map(|item| self.build_where_condition_item(item))
+            .map(|item| self.build_where_condition_item(item, params))
             .collect::<Result<Vec<_>, _>>()?;
 
         if parts.is_empty() {
@@ -29,43 +34,61 @@ impl<'a> SqlBuilder<'a> {
     }
 
     /// WHERE条件アイテムを生成
-    fn build_where_condition_item(&self, item: &WhereConditionItem) -> Result<String, String> {
+    fn build_where_condition_item(
+        &self,
+        item: &WhereConditionItem,
+        params: &mut ParamCollector,
+    ) -> Result<String, String> {
         match item {
-            WhereConditionItem::Condition(cond) => self.build_condition(cond),
+            WhereConditionItem::Condition(cond) => self.build_condition(cond, params),
             WhereConditionItem::Group(group) => {
-                let inner = self.build_where_conditions(&group.conditions, &group.logic)?;
+                let inner = self.build_where_conditions(&group.conditions, &group.logic, params)?;
                 Ok(format!("({})", inner))
             }
         }
     }
 
     /// 単一条件を生成
-    fn build_condition(&self, cond: &WhereCondition) -> Result<String, String> {
+    fn build_condition(
+        &self,
+        cond: &WhereCondition,
+        params: &mut ParamCollector,
+    ) -> Result<String, String> {
         let column = format!(
             "{}.{}",
             self.quote_identifier(&cond.column.table_alias),
             self.quote_identifier(&cond.column.column_name)
         );
 
-        let value_sql = self.build_where_value(&cond.value, &cond.operator)?;
+        // LIKE は文字列として比較するため、カラムの型には変換しない
+        let data_type = match cond.operator.to_uppercase().as_str() {
+            "LIKE" | "NOT LIKE" | "ILIKE" | "NOT ILIKE" => None,
+            _ => cond.column.data_type.as_deref(),
+        };
+        let value_sql = self.build_where_value(&cond.value, data_type, params)?;
 
         Ok(format!("{} {} {}", column, cond.operator, value_sql))
     }
 
     /// WHERE値を生成
-    fn build_where_value(&self, value: &WhereValue, _operator: &str) -> Result<String, String> {
+    fn build_where_value(
+        &self,
+        value: &WhereValue,
+        data_type: Option<&str>,
+        params: &mut ParamCollector,
+    ) -> Result<String, String> {
         match value {
-            WhereValue::Literal { value } => self.build_literal_value(value),
+            WhereValue::Literal { value } => self.build_literal_value(value, data_type, params),
             WhereValue::List { values } => {
                 let items: Vec<String> = values
                     .iter()
-                    .map(|v| self.build_literal_value(v))
+                    .map(|v| self.build_literal_value(v, data_type, params))
                     .collect::<Result<Vec<_>, _>>()?;
                 Ok(format!("({})", items.join(", ")))
             }
             WhereValue::Range { from, to } => {
-                let from_sql = self.build_literal_value(from)?;
-                let to_sql = self.build_literal_value(to)?;
+                let from_sql = self.build_literal_value(from, data_type, params)?;
+                let to_sql = self.build_literal_value(to, data_type, params)?;
                 Ok(format!("{} AND {}", from_sql, to_sql))
             }
             WhereValue::Column {
@@ -9,6 +9,18 @@ pub trait Dialect: Send + Sync {
     /// LIMITオフセット構文
     fn limit_offset(&self, limit: u64, offset: Option<u64>) -> String;
 
+    /// SELECTの直後に書く行数制限（`TOP n`）
+    ///
+    /// `Some` を返した場合はLIMIT句を出力しない
+    fn top_clause(&self, _limit: u64, _offset: Option<u64>) -> Option<String> {
+        None
+    }
+
+    /// OFFSETを指定する際にORDER BYが必須か
+    fn offset_requires_order_by(&self) -> bool {
+        false
+    }
+
     /// LIKE演算子のエスケープ文字
     fn like_escape_char(&self) -> char {
         '\\'
@@ -24,6 +36,23 @@ pub trait Dialect: Send + Sync {
         true
     }
 
+    /// 真偽値リテラル
+    fn boolean_literal(&self, value: bool) -> String {
+        if value { "TRUE" } else { "FALSE" }.to_string()
+    }
+
+    /// バインドパラメータのプレースホルダー（indexは1始まり）
+    fn placeholder(&self, _index: usize) -> String {
+        "?".to_string()
+    }
+
+    /// バインドパラメータを比較先のカラムの型に変換する場合の型名
+    ///
+    /// 文字列のパラメータを暗黙に変換できないDBでは、`CAST(? AS 型)` として比較する
+    fn bind_cast_type(&self, _data_type: &str) -> Option<String> {
+        None
+    }
+
     /// データベース種別名
     fn dialect_name(&self) -> &str;
 }
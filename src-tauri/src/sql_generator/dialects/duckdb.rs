@@ -0,0 +1,35 @@
+use super::super::dialect::Dialect;
+
+/// DuckDB方言
+///
+/// 構文はPostgreSQLに近いが、プレースホルダーは `?` を使う
+pub struct DuckDbDialect;
+
+impl Dialect for DuckDbDialect {
+    fn quote_identifier(&self, identifier: &str) -> String {
+        format!("\"{}\"", identifier.replace('"', "\"\""))
+    }
+
+    fn escape_string(&self, value: &str) -> String {
+        format!("'{}'", value.replace('\'', "''"))
+    }
+
+    fn limit_offset(&self, limit: u64, offset: Option<u64>) -> String {
+        match offset {
+            Some(off) => format!("LIMIT {} OFFSET {}", limit, off),
+            None => format!("LIMIT {}", limit),
+        }
+    }
+
+    fn ilike_operator(&self) -> &str {
+        "ILIKE"
+    }
+
+    fn supports_nulls_order(&self) -> bool {
+        true
+    }
+
+    fn dialect_name(&self) -> &str {
+        "duckdb"
+    }
+}
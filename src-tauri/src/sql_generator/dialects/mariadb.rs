@@ -0,0 +1,33 @@
+use super::super::dialect::Dialect;
+use super::mysql::MysqlDialect;
+
+/// MariaDB方言
+///
+/// 構文はMySQLと共通で、予約語の判定だけを区別する
+pub struct MariadbDialect;
+
+impl Dialect for MariadbDialect {
+    fn quote_identifier(&self, identifier: &str) -> String {
+        MysqlDialect.quote_identifier(identifier)
+    }
+
+    fn escape_string(&self, value: &str) -> String {
+        MysqlDialect.escape_string(value)
+    }
+
+    fn limit_offset(&self, limit: u64, offset: Option<u64>) -> String {
+        MysqlDialect.limit_offset(limit, offset)
+    }
+
+    fn ilike_operator(&self) -> &str {
+        "LIKE"
+    }
+
+    fn supports_nulls_order(&self) -> bool {
+        false
+    }
+
+    fn dialect_name(&self) -> &str {
+        "mariadb"
+    }
+}
@@ -1,7 +1,28 @@
+use super::Dialect;
+use crate::connection::DatabaseType;
+
+pub mod duckdb;
+pub mod mariadb;
+pub mod mssql;
 pub mod mysql;
 pub mod postgres;
 pub mod sqlite;
 
+pub use duckdb::DuckDbDialect;
+pub use mariadb::MariadbDialect;
+pub use mssql::MssqlDialect;
 pub use mysql::MysqlDialect;
 pub use postgres::PostgresDialect;
 pub use sqlite::SqliteDialect;
+
+/// データベース種別に対応するSQL方言
+pub fn dialect_for(database_type: &DatabaseType) -> Box<dyn Dialect> {
+    match database_type {
+        DatabaseType::PostgreSQL => Box::new(PostgresDialect),
+        DatabaseType::MySQL => Box::new(MysqlDialect),
+        DatabaseType::MariaDB => Box::new(MariadbDialect),
+        DatabaseType::SQLite => Box::new(SqliteDialect),
+        DatabaseType::DuckDB => Box::new(DuckDbDialect),
+        DatabaseType::SqlServer => Box::new(MssqlDialect),
+    }
+}
@@ -0,0 +1,55 @@
+use super::super::dialect::Dialect;
+
+/// SQL Server方言
+///
+/// 行数制限はOFFSETがなければ `TOP n`、あれば `OFFSET m ROWS FETCH NEXT n ROWS ONLY` を使う
+pub struct MssqlDialect;
+
+impl Dialect for MssqlDialect {
+    fn quote_identifier(&self, identifier: &str) -> String {
+        format!("[{}]", identifier.replace(']', "]]"))
+    }
+
+    fn escape_string(&self, value: &str) -> String {
+        format!("N'{}'", value.replace('\'', "''"))
+    }
+
+    fn limit_offset(&self, limit: u64, offset: Option<u64>) -> String {
+        format!(
+            "OFFSET {} ROWS FETCH NEXT {} ROWS ONLY",
+            offset.unwrap_or(0),
+            limit
+        )
+    }
+
+    fn top_clause(&self, limit: u64, offset: Option<u64>) -> Option<String> {
+        match offset {
+            Some(_) => None,
+            None => Some(format!("TOP {}", limit)),
+        }
+    }
+
+    fn offset_requires_order_by(&self) -> bool {
+        true
+    }
+
+    fn ilike_operator(&self) -> &str {
+        "LIKE" // 既定の照合順序では大文字小文字を区別しない
+    }
+
+    fn supports_nulls_order(&self) -> bool {
+        false
+    }
+
+    fn boolean_literal(&self, value: bool) -> String {
+        if value { "1" } else { "0" }.to_string()
+    }
+
+    fn placeholder(&self, index: usize) -> String {
+        format!("@P{}", index)
+    }
+
+    fn dialect_name(&self) -> &str {
+        "sqlserver"
+    }
+}
@@ -26,6 +26,56 @@ impl Dialect for PostgresDialect {
         true
     }
 
+    fn placeholder(&self, index: usize) -> String {
+        format!("${}", index)
+    }
+
+    /// 文字列でバインドした値は text 型になり、日付・UUIDなどの列と比較できないため型変換する
+    ///
+    /// 文字列型と、型名をそのまま CAST に使えない型（配列・ユーザー定義型など）は変換しない
+    fn bind_cast_type(&self, data_type: &str) -> Option<String> {
+        let base = data_type
+            .split('(')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_lowercase();
+        match base.as_str() {
+            "smallint"
+            | "integer"
+            | "bigint"
+            | "int2"
+            | "int4"
+            | "int8"
+            | "real"
+            | "double precision"
+            | "float4"
+            | "float8"
+            | "numeric"
+            | "decimal"
+            | "money"
+            | "boolean"
+            | "bool"
+            | "date"
+            | "time"
+            | "time without time zone"
+            | "time with time zone"
+            | "timetz"
+            | "timestamp"
+            | "timestamp without time zone"
+            | "timestamp with time zone"
+            | "timestamptz"
+            | "interval"
+            | "uuid"
+            | "json"
+            | "jsonb"
+            | "inet"
+            | "cidr"
+            | "macaddr" => Some(base),
+            _ => None,
+        }
+    }
+
     fn dialect_name(&self) -> &str {
         "postgresql"
     }
@@ -8,5 +8,5 @@ pub mod reserved_words;
 #[cfg(test)]
 mod tests;
 
-pub use builder::SqlBuilder;
+pub use builder::{BoundSql, ParamCollector, SqlBuilder};
 pub use dialect::Dialect;
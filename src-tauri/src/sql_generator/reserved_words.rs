@@ -4,7 +4,7 @@
 /// 1. アルファベット、数字、アンダースコアのみで構成されている
 /// 2. 数字で始まっていない
 /// 3. 予約語に含まれていない
-pub fn is_safe_identifier(identifier: &str, _dialect: &str) -> bool {
+pub fn is_safe_identifier(identifier: &str, dialect: &str) -> bool {
     // 空文字は安全でない
     if identifier.is_empty() {
         return false;
@@ -21,9 +21,89 @@ pub fn is_safe_identifier(identifier: &str, _dialect: &str) -> bool {
     }
 
     // 予約語チェック（大文字小文字を無視）
-    !simple_impl::is_reserved(identifier)
+    !is_reserved_in(identifier, dialect)
 }
 
+/// 方言の予約語かどうかを判定（共通の予約語に方言固有のものを加える）
+pub fn is_reserved_in(word: &str, dialect: &str) -> bool {
+    if simple_impl::is_reserved(word) {
+        return true;
+    }
+
+    let upper = word.to_uppercase();
+    dialect_reserved_words(dialect)
+        .iter()
+        .any(|words| words.contains(&upper.as_str()))
+}
+
+/// 方言固有の予約語（MariaDBはMySQLの予約語にMariaDB固有のものを加える）
+fn dialect_reserved_words(dialect: &str) -> &'static [&'static [&'static str]] {
+    match dialect {
+        "mysql" => &[MYSQL_RESERVED],
+        "mariadb" => &[MYSQL_RESERVED, MARIADB_RESERVED],
+        "sqlserver" => &[SQLSERVER_RESERVED],
+        _ => &[],
+    }
+}
+
+/// MySQL・MariaDB共通の予約語（共通の予約語に含まれないもの）
+const MYSQL_RESERVED: &[&str] = &[
+    "ACCESSIBLE",
+    "ANALYZE",
+    "CHANGE",
+    "CUME_DIST",
+    "DATABASES",
+    "DELAYED",
+    "DENSE_RANK",
+    "DIV",
+    "DUAL",
+    "ELSEIF",
+    "EXPLAIN",
+    "FIRST_VALUE",
+    "FORCE",
+    "FULLTEXT",
+    "GROUPS",
+    "IGNORE",
+    "INTERVAL",
+    "KEYS",
+    "LAG",
+    "LAST_VALUE",
+    "LATERAL",
+    "LEAD",
+    "LOCK",
+    "MOD",
+    "NATURAL",
+    "NTH_VALUE",
+    "NTILE",
+    "OPTIMIZE",
+    "OVER",
+    "PARTITION",
+    "PERCENT_RANK",
+    "RANGE",
+    "RANK",
+    "RECURSIVE",
+    "REGEXP",
+    "RENAME",
+    "REPLACE",
+    "RLIKE",
+    "ROW_NUMBER",
+    "ROWS",
+    "SCHEMAS",
+    "SHOW",
+    "SPATIAL",
+    "UNLOCK",
+    "UNSIGNED",
+    "USING",
+    "WINDOW",
+    "XOR",
+];
+
+/// MariaDB固有の予約語（MySQLの予約語に含まれないもの）
+const MARIADB_RESERVED: &[&str] = &["RETURNING"];
+
+/// SQL Server固有の予約語（共通の予約語に含まれないもの）
+const SQLSERVER_RESERVED: &[&str] = &["ADD", "ALTER"];
+
 pub mod simple_impl {
     pub fn is_reserved(word: &str) -> bool {
         let upper = word.to_uppercase();
@@ -411,8 +411,9 @@ mod join_tests {
 #[cfg(test)]
 mod where_tests {
     use crate::models::query::*;
+    use crate::models::query_result::QueryParam;
     use crate::sql_generator::builder::SqlBuilder;
-    use crate::sql_generator::dialects::PostgresDialect;
+    use crate::sql_generator::dialects::{MysqlDialect, PostgresDialect};
 
     fn create_base_query() -> QueryModel {
         QueryModel {
@@ -458,6 +459,7 @@ mod where_tests {
                 column: WhereConditionColumn {
                     table_alias: "u".to_string(),
                     column_name: "name".to_string(),
+                    data_type: None,
                 },
                 operator: "=".to_string(),
                 value: WhereValue::Literal {
@@ -484,6 +486,7 @@ mod where_tests {
                 column: WhereConditionColumn {
                     table_alias: "u".to_string(),
                     column_name: "age".to_string(),
+                    data_type: None,
                 },
                 operator: ">=".to_string(),
                 value: WhereValue::Literal {
@@ -509,6 +512,7 @@ mod where_tests {
                 column: WhereConditionColumn {
                     table_alias: "u".to_string(),
                     column_name: "deleted_at".to_string(),
+                    data_type: None,
                 },
                 operator: "IS".to_string(),
                 value: WhereValue::Literal {
@@ -534,6 +538,7 @@ mod where_tests {
                 column: WhereConditionColumn {
                     table_alias: "u".to_string(),
                     column_name: "is_active".to_string(),
+                    data_type: None,
                 },
                 operator: "=".to_string(),
                 value: WhereValue::Literal {
@@ -559,6 +564,7 @@ mod where_tests {
                 column: WhereConditionColumn {
                     table_alias: "u".to_string(),
                     column_name: "status".to_string(),
+                    data_type: None,
                 },
                 operator: "IN".to_string(),
                 value: WhereValue::List {
@@ -587,6 +593,7 @@ mod where_tests {
                 column: WhereConditionColumn {
                     table_alias: "u".to_string(),
                     column_name: "age".to_string(),
+                    data_type: None,
                 },
                 operator: "BETWEEN".to_string(),
                 value: WhereValue::Range {
@@ -613,6 +620,7 @@ mod where_tests {
                 column: WhereConditionColumn {
                     table_alias: "u".to_string(),
                     column_name: "created_at".to_string(),
+                    data_type: None,
                 },
                 operator: "<".to_string(),
                 value: WhereValue::Column {
@@ -640,6 +648,7 @@ mod where_tests {
                     column: WhereConditionColumn {
                         table_alias: "u".to_string(),
                         column_name: "age".to_string(),
+                        data_type: None,
                     },
                     operator: ">=".to_string(),
                     value: WhereValue::Literal {
@@ -651,6 +660,7 @@ mod where_tests {
                     column: WhereConditionColumn {
                         table_alias: "u".to_string(),
                         column_name: "is_active".to_string(),
+                        data_type: None,
                     },
                     operator: "=".to_string(),
                     value: WhereValue::Literal {
@@ -678,6 +688,7 @@ mod where_tests {
                     column: WhereConditionColumn {
                         table_alias: "u".to_string(),
                         column_name: "role".to_string(),
+                        data_type: None,
                     },
                     operator: "=".to_string(),
                     value: WhereValue::Literal {
@@ -689,6 +700,7 @@ mod where_tests {
                     column: WhereConditionColumn {
                         table_alias: "u".to_string(),
                         column_name: "role".to_string(),
+                        data_type: None,
                     },
                     operator: "=".to_string(),
                     value: WhereValue::Literal {
@@ -716,6 +728,7 @@ mod where_tests {
                     column: WhereConditionColumn {
                         table_alias: "u".to_string(),
                         column_name: "is_active".to_string(),
+                        data_type: None,
                     },
                     operator: "=".to_string(),
                     value: WhereValue::Literal {
@@ -731,6 +744,7 @@ mod where_tests {
                             column: WhereConditionColumn {
                                 table_alias: "u".to_string(),
                                 column_name: "role".to_string(),
+                                data_type: None,
                             },
                             operator: "=".to_string(),
                             value: WhereValue::Literal {
@@ -742,6 +756,7 @@ mod where_tests {
                             column: WhereConditionColumn {
                                 table_alias: "u".to_string(),
                                 column_name: "role".to_string(),
+                                data_type: None,
                             },
                             operator: "=".to_string(),
                             value: WhereValue::Literal {
@@ -757,6 +772,162 @@ mod where_tests {
 
         assert!(sql.contains("u.is_active = TRUE AND (u.role = 'admin' OR u.role = 'moderator')"));
     }
+
+    /// バインドモード用の条件（IN リスト + 文字列 + NULL）
+    fn create_bound_where() -> WhereClause {
+        WhereClause {
+            logic: "AND".to_string(),
+            conditions: vec![
+                WhereConditionItem::Condition(WhereCondition {
+                    id: "cond1".to_string(),
+                    column: WhereConditionColumn {
+                        table_alias: "u".to_string(),
+                        column_name: "name".to_string(),
+                        data_type: None,
+                    },
+                    operator: "=".to_string(),
+                    value: WhereValue::Literal {
+                        value: LiteralValue::String("O'Brien".to_string()),
+                    },
+                }),
+                WhereConditionItem::Condition(WhereCondition {
+                    id: "cond2".to_string(),
+                    column: WhereConditionColumn {
+                        table_alias: "u".to_string(),
+                        column_name: "age".to_string(),
+                        data_type: None,
+                    },
+                    operator: "IN".to_string(),
+                    value: WhereValue::List {
+                        values: vec![LiteralValue::Number(20.0), LiteralValue::Number(1.5)],
+                    },
+                }),
+                WhereConditionItem::Condition(WhereCondition {
+                    id: "cond3".to_string(),
+                    column: WhereConditionColumn {
+                        table_alias: "u".to_string(),
+                        column_name: "deleted_at".to_string(),
+                        data_type: None,
+                    },
+                    operator: "IS".to_string(),
+                    value: WhereValue::Literal {
+                        value: LiteralValue::Null,
+                    },
+                }),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_where_bound_params_postgres() {
+        let dialect = PostgresDialect;
+        let builder = SqlBuilder::new(&dialect).compact();
+        let mut query = create_base_query();
+        query.where_clause = Some(create_bound_where());
+
+        let bound = builder.build_with_params(&query).unwrap();
+
+        assert!(bound
+            .sql
+            .contains("u.name = $1 AND u.age IN ($2, $3) AND u.deleted_at IS NULL"));
+        assert!(!bound.sql.contains("O'Brien"));
+        assert_eq!(
+            bound.params,
+            vec![
+                QueryParam::String("O'Brien".to_string()),
+                QueryParam::Int(20),
+                QueryParam::Float(1.5),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_where_bound_params_postgres_timestamp() {
+        let dialect = PostgresDialect;
+        let builder = SqlBuilder::new(&dialect).compact();
+        let mut query = create_base_query();
+        let condition = |operator: &str, value: WhereValue| {
+            WhereConditionItem::Condition(WhereCondition {
+                id: operator.to_string(),
+                column: WhereConditionColumn {
+                    table_alias: "u".to_string(),
+                    column_name: "created_at".to_string(),
+                    data_type: Some("timestamp without time zone".to_string()),
+                },
+                operator: operator.to_string(),
+                value,
+            })
+        };
+        query.where_clause = Some(WhereClause {
+            logic: "AND".to_string(),
+            conditions: vec![
+                condition(
+                    ">",
+                    WhereValue::Literal {
+                        value: LiteralValue::String("2024-01-01 00:00:00".to_string()),
+                    },
+                ),
+                condition(
+                    "BETWEEN",
+                    WhereValue::Range {
+                        from: LiteralValue::String("2024-01-01".to_string()),
+                        to: LiteralValue::String("2024-12-31".to_string()),
+                    },
+                ),
+                // LIKE は文字列のまま比較する
+                condition(
+                    "LIKE",
+                    WhereValue::Literal {
+                        value: LiteralValue::String("2024%".to_string()),
+                    },
+                ),
+            ],
+        });
+
+        let bound = builder.build_with_params(&query).unwrap();
+
+        assert!(bound.sql.contains(
+            "u.created_at > CAST($1 AS timestamp without time zone) \
+             AND u.created_at BETWEEN CAST($2 AS timestamp without time zone) \
+             AND CAST($3 AS timestamp without time zone) AND u.created_at LIKE $4"
+        ));
+        assert_eq!(bound.params.len(), 4);
+
+        // 文字列型の列と、変換の必要がない方言ではそのままバインドする
+        if let Some(WhereClause { conditions, .. }) = query.where_clause.as_mut() {
+            if let WhereConditionItem::Condition(cond) = &mut conditions[0] {
+                cond.column.data_type = Some("character varying".to_string());
+            }
+        }
+        let bound = builder.build_with_params(&query).unwrap();
+        assert!(bound.sql.contains("u.created_at > $1 AND"));
+        let bound = SqlBuilder::new(&MysqlDialect)
+            .compact()
+            .build_with_params(&query)
+            .unwrap();
+        assert!(bound
+            .sql
+            .contains("u.created_at > ? AND u.created_at BETWEEN ? AND ? AND u.created_at LIKE ?"));
+    }
+
+    #[test]
+    fn test_where_bound_params_mysql() {
+        let dialect = MysqlDialect;
+        let builder = SqlBuilder::new(&dialect).compact();
+        let mut query = create_base_query();
+        query.where_clause = Some(create_bound_where());
+
+        let bound = builder.build_with_params(&query).unwrap();
+
+        assert!(bound
+            .sql
+            .contains("u.name = ? AND u.age IN (?, ?) AND u.deleted_at IS NULL"));
+        assert_eq!(bound.params.len(), 3);
+
+        // 通常のbuildはリテラルを埋め込んだまま
+        let sql = builder.build(&query).unwrap();
+        assert!(sql.contains("u.name = 'O\\'Brien'"));
+    }
 }
 
 #[cfg(test)]
@@ -1086,7 +1257,9 @@ mod group_by_having_tests {
 mod limit_tests {
     use crate::models::query::*;
     use crate::sql_generator::builder::SqlBuilder;
-    use crate::sql_generator::dialects::{MysqlDialect, PostgresDialect, SqliteDialect};
+    use crate::sql_generator::dialects::{
+        MssqlDialect, MysqlDialect, PostgresDialect, SqliteDialect,
+    };
 
     fn create_base_query() -> QueryModel {
         QueryModel {
@@ -1197,12 +1370,57 @@ mod limit_tests {
         // SQLite uses same syntax as PostgreSQL
         assert!(sql.contains("LIMIT 10 OFFSET 20"));
     }
+
+    #[test]
+    fn test_limit_only_sqlserver_uses_top() {
+        let dialect = MssqlDialect;
+        let builder = SqlBuilder::new(&dialect).compact();
+        let mut query = create_base_query();
+        query.select.distinct = true;
+        query.limit = Some(LimitClause {
+            limit: 10,
+            offset: None,
+        });
+
+        let sql = builder.build(&query).unwrap();
+
+        assert_eq!(sql, "SELECT DISTINCT TOP 10 u.id FROM [public].users u");
+    }
+
+    #[test]
+    fn test_limit_with_offset_sqlserver() {
+        let dialect = MssqlDialect;
+        let builder = SqlBuilder::new(&dialect).compact();
+        let mut query = create_base_query();
+        query.limit = Some(LimitClause {
+            limit: 10,
+            offset: Some(20),
+        });
+
+        // OFFSETにはORDER BYが必須のため、並び順がなければ補う
+        let sql = builder.build(&query).unwrap();
+        assert!(sql.ends_with("ORDER BY (SELECT NULL) OFFSET 20 ROWS FETCH NEXT 10 ROWS ONLY"));
+        assert!(!sql.contains("TOP"));
+
+        query.order_by = Some(OrderByClause {
+            items: vec![OrderByItem {
+                table_alias: "u".to_string(),
+                column_name: "id".to_string(),
+                direction: "DESC".to_string(),
+                nulls: Some("FIRST".to_string()),
+            }],
+        });
+        let sql = builder.build(&query).unwrap();
+        assert!(sql.ends_with("ORDER BY u.id DESC OFFSET 20 ROWS FETCH NEXT 10 ROWS ONLY"));
+    }
 }
 
 #[cfg(test)]
 mod dialect_tests {
     use crate::sql_generator::dialect::Dialect;
-    use crate::sql_generator::dialects::{MysqlDialect, PostgresDialect, SqliteDialect};
+    use crate::sql_generator::dialects::{
+        DuckDbDialect, MssqlDialect, MysqlDialect, PostgresDialect, SqliteDialect,
+    };
 
     #[test]
     fn test_postgres_quote_identifier() {
@@ -1293,6 +1511,36 @@ mod dialect_tests {
         let dialect = SqliteDialect;
         assert!(!dialect.supports_nulls_order());
     }
+
+    #[test]
+    fn test_duckdb_dialect() {
+        let dialect = DuckDbDialect;
+        assert_eq!(dialect.quote_identifier("user\"name"), "\"user\"\"name\"");
+        assert_eq!(dialect.escape_string("it's"), "'it''s'");
+        assert_eq!(dialect.limit_offset(10, Some(20)), "LIMIT 10 OFFSET 20");
+        assert_eq!(dialect.ilike_operator(), "ILIKE");
+        assert!(dialect.supports_nulls_order());
+        assert_eq!(dialect.placeholder(1), "?");
+        assert_eq!(dialect.dialect_name(), "duckdb");
+    }
+
+    #[test]
+    fn test_sqlserver_dialect() {
+        let dialect = MssqlDialect;
+        assert_eq!(dialect.quote_identifier("user]name"), "[user]]name]");
+        assert_eq!(dialect.escape_string("it's"), "N'it''s'");
+        assert_eq!(
+            dialect.limit_offset(10, Some(20)),
+            "OFFSET 20 ROWS FETCH NEXT 10 ROWS ONLY"
+        );
+        assert_eq!(dialect.top_clause(10, None).as_deref(), Some("TOP 10"));
+        assert_eq!(dialect.top_clause(10, Some(20)), None);
+        assert_eq!(dialect.ilike_operator(), "LIKE");
+        assert!(!dialect.supports_nulls_order());
+        assert_eq!(dialect.boolean_literal(true), "1");
+        assert_eq!(dialect.placeholder(2), "@P2");
+        assert_eq!(dialect.dialect_name(), "sqlserver");
+    }
 }
 
 #[cfg(test)]
@@ -1373,6 +1621,7 @@ mod complete_query_tests {
                     column: WhereConditionColumn {
                         table_alias: "u".to_string(),
                         column_name: "is_active".to_string(),
+                        data_type: None,
                     },
                     operator: "=".to_string(),
                     value: WhereValue::Literal {
@@ -1479,3 +1728,45 @@ mod complete_query_tests {
         );
     }
 }
+
+#[cfg(test)]
+mod reserved_words_tests {
+    use crate::sql_generator::dialect::Dialect;
+    use crate::sql_generator::dialects::{MariadbDialect, MysqlDialect};
+    use crate::sql_generator::reserved_words::{is_reserved_in, is_safe_identifier};
+
+    #[test]
+    fn test_mariadb_reserved_words() {
+        // 共通の予約語はすべての方言で予約語
+        assert!(is_reserved_in("select", "mariadb"));
+        assert!(is_reserved_in("select", "mysql"));
+
+        // MySQL・MariaDB共通の予約語
+        assert!(!is_safe_identifier("rows", "mariadb"));
+        assert!(!is_safe_identifier("rank", "mysql"));
+        assert!(!is_safe_identifier("window", "mysql"));
+        assert!(is_safe_identifier("rows", "postgresql"));
+        assert!(is_safe_identifier("customer_id", "mariadb"));
+
+        // MariaDB固有の予約語
+        assert!(is_reserved_in("returning", "mariadb"));
+        assert!(!is_reserved_in("returning", "mysql"));
+    }
+
+    #[test]
+    fn test_mariadb_dialect_matches_mysql_syntax() {
+        let mariadb = MariadbDialect;
+        let mysql = MysqlDialect;
+
+        assert_eq!(mariadb.dialect_name(), "mariadb");
+        assert_eq!(
+            mariadb.quote_identifier("a`b"),
+            mysql.quote_identifier("a`b")
+        );
+        assert_eq!(
+            mariadb.limit_offset(10, Some(20)),
+            mysql.limit_offset(10, Some(20))
+        );
+        assert!(!mariadb.supports_nulls_order());
+    }
+}
@@ -31,6 +31,7 @@ async fn test_full_connection_save_load_cycle() {
             username: "testuser".to_string(),
             encrypted_password: None,
             ssl_config: None,
+            ssh_tunnel: None,
             options: None,
         }),
     );
@@ -105,6 +106,7 @@ async fn test_connection_collection_persistence() {
                     .unwrap(),
             ),
             ssl_config: None,
+            ssh_tunnel: None,
             options: None,
         }),
     );
@@ -126,6 +128,7 @@ async fn test_connection_collection_persistence() {
                     .unwrap(),
             ),
             ssl_config: None,
+            ssh_tunnel: None,
             options: None,
         }),
     );
@@ -239,6 +242,7 @@ async fn test_concurrent_storage_and_crypto_operations() {
                             .unwrap(),
                     ),
                     ssl_config: None,
+                    ssh_tunnel: None,
                     options: None,
                 }),
             );
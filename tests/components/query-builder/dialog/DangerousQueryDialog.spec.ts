@@ -36,6 +36,7 @@ describe('DangerousQueryDialog', () => {
     ],
     affectedTables: ['users'],
     hasWhereClause: false,
+    statements: [],
   }
 
   const warningResult: QueryAnalysisResult = {
@@ -46,6 +47,18 @@ describe('DangerousQueryDialog', () => {
     ],
     affectedTables: ['users'],
     hasWhereClause: true,
+    statements: [],
+  }
+
+  const unparseableResult: QueryAnalysisResult = {
+    queryType: 'unknown',
+    riskLevel: 'unparseable',
+    riskFactors: [
+      { code: 'unparseable', message: 'SQLを解析できませんでした。内容を確認してから実行してください' }
+    ],
+    affectedTables: [],
+    hasWhereClause: false,
+    statements: [],
   }
 
   it('Dangerレベルで3秒カウントダウンが表示される', async () => {
@@ -94,6 +107,49 @@ describe('DangerousQueryDialog', () => {
     expect(wrapper.text()).toContain('実行する')
   })
 
+  it('解析不可のクエリもカウントダウン対象になる', () => {
+    const wrapper = mount(DangerousQueryDialog, {
+      props: {
+        open: true,
+        analysisResult: unparseableResult,
+        sql: 'FROBNICATE users',
+        countdownSeconds: 3,
+      },
+      global: { stubs }
+    })
+
+    expect(wrapper.text()).toContain('解析不可')
+    expect(wrapper.text()).toContain('3秒待機')
+  })
+
+  it('対象行数とサンプルが表示される', () => {
+    const wrapper = mount(DangerousQueryDialog, {
+      props: {
+        open: true,
+        analysisResult: warningResult,
+        sql: 'UPDATE users SET name = "test" WHERE id > 1',
+        countdownSeconds: 3,
+        preview: {
+          affectedRows: 12408,
+          sample: {
+            columns: [{ name: 'id', dataType: 'INTEGER', nullable: false }],
+            rows: [{ values: [2] }, { values: [3] }],
+            rowCount: 2,
+            executionTimeMs: 1,
+            warnings: [],
+          },
+          countSql: 'SELECT COUNT(*) AS affected_rows FROM users WHERE id > 1',
+          sampleSql: 'SELECT * FROM users WHERE id > 1 LIMIT 10',
+        },
+      },
+      global: { stubs }
+    })
+
+    expect(wrapper.text()).toContain((12408).toLocaleString())
+    expect(wrapper.text()).toContain('行が変更されます')
+    expect(wrapper.text()).toContain('先頭 2 行を表示しています')
+  })
+
   it('危険度バッジが正しく表示される', () => {
     const wrapper = mount(DangerousQueryDialog, {
       props: {
@@ -29,6 +29,35 @@ describe('useSqlFormatter', () => {
     expect(result.endsWith(';\n')).toBe(true)
   })
 
+  it('UPSERTの重複時の動作の前で改行する', () => {
+    const { formatInsertSql } = useSqlFormatter()
+    const sql = "INSERT INTO users (id, name) VALUES (1, 'a') ON CONFLICT (id) DO UPDATE SET name = EXCLUDED.name;"
+
+    const result = formatInsertSql(sql)
+
+    expect(result).toBe(
+      "INSERT INTO users (id, name)\nVALUES\n  (1, 'a')\nON CONFLICT (id) DO UPDATE SET name = EXCLUDED.name;\n"
+    )
+  })
+
+  it('INSERT ... SELECTのSELECTの前で改行する', () => {
+    const { formatInsertSql } = useSqlFormatter()
+    const sql = 'INSERT INTO users (id, name) SELECT a.id, a.name FROM archive.archived_users a;'
+
+    const result = formatInsertSql(sql)
+
+    expect(result).toBe(
+      'INSERT INTO users (id, name)\nSELECT a.id, a.name FROM archive.archived_users a;\n'
+    )
+  })
+
+  it('MERGEは整形しない', () => {
+    const { formatInsertSql } = useSqlFormatter()
+    const sql = 'MERGE INTO [users] AS [target] USING (VALUES (1)) AS [source] ([id]) ON [target].[id] = [source].[id] WHEN NOT MATCHED THEN INSERT ([id]) VALUES ([source].[id]);'
+
+    expect(formatInsertSql(sql)).toBe(sql)
+  })
+
   it('空文字列を正しく処理する', () => {
     const { formatInsertSql } = useSqlFormatter()
 
@@ -85,6 +114,17 @@ describe('useSqlFormatter', () => {
     expect(result).toContain('\nWHERE\n  ')
   })
 
+  it('MySQLの結合付きUPDATEはJOINの前で改行する', () => {
+    const { formatUpdateSql } = useSqlFormatter()
+    const sql = 'UPDATE users INNER JOIN orders o ON users.id = o.user_id SET users.note = o.memo'
+
+    const result = formatUpdateSql(sql)
+
+    expect(result).toBe(
+      'UPDATE users\nINNER JOIN orders o ON users.id = o.user_id\nSET\n  users.note = o.memo'
+    )
+  })
+
   it('UPDATEの空文字列を正しく処理する', () => {
     const { formatUpdateSql } = useSqlFormatter()
 
@@ -101,6 +141,15 @@ describe('useSqlFormatter', () => {
     expect(result).toBe('DELETE FROM users\nWHERE\n  id = 1')
   })
 
+  it('結合付きDELETEはUSINGの前で改行する', () => {
+    const { formatDeleteSql } = useSqlFormatter()
+    const sql = 'DELETE FROM users USING orders o WHERE users.id = o.user_id'
+
+    const result = formatDeleteSql(sql)
+
+    expect(result).toBe('DELETE FROM users\nUSING orders o\nWHERE\n  users.id = o.user_id')
+  })
+
   it('DELETEの空文字列を正しく処理する', () => {
     const { formatDeleteSql } = useSqlFormatter()
 
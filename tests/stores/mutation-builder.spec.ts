@@ -1,6 +1,46 @@
-import { describe, it, expect, beforeEach } from 'vitest'
+import { describe, it, expect, beforeEach, vi } from 'vitest'
 import { setActivePinia, createPinia } from 'pinia'
 import { useMutationBuilderStore } from '@/stores/mutation-builder'
+import { mutationApi } from '~/api/mutation'
+
+vi.mock('~/api/mutation', () => ({
+  mutationApi: {
+    generateInsertSql: vi.fn().mockResolvedValue(''),
+    generateUpdateSql: vi.fn().mockResolvedValue({ sql: '', hasWhereClause: false }),
+    generateDeleteSql: vi.fn().mockResolvedValue('DELETE FROM users;'),
+    executeMutation: vi.fn(),
+    previewMutation: vi.fn(),
+  },
+}))
+
+vi.mock('~/api/query', () => ({
+  queryApi: {
+    analyzeQuery: vi.fn().mockResolvedValue(null),
+  },
+}))
+
+vi.mock('~/stores/connection', () => ({
+  useConnectionStore: () => ({
+    activeConnection: {
+      id: 'test-connection-id',
+      type: 'postgresql',
+    },
+    connections: [],
+  }),
+}))
+
+vi.mock('~/stores/window', () => ({
+  useWindowStore: () => ({
+    currentConnectionId: 'test-connection-id',
+  }),
+}))
+
+const addHistory = vi.fn()
+vi.mock('~/stores/query-history', () => ({
+  useQueryHistoryStore: () => ({
+    addHistory,
+  }),
+}))
 
 describe('useMutationBuilderStore', () => {
   beforeEach(() => {
@@ -62,6 +102,153 @@ describe('useMutationBuilderStore', () => {
     expect(store.hasWhereConditions).toBe(false)
   })
 
+  it('should load preview for DELETE with the same WHERE model', async () => {
+    const preview = {
+      affectedRows: 12408,
+      sample: { columns: [], rows: [], rowCount: 0, executionTimeMs: 1, warnings: [] },
+      countSql: 'SELECT COUNT(*) AS affected_rows FROM users',
+      sampleSql: 'SELECT * FROM users LIMIT 10',
+    }
+    vi.mocked(mutationApi.previewMutation).mockResolvedValueOnce(preview)
+
+    const store = useMutationBuilderStore()
+    store.setMutationType('DELETE')
+    store.setSelectedTable('users')
+    await store.loadPreview()
+
+    expect(mutationApi.previewMutation).toHaveBeenCalledWith(
+      expect.objectContaining({
+        connectionId: 'test-connection-id',
+        target: {
+          kind: 'delete',
+          query: { type: 'DELETE', table: 'users', whereClause: null },
+        },
+      })
+    )
+    expect(store.preview).toEqual(preview)
+    expect(store.isLoadingPreview).toBe(false)
+  })
+
+  it('should leave preview empty when it cannot be loaded', async () => {
+    vi.mocked(mutationApi.previewMutation).mockRejectedValueOnce('Preview failed')
+
+    const store = useMutationBuilderStore()
+    store.setMutationType('UPDATE')
+    store.setSelectedTable('users')
+    await store.loadPreview()
+
+    expect(store.preview).toBeNull()
+  })
+
+  it('should pass on conflict settings to INSERT SQL generation', async () => {
+    const store = useMutationBuilderStore()
+    store.setSelectedTable('users')
+    store.updateInsertQueryModel({ columns: ['id', 'name'], values: [{ id: 1, name: 'a' }] })
+
+    const onConflict = {
+      target: ['id'],
+      action: { type: 'doUpdate' as const, columns: ['name'] },
+    }
+    store.setOnConflict(onConflict)
+
+    expect(mutationApi.generateInsertSql).toHaveBeenLastCalledWith(
+      expect.objectContaining({ table: 'users', onConflict }),
+      'test-connection-id',
+      true
+    )
+
+    store.setOnConflict(null)
+    expect(mutationApi.generateInsertSql).toHaveBeenLastCalledWith(
+      expect.objectContaining({ onConflict: null }),
+      'test-connection-id',
+      true
+    )
+  })
+
+  it('should send INSERT ... SELECT without form values', async () => {
+    const store = useMutationBuilderStore()
+    store.setSelectedTable('users')
+    store.updateInsertQueryModel({ columns: ['id', 'name'], values: [{ id: 1, name: 'a' }] })
+
+    const source = { connectionId: 'test-connection-id', joins: [] } as any
+    store.setInsertSource(source)
+
+    expect(mutationApi.generateInsertSql).toHaveBeenLastCalledWith(
+      expect.objectContaining({ source, values: [] }),
+      'test-connection-id',
+      true
+    )
+  })
+
+  it('should pass joins to DELETE SQL generation', async () => {
+    const store = useMutationBuilderStore()
+    store.setMutationType('DELETE')
+    store.setSelectedTable('users')
+
+    const joins = [
+      {
+        id: 'join-1',
+        type: 'INNER' as const,
+        table: { schema: 'public', name: 'orders', alias: 'o' },
+        conditions: [
+          {
+            left: { tableAlias: 'users', columnName: 'id' },
+            operator: '=' as const,
+            right: { tableAlias: 'o', columnName: 'user_id' },
+          },
+        ],
+        conditionLogic: 'AND' as const,
+      },
+    ]
+    store.setJoins(joins)
+
+    expect(mutationApi.generateDeleteSql).toHaveBeenLastCalledWith(
+      expect.objectContaining({ table: 'users', joins }),
+      'test-connection-id',
+      true
+    )
+  })
+
+  it('should capture undo SQL for DELETE and save it with the history', async () => {
+    const undoSql = "INSERT INTO \"users\" (\"id\") VALUES (1);"
+    vi.mocked(mutationApi.executeMutation).mockResolvedValueOnce({
+      affectedRows: 1,
+      executionTimeMs: 5,
+      undo: { status: 'available', sql: undoSql, rowCount: 1 },
+    })
+
+    const store = useMutationBuilderStore()
+    store.setMutationType('DELETE')
+    store.setSelectedTable('users')
+    store.generatedSql = 'DELETE FROM users WHERE id = 1;'
+    await store.executeMutation()
+
+    expect(mutationApi.executeMutation).toHaveBeenCalledWith(
+      expect.objectContaining({ captureUndo: true })
+    )
+    expect(store.lastUndo).toEqual({ status: 'available', sql: undoSql, rowCount: 1 })
+    expect(addHistory).toHaveBeenCalledWith(
+      expect.objectContaining({ success: true, undoSql })
+    )
+  })
+
+  it('should not capture undo SQL for INSERT', async () => {
+    vi.mocked(mutationApi.executeMutation).mockResolvedValueOnce({
+      affectedRows: 1,
+      executionTimeMs: 5,
+    })
+
+    const store = useMutationBuilderStore()
+    store.setSelectedTable('users')
+    store.generatedSql = "INSERT INTO users (name) VALUES ('a');"
+    await store.executeMutation()
+
+    expect(mutationApi.executeMutation).toHaveBeenLastCalledWith(
+      expect.objectContaining({ captureUndo: false })
+    )
+    expect(store.lastUndo).toBeNull()
+  })
+
   it('should reset state', () => {
     const store = useMutationBuilderStore()
     store.setSelectedTable('users')
@@ -15,7 +15,7 @@ vi.mock('~/composables/useTauri', () => ({
 vi.mock('~/api/query', () => ({
   queryApi: {
     generateSqlFormatted: vi.fn().mockResolvedValue('SELECT * FROM users'),
-    analyzeQuery: vi.fn().mockResolvedValue({ queryType: 'select', riskLevel: 'safe', riskFactors: [], affectedTables: [], hasWhereClause: true })
+    analyzeQuery: vi.fn().mockResolvedValue({ queryType: 'select', riskLevel: 'safe', riskFactors: [], affectedTables: [], hasWhereClause: true, statements: [] })
   }
 }))
 
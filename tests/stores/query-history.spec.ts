@@ -2,7 +2,10 @@ import { describe, it, expect, beforeEach, vi } from 'vitest'
 import { setActivePinia, createPinia } from 'pinia'
 import { useQueryHistoryStore } from '~/stores/query-history'
 import { queryHistoryApi } from '@/api/query-history'
-import type { QueryHistoryMetadata } from '@/types/query-history'
+import { queryApi } from '@/api/query'
+import { safetyApi } from '@/api/safetyApi'
+import { transactionApi } from '@/api/transaction'
+import type { QueryHistory, QueryHistoryMetadata } from '@/types/query-history'
 
 vi.mock('@/api/query-history', () => ({
   queryHistoryApi: {
@@ -14,6 +17,26 @@ vi.mock('@/api/query-history', () => ({
   },
 }))
 
+vi.mock('@/api/query', () => ({
+  queryApi: {
+    executeScript: vi.fn(),
+  },
+}))
+
+vi.mock('@/api/safetyApi', () => ({
+  safetyApi: {
+    issueConfirmationToken: vi.fn(),
+  },
+}))
+
+vi.mock('@/api/transaction', () => ({
+  transactionApi: {
+    beginTransaction: vi.fn(),
+    commitTransaction: vi.fn(),
+    rollbackTransaction: vi.fn(),
+  },
+}))
+
 describe('useQueryHistoryStore', () => {
   beforeEach(() => {
     setActivePinia(createPinia())
@@ -111,6 +134,106 @@ describe('useQueryHistoryStore', () => {
     expect(queryHistoryApi.listHistories).toHaveBeenCalled()
   })
 
+  it('revertHistory executes undo SQL and records the result', async () => {
+    const store = useQueryHistoryStore()
+    const undoSql = 'UPDATE "users" SET "name" = \'a\' WHERE "id" = 1;\nUPDATE "users" SET "name" = \'b\' WHERE "id" = 2;'
+    const history: QueryHistory = {
+      id: '3',
+      connectionId: 'conn2',
+      query: {} as any,
+      sql: 'UPDATE users SET name = "test"',
+      executedAt: '2025-01-01T12:00:00Z',
+      success: true,
+      resultCount: 2,
+      undoSql,
+    }
+    vi.mocked(queryHistoryApi.loadHistory).mockResolvedValue(history)
+    vi.mocked(queryHistoryApi.addHistory).mockResolvedValue({} as any)
+    vi.mocked(queryHistoryApi.listHistories).mockResolvedValue(mockHistories)
+    vi.mocked(safetyApi.issueConfirmationToken).mockResolvedValue({ token: 'token-1' } as any)
+    vi.mocked(queryApi.executeScript).mockResolvedValue({
+      queryId: 'q1',
+      results: [
+        { index: 0, range: { start: 0, end: 10 }, outcome: { type: 'affected', affectedRows: 1 }, executionTimeMs: 1 },
+        { index: 1, range: { start: 11, end: 20 }, outcome: { type: 'affected', affectedRows: 1 }, executionTimeMs: 1 },
+      ],
+      statementCount: 2,
+      executionTimeMs: 3,
+    } as any)
+
+    const count = await store.revertHistory('3')
+
+    expect(count).toBe(2)
+    expect(safetyApi.issueConfirmationToken).toHaveBeenCalledWith('conn2', undoSql)
+    expect(queryApi.executeScript).toHaveBeenCalledWith({
+      connectionId: 'conn2',
+      sql: undoSql,
+      errorMode: 'stop_on_error',
+      confirmationToken: 'token-1',
+    })
+    expect(transactionApi.beginTransaction).toHaveBeenCalledWith('conn2')
+    expect(transactionApi.commitTransaction).toHaveBeenCalledWith('conn2')
+    expect(transactionApi.rollbackTransaction).not.toHaveBeenCalled()
+    expect(queryHistoryApi.addHistory).toHaveBeenCalledWith(
+      expect.objectContaining({ sql: undoSql, success: true, resultCount: 2 })
+    )
+  })
+
+  it('revertHistory rolls back when a statement fails', async () => {
+    const store = useQueryHistoryStore()
+    const undoSql = 'DELETE FROM "users" WHERE "id" = 1;\nDELETE FROM "users" WHERE "id" = 2;'
+    vi.mocked(queryHistoryApi.loadHistory).mockResolvedValue({
+      id: '3',
+      connectionId: 'conn2',
+      query: {} as any,
+      sql: 'INSERT INTO users (id) VALUES (1), (2)',
+      executedAt: '2025-01-01T12:00:00Z',
+      success: true,
+      undoSql,
+    })
+    vi.mocked(queryHistoryApi.addHistory).mockResolvedValue({} as any)
+    vi.mocked(queryHistoryApi.listHistories).mockResolvedValue(mockHistories)
+    vi.mocked(safetyApi.issueConfirmationToken).mockResolvedValue({ token: 'token-1' } as any)
+    vi.mocked(transactionApi.rollbackTransaction).mockResolvedValue()
+    vi.mocked(queryApi.executeScript).mockResolvedValue({
+      queryId: 'q1',
+      results: [
+        { index: 0, range: { start: 0, end: 10 }, outcome: { type: 'affected', affectedRows: 1 }, executionTimeMs: 1 },
+        { index: 1, range: { start: 11, end: 20 }, outcome: { type: 'error', error: { message: 'locked' } }, executionTimeMs: 1 },
+      ],
+      statementCount: 2,
+      executionTimeMs: 3,
+    } as any)
+
+    const count = await store.revertHistory('3')
+
+    expect(count).toBeNull()
+    expect(store.error).toBe('locked')
+    expect(transactionApi.rollbackTransaction).toHaveBeenCalledWith('conn2')
+    expect(transactionApi.commitTransaction).not.toHaveBeenCalled()
+    expect(queryHistoryApi.addHistory).toHaveBeenCalledWith(
+      expect.objectContaining({ success: false, resultCount: 0, errorMessage: 'locked' })
+    )
+  })
+
+  it('revertHistory fails when the history has no undo SQL', async () => {
+    const store = useQueryHistoryStore()
+    vi.mocked(queryHistoryApi.loadHistory).mockResolvedValue({
+      id: '1',
+      connectionId: 'conn1',
+      query: {} as any,
+      sql: 'SELECT * FROM users',
+      executedAt: '2025-01-01T10:00:00Z',
+      success: true,
+    })
+
+    const count = await store.revertHistory('1')
+
+    expect(count).toBeNull()
+    expect(store.error).toBe('No undo SQL for this history')
+    expect(queryApi.executeScript).not.toHaveBeenCalled()
+  })
+
   it('filteredHistories filters by keyword', async () => {
     const store = useQueryHistoryStore()
     store.histories = mockHistories
@@ -3,11 +3,26 @@ import { setActivePinia, createPinia } from 'pinia'
 import { useSqlEditorStore } from '~/stores/sql-editor'
 import { queryApi } from '~/api/query'
 import { sqlEditorApi } from '~/api/sql-editor'
+import { safetyApi } from '~/api/safetyApi'
+import { mutationApi } from '~/api/mutation'
 
 vi.mock('~/api/query', () => ({
   queryApi: {
     executeQuery: vi.fn(),
     cancelQuery: vi.fn(),
+    analyzeQuery: vi.fn(),
+  },
+}))
+
+vi.mock('~/api/safetyApi', () => ({
+  safetyApi: {
+    issueConfirmationToken: vi.fn(),
+  },
+}))
+
+vi.mock('~/api/mutation', () => ({
+  mutationApi: {
+    previewMutation: vi.fn(),
   },
 }))
 
@@ -167,6 +182,68 @@ describe('SqlEditorStore', () => {
     })
   })
 
+  it('確認が必要なクエリは確認後にトークン付きで再実行される', async () => {
+    const store = useSqlEditorStore()
+    store.setConnection('conn-1')
+    store.updateSql('DELETE FROM users WHERE id = 1')
+
+    vi.mocked(queryApi.executeQuery).mockRejectedValueOnce(
+      JSON.stringify({ code: 'confirmation_required', message: 'confirmation required' })
+    )
+    vi.mocked(queryApi.analyzeQuery).mockResolvedValueOnce({
+      queryType: 'delete',
+      riskLevel: 'warning',
+      riskFactors: [],
+      affectedTables: ['users'],
+      hasWhereClause: true,
+      statements: [],
+    })
+    vi.mocked(mutationApi.previewMutation).mockResolvedValueOnce({
+      affectedRows: 1,
+      sample: { columns: [], rows: [], rowCount: 0, executionTimeMs: 1, warnings: [] },
+      countSql: 'SELECT COUNT(*) AS affected_rows FROM users WHERE id = 1',
+      sampleSql: 'SELECT * FROM users WHERE id = 1 LIMIT 10',
+    })
+
+    await store.executeQuery()
+
+    expect(store.pendingConfirmation?.sql).toBe('DELETE FROM users WHERE id = 1')
+    expect(sqlEditorApi.addHistory).not.toHaveBeenCalled()
+    // 単一のDELETEは対象行数を取得してダイアログに渡す
+    await vi.waitFor(() => expect(store.pendingConfirmation?.preview?.affectedRows).toBe(1))
+    expect(mutationApi.previewMutation).toHaveBeenCalledWith({
+      connectionId: 'conn-1',
+      target: { kind: 'sql', sql: 'DELETE FROM users WHERE id = 1' },
+    })
+
+    vi.mocked(safetyApi.issueConfirmationToken).mockResolvedValueOnce({
+      token: 'signed-token',
+      expiresAt: 0,
+    })
+    vi.mocked(queryApi.executeQuery).mockResolvedValueOnce({
+      queryId: 'q-2',
+      result: {
+        columns: [],
+        rows: [],
+        rowCount: 1,
+        executionTimeMs: 5,
+        warnings: [],
+      },
+    })
+
+    await store.confirmPendingExecution()
+
+    expect(store.pendingConfirmation).toBeNull()
+    expect(safetyApi.issueConfirmationToken).toHaveBeenCalledWith(
+      'conn-1',
+      'DELETE FROM users WHERE id = 1'
+    )
+    expect(vi.mocked(queryApi.executeQuery).mock.calls[1]?.[0].confirmationToken).toBe(
+      'signed-token'
+    )
+    expect(store.error).toBeNull()
+  })
+
   it('cancelQueryでキャンセル状態になる', async () => {
     const store = useSqlEditorStore()
     store.isExecuting = true